@@ -18,6 +18,8 @@
 use std::sync::Arc;
 
 use anyhow::Result;
+#[cfg(feature = "frontends-http")]
+use oay::services::HttpService;
 use oay::services::S3Service;
 use oay::Config;
 use opendal::services::Memory;
@@ -42,12 +44,26 @@ async fn main() -> Result<()> {
                 enable: true,
                 addr: "127.0.0.1:3000".to_string(),
             },
+            http: oay::HttpConfig {
+                enable: true,
+                addr: "127.0.0.1:3001".to_string(),
+            },
+            fuse: Default::default(),
         },
     };
+    let cfg = Arc::new(cfg);
 
     let op = Operator::new(Memory::default())?.finish();
 
-    let s3 = S3Service::new(Arc::new(cfg), op);
+    let s3 = S3Service::new(cfg.clone(), op.clone());
+
+    #[cfg(feature = "frontends-http")]
+    if cfg.frontends.http.enable {
+        let http = HttpService::new(cfg.clone(), op.clone());
+
+        tokio::try_join!(s3.serve(), http.serve())?;
+        return Ok(());
+    }
 
     s3.serve().await?;
 
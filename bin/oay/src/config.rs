@@ -33,6 +33,10 @@ pub struct BackendConfig {
 #[derive(Serialize, Deserialize)]
 pub struct FrontendsConfig {
     pub s3: S3Config,
+    #[serde(default)]
+    pub http: HttpConfig,
+    #[serde(default)]
+    pub fuse: FuseConfig,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -40,3 +44,15 @@ pub struct S3Config {
     pub enable: bool,
     pub addr: String,
 }
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct HttpConfig {
+    pub enable: bool,
+    pub addr: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct FuseConfig {
+    pub enable: bool,
+    pub mount_path: String,
+}
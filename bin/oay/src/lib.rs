@@ -19,3 +19,6 @@ pub mod services;
 
 mod config;
 pub use config::*;
+
+mod serve;
+pub use serve::serve_object;
@@ -0,0 +1,388 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use axum::body::boxed;
+use axum::body::Empty;
+use axum::body::Full;
+use axum::body::StreamBody;
+use axum::http::header;
+use axum::http::HeaderMap;
+use axum::http::HeaderValue;
+use axum::http::Method;
+use axum::http::StatusCode;
+use axum::response::Response;
+use chrono::DateTime;
+use chrono::Utc;
+use opendal::ErrorKind;
+use opendal::Metadata;
+use opendal::Operator;
+
+/// Serve the object at `path` as a http response.
+///
+/// Following request headers will be respected:
+///
+/// - `Range`: only single range is supported, multiple ranges will be
+///   ignored and the whole content will be returned.
+/// - `If-Match`, `If-None-Match` and `If-Modified-Since`.
+///
+/// The response will be one of:
+///
+/// - `200 OK` or `206 Partial Content` with a streaming body
+/// - `304 Not Modified` or `412 Precondition Failed` if conditions not met
+/// - `416 Range Not Satisfiable` if the range is out of content
+/// - `404 Not Found` or other errors returned by the operator
+///
+/// For `HEAD` requests, only headers will be returned and no read request
+/// will be sent to the backend.
+pub async fn serve_object(
+    op: &Operator,
+    method: &Method,
+    path: &str,
+    headers: &HeaderMap,
+) -> Response {
+    let meta = match op.stat(path).await {
+        Ok(meta) => meta,
+        Err(err) => return error_response(err),
+    };
+    if meta.is_dir() {
+        return status_response(StatusCode::NOT_FOUND);
+    }
+
+    let etag = meta.etag();
+    let last_modified = meta.last_modified();
+    let total_size = meta.content_length();
+
+    if let Some(v) = header_str(headers, header::IF_MATCH) {
+        if !etag_matches(v, etag, false) {
+            return status_response(StatusCode::PRECONDITION_FAILED);
+        }
+    }
+    if let Some(v) = header_str(headers, header::IF_NONE_MATCH) {
+        if etag_matches(v, etag, true) {
+            return not_modified_response(&meta);
+        }
+    } else if let (Some(v), Some(last_modified)) = (
+        header_str(headers, header::IF_MODIFIED_SINCE),
+        last_modified,
+    ) {
+        // HTTP date only has precision of seconds.
+        match DateTime::parse_from_rfc2822(v) {
+            Ok(since) if last_modified.timestamp() <= since.timestamp() => {
+                return not_modified_response(&meta)
+            }
+            _ => {}
+        }
+    }
+
+    let range = match header_str(headers, header::RANGE).map(|v| parse_range(v, total_size)) {
+        Some(Ok(range)) => range,
+        Some(Err(())) => {
+            let mut resp = status_response(StatusCode::RANGE_NOT_SATISFIABLE);
+            insert_header(
+                &mut resp,
+                header::CONTENT_RANGE,
+                &format!("bytes */{total_size}"),
+            );
+            return resp;
+        }
+        None => None,
+    };
+    let (status, start, end) = match range {
+        Some((start, end)) => (StatusCode::PARTIAL_CONTENT, start, end),
+        None => (StatusCode::OK, 0, total_size),
+    };
+
+    let body = if start == end || method == Method::HEAD {
+        boxed(Empty::new())
+    } else {
+        match op.range_reader(path, start..end).await {
+            Ok(r) => boxed(StreamBody::new(r)),
+            Err(err) => return error_response(err),
+        }
+    };
+
+    let mut resp = Response::new(body);
+    *resp.status_mut() = status;
+    insert_metadata_headers(&mut resp, &meta);
+    insert_header(&mut resp, header::ACCEPT_RANGES, "bytes");
+    insert_header(
+        &mut resp,
+        header::CONTENT_LENGTH,
+        &(end - start).to_string(),
+    );
+    if let Some(v) = meta.content_type() {
+        insert_header(&mut resp, header::CONTENT_TYPE, v);
+    }
+    if status == StatusCode::PARTIAL_CONTENT {
+        let v = format!("bytes {}-{}/{}", start, end - 1, total_size);
+        insert_header(&mut resp, header::CONTENT_RANGE, &v);
+    }
+    resp
+}
+
+/// Parse single range header into `[start, end)` of content.
+///
+/// Returns `Ok(None)` if the range should be ignored and `Err` if the range
+/// is not satisfiable.
+fn parse_range(value: &str, total_size: u64) -> Result<Option<(u64, u64)>, ()> {
+    let Some(v) = value.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if v.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = v.split_once('-') else {
+        return Ok(None);
+    };
+
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", "") => return Ok(None),
+        // bytes=-<suffix-length>
+        ("", suffix) => match suffix.parse::<u64>() {
+            Ok(0) => return Err(()),
+            Ok(suffix) => (total_size.saturating_sub(suffix), total_size),
+            Err(_) => return Ok(None),
+        },
+        // bytes=<range-start>-
+        (start, "") => match start.parse::<u64>() {
+            Ok(start) => (start, total_size),
+            Err(_) => return Ok(None),
+        },
+        // bytes=<range-start>-<range-end>
+        (start, end) => match (start.parse::<u64>(), end.parse::<u64>()) {
+            (Ok(start), Ok(end)) if start <= end => (start, total_size.min(end + 1)),
+            _ => return Ok(None),
+        },
+    };
+
+    if start >= total_size {
+        return Err(());
+    }
+    Ok(Some((start, end)))
+}
+
+/// Check if etag matches the value of `If-Match` or `If-None-Match`.
+///
+/// Weak comparison will ignore the `W/` prefix of etags.
+pub(crate) fn etag_matches(value: &str, etag: Option<&str>, weak: bool) -> bool {
+    let normalize = |v: &str| {
+        let v = v.trim();
+        if weak {
+            v.trim_start_matches("W/").to_string()
+        } else {
+            v.to_string()
+        }
+    };
+
+    value.split(',').any(|v| {
+        let v = v.trim();
+        v == "*" || etag.map_or(false, |etag| normalize(v) == normalize(etag))
+    })
+}
+
+pub(crate) fn header_str(headers: &HeaderMap, name: header::HeaderName) -> Option<&str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn insert_header(resp: &mut Response, name: header::HeaderName, value: &str) {
+    if let Ok(v) = HeaderValue::from_str(value) {
+        resp.headers_mut().insert(name, v);
+    }
+}
+
+fn insert_metadata_headers(resp: &mut Response, meta: &Metadata) {
+    if let Some(v) = meta.etag() {
+        insert_header(resp, header::ETAG, v);
+    }
+    if let Some(v) = meta.last_modified() {
+        insert_header(resp, header::LAST_MODIFIED, &format_http_date(v));
+    }
+}
+
+fn format_http_date(v: DateTime<Utc>) -> String {
+    v.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+fn not_modified_response(meta: &Metadata) -> Response {
+    let mut resp = status_response(StatusCode::NOT_MODIFIED);
+    insert_metadata_headers(&mut resp, meta);
+    resp
+}
+
+pub(crate) fn status_response(status: StatusCode) -> Response {
+    let mut resp = Response::new(boxed(Empty::new()));
+    *resp.status_mut() = status;
+    resp
+}
+
+pub(crate) fn error_response(err: opendal::Error) -> Response {
+    let status = match err.kind() {
+        ErrorKind::NotFound => StatusCode::NOT_FOUND,
+        ErrorKind::PermissionDenied => StatusCode::FORBIDDEN,
+        ErrorKind::ConditionNotMatch => StatusCode::PRECONDITION_FAILED,
+        ErrorKind::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    let mut resp = Response::new(boxed(Full::from(err.to_string())));
+    *resp.status_mut() = status;
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::HttpBody;
+    use opendal::raw::Operation;
+    use opendal::services::Memory;
+
+    use super::*;
+
+    async fn new_operator() -> Operator {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("test", "Hello, World!").await.unwrap();
+        op
+    }
+
+    async fn serve(op: &Operator, method: Method, headers: &[(&str, &str)]) -> Response {
+        let mut map = HeaderMap::new();
+        for (k, v) in headers {
+            map.insert(
+                header::HeaderName::from_bytes(k.as_bytes()).unwrap(),
+                HeaderValue::from_str(v).unwrap(),
+            );
+        }
+        serve_object(op, &method, "test", &map).await
+    }
+
+    async fn read_body(resp: Response) -> String {
+        let mut body = resp.into_body();
+        let mut buf = Vec::new();
+        while let Some(bs) = body.data().await {
+            buf.extend_from_slice(&bs.unwrap());
+        }
+        String::from_utf8(buf).unwrap()
+    }
+
+    fn header_of(resp: &Response, name: header::HeaderName) -> Option<&str> {
+        header_str(resp.headers(), name)
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let cases = vec![
+            ("bytes=0-4", Ok(Some((0, 5)))),
+            ("bytes=7-", Ok(Some((7, 13)))),
+            ("bytes=-6", Ok(Some((7, 13)))),
+            ("bytes=-100", Ok(Some((0, 13)))),
+            ("bytes=5-100", Ok(Some((5, 13)))),
+            ("bytes=13-", Err(())),
+            ("bytes=-0", Err(())),
+            ("bytes=0-1,3-4", Ok(None)),
+            ("bytes=4-1", Ok(None)),
+            ("bytes=-", Ok(None)),
+            ("items=0-1", Ok(None)),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(parse_range(input, 13), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_etag_matches() {
+        assert!(etag_matches("\"abc\"", Some("\"abc\""), false));
+        assert!(etag_matches("\"xyz\", \"abc\"", Some("\"abc\""), false));
+        assert!(etag_matches("*", None, false));
+        assert!(!etag_matches("\"abc\"", None, false));
+        assert!(!etag_matches("W/\"abc\"", Some("\"abc\""), false));
+        assert!(etag_matches("W/\"abc\"", Some("\"abc\""), true));
+    }
+
+    #[tokio::test]
+    async fn test_serve_object_ok() {
+        let op = new_operator().await;
+
+        let resp = serve(&op, Method::GET, &[]).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(header_of(&resp, header::CONTENT_LENGTH), Some("13"));
+        assert_eq!(header_of(&resp, header::ACCEPT_RANGES), Some("bytes"));
+        assert_eq!(read_body(resp).await, "Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn test_serve_object_partial_content() {
+        let op = new_operator().await;
+
+        let cases = vec![
+            ("bytes=0-4", "bytes 0-4/13", "Hello"),
+            ("bytes=-6", "bytes 7-12/13", "World!"),
+            ("bytes=7-", "bytes 7-12/13", "World!"),
+        ];
+        for (range, content_range, content) in cases {
+            let resp = serve(&op, Method::GET, &[("range", range)]).await;
+            assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT, "{range}");
+            assert_eq!(
+                header_of(&resp, header::CONTENT_RANGE),
+                Some(content_range),
+                "{range}"
+            );
+            assert_eq!(
+                header_of(&resp, header::CONTENT_LENGTH),
+                Some(content.len().to_string().as_str()),
+                "{range}"
+            );
+            assert_eq!(read_body(resp).await, content, "{range}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serve_object_range_not_satisfiable() {
+        let op = new_operator().await;
+
+        let resp = serve(&op, Method::GET, &[("range", "bytes=100-")]).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(header_of(&resp, header::CONTENT_RANGE), Some("bytes */13"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_object_not_modified() {
+        let op = new_operator().await;
+
+        let resp = serve(&op, Method::GET, &[("if-none-match", "*")]).await;
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(read_body(resp).await, "");
+    }
+
+    #[tokio::test]
+    async fn test_serve_object_precondition_failed() {
+        let op = new_operator().await;
+
+        let resp = serve(&op, Method::GET, &[("if-match", "\"not-exist\"")]).await;
+        assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+    }
+
+    #[tokio::test]
+    async fn test_serve_object_head() {
+        let op = new_operator().await;
+
+        let resp = serve(&op, Method::HEAD, &[("range", "bytes=0-4")]).await;
+        assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(header_of(&resp, header::CONTENT_LENGTH), Some("5"));
+        assert_eq!(read_body(resp).await, "");
+        assert_eq!(op.stats().operations_of(Operation::Read), 0);
+    }
+}
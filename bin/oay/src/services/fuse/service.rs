@@ -0,0 +1,698 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::io;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use bytes::Bytes;
+use fuse3::path::prelude::*;
+use fuse3::raw::MountHandle;
+use fuse3::Errno;
+use fuse3::MountOptions;
+use fuse3::Result;
+use futures::stream;
+use futures::stream::Iter;
+use futures::TryStreamExt;
+use opendal::EntryMode;
+use opendal::ErrorKind;
+use opendal::Metadata;
+use opendal::Metakey;
+use opendal::Operator;
+use tracing::debug;
+
+use crate::Config;
+
+const TTL: Duration = Duration::from_secs(1);
+const BLOCK_SIZE: u32 = 4096;
+const MAX_WRITE: u32 = 128 * 1024;
+
+pub struct FuseService {
+    cfg: Arc<Config>,
+    op: Operator,
+}
+
+impl FuseService {
+    pub fn new(cfg: Arc<Config>, op: Operator) -> Self {
+        Self { cfg, op }
+    }
+
+    /// Mount backend at `mount_path` until it's unmounted.
+    pub async fn serve(&self) -> anyhow::Result<()> {
+        let fuse_cfg = &self.cfg.frontends.fuse;
+
+        let handle = Self::mount(self.op.clone(), &fuse_cfg.mount_path).await?;
+        handle.await?;
+
+        Ok(())
+    }
+
+    /// Mount operator at given path, the returned handle will be ready
+    /// after the filesystem is unmounted.
+    ///
+    /// Root mounts via mount syscall directly, others mount via `fusermount3`.
+    pub async fn mount(op: Operator, mount_path: impl AsRef<Path>) -> io::Result<MountHandle> {
+        // Safety: getuid and getgid are always successful.
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+
+        let mut opts = MountOptions::default();
+        opts.fs_name("oay").uid(uid).gid(gid);
+
+        let session = Session::new(opts);
+        let fs = Fuse::new(op, uid, gid);
+        if uid == 0 {
+            session.mount(fs, mount_path).await
+        } else {
+            session.mount_with_unprivileged(fs, mount_path).await
+        }
+    }
+}
+
+/// Fuse maps fuse requests to operations of the operator.
+///
+/// OpenDAL doesn't support random write, so opened files for write are
+/// buffered in memory and written to backend as a whole on flush.
+struct Fuse {
+    op: Operator,
+    uid: u32,
+    gid: u32,
+
+    next_fh: AtomicU64,
+    opened_files: Mutex<HashMap<u64, OpenedFile>>,
+}
+
+struct OpenedFile {
+    path: String,
+    /// Content of the file, only set if file is opened for write.
+    buf: Option<Vec<u8>>,
+    dirty: bool,
+}
+
+impl Fuse {
+    fn new(op: Operator, uid: u32, gid: u32) -> Self {
+        Self {
+            op,
+            uid,
+            gid,
+            next_fh: AtomicU64::new(1),
+            opened_files: Mutex::default(),
+        }
+    }
+
+    fn open_file(&self, path: String, buf: Option<Vec<u8>>, dirty: bool) -> u64 {
+        let fh = self.next_fh.fetch_add(1, Ordering::Relaxed);
+        self.opened_files
+            .lock()
+            .expect("lock must succeed")
+            .insert(fh, OpenedFile { path, buf, dirty });
+        fh
+    }
+
+    fn file_attr(&self, mode: EntryMode, size: u64, mtime: SystemTime) -> FileAttr {
+        let (kind, perm) = match mode {
+            EntryMode::DIR => (FileType::Directory, 0o755),
+            _ => (FileType::RegularFile, 0o644),
+        };
+
+        FileAttr {
+            size,
+            blocks: (size + BLOCK_SIZE as u64 - 1) / BLOCK_SIZE as u64,
+            atime: mtime,
+            mtime,
+            ctime: mtime,
+            #[cfg(target_os = "macos")]
+            crtime: mtime,
+            kind,
+            perm,
+            nlink: 1,
+            uid: self.uid,
+            gid: self.gid,
+            rdev: 0,
+            #[cfg(target_os = "macos")]
+            flags: 0,
+            blksize: BLOCK_SIZE,
+        }
+    }
+
+    fn metadata_attr(&self, meta: &Metadata) -> FileAttr {
+        let mtime = meta
+            .last_modified()
+            .map(SystemTime::from)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        self.file_attr(meta.mode(), meta.content_length(), mtime)
+    }
+
+    /// Stat the path as file first, then look it up as dir in its parent.
+    async fn stat(&self, path: &str) -> Result<FileAttr> {
+        if path.is_empty() {
+            return Ok(self.file_attr(EntryMode::DIR, 0, SystemTime::UNIX_EPOCH));
+        }
+
+        match self.op.stat(path).await {
+            Ok(meta) => return Ok(self.metadata_attr(&meta)),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(format_errno(err)),
+        }
+
+        // Some services treat every dir path as existing, so find the dir
+        // in its parent instead of stat it.
+        let dir = format!("{path}/");
+        let parent = match path.rfind('/') {
+            Some(idx) => &path[..=idx],
+            None => "/",
+        };
+        let mut lister = self.op.list(parent).await.map_err(format_errno)?;
+        while let Some(entry) = lister.try_next().await.map_err(format_errno)? {
+            if entry.path() == dir {
+                let meta = self
+                    .op
+                    .metadata(&entry, Metakey::Mode | Metakey::LastModified)
+                    .await
+                    .map_err(format_errno)?;
+                return Ok(self.metadata_attr(&meta));
+            }
+        }
+
+        Err(Errno::from(libc::ENOENT))
+    }
+
+    /// Write the content of opened file back to backend if it's dirty.
+    async fn flush_file(&self, fh: u64) -> Result<()> {
+        let (path, bs) = {
+            let mut files = self.opened_files.lock().expect("lock must succeed");
+            let file = files.get_mut(&fh).ok_or_else(|| Errno::from(libc::EBADF))?;
+            match (&file.buf, file.dirty) {
+                (Some(buf), true) => {
+                    file.dirty = false;
+                    (file.path.clone(), buf.clone())
+                }
+                _ => return Ok(()),
+            }
+        };
+
+        debug!("flush file {} with {} bytes", path, bs.len());
+        self.op.write(&path, bs).await.map_err(format_errno)
+    }
+
+    async fn list(&self, path: &OsStr) -> Result<Vec<(String, FileAttr)>> {
+        let path = format_dir(path);
+
+        let mut entries = Vec::new();
+        let mut lister = self.op.list(&path).await.map_err(format_errno)?;
+        while let Some(entry) = lister.try_next().await.map_err(format_errno)? {
+            let meta = self
+                .op
+                .metadata(
+                    &entry,
+                    Metakey::Mode | Metakey::ContentLength | Metakey::LastModified,
+                )
+                .await
+                .map_err(format_errno)?;
+            let name = entry.name().trim_end_matches('/').to_string();
+            entries.push((name, self.metadata_attr(&meta)));
+        }
+
+        Ok(entries)
+    }
+}
+
+impl PathFilesystem for Fuse {
+    type DirEntryStream<'a> = Iter<std::vec::IntoIter<Result<DirectoryEntry>>>;
+    type DirEntryPlusStream<'a> = Iter<std::vec::IntoIter<Result<DirectoryEntryPlus>>>;
+
+    async fn init(&self, _req: Request) -> Result<ReplyInit> {
+        Ok(ReplyInit {
+            max_write: NonZeroU32::new(MAX_WRITE).expect("max write must be non zero"),
+        })
+    }
+
+    async fn destroy(&self, _req: Request) {}
+
+    async fn lookup(&self, _req: Request, parent: &OsStr, name: &OsStr) -> Result<ReplyEntry> {
+        let attr = self.stat(&format_path(parent, name)).await?;
+
+        Ok(ReplyEntry { ttl: TTL, attr })
+    }
+
+    async fn getattr(
+        &self,
+        _req: Request,
+        path: Option<&OsStr>,
+        fh: Option<u64>,
+        _flags: u32,
+    ) -> Result<ReplyAttr> {
+        // Opened files for write may have content that not flushed yet.
+        if let Some(fh) = fh {
+            let size = self
+                .opened_files
+                .lock()
+                .expect("lock must succeed")
+                .get(&fh)
+                .and_then(|file| file.buf.as_ref().map(|buf| buf.len() as u64));
+            if let Some(size) = size {
+                return Ok(ReplyAttr {
+                    ttl: TTL,
+                    attr: self.file_attr(EntryMode::FILE, size, SystemTime::now()),
+                });
+            }
+        }
+
+        let path = path.ok_or_else(|| Errno::from(libc::ENOENT))?;
+        let attr = self.stat(&format_file(path)).await?;
+
+        Ok(ReplyAttr { ttl: TTL, attr })
+    }
+
+    async fn setattr(
+        &self,
+        req: Request,
+        path: Option<&OsStr>,
+        fh: Option<u64>,
+        set_attr: SetAttr,
+    ) -> Result<ReplyAttr> {
+        // Only truncate is supported, other attributes will be ignored.
+        if let Some(size) = set_attr.size {
+            let truncated = fh.map_or(false, |fh| {
+                let mut files = self.opened_files.lock().expect("lock must succeed");
+                match files.get_mut(&fh) {
+                    Some(OpenedFile {
+                        buf: Some(buf),
+                        dirty,
+                        ..
+                    }) => {
+                        buf.resize(size as usize, 0);
+                        *dirty = true;
+                        true
+                    }
+                    _ => false,
+                }
+            });
+
+            if !truncated {
+                let path = format_file(path.ok_or_else(|| Errno::from(libc::ENOENT))?);
+                let mut bs = if size == 0 {
+                    vec![]
+                } else {
+                    self.op.read(&path).await.map_err(format_errno)?
+                };
+                bs.resize(size as usize, 0);
+                self.op.write(&path, bs).await.map_err(format_errno)?;
+            }
+        }
+
+        self.getattr(req, path, fh, 0).await
+    }
+
+    async fn mkdir(
+        &self,
+        _req: Request,
+        parent: &OsStr,
+        name: &OsStr,
+        _mode: u32,
+        _umask: u32,
+    ) -> Result<ReplyEntry> {
+        let path = format!("{}/", format_path(parent, name));
+        self.op.create_dir(&path).await.map_err(format_errno)?;
+
+        Ok(ReplyEntry {
+            ttl: TTL,
+            attr: self.file_attr(EntryMode::DIR, 0, SystemTime::now()),
+        })
+    }
+
+    async fn unlink(&self, _req: Request, parent: &OsStr, name: &OsStr) -> Result<()> {
+        self.op
+            .delete(&format_path(parent, name))
+            .await
+            .map_err(format_errno)
+    }
+
+    async fn rmdir(&self, _req: Request, parent: &OsStr, name: &OsStr) -> Result<()> {
+        let path = format!("{}/", format_path(parent, name));
+
+        let mut lister = self.op.list(&path).await.map_err(format_errno)?;
+        if lister.try_next().await.map_err(format_errno)?.is_some() {
+            return Err(Errno::from(libc::ENOTEMPTY));
+        }
+
+        self.op.delete(&path).await.map_err(format_errno)
+    }
+
+    async fn rename(
+        &self,
+        _req: Request,
+        origin_parent: &OsStr,
+        origin_name: &OsStr,
+        parent: &OsStr,
+        name: &OsStr,
+    ) -> Result<()> {
+        self.op
+            .rename(
+                &format_path(origin_parent, origin_name),
+                &format_path(parent, name),
+            )
+            .await
+            .map_err(format_errno)
+    }
+
+    async fn open(&self, _req: Request, path: &OsStr, flags: u32) -> Result<ReplyOpen> {
+        let path = format_file(path);
+        let flags = flags as i32;
+
+        let buf = if flags & libc::O_ACCMODE == libc::O_RDONLY {
+            None
+        } else if flags & libc::O_TRUNC != 0 {
+            Some(vec![])
+        } else {
+            match self.op.read(&path).await {
+                Ok(bs) => Some(bs),
+                Err(err) if err.kind() == ErrorKind::NotFound => Some(vec![]),
+                Err(err) => return Err(format_errno(err)),
+            }
+        };
+        let dirty = flags & libc::O_TRUNC != 0 && buf.is_some();
+
+        Ok(ReplyOpen {
+            fh: self.open_file(path, buf, dirty),
+            flags: 0,
+        })
+    }
+
+    async fn read(
+        &self,
+        _req: Request,
+        path: Option<&OsStr>,
+        fh: u64,
+        offset: u64,
+        size: u32,
+    ) -> Result<ReplyData> {
+        {
+            let files = self.opened_files.lock().expect("lock must succeed");
+            if let Some(OpenedFile { buf: Some(buf), .. }) = files.get(&fh) {
+                let start = (offset as usize).min(buf.len());
+                let end = (start + size as usize).min(buf.len());
+                return Ok(ReplyData {
+                    data: Bytes::copy_from_slice(&buf[start..end]),
+                });
+            }
+        }
+
+        let path = format_file(path.ok_or_else(|| Errno::from(libc::ENOENT))?);
+        let bs = self
+            .op
+            .range_read(&path, offset..offset + size as u64)
+            .await
+            .map_err(format_errno)?;
+
+        Ok(ReplyData { data: bs.into() })
+    }
+
+    async fn write(
+        &self,
+        _req: Request,
+        _path: Option<&OsStr>,
+        fh: u64,
+        offset: u64,
+        data: &[u8],
+        _write_flags: u32,
+        _flags: u32,
+    ) -> Result<ReplyWrite> {
+        let mut files = self.opened_files.lock().expect("lock must succeed");
+        let (buf, dirty) = match files.get_mut(&fh) {
+            Some(OpenedFile {
+                buf: Some(buf),
+                dirty,
+                ..
+            }) => (buf, dirty),
+            _ => return Err(Errno::from(libc::EBADF)),
+        };
+
+        let offset = offset as usize;
+        if buf.len() < offset + data.len() {
+            buf.resize(offset + data.len(), 0);
+        }
+        buf[offset..offset + data.len()].copy_from_slice(data);
+        *dirty = true;
+
+        Ok(ReplyWrite {
+            written: data.len() as u32,
+        })
+    }
+
+    async fn flush(
+        &self,
+        _req: Request,
+        _path: Option<&OsStr>,
+        fh: u64,
+        _lock_owner: u64,
+    ) -> Result<()> {
+        self.flush_file(fh).await
+    }
+
+    async fn release(
+        &self,
+        _req: Request,
+        _path: Option<&OsStr>,
+        fh: u64,
+        _flags: u32,
+        _lock_owner: u64,
+        _flush: bool,
+    ) -> Result<()> {
+        let res = self.flush_file(fh).await;
+        self.opened_files
+            .lock()
+            .expect("lock must succeed")
+            .remove(&fh);
+        res
+    }
+
+    async fn create(
+        &self,
+        _req: Request,
+        parent: &OsStr,
+        name: &OsStr,
+        _mode: u32,
+        _flags: u32,
+    ) -> Result<ReplyCreated> {
+        let path = format_path(parent, name);
+        // Write an empty file so that it can be found before flush.
+        self.op.write(&path, vec![]).await.map_err(format_errno)?;
+
+        Ok(ReplyCreated {
+            ttl: TTL,
+            attr: self.file_attr(EntryMode::FILE, 0, SystemTime::now()),
+            generation: 0,
+            fh: self.open_file(path, Some(vec![]), false),
+            flags: 0,
+        })
+    }
+
+    async fn readdir<'a>(
+        &'a self,
+        _req: Request,
+        path: &'a OsStr,
+        _fh: u64,
+        offset: i64,
+    ) -> Result<ReplyDirectory<Self::DirEntryStream<'a>>> {
+        let mut entries = vec![
+            (FileType::Directory, OsString::from(".")),
+            (FileType::Directory, OsString::from("..")),
+        ];
+        for (name, attr) in self.list(path).await? {
+            entries.push((attr.kind, name.into()));
+        }
+
+        let entries = entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, (kind, name))| {
+                Ok(DirectoryEntry {
+                    kind,
+                    name,
+                    offset: i as i64 + 1,
+                })
+            })
+            .skip(offset as usize)
+            .collect::<Vec<_>>();
+
+        Ok(ReplyDirectory {
+            entries: stream::iter(entries),
+        })
+    }
+
+    async fn readdirplus<'a>(
+        &'a self,
+        _req: Request,
+        parent: &'a OsStr,
+        _fh: u64,
+        offset: u64,
+        _lock_owner: u64,
+    ) -> Result<ReplyDirectoryPlus<Self::DirEntryPlusStream<'a>>> {
+        let mut entries = vec![
+            (
+                OsString::from("."),
+                self.file_attr(EntryMode::DIR, 0, SystemTime::UNIX_EPOCH),
+            ),
+            (
+                OsString::from(".."),
+                self.file_attr(EntryMode::DIR, 0, SystemTime::UNIX_EPOCH),
+            ),
+        ];
+        for (name, attr) in self.list(parent).await? {
+            entries.push((name.into(), attr));
+        }
+
+        let entries = entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, attr))| {
+                Ok(DirectoryEntryPlus {
+                    kind: attr.kind,
+                    name,
+                    offset: i as i64 + 1,
+                    attr,
+                    entry_ttl: TTL,
+                    attr_ttl: TTL,
+                })
+            })
+            .skip(offset as usize)
+            .collect::<Vec<_>>();
+
+        Ok(ReplyDirectoryPlus {
+            entries: stream::iter(entries),
+        })
+    }
+}
+
+/// Convert fuse path like `/path/to/file` into OpenDAL path.
+fn format_file(path: &OsStr) -> String {
+    path.to_string_lossy().trim_matches('/').to_string()
+}
+
+/// Convert fuse path like `/path/to/dir` into OpenDAL dir path.
+fn format_dir(path: &OsStr) -> String {
+    match format_file(path) {
+        p if p.is_empty() => "/".to_string(),
+        p => format!("{p}/"),
+    }
+}
+
+fn format_path(parent: &OsStr, name: &OsStr) -> String {
+    match format_file(parent) {
+        p if p.is_empty() => name.to_string_lossy().to_string(),
+        p => format!("{p}/{}", name.to_string_lossy()),
+    }
+}
+
+fn format_errno(err: opendal::Error) -> Errno {
+    let errno = match err.kind() {
+        ErrorKind::NotFound => libc::ENOENT,
+        ErrorKind::PermissionDenied => libc::EACCES,
+        ErrorKind::AlreadyExists => libc::EEXIST,
+        ErrorKind::IsADirectory => libc::EISDIR,
+        ErrorKind::NotADirectory => libc::ENOTDIR,
+        ErrorKind::Unsupported => libc::EOPNOTSUPP,
+        _ => libc::EIO,
+    };
+
+    Errno::from(errno)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::PathBuf;
+
+    use opendal::services::Memory;
+
+    use super::*;
+
+    fn unmount(path: &Path) -> io::Result<()> {
+        let path = std::ffi::CString::new(path.as_os_str().as_bytes())?;
+        // Safety: path is a valid c string.
+        match unsafe { libc::umount2(path.as_ptr(), libc::MNT_DETACH) } {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mount() {
+        // Mounting without fusermount3 requires root and fuse device.
+        // Safety: getuid is always successful.
+        if unsafe { libc::getuid() } != 0 || !Path::new("/dev/fuse").exists() {
+            return;
+        }
+
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("hello.txt", "Hello, World!").await.unwrap();
+
+        let dir: PathBuf = std::env::temp_dir().join(format!("oay-fuse-{}", uuid::Uuid::new_v4()));
+        fs::create_dir(&dir).unwrap();
+
+        let handle = FuseService::mount(op.clone(), &dir).await.unwrap();
+
+        // Files must be accessed in blocking threads since requests are
+        // served by this runtime.
+        let path = dir.clone();
+        let res = tokio::task::spawn_blocking(move || {
+            let res = std::panic::catch_unwind(|| {
+                assert_eq!(
+                    fs::read_to_string(path.join("hello.txt")).unwrap(),
+                    "Hello, World!"
+                );
+
+                fs::write(path.join("new.txt"), "Hello, FUSE!").unwrap();
+                fs::create_dir(path.join("dir")).unwrap();
+
+                let mut names = fs::read_dir(&path)
+                    .unwrap()
+                    .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+                    .collect::<Vec<_>>();
+                names.sort();
+                assert_eq!(names, vec!["dir", "hello.txt", "new.txt"]);
+
+                fs::remove_file(path.join("hello.txt")).unwrap();
+            });
+            unmount(&path).unwrap();
+            res
+        })
+        .await
+        .unwrap();
+
+        handle.await.unwrap();
+        fs::remove_dir(&dir).unwrap();
+        if let Err(err) = res {
+            std::panic::resume_unwind(err);
+        }
+
+        assert_eq!(op.read("new.txt").await.unwrap(), b"Hello, FUSE!");
+        assert!(op.is_exist("dir/").await.unwrap());
+        assert!(!op.is_exist("hello.txt").await.unwrap());
+    }
+}
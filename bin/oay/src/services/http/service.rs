@@ -0,0 +1,264 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use axum::extract::BodyStream;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::header;
+use axum::http::HeaderMap;
+use axum::http::Method;
+use axum::http::StatusCode;
+use axum::response::Response;
+use axum::routing::get;
+use axum::Router;
+use futures::TryStreamExt;
+use opendal::ErrorKind;
+use opendal::Operator;
+use tower::ServiceBuilder;
+use tower_http::trace::TraceLayer;
+use tracing::debug;
+
+use crate::serve::error_response;
+use crate::serve::etag_matches;
+use crate::serve::header_str;
+use crate::serve::status_response;
+use crate::serve_object;
+use crate::Config;
+
+pub struct HttpService {
+    cfg: Arc<Config>,
+    op: Operator,
+}
+
+impl HttpService {
+    pub fn new(cfg: Arc<Config>, op: Operator) -> Self {
+        Self { cfg, op }
+    }
+
+    /// Build a router which maps requests to operations of the operator.
+    ///
+    /// The router can be nested into other apps under any route prefix:
+    ///
+    /// ```ignore
+    /// let app = Router::new().nest("/files", HttpService::router(op));
+    /// ```
+    pub fn router(op: Operator) -> Router {
+        Router::new()
+            .route(
+                "/*path",
+                get(handle_get).put(handle_put).delete(handle_delete),
+            )
+            .with_state(HttpState { op })
+    }
+
+    pub async fn serve(&self) -> anyhow::Result<()> {
+        let http_cfg = &self.cfg.frontends.http;
+
+        let app = Self::router(self.op.clone())
+            .layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()));
+
+        axum::Server::bind(&http_cfg.addr.parse()?)
+            .serve(app.into_make_service())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct HttpState {
+    op: Operator,
+}
+
+/// Handle `GET` and `HEAD`, `HEAD` won't read content from the backend.
+async fn handle_get(
+    state: State<HttpState>,
+    method: Method,
+    Path(path): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    serve_object(&state.op, &method, &path, &headers).await
+}
+
+async fn handle_put(
+    state: State<HttpState>,
+    Path(path): Path<String>,
+    headers: HeaderMap,
+    mut body: BodyStream,
+) -> Response {
+    debug!("put object: {}", path);
+
+    // Conditions are checked by stat before writing, which is not atomic.
+    if let Some(resp) = check_conditions(&state.op, &path, &headers).await {
+        return resp;
+    }
+
+    let mut fut = state.op.writer_with(&path);
+    if let Some(v) = header_str(&headers, header::CONTENT_LENGTH).and_then(|v| v.parse().ok()) {
+        fut = fut.content_length(v);
+    }
+    if let Some(v) = header_str(&headers, header::CONTENT_TYPE) {
+        fut = fut.content_type(v);
+    }
+    let mut w = match fut.await {
+        Ok(w) => w,
+        Err(err) => return error_response(err),
+    };
+
+    loop {
+        match body.try_next().await {
+            Ok(Some(bs)) => {
+                if let Err(err) = w.write(bs).await {
+                    let _ = w.abort().await;
+                    return error_response(err);
+                }
+            }
+            Ok(None) => break,
+            Err(err) => {
+                let _ = w.abort().await;
+                debug!("read request body failed: {}", err);
+                return status_response(StatusCode::BAD_REQUEST);
+            }
+        }
+    }
+    if let Err(err) = w.close().await {
+        return error_response(err);
+    }
+
+    status_response(StatusCode::OK)
+}
+
+async fn handle_delete(
+    state: State<HttpState>,
+    Path(path): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    debug!("delete object: {}", path);
+
+    if let Some(resp) = check_conditions(&state.op, &path, &headers).await {
+        return resp;
+    }
+
+    match state.op.delete(&path).await {
+        Ok(()) => status_response(StatusCode::NO_CONTENT),
+        Err(err) => error_response(err),
+    }
+}
+
+/// Check `If-Match` and `If-None-Match` of write requests, returns the
+/// response if conditions not met.
+async fn check_conditions(op: &Operator, path: &str, headers: &HeaderMap) -> Option<Response> {
+    let if_match = header_str(headers, header::IF_MATCH);
+    let if_none_match = header_str(headers, header::IF_NONE_MATCH);
+    if if_match.is_none() && if_none_match.is_none() {
+        return None;
+    }
+
+    let (exists, etag) = match op.stat(path).await {
+        Ok(meta) => (true, meta.etag().map(|v| v.to_string())),
+        Err(err) if err.kind() == ErrorKind::NotFound => (false, None),
+        Err(err) => return Some(error_response(err)),
+    };
+
+    if let Some(v) = if_match {
+        if !exists || !etag_matches(v, etag.as_deref(), false) {
+            return Some(status_response(StatusCode::PRECONDITION_FAILED));
+        }
+    }
+    if let Some(v) = if_none_match {
+        if exists && etag_matches(v, etag.as_deref(), true) {
+            return Some(status_response(StatusCode::PRECONDITION_FAILED));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::body::HttpBody;
+    use axum::http::Request;
+    use opendal::services::Memory;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn send(router: Router, req: Request<Body>) -> (StatusCode, String) {
+        let resp = router.oneshot(req).await.unwrap();
+        let status = resp.status();
+
+        let mut body = resp.into_body();
+        let mut buf = Vec::new();
+        while let Some(bs) = body.data().await {
+            buf.extend_from_slice(&bs.unwrap());
+        }
+        (status, String::from_utf8(buf).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_router_get_with_range() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("dir/test", "Hello, World!").await.unwrap();
+
+        let req = Request::get("/dir/test")
+            .header(header::RANGE, "bytes=7-")
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(HttpService::router(op), req).await;
+        assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+        assert_eq!(body, "World!");
+    }
+
+    #[tokio::test]
+    async fn test_router_put() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+
+        let req = Request::put("/dir/test")
+            .body(Body::from("Hello, World!"))
+            .unwrap();
+        let (status, _) = send(HttpService::router(op.clone()), req).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(op.read("dir/test").await.unwrap(), b"Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn test_router_delete() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("dir/test", "Hello, World!").await.unwrap();
+
+        let req = Request::delete("/dir/test").body(Body::empty()).unwrap();
+        let (status, _) = send(HttpService::router(op.clone()), req).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert!(!op.is_exist("dir/test").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_router_put_precondition_failed() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("dir/test", "Hello, World!").await.unwrap();
+
+        let req = Request::put("/dir/test")
+            .header(header::IF_NONE_MATCH, "*")
+            .body(Body::from("Hello, OpenDAL!"))
+            .unwrap();
+        let (status, _) = send(HttpService::router(op.clone()), req).await;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        assert_eq!(op.read("dir/test").await.unwrap(), b"Hello, World!");
+    }
+}
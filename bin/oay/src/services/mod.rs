@@ -17,3 +17,13 @@
 
 mod s3;
 pub use s3::S3Service;
+
+#[cfg(feature = "frontends-fuse")]
+mod fuse;
+#[cfg(feature = "frontends-fuse")]
+pub use fuse::FuseService;
+
+#[cfg(feature = "frontends-http")]
+mod http;
+#[cfg(feature = "frontends-http")]
+pub use http::HttpService;
@@ -17,6 +17,8 @@
 
 use std::ops::Range;
 use std::pin::Pin;
+#[cfg(feature = "datafusion")]
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 
@@ -37,6 +39,8 @@ use opendal::Metakey;
 use opendal::Operator;
 use opendal::Reader;
 use tokio::io::AsyncWrite;
+#[cfg(feature = "datafusion")]
+use url::Url;
 
 #[derive(Debug)]
 pub struct OpendalStore {
@@ -217,6 +221,25 @@ impl ObjectStore for OpendalStore {
     }
 }
 
+/// Register the operator as the object store of `url` in DataFusion's
+/// [`RuntimeEnv`](datafusion::execution::runtime_env::RuntimeEnv).
+///
+/// Object store is registered by the scheme and host of `url`, for example
+/// `s3://bucket`, path of `url` will be ignored. Any previously registered
+/// object store for the same scheme and host will be returned.
+#[cfg(feature = "datafusion")]
+pub fn register_operator(
+    runtime: &datafusion::execution::runtime_env::RuntimeEnv,
+    url: &Url,
+    op: Operator,
+) -> Option<Arc<dyn ObjectStore>> {
+    runtime.register_object_store(
+        url.scheme(),
+        &url[url::Position::BeforeUsername..url::Position::BeforePath],
+        Arc::new(OpendalStore::new(op)),
+    )
+}
+
 fn format_object_store_error(err: opendal::Error, path: &str) -> object_store::Error {
     use opendal::ErrorKind;
     match err.kind() {
@@ -335,4 +358,25 @@ mod tests {
         assert_eq!(result.objects[0].location.as_ref(), "data/test.txt");
         assert_eq!(result.common_prefixes[0].as_ref(), "data/nested");
     }
+
+    #[cfg(feature = "datafusion")]
+    #[tokio::test]
+    async fn test_register_operator() {
+        use datafusion::datasource::object_store::ObjectStoreUrl;
+        use datafusion::execution::runtime_env::RuntimeConfig;
+        use datafusion::execution::runtime_env::RuntimeEnv;
+
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        op.write("data/test.txt", "hello, world!").await.unwrap();
+
+        let runtime = RuntimeEnv::new(RuntimeConfig::new()).unwrap();
+        let url = Url::parse("opendal://memory").unwrap();
+        assert!(register_operator(&runtime, &url, op).is_none());
+
+        let url = ObjectStoreUrl::parse("opendal://memory").unwrap();
+        let object_store = runtime.object_store(url).unwrap();
+        let path: Path = "data/test.txt".try_into().unwrap();
+        let meta = object_store.head(&path).await.unwrap();
+        assert_eq!(meta.size, 13);
+    }
 }
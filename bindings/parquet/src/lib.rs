@@ -0,0 +1,303 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use opendal::Operator;
+use parquet::arrow::async_reader::AsyncFileReader;
+use parquet::errors::ParquetError;
+use parquet::errors::Result;
+use parquet::file::footer::decode_footer;
+use parquet::file::footer::decode_metadata;
+use parquet::file::metadata::ParquetMetaData;
+
+/// The size of parquet footer: 4 bytes metadata length + 4 bytes magic.
+const FOOTER_SIZE: usize = 8;
+
+/// OpendalAsyncReader implements parquet's [`AsyncFileReader`] over a file
+/// on any OpenDAL backend.
+///
+/// - Column chunks are fetched by ranged reads, nearby ranges will be merged
+///   into one request.
+/// - Footer will be fetched by one suffix read of `footer_size_hint` bytes if
+///   the metadata fits in, otherwise one more read is needed.
+/// - Decoded metadata can be shared between readers via [`MetadataCache`].
+#[derive(Debug)]
+pub struct OpendalAsyncReader {
+    op: Operator,
+    path: String,
+    content_length: Option<u64>,
+    footer_size_hint: usize,
+    cache: Option<MetadataCache>,
+    metadata: Option<Arc<ParquetMetaData>>,
+}
+
+impl OpendalAsyncReader {
+    /// Create a new OpendalAsyncReader by given operator and path.
+    pub fn new(op: Operator, path: &str) -> Self {
+        Self {
+            op,
+            path: path.to_string(),
+            content_length: None,
+            footer_size_hint: FOOTER_SIZE,
+            cache: None,
+            metadata: None,
+        }
+    }
+
+    /// Set the content length of the file.
+    ///
+    /// Reader will stat the file to learn its content length before fetching
+    /// footer if not set. Callers that already have the metadata (for
+    /// example, from a list) can set it to save one round trip.
+    pub fn with_content_length(mut self, content_length: u64) -> Self {
+        self.content_length = Some(content_length);
+        self
+    }
+
+    /// Set the size of the first suffix read while fetching footer.
+    ///
+    /// Metadata will be decoded from this read directly if it fits in,
+    /// saving one round trip. Default to the footer size (8 bytes).
+    pub fn with_footer_size_hint(mut self, size: usize) -> Self {
+        self.footer_size_hint = size.max(FOOTER_SIZE);
+        self
+    }
+
+    /// Set the metadata cache of this reader.
+    pub fn with_metadata_cache(mut self, cache: MetadataCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    async fn fetch_metadata(&self) -> Result<ParquetMetaData> {
+        let file_size = match self.content_length {
+            Some(v) => v,
+            None => self
+                .op
+                .stat(&self.path)
+                .await
+                .map_err(format_parquet_error)?
+                .content_length(),
+        };
+        if file_size < FOOTER_SIZE as u64 {
+            return Err(ParquetError::EOF(format!(
+                "file size of {} is less than footer",
+                file_size
+            )));
+        }
+
+        let suffix_len = (self.footer_size_hint as u64).min(file_size);
+        let suffix = self
+            .op
+            .read_with(&self.path)
+            .range(file_size - suffix_len..file_size)
+            .size_hint(file_size)
+            .await
+            .map_err(format_parquet_error)?;
+        if suffix.len() < FOOTER_SIZE {
+            return Err(ParquetError::EOF(format!(
+                "read {} bytes of footer, expect {}",
+                suffix.len(),
+                FOOTER_SIZE
+            )));
+        }
+
+        let mut footer = [0; FOOTER_SIZE];
+        footer.copy_from_slice(&suffix[suffix.len() - FOOTER_SIZE..]);
+        let metadata_len = decode_footer(&footer)?;
+        let footer_len = (metadata_len + FOOTER_SIZE) as u64;
+        if footer_len > file_size {
+            return Err(ParquetError::EOF(format!(
+                "file size of {} is less than footer + metadata {}",
+                file_size, footer_len
+            )));
+        }
+
+        // Decode from the suffix directly if metadata has been fetched.
+        if footer_len <= suffix.len() as u64 {
+            let start = suffix.len() - footer_len as usize;
+            return decode_metadata(&suffix[start..suffix.len() - FOOTER_SIZE]);
+        }
+
+        let bs = self
+            .op
+            .read_with(&self.path)
+            .range(file_size - footer_len..file_size - FOOTER_SIZE as u64)
+            .size_hint(file_size)
+            .await
+            .map_err(format_parquet_error)?;
+        decode_metadata(&bs)
+    }
+}
+
+impl AsyncFileReader for OpendalAsyncReader {
+    fn get_bytes(&mut self, range: Range<usize>) -> BoxFuture<'_, Result<Bytes>> {
+        async move {
+            let bs = self
+                .op
+                .range_read(&self.path, range.start as u64..range.end as u64)
+                .await
+                .map_err(format_parquet_error)?;
+
+            Ok(Bytes::from(bs))
+        }
+        .boxed()
+    }
+
+    fn get_byte_ranges(&mut self, ranges: Vec<Range<usize>>) -> BoxFuture<'_, Result<Vec<Bytes>>> {
+        async move {
+            let ranges = ranges
+                .into_iter()
+                .map(|r| r.start as u64..r.end as u64)
+                .collect::<Vec<_>>();
+
+            self.op
+                .read_ranges(&self.path, &ranges)
+                .await
+                .map_err(format_parquet_error)
+        }
+        .boxed()
+    }
+
+    fn get_metadata(&mut self) -> BoxFuture<'_, Result<Arc<ParquetMetaData>>> {
+        async move {
+            if let Some(metadata) = &self.metadata {
+                return Ok(metadata.clone());
+            }
+            if let Some(metadata) = self.cache.as_ref().and_then(|c| c.get(&self.path)) {
+                self.metadata = Some(metadata.clone());
+                return Ok(metadata);
+            }
+
+            let metadata = Arc::new(self.fetch_metadata().await?);
+            if let Some(cache) = &self.cache {
+                cache.insert(&self.path, metadata.clone());
+            }
+            self.metadata = Some(metadata.clone());
+            Ok(metadata)
+        }
+        .boxed()
+    }
+}
+
+/// MetadataCache caches decoded parquet metadata by path, which can be
+/// shared between readers to avoid fetching the same footer again.
+///
+/// # Notes
+///
+/// Cached metadata will never be invalidated, please only use it for files
+/// that won't be overwritten.
+#[derive(Debug, Clone, Default)]
+pub struct MetadataCache {
+    inner: Arc<Mutex<HashMap<String, Arc<ParquetMetaData>>>>,
+}
+
+impl MetadataCache {
+    /// Create a new empty MetadataCache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn get(&self, path: &str) -> Option<Arc<ParquetMetaData>> {
+        self.inner
+            .lock()
+            .expect("lock must succeed")
+            .get(path)
+            .cloned()
+    }
+
+    fn insert(&self, path: &str, metadata: Arc<ParquetMetaData>) {
+        self.inner
+            .lock()
+            .expect("lock must succeed")
+            .insert(path.to_string(), metadata);
+    }
+}
+
+fn format_parquet_error(err: opendal::Error) -> ParquetError {
+    ParquetError::External(Box::new(err))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::ArrayRef;
+    use arrow_array::Int64Array;
+    use arrow_array::RecordBatch;
+    use futures::TryStreamExt;
+    use opendal::services;
+    use parquet::arrow::ArrowWriter;
+    use parquet::arrow::ParquetRecordBatchStreamBuilder;
+
+    use super::*;
+
+    async fn create_test_file(op: &Operator, path: &str) -> RecordBatch {
+        let col = Arc::new(Int64Array::from_iter_values(0..1024)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("col", col)]).unwrap();
+
+        let mut buf = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        op.write(path, buf).await.unwrap();
+        batch
+    }
+
+    #[tokio::test]
+    async fn test_read_parquet() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let batch = create_test_file(&op, "data/test.parquet").await;
+
+        for hint in [FOOTER_SIZE, 64 * 1024] {
+            let r = OpendalAsyncReader::new(op.clone(), "data/test.parquet")
+                .with_footer_size_hint(hint);
+            let stream = ParquetRecordBatchStreamBuilder::new(r)
+                .await
+                .unwrap()
+                .build()
+                .unwrap();
+            let batches = stream.try_collect::<Vec<_>>().await.unwrap();
+
+            assert_eq!(batches, vec![batch.clone()]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metadata_cache() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        create_test_file(&op, "data/test.parquet").await;
+        let cache = MetadataCache::new();
+
+        let mut r = OpendalAsyncReader::new(op.clone(), "data/test.parquet")
+            .with_metadata_cache(cache.clone());
+        let expected = r.get_metadata().await.unwrap();
+        assert!(cache.get("data/test.parquet").is_some());
+
+        // Reader should hit cache without touching the file.
+        op.delete("data/test.parquet").await.unwrap();
+        let mut r = OpendalAsyncReader::new(op, "data/test.parquet").with_metadata_cache(cache);
+        let actual = r.get_metadata().await.unwrap();
+        assert!(Arc::ptr_eq(&expected, &actual));
+    }
+}
@@ -124,8 +124,25 @@ use crate::*;
 /// the operation against capability of the underlying service. If the
 /// operation is not supported, an error will be returned directly.
 ///
+/// ## Buffer Pool
+///
+/// Buffers used to make readers streamable are taken from the given
+/// [`BufferPool`] and will be returned back after readers dropped. The pool
+/// is also passed to writers via [`OpWrite::with_buffer_pool`] so that
+/// services could reuse the buffers of multipart uploads.
+///
 /// [`AccessorHint`]: crate::raw::AccessorHint
-pub struct CompleteLayer;
+#[derive(Default)]
+pub struct CompleteLayer {
+    pool: BufferPool,
+}
+
+impl CompleteLayer {
+    /// Create a new CompleteLayer with given buffer pool.
+    pub fn new(pool: BufferPool) -> Self {
+        Self { pool }
+    }
+}
 
 impl<A: Accessor> Layer<A> for CompleteLayer {
     type LayeredAccessor = CompleteReaderAccessor<A>;
@@ -135,6 +152,7 @@ impl<A: Accessor> Layer<A> for CompleteLayer {
         CompleteReaderAccessor {
             meta,
             inner: Arc::new(inner),
+            pool: self.pool.clone(),
         }
     }
 }
@@ -143,6 +161,7 @@ impl<A: Accessor> Layer<A> for CompleteLayer {
 pub struct CompleteReaderAccessor<A: Accessor> {
     meta: AccessorInfo,
     inner: Arc<A>,
+    pool: BufferPool,
 }
 
 impl<A: Accessor> Debug for CompleteReaderAccessor<A> {
@@ -166,13 +185,15 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
         let streamable = capability.read_can_next;
 
         let range = args.range();
+        let size_hint = args.size_hint();
+        let if_match = args.if_match().map(|v| v.to_string());
         let (rp, r) = self.inner.read(path, args).await?;
         let content_length = rp.metadata().content_length();
 
         match (seekable, streamable) {
             (true, true) => Ok((rp, CompleteReader::AlreadyComplete(r))),
             (true, false) => {
-                let r = oio::into_streamable_reader(r, 256 * 1024);
+                let r = oio::into_streamable_reader_with_pool(r, &self.pool);
                 Ok((rp, CompleteReader::NeedStreamable(r)))
             }
             _ => {
@@ -180,10 +201,27 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
                     (Some(offset), _) => (offset, content_length),
                     (None, None) => (0, content_length),
                     (None, Some(size)) => {
-                        // TODO: we can read content range to calculate
-                        // the total content length.
-                        let om = self.inner.stat(path, OpStat::new()).await?.into_metadata();
-                        let total_size = om.content_length();
+                        // Try the size hint first, then the total size in
+                        // the content range of response, only fallback to
+                        // stat if both of them are unknown.
+                        let meta = rp.metadata();
+                        let total_size = match size_hint.or_else(|| {
+                            if meta.bit().contains(Metakey::ContentRange)
+                                || meta.bit().contains(Metakey::Complete)
+                            {
+                                meta.content_range().and_then(|v| v.size())
+                            } else {
+                                None
+                            }
+                        }) {
+                            Some(v) => v,
+                            None => self
+                                .inner
+                                .stat(path, OpStat::new())
+                                .await?
+                                .into_metadata()
+                                .content_length(),
+                        };
                         let (offset, size) = if size > total_size {
                             (0, total_size)
                         } else {
@@ -193,12 +231,26 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
                         (offset, size)
                     }
                 };
-                let r = oio::into_reader::by_range(self.inner.clone(), path, r, offset, size);
+                // Pin the version of following range requests so that
+                // resumed reads won't mix content of different versions.
+                let meta = rp.metadata();
+                let if_match = if_match.or_else(|| {
+                    if meta.bit().contains(Metakey::Etag) || meta.bit().contains(Metakey::Complete)
+                    {
+                        meta.etag().map(|v| v.to_string())
+                    } else {
+                        None
+                    }
+                });
+                let mut r = oio::into_reader::by_range(self.inner.clone(), path, r, offset, size);
+                if let (true, Some(etag)) = (capability.read_with_if_match, if_match) {
+                    r = r.with_if_match(&etag);
+                }
 
                 if streamable {
                     Ok((rp, CompleteReader::NeedSeekable(r)))
                 } else {
-                    let r = oio::into_streamable_reader(r, 256 * 1024);
+                    let r = oio::into_streamable_reader_with_pool(r, &self.pool);
                     Ok((rp, CompleteReader::NeedBoth(r)))
                 }
             }
@@ -223,7 +275,7 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
         match (seekable, streamable) {
             (true, true) => Ok((rp, CompleteReader::AlreadyComplete(r))),
             (true, false) => {
-                let r = oio::into_streamable_reader(r, 256 * 1024);
+                let r = oio::into_streamable_reader_with_pool(r, &self.pool);
                 Ok((rp, CompleteReader::NeedStreamable(r)))
             }
             (false, _) => Err(Error::new(
@@ -391,6 +443,7 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         }
 
         let size = args.content_length();
+        let args = args.with_buffer_pool(self.pool.clone());
         self.inner
             .write(path, args)
             .await
@@ -444,6 +497,7 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         if !capability.delete {
             return new_capability_unsupported_error(Operation::Delete);
         }
+        check_delete_args(capability, &args)?;
 
         self.inner().delete(path, args).await
     }
@@ -453,6 +507,7 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         if !capability.delete || !capability.blocking {
             return new_capability_unsupported_error(Operation::BlockingDelete);
         }
+        check_delete_args(capability, &args)?;
 
         self.inner().blocking_delete(path, args)
     }
@@ -462,6 +517,7 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         if !capability.copy {
             return new_capability_unsupported_error(Operation::Copy);
         }
+        check_copy_args(capability, &args)?;
 
         self.inner().copy(from, to, args).await
     }
@@ -471,6 +527,7 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         if !capability.copy || !capability.blocking {
             return new_capability_unsupported_error(Operation::BlockingCopy);
         }
+        check_copy_args(capability, &args)?;
 
         self.inner().blocking_copy(from, to, args)
     }
@@ -528,6 +585,60 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
 
         self.inner().batch(args).await
     }
+
+    async fn restore(&self, path: &str, args: OpRestore) -> Result<RpRestore> {
+        let capability = self.meta.capability();
+        if !capability.restore {
+            return new_capability_unsupported_error(Operation::Restore);
+        }
+
+        self.inner.restore(path, args).await
+    }
+
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        let capability = self.meta.capability();
+        if !capability.select {
+            return new_capability_unsupported_error(Operation::Select);
+        }
+
+        self.inner.select(path, args).await
+    }
+
+    async fn write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        let capability = self.meta.capability();
+        if !capability.write_at {
+            return new_capability_unsupported_error(Operation::WriteAt);
+        }
+
+        self.inner.write_at(path, args, bs).await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let capability = self.meta.capability();
+        if !capability.truncate {
+            return new_capability_unsupported_error(Operation::Truncate);
+        }
+
+        self.inner.truncate(path, args).await
+    }
+
+    fn blocking_write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        let capability = self.meta.capability();
+        if !capability.write_at || !capability.blocking {
+            return new_capability_unsupported_error(Operation::BlockingWriteAt);
+        }
+
+        self.inner.blocking_write_at(path, args, bs)
+    }
+
+    fn blocking_truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let capability = self.meta.capability();
+        if !capability.truncate || !capability.blocking {
+            return new_capability_unsupported_error(Operation::BlockingTruncate);
+        }
+
+        self.inner.blocking_truncate(path, args)
+    }
 }
 
 pub enum CompleteReader<A: Accessor, R> {
@@ -866,6 +977,42 @@ fn new_capability_unsupported_error<R>(operation: Operation) -> Result<R> {
     Err(Error::new(ErrorKind::Unsupported, "operation is not supported").with_operation(operation))
 }
 
+fn new_capability_unsupported_args_error<R>(operation: Operation, arg: &'static str) -> Result<R> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "operation with this argument is not supported",
+    )
+    .with_operation(operation)
+    .with_context("argument", arg))
+}
+
+/// Make sure the metadata overrides of copy will not be dropped silently.
+fn check_copy_args(capability: Capability, args: &OpCopy) -> Result<()> {
+    if args.content_type().is_some() && !capability.copy_with_content_type {
+        return new_capability_unsupported_args_error(Operation::Copy, "content_type");
+    }
+    if args.user_metadata().is_some() && !capability.copy_with_user_metadata {
+        return new_capability_unsupported_args_error(Operation::Copy, "user_metadata");
+    }
+    if args.tags().is_some() && !capability.copy_with_tags {
+        return new_capability_unsupported_args_error(Operation::Copy, "tags");
+    }
+
+    Ok(())
+}
+
+/// Make sure the conditions of delete will not be ignored silently.
+fn check_delete_args(capability: Capability, args: &OpDelete) -> Result<()> {
+    if args.if_match().is_some() && !capability.delete_with_if_match {
+        return new_capability_unsupported_args_error(Operation::Delete, "if_match");
+    }
+    if args.version().is_some() && !capability.delete_with_version {
+        return new_capability_unsupported_args_error(Operation::Delete, "version");
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -119,6 +119,8 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let size = args.content_length();
+
         self.inner
             .write(path, args)
             .map_ok(|(rp, os)| {
@@ -132,9 +134,14 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
                 )
             })
             .map_err(|err| {
-                err.with_operation(Operation::Write)
+                let err = err
+                    .with_operation(Operation::Write)
                     .with_context("service", self.meta.scheme())
-                    .with_context("path", path)
+                    .with_context("path", path);
+                match size {
+                    Some(size) => err.with_context("size", size.to_string()),
+                    None => err,
+                }
             })
             .await
     }
@@ -235,6 +242,42 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
         })
     }
 
+    async fn restore(&self, path: &str, args: OpRestore) -> Result<RpRestore> {
+        self.inner.restore(path, args).await.map_err(|err| {
+            err.with_operation(Operation::Restore)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        self.inner.select(path, args).await.map_err(|err| {
+            err.with_operation(Operation::Select)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        let offset = args.offset();
+        self.inner.write_at(path, args, bs).await.map_err(|err| {
+            err.with_operation(Operation::WriteAt)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+                .with_context("offset", offset.to_string())
+        })
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let size = args.size();
+        self.inner.truncate(path, args).await.map_err(|err| {
+            err.with_operation(Operation::Truncate)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+                .with_context("size", size.to_string())
+        })
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         self.inner
             .batch(args)
@@ -270,6 +313,8 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let br = args.range();
+
         self.inner
             .blocking_read(path, args)
             .map(|(rp, os)| {
@@ -286,10 +331,13 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
                 err.with_operation(Operation::BlockingRead)
                     .with_context("service", self.meta.scheme())
                     .with_context("path", path)
+                    .with_context("range", br.to_string())
             })
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let size = args.content_length();
+
         self.inner
             .blocking_write(path, args)
             .map(|(rp, os)| {
@@ -303,9 +351,14 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
                 )
             })
             .map_err(|err| {
-                err.with_operation(Operation::BlockingWrite)
+                let err = err
+                    .with_operation(Operation::BlockingWrite)
                     .with_context("service", self.meta.scheme())
-                    .with_context("path", path)
+                    .with_context("path", path);
+                match size {
+                    Some(size) => err.with_context("size", size.to_string()),
+                    None => err,
+                }
             })
     }
 
@@ -343,6 +396,26 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
         })
     }
 
+    fn blocking_write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        let offset = args.offset();
+        self.inner.blocking_write_at(path, args, bs).map_err(|err| {
+            err.with_operation(Operation::BlockingWriteAt)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+                .with_context("offset", offset.to_string())
+        })
+    }
+
+    fn blocking_truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let size = args.size();
+        self.inner.blocking_truncate(path, args).map_err(|err| {
+            err.with_operation(Operation::BlockingTruncate)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+                .with_context("size", size.to_string())
+        })
+    }
+
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
         self.inner
             .blocking_list(path, args)
@@ -427,10 +500,13 @@ impl<T: oio::BlockingRead> oio::BlockingRead for ErrorContextWrapper<T> {
 #[async_trait::async_trait]
 impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
     async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len();
+
         self.inner.write(bs).await.map_err(|err| {
             err.with_operation(WriteOperation::Write)
                 .with_context("service", self.scheme)
                 .with_context("path", &self.path)
+                .with_context("size", size.to_string())
         })
     }
 
@@ -447,6 +523,7 @@ impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
             err.with_operation(WriteOperation::Sink)
                 .with_context("service", self.scheme)
                 .with_context("path", &self.path)
+                .with_context("size", size.to_string())
         })
     }
 
@@ -461,10 +538,13 @@ impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
 
 impl<T: oio::BlockingWrite> oio::BlockingWrite for ErrorContextWrapper<T> {
     fn write(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len();
+
         self.inner.write(bs).map_err(|err| {
             err.with_operation(WriteOperation::BlockingWrite)
                 .with_context("service", self.scheme)
                 .with_context("path", &self.path)
+                .with_context("size", size.to_string())
         })
     }
 
@@ -480,10 +560,13 @@ impl<T: oio::BlockingWrite> oio::BlockingWrite for ErrorContextWrapper<T> {
 #[async_trait::async_trait]
 impl<T: oio::Append> oio::Append for ErrorContextWrapper<T> {
     async fn append(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len();
+
         self.inner.append(bs).await.map_err(|err| {
             err.with_operation(AppendOperation::Append)
                 .with_context("service", self.scheme)
                 .with_context("path", &self.path)
+                .with_context("size", size.to_string())
         })
     }
 
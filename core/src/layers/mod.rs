@@ -17,7 +17,9 @@
 
 //! `Layer` is the mechanism to intercept operations.
 
+#[cfg(feature = "tokio")]
 mod concurrent_limit;
+#[cfg(feature = "tokio")]
 pub use concurrent_limit::ConcurrentLimitLayer;
 
 mod immutable_index;
@@ -66,6 +68,9 @@ pub(crate) use error_context::ErrorContextLayer;
 mod complete;
 pub(crate) use complete::CompleteLayer;
 
+mod stats;
+pub(crate) use stats::StatsLayer;
+
 #[cfg(feature = "layers-madsim")]
 mod madsim;
 
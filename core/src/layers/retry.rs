@@ -17,6 +17,7 @@
 
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::future::Future;
 use std::io;
 use std::pin::Pin;
 use std::task::ready;
@@ -25,11 +26,6 @@ use std::task::Poll;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use backon::BackoffBuilder;
-use backon::BlockingRetryable;
-use backon::ExponentialBackoff;
-use backon::ExponentialBuilder;
-use backon::Retryable;
 use bytes::Bytes;
 use futures::FutureExt;
 use log::warn;
@@ -51,6 +47,11 @@ use crate::*;
 ///
 /// `write` and `blocking_write` don't support retry so far, visit [this issue](https://github.com/apache/incubator-opendal/issues/1223) for more details.
 ///
+/// Readers of services that can't seek will resume from the already read
+/// offset via a new range request while retrying, and the request will
+/// carry `If-Match` if the service supports it so that content of different
+/// versions won't be mixed.
+///
 /// # Examples
 ///
 /// ```
@@ -146,6 +147,58 @@ pub struct RetryAccessor<A: Accessor> {
     builder: ExponentialBuilder,
 }
 
+impl<A: Accessor> RetryAccessor<A> {
+    /// Retry the future returned by `f` while it fails with temporary errors.
+    async fn retry<T, F, Fut>(&self, op: Operation, mut f: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut backoff = self.builder.build();
+
+        loop {
+            match f().await {
+                Ok(v) => return Ok(v),
+                Err(e) if !e.is_temporary() => return Err(e.set_persistent()),
+                Err(e) => match backoff.next() {
+                    None => return Err(e.set_persistent()),
+                    Some(dur) => {
+                        warn!(
+                            target: "opendal::service",
+                            "operation={} -> retry after {}s: error={:?}",
+                            op, dur.as_secs_f64(), e);
+                        sleep(dur).await;
+                        continue;
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Call `f` until it succeeds or fails with a non-temporary error, `notify`
+/// will be called before every retry.
+fn blocking_retry<T>(
+    builder: &ExponentialBuilder,
+    mut f: impl FnMut() -> Result<T>,
+    notify: impl Fn(&Error, Duration),
+) -> Result<T> {
+    let mut backoff = builder.build();
+
+    loop {
+        match f() {
+            Err(e) if e.is_temporary() => match backoff.next() {
+                None => return Err(e),
+                Some(dur) => {
+                    notify(&e, dur);
+                    std::thread::sleep(dur);
+                }
+            },
+            v => return v,
+        }
+    }
+}
+
 impl<A: Accessor> Debug for RetryAccessor<A> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RetryAccessor")
@@ -170,261 +223,165 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        { || self.inner.create_dir(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::CreateDir, dur.as_secs_f64(), err)
-            })
-            .map(|v| v.map_err(|e| e.set_persistent()))
-            .await
+        self.retry(Operation::CreateDir, || {
+            self.inner.create_dir(path, args.clone())
+        })
+        .await
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        { || self.inner.read(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Read, dur.as_secs_f64(), err)
-            })
-            .map(|v| {
-                v.map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
-                    .map_err(|e| e.set_persistent())
-            })
+        self.retry(Operation::Read, || self.inner.read(path, args.clone()))
             .await
+            .map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
     }
 
     /// Return `Interrupted` Error even after retry.
     ///
     /// Allowing users to retry the write request from upper logic.
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        { || self.inner.write(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Write, dur.as_secs_f64(), err)
-            })
-            .map(|v| {
-                v.map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
-                    .map_err(|e| e.set_persistent())
-            })
+        self.retry(Operation::Write, || self.inner.write(path, args.clone()))
             .await
+            .map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
     }
 
     async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
-        { || self.inner.append(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Append, dur.as_secs_f64(), err)
-            })
-            .map(|v| {
-                v.map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
-                    .map_err(|e| e.set_persistent())
-            })
+        self.retry(Operation::Append, || self.inner.append(path, args.clone()))
             .await
+            .map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        { || self.inner.stat(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Stat, dur.as_secs_f64(), err)
-            })
-            .map(|v| v.map_err(|e| e.set_persistent()))
+        self.retry(Operation::Stat, || self.inner.stat(path, args.clone()))
             .await
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        { || self.inner.delete(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Delete, dur.as_secs_f64(), err)
-            })
-            .map(|v| v.map_err(|e| e.set_persistent()))
+        self.retry(Operation::Delete, || self.inner.delete(path, args.clone()))
             .await
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        { || self.inner.copy(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Copy, dur.as_secs_f64(), err)
-            })
-            .map(|v| v.map_err(|e| e.set_persistent()))
+        self.retry(Operation::Copy, || self.inner.copy(from, to, args.clone()))
             .await
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        { || self.inner.rename(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Rename, dur.as_secs_f64(), err)
-            })
-            .map(|v| v.map_err(|e| e.set_persistent()))
-            .await
+        self.retry(Operation::Rename, || {
+            self.inner.rename(from, to, args.clone())
+        })
+        .await
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
-        { || self.inner.list(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::List, dur.as_secs_f64(), err)
-            })
-            .map(|v| {
-                v.map(|(l, p)| {
-                    let pager = RetryWrapper::new(p, path, self.builder.clone());
-                    (l, pager)
-                })
-                .map_err(|e| e.set_persistent())
-            })
+        self.retry(Operation::List, || self.inner.list(path, args.clone()))
             .await
+            .map(|(l, p)| {
+                let pager = RetryWrapper::new(p, path, self.builder.clone());
+                (l, pager)
+            })
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        {
-            || async {
-                let rp = self.inner.batch(args.clone()).await?;
-                let mut nrp = Vec::with_capacity(rp.results().len());
-                for (path, result) in rp.into_results() {
-                    let result = result?;
-                    nrp.push((path, Ok(result)))
-                }
-                Ok(RpBatch::new(nrp))
+        self.retry(Operation::Batch, || async {
+            let rp = self.inner.batch(args.clone()).await?;
+            let mut nrp = Vec::with_capacity(rp.results().len());
+            for (path, result) in rp.into_results() {
+                let result = result?;
+                nrp.push((path, Ok(result)))
             }
-        }
-        .retry(&self.builder)
-        .when(|e: &Error| e.is_temporary())
-        .notify(|err, dur| {
-            warn!(
-                target: "opendal::service",
-                "operation={} -> retry after {}s: error={:?}",
-                Operation::Batch, dur.as_secs_f64(), err)
+            Ok(RpBatch::new(nrp))
         })
         .await
-        .map_err(|e| e.set_persistent())
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        { || self.inner.blocking_create_dir(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.blocking_create_dir(path, args.clone()),
+            |err, dur| {
                 warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::BlockingCreateDir, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map_err(|e| e.set_persistent())
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        { || self.inner.blocking_read(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.blocking_read(path, args.clone()),
+            |err, dur| {
                 warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::BlockingRead, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
+        .map_err(|e| e.set_persistent())
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        { || self.inner.blocking_write(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.blocking_write(path, args.clone()),
+            |err, dur| {
                 warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::BlockingWrite, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
+        .map_err(|e| e.set_persistent())
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        { || self.inner.blocking_stat(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.blocking_stat(path, args.clone()),
+            |err, dur| {
                 warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::BlockingStat, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map_err(|e| e.set_persistent())
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        { || self.inner.blocking_delete(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.blocking_delete(path, args.clone()),
+            |err, dur| {
                 warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::BlockingDelete, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map_err(|e| e.set_persistent())
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
-        { || self.inner.blocking_list(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.blocking_list(path, args.clone()),
+            |err, dur| {
                 warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::BlockingList, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map(|(rp, p)| {
-                let p = RetryWrapper::new(p, path, self.builder.clone());
-                (rp, p)
-            })
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map(|(rp, p)| {
+            let p = RetryWrapper::new(p, path, self.builder.clone());
+            (rp, p)
+        })
+        .map_err(|e| e.set_persistent())
     }
 }
 
@@ -433,7 +390,7 @@ pub struct RetryWrapper<R> {
     path: String,
     builder: ExponentialBuilder,
     current_backoff: Option<ExponentialBackoff>,
-    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    sleep: Option<Pin<Box<Sleep>>>,
 }
 
 impl<R> RetryWrapper<R> {
@@ -483,7 +440,7 @@ impl<R: oio::Read> oio::Read for RetryWrapper<R> {
                             target: "opendal::service",
                             "operation={} path={} -> retry after {}s: error={:?}",
                             ReadOperation::Read, self.path, dur.as_secs_f64(), err);
-                        self.sleep = Some(Box::pin(tokio::time::sleep(dur)));
+                        self.sleep = Some(Box::pin(sleep(dur)));
                         self.poll_read(cx, buf)
                     }
                 }
@@ -525,7 +482,7 @@ impl<R: oio::Read> oio::Read for RetryWrapper<R> {
                             target: "opendal::service",
                             "operation={} path={} -> retry after {}s: error={:?}",
                              ReadOperation::Seek, self.path, dur.as_secs_f64(), err);
-                        self.sleep = Some(Box::pin(tokio::time::sleep(dur)));
+                        self.sleep = Some(Box::pin(sleep(dur)));
                         self.poll_seek(cx, pos)
                     }
                 }
@@ -571,7 +528,7 @@ impl<R: oio::Read> oio::Read for RetryWrapper<R> {
                             target: "opendal::service",
                             "operation={} path={} -> retry after {}s: error={:?}",
                             ReadOperation::Next, self.path, dur.as_secs_f64(), err);
-                        self.sleep = Some(Box::pin(tokio::time::sleep(dur)));
+                        self.sleep = Some(Box::pin(sleep(dur)));
                         self.poll_next(cx)
                     }
                 }
@@ -582,46 +539,46 @@ impl<R: oio::Read> oio::Read for RetryWrapper<R> {
 
 impl<R: oio::BlockingRead> oio::BlockingRead for RetryWrapper<R> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        { || self.inner.read(buf) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(move |err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.read(buf),
+            |err, dur| {
                 warn!(
                 target: "opendal::service",
                 "operation={} -> pager retry after {}s: error={:?}",
                 ReadOperation::BlockingRead, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map_err(|e| e.set_persistent())
     }
 
     fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
-        { || self.inner.seek(pos) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(move |err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.seek(pos),
+            |err, dur| {
                 warn!(
                 target: "opendal::service",
                 "operation={} -> pager retry after {}s: error={:?}",
                 ReadOperation::BlockingSeek, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map_err(|e| e.set_persistent())
     }
 
     fn next(&mut self) -> Option<Result<Bytes>> {
-        { || self.inner.next().transpose() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(move |err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.next().transpose(),
+            |err, dur| {
                 warn!(
                 target: "opendal::service",
                 "operation={} -> pager retry after {}s: error={:?}",
                 ReadOperation::BlockingNext, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map_err(|e| e.set_persistent())
-            .transpose()
+            },
+        )
+        .map_err(|e| e.set_persistent())
+        .transpose()
     }
 }
 
@@ -640,7 +597,7 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
                         warn!(target: "opendal::service",
                               "operation={} path={} -> pager retry after {}s: error={:?}",
                               WriteOperation::Write, self.path, dur.as_secs_f64(), e);
-                        tokio::time::sleep(dur).await;
+                        sleep(dur).await;
                         continue;
                     }
                 },
@@ -666,7 +623,7 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
                         warn!(target: "opendal::service",
                               "operation={} path={} -> pager retry after {}s: error={:?}",
                               WriteOperation::Abort, self.path, dur.as_secs_f64(), e);
-                        tokio::time::sleep(dur).await;
+                        sleep(dur).await;
                         continue;
                     }
                 },
@@ -687,7 +644,7 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
                         warn!(target: "opendal::service",
                               "operation={} path={} -> pager retry after {}s: error={:?}",
                               WriteOperation::Close, self.path, dur.as_secs_f64(), e);
-                        tokio::time::sleep(dur).await;
+                        sleep(dur).await;
                         continue;
                     }
                 },
@@ -698,31 +655,31 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for RetryWrapper<R> {
     fn write(&mut self, bs: Bytes) -> Result<()> {
-        { || self.inner.write(bs.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(move |err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.write(bs.clone()),
+            |err, dur| {
                 warn!(
                 target: "opendal::service",
                 "operation={} -> pager retry after {}s: error={:?}",
                 WriteOperation::BlockingWrite, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map_err(|e| e.set_persistent())
     }
 
     fn close(&mut self) -> Result<()> {
-        { || self.inner.close() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(move |err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.close(),
+            |err, dur| {
                 warn!(
                 target: "opendal::service",
                 "operation={} -> pager retry after {}s: error={:?}",
                WriteOperation::BlockingClose, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map_err(|e| e.set_persistent())
     }
 }
 
@@ -741,7 +698,7 @@ impl<A: oio::Append> oio::Append for RetryWrapper<A> {
                         warn!(target: "opendal::service",
                               "operation={} path={} -> appender retry after {}s: error={:?}",
                               AppendOperation::Append, self.path, dur.as_secs_f64(), e);
-                        tokio::time::sleep(dur).await;
+                        sleep(dur).await;
                         continue;
                     }
                 },
@@ -762,7 +719,7 @@ impl<A: oio::Append> oio::Append for RetryWrapper<A> {
                         warn!(target: "opendal::service",
                               "operation={} path={} -> appender retry after {}s: error={:?}",
                               AppendOperation::Close, self.path, dur.as_secs_f64(), e);
-                        tokio::time::sleep(dur).await;
+                        sleep(dur).await;
                         continue;
                     }
                 },
@@ -786,7 +743,7 @@ impl<P: oio::Page> oio::Page for RetryWrapper<P> {
                         warn!(target: "opendal::service",
                               "operation={} path={} -> pager retry after {}s: error={:?}",
                               PageOperation::Next, self.path, dur.as_secs_f64(), e);
-                        tokio::time::sleep(dur).await;
+                        sleep(dur).await;
                         continue;
                     }
                 },
@@ -797,17 +754,17 @@ impl<P: oio::Page> oio::Page for RetryWrapper<P> {
 
 impl<P: oio::BlockingPage> oio::BlockingPage for RetryWrapper<P> {
     fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
-        { || self.inner.next() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(move |err, dur| {
+        blocking_retry(
+            &self.builder,
+            || self.inner.next(),
+            |err, dur| {
                 warn!(
                 target: "opendal::service",
                 "operation={} -> pager retry after {}s: error={:?}",
                 PageOperation::BlockingNext, dur.as_secs_f64(), err)
-            })
-            .call()
-            .map_err(|e| e.set_persistent())
+            },
+        )
+        .map_err(|e| e.set_persistent())
     }
 }
 
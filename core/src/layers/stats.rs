@@ -0,0 +1,409 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+/// StatsLayer will maintain the counters behind [`Operator::stats`].
+///
+/// # Notes
+///
+/// This layer is attached by [`OperatorBuilder`] and users can't add it.
+pub struct StatsLayer {
+    stats: Arc<StatsCounters>,
+}
+
+impl StatsLayer {
+    pub fn new(stats: Arc<StatsCounters>) -> Self {
+        Self { stats }
+    }
+}
+
+impl<A: Accessor> Layer<A> for StatsLayer {
+    type LayeredAccessor = StatsAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        StatsAccessor {
+            inner,
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+/// Provide statistics wrapper for backend.
+pub struct StatsAccessor<A: Accessor> {
+    inner: A,
+    stats: Arc<StatsCounters>,
+}
+
+impl<A: Accessor> Debug for StatsAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for StatsAccessor<A> {
+    type Inner = A;
+    type Reader = StatsWrapper<A::Reader>;
+    type BlockingReader = StatsWrapper<A::BlockingReader>;
+    type Writer = StatsWrapper<A::Writer>;
+    type BlockingWriter = StatsWrapper<A::BlockingWriter>;
+    type Appender = StatsWrapper<A::Appender>;
+    type Pager = StatsWrapper<A::Pager>;
+    type BlockingPager = StatsWrapper<A::BlockingPager>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let _guard = self.stats.start(Operation::CreateDir);
+        self.stats.record(self.inner.create_dir(path, args).await)
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let _guard = self.stats.start(Operation::Read);
+        self.stats
+            .record(self.inner.read(path, args).await)
+            .map(|(rp, r)| (rp, StatsWrapper::new(r, self.stats.clone())))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let _guard = self.stats.start(Operation::Write);
+        self.stats
+            .record(self.inner.write(path, args).await)
+            .map(|(rp, w)| (rp, StatsWrapper::new(w, self.stats.clone())))
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        let _guard = self.stats.start(Operation::Append);
+        self.stats
+            .record(self.inner.append(path, args).await)
+            .map(|(rp, a)| (rp, StatsWrapper::new(a, self.stats.clone())))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let _guard = self.stats.start(Operation::Copy);
+        self.stats.record(self.inner.copy(from, to, args).await)
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let _guard = self.stats.start(Operation::Rename);
+        self.stats.record(self.inner.rename(from, to, args).await)
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let _guard = self.stats.start(Operation::Stat);
+        self.stats.record(self.inner.stat(path, args).await)
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let _guard = self.stats.start(Operation::Delete);
+        self.stats.record(self.inner.delete(path, args).await)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let _guard = self.stats.start(Operation::List);
+        self.stats
+            .record(self.inner.list(path, args).await)
+            .map(|(rp, p)| (rp, StatsWrapper::new(p, self.stats.clone())))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let _guard = self.stats.start(Operation::Batch);
+        self.stats.record(self.inner.batch(args).await)
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let _guard = self.stats.start(Operation::Presign);
+        self.stats.record(self.inner.presign(path, args).await)
+    }
+
+    async fn restore(&self, path: &str, args: OpRestore) -> Result<RpRestore> {
+        let _guard = self.stats.start(Operation::Restore);
+        self.stats.record(self.inner.restore(path, args).await)
+    }
+
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        let _guard = self.stats.start(Operation::Select);
+        self.stats
+            .record(self.inner.select(path, args).await)
+            .map(|(rp, r)| {
+                let r: oio::Reader = Box::new(StatsWrapper::new(r, self.stats.clone()));
+                (rp, r)
+            })
+    }
+
+    async fn write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        let _guard = self.stats.start(Operation::WriteAt);
+        let size = bs.len() as u64;
+
+        let rp = self
+            .stats
+            .record(self.inner.write_at(path, args, bs).await)?;
+        self.stats.add_bytes_written(size);
+        Ok(rp)
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let _guard = self.stats.start(Operation::Truncate);
+        self.stats.record(self.inner.truncate(path, args).await)
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let _guard = self.stats.start(Operation::BlockingCreateDir);
+        self.stats
+            .record(self.inner.blocking_create_dir(path, args))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let _guard = self.stats.start(Operation::BlockingRead);
+        self.stats
+            .record(self.inner.blocking_read(path, args))
+            .map(|(rp, r)| (rp, StatsWrapper::new(r, self.stats.clone())))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let _guard = self.stats.start(Operation::BlockingWrite);
+        self.stats
+            .record(self.inner.blocking_write(path, args))
+            .map(|(rp, w)| (rp, StatsWrapper::new(w, self.stats.clone())))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let _guard = self.stats.start(Operation::BlockingCopy);
+        self.stats.record(self.inner.blocking_copy(from, to, args))
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let _guard = self.stats.start(Operation::BlockingRename);
+        self.stats
+            .record(self.inner.blocking_rename(from, to, args))
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let _guard = self.stats.start(Operation::BlockingStat);
+        self.stats.record(self.inner.blocking_stat(path, args))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let _guard = self.stats.start(Operation::BlockingDelete);
+        self.stats.record(self.inner.blocking_delete(path, args))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        let _guard = self.stats.start(Operation::BlockingList);
+        self.stats
+            .record(self.inner.blocking_list(path, args))
+            .map(|(rp, p)| (rp, StatsWrapper::new(p, self.stats.clone())))
+    }
+
+    fn blocking_write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        let _guard = self.stats.start(Operation::BlockingWriteAt);
+        let size = bs.len() as u64;
+
+        let rp = self
+            .stats
+            .record(self.inner.blocking_write_at(path, args, bs))?;
+        self.stats.add_bytes_written(size);
+        Ok(rp)
+    }
+
+    fn blocking_truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let _guard = self.stats.start(Operation::BlockingTruncate);
+        self.stats.record(self.inner.blocking_truncate(path, args))
+    }
+}
+
+/// StatsWrapper counts bytes and errors of readers, writers and pagers.
+pub struct StatsWrapper<T> {
+    inner: T,
+    stats: Arc<StatsCounters>,
+}
+
+impl<T> StatsWrapper<T> {
+    fn new(inner: T, stats: Arc<StatsCounters>) -> Self {
+        Self { inner, stats }
+    }
+}
+
+impl<R: oio::Read> oio::Read for StatsWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let n = self.stats.record(ready!(self.inner.poll_read(cx, buf)))?;
+        self.stats.add_bytes_read(n as u64);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        let res = ready!(self.inner.poll_seek(cx, pos));
+        Poll::Ready(self.stats.record(res))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        let res = ready!(self.inner.poll_next(cx)).map(|v| self.stats.record(v));
+        if let Some(Ok(bs)) = &res {
+            self.stats.add_bytes_read(bs.len() as u64);
+        }
+        Poll::Ready(res)
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for StatsWrapper<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.stats.record(self.inner.read(buf))?;
+        self.stats.add_bytes_read(n as u64);
+        Ok(n)
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.stats.record(self.inner.seek(pos))
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        let res = self.inner.next().map(|v| self.stats.record(v));
+        if let Some(Ok(bs)) = &res {
+            self.stats.add_bytes_read(bs.len() as u64);
+        }
+        res
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for StatsWrapper<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+
+        self.stats.record(self.inner.write(bs).await)?;
+        self.stats.add_bytes_written(size);
+        Ok(())
+    }
+
+    async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
+        self.stats.record(self.inner.sink(size, s).await)?;
+        self.stats.add_bytes_written(size);
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.stats.record(self.inner.abort().await)
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.stats.record(self.inner.close().await)
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for StatsWrapper<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+
+        self.stats.record(self.inner.write(bs))?;
+        self.stats.add_bytes_written(size);
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.stats.record(self.inner.close())
+    }
+}
+
+#[async_trait]
+impl<A: oio::Append> oio::Append for StatsWrapper<A> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+
+        self.stats.record(self.inner.append(bs).await)?;
+        self.stats.add_bytes_written(size);
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.stats.record(self.inner.close().await)
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for StatsWrapper<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        self.stats.record(self.inner.next().await)
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for StatsWrapper<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        self.stats.record(self.inner.next())
+    }
+}
+
+#[cfg(all(test, feature = "services-memory"))]
+mod tests {
+    use super::*;
+    use crate::layers::LoggingLayer;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_stats() {
+        let op = Operator::new(Memory::default())
+            .unwrap()
+            .finish()
+            .layer(LoggingLayer::default());
+
+        op.write("a", "Hello, World!").await.unwrap();
+        assert_eq!(op.read("a").await.unwrap(), b"Hello, World!");
+        let err = op.stat("not_exist").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let stats = op.stats();
+        assert_eq!(stats.operations_of(Operation::Write), 1);
+        assert_eq!(stats.operations_of(Operation::Read), 1);
+        assert_eq!(stats.operations_of(Operation::Stat), 1);
+        assert_eq!(stats.operations(), 3);
+        assert_eq!(stats.errors_of(ErrorKind::NotFound), 1);
+        assert_eq!(stats.errors(), 1);
+        assert_eq!(stats.bytes_written(), 13);
+        assert_eq!(stats.bytes_read(), 13);
+        assert_eq!(stats.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_stats_blocking() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        let bop = op.blocking();
+
+        bop.write("a", "Hello").unwrap();
+        assert_eq!(bop.read("a").unwrap(), b"Hello");
+
+        let stats = op.stats();
+        assert_eq!(stats.operations_of(Operation::BlockingWrite), 1);
+        assert_eq!(stats.operations_of(Operation::BlockingRead), 1);
+        assert_eq!(stats.bytes_written(), 5);
+        assert_eq!(stats.bytes_read(), 5);
+        assert_eq!(bop.stats().operations(), 2);
+    }
+}
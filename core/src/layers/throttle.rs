@@ -238,7 +238,7 @@ impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
                     NegativeMultiDecision::BatchNonConforming(_, not_until) => {
                         let wait_time = not_until.wait_time_from(DefaultClock::default().now());
                         // TODO: Should lock the limiter and wait for the wait_time, or should let other small requests go first?
-                        tokio::time::sleep(wait_time).await;
+                        sleep(wait_time).await;
                     }
                     // the query was invalid as the rate limit parameters can "never" accommodate the number of cells queried for.
                     NegativeMultiDecision::InsufficientCapacity(_) => {
@@ -307,7 +307,7 @@ impl<R: oio::Append> oio::Append for ThrottleWrapper<R> {
                     // the query is valid but the Decider can not accommodate them.
                     NegativeMultiDecision::BatchNonConforming(_, not_until) => {
                         let wait_time = not_until.wait_time_from(DefaultClock::default().now());
-                        tokio::time::sleep(wait_time).await;
+                        sleep(wait_time).await;
                     }
                     // the query was invalid as the rate limit parameters can "never" accommodate the number of cells queried for.
                     NegativeMultiDecision::InsufficientCapacity(_) => {
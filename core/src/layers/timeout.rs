@@ -16,13 +16,15 @@
 // under the License.
 
 use std::io::SeekFrom;
+use std::pin::Pin;
+use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 use std::time::Duration;
-use std::time::Instant;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use futures::FutureExt;
 
 use crate::raw::oio::AppendOperation;
 use crate::raw::oio::PageOperation;
@@ -156,9 +158,9 @@ impl<A: Accessor> LayeredAccessor for TimeoutAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        tokio::time::timeout(self.timeout, self.inner.read(path, args))
+        timeout(self.timeout, self.inner.read(path, args))
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(Operation::Read)
                     .with_context("timeout", self.timeout.as_secs_f64().to_string())
@@ -168,9 +170,9 @@ impl<A: Accessor> LayeredAccessor for TimeoutAccessor<A> {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        tokio::time::timeout(self.timeout, self.inner.write(path, args))
+        timeout(self.timeout, self.inner.write(path, args))
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(Operation::Write)
                     .with_context("timeout", self.timeout.as_secs_f64().to_string())
@@ -180,9 +182,9 @@ impl<A: Accessor> LayeredAccessor for TimeoutAccessor<A> {
     }
 
     async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
-        tokio::time::timeout(self.timeout, self.inner.append(path, args))
+        timeout(self.timeout, self.inner.append(path, args))
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(Operation::Append)
                     .with_context("timeout", self.timeout.as_secs_f64().to_string())
@@ -192,9 +194,9 @@ impl<A: Accessor> LayeredAccessor for TimeoutAccessor<A> {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
-        tokio::time::timeout(self.timeout, self.inner.list(path, args))
+        timeout(self.timeout, self.inner.list(path, args))
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(Operation::List)
                     .with_context("timeout", self.timeout.as_secs_f64().to_string())
@@ -222,7 +224,7 @@ pub struct TimeoutWrapper<R> {
     timeout: Duration,
     speed: u64,
 
-    start: Option<Instant>,
+    sleep: Option<Pin<Box<Sleep>>>,
 }
 
 impl<R> TimeoutWrapper<R> {
@@ -231,7 +233,7 @@ impl<R> TimeoutWrapper<R> {
             inner,
             timeout,
             speed,
-            start: None,
+            sleep: None,
         }
     }
 
@@ -244,90 +246,66 @@ impl<R> TimeoutWrapper<R> {
 
 impl<R: oio::Read> oio::Read for TimeoutWrapper<R> {
     fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
-        match self.start {
-            Some(start) => {
-                if start.elapsed() > self.timeout {
-                    // Clean up the start time before return ready.
-                    self.start = None;
-
-                    return Poll::Ready(Err(Error::new(
-                        ErrorKind::Unexpected,
-                        "operation timeout",
-                    )
+        match self.inner.poll_read(cx, buf) {
+            Poll::Pending => {
+                let timeout = self.timeout;
+                let timer = self.sleep.get_or_insert_with(|| Box::pin(sleep(timeout)));
+                ready!(timer.poll_unpin(cx));
+                // Clean up the timer before return ready.
+                self.sleep = None;
+
+                Poll::Ready(Err(Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(ReadOperation::Read)
                     .with_context("timeout", self.timeout.as_secs_f64().to_string())
-                    .set_temporary()));
-                }
-            }
-            None => {
-                self.start = Some(Instant::now());
+                    .set_temporary()))
             }
-        }
-
-        match self.inner.poll_read(cx, buf) {
-            Poll::Pending => Poll::Pending,
             Poll::Ready(v) => {
-                self.start = None;
+                self.sleep = None;
                 Poll::Ready(v)
             }
         }
     }
 
     fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
-        match self.start {
-            Some(start) => {
-                if start.elapsed() > self.timeout {
-                    // Clean up the start time before return ready.
-                    self.start = None;
-
-                    return Poll::Ready(Err(Error::new(
-                        ErrorKind::Unexpected,
-                        "operation timeout",
-                    )
+        match self.inner.poll_seek(cx, pos) {
+            Poll::Pending => {
+                let timeout = self.timeout;
+                let timer = self.sleep.get_or_insert_with(|| Box::pin(sleep(timeout)));
+                ready!(timer.poll_unpin(cx));
+                // Clean up the timer before return ready.
+                self.sleep = None;
+
+                Poll::Ready(Err(Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(ReadOperation::Seek)
                     .with_context("timeout", self.timeout.as_secs_f64().to_string())
-                    .set_temporary()));
-                }
+                    .set_temporary()))
             }
-            None => {
-                self.start = Some(Instant::now());
-            }
-        }
-
-        match self.inner.poll_seek(cx, pos) {
-            Poll::Pending => Poll::Pending,
             Poll::Ready(v) => {
-                self.start = None;
+                self.sleep = None;
                 Poll::Ready(v)
             }
         }
     }
 
     fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
-        match self.start {
-            Some(start) => {
-                if start.elapsed() > self.timeout {
-                    // Clean up the start time before return ready.
-                    self.start = None;
-
-                    return Poll::Ready(Some(Err(Error::new(
-                        ErrorKind::Unexpected,
-                        "operation timeout",
-                    )
-                    .with_operation(ReadOperation::Next)
-                    .with_context("timeout", self.timeout.as_secs_f64().to_string())
-                    .set_temporary())));
-                }
-            }
-            None => {
-                self.start = Some(Instant::now());
-            }
-        }
-
         match self.inner.poll_next(cx) {
-            Poll::Pending => Poll::Pending,
+            Poll::Pending => {
+                let timeout = self.timeout;
+                let timer = self.sleep.get_or_insert_with(|| Box::pin(sleep(timeout)));
+                ready!(timer.poll_unpin(cx));
+                // Clean up the timer before return ready.
+                self.sleep = None;
+
+                Poll::Ready(Some(Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "operation timeout",
+                )
+                .with_operation(ReadOperation::Next)
+                .with_context("timeout", self.timeout.as_secs_f64().to_string())
+                .set_temporary())))
+            }
             Poll::Ready(v) => {
-                self.start = None;
+                self.sleep = None;
                 Poll::Ready(v)
             }
         }
@@ -337,35 +315,35 @@ impl<R: oio::Read> oio::Read for TimeoutWrapper<R> {
 #[async_trait]
 impl<R: oio::Write> oio::Write for TimeoutWrapper<R> {
     async fn write(&mut self, bs: Bytes) -> Result<()> {
-        let timeout = self.io_timeout(bs.len() as u64);
+        let io_timeout = self.io_timeout(bs.len() as u64);
 
-        tokio::time::timeout(timeout, self.inner.write(bs))
+        timeout(io_timeout, self.inner.write(bs))
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(WriteOperation::Write)
-                    .with_context("timeout", timeout.as_secs_f64().to_string())
+                    .with_context("timeout", io_timeout.as_secs_f64().to_string())
                     .set_temporary()
             })?
     }
 
     async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
-        let timeout = self.io_timeout(size);
+        let io_timeout = self.io_timeout(size);
 
-        tokio::time::timeout(timeout, self.inner.sink(size, s))
+        timeout(io_timeout, self.inner.sink(size, s))
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(WriteOperation::Sink)
-                    .with_context("timeout", timeout.as_secs_f64().to_string())
+                    .with_context("timeout", io_timeout.as_secs_f64().to_string())
                     .set_temporary()
             })?
     }
 
     async fn abort(&mut self) -> Result<()> {
-        tokio::time::timeout(self.timeout, self.inner.abort())
+        timeout(self.timeout, self.inner.abort())
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(WriteOperation::Abort)
                     .with_context("timeout", self.timeout.as_secs_f64().to_string())
@@ -374,9 +352,9 @@ impl<R: oio::Write> oio::Write for TimeoutWrapper<R> {
     }
 
     async fn close(&mut self) -> Result<()> {
-        tokio::time::timeout(self.timeout, self.inner.close())
+        timeout(self.timeout, self.inner.close())
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(WriteOperation::Close)
                     .with_context("timeout", self.timeout.as_secs_f64().to_string())
@@ -388,22 +366,22 @@ impl<R: oio::Write> oio::Write for TimeoutWrapper<R> {
 #[async_trait]
 impl<A: oio::Append> oio::Append for TimeoutWrapper<A> {
     async fn append(&mut self, bs: Bytes) -> Result<()> {
-        let timeout = self.io_timeout(bs.len() as u64);
+        let io_timeout = self.io_timeout(bs.len() as u64);
 
-        tokio::time::timeout(timeout, self.inner.append(bs))
+        timeout(io_timeout, self.inner.append(bs))
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(AppendOperation::Append)
-                    .with_context("timeout", timeout.as_secs_f64().to_string())
+                    .with_context("timeout", io_timeout.as_secs_f64().to_string())
                     .set_temporary()
             })?
     }
 
     async fn close(&mut self) -> Result<()> {
-        tokio::time::timeout(self.timeout, self.inner.close())
+        timeout(self.timeout, self.inner.close())
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(AppendOperation::Close)
                     .with_context("timeout", self.timeout.as_secs_f64().to_string())
@@ -415,9 +393,9 @@ impl<A: oio::Append> oio::Append for TimeoutWrapper<A> {
 #[async_trait]
 impl<R: oio::Page> oio::Page for TimeoutWrapper<R> {
     async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
-        tokio::time::timeout(self.timeout, self.inner.next())
+        timeout(self.timeout, self.inner.next())
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "operation timeout")
                     .with_operation(PageOperation::Next)
                     .with_context("timeout", self.timeout.as_secs_f64().to_string())
@@ -19,6 +19,9 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bytes::Bytes;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::raw::*;
 use crate::*;
@@ -265,6 +268,80 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
         ))
     }
 
+    /// Invoke the `restore` operation on the specified path.
+    ///
+    /// Require [`Capability::restore`]
+    ///
+    /// # Behavior
+    ///
+    /// - Restore will only initiate the restoration of an archived object,
+    ///   the restore status could be fetched by `stat`.
+    async fn restore(&self, path: &str, args: OpRestore) -> Result<RpRestore> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `select` operation on the specified path.
+    ///
+    /// Require [`Capability::select`]
+    ///
+    /// # Behavior
+    ///
+    /// - Select will filter the content of the file by given SQL expression
+    ///   in service side, only the matched records will be returned.
+    /// - The returning reader can't be seeked since the content length is
+    ///   unknown.
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `write_at` operation on the specified path.
+    ///
+    /// Require [`Capability::write_at`]
+    ///
+    /// # Behavior
+    ///
+    /// - Write the given bytes at the offset of the file and keep other
+    ///   content of the file unchanged.
+    /// - The file will be created if it doesn't exist.
+    /// - The gap between the end of the file and the offset will be
+    ///   filled with zeros.
+    async fn write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        let (_, _, _) = (path, args, bs);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `truncate` operation on the specified path.
+    ///
+    /// Require [`Capability::truncate`]
+    ///
+    /// # Behavior
+    ///
+    /// - Truncate or extend the file to the given size, extended part will
+    ///   be filled with zeros.
+    /// - Truncate a not existing file should return `NotFound`.
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
     /// Invoke the `blocking_create` operation on the specified path.
     ///
     /// This operation is the blocking version of [`Accessor::create_dir`]
@@ -380,6 +457,34 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
             "operation is not supported",
         ))
     }
+
+    /// Invoke the `blocking_write_at` operation on the specified path.
+    ///
+    /// This operation is the blocking version of [`Accessor::write_at`]
+    ///
+    /// Require [`Capability::write_at`] and [`Capability::blocking`]
+    fn blocking_write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        let (_, _, _) = (path, args, bs);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `blocking_truncate` operation on the specified path.
+    ///
+    /// This operation is the blocking version of [`Accessor::truncate`]
+    ///
+    /// Require [`Capability::truncate`] and [`Capability::blocking`]
+    fn blocking_truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
 }
 
 /// Dummy implementation of accessor.
@@ -460,6 +565,22 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
         self.as_ref().presign(path, args).await
     }
 
+    async fn restore(&self, path: &str, args: OpRestore) -> Result<RpRestore> {
+        self.as_ref().restore(path, args).await
+    }
+
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        self.as_ref().select(path, args).await
+    }
+
+    async fn write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        self.as_ref().write_at(path, args, bs).await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        self.as_ref().truncate(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.as_ref().blocking_create_dir(path, args)
     }
@@ -487,6 +608,12 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
         self.as_ref().blocking_list(path, args)
     }
+    fn blocking_write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        self.as_ref().blocking_write_at(path, args, bs)
+    }
+    fn blocking_truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        self.as_ref().blocking_truncate(path, args)
+    }
 }
 
 /// FusedAccessor is the type erased accessor with `Arc<dyn Accessor>`.
@@ -503,7 +630,7 @@ pub type FusedAccessor = Arc<
 >;
 
 /// Metadata for accessor, users can use this metadata to get information of underlying backend.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct AccessorInfo {
     scheme: Scheme,
     root: String,
@@ -0,0 +1,212 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::Duration;
+
+/// ExponentialBuilder is used to build [`ExponentialBackoff`].
+///
+/// By default, backoff starts with 1s delay, doubles the delay until 60s
+/// and stops after 3 times.
+///
+/// This follows the behavior of backon's `ExponentialBuilder` which we
+/// used before, backon can't be used without tokio.
+#[derive(Debug, Clone)]
+pub struct ExponentialBuilder {
+    jitter: bool,
+    factor: f32,
+    min_delay: Duration,
+    max_delay: Option<Duration>,
+    max_times: Option<usize>,
+}
+
+impl Default for ExponentialBuilder {
+    fn default() -> Self {
+        Self {
+            jitter: false,
+            factor: 2.0,
+            min_delay: Duration::from_secs(1),
+            max_delay: Some(Duration::from_secs(60)),
+            max_times: Some(3),
+        }
+    }
+}
+
+impl ExponentialBuilder {
+    /// Add a random jitter in `[0, min_delay)` to every delay.
+    pub fn with_jitter(mut self) -> Self {
+        self.jitter = true;
+        self
+    }
+
+    /// Set factor of backoff.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if input factor smaller than `1.0`.
+    pub fn with_factor(mut self, factor: f32) -> Self {
+        debug_assert!(factor >= 1.0, "invalid factor that lower than 1");
+
+        self.factor = factor;
+        self
+    }
+
+    /// Set min_delay of backoff.
+    pub fn with_min_delay(mut self, min_delay: Duration) -> Self {
+        self.min_delay = min_delay;
+        self
+    }
+
+    /// Set max_delay of backoff.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Set max_times of backoff.
+    pub fn with_max_times(mut self, max_times: usize) -> Self {
+        self.max_times = Some(max_times);
+        self
+    }
+
+    /// Build a new backoff.
+    pub fn build(&self) -> ExponentialBackoff {
+        ExponentialBackoff {
+            jitter: self.jitter,
+            factor: self.factor,
+            min_delay: self.min_delay,
+            max_delay: self.max_delay,
+            max_times: self.max_times,
+
+            current_delay: None,
+            attempts: 0,
+        }
+    }
+}
+
+/// ExponentialBackoff yields the delays before every retry, returns `None`
+/// if max times is reached.
+#[derive(Debug)]
+pub struct ExponentialBackoff {
+    jitter: bool,
+    factor: f32,
+    min_delay: Duration,
+    max_delay: Option<Duration>,
+    max_times: Option<usize>,
+
+    current_delay: Option<Duration>,
+    attempts: usize,
+}
+
+impl Iterator for ExponentialBackoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.attempts >= self.max_times.unwrap_or(usize::MAX) {
+            return None;
+        }
+        self.attempts += 1;
+
+        let mut cur = match self.current_delay {
+            None => self.min_delay,
+            Some(mut cur) => {
+                // Delay will not increase anymore once reaching max delay.
+                if self.max_delay.map_or(true, |max| cur < max) {
+                    cur = saturating_mul(cur, self.factor);
+                }
+                if let Some(max) = self.max_delay {
+                    cur = cur.min(max);
+                }
+                cur
+            }
+        };
+        self.current_delay = Some(cur);
+
+        if self.jitter {
+            cur = cur.saturating_add(self.min_delay.mul_f32(fastrand::f32()));
+        }
+        Some(cur)
+    }
+}
+
+fn saturating_mul(d: Duration, rhs: f32) -> Duration {
+    let secs = d.as_secs_f32() * rhs;
+    if secs >= Duration::MAX.as_secs_f32() {
+        Duration::MAX
+    } else {
+        Duration::from_secs_f32(secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential_default() {
+        let mut exp = ExponentialBuilder::default().build();
+
+        assert_eq!(Some(Duration::from_secs(1)), exp.next());
+        assert_eq!(Some(Duration::from_secs(2)), exp.next());
+        assert_eq!(Some(Duration::from_secs(4)), exp.next());
+        assert_eq!(None, exp.next());
+    }
+
+    #[test]
+    fn test_exponential_factor() {
+        let mut exp = ExponentialBuilder::default().with_factor(1.5).build();
+
+        assert_eq!(Some(Duration::from_secs_f32(1.0)), exp.next());
+        assert_eq!(Some(Duration::from_secs_f32(1.5)), exp.next());
+        assert_eq!(Some(Duration::from_secs_f32(2.25)), exp.next());
+        assert_eq!(None, exp.next());
+    }
+
+    #[test]
+    fn test_exponential_jitter() {
+        let mut exp = ExponentialBuilder::default().with_jitter().build();
+
+        for (min, max) in [(1, 2), (2, 3), (4, 5)] {
+            let v = exp.next().expect("value must valid");
+            assert!(v >= Duration::from_secs(min), "current: {v:?}");
+            assert!(v < Duration::from_secs(max), "current: {v:?}");
+        }
+        assert_eq!(None, exp.next());
+    }
+
+    #[test]
+    fn test_exponential_max_delay() {
+        let mut exp = ExponentialBuilder::default()
+            .with_min_delay(Duration::from_millis(500))
+            .with_max_delay(Duration::from_secs(1))
+            .with_max_times(4)
+            .build();
+
+        assert_eq!(Some(Duration::from_millis(500)), exp.next());
+        assert_eq!(Some(Duration::from_secs(1)), exp.next());
+        assert_eq!(Some(Duration::from_secs(1)), exp.next());
+        assert_eq!(Some(Duration::from_secs(1)), exp.next());
+        assert_eq!(None, exp.next());
+    }
+
+    #[test]
+    fn test_exponential_max_times() {
+        let mut exp = ExponentialBuilder::default().with_max_times(1).build();
+
+        assert_eq!(Some(Duration::from_secs(1)), exp.next());
+        assert_eq!(None, exp.next());
+    }
+}
@@ -0,0 +1,181 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use bytes::BytesMut;
+
+/// The default chunk size of [`BufferPool`].
+pub const DEFAULT_BUFFER_CHUNK_SIZE: usize = 256 * 1024;
+
+/// The default max buffers that [`BufferPool`] will keep.
+const DEFAULT_BUFFER_MAX_IDLE: usize = 64;
+
+/// BufferPool keeps the idle buffers so that readers and writers can reuse
+/// them instead of allocating fresh buffers for every request.
+///
+/// BufferPool is cheap to clone, all clones share the same buffers.
+///
+/// # Notes
+///
+/// - Buffers returned by [`BufferPool::get`] are empty with capacity at
+///   least `chunk_size`.
+/// - At most 64 idle buffers will be kept, others will be dropped.
+#[derive(Clone)]
+pub struct BufferPool {
+    inner: Arc<BufferPoolInner>,
+}
+
+struct BufferPoolInner {
+    chunk_size: AtomicUsize,
+    max_idle: usize,
+    buffers: Mutex<Vec<BytesMut>>,
+}
+
+impl Debug for BufferPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("chunk_size", &self.chunk_size())
+            .field("max_idle", &self.inner.max_idle)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUFFER_CHUNK_SIZE)
+    }
+}
+
+impl BufferPool {
+    /// Create a new buffer pool with given chunk size.
+    pub fn new(chunk_size: usize) -> Self {
+        Self {
+            inner: Arc::new(BufferPoolInner {
+                chunk_size: AtomicUsize::new(chunk_size.max(1)),
+                max_idle: DEFAULT_BUFFER_MAX_IDLE,
+                buffers: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Get the chunk size of this pool.
+    pub fn chunk_size(&self) -> usize {
+        self.inner.chunk_size.load(Ordering::Relaxed)
+    }
+
+    /// Update the chunk size of this pool.
+    ///
+    /// Idle buffers that are smaller than the new chunk size will be dropped.
+    pub fn set_chunk_size(&self, chunk_size: usize) {
+        let chunk_size = chunk_size.max(1);
+        self.inner.chunk_size.store(chunk_size, Ordering::Relaxed);
+
+        let mut buffers = self.inner.buffers.lock().expect("lock must succeed");
+        buffers.retain(|v| v.capacity() >= chunk_size);
+    }
+
+    /// Get an empty buffer with capacity at least `chunk_size`.
+    pub fn get(&self) -> BytesMut {
+        let chunk_size = self.chunk_size();
+
+        let buf = self.inner.buffers.lock().expect("lock must succeed").pop();
+        match buf {
+            Some(buf) if buf.capacity() >= chunk_size => buf,
+            _ => BytesMut::with_capacity(chunk_size),
+        }
+    }
+
+    /// Get an empty buffer with capacity at least `capacity`.
+    ///
+    /// This is used by writers whose buffers are usually larger than
+    /// `chunk_size`, like the parts of multipart upload.
+    pub fn get_with_capacity(&self, capacity: usize) -> BytesMut {
+        let mut buffers = self.inner.buffers.lock().expect("lock must succeed");
+        match buffers.iter().position(|v| v.capacity() >= capacity) {
+            Some(idx) => buffers.swap_remove(idx),
+            None => BytesMut::with_capacity(capacity),
+        }
+    }
+
+    /// Return the buffer back to the pool so that it can be reused.
+    ///
+    /// Buffers whose capacity is smaller than `chunk_size` will be dropped.
+    pub fn put(&self, mut buf: BytesMut) {
+        if buf.capacity() < self.chunk_size() {
+            return;
+        }
+        buf.clear();
+
+        let mut buffers = self.inner.buffers.lock().expect("lock must succeed");
+        if buffers.len() < self.inner.max_idle {
+            buffers.push(buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_pool() {
+        let pool = BufferPool::new(1024);
+
+        let mut buf = pool.get();
+        assert!(buf.is_empty());
+        assert!(buf.capacity() >= 1024);
+
+        buf.extend_from_slice(b"hello");
+        let ptr = buf.as_ptr();
+        pool.put(buf);
+
+        let buf = pool.get();
+        assert!(buf.is_empty(), "reused buffer must be cleared");
+        assert_eq!(buf.as_ptr(), ptr, "buffer must be reused");
+        pool.put(buf);
+
+        pool.set_chunk_size(4096);
+        let buf = pool.get();
+        assert!(buf.capacity() >= 4096);
+        assert_ne!(buf.as_ptr(), ptr, "small buffer must be dropped");
+
+        // Buffers smaller than chunk size will not be kept.
+        pool.put(BytesMut::with_capacity(16));
+        assert!(pool.get().capacity() >= 4096);
+    }
+
+    #[test]
+    fn test_buffer_pool_get_with_capacity() {
+        let pool = BufferPool::new(1024);
+
+        let buf = BytesMut::with_capacity(8192);
+        let ptr = buf.as_ptr();
+        pool.put(BytesMut::with_capacity(1024));
+        pool.put(buf);
+        pool.put(BytesMut::with_capacity(2048));
+
+        let buf = pool.get_with_capacity(4096);
+        assert_eq!(buf.as_ptr(), ptr, "large enough buffer must be reused");
+        assert!(pool.get_with_capacity(4096).capacity() >= 4096);
+    }
+}
@@ -15,14 +15,23 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::mem;
+#[cfg(not(target_arch = "wasm32"))]
+use std::net::SocketAddr;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
 
 use futures::TryStreamExt;
 use http::Request;
 use http::Response;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
 
 use super::body::IncomingAsyncBody;
 use super::parse_content_length;
@@ -36,6 +45,7 @@ use crate::Result;
 #[derive(Clone)]
 pub struct HttpClient {
     client: reqwest::Client,
+    host_permits: Option<Arc<HostPermits>>,
 }
 
 /// We don't want users to know details about our clients.
@@ -51,22 +61,41 @@ impl HttpClient {
         Self::build(reqwest::ClientBuilder::new())
     }
 
+    /// Create a new [`HttpClientBuilder`] to tune the connection pool,
+    /// timeouts and dns resolving of the http client.
+    pub fn builder() -> HttpClientBuilder {
+        HttpClientBuilder::default()
+    }
+
     /// Build a new http client in async context.
+    ///
+    /// Users can build the [`reqwest::ClientBuilder`] by themselves to
+    /// use custom tls roots, proxies and so on.
+    ///
+    /// # Notes
+    ///
+    /// On `wasm32`, requests are sent via `fetch` of the js runtime which
+    /// handles compression, connection pool and dns resolving by itself.
+    #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
     pub fn build(mut builder: reqwest::ClientBuilder) -> Result<Self> {
-        // Make sure we don't enable auto gzip decompress.
-        builder = builder.no_gzip();
-        // Make sure we don't enable auto brotli decompress.
-        builder = builder.no_brotli();
-        // Make sure we don't enable auto deflate decompress.
-        builder = builder.no_deflate();
-
-        #[cfg(feature = "trust-dns")]
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            // Make sure we don't enable auto gzip decompress.
+            builder = builder.no_gzip();
+            // Make sure we don't enable auto brotli decompress.
+            builder = builder.no_brotli();
+            // Make sure we don't enable auto deflate decompress.
+            builder = builder.no_deflate();
+        }
+
+        #[cfg(all(feature = "trust-dns", not(target_arch = "wasm32")))]
         let builder = builder.trust_dns(true);
 
         Ok(Self {
             client: builder.build().map_err(|err| {
                 Error::new(ErrorKind::Unexpected, "async client build failed").set_source(err)
             })?,
+            host_permits: None,
         })
     }
 
@@ -82,6 +111,16 @@ impl HttpClient {
         let uri = req.uri().clone();
         let is_head = req.method() == http::Method::HEAD;
 
+        // Wait for a free connection slot of this host if limited.
+        let permit = match &self.host_permits {
+            Some(permits) => Some(
+                permits
+                    .acquire(uri.authority().map(|v| v.as_str()).unwrap_or_default())
+                    .await,
+            ),
+            None => None,
+        };
+
         let (parts, body) = req.into_parts();
 
         let mut req_builder = self
@@ -90,16 +129,33 @@ impl HttpClient {
                 parts.method,
                 reqwest::Url::from_str(&uri.to_string()).expect("input request url must be valid"),
             )
-            .version(parts.version)
             .headers(parts.headers);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            req_builder = req_builder.version(parts.version);
+        }
 
         req_builder = match body {
             AsyncBody::Empty => req_builder.body(reqwest::Body::from("")),
             AsyncBody::Bytes(bs) => req_builder.body(reqwest::Body::from(bs)),
+            #[cfg(not(target_arch = "wasm32"))]
             AsyncBody::Stream(s) => req_builder.body(reqwest::Body::wrap_stream(s)),
+            // fetch doesn't support streaming request body.
+            #[cfg(target_arch = "wasm32")]
+            AsyncBody::Stream(_) => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "streaming request body is not supported on wasm32",
+                )
+                .with_operation("http_util::Client::send_async")
+                .with_context("url", uri.to_string()))
+            }
         };
 
-        let mut resp = req_builder.send().await.map_err(|err| {
+        let fut = req_builder.send();
+        #[cfg(target_arch = "wasm32")]
+        let fut = send_wrapper::SendWrapper::new(fut);
+        let mut resp = fut.await.map_err(|err| {
             let is_temporary = !(
                 // Builder related error should not be retried.
                 err.is_builder() ||
@@ -132,8 +188,10 @@ impl HttpClient {
             parse_content_length(resp.headers()).expect("response content length must be valid")
         };
 
-        let mut hr = Response::builder()
-            .version(resp.version())
+        let hr = Response::builder();
+        #[cfg(not(target_arch = "wasm32"))]
+        let hr = hr.version(resp.version());
+        let mut hr = hr
             .status(resp.status())
             // Insert uri into response extension so that we can fetch
             // it later.
@@ -142,6 +200,10 @@ impl HttpClient {
         mem::swap(hr.headers_mut().unwrap(), resp.headers_mut());
 
         let stream = resp.bytes_stream().map_err(move |err| {
+            // Hold the permit until the body has been dropped so that
+            // the connection is counted while it's still in use.
+            let _ = &permit;
+
             // If stream returns a body related error, we can convert
             // it to interrupt so we can retry it.
             Error::new(ErrorKind::Unexpected, "read data from http stream")
@@ -150,6 +212,8 @@ impl HttpClient {
                 .set_source(err)
         });
 
+        #[cfg(target_arch = "wasm32")]
+        let stream = send_wrapper::SendWrapper::new(stream);
         let body = IncomingAsyncBody::new(
             Box::new(into_stream::from_futures_stream(stream)),
             content_length,
@@ -160,3 +224,278 @@ impl HttpClient {
         Ok(resp)
     }
 }
+
+/// HttpClientBuilder is used to build a [`HttpClient`] with tuned
+/// connection pool, timeouts and dns resolving.
+///
+/// All options left unset will use reqwest's default values.
+///
+/// Options about connection pool, dns and tls are not available on
+/// `wasm32` since they are managed by the js runtime.
+#[derive(Default)]
+pub struct HttpClientBuilder {
+    builder: reqwest::ClientBuilder,
+    max_connections_per_host: Option<usize>,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxy: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    root_certificates: Vec<Vec<u8>>,
+}
+
+impl Debug for HttpClientBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut d = f.debug_struct("HttpClientBuilder");
+        d.field("max_connections_per_host", &self.max_connections_per_host);
+        #[cfg(not(target_arch = "wasm32"))]
+        d.field("proxy", &self.proxy);
+        d.finish_non_exhaustive()
+    }
+}
+
+impl HttpClientBuilder {
+    /// Set the maximum idle connections kept in pool per host.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.builder = self.builder.pool_max_idle_per_host(max);
+        self
+    }
+
+    /// Set the timeout for idle connections kept in pool.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.builder = self.builder.pool_idle_timeout(timeout);
+        self
+    }
+
+    /// Set the maximum in-flight requests per host.
+    ///
+    /// Requests exceeding this limit will wait until a previous response
+    /// body has been dropped.
+    pub fn max_connections_per_host(mut self, max: usize) -> Self {
+        self.max_connections_per_host = Some(max);
+        self
+    }
+
+    /// Only use HTTP/1.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http1_only(mut self) -> Self {
+        self.builder = self.builder.http1_only();
+        self
+    }
+
+    /// Use HTTP/2 directly without negotiating, only enable this while
+    /// the endpoint is known to support HTTP/2.
+    ///
+    /// For https endpoints, HTTP/2 will be negotiated via ALPN already.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.builder = self.builder.http2_prior_knowledge();
+        self
+    }
+
+    /// Enable HTTP/2 adaptive flow control to make better use of
+    /// high bandwidth connections.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.builder = self.builder.http2_adaptive_window(enabled);
+        self
+    }
+
+    /// Set the interval for HTTP/2 keep-alive ping frames.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.builder = self.builder.http2_keep_alive_interval(interval);
+        self
+    }
+
+    /// Set the timeout for receiving an acknowledgement of HTTP/2
+    /// keep-alive ping.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.builder = self.builder.http2_keep_alive_timeout(timeout);
+        self
+    }
+
+    /// Set `TCP_NODELAY` on connections.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tcp_nodelay(mut self, enabled: bool) -> Self {
+        self.builder = self.builder.tcp_nodelay(enabled);
+        self
+    }
+
+    /// Set `SO_KEEPALIVE` on connections with the given interval.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.builder = self.builder.tcp_keepalive(interval);
+        self
+    }
+
+    /// Set the timeout for connecting to remote hosts.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.builder = self.builder.connect_timeout(timeout);
+        self
+    }
+
+    /// Override dns resolving for the given domain to the given address.
+    ///
+    /// The port in the address will be ignored, port in request url
+    /// will be used instead.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn resolve(mut self, domain: &str, addr: SocketAddr) -> Self {
+        self.builder = self.builder.resolve(domain, addr);
+        self
+    }
+
+    /// Use the given dns resolver instead of the default one.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn dns_resolver<R: reqwest::dns::Resolve + 'static>(mut self, resolver: Arc<R>) -> Self {
+        self.builder = self.builder.dns_resolver(resolver);
+        self
+    }
+
+    /// Send all requests via the given proxy url, for example:
+    /// `http://127.0.0.1:8080`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, url: &str) -> Self {
+        self.proxy = Some(url.to_string());
+        self
+    }
+
+    /// Trust the given PEM encoded certificate in addition to the
+    /// system root certificates.
+    ///
+    /// This option only takes effect while tls features enabled.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_root_certificate(mut self, pem: &[u8]) -> Self {
+        self.root_certificates.push(pem.to_vec());
+        self
+    }
+
+    /// Build the [`HttpClient`].
+    pub fn build(self) -> Result<HttpClient> {
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut builder = self.builder;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(url) = &self.proxy {
+            let proxy = reqwest::Proxy::all(url).map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "proxy is invalid")
+                    .with_context("proxy", url)
+                    .set_source(err)
+            })?;
+            builder = builder.proxy(proxy);
+        }
+
+        #[cfg(all(
+            not(target_arch = "wasm32"),
+            any(
+                feature = "rustls",
+                feature = "native-tls",
+                feature = "native-tls-vendored"
+            )
+        ))]
+        for pem in &self.root_certificates {
+            let cert = reqwest::Certificate::from_pem(pem).map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "root certificate is invalid").set_source(err)
+            })?;
+            builder = builder.add_root_certificate(cert);
+        }
+
+        let mut client = HttpClient::build(builder)?;
+        if let Some(max) = self.max_connections_per_host {
+            if max == 0 {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "max connections per host must be greater than 0",
+                ));
+            }
+            client.host_permits = Some(Arc::new(HostPermits::new(max)));
+        }
+
+        Ok(client)
+    }
+}
+
+/// HostPermits limits the in-flight requests per host.
+struct HostPermits {
+    permits: usize,
+    semaphores: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl HostPermits {
+    fn new(permits: usize) -> Self {
+        Self {
+            permits,
+            semaphores: Mutex::default(),
+        }
+    }
+
+    async fn acquire(&self, host: &str) -> OwnedSemaphorePermit {
+        let semaphore = self
+            .semaphores
+            .lock()
+            .expect("lock must succeed")
+            .entry(host.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.permits)))
+            .clone();
+
+        semaphore
+            .acquire_owned()
+            .await
+            .expect("semaphore must be valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[test]
+    fn test_http_client_builder() {
+        let client = HttpClient::builder()
+            .pool_max_idle_per_host(8)
+            .pool_idle_timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(5))
+            .max_connections_per_host(16)
+            .http2_adaptive_window(true)
+            .http2_keep_alive_interval(Duration::from_secs(10))
+            .tcp_nodelay(true)
+            .tcp_keepalive(Duration::from_secs(60))
+            .resolve("example.com", "127.0.0.1:0".parse().unwrap())
+            .proxy("http://127.0.0.1:8080")
+            .build();
+        assert!(client.is_ok());
+
+        let err = HttpClient::builder()
+            .proxy("not a valid url")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let err = HttpClient::builder()
+            .max_connections_per_host(0)
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_host_permits() {
+        let permits = HostPermits::new(1);
+
+        let first = permits.acquire("a.example.com").await;
+        // Other hosts should not be blocked.
+        let _other = permits.acquire("b.example.com").await;
+
+        assert!(
+            permits.acquire("a.example.com").now_or_never().is_none(),
+            "second permit of the same host must wait"
+        );
+
+        drop(first);
+        let _second = permits.acquire("a.example.com").await;
+    }
+}
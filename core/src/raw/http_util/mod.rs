@@ -22,8 +22,12 @@
 //! This mod is not a part of OpenDAL's public API. We expose them out to make
 //! it easier to develop services and layers outside opendal.
 
+#[cfg(feature = "internal-http-client")]
 mod client;
+#[cfg(feature = "internal-http-client")]
 pub use client::HttpClient;
+#[cfg(feature = "internal-http-client")]
+pub use client::HttpClientBuilder;
 
 mod body;
 pub use body::AsyncBody;
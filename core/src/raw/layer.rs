@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 
 use async_trait::async_trait;
+use bytes::Bytes;
 
 use crate::raw::*;
 use crate::*;
@@ -183,6 +184,22 @@ pub trait LayeredAccessor: Send + Sync + Debug + Unpin + 'static {
         self.inner().presign(path, args).await
     }
 
+    async fn restore(&self, path: &str, args: OpRestore) -> Result<RpRestore> {
+        self.inner().restore(path, args).await
+    }
+
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        self.inner().select(path, args).await
+    }
+
+    async fn write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        self.inner().write_at(path, args, bs).await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        self.inner().truncate(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.inner().blocking_create_dir(path, args)
     }
@@ -208,6 +225,14 @@ pub trait LayeredAccessor: Send + Sync + Debug + Unpin + 'static {
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)>;
+
+    fn blocking_write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        self.inner().blocking_write_at(path, args, bs)
+    }
+
+    fn blocking_truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        self.inner().blocking_truncate(path, args)
+    }
 }
 
 #[async_trait]
@@ -268,6 +293,22 @@ impl<L: LayeredAccessor> Accessor for L {
         (self as &L).presign(path, args).await
     }
 
+    async fn restore(&self, path: &str, args: OpRestore) -> Result<RpRestore> {
+        (self as &L).restore(path, args).await
+    }
+
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        (self as &L).select(path, args).await
+    }
+
+    async fn write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        (self as &L).write_at(path, args, bs).await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        (self as &L).truncate(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         (self as &L).blocking_create_dir(path, args)
     }
@@ -299,6 +340,14 @@ impl<L: LayeredAccessor> Accessor for L {
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
         (self as &L).blocking_list(path, args)
     }
+
+    fn blocking_write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        (self as &L).blocking_write_at(path, args, bs)
+    }
+
+    fn blocking_truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        (self as &L).blocking_truncate(path, args)
+    }
 }
 
 #[cfg(test)]
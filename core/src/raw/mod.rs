@@ -50,6 +50,10 @@ pub use path::validate_path;
 mod operation;
 pub use operation::Operation;
 
+mod buffer_pool;
+pub use buffer_pool::BufferPool;
+pub use buffer_pool::DEFAULT_BUFFER_CHUNK_SIZE;
+
 mod version;
 pub use version::VERSION;
 
@@ -68,5 +72,11 @@ pub use serde_util::*;
 mod chrono_util;
 pub use chrono_util::*;
 
+mod time_util;
+pub use time_util::*;
+
+mod backoff;
+pub use backoff::*;
+
 // Expose as a pub mod to avoid confusing.
 pub mod adapters;
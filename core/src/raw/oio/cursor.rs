@@ -218,17 +218,7 @@ impl VectorCursor {
             return self.inner[0].slice(..n);
         }
 
-        let mut bs = BytesMut::with_capacity(n);
-        let mut n = n;
-        for b in &self.inner {
-            if n == 0 {
-                break;
-            }
-            let len = b.len().min(n);
-            bs.extend_from_slice(&b[..len]);
-            n -= len;
-        }
-        bs.freeze()
+        self.copy_exact(n, BytesMut::with_capacity(n))
     }
 
     /// peak_at_least will read and copy at least n bytes from current
@@ -252,7 +242,35 @@ impl VectorCursor {
             return self.inner[0].clone();
         }
 
-        let mut bs = BytesMut::with_capacity(n);
+        self.copy_exact(n, BytesMut::with_capacity(n))
+    }
+
+    /// Same as [`VectorCursor::peak_exact`], but the buffer to copy into
+    /// will be taken from given pool.
+    pub fn peak_exact_with_pool(&self, n: usize, pool: &BufferPool) -> Bytes {
+        assert!(n <= self.size, "peak size must smaller than current size");
+
+        if self.inner[0].len() >= n {
+            return self.inner[0].slice(..n);
+        }
+
+        self.copy_exact(n, pool.get_with_capacity(n))
+    }
+
+    /// Same as [`VectorCursor::peak_at_least`], but the buffer to copy into
+    /// will be taken from given pool.
+    pub fn peak_at_least_with_pool(&self, n: usize, pool: &BufferPool) -> Bytes {
+        assert!(n <= self.size, "peak size must smaller than current size");
+
+        if self.inner[0].len() >= n {
+            return self.inner[0].clone();
+        }
+
+        self.copy_exact(n, pool.get_with_capacity(n))
+    }
+
+    /// Copy exactly n bytes into given buffer.
+    fn copy_exact(&self, n: usize, mut bs: BytesMut) -> Bytes {
         let mut n = n;
         for b in &self.inner {
             if n == 0 {
@@ -329,4 +347,26 @@ mod tests {
         vc.take(5);
         assert_eq!(vc.peak_exact(1), Bytes::from("r"));
     }
+
+    #[test]
+    fn test_vector_cursor_with_pool() {
+        let pool = BufferPool::new(4);
+        let mut vc = VectorCursor::new();
+
+        vc.push(Bytes::from("hello"));
+        vc.push(Bytes::from("world"));
+
+        // No copy is needed, bytes will be sliced from the first chunk.
+        assert_eq!(vc.peak_exact_with_pool(4, &pool), Bytes::from("hell"));
+
+        let bs = vc.peak_exact_with_pool(8, &pool);
+        assert_eq!(bs, Bytes::from("hellowor"));
+        let ptr = bs.as_ptr();
+
+        // The buffer could be reused once the part is returned.
+        pool.put(bs.try_into_mut().expect("bytes must be unique"));
+        let bs = vc.peak_at_least_with_pool(7, &pool);
+        assert_eq!(bs, Bytes::from("hellowo"));
+        assert_eq!(bs.as_ptr(), ptr, "buffer must be reused");
+    }
 }
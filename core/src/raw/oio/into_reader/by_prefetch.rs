@@ -0,0 +1,256 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp;
+use std::collections::VecDeque;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::FuturesOrdered;
+use futures::StreamExt;
+
+use crate::raw::oio::ReadExt;
+use crate::raw::*;
+use crate::*;
+
+/// Convert given accessor into [`oio::Reader`] which prefetches the
+/// following chunks while the current one is being consumed.
+///
+/// At most `window` chunks of `chunk_size` will be fetched or kept in
+/// memory at the same time.
+///
+/// # Notes
+///
+/// The reader will send a new read request for every chunk, so it's only
+/// worth for sequential read on high-latency services.
+pub fn by_prefetch<A: Accessor + ?Sized>(
+    acc: Arc<A>,
+    path: &str,
+    op: OpRead,
+    offset: u64,
+    size: u64,
+    chunk_size: usize,
+    window: usize,
+) -> PrefetchReader<A> {
+    PrefetchReader {
+        acc,
+        path: path.to_string(),
+        op,
+        offset,
+        size,
+        chunk_size: cmp::max(chunk_size, 1),
+        window: cmp::max(window, 1),
+        cur: 0,
+        next: 0,
+        buf: Bytes::new(),
+        fetched: VecDeque::new(),
+        futures: FuturesOrdered::new(),
+    }
+}
+
+/// PrefetchReader that pipelines the read requests of following chunks.
+///
+/// Chunks are always contiguous: `buf` starts at `cur`, followed by
+/// `fetched` and then `futures` which end at `next`.
+pub struct PrefetchReader<A: Accessor + ?Sized> {
+    acc: Arc<A>,
+    path: String,
+    op: OpRead,
+
+    offset: u64,
+    size: u64,
+    chunk_size: usize,
+    window: usize,
+
+    /// The position that has been consumed by caller.
+    cur: u64,
+    /// The position of the next chunk to fetch.
+    next: u64,
+    /// The remaining bytes of current chunk.
+    buf: Bytes,
+    /// Chunks that have been fetched but not consumed yet.
+    fetched: VecDeque<Bytes>,
+    /// Chunks that are fetching.
+    futures: FuturesOrdered<BoxFuture<'static, Result<Bytes>>>,
+}
+
+/// Safety: futures will only be accessed under &mut.
+unsafe impl<A: Accessor + ?Sized> Sync for PrefetchReader<A> {}
+
+impl<A: Accessor + ?Sized> PrefetchReader<A> {
+    fn chunk_future(&self, offset: u64, size: usize) -> BoxFuture<'static, Result<Bytes>> {
+        let acc = self.acc.clone();
+        let path = self.path.clone();
+        let op = self
+            .op
+            .clone()
+            .with_range(BytesRange::new(Some(offset), Some(size as u64)));
+
+        Box::pin(async move {
+            let (_, mut r) = acc.read(&path, op).await?;
+
+            let mut buf = vec![0; size];
+            let mut n = 0;
+            while n < size {
+                match r.read(&mut buf[n..]).await? {
+                    0 => break,
+                    m => n += m,
+                }
+            }
+            buf.truncate(n);
+
+            Ok(Bytes::from(buf))
+        })
+    }
+
+    /// Submit chunks until the window is full.
+    fn fill(&mut self) {
+        while self.fetched.len() + self.futures.len() < self.window && self.next < self.size {
+            let size = cmp::min(self.chunk_size as u64, self.size - self.next) as usize;
+            let fut = self.chunk_future(self.offset + self.next, size);
+            self.futures.push_back(fut);
+            self.next += size as u64;
+        }
+    }
+
+    /// Drop all prefetched chunks, the next chunk will start from `cur`.
+    fn reset(&mut self) {
+        self.buf = Bytes::new();
+        self.fetched.clear();
+        self.futures = FuturesOrdered::new();
+        self.next = self.cur;
+    }
+
+    /// Drive the fetching chunks without waiting for them.
+    fn poll_ahead(&mut self, cx: &mut Context<'_>) {
+        loop {
+            self.fill();
+
+            match self.futures.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(bs))) => self.fetched.push_back(bs),
+                Poll::Ready(Some(Err(_))) => {
+                    // Drop the failed chunk and all chunks after it, they
+                    // will be fetched again in the next read.
+                    self.futures = FuturesOrdered::new();
+                    self.next = self.cur
+                        + self.buf.len() as u64
+                        + self.fetched.iter().map(|v| v.len() as u64).sum::<u64>();
+                    return;
+                }
+                Poll::Ready(None) | Poll::Pending => return,
+            }
+        }
+    }
+
+    /// Make sure `buf` is not empty unless all data has been consumed.
+    fn poll_fill_buf(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if !self.buf.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+
+        if let Some(bs) = self.fetched.pop_front() {
+            self.buf = bs;
+        } else {
+            self.fill();
+
+            match ready!(self.futures.poll_next_unpin(cx)) {
+                Some(Ok(bs)) => self.buf = bs,
+                Some(Err(err)) => {
+                    self.reset();
+                    return Poll::Ready(Err(err));
+                }
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+
+        // The content is shorter than we expected, treat it as EOF.
+        if self.buf.is_empty() {
+            self.size = self.cur;
+            self.reset();
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// calculate the seek position.
+    fn seek_pos(&self, pos: SeekFrom) -> Result<u64> {
+        let (base, amt) = match pos {
+            SeekFrom::Start(n) => (0, n as i64),
+            SeekFrom::End(n) => (self.size as i64, n),
+            SeekFrom::Current(n) => (self.cur as i64, n),
+        };
+
+        match base.checked_add(amt) {
+            Some(n) if n >= 0 => Ok(n as u64),
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+impl<A: Accessor + ?Sized> oio::Read for PrefetchReader<A> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        ready!(self.poll_fill_buf(cx))?;
+
+        let n = cmp::min(self.buf.len(), buf.len());
+        buf[..n].copy_from_slice(&self.buf.split_to(n));
+        self.cur += n as u64;
+
+        self.poll_ahead(cx);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, _: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        let seek_pos = self.seek_pos(pos)?;
+
+        // Reuse the current chunk if seek pos is inside it.
+        if seek_pos >= self.cur && seek_pos < self.cur + self.buf.len() as u64 {
+            let _ = self.buf.split_to((seek_pos - self.cur) as usize);
+            self.cur = seek_pos;
+        } else if seek_pos != self.cur {
+            self.cur = seek_pos;
+            self.reset();
+        }
+
+        Poll::Ready(Ok(self.cur))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        ready!(self.poll_fill_buf(cx))?;
+
+        if self.buf.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        let bs = self.buf.split_off(0);
+        self.cur += bs.len() as u64;
+
+        self.poll_ahead(cx);
+        Poll::Ready(Some(Ok(bs)))
+    }
+}
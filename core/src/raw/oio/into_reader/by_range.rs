@@ -25,8 +25,8 @@ use std::task::Context;
 use std::task::Poll;
 
 use futures::future::BoxFuture;
-use tokio::io::ReadBuf;
 
+use super::SEEK_DISCARD_WINDOW;
 use crate::raw::*;
 use crate::*;
 
@@ -36,6 +36,10 @@ use crate::*;
 ///
 /// This operation is not zero cost. If the accessor already returns a
 /// seekable reader, please don't use this.
+///
+/// If the underlying reader returns an error, the next read will send a
+/// new range request starting from the already read offset, so that
+/// retries can resume the read instead of starting over.
 pub fn by_range<A: Accessor>(
     acc: Arc<A>,
     path: &str,
@@ -50,6 +54,7 @@ pub fn by_range<A: Accessor>(
         size,
         cur: 0,
         state: State::Reading(reader),
+        if_match: None,
         last_seek_pos: None,
         sink: Vec::new(),
     }
@@ -64,6 +69,8 @@ pub struct RangeReader<A: Accessor> {
     size: u64,
     cur: u64,
     state: State<A::Reader>,
+    /// The etag that all following range requests must match.
+    if_match: Option<String>,
 
     /// Seek operation could return Pending which may lead
     /// `SeekFrom::Current(off)` been input multiple times.
@@ -85,13 +92,24 @@ enum State<R: oio::Read> {
 unsafe impl<R: oio::Read> Sync for State<R> {}
 
 impl<A: Accessor> RangeReader<A> {
+    /// Send all following range requests with `If-Match: <etag>` so that
+    /// we will get an error instead of mixing content of different versions
+    /// if the object has been changed.
+    pub fn with_if_match(mut self, etag: &str) -> Self {
+        self.if_match = Some(etag.to_string());
+        self
+    }
+
     fn read_future(&self) -> BoxFuture<'static, Result<(RpRead, A::Reader)>> {
         let acc = self.acc.clone();
         let path = self.path.clone();
-        let op = OpRead::default().with_range(BytesRange::new(
+        let mut op = OpRead::default().with_range(BytesRange::new(
             Some(self.offset + self.cur),
             Some(self.size - self.cur),
         ));
+        if let Some(etag) = &self.if_match {
+            op = op.with_if_match(etag);
+        }
 
         Box::pin(async move { acc.read(&path, op).await })
     }
@@ -190,9 +208,7 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
 
                 // If the next seek pos is close enough, we can just
                 // read the cnt instead of dropping the reader.
-                //
-                // TODO: make this value configurable
-                if seek_pos > self.cur && seek_pos - self.cur < 1024 * 1024 {
+                if seek_pos > self.cur && seek_pos - self.cur < SEEK_DISCARD_WINDOW {
                     // 212992 is the default read mem buffer of archlinux.
                     // Ideally we should make this configurable.
                     //
@@ -200,10 +216,13 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
                     let consume = cmp::min((seek_pos - self.cur) as usize, 212992);
                     self.sink.reserve(consume);
 
-                    let mut buf = ReadBuf::uninit(self.sink.spare_capacity_mut());
-                    unsafe { buf.assume_init(consume) };
+                    let dst = self.sink.spare_capacity_mut();
+                    // Safety: the sink has reserved at least `consume` bytes.
+                    let buf = unsafe {
+                        std::slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut u8, consume)
+                    };
 
-                    match ready!(Pin::new(r).poll_read(cx, buf.initialized_mut())) {
+                    match ready!(Pin::new(r).poll_read(cx, buf)) {
                         Ok(n) => {
                             assert!(n > 0, "consumed bytes must be valid");
                             self.cur += n as u64;
@@ -303,11 +322,15 @@ mod tests {
     #[derive(Debug, Clone, Default)]
     struct MockReadService {
         data: Bytes,
+        etag: String,
     }
 
     impl MockReadService {
         fn new(data: Bytes) -> Self {
-            Self { data }
+            Self {
+                data,
+                etag: "etag".to_string(),
+            }
         }
     }
 
@@ -332,12 +355,17 @@ mod tests {
         }
 
         async fn read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            if matches!(args.if_match(), Some(v) if v != self.etag) {
+                return Err(Error::new(ErrorKind::ConditionNotMatch, "etag not match"));
+            }
+
             let bs = args.range().apply_on_bytes(self.data.clone());
 
             Ok((
                 RpRead::new(bs.len() as u64),
                 MockReader {
                     inner: futures::io::Cursor::new(bs.into()),
+                    ..Default::default()
                 },
             ))
         }
@@ -346,10 +374,26 @@ mod tests {
     #[derive(Debug, Clone, Default)]
     struct MockReader {
         inner: futures::io::Cursor<Vec<u8>>,
+        /// Return a temporary error after reading so many bytes.
+        break_after: Option<usize>,
     }
 
     impl oio::Read for MockReader {
         fn poll_read(&mut self, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize>> {
+            let buf = match self.break_after {
+                Some(0) => {
+                    return Poll::Ready(Err(
+                        Error::new(ErrorKind::Unexpected, "connection broken").set_temporary()
+                    ))
+                }
+                Some(n) => {
+                    let n = n.min(buf.len());
+                    self.break_after = Some(self.break_after.unwrap() - n);
+                    &mut buf[..n]
+                }
+                None => buf,
+            };
+
             Pin::new(&mut self.inner).poll_read(cx, buf).map_err(|err| {
                 Error::new(ErrorKind::Unexpected, "read data from mock").set_source(err)
             })
@@ -386,6 +430,7 @@ mod tests {
 
         let r = MockReader {
             inner: futures::io::Cursor::new(bs.to_vec()),
+            ..Default::default()
         };
         let mut r = Box::new(by_range(acc, "x", r, 0, bs.len() as u64)) as oio::Reader;
 
@@ -420,6 +465,7 @@ mod tests {
 
         let r = MockReader {
             inner: futures::io::Cursor::new(bs[4096..4096 + 4096].to_vec()),
+            ..Default::default()
         };
         let mut r = Box::new(by_range(acc, "x", r, 4096, 4096)) as oio::Reader;
 
@@ -468,4 +514,68 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_read_resume() -> anyhow::Result<()> {
+        let (bs, _) = gen_bytes();
+        let acc = Arc::new(MockReadService::new(bs.clone()));
+
+        let r = MockReader {
+            inner: futures::io::Cursor::new(bs.to_vec()),
+            break_after: Some(4096),
+        };
+        let mut r = by_range(acc, "x", r, 0, bs.len() as u64).with_if_match("etag");
+
+        let mut buf = Vec::new();
+        let mut chunk = vec![0; 1024];
+        let err = loop {
+            match oio::ReadExt::read(&mut r, &mut chunk).await {
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                Err(err) => break err,
+            }
+        };
+        assert!(err.is_temporary(), "read must be broken");
+        assert_eq!(buf.len(), 4096);
+
+        // The next read should resume from the already read offset.
+        loop {
+            let n = oio::ReadExt::read(&mut r, &mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        assert_eq!(bs.len(), buf.len(), "read size");
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&bs)),
+            format!("{:x}", Sha256::digest(&buf)),
+            "read content"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_resume_with_changed_etag() -> anyhow::Result<()> {
+        let (bs, _) = gen_bytes();
+        let acc = Arc::new(MockReadService::new(bs.clone()));
+
+        let r = MockReader {
+            inner: futures::io::Cursor::new(bs.to_vec()),
+            break_after: Some(4096),
+        };
+        let mut r = by_range(acc, "x", r, 0, bs.len() as u64).with_if_match("old");
+
+        let mut buf = vec![0; 4096];
+        let n = oio::ReadExt::read(&mut r, &mut buf).await?;
+        assert_eq!(n, 4096);
+        let err = oio::ReadExt::read(&mut r, &mut buf).await.unwrap_err();
+        assert!(err.is_temporary(), "read must be broken");
+
+        // The object has been changed, resume must fail.
+        let err = oio::ReadExt::read(&mut r, &mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        Ok(())
+    }
 }
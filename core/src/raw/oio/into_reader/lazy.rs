@@ -0,0 +1,537 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::future::Future;
+use std::io::SeekFrom;
+use std::mem;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use futures::future;
+use futures::future::BoxFuture;
+
+use super::SEEK_DISCARD_WINDOW;
+use crate::raw::*;
+use crate::*;
+
+/// Convert given accessor into [`oio::Reader`] which sends the read request
+/// only on the first read.
+///
+/// Seeks before the first read will be merged into the read request, so
+/// open-then-seek only sends one request at the right position. Use
+/// [`LazyReader::open`] to send the request right now.
+///
+/// If the service can't seek, short forward seeks will read and discard
+/// bytes instead of sending a new request.
+pub fn lazy<A: Accessor + ?Sized>(acc: Arc<A>, path: &str, op: OpRead) -> LazyReader<A> {
+    let seekable = acc.info().capability().read_can_seek;
+
+    LazyReader {
+        acc,
+        seekable,
+        path: path.to_string(),
+        op,
+        pos: 0,
+        base: 0,
+        discard_to: None,
+        state: State::Idle,
+    }
+}
+
+/// LazyReader that delays the read request until it's required.
+pub struct LazyReader<A: Accessor + ?Sized> {
+    acc: Arc<A>,
+    path: String,
+    op: OpRead,
+    /// Whether the reader returned by service can seek natively.
+    seekable: bool,
+
+    /// The current position of reader.
+    pos: u64,
+    /// The position where the opened reader starts at.
+    base: u64,
+    /// The target of the seek that is discarding bytes, so that relative
+    /// seeks could continue after pending.
+    discard_to: Option<u64>,
+    state: State<A::Reader>,
+}
+
+type ReadFuture<R> = BoxFuture<'static, Result<(RpRead, R)>>;
+
+enum State<R: oio::Read> {
+    Idle,
+    Sending(ReadFuture<R>),
+    /// The reader is opened before `pos`, seeking to `pos`.
+    Seeking(R),
+    Reading(R),
+}
+
+/// Safety: State will only be accessed under &mut.
+unsafe impl<R: oio::Read> Sync for State<R> {}
+
+impl<A: Accessor + ?Sized> LazyReader<A> {
+    /// Send the read request right now instead of on the first read, so
+    /// that errors like `NotFound` will be returned here.
+    pub async fn open(mut self) -> Result<Self> {
+        future::poll_fn(|cx| self.poll_open(cx)).await?;
+        Ok(self)
+    }
+
+    /// Build the read future at `self.pos`.
+    ///
+    /// Returns the start position of the reader and whether we need to
+    /// seek after the reader is opened.
+    fn read_future(&self) -> (u64, ReadFuture<A::Reader>) {
+        let acc = self.acc.clone();
+        let path = self.path.clone();
+        let br = self.op.range();
+
+        let (base, op) = match (br.offset(), br.size()) {
+            _ if self.pos == 0 => (0, self.op.clone()),
+            // We don't know where the tail range starts, read from the
+            // start and seek later.
+            (None, Some(_)) => (0, self.op.clone()),
+            (offset, size) => {
+                let range = BytesRange::new(
+                    Some(offset.unwrap_or_default() + self.pos),
+                    size.map(|v| v.saturating_sub(self.pos)),
+                );
+                (self.pos, self.op.clone().with_range(range))
+            }
+        };
+
+        (base, Box::pin(async move { acc.read(&path, op).await }))
+    }
+
+    /// Returns true if `self.pos` is beyond the range that user specified.
+    fn is_eof(&self) -> bool {
+        match self.op.range().size() {
+            Some(size) => self.op.range().offset().is_some() && self.pos >= size,
+            None => false,
+        }
+    }
+
+    fn seek_pos(&self, pos: SeekFrom) -> Result<u64> {
+        let (base, amt) = match pos {
+            SeekFrom::Start(n) => (0, n as i64),
+            SeekFrom::Current(n) => (self.pos as i64, n),
+            SeekFrom::End(_) => unreachable!("seek from end must be handled by reader"),
+        };
+
+        match base.checked_add(amt) {
+            Some(n) if n >= 0 => Ok(n as u64),
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    /// Make sure the reader has been opened at `self.pos`.
+    fn poll_open(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match &mut self.state {
+            State::Idle => {
+                let (base, fut) = self.read_future();
+                self.base = base;
+                self.state = State::Sending(fut);
+                self.poll_open(cx)
+            }
+            State::Sending(fut) => {
+                let (_, r) = ready!(Pin::new(fut).poll(cx)).map_err(|err| {
+                    // If read future returns an error, we should reset
+                    // state to Idle so that we can retry it.
+                    self.state = State::Idle;
+                    err
+                })?;
+
+                if self.base == self.pos {
+                    self.state = State::Reading(r);
+                    return Poll::Ready(Ok(()));
+                }
+                self.state = State::Seeking(r);
+                self.poll_open(cx)
+            }
+            State::Seeking(r) => {
+                let pos = SeekFrom::Start(self.pos - self.base);
+                if let Err(err) = ready!(oio::Read::poll_seek(r, cx, pos)) {
+                    self.state = State::Idle;
+                    return Poll::Ready(Err(err));
+                }
+
+                self.state = match mem::replace(&mut self.state, State::Idle) {
+                    State::Seeking(r) => State::Reading(r),
+                    _ => unreachable!("state must be seeking"),
+                };
+                Poll::Ready(Ok(()))
+            }
+            State::Reading(_) => Poll::Ready(Ok(())),
+        }
+    }
+}
+
+impl<A: Accessor + ?Sized> oio::Read for LazyReader<A> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        // The pending seek has been abandoned.
+        self.discard_to = None;
+        if matches!(self.state, State::Idle) && self.is_eof() {
+            return Poll::Ready(Ok(0));
+        }
+
+        ready!(self.poll_open(cx))?;
+
+        match &mut self.state {
+            State::Reading(r) => {
+                let n = ready!(Pin::new(r).poll_read(cx, buf))?;
+                self.pos += n as u64;
+                Poll::Ready(Ok(n))
+            }
+            _ => unreachable!("reader must be opened"),
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        if let SeekFrom::End(_) = pos {
+            // We need the reader to know where the end is, and it should
+            // start at the beginning so that we can seek to any position.
+            if matches!(self.state, State::Idle) || self.base != 0 {
+                self.state = State::Idle;
+                self.pos = 0;
+            }
+            ready!(self.poll_open(cx))?;
+
+            return match &mut self.state {
+                State::Reading(r) => {
+                    let n = ready!(oio::Read::poll_seek(r, cx, pos))?;
+                    self.pos = n + self.base;
+                    Poll::Ready(Ok(self.pos))
+                }
+                _ => unreachable!("reader must be opened"),
+            };
+        }
+
+        let n = match self.discard_to.take() {
+            Some(n) => n,
+            None => self.seek_pos(pos)?,
+        };
+        match &mut self.state {
+            State::Reading(r)
+                if !self.seekable && n > self.pos && n - self.pos < SEEK_DISCARD_WINDOW =>
+            {
+                // Discard the bytes in between, `self.pos` keeps the progress
+                // so that we can continue after pending.
+                let mut buf = [0; 8 * 1024];
+                while self.pos < n {
+                    let size = (n - self.pos).min(buf.len() as u64) as usize;
+                    let read = match oio::Read::poll_read(r, cx, &mut buf[..size]) {
+                        Poll::Ready(res) => res?,
+                        Poll::Pending => {
+                            self.discard_to = Some(n);
+                            return Poll::Pending;
+                        }
+                    };
+                    // Seeking beyond the end is allowed, following reads
+                    // will return EOF.
+                    if read == 0 {
+                        break;
+                    }
+                    self.pos += read as u64;
+                }
+                self.pos = n;
+            }
+            State::Reading(r) if n >= self.base => {
+                ready!(oio::Read::poll_seek(r, cx, SeekFrom::Start(n - self.base)))?;
+                self.pos = n;
+            }
+            State::Reading(_) => {
+                // The reader can't seek before where it starts, drop it
+                // and read again.
+                self.state = State::Idle;
+                self.pos = n;
+            }
+            _ => {
+                if n != self.pos {
+                    // Drop the sending request if the position changed.
+                    self.state = State::Idle;
+                    self.pos = n;
+                }
+            }
+        }
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        // The pending seek has been abandoned.
+        self.discard_to = None;
+        if matches!(self.state, State::Idle) && self.is_eof() {
+            return Poll::Ready(None);
+        }
+
+        ready!(self.poll_open(cx))?;
+
+        match &mut self.state {
+            State::Reading(r) => {
+                let bs = ready!(Pin::new(r).poll_next(cx));
+                if let Some(Ok(bs)) = &bs {
+                    self.pos += bs.len() as u64;
+                }
+                Poll::Ready(bs)
+            }
+            _ => unreachable!("reader must be opened"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use async_trait::async_trait;
+    use futures::AsyncRead;
+    use futures::AsyncReadExt;
+    use futures::AsyncSeek;
+    use futures::AsyncSeekExt;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockReadService {
+        data: Bytes,
+        seekable: bool,
+        reads: AtomicUsize,
+        seeks: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockReadService {
+        type Reader = MockReader;
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Appender = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capability(Capability {
+                read: true,
+                read_can_seek: self.seekable,
+                ..Default::default()
+            });
+
+            am
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            if path != "file" {
+                return Err(Error::new(ErrorKind::NotFound, "file not found"));
+            }
+
+            let bs = args.range().apply_on_bytes(self.data.clone());
+            Ok((
+                RpRead::new(bs.len() as u64),
+                MockReader {
+                    inner: futures::io::Cursor::new(bs.into()),
+                    seeks: self.seeks.clone(),
+                    pending: false,
+                },
+            ))
+        }
+    }
+
+    struct MockReader {
+        inner: futures::io::Cursor<Vec<u8>>,
+        seeks: Arc<AtomicUsize>,
+        /// Return pending on every other read like a real network reader.
+        pending: bool,
+    }
+
+    impl oio::Read for MockReader {
+        fn poll_read(&mut self, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize>> {
+            self.pending = !self.pending;
+            if self.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+
+            Pin::new(&mut self.inner).poll_read(cx, buf).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "read data from mock").set_source(err)
+            })
+        }
+
+        fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+            self.seeks.fetch_add(1, Ordering::SeqCst);
+            Pin::new(&mut self.inner).poll_seek(cx, pos).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "seek data from mock").set_source(err)
+            })
+        }
+
+        fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            let mut bs = vec![0; 4 * 1024];
+            let n = ready!(oio::Read::poll_read(self, cx, &mut bs))?;
+            if n == 0 {
+                Poll::Ready(None)
+            } else {
+                Poll::Ready(Some(Ok(Bytes::from(bs[..n].to_vec()))))
+            }
+        }
+    }
+
+    fn new_service(size: usize) -> (Arc<MockReadService>, Bytes) {
+        let data = Bytes::from((0..size).map(|v| v as u8).collect::<Vec<_>>());
+        let acc = Arc::new(MockReadService {
+            data: data.clone(),
+            ..Default::default()
+        });
+        (acc, data)
+    }
+
+    #[tokio::test]
+    async fn test_seek_before_read() -> anyhow::Result<()> {
+        let (acc, data) = new_service(1024);
+
+        let mut r = Box::new(lazy(acc.clone(), "file", OpRead::new())) as oio::Reader;
+        r.seek(SeekFrom::Start(100)).await?;
+        r.seek(SeekFrom::Current(100)).await?;
+
+        let mut bs = vec![0; 10];
+        r.read_exact(&mut bs).await?;
+        assert_eq!(bs, data[200..210]);
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 1);
+        assert_eq!(acc.seeks.load(Ordering::SeqCst), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seek_within_discard_window() -> anyhow::Result<()> {
+        let size = 4 * SEEK_DISCARD_WINDOW as usize;
+        let (acc, data) = new_service(size);
+
+        let mut r = Box::new(lazy(acc.clone(), "file", OpRead::new())) as oio::Reader;
+        let mut bs = vec![0; 10];
+        r.read_exact(&mut bs).await?;
+        assert_eq!(bs, data[..10]);
+
+        // Seek ahead inside the window should discard bytes.
+        let pos = r
+            .seek(SeekFrom::Current(SEEK_DISCARD_WINDOW as i64 - 1))
+            .await?;
+        assert_eq!(pos, 9 + SEEK_DISCARD_WINDOW);
+        r.read_exact(&mut bs).await?;
+        let pos = pos as usize;
+        assert_eq!(bs, data[pos..pos + 10]);
+
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 1, "no second read");
+        assert_eq!(acc.seeks.load(Ordering::SeqCst), 0, "no seek");
+
+        // Seek ahead beyond the window should seek the reader.
+        let pos = r.seek(SeekFrom::Start(size as u64 - 10)).await?;
+        r.read_exact(&mut bs).await?;
+        assert_eq!(bs, data[pos as usize..]);
+        assert_eq!(acc.seeks.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seek_seekable_reader() -> anyhow::Result<()> {
+        let data = Bytes::from((0..1024).map(|v| v as u8).collect::<Vec<_>>());
+        let acc = Arc::new(MockReadService {
+            data: data.clone(),
+            seekable: true,
+            ..Default::default()
+        });
+
+        let mut r = Box::new(lazy(acc.clone(), "file", OpRead::new())) as oio::Reader;
+        let mut bs = vec![0; 10];
+        r.read_exact(&mut bs).await?;
+
+        // Seekable reader should seek directly instead of discarding bytes.
+        let pos = r.seek(SeekFrom::Current(100)).await?;
+        assert_eq!(pos, 110);
+        r.read_exact(&mut bs).await?;
+        assert_eq!(bs, data[110..120]);
+
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 1, "no second read");
+        assert_eq!(acc.seeks.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seek_discard_beyond_end() -> anyhow::Result<()> {
+        let (acc, _) = new_service(1024);
+
+        let mut r = Box::new(lazy(acc.clone(), "file", OpRead::new())) as oio::Reader;
+        let mut bs = vec![0; 10];
+        r.read_exact(&mut bs).await?;
+
+        let pos = r.seek(SeekFrom::Start(2048)).await?;
+        assert_eq!(pos, 2048);
+        assert_eq!(r.read(&mut bs).await?, 0);
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_open() -> anyhow::Result<()> {
+        let (acc, data) = new_service(1024);
+
+        // Errors should be returned while opening.
+        let err = lazy(acc.clone(), "not_exist", OpRead::new())
+            .open()
+            .await
+            .err()
+            .expect("open must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let r = lazy(acc.clone(), "file", OpRead::new()).open().await?;
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 2);
+
+        let mut r = Box::new(r) as oio::Reader;
+        let mut bs = Vec::new();
+        r.read_to_end(&mut bs).await?;
+        assert_eq!(bs, data);
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_next_after_seek() -> anyhow::Result<()> {
+        let (acc, data) = new_service(1024);
+
+        let mut r = Box::new(lazy(acc.clone(), "file", OpRead::new())) as oio::Reader;
+        r.seek(SeekFrom::Start(1000)).await?;
+
+        let mut bs = Vec::new();
+        while let Some(chunk) = oio::ReadExt::next(&mut r).await {
+            bs.extend_from_slice(&chunk?);
+        }
+        assert_eq!(bs, data[1000..]);
+        assert!(
+            oio::ReadExt::next(&mut r).await.is_none(),
+            "stream must be empty at the end"
+        );
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+}
@@ -37,10 +37,22 @@
 //! user call `poll_read` first, we can get the total_size from returning
 //! reader. In this way, we can save 40ms in average for every s3 read call.
 
+/// Forward seeks within this window will read and discard bytes instead
+/// of sending a new request if the reader can't seek.
+const SEEK_DISCARD_WINDOW: u64 = 1024 * 1024;
+
 mod by_range;
 pub use by_range::by_range;
 pub use by_range::RangeReader;
 
+mod lazy;
+pub use lazy::lazy;
+pub use lazy::LazyReader;
+
+mod by_prefetch;
+pub use by_prefetch::by_prefetch;
+pub use by_prefetch::PrefetchReader;
+
 mod from_fd;
 pub use from_fd::from_fd;
 pub use from_fd::FdReader;
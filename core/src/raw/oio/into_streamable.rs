@@ -16,12 +16,13 @@
 // under the License.
 
 use std::io::SeekFrom;
+use std::mem;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 
 use bytes::Bytes;
-use tokio::io::ReadBuf;
+use bytes::BytesMut;
 
 use crate::raw::*;
 use crate::*;
@@ -31,15 +32,82 @@ pub fn into_streamable_reader<R>(r: R, capacity: usize) -> IntoStreamableReader<
     IntoStreamableReader {
         r,
         cap: capacity,
-        buf: Vec::with_capacity(capacity),
+        buf: BytesMut::with_capacity(capacity),
+        pool: None,
+    }
+}
+
+/// Make given read streamable with the buffer from [`BufferPool`].
+///
+/// The buffer will be returned back to the pool after reader dropped.
+pub fn into_streamable_reader_with_pool<R>(r: R, pool: &BufferPool) -> IntoStreamableReader<R> {
+    IntoStreamableReader {
+        r,
+        cap: pool.chunk_size(),
+        buf: pool.get(),
+        pool: Some(pool.clone()),
     }
 }
 
 /// Make given read streamable.
+///
+/// Bytes are read into the spare capacity of an internal [`BytesMut`] and
+/// split out without copying. The allocation will be reclaimed once all
+/// returned bytes have been dropped, otherwise a new buffer will be used.
 pub struct IntoStreamableReader<R> {
     r: R,
     cap: usize,
-    buf: Vec<u8>,
+    buf: BytesMut,
+    pool: Option<BufferPool>,
+}
+
+impl<R> IntoStreamableReader<R> {
+    /// Make sure there is enough spare capacity to read into.
+    ///
+    /// The spare capacity left by previous reads will be used until it's
+    /// less than a quarter of `cap`. Then the whole allocation will be
+    /// reclaimed by `reserve` if all returned bytes have been dropped,
+    /// otherwise a new buffer will be allocated.
+    fn reserve(&mut self) {
+        if self.buf.capacity() < (self.cap / 4).max(1) {
+            self.buf.reserve(self.cap);
+        }
+    }
+
+    /// Read into the spare capacity and split the filled bytes out.
+    fn poll_split<F>(&mut self, read: F) -> Poll<Option<Result<Bytes>>>
+    where
+        F: FnOnce(&mut R, &mut [u8]) -> Poll<Result<usize>>,
+    {
+        self.reserve();
+
+        let size = self.buf.capacity().min(self.cap);
+        let dst = &mut self.buf.spare_capacity_mut()[..size];
+        // Safety: the buffer has at least `size` bytes of spare capacity.
+        let buf = unsafe { std::slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut u8, size) };
+
+        match ready!(read(&mut self.r, buf)) {
+            Err(err) => Poll::Ready(Some(Err(err))),
+            Ok(0) => Poll::Ready(None),
+            Ok(n) => {
+                // Safety: the first n bytes have been filled by reader.
+                unsafe { self.buf.set_len(n) };
+                Poll::Ready(Some(Ok(self.buf.split().freeze())))
+            }
+        }
+    }
+}
+
+impl<R> Drop for IntoStreamableReader<R> {
+    fn drop(&mut self) {
+        if let Some(pool) = &self.pool {
+            // Returned bytes may still refer to the allocation, `reserve`
+            // makes sure the buffer put back is owned by the pool alone.
+            let mut buf = mem::take(&mut self.buf);
+            buf.reserve(self.cap);
+            pool.put(buf);
+        }
+    }
 }
 
 impl<R: oio::Read> oio::Read for IntoStreamableReader<R> {
@@ -52,18 +120,7 @@ impl<R: oio::Read> oio::Read for IntoStreamableReader<R> {
     }
 
     fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
-        let dst = self.buf.spare_capacity_mut();
-        let mut buf = ReadBuf::uninit(dst);
-        unsafe { buf.assume_init(self.cap) };
-
-        match ready!(self.r.poll_read(cx, buf.initialized_mut())) {
-            Err(err) => Poll::Ready(Some(Err(err))),
-            Ok(0) => Poll::Ready(None),
-            Ok(n) => {
-                buf.set_filled(n);
-                Poll::Ready(Some(Ok(Bytes::from(buf.filled().to_vec()))))
-            }
-        }
+        self.poll_split(|r, buf| r.poll_read(cx, buf))
     }
 }
 
@@ -77,17 +134,9 @@ impl<R: oio::BlockingRead> oio::BlockingRead for IntoStreamableReader<R> {
     }
 
     fn next(&mut self) -> Option<Result<Bytes>> {
-        let dst = self.buf.spare_capacity_mut();
-        let mut buf = ReadBuf::uninit(dst);
-        unsafe { buf.assume_init(self.cap) };
-
-        match self.r.read(buf.initialized_mut()) {
-            Err(err) => Some(Err(err)),
-            Ok(0) => None,
-            Ok(n) => {
-                buf.set_filled(n);
-                Some(Ok(Bytes::from(buf.filled().to_vec())))
-            }
+        match self.poll_split(|r, buf| Poll::Ready(r.read(buf))) {
+            Poll::Ready(v) => v,
+            Poll::Pending => unreachable!("blocking read must be ready"),
         }
     }
 }
@@ -145,4 +194,51 @@ mod tests {
         }
         assert_eq!(bs.freeze().to_vec(), content)
     }
+
+    #[test]
+    fn test_into_stream_reclaim() {
+        use oio::BlockingRead;
+
+        let content: Vec<u8> = (0..16 * 1024).map(|v| v as u8).collect();
+        let r = oio::Cursor::from(content.clone());
+        let mut s = into_streamable_reader(Box::new(r) as oio::BlockingReader, 1024);
+
+        // The allocation will be reclaimed if returned bytes are dropped.
+        let ptr = s.next().unwrap().unwrap().as_ptr();
+        assert_eq!(s.next().unwrap().unwrap().as_ptr(), ptr);
+
+        // Returned bytes that are still alive must not be overwritten.
+        let mut chunks = vec![s.next().unwrap().unwrap()];
+        while let Some(b) = s.next() {
+            chunks.push(b.expect("read must success"));
+        }
+        assert_eq!(chunks.concat(), content[2048..]);
+    }
+
+    #[test]
+    fn test_into_stream_with_pool() {
+        use oio::BlockingRead;
+
+        let pool = BufferPool::new(1024);
+        let content: Vec<u8> = (0..4096).map(|v| v as u8).collect();
+
+        let r = oio::Cursor::from(content.clone());
+        let mut s = into_streamable_reader_with_pool(Box::new(r) as oio::BlockingReader, &pool);
+        let mut bs = BytesMut::new();
+        let mut ptr = None;
+        while let Some(b) = s.next() {
+            let b = b.expect("read must success");
+            ptr = Some(b.as_ptr());
+            bs.put_slice(&b);
+        }
+        assert_eq!(bs.freeze().to_vec(), content);
+        drop(s);
+
+        // The next read should reuse the buffer returned by previous reader.
+        let r = oio::Cursor::from(content.clone());
+        let mut s = into_streamable_reader_with_pool(Box::new(r) as oio::BlockingReader, &pool);
+        let b = s.next().unwrap().expect("read must success");
+        assert_eq!(Some(b.as_ptr()), ptr, "buffer must be reused");
+        assert_eq!(b, content[..1024]);
+    }
 }
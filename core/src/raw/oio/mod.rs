@@ -57,6 +57,7 @@ pub use cursor::VectorCursor;
 
 mod into_streamable;
 pub use into_streamable::into_streamable_reader;
+pub use into_streamable::into_streamable_reader_with_pool;
 pub use into_streamable::IntoStreamableReader;
 
 mod entry;
@@ -69,6 +70,10 @@ pub use page::Page;
 pub use page::PageOperation;
 pub use page::Pager;
 
+mod to_concurrent_pager;
+pub use to_concurrent_pager::to_concurrent_pager;
+pub use to_concurrent_pager::ConcurrentPager;
+
 mod to_flat_pager;
 pub use to_flat_pager::to_flat_pager;
 pub use to_flat_pager::ToFlatPager;
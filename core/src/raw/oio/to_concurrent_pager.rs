@@ -0,0 +1,145 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::FutureExt;
+use futures::TryStreamExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// to_concurrent_pager is used to scan a prefix by splitting it into
+/// sub-prefixes and listing them concurrently.
+///
+/// The input pager must be a hierarchy pager of `path`, every dir it
+/// returned will be scanned by a flat pager with given `args`.
+pub fn to_concurrent_pager<A: Accessor + Clone>(
+    acc: A,
+    path: &str,
+    pager: A::Pager,
+    args: OpList,
+) -> ConcurrentPager<A> {
+    ConcurrentPager {
+        acc,
+        root: path.to_string(),
+        concurrent: args.concurrent().max(1),
+        args,
+        pager: Some(pager),
+        dirs: VecDeque::new(),
+        futs: FuturesUnordered::new(),
+    }
+}
+
+#[allow(clippy::type_complexity)]
+type PageFuture<P> = BoxFuture<'static, Result<(String, P, Option<Vec<oio::Entry>>)>>;
+
+/// ConcurrentPager will list the top level of given path first, and then
+/// scan all sub-prefixes concurrently with at most `concurrent` requests
+/// in flight.
+///
+/// # Note
+///
+/// There is no guarantee about the order of returned entries, pages of
+/// different sub-prefixes will be returned as soon as they are ready.
+pub struct ConcurrentPager<A: Accessor> {
+    acc: A,
+    root: String,
+    concurrent: usize,
+    args: OpList,
+
+    pager: Option<A::Pager>,
+    dirs: VecDeque<String>,
+    futs: FuturesUnordered<PageFuture<A::Pager>>,
+}
+
+/// # Safety
+///
+/// ConcurrentPager will only be accessed by `&mut Self`
+unsafe impl<A: Accessor> Sync for ConcurrentPager<A> {}
+
+impl<A: Accessor + Clone> ConcurrentPager<A> {
+    fn submit_dir(&mut self, dir: String) {
+        let acc = self.acc.clone();
+        let args = self.args.clone();
+
+        self.futs.push(
+            async move {
+                let (_, mut pager) = acc.list(&dir, args).await?;
+                let entries = oio::Page::next(&mut pager).await?;
+                Ok((dir, pager, entries))
+            }
+            .boxed(),
+        );
+    }
+
+    fn submit_page(&mut self, dir: String, mut pager: A::Pager) {
+        self.futs.push(
+            async move {
+                let entries = oio::Page::next(&mut pager).await?;
+                Ok((dir, pager, entries))
+            }
+            .boxed(),
+        );
+    }
+}
+
+#[async_trait]
+impl<A: Accessor + Clone> oio::Page for ConcurrentPager<A> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        loop {
+            if let Some(pager) = self.pager.as_mut() {
+                match pager.next().await? {
+                    Some(mut entries) => {
+                        entries.retain(|v| v.path() != self.root);
+                        for de in entries.iter().filter(|v| v.mode().is_dir()) {
+                            self.dirs.push_back(de.path().to_string());
+                        }
+                        if !entries.is_empty() {
+                            return Ok(Some(entries));
+                        }
+                    }
+                    None => self.pager = None,
+                }
+                continue;
+            }
+
+            while self.futs.len() < self.concurrent {
+                match self.dirs.pop_front() {
+                    Some(dir) => self.submit_dir(dir),
+                    None => break,
+                }
+            }
+
+            let (dir, pager, entries) = match self.futs.try_next().await? {
+                Some(v) => v,
+                None => return Ok(None),
+            };
+            if let Some(mut entries) = entries {
+                // The dir itself has been returned while listing top level.
+                entries.retain(|v| v.path() != dir);
+                self.submit_page(dir, pager);
+                if !entries.is_empty() {
+                    return Ok(Some(entries));
+                }
+            }
+        }
+    }
+}
@@ -47,6 +47,14 @@ pub enum Operation {
     Batch,
     /// Operation for [`crate::raw::Accessor::presign`]
     Presign,
+    /// Operation for [`crate::raw::Accessor::restore`]
+    Restore,
+    /// Operation for [`crate::raw::Accessor::select`]
+    Select,
+    /// Operation for [`crate::raw::Accessor::write_at`]
+    WriteAt,
+    /// Operation for [`crate::raw::Accessor::truncate`]
+    Truncate,
     /// Operation for [`crate::raw::Accessor::blocking_create_dir`]
     BlockingCreateDir,
     /// Operation for [`crate::raw::Accessor::blocking_read`]
@@ -63,6 +71,10 @@ pub enum Operation {
     BlockingDelete,
     /// Operation for [`crate::raw::Accessor::blocking_list`]
     BlockingList,
+    /// Operation for [`crate::raw::Accessor::blocking_write_at`]
+    BlockingWriteAt,
+    /// Operation for [`crate::raw::Accessor::blocking_truncate`]
+    BlockingTruncate,
 }
 
 impl Operation {
@@ -93,6 +105,10 @@ impl From<Operation> for &'static str {
             Operation::List => "list",
             Operation::Presign => "presign",
             Operation::Batch => "batch",
+            Operation::Restore => "restore",
+            Operation::Select => "select",
+            Operation::WriteAt => "write_at",
+            Operation::Truncate => "truncate",
             Operation::BlockingCreateDir => "blocking_create_dir",
             Operation::BlockingRead => "blocking_read",
             Operation::BlockingWrite => "blocking_write",
@@ -101,6 +117,8 @@ impl From<Operation> for &'static str {
             Operation::BlockingStat => "blocking_stat",
             Operation::BlockingDelete => "blocking_delete",
             Operation::BlockingList => "blocking_list",
+            Operation::BlockingWriteAt => "blocking_write_at",
+            Operation::BlockingTruncate => "blocking_truncate",
         }
     }
 }
@@ -19,6 +19,7 @@
 //!
 //! By using ops, users can add more context for operation.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
 use crate::raw::*;
@@ -40,12 +41,40 @@ impl OpCreateDir {
 ///
 /// The path must be normalized.
 #[derive(Debug, Clone, Default)]
-pub struct OpDelete {}
+pub struct OpDelete {
+    if_match: Option<String>,
+    version: Option<String>,
+}
 
 impl OpDelete {
     /// Create a new `OpDelete`.
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Set the If-Match of the option
+    ///
+    /// The delete will fail with `ConditionNotMatch` if the etag of
+    /// the object doesn't match.
+    pub fn with_if_match(mut self, if_match: &str) -> Self {
+        self.if_match = Some(if_match.to_string());
+        self
+    }
+
+    /// Get If-Match from option
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Set the version of the object to delete.
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Get version from option
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
     }
 }
 
@@ -62,6 +91,9 @@ pub struct OpList {
 
     /// The delimiter used to for the list operation. Default to be `/`
     delimiter: String,
+
+    /// The max sub-prefixes could be scanned concurrently. Default to be `1`
+    concurrent: usize,
 }
 
 impl Default for OpList {
@@ -70,6 +102,7 @@ impl Default for OpList {
             limit: None,
             start_after: None,
             delimiter: "/".to_string(),
+            concurrent: 1,
         }
     }
 }
@@ -112,6 +145,17 @@ impl OpList {
     pub fn delimiter(&self) -> &str {
         &self.delimiter
     }
+
+    /// Change the concurrent of this list operation.
+    pub fn with_concurrent(mut self, concurrent: usize) -> Self {
+        self.concurrent = concurrent;
+        self
+    }
+
+    /// Get the concurrent of list operation.
+    pub fn concurrent(&self) -> usize {
+        self.concurrent
+    }
 }
 
 /// Args for `presign` operation.
@@ -174,6 +218,146 @@ impl From<OpWrite> for PresignOperation {
     }
 }
 
+/// Args for `restore` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpRestore {
+    days: Option<u32>,
+    tier: Option<String>,
+}
+
+impl OpRestore {
+    /// Create a new `OpRestore`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the days that the restored copy will be kept.
+    pub fn with_days(mut self, days: u32) -> Self {
+        self.days = Some(days);
+        self
+    }
+
+    /// Get the days from option
+    pub fn days(&self) -> Option<u32> {
+        self.days
+    }
+
+    /// Set the tier of the restore, for example `Standard`, `Bulk` or
+    /// `Expedited` for s3.
+    pub fn with_tier(mut self, tier: &str) -> Self {
+        self.tier = Some(tier.to_string());
+        self
+    }
+
+    /// Get the tier from option
+    pub fn tier(&self) -> Option<&str> {
+        self.tier.as_deref()
+    }
+}
+
+/// Args for `write_at` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpWriteAt {
+    offset: u64,
+}
+
+impl OpWriteAt {
+    /// Create a new `OpWriteAt` with the offset to write at.
+    pub fn new(offset: u64) -> Self {
+        Self { offset }
+    }
+
+    /// Get the offset from option
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+/// Args for `truncate` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpTruncate {
+    size: u64,
+}
+
+impl OpTruncate {
+    /// Create a new `OpTruncate` with the size to truncate to.
+    pub fn new(size: u64) -> Self {
+        Self { size }
+    }
+
+    /// Get the size from option
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// Format of the input and output data of `select` operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum SelectFormat {
+    /// CSV with the first line as header, so that columns can be
+    /// referenced by name.
+    #[default]
+    Csv,
+    /// JSON in lines, a.k.a, every line is a JSON object.
+    Json,
+    /// Parquet, only could be used as input.
+    Parquet,
+}
+
+/// Args for `select` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpSelect {
+    expression: String,
+    input_format: SelectFormat,
+    output_format: SelectFormat,
+}
+
+impl OpSelect {
+    /// Create a new `OpSelect` with given SQL expression.
+    pub fn new(expression: &str) -> Self {
+        Self {
+            expression: expression.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Get the SQL expression from option
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Set the format of the object to select from.
+    pub fn with_input_format(mut self, format: SelectFormat) -> Self {
+        self.input_format = format;
+        self
+    }
+
+    /// Get the input format from option
+    pub fn input_format(&self) -> SelectFormat {
+        self.input_format
+    }
+
+    /// Set the format of the returning records.
+    pub fn with_output_format(mut self, format: SelectFormat) -> Self {
+        self.output_format = format;
+        self
+    }
+
+    /// Get the output format from option
+    pub fn output_format(&self) -> SelectFormat {
+        self.output_format
+    }
+}
+
 /// Args for `batch` operation.
 #[derive(Debug, Clone)]
 pub struct OpBatch {
@@ -229,6 +413,9 @@ pub struct OpRead {
     if_none_match: Option<String>,
     override_cache_control: Option<String>,
     override_content_disposition: Option<String>,
+    prefetch: Option<(usize, usize)>,
+    size_hint: Option<u64>,
+    lazy: bool,
 }
 
 impl OpRead {
@@ -292,6 +479,44 @@ impl OpRead {
     pub fn if_none_match(&self) -> Option<&str> {
         self.if_none_match.as_deref()
     }
+
+    /// Set the prefetch of the option: fetch at most `window` chunks of
+    /// `chunk_size` ahead while reading.
+    pub fn with_prefetch(mut self, chunk_size: usize, window: usize) -> Self {
+        self.prefetch = Some((chunk_size, window));
+        self
+    }
+
+    /// Get the prefetch `(chunk_size, window)` from option.
+    pub fn prefetch(&self) -> Option<(usize, usize)> {
+        self.prefetch
+    }
+
+    /// Set the size hint of the option: the known total size of the file.
+    ///
+    /// Reading paths that need the total size (like suffix range or
+    /// prefetch) will use this hint instead of sending an extra stat.
+    pub fn with_size_hint(mut self, size: u64) -> Self {
+        self.size_hint = Some(size);
+        self
+    }
+
+    /// Get the size hint from option.
+    pub fn size_hint(&self) -> Option<u64> {
+        self.size_hint
+    }
+
+    /// Set the lazy of the option: send the read request on the first
+    /// read instead of while creating the reader.
+    pub fn with_lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    /// Get the lazy from option.
+    pub fn lazy(&self) -> bool {
+        self.lazy
+    }
 }
 
 /// Args for `stat` operation.
@@ -337,6 +562,11 @@ pub struct OpWrite {
     content_type: Option<String>,
     content_disposition: Option<String>,
     cache_control: Option<String>,
+    storage_class: Option<String>,
+    acl: Option<String>,
+    permission: Option<u32>,
+    user_metadata: Option<HashMap<String, String>>,
+    buffer_pool: Option<BufferPool>,
 }
 
 impl OpWrite {
@@ -395,6 +625,72 @@ impl OpWrite {
         self.cache_control = Some(cache_control.to_string());
         self
     }
+
+    /// Get the storage class from option
+    pub fn storage_class(&self) -> Option<&str> {
+        self.storage_class.as_deref()
+    }
+
+    /// Set the storage class of option
+    ///
+    /// Storage class is service specific, for example `STANDARD_IA` or
+    /// `GLACIER_IR` for s3.
+    pub fn with_storage_class(mut self, storage_class: &str) -> Self {
+        self.storage_class = Some(storage_class.to_string());
+        self
+    }
+
+    /// Get the acl from option
+    pub fn acl(&self) -> Option<&str> {
+        self.acl.as_deref()
+    }
+
+    /// Set the acl of option
+    ///
+    /// Acl is service specific, for example canned acl like `private` or
+    /// `bucket-owner-full-control` for s3.
+    pub fn with_acl(mut self, acl: &str) -> Self {
+        self.acl = Some(acl.to_string());
+        self
+    }
+
+    /// Get the permission from option
+    pub fn permission(&self) -> Option<u32> {
+        self.permission
+    }
+
+    /// Set the permission of option
+    ///
+    /// Permission is the POSIX permission bits like `0o644`.
+    pub fn with_permission(mut self, permission: u32) -> Self {
+        self.permission = Some(permission);
+        self
+    }
+
+    /// Get the user metadata from option
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        self.user_metadata.as_ref()
+    }
+
+    /// Set the user metadata of option
+    ///
+    /// Existing user metadata of the path will be replaced.
+    pub fn with_user_metadata(mut self, user_metadata: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(user_metadata);
+        self
+    }
+
+    /// Get the buffer pool from option.
+    pub fn buffer_pool(&self) -> Option<&BufferPool> {
+        self.buffer_pool.as_ref()
+    }
+
+    /// Set the buffer pool of the option, writers that need to buffer
+    /// content will take buffers from it.
+    pub fn with_buffer_pool(mut self, pool: BufferPool) -> Self {
+        self.buffer_pool = Some(pool);
+        self
+    }
 }
 
 /// Args for `append` operation.
@@ -446,14 +742,63 @@ impl OpAppend {
 }
 
 /// Args for `copy` operation.
+///
+/// # Notes
+///
+/// By default, copy will preserve all metadata of the source object.
+/// If any of `content_type` and `user_metadata` is set, the metadata
+/// of the destination object will be replaced by the given values
+/// instead of copied from source. The same for `tags`.
 #[derive(Debug, Clone, Default)]
-pub struct OpCopy {}
+pub struct OpCopy {
+    content_type: Option<String>,
+    user_metadata: Option<HashMap<String, String>>,
+    tags: Option<HashMap<String, String>>,
+}
 
 impl OpCopy {
     /// Create a new `OpCopy`.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Get the content type from option
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Set the content type of the destination object.
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Get the user metadata from option
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        self.user_metadata.as_ref()
+    }
+
+    /// Set the user metadata of the destination object.
+    pub fn with_user_metadata(mut self, user_metadata: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(user_metadata);
+        self
+    }
+
+    /// Get the tags from option
+    pub fn tags(&self) -> Option<&HashMap<String, String>> {
+        self.tags.as_ref()
+    }
+
+    /// Set the tags of the destination object.
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Check if the metadata of the destination object should be replaced.
+    pub fn is_metadata_replaced(&self) -> bool {
+        self.content_type.is_some() || self.user_metadata.is_some()
+    }
 }
 
 /// Args for `rename` operation.
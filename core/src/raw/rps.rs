@@ -27,6 +27,22 @@ pub struct RpCreateDir {}
 #[derive(Debug, Clone, Default)]
 pub struct RpDelete {}
 
+/// Reply for `restore` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpRestore {}
+
+/// Reply for `write_at` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpWriteAt {}
+
+/// Reply for `truncate` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpTruncate {}
+
+/// Reply for `select` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpSelect {}
+
 /// Reply for `list` operation.
 #[derive(Debug, Clone, Default)]
 pub struct RpList {}
@@ -0,0 +1,68 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::future::Future;
+use std::time::Duration;
+
+use futures::future::select;
+use futures::future::Either;
+
+/// Future returned by [`sleep`].
+#[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+pub type Sleep = tokio::time::Sleep;
+/// Future returned by [`sleep`].
+#[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+pub type Sleep = futures_timer::Delay;
+
+/// Wait until `dur` has elapsed.
+///
+/// Timers of tokio are used if feature `tokio` is enabled. Otherwise, and
+/// on wasm32 where timers of tokio are not available, `futures-timer` will
+/// be used which runs timers of the js runtime on wasm32 and a background
+/// thread elsewhere.
+pub fn sleep(dur: Duration) -> Sleep {
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    return tokio::time::sleep(dur);
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+    return futures_timer::Delay::new(dur);
+}
+
+/// Wait for `fut` to complete before `dur` has elapsed.
+///
+/// Returns `None` if `fut` doesn't complete in time.
+pub async fn timeout<F: Future>(dur: Duration, fut: F) -> Option<F::Output> {
+    let fut = Box::pin(fut);
+    let sleep = Box::pin(sleep(dur));
+
+    match select(fut, sleep).await {
+        Either::Left((v, _)) => Some(v),
+        Either::Right(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let v = timeout(Duration::from_secs(10), async { 1 }).await;
+        assert_eq!(v, Some(1));
+
+        let v = timeout(Duration::from_millis(10), sleep(Duration::from_secs(10))).await;
+        assert_eq!(v, None);
+    }
+}
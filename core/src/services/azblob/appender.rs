@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp::min;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -29,6 +30,12 @@ use crate::*;
 const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
 const X_MS_BLOB_APPEND_OFFSET: &str = "x-ms-blob-append-offset";
 
+/// The max size of a block that could be appended by `Append Block`.
+///
+/// Newer service versions accept up to 100 MiB, we use the smallest
+/// limit to be compatible with all of them including Azurite.
+const AZBLOB_APPEND_BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
 pub struct AzblobAppender {
     core: Arc<AzblobCore>,
 
@@ -47,6 +54,38 @@ impl AzblobAppender {
             position: None,
         }
     }
+
+    async fn append_block(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len();
+
+        let mut req = self.core.azblob_append_blob_request(
+            &self.path,
+            size,
+            self.position,
+            AsyncBody::Bytes(bs),
+        )?;
+
+        self.core.sign(&mut req).await?;
+
+        let resp = self.core.send(req).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::CREATED => {
+                let headers = resp.headers();
+                let position = headers
+                    .get(X_MS_BLOB_APPEND_OFFSET)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok());
+                self.position = position.map(|v| v + size as u64);
+            }
+            _ => {
+                return Err(parse_error(resp).await?);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -63,9 +102,12 @@ impl oio::Append for AzblobAppender {
             let status = resp.status();
 
             match status {
-                // Just check the blob type.
-                // If it is not an appendable blob, return an error.
-                // We can not get the append position of the blob here.
+                // Check the blob type, if it is not an appendable blob,
+                // return an error.
+                //
+                // The append position is the current length of the blob,
+                // which will be used to make sure the content is appended
+                // to the right position.
                 StatusCode::OK => {
                     let headers = resp.headers();
                     let blob_type = headers.get(X_MS_BLOB_TYPE).and_then(|v| v.to_str().ok());
@@ -75,6 +117,8 @@ impl oio::Append for AzblobAppender {
                             "the blob is not an appendable blob.",
                         ));
                     }
+
+                    self.position = parse_content_length(headers)?;
                 }
 
                 // If the blob is not existing, we need to create one.
@@ -108,32 +152,12 @@ impl oio::Append for AzblobAppender {
             }
         }
 
-        let size = bs.len();
-
-        let mut req = self.core.azblob_append_blob_request(
-            &self.path,
-            size,
-            self.position,
-            AsyncBody::Bytes(bs),
-        )?;
-
-        self.core.sign(&mut req).await?;
-
-        let resp = self.core.send(req).await?;
-
-        let status = resp.status();
-        match status {
-            StatusCode::CREATED => {
-                let headers = resp.headers();
-                let position = headers
-                    .get(X_MS_BLOB_APPEND_OFFSET)
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok());
-                self.position = position.map(|v| v + size as u64);
-            }
-            _ => {
-                return Err(parse_error(resp).await?);
-            }
+        // Split content that exceeds the limit of `Append Block`.
+        let mut offset = 0;
+        while offset < bs.len() {
+            let end = min(offset + AZBLOB_APPEND_BLOCK_SIZE, bs.len());
+            self.append_block(bs.slice(offset..end)).await?;
+            offset = end;
         }
 
         Ok(())
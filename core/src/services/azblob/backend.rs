@@ -16,6 +16,7 @@
 // under the License.
 
 use std::collections::HashMap;
+use std::env;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
@@ -34,8 +35,13 @@ use sha2::Sha256;
 
 use super::appender::AzblobAppender;
 use super::batch::parse_batch_delete_response;
+use super::core::constants;
 use super::error::parse_error;
 use super::pager::AzblobPager;
+use super::token::AzblobTokenLoader;
+use super::token::AzblobTokenSource;
+use super::token::DEFAULT_AUTHORITY_HOST;
+use super::token::IMDS_ENDPOINT;
 use super::writer::AzblobWriter;
 use crate::raw::*;
 use crate::services::azblob::core::AzblobCore;
@@ -66,6 +72,12 @@ pub struct AzblobBuilder {
     encryption_key_sha256: Option<String>,
     encryption_algorithm: Option<String>,
     sas_token: Option<String>,
+    tenant_id: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    federated_token_file: Option<String>,
+    authority_host: Option<String>,
+    enable_managed_identity: bool,
     http_client: Option<HttpClient>,
     batch_max_operations: Option<usize>,
 }
@@ -87,6 +99,14 @@ impl Debug for AzblobBuilder {
         if self.sas_token.is_some() {
             ds.field("sas_token", &"<redacted>");
         }
+        ds.field("tenant_id", &self.tenant_id);
+        ds.field("client_id", &self.client_id);
+        if self.client_secret.is_some() {
+            ds.field("client_secret", &"<redacted>");
+        }
+        ds.field("federated_token_file", &self.federated_token_file);
+        ds.field("authority_host", &self.authority_host);
+        ds.field("enable_managed_identity", &self.enable_managed_identity);
 
         ds.finish()
     }
@@ -246,6 +266,79 @@ impl AzblobBuilder {
         self
     }
 
+    /// Set tenant id of Azure AD.
+    ///
+    /// - If tenant_id is set, we will take user's input first.
+    /// - If not, we will try to load it from `AZURE_TENANT_ID`.
+    pub fn tenant_id(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.tenant_id = Some(v.to_string());
+        }
+
+        self
+    }
+
+    /// Set client id of Azure AD application or user assigned managed
+    /// identity.
+    ///
+    /// - If client_id is set, we will take user's input first.
+    /// - If not, we will try to load it from `AZURE_CLIENT_ID`.
+    pub fn client_id(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.client_id = Some(v.to_string());
+        }
+
+        self
+    }
+
+    /// Set client secret of Azure AD application.
+    ///
+    /// - If client_secret is set, we will take user's input first.
+    /// - If not, we will try to load it from `AZURE_CLIENT_SECRET`.
+    pub fn client_secret(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.client_secret = Some(v.to_string());
+        }
+
+        self
+    }
+
+    /// Set the path of federated token file which is projected by AKS
+    /// workload identity.
+    ///
+    /// - If federated_token_file is set, we will take user's input first.
+    /// - If not, we will try to load it from `AZURE_FEDERATED_TOKEN_FILE`.
+    pub fn federated_token_file(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.federated_token_file = Some(v.to_string());
+        }
+
+        self
+    }
+
+    /// Set authority host of Azure AD.
+    ///
+    /// - If authority_host is set, we will take user's input first.
+    /// - If not, we will try to load it from `AZURE_AUTHORITY_HOST`.
+    /// - Otherwise, `https://login.microsoftonline.com` will be used.
+    pub fn authority_host(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.authority_host = Some(v.trim_end_matches('/').to_string());
+        }
+
+        self
+    }
+
+    /// Enable loading tokens of managed identity from Azure Instance
+    /// Metadata Service (IMDS).
+    ///
+    /// Set `client_id` to select a user assigned managed identity.
+    pub fn enable_managed_identity(&mut self) -> &mut Self {
+        self.enable_managed_identity = true;
+
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -349,6 +442,63 @@ impl AzblobBuilder {
     }
 }
 
+impl AzblobBuilder {
+    /// Decide how to load Azure AD tokens.
+    ///
+    /// Azure AD will not be used if account key or sas token is set.
+    /// Otherwise, the first available source will be used:
+    ///
+    /// - Client secret with `tenant_id` and `client_id`.
+    /// - Workload identity with `tenant_id`, `client_id` and
+    ///   `federated_token_file`, which are injected by AKS.
+    /// - Managed identity if `enable_managed_identity` is set.
+    fn build_token_source(&self) -> Option<AzblobTokenSource> {
+        if self.account_key.is_some() || self.sas_token.is_some() {
+            return None;
+        }
+
+        let load = |v: &Option<String>, key: &str| {
+            v.clone()
+                .or_else(|| env::var(key).ok().filter(|v| !v.is_empty()))
+        };
+        let tenant_id = load(&self.tenant_id, "AZURE_TENANT_ID");
+        let client_id = load(&self.client_id, "AZURE_CLIENT_ID");
+        let authority_host = load(&self.authority_host, "AZURE_AUTHORITY_HOST")
+            .map(|v| v.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| DEFAULT_AUTHORITY_HOST.to_string());
+
+        if let (Some(tenant_id), Some(client_id)) = (&tenant_id, &client_id) {
+            if let Some(client_secret) = load(&self.client_secret, "AZURE_CLIENT_SECRET") {
+                return Some(AzblobTokenSource::ClientSecret {
+                    tenant_id: tenant_id.clone(),
+                    client_id: client_id.clone(),
+                    client_secret,
+                    authority_host,
+                });
+            }
+            if let Some(federated_token_file) =
+                load(&self.federated_token_file, "AZURE_FEDERATED_TOKEN_FILE")
+            {
+                return Some(AzblobTokenSource::WorkloadIdentity {
+                    tenant_id: tenant_id.clone(),
+                    client_id: client_id.clone(),
+                    federated_token_file,
+                    authority_host,
+                });
+            }
+        }
+
+        if self.enable_managed_identity {
+            return Some(AzblobTokenSource::ManagedIdentity {
+                client_id,
+                endpoint: IMDS_ENDPOINT.to_string(),
+            });
+        }
+
+        None
+    }
+}
+
 impl Builder for AzblobBuilder {
     const SCHEME: Scheme = Scheme::Azblob;
     type Accessor = AzblobBackend;
@@ -367,6 +517,15 @@ impl Builder for AzblobBuilder {
         map.get("encryption_algorithm")
             .map(|v| builder.encryption_algorithm(v));
         map.get("sas_token").map(|v| builder.sas_token(v));
+        map.get("tenant_id").map(|v| builder.tenant_id(v));
+        map.get("client_id").map(|v| builder.client_id(v));
+        map.get("client_secret").map(|v| builder.client_secret(v));
+        map.get("federated_token_file")
+            .map(|v| builder.federated_token_file(v));
+        map.get("authority_host").map(|v| builder.authority_host(v));
+        map.get("enable_managed_identity")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.enable_managed_identity());
         map.get("batch_max_operations")
             .map(|v| builder.batch_max_operations(v.parse::<usize>().unwrap()));
 
@@ -448,6 +607,10 @@ impl Builder for AzblobBuilder {
 
         let cred_loader = AzureStorageLoader::new(config_loader);
 
+        let token_loader = self
+            .build_token_source()
+            .map(|source| AzblobTokenLoader::new(client.clone(), source));
+
         let signer = AzureStorageSigner::new();
 
         let batch_max_operations = self.batch_max_operations.unwrap_or(AZBLOB_BATCH_LIMIT);
@@ -465,6 +628,7 @@ impl Builder for AzblobBuilder {
                 client,
                 loader: cred_loader,
                 signer,
+                token_loader,
                 batch_max_operations,
             }),
             has_sas_token: self.sas_token.is_some(),
@@ -532,6 +696,7 @@ impl Accessor for AzblobBackend {
 
                 write: true,
                 write_with_cache_control: true,
+                write_with_storage_class: true,
                 write_with_content_type: true,
 
                 append: true,
@@ -539,8 +704,12 @@ impl Accessor for AzblobBackend {
                 append_with_content_type: true,
 
                 delete: true,
+                delete_with_if_match: true,
+                delete_with_version: true,
                 create_dir: true,
                 copy: true,
+                copy_with_user_metadata: true,
+                copy_with_tags: true,
 
                 list: true,
                 list_with_delimiter_slash: true,
@@ -564,7 +733,7 @@ impl Accessor for AzblobBackend {
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let mut req =
             self.core
-                .azblob_put_blob_request(path, Some(0), None, None, AsyncBody::Empty)?;
+                .azblob_put_blob_request(path, Some(0), None, None, None, AsyncBody::Empty)?;
 
         self.core.sign(&mut req).await?;
 
@@ -626,8 +795,8 @@ impl Accessor for AzblobBackend {
         ))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.azblob_copy_blob(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.azblob_copy_blob(from, to, &args).await?;
 
         let status = resp.status();
 
@@ -654,7 +823,30 @@ impl Accessor for AzblobBackend {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::OK => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+
+                // Access tier will only be returned for block blobs.
+                if let Some(v) = resp
+                    .headers()
+                    .get(constants::X_MS_ACCESS_TIER)
+                    .and_then(|v| v.to_str().ok())
+                {
+                    meta.set_storage_class(v);
+                }
+
+                // Archive status will only be returned for archived blobs
+                // that are being rehydrated, like `rehydrate-pending-to-hot`.
+                if let Some(v) = resp
+                    .headers()
+                    .get(constants::X_MS_ARCHIVE_STATUS)
+                    .and_then(|v| v.to_str().ok())
+                {
+                    meta.set_restore_status(v);
+                }
+
+                Ok(RpStat::new(meta))
+            }
             StatusCode::NOT_FOUND if path.ends_with('/') => {
                 Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
             }
@@ -662,8 +854,11 @@ impl Accessor for AzblobBackend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.azblob_delete_blob(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self
+            .core
+            .azblob_delete_blob(path, args.if_match(), args.version())
+            .await?;
 
         let status = resp.status();
 
@@ -699,7 +894,7 @@ impl Accessor for AzblobBackend {
             )?,
             PresignOperation::Write(_) => {
                 self.core
-                    .azblob_put_blob_request(path, None, None, None, AsyncBody::Empty)?
+                    .azblob_put_blob_request(path, None, None, None, None, AsyncBody::Empty)?
             }
         };
 
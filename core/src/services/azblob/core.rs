@@ -33,14 +33,20 @@ use reqsign::AzureStorageCredential;
 use reqsign::AzureStorageLoader;
 use reqsign::AzureStorageSigner;
 
+use super::token::AzblobTokenLoader;
 use crate::raw::*;
 use crate::*;
 
-mod constants {
+pub mod constants {
     pub const X_MS_VERSION: &str = "x-ms-version";
+    pub const X_MS_DATE: &str = "x-ms-date";
 
     pub const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
+    pub const X_MS_ACCESS_TIER: &str = "x-ms-access-tier";
+    pub const X_MS_ARCHIVE_STATUS: &str = "x-ms-archive-status";
     pub const X_MS_COPY_SOURCE: &str = "x-ms-copy-source";
+    pub const X_MS_TAGS: &str = "x-ms-tags";
+    pub const X_MS_META_PREFIX: &str = "x-ms-meta-";
     pub const X_MS_BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
     pub const X_MS_BLOB_CONDITION_APPENDPOS: &str = "x-ms-blob-condition-appendpos";
 
@@ -60,6 +66,8 @@ pub struct AzblobCore {
     pub client: HttpClient,
     pub loader: AzureStorageLoader,
     pub signer: AzureStorageSigner,
+    /// Sign requests with Azure AD tokens instead of shared key if set.
+    pub token_loader: Option<AzblobTokenLoader>,
     pub batch_max_operations: usize,
 }
 
@@ -100,7 +108,6 @@ impl AzblobCore {
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
-        let cred = self.load_credential().await?;
         // Insert x-ms-version header for normal requests.
         req.headers_mut().insert(
             HeaderName::from_static(constants::X_MS_VERSION),
@@ -111,10 +118,34 @@ impl AzblobCore {
             // In the future, we could allow users to configure this value.
             HeaderValue::from_static("2022-11-02"),
         );
-        self.signer.sign(req, &cred).map_err(new_request_sign_error)
+        self.batch_sign(req).await
     }
 
     async fn batch_sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        if let Some(loader) = &self.token_loader {
+            let token = loader.load().await?;
+            let mut value: HeaderValue =
+                format_authorization_by_bearer(&token)?
+                    .parse()
+                    .map_err(|_| {
+                        Error::new(
+                            ErrorKind::Unexpected,
+                            "access token contains invalid header value",
+                        )
+                    })?;
+            value.set_sensitive(true);
+            req.headers_mut().insert(http::header::AUTHORIZATION, value);
+            req.headers_mut().insert(
+                HeaderName::from_static(constants::X_MS_DATE),
+                chrono::Utc::now()
+                    .format("%a, %d %b %Y %H:%M:%S GMT")
+                    .to_string()
+                    .parse()
+                    .expect("date must be valid header value"),
+            );
+            return Ok(());
+        }
+
         let cred = self.load_credential().await?;
         self.signer.sign(req, &cred).map_err(new_request_sign_error)
     }
@@ -247,6 +278,7 @@ impl AzblobCore {
         size: Option<usize>,
         content_type: Option<&str>,
         cache_control: Option<&str>,
+        access_tier: Option<&str>,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
@@ -266,6 +298,11 @@ impl AzblobCore {
         if let Some(cache_control) = cache_control {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
+        // Access tier like `Hot`, `Cool`, `Cold` and `Archive`, the default
+        // tier of the account will be used if not set.
+        if let Some(access_tier) = access_tier {
+            req = req.header(constants::X_MS_ACCESS_TIER, access_tier);
+        }
         if let Some(size) = size {
             req = req.header(CONTENT_LENGTH, size)
         }
@@ -350,7 +387,8 @@ impl AzblobCore {
     ///
     /// # Notes
     ///
-    /// - The maximum size of the content could be appended is 4MB.
+    /// - The maximum size of the content could be appended is 4MB, larger
+    ///   content should be split by callers.
     /// - `Append Block` succeeds only if the blob already exists.
     /// - It does not need to provide append position.
     /// - But it could use append position to verify the content is appended to the right position.
@@ -444,25 +482,43 @@ impl AzblobCore {
         self.send(req).await
     }
 
-    pub fn azblob_delete_blob_request(&self, path: &str) -> Result<Request<AsyncBody>> {
+    pub fn azblob_delete_blob_request(
+        &self,
+        path: &str,
+        if_match: Option<&str>,
+        version: Option<&str>,
+    ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        let mut url = format!(
             "{}/{}/{}",
             self.endpoint,
             self.container,
             percent_encode_path(&p)
         );
+        if let Some(version) = version {
+            write!(url, "?versionid={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
-        let req = Request::delete(&url);
+        let mut req = Request::delete(&url);
+
+        if let Some(if_match) = if_match {
+            req = req.header(IF_MATCH, if_match);
+        }
 
         req.header(CONTENT_LENGTH, 0)
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)
     }
 
-    pub async fn azblob_delete_blob(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.azblob_delete_blob_request(path)?;
+    pub async fn azblob_delete_blob(
+        &self,
+        path: &str,
+        if_match: Option<&str>,
+        version: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.azblob_delete_blob_request(path, if_match, version)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
@@ -472,6 +528,7 @@ impl AzblobCore {
         &self,
         from: &str,
         to: &str,
+        args: &OpCopy,
     ) -> Result<Response<IncomingAsyncBody>> {
         let source = build_abs_path(&self.root, from);
         let target = build_abs_path(&self.root, to);
@@ -491,7 +548,27 @@ impl AzblobCore {
 
         let mut req = Request::put(&target)
             .header(constants::X_MS_COPY_SOURCE, source)
-            .header(CONTENT_LENGTH, 0)
+            .header(CONTENT_LENGTH, 0);
+
+        // Azblob will use the given metadata instead of copying from source
+        // if any metadata header is specified.
+        if let Some(user_metadata) = args.user_metadata() {
+            for (k, v) in user_metadata {
+                req = req.header(format!("{}{k}", constants::X_MS_META_PREFIX), v);
+            }
+        }
+
+        if let Some(tags) = args.tags() {
+            let tags = tags
+                .iter()
+                .map(|(k, v)| format!("{}={}", percent_encode_path(k), percent_encode_path(v)))
+                .collect::<Vec<_>>()
+                .join("&");
+
+            req = req.header(constants::X_MS_TAGS, tags);
+        }
+
+        let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
@@ -546,7 +623,7 @@ impl AzblobCore {
         let mut multipart = Multipart::new();
 
         for (idx, path) in paths.iter().enumerate() {
-            let mut req = self.azblob_delete_blob_request(path)?;
+            let mut req = self.azblob_delete_blob_request(path, None, None)?;
             self.batch_sign(&mut req).await?;
 
             multipart = multipart.part(
@@ -23,4 +23,5 @@ mod batch;
 mod core;
 mod error;
 mod pager;
+mod token;
 mod writer;
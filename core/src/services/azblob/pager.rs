@@ -107,7 +107,7 @@ impl oio::Page for AzblobPager {
                 continue;
             }
 
-            let meta = Metadata::new(EntryMode::FILE)
+            let mut meta = Metadata::new(EntryMode::FILE)
                 // Keep fit with ETag header.
                 .with_etag(format!("\"{}\"", object.properties.etag.as_str()))
                 .with_content_length(object.properties.content_length)
@@ -116,6 +116,12 @@ impl oio::Page for AzblobPager {
                 .with_last_modified(parse_datetime_from_rfc2822(
                     object.properties.last_modified.as_str(),
                 )?);
+            if let Some(v) = &object.properties.access_tier {
+                meta.set_storage_class(v);
+            }
+            if let Some(v) = &object.properties.archive_status {
+                meta.set_restore_status(v);
+            }
 
             let de = oio::Entry::new(&build_rel_path(&self.core.root, &object.name), meta);
 
@@ -166,6 +172,8 @@ struct Properties {
     #[serde(rename = "Content-Type")]
     content_type: String,
     etag: String,
+    access_tier: Option<String>,
+    archive_status: Option<String>,
 }
 
 #[cfg(test)]
@@ -329,6 +337,11 @@ mod tests {
                 .collect::<Vec<String>>(),
             ["dir1/dir2/", "dir1/dir21/"]
         );
+        assert_eq!(
+            out.blobs.blob[0].properties.access_tier.as_deref(),
+            Some("Hot")
+        );
+        assert_eq!(out.blobs.blob[0].properties.archive_status, None);
     }
 
     /// This case is copied from real environment for testing
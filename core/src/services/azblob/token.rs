@@ -0,0 +1,292 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use http::header;
+use http::Request;
+use http::StatusCode;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The default authority host of Azure public cloud.
+pub const DEFAULT_AUTHORITY_HOST: &str = "https://login.microsoftonline.com";
+/// The endpoint of Azure Instance Metadata Service.
+pub const IMDS_ENDPOINT: &str = "http://169.254.169.254";
+/// The resource that tokens are requested for.
+const STORAGE_RESOURCE: &str = "https://storage.azure.com/";
+const STORAGE_SCOPE: &str = "https://storage.azure.com/.default";
+
+/// AzblobTokenSource decides how to acquire Azure AD tokens.
+pub enum AzblobTokenSource {
+    /// Exchange the client secret of a service principal for tokens.
+    ClientSecret {
+        tenant_id: String,
+        client_id: String,
+        client_secret: String,
+        authority_host: String,
+    },
+    /// Exchange the federated token projected by AKS workload identity
+    /// for tokens.
+    ///
+    /// The token file will be read again while refreshing since it will
+    /// be rotated.
+    WorkloadIdentity {
+        tenant_id: String,
+        client_id: String,
+        federated_token_file: String,
+        authority_host: String,
+    },
+    /// Fetch tokens of the managed identity from IMDS.
+    ///
+    /// `client_id` is used to select a user assigned identity.
+    ManagedIdentity {
+        client_id: Option<String>,
+        endpoint: String,
+    },
+}
+
+/// AzblobTokenLoader loads Azure AD tokens and refreshes them before
+/// expiring.
+pub struct AzblobTokenLoader {
+    client: HttpClient,
+    source: AzblobTokenSource,
+    token: Mutex<AzblobToken>,
+}
+
+impl Debug for AzblobTokenLoader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let source = match self.source {
+            AzblobTokenSource::ClientSecret { .. } => "client_secret",
+            AzblobTokenSource::WorkloadIdentity { .. } => "workload_identity",
+            AzblobTokenSource::ManagedIdentity { .. } => "managed_identity",
+        };
+
+        f.debug_struct("AzblobTokenLoader")
+            .field("source", &source)
+            .finish_non_exhaustive()
+    }
+}
+
+struct AzblobToken {
+    access_token: String,
+    expires_in: DateTime<Utc>,
+}
+
+impl AzblobTokenLoader {
+    pub fn new(client: HttpClient, source: AzblobTokenSource) -> Self {
+        Self {
+            client,
+            source,
+            token: Mutex::new(AzblobToken {
+                access_token: String::new(),
+                expires_in: DateTime::<Utc>::MIN_UTC,
+            }),
+        }
+    }
+
+    /// Load a valid access token, it will be refreshed if expired.
+    pub async fn load(&self) -> Result<String> {
+        let mut token = self.token.lock().await;
+
+        if token.expires_in <= Utc::now() {
+            let req = self.build_token_request().await?;
+            let resp = self.client.send(req).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let resp: AzblobTokenResponse =
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+            token.access_token = resp.access_token;
+            // Refresh the token a bit earlier to avoid using an expired token.
+            token.expires_in =
+                Utc::now() + Duration::seconds(resp.expires_in.as_secs()?) - Duration::seconds(120);
+        }
+
+        Ok(token.access_token.clone())
+    }
+
+    async fn build_token_request(&self) -> Result<Request<AsyncBody>> {
+        let (url, body) = match &self.source {
+            AzblobTokenSource::ClientSecret {
+                tenant_id,
+                client_id,
+                client_secret,
+                authority_host,
+            } => (
+                format!("{authority_host}/{tenant_id}/oauth2/v2.0/token"),
+                format!(
+                    "grant_type=client_credentials&scope={}&client_id={}&client_secret={}",
+                    utf8_percent_encode(STORAGE_SCOPE, NON_ALPHANUMERIC),
+                    utf8_percent_encode(client_id, NON_ALPHANUMERIC),
+                    utf8_percent_encode(client_secret, NON_ALPHANUMERIC),
+                ),
+            ),
+            AzblobTokenSource::WorkloadIdentity {
+                tenant_id,
+                client_id,
+                federated_token_file,
+                authority_host,
+            } => {
+                let assertion = tokio::fs::read_to_string(federated_token_file)
+                    .await
+                    .map_err(|err| {
+                        Error::new(ErrorKind::ConfigInvalid, "read federated token file failed")
+                            .with_context("federated_token_file", federated_token_file)
+                            .set_source(err)
+                    })?;
+
+                (
+                    format!("{authority_host}/{tenant_id}/oauth2/v2.0/token"),
+                    format!(
+                        "grant_type=client_credentials&scope={}&client_id={}&client_assertion_type={}&client_assertion={}",
+                        utf8_percent_encode(STORAGE_SCOPE, NON_ALPHANUMERIC),
+                        utf8_percent_encode(client_id, NON_ALPHANUMERIC),
+                        utf8_percent_encode(
+                            "urn:ietf:params:oauth:client-assertion-type:jwt-bearer",
+                            NON_ALPHANUMERIC
+                        ),
+                        utf8_percent_encode(assertion.trim(), NON_ALPHANUMERIC),
+                    ),
+                )
+            }
+            AzblobTokenSource::ManagedIdentity {
+                client_id,
+                endpoint,
+            } => {
+                let mut url = format!(
+                    "{endpoint}/metadata/identity/oauth2/token?api-version=2018-02-01&resource={}",
+                    utf8_percent_encode(STORAGE_RESOURCE, NON_ALPHANUMERIC)
+                );
+                if let Some(client_id) = client_id {
+                    url.push_str(&format!(
+                        "&client_id={}",
+                        utf8_percent_encode(client_id, NON_ALPHANUMERIC)
+                    ));
+                }
+
+                return Request::get(url)
+                    .header("Metadata", "true")
+                    .body(AsyncBody::Empty)
+                    .map_err(new_request_build_error);
+            }
+        };
+
+        Request::post(url)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(AsyncBody::Bytes(Bytes::from(body)))
+            .map_err(new_request_build_error)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AzblobTokenResponse {
+    access_token: String,
+    expires_in: AzblobTokenExpiresIn,
+}
+
+/// Azure AD returns `expires_in` as number but IMDS returns it as string.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AzblobTokenExpiresIn {
+    Number(i64),
+    String(String),
+}
+
+impl AzblobTokenExpiresIn {
+    fn as_secs(&self) -> Result<i64> {
+        match self {
+            AzblobTokenExpiresIn::Number(v) => Ok(*v),
+            AzblobTokenExpiresIn::String(v) => v.parse().map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "parse expires_in of token failed")
+                    .with_context("expires_in", v)
+                    .set_source(err)
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token_response() {
+        let resp: AzblobTokenResponse = serde_json::from_str(
+            r#"{"token_type":"Bearer","expires_in":3599,"ext_expires_in":3599,"access_token":"token"}"#,
+        )
+        .expect("must succeed");
+        assert_eq!(resp.access_token, "token");
+        assert_eq!(resp.expires_in.as_secs().unwrap(), 3599);
+
+        // Response of IMDS.
+        let resp: AzblobTokenResponse = serde_json::from_str(
+            r#"{"access_token":"token","client_id":"id","expires_in":"86399","expires_on":"1690000000","resource":"https://storage.azure.com/","token_type":"Bearer"}"#,
+        )
+        .expect("must succeed");
+        assert_eq!(resp.access_token, "token");
+        assert_eq!(resp.expires_in.as_secs().unwrap(), 86399);
+    }
+
+    #[tokio::test]
+    async fn test_build_token_request() {
+        let loader = AzblobTokenLoader::new(
+            HttpClient::new().unwrap(),
+            AzblobTokenSource::ClientSecret {
+                tenant_id: "tenant".to_string(),
+                client_id: "client".to_string(),
+                client_secret: "se/cret".to_string(),
+                authority_host: DEFAULT_AUTHORITY_HOST.to_string(),
+            },
+        );
+        let req = loader.build_token_request().await.unwrap();
+        assert_eq!(
+            req.uri(),
+            "https://login.microsoftonline.com/tenant/oauth2/v2.0/token"
+        );
+        let body = match req.into_body() {
+            AsyncBody::Bytes(bs) => String::from_utf8(bs.to_vec()).unwrap(),
+            _ => panic!("body must be bytes"),
+        };
+        assert_eq!(body, "grant_type=client_credentials&scope=https%3A%2F%2Fstorage%2Eazure%2Ecom%2F%2Edefault&client_id=client&client_secret=se%2Fcret");
+
+        let loader = AzblobTokenLoader::new(
+            HttpClient::new().unwrap(),
+            AzblobTokenSource::ManagedIdentity {
+                client_id: Some("client".to_string()),
+                endpoint: IMDS_ENDPOINT.to_string(),
+            },
+        );
+        let req = loader.build_token_request().await.unwrap();
+        assert_eq!(req.uri(), "http://169.254.169.254/metadata/identity/oauth2/token?api-version=2018-02-01&resource=https%3A%2F%2Fstorage%2Eazure%2Ecom%2F&client_id=client");
+        assert_eq!(req.headers().get("Metadata").unwrap(), "true");
+    }
+}
@@ -47,6 +47,7 @@ impl oio::Write for AzblobWriter {
             Some(bs.len()),
             self.op.content_type(),
             self.op.cache_control(),
+            self.op.storage_class(),
             AsyncBody::Bytes(bs),
         )?;
 
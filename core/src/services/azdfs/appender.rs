@@ -0,0 +1,127 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::StatusCode;
+
+use super::core::AzdfsCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct AzdfsAppender {
+    core: Arc<AzdfsCore>,
+
+    op: OpAppend,
+    path: String,
+
+    position: Option<u64>,
+}
+
+impl AzdfsAppender {
+    pub fn new(core: Arc<AzdfsCore>, path: &str, op: OpAppend) -> Self {
+        Self {
+            core,
+            op,
+            path: path.to_string(),
+            position: None,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Append for AzdfsAppender {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        // If the position is not set, we need to check the file.
+        // Any successful append operation will update the position.
+        if self.position.is_none() {
+            let resp = self.core.azdfs_get_properties(&self.path).await?;
+
+            let status = resp.status();
+
+            match status {
+                StatusCode::OK => {
+                    let position = parse_content_length(resp.headers())?.unwrap_or_default();
+                    resp.into_body().consume().await?;
+                    self.position = Some(position);
+                }
+
+                // If the file is not existing, we need to create one.
+                StatusCode::NOT_FOUND => {
+                    let mut req = self.core.azdfs_create_request(
+                        &self.path,
+                        "file",
+                        self.op.content_type(),
+                        self.op.content_disposition(),
+                        AsyncBody::Empty,
+                    )?;
+
+                    self.core.sign(&mut req).await?;
+
+                    let resp = self.core.send(req).await?;
+
+                    let status = resp.status();
+                    match status {
+                        StatusCode::CREATED | StatusCode::OK => {
+                            resp.into_body().consume().await?;
+                        }
+                        _ => {
+                            return Err(parse_error(resp)
+                                .await?
+                                .with_operation("Backend::azdfs_create_request"));
+                        }
+                    }
+
+                    self.position = Some(0);
+                }
+
+                _ => {
+                    return Err(parse_error(resp).await?);
+                }
+            }
+        }
+
+        let position = self.position.unwrap_or_default();
+        let size = bs.len();
+
+        let mut req =
+            self.core
+                .azdfs_append_request(&self.path, size, position, AsyncBody::Bytes(bs))?;
+
+        self.core.sign(&mut req).await?;
+
+        let resp = self.core.send(req).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK | StatusCode::ACCEPTED => {
+                resp.into_body().consume().await?;
+                self.position = Some(position + size as u64);
+                Ok(())
+            }
+            _ => Err(parse_error(resp)
+                .await?
+                .with_operation("Backend::azdfs_append_request")),
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -27,6 +27,7 @@ use reqsign::AzureStorageConfig;
 use reqsign::AzureStorageLoader;
 use reqsign::AzureStorageSigner;
 
+use super::appender::AzdfsAppender;
 use super::core::AzdfsCore;
 use super::error::parse_error;
 use super::pager::AzdfsPager;
@@ -232,7 +233,7 @@ impl Accessor for AzdfsBackend {
     type BlockingReader = ();
     type Writer = AzdfsWriter;
     type BlockingWriter = ();
-    type Appender = ();
+    type Appender = AzdfsAppender;
     type Pager = AzdfsPager;
     type BlockingPager = ();
 
@@ -253,6 +254,10 @@ impl Accessor for AzdfsBackend {
                 delete: true,
                 rename: true,
 
+                append: true,
+                append_with_content_type: true,
+                append_with_content_disposition: true,
+
                 list: true,
                 list_with_delimiter_slash: true,
 
@@ -310,6 +315,13 @@ impl Accessor for AzdfsBackend {
         ))
     }
 
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        Ok((
+            RpAppend::default(),
+            AzdfsAppender::new(self.core.clone(), path, args),
+        ))
+    }
+
     async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
         if let Some(resp) = self.core.azdfs_ensure_parent_path(to).await? {
             let status = resp.status();
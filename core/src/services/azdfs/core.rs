@@ -230,6 +230,33 @@ impl AzdfsCore {
         Ok(req)
     }
 
+    /// Append content to the file at the given position and flush it.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/datalakestoragegen2/path/update
+    pub fn azdfs_append_request(
+        &self,
+        path: &str,
+        size: usize,
+        position: u64,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?action=append&flush=true&position={position}",
+            self.endpoint,
+            self.filesystem,
+            percent_encode_path(&p)
+        );
+
+        let req = Request::patch(&url)
+            .header(CONTENT_LENGTH, size)
+            .body(body)
+            .map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
     pub async fn azdfs_get_properties(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path)
             .trim_end_matches('/')
@@ -18,6 +18,7 @@
 mod backend;
 pub use backend::AzdfsBuilder as Azdfs;
 
+mod appender;
 mod core;
 mod error;
 mod pager;
@@ -0,0 +1,373 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Write;
+
+use http::header::CONTENT_LENGTH;
+use http::header::RANGE;
+use http::HeaderName;
+use http::HeaderValue;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use reqsign::AzureStorageCredential;
+use reqsign::AzureStorageLoader;
+use reqsign::AzureStorageSigner;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+const X_MS_VERSION: &str = "x-ms-version";
+const X_MS_TYPE: &str = "x-ms-type";
+const X_MS_CONTENT_LENGTH: &str = "x-ms-content-length";
+const X_MS_CONTENT_TYPE: &str = "x-ms-content-type";
+const X_MS_CONTENT_DISPOSITION: &str = "x-ms-content-disposition";
+const X_MS_RANGE: &str = "x-ms-range";
+const X_MS_WRITE: &str = "x-ms-write";
+const X_MS_FILE_RENAME_SOURCE: &str = "x-ms-file-rename-source";
+const X_MS_FILE_RENAME_REPLACE_IF_EXISTS: &str = "x-ms-file-rename-replace-if-exists";
+
+/// The max size of a range that could be written in a single `Put Range` request.
+///
+/// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/put-range
+pub const AZFILE_MAX_RANGE_SIZE: usize = 4 * 1024 * 1024;
+
+pub struct AzfileCore {
+    pub share_name: String,
+    pub root: String,
+    pub endpoint: String,
+
+    pub client: HttpClient,
+    pub loader: AzureStorageLoader,
+    pub signer: AzureStorageSigner,
+}
+
+impl Debug for AzfileCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AzfileCore")
+            .field("share_name", &self.share_name)
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AzfileCore {
+    async fn load_credential(&self) -> Result<AzureStorageCredential> {
+        let cred = self
+            .loader
+            .load()
+            .await
+            .map_err(new_request_credential_error)?;
+
+        if let Some(cred) = cred {
+            Ok(cred)
+        } else {
+            Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "no valid credential found",
+            ))
+        }
+    }
+
+    pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        let cred = self.load_credential().await?;
+        // Insert x-ms-version header for normal requests.
+        //
+        // Rename is only supported since 2021-04-10, so we use the same
+        // version as azdfs which is also supported by Azurite V3.
+        req.headers_mut().insert(
+            HeaderName::from_static(X_MS_VERSION),
+            HeaderValue::from_static("2022-11-02"),
+        );
+        self.signer.sign(req, &cred).map_err(new_request_sign_error)
+    }
+
+    #[inline]
+    pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        self.client.send(req).await
+    }
+
+    fn build_url(&self, abs_path: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.share_name,
+            percent_encode_path(abs_path.trim_end_matches('/'))
+        )
+    }
+}
+
+impl AzfileCore {
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/get-file
+    pub async fn azfile_read(
+        &self,
+        path: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut req = Request::get(self.build_url(&p));
+
+        if !range.is_full() {
+            // azfile doesn't support read with suffix range.
+            if range.offset().is_none() && range.size().is_some() {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "azfile doesn't support read with suffix range",
+                ));
+            }
+
+            req = req.header(RANGE, range.to_header());
+        }
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Create a file with given size, the content of file will be filled by
+    /// following `Put Range` requests.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/create-file
+    pub async fn azfile_create_file(
+        &self,
+        path: &str,
+        size: u64,
+        content_type: Option<&str>,
+        content_disposition: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut req = Request::put(self.build_url(&p))
+            .header(X_MS_TYPE, "file")
+            .header(X_MS_CONTENT_LENGTH, size)
+            // Content length must be 0 for create request.
+            .header(CONTENT_LENGTH, 0);
+
+        if let Some(ty) = content_type {
+            req = req.header(X_MS_CONTENT_TYPE, ty)
+        }
+
+        if let Some(pos) = content_disposition {
+            req = req.header(X_MS_CONTENT_DISPOSITION, pos)
+        }
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Write content into the range `[offset, offset + size)` of the file.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/put-range
+    pub fn azfile_put_range_request(
+        &self,
+        path: &str,
+        offset: u64,
+        size: usize,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        debug_assert!(size > 0, "put range size must be larger than 0");
+
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}?comp=range", self.build_url(&p));
+
+        let req = Request::put(&url)
+            .header(X_MS_WRITE, "update")
+            .header(
+                X_MS_RANGE,
+                format!("bytes={}-{}", offset, offset + size as u64 - 1),
+            )
+            .header(CONTENT_LENGTH, size)
+            .body(body)
+            .map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/get-file-properties
+    pub async fn azfile_get_file_properties(
+        &self,
+        path: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut req = Request::head(self.build_url(&p))
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/get-directory-properties
+    pub async fn azfile_get_directory_properties(
+        &self,
+        path: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}?restype=directory", self.build_url(&p));
+
+        let mut req = Request::head(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/delete-file2
+    pub async fn azfile_delete_file(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut req = Request::delete(self.build_url(&p))
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Delete an empty directory.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/delete-directory
+    pub async fn azfile_delete_directory(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}?restype=directory", self.build_url(&p));
+
+        let mut req = Request::delete(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Rename a file, the target will be replaced if exists.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/rename-file
+    pub async fn azfile_rename_file(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let source = build_abs_path(&self.root, from);
+        let target = build_abs_path(&self.root, to);
+
+        let url = format!("{}?restype=file&comp=rename", self.build_url(&target));
+
+        let mut req = Request::put(&url)
+            .header(X_MS_FILE_RENAME_SOURCE, self.build_url(&source))
+            .header(X_MS_FILE_RENAME_REPLACE_IF_EXISTS, "true")
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/list-directories-and-files
+    pub async fn azfile_list(
+        &self,
+        path: &str,
+        marker: &str,
+        limit: Option<usize>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}?restype=directory&comp=list&include=Timestamps,ETag",
+            self.build_url(&p)
+        );
+        if let Some(limit) = limit {
+            write!(url, "&maxresults={limit}").expect("write into string must succeed");
+        }
+        if !marker.is_empty() {
+            write!(url, "&marker={}", percent_encode_path(marker))
+                .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/create-directory
+    async fn azfile_create_directory(&self, abs_path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}?restype=directory", self.build_url(abs_path));
+
+        let mut req = Request::put(&url)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Azure Files doesn't create parent directories automatically, so we
+    /// need to create all of them one by one from top to bottom.
+    ///
+    /// The input path will be treated as a directory if it ends with `/`,
+    /// otherwise only its parents will be created.
+    pub async fn azfile_ensure_dirs(&self, path: &str) -> Result<()> {
+        let p = build_abs_path(&self.root, path);
+        let p = if p.ends_with('/') {
+            p.as_str()
+        } else {
+            p.rsplit_once('/').map(|(parent, _)| parent).unwrap_or("")
+        };
+
+        let mut dir = String::with_capacity(p.len());
+        for part in p.split('/').filter(|v| !v.is_empty()) {
+            dir.push_str(part);
+            dir.push('/');
+
+            let resp = self.azfile_create_directory(&dir).await?;
+            match resp.status() {
+                // CONFLICT means the directory already exists.
+                StatusCode::CREATED | StatusCode::CONFLICT => {
+                    resp.into_body().consume().await?;
+                }
+                _ => {
+                    return Err(parse_error(resp)
+                        .await?
+                        .with_operation("Backend::azfile_create_directory"))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Buf;
+use http::StatusCode;
+use quick_xml::de;
+use serde::Deserialize;
+
+use super::core::AzfileCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct AzfilePager {
+    core: Arc<AzfileCore>,
+
+    path: String,
+    limit: Option<usize>,
+
+    next_marker: String,
+    done: bool,
+}
+
+impl AzfilePager {
+    pub fn new(core: Arc<AzfileCore>, path: String, limit: Option<usize>) -> Self {
+        Self {
+            core,
+            path,
+            limit,
+
+            next_marker: "".to_string(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for AzfilePager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let resp = self
+            .core
+            .azfile_list(&self.path, &self.next_marker, self.limit)
+            .await?;
+
+        // Azfile will return not found for not-exist directory.
+        if resp.status() == StatusCode::NOT_FOUND {
+            resp.into_body().consume().await?;
+            return Ok(None);
+        }
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+
+        let output: Output = de::from_reader(bs.reader()).map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "deserialize xml from response").set_source(e)
+        })?;
+
+        self.next_marker = output.next_marker.unwrap_or_default();
+        self.done = self.next_marker.is_empty();
+
+        // Entries returned by azfile only contain the name without parent.
+        let parent = if self.path == "/" {
+            ""
+        } else {
+            self.path.as_str()
+        };
+
+        let mut entries =
+            Vec::with_capacity(output.entries.directory.len() + output.entries.file.len());
+
+        for dir in output.entries.directory {
+            let de = oio::Entry::new(
+                &format!("{parent}{}/", dir.name),
+                Metadata::new(EntryMode::DIR),
+            );
+
+            entries.push(de);
+        }
+
+        for file in output.entries.file {
+            let mut meta =
+                Metadata::new(EntryMode::FILE).with_content_length(file.properties.content_length);
+            if !file.properties.etag.is_empty() {
+                // Keep fit with ETag header.
+                meta.set_etag(&format!("\"{}\"", file.properties.etag));
+            }
+            if !file.properties.last_modified.is_empty() {
+                meta.set_last_modified(parse_datetime_from_rfc2822(
+                    &file.properties.last_modified,
+                )?);
+            }
+
+            let de = oio::Entry::new(&format!("{parent}{}", file.name), meta);
+
+            entries.push(de);
+        }
+
+        Ok(Some(entries))
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct Output {
+    entries: Entries,
+    next_marker: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct Entries {
+    file: Vec<File>,
+    directory: Vec<Directory>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct File {
+    name: String,
+    properties: Properties,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct Directory {
+    name: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct Properties {
+    #[serde(rename = "Content-Length")]
+    content_length: u64,
+    #[serde(rename = "Last-Modified")]
+    last_modified: String,
+    etag: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_xml() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <EnumerationResults ServiceEndpoint="https://test.file.core.windows.net/" ShareName="myshare" DirectoryPath="dir1">
+                <MaxResults>2</MaxResults>
+                <DirectoryId>13835128424026341376</DirectoryId>
+                <Entries>
+                    <File>
+                        <FileId>13835093239654252544</FileId>
+                        <Name>a.txt</Name>
+                        <Properties>
+                            <Content-Length>3485277</Content-Length>
+                            <CreationTime>2023-05-06T09:21:12.9212880Z</CreationTime>
+                            <Last-Modified>Sat, 06 May 2023 09:21:12 GMT</Last-Modified>
+                            <Etag>0x8DB4E1A10FB3B6E</Etag>
+                        </Properties>
+                    </File>
+                    <Directory>
+                        <FileId>13835163608398430208</FileId>
+                        <Name>sub</Name>
+                        <Properties>
+                            <Last-Modified>Sat, 06 May 2023 09:21:12 GMT</Last-Modified>
+                            <Etag>0x8DB4E1A10FB3B6F</Etag>
+                        </Properties>
+                    </Directory>
+                    <File>
+                        <FileId>13835093239654252545</FileId>
+                        <Name>b.txt</Name>
+                        <Properties>
+                            <Content-Length>0</Content-Length>
+                        </Properties>
+                    </File>
+                </Entries>
+                <NextMarker>2!64!YS50eHQ-</NextMarker>
+            </EnumerationResults>"#,
+        );
+
+        let out: Output = de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(out.next_marker, Some("2!64!YS50eHQ-".to_string()));
+        assert_eq!(
+            out.entries
+                .file
+                .iter()
+                .map(|v| v.name.as_str())
+                .collect::<Vec<_>>(),
+            ["a.txt", "b.txt"]
+        );
+        assert_eq!(out.entries.file[0].properties.content_length, 3485277);
+        assert_eq!(out.entries.file[0].properties.etag, "0x8DB4E1A10FB3B6E");
+        assert_eq!(
+            out.entries.file[0].properties.last_modified,
+            "Sat, 06 May 2023 09:21:12 GMT"
+        );
+        assert_eq!(out.entries.directory[0].name, "sub");
+    }
+}
@@ -0,0 +1,138 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::StatusCode;
+
+use super::core::AzfileCore;
+use super::core::AZFILE_MAX_RANGE_SIZE;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct AzfileWriter {
+    core: Arc<AzfileCore>,
+
+    op: OpWrite,
+    path: String,
+
+    created: bool,
+    written: u64,
+}
+
+impl AzfileWriter {
+    pub fn new(core: Arc<AzfileCore>, op: OpWrite, path: String) -> Self {
+        AzfileWriter {
+            core,
+            op,
+            path,
+
+            created: false,
+            written: 0,
+        }
+    }
+
+    async fn create(&mut self) -> Result<()> {
+        self.core.azfile_ensure_dirs(&self.path).await?;
+
+        let resp = self
+            .core
+            .azfile_create_file(
+                &self.path,
+                self.op.content_length().unwrap_or_default(),
+                self.op.content_type(),
+                self.op.content_disposition(),
+            )
+            .await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::CREATED => {
+                resp.into_body().consume().await?;
+                self.created = true;
+                Ok(())
+            }
+            _ => Err(parse_error(resp)
+                .await?
+                .with_operation("Backend::azfile_create_file")),
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Write for AzfileWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        if !self.created {
+            self.create().await?;
+        }
+
+        // Azure Files only allows writing at most 4 MiB in one range.
+        let mut bs = bs;
+        while !bs.is_empty() {
+            let size = bs.len().min(AZFILE_MAX_RANGE_SIZE);
+            let chunk = bs.split_to(size);
+
+            let mut req = self.core.azfile_put_range_request(
+                &self.path,
+                self.written,
+                size,
+                AsyncBody::Bytes(chunk),
+            )?;
+
+            self.core.sign(&mut req).await?;
+
+            let resp = self.core.send(req).await?;
+
+            let status = resp.status();
+            match status {
+                StatusCode::CREATED => {
+                    resp.into_body().consume().await?;
+                    self.written += size as u64;
+                }
+                _ => {
+                    return Err(parse_error(resp)
+                        .await?
+                        .with_operation("Backend::azfile_put_range_request"))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "Write::sink is not supported",
+        ))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        // Make sure empty file has been created.
+        if !self.created {
+            self.create().await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,402 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+use tokio::sync::RwLock;
+
+use super::core::*;
+use super::error::parse_error;
+use super::pager::B2Pager;
+use super::writer::B2Writer;
+use crate::raw::*;
+use crate::*;
+
+/// B2 requires every part except the last one to be at least 5 MiB, we use
+/// 8 MiB by default to reduce the number of parts.
+///
+/// ref: <https://www.backblaze.com/docs/cloud-storage-large-files>
+const DEFAULT_WRITE_MIN_SIZE: usize = 8 * 1024 * 1024;
+
+/// Backblaze B2 services support via the B2 native API.
+#[doc = include_str!("docs.md")]
+#[derive(Default, Clone)]
+pub struct B2Builder {
+    root: Option<String>,
+    bucket: Option<String>,
+    bucket_id: Option<String>,
+    application_key_id: Option<String>,
+    application_key: Option<String>,
+    http_client: Option<HttpClient>,
+    /// the size of each part, and the range is 5MB ~ 5 GB.
+    write_min_size: Option<usize>,
+}
+
+impl Debug for B2Builder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Builder")
+            .field("root", &self.root)
+            .field("bucket", &self.bucket)
+            .field("bucket_id", &self.bucket_id)
+            .field("application_key_id", &"<redacted>")
+            .field("application_key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl B2Builder {
+    /// Set root of this backend.
+    ///
+    /// All operations will happen under this root.
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+
+        self
+    }
+
+    /// Set bucket name of this backend.
+    /// The param is required.
+    pub fn bucket(&mut self, bucket: &str) -> &mut Self {
+        if !bucket.is_empty() {
+            self.bucket = Some(bucket.to_string());
+        }
+
+        self
+    }
+
+    /// Set bucket id of this backend.
+    ///
+    /// B2 native API identifies buckets by id for most operations, the
+    /// param is required.
+    pub fn bucket_id(&mut self, bucket_id: &str) -> &mut Self {
+        if !bucket_id.is_empty() {
+            self.bucket_id = Some(bucket_id.to_string());
+        }
+
+        self
+    }
+
+    /// Set application_key_id of this backend.
+    pub fn application_key_id(&mut self, application_key_id: &str) -> &mut Self {
+        if !application_key_id.is_empty() {
+            self.application_key_id = Some(application_key_id.to_string());
+        }
+
+        self
+    }
+
+    /// Set application_key of this backend.
+    pub fn application_key(&mut self, application_key: &str) -> &mut Self {
+        if !application_key.is_empty() {
+            self.application_key = Some(application_key.to_string());
+        }
+
+        self
+    }
+
+    /// Set the minimum size of unsized write, it should be greater than 5 MB.
+    ///
+    /// Reference: [B2 Large Files](https://www.backblaze.com/docs/cloud-storage-large-files)
+    pub fn write_min_size(&mut self, write_min_size: usize) -> &mut Self {
+        self.write_min_size = Some(write_min_size);
+
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for B2Builder {
+    const SCHEME: Scheme = Scheme::B2;
+    type Accessor = B2Backend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = B2Builder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("bucket").map(|v| builder.bucket(v));
+        map.get("bucket_id").map(|v| builder.bucket_id(v));
+        map.get("application_key_id")
+            .map(|v| builder.application_key_id(v));
+        map.get("application_key")
+            .map(|v| builder.application_key(v));
+        map.get("write_min_size")
+            .map(|v| builder.write_min_size(v.parse::<usize>().unwrap()));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        debug!("backend build started: {:?}", &self);
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let bucket = match &self.bucket {
+            Some(bucket) => Ok(bucket.to_string()),
+            None => Err(
+                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
+                    .with_context("service", Scheme::B2),
+            ),
+        }?;
+        debug!("backend use bucket {}", &bucket);
+
+        let bucket_id = match &self.bucket_id {
+            Some(bucket_id) => Ok(bucket_id.to_string()),
+            None => Err(
+                Error::new(ErrorKind::ConfigInvalid, "The bucket_id is misconfigured")
+                    .with_context("service", Scheme::B2),
+            ),
+        }?;
+        debug!("backend use bucket_id {}", &bucket_id);
+
+        let application_key_id = match self.application_key_id.take() {
+            Some(v) => Ok(v),
+            None => Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The application_key_id is misconfigured",
+            )
+            .with_context("service", Scheme::B2)),
+        }?;
+
+        let application_key = match self.application_key.take() {
+            Some(v) => Ok(v),
+            None => Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The application_key is misconfigured",
+            )
+            .with_context("service", Scheme::B2)),
+        }?;
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::B2)
+            })?
+        };
+
+        let write_min_size = self.write_min_size.unwrap_or(DEFAULT_WRITE_MIN_SIZE);
+        if write_min_size < 5 * 1024 * 1024 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The write minimum buffer size is misconfigured",
+            )
+            .with_context("service", Scheme::B2));
+        }
+
+        let signer = B2Signer {
+            application_key_id,
+            application_key,
+            auth_info: AuthInfo::default(),
+        };
+
+        debug!("backend build finished");
+        Ok(B2Backend {
+            core: Arc::new(B2Core {
+                signer: Arc::new(RwLock::new(signer)),
+                root,
+                bucket,
+                bucket_id,
+                client,
+                write_min_size,
+            }),
+        })
+    }
+}
+
+/// Backend for Backblaze B2 services.
+#[derive(Debug, Clone)]
+pub struct B2Backend {
+    core: Arc<B2Core>,
+}
+
+#[async_trait]
+impl Accessor for B2Backend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = B2Writer;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = B2Pager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::B2)
+            .set_root(&self.core.root)
+            .set_name(&self.core.bucket)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_with_content_type: true,
+                write_without_content_length: true,
+
+                delete: true,
+                create_dir: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_delimiter_slash: true,
+                list_without_delimiter: true,
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        // The sha1 checksum of empty content.
+        const EMPTY_SHA1: &str = "da39a3ee5e6b4b0d3255bfef95601890afd80709";
+
+        let resp = self
+            .core
+            .b2_upload_file(path, 0, None, EMPTY_SHA1, AsyncBody::Empty)
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCreateDir::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let resp = self
+            .core
+            .b2_download_file_by_name(path, args.range())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Ok((
+            RpWrite::default(),
+            B2Writer::new(self.core.clone(), args, path.to_string()),
+        ))
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        // B2 doesn't have real dirs, check if there are any files under
+        // this prefix instead.
+        if path.ends_with('/') {
+            let resp = self
+                .core
+                .b2_list_file_names(path, Some("/"), None, Some(1))
+                .await?;
+
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let output: ListFileNamesResponse =
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+            return if output.files.is_empty() {
+                Err(Error::new(ErrorKind::NotFound, "dir not found"))
+            } else {
+                Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
+            };
+        }
+
+        let resp = self.core.b2_head_file_by_name(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                if meta.last_modified().is_none() {
+                    if let Some(v) = parse_upload_timestamp(resp.headers())? {
+                        meta.set_last_modified(v);
+                    }
+                }
+                Ok(RpStat::new(meta))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.b2_hide_file(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => {
+                let err = parse_error(resp).await?;
+                match err.kind() {
+                    ErrorKind::NotFound => Ok(RpDelete::default()),
+                    _ => Err(err),
+                }
+            }
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((
+            RpList::default(),
+            B2Pager::new(self.core.clone(), path, args.delimiter(), args.limit()),
+        ))
+    }
+}
@@ -0,0 +1,588 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use http::header;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::RwLock;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+const B2_AUTHORIZE_ACCOUNT_URL: &str = "https://api.backblazeb2.com/b2api/v2/b2_authorize_account";
+
+mod constants {
+    pub const X_BZ_FILE_NAME: &str = "X-Bz-File-Name";
+    pub const X_BZ_CONTENT_SHA1: &str = "X-Bz-Content-Sha1";
+    pub const X_BZ_PART_NUMBER: &str = "X-Bz-Part-Number";
+    pub const X_BZ_UPLOAD_TIMESTAMP: &str = "X-Bz-Upload-Timestamp";
+}
+
+pub struct B2Core {
+    pub signer: Arc<RwLock<B2Signer>>,
+
+    /// The root of this core.
+    pub root: String,
+    /// The bucket name of this backend.
+    pub bucket: String,
+    /// The bucket id of this backend.
+    pub bucket_id: String,
+
+    pub client: HttpClient,
+    pub write_min_size: usize,
+}
+
+impl Debug for B2Core {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Backend")
+            .field("root", &self.root)
+            .field("bucket", &self.bucket)
+            .field("bucket_id", &self.bucket_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl B2Core {
+    /// Get the authorization info of current account.
+    ///
+    /// The authorization token will be cached and refreshed before it
+    /// expires (24 hours after `b2_authorize_account`).
+    ///
+    /// ref: https://www.backblaze.com/apidocs/b2-authorize-account
+    pub async fn get_auth_info(&self) -> Result<AuthInfo> {
+        {
+            let signer = self.signer.read().await;
+
+            if !signer.auth_info.authorization_token.is_empty()
+                && signer.auth_info.expires_in > Utc::now()
+            {
+                return Ok(signer.auth_info.clone());
+            }
+        }
+
+        let mut signer = self.signer.write().await;
+
+        // Another task could have refreshed the token while we are waiting.
+        if !signer.auth_info.authorization_token.is_empty()
+            && signer.auth_info.expires_in > Utc::now()
+        {
+            return Ok(signer.auth_info.clone());
+        }
+
+        let req = Request::get(B2_AUTHORIZE_ACCOUNT_URL)
+            .header(
+                header::AUTHORIZATION,
+                format_authorization_by_basic(&signer.application_key_id, &signer.application_key)?,
+            )
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let token: AuthorizeAccountResponse =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+                signer.auth_info = AuthInfo {
+                    authorization_token: token.authorization_token,
+                    api_url: token.api_url,
+                    download_url: token.download_url,
+                    // The token is valid for 24 hours, refresh it one hour earlier.
+                    expires_in: Utc::now() + Duration::hours(23),
+                };
+
+                Ok(signer.auth_info.clone())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    #[inline]
+    pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        self.client.send(req).await
+    }
+
+    async fn send_json<T: Serialize>(
+        &self,
+        api: &str,
+        payload: &T,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let auth_info = self.get_auth_info().await?;
+
+        let url = format!("{}/b2api/v2/{}", auth_info.api_url, api);
+
+        let bs = serde_json::to_vec(payload).map_err(new_json_serialize_error)?;
+
+        let req = Request::post(&url)
+            .header(header::AUTHORIZATION, auth_info.authorization_token)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_LENGTH, bs.len())
+            .body(AsyncBody::Bytes(Bytes::from(bs)))
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+}
+
+impl B2Core {
+    /// ref: https://www.backblaze.com/apidocs/b2-download-file-by-name
+    pub async fn b2_download_file_by_name(
+        &self,
+        path: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let auth_info = self.get_auth_info().await?;
+
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/file/{}/{}",
+            auth_info.download_url,
+            self.bucket,
+            percent_encode_path(&p)
+        );
+
+        let mut req =
+            Request::get(&url).header(header::AUTHORIZATION, auth_info.authorization_token);
+
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    /// Fetch the metadata of file via `HEAD` request of `b2_download_file_by_name`.
+    pub async fn b2_head_file_by_name(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let auth_info = self.get_auth_info().await?;
+
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/file/{}/{}",
+            auth_info.download_url,
+            self.bucket,
+            percent_encode_path(&p)
+        );
+
+        let req = Request::head(&url)
+            .header(header::AUTHORIZATION, auth_info.authorization_token)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    /// ref: https://www.backblaze.com/apidocs/b2-get-upload-url
+    async fn b2_get_upload_url(&self) -> Result<UploadUrlResponse> {
+        let resp = self
+            .send_json(
+                "b2_get_upload_url",
+                &GetUploadUrlRequest {
+                    bucket_id: self.bucket_id.clone(),
+                },
+            )
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Upload a file in one request.
+    ///
+    /// ref: https://www.backblaze.com/apidocs/b2-upload-file
+    pub async fn b2_upload_file(
+        &self,
+        path: &str,
+        size: u64,
+        content_type: Option<&str>,
+        sha1: &str,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let upload_url = self.b2_get_upload_url().await?;
+
+        let p = build_abs_path(&self.root, path);
+
+        let req = Request::post(&upload_url.upload_url)
+            .header(header::AUTHORIZATION, upload_url.authorization_token)
+            .header(constants::X_BZ_FILE_NAME, percent_encode_path(&p))
+            // `b2/x-auto` means B2 will figure out the content type by itself.
+            .header(header::CONTENT_TYPE, content_type.unwrap_or("b2/x-auto"))
+            .header(header::CONTENT_LENGTH, size)
+            .header(constants::X_BZ_CONTENT_SHA1, sha1)
+            .body(body)
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    /// ref: https://www.backblaze.com/apidocs/b2-start-large-file
+    pub async fn b2_start_large_file(
+        &self,
+        path: &str,
+        content_type: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        self.send_json(
+            "b2_start_large_file",
+            &StartLargeFileRequest {
+                bucket_id: self.bucket_id.clone(),
+                file_name: p,
+                content_type: content_type.unwrap_or("b2/x-auto").to_string(),
+            },
+        )
+        .await
+    }
+
+    /// ref: https://www.backblaze.com/apidocs/b2-get-upload-part-url
+    async fn b2_get_upload_part_url(&self, file_id: &str) -> Result<UploadUrlResponse> {
+        let resp = self
+            .send_json(
+                "b2_get_upload_part_url",
+                &GetUploadPartUrlRequest {
+                    file_id: file_id.to_string(),
+                },
+            )
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// ref: https://www.backblaze.com/apidocs/b2-upload-part
+    pub async fn b2_upload_part(
+        &self,
+        file_id: &str,
+        part_number: usize,
+        size: u64,
+        sha1: &str,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let upload_url = self.b2_get_upload_part_url(file_id).await?;
+
+        let req = Request::post(&upload_url.upload_url)
+            .header(header::AUTHORIZATION, upload_url.authorization_token)
+            .header(constants::X_BZ_PART_NUMBER, part_number)
+            .header(header::CONTENT_LENGTH, size)
+            .header(constants::X_BZ_CONTENT_SHA1, sha1)
+            .body(body)
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    /// ref: https://www.backblaze.com/apidocs/b2-finish-large-file
+    pub async fn b2_finish_large_file(
+        &self,
+        file_id: &str,
+        part_sha1_array: &[String],
+    ) -> Result<Response<IncomingAsyncBody>> {
+        self.send_json(
+            "b2_finish_large_file",
+            &FinishLargeFileRequest {
+                file_id: file_id.to_string(),
+                part_sha1_array: part_sha1_array.to_vec(),
+            },
+        )
+        .await
+    }
+
+    /// ref: https://www.backblaze.com/apidocs/b2-cancel-large-file
+    pub async fn b2_cancel_large_file(&self, file_id: &str) -> Result<Response<IncomingAsyncBody>> {
+        self.send_json(
+            "b2_cancel_large_file",
+            &CancelLargeFileRequest {
+                file_id: file_id.to_string(),
+            },
+        )
+        .await
+    }
+
+    /// Hide the file so that it will not be visible in `b2_list_file_names`
+    /// and `b2_download_file_by_name` anymore.
+    ///
+    /// ref: https://www.backblaze.com/apidocs/b2-hide-file
+    pub async fn b2_hide_file(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        self.send_json(
+            "b2_hide_file",
+            &HideFileRequest {
+                bucket_id: self.bucket_id.clone(),
+                file_name: p,
+            },
+        )
+        .await
+    }
+
+    /// ref: https://www.backblaze.com/apidocs/b2-list-file-names
+    pub async fn b2_list_file_names(
+        &self,
+        path: &str,
+        delimiter: Option<&str>,
+        start_file_name: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        self.send_json(
+            "b2_list_file_names",
+            &ListFileNamesRequest {
+                bucket_id: self.bucket_id.clone(),
+                prefix: p,
+                delimiter: delimiter.map(|v| v.to_string()),
+                start_file_name: start_file_name.map(|v| v.to_string()),
+                max_file_count: limit,
+            },
+        )
+        .await
+    }
+}
+
+pub struct B2Signer {
+    /// The key id of application key.
+    pub application_key_id: String,
+    /// The secret of application key.
+    pub application_key: String,
+
+    pub auth_info: AuthInfo,
+}
+
+#[derive(Clone)]
+pub struct AuthInfo {
+    pub authorization_token: String,
+    /// The base URL to use for all API calls except for uploading and downloading files.
+    pub api_url: String,
+    /// The base URL to use for downloading files.
+    pub download_url: String,
+
+    pub expires_in: DateTime<Utc>,
+}
+
+impl Default for AuthInfo {
+    fn default() -> Self {
+        AuthInfo {
+            authorization_token: String::new(),
+            api_url: String::new(),
+            download_url: String::new(),
+            expires_in: DateTime::<Utc>::MIN_UTC,
+        }
+    }
+}
+
+/// Parse the `X-Bz-Upload-Timestamp` header which is the milliseconds since epoch.
+pub fn parse_upload_timestamp(headers: &http::HeaderMap) -> Result<Option<DateTime<Utc>>> {
+    match headers.get(constants::X_BZ_UPLOAD_TIMESTAMP) {
+        None => Ok(None),
+        Some(v) => {
+            let v = v
+                .to_str()
+                .map_err(|e| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "header value is not valid utf-8 string",
+                    )
+                    .with_operation("http_util::parse_upload_timestamp")
+                    .set_source(e)
+                })?
+                .parse::<i64>()
+                .map_err(|e| {
+                    Error::new(ErrorKind::Unexpected, "header value is not valid integer")
+                        .with_operation("http_util::parse_upload_timestamp")
+                        .set_source(e)
+                })?;
+
+            Ok(Some(parse_datetime_from_from_timestamp_millis(v)?))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthorizeAccountResponse {
+    authorization_token: String,
+    api_url: String,
+    download_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadUrlResponse {
+    upload_url: String,
+    authorization_token: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetUploadUrlRequest {
+    bucket_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetUploadPartUrlRequest {
+    file_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StartLargeFileRequest {
+    bucket_id: String,
+    file_name: String,
+    content_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartLargeFileResponse {
+    pub file_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FinishLargeFileRequest {
+    file_id: String,
+    part_sha1_array: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CancelLargeFileRequest {
+    file_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HideFileRequest {
+    bucket_id: String,
+    file_name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ListFileNamesRequest {
+    bucket_id: String,
+    prefix: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delimiter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_file_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_file_count: Option<usize>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ListFileNamesResponse {
+    pub files: Vec<File>,
+    pub next_file_name: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct File {
+    /// `upload` for files and `folder` for the common prefixes.
+    pub action: String,
+    pub file_name: String,
+    pub content_length: u64,
+    pub content_type: Option<String>,
+    pub content_md5: Option<String>,
+    pub upload_timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_list_file_names_response() {
+        let bs = Bytes::from(
+            r#"{
+  "files": [
+    {
+      "accountId": "d522aa47a10f",
+      "action": "upload",
+      "bucketId": "e73ede9c9c8412db49f60715",
+      "contentLength": 6,
+      "contentSha1": "a9993e364706816aba3e25717850c26c9cd0d89d",
+      "contentMd5": "900150983cd24fb0d6963f7d28e17f72",
+      "contentType": "text/plain",
+      "fileId": "4_z27c88f1d182b150646ff0b16_f1004ba650fe24e6b_d20150809_m012853_c100_v0009990_t0000",
+      "fileInfo": {},
+      "fileName": "dir/a.txt",
+      "uploadTimestamp": 1439083733000
+    },
+    {
+      "accountId": "d522aa47a10f",
+      "action": "folder",
+      "bucketId": "e73ede9c9c8412db49f60715",
+      "contentLength": 0,
+      "contentSha1": null,
+      "contentMd5": null,
+      "contentType": null,
+      "fileId": null,
+      "fileInfo": {},
+      "fileName": "dir/sub/",
+      "uploadTimestamp": 0
+    }
+  ],
+  "nextFileName": "dir/sub/b.txt"
+}"#,
+        );
+
+        let out: ListFileNamesResponse = serde_json::from_slice(&bs).expect("must success");
+
+        assert_eq!(out.next_file_name, Some("dir/sub/b.txt".to_string()));
+        assert_eq!(out.files.len(), 2);
+        assert_eq!(out.files[0].action, "upload");
+        assert_eq!(out.files[0].file_name, "dir/a.txt");
+        assert_eq!(out.files[0].content_length, 6);
+        assert_eq!(out.files[0].content_type, Some("text/plain".to_string()));
+        assert_eq!(out.files[0].upload_timestamp, 1439083733000);
+        assert_eq!(out.files[1].action, "folder");
+        assert_eq!(out.files[1].file_name, "dir/sub/");
+        assert_eq!(out.files[1].content_type, None);
+    }
+}
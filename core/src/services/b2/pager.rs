@@ -0,0 +1,128 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::core::*;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct B2Pager {
+    core: Arc<B2Core>,
+
+    path: String,
+    delimiter: String,
+    limit: Option<usize>,
+
+    start_file_name: Option<String>,
+    done: bool,
+}
+
+impl B2Pager {
+    pub fn new(core: Arc<B2Core>, path: &str, delimiter: &str, limit: Option<usize>) -> Self {
+        Self {
+            core,
+            path: path.to_string(),
+            delimiter: delimiter.to_string(),
+            limit,
+
+            start_file_name: None,
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for B2Pager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let delimiter = if self.delimiter.is_empty() {
+            None
+        } else {
+            Some(self.delimiter.as_str())
+        };
+
+        let resp = self
+            .core
+            .b2_list_file_names(
+                &self.path,
+                delimiter,
+                self.start_file_name.as_deref(),
+                self.limit,
+            )
+            .await?;
+
+        if resp.status() != http::StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+
+        let output: ListFileNamesResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        self.done = output.next_file_name.is_none();
+        self.start_file_name = output.next_file_name;
+
+        let prefix = build_abs_path(&self.core.root, &self.path);
+
+        let mut entries = Vec::with_capacity(output.files.len());
+
+        for file in output.files {
+            // The dir itself should not be returned.
+            if file.file_name == prefix {
+                continue;
+            }
+
+            if file.action == "folder" {
+                let de = oio::Entry::new(
+                    &build_rel_path(&self.core.root, &file.file_name),
+                    Metadata::new(EntryMode::DIR),
+                );
+                entries.push(de);
+                continue;
+            }
+
+            if file.file_name.ends_with('/') {
+                continue;
+            }
+
+            let mut meta = Metadata::new(EntryMode::FILE);
+
+            meta.set_content_length(file.content_length);
+            if let Some(v) = &file.content_type {
+                meta.set_content_type(v);
+            }
+            if let Some(v) = &file.content_md5 {
+                meta.set_content_md5(v);
+            }
+            meta.set_last_modified(parse_datetime_from_from_timestamp_millis(
+                file.upload_timestamp,
+            )?);
+
+            let de = oio::Entry::new(&build_rel_path(&self.core.root, &file.file_name), meta);
+            entries.push(de);
+        }
+
+        Ok(Some(entries))
+    }
+}
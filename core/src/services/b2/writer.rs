@@ -0,0 +1,261 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::StatusCode;
+use sha1::Digest;
+use sha1::Sha1;
+
+use super::core::*;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct B2Writer {
+    core: Arc<B2Core>,
+
+    op: OpWrite,
+    path: String,
+    file_id: Option<String>,
+
+    part_sha1_array: Vec<String>,
+    buffer: oio::VectorCursor,
+    buffer_size: usize,
+}
+
+impl B2Writer {
+    pub fn new(core: Arc<B2Core>, op: OpWrite, path: String) -> Self {
+        let buffer_size = core.write_min_size;
+        B2Writer {
+            core,
+            op,
+            path,
+
+            file_id: None,
+            part_sha1_array: vec![],
+            buffer: oio::VectorCursor::new(),
+            buffer_size,
+        }
+    }
+
+    async fn write_oneshot(&self, bs: Bytes) -> Result<()> {
+        let sha1 = format_sha1(&bs);
+
+        let resp = self
+            .core
+            .b2_upload_file(
+                &self.path,
+                bs.len() as u64,
+                self.op.content_type(),
+                &sha1,
+                AsyncBody::Bytes(bs),
+            )
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn start_large_file(&self) -> Result<String> {
+        let resp = self
+            .core
+            .b2_start_large_file(&self.path, self.op.content_type())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let result: StartLargeFileResponse =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+                Ok(result.file_id)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write_part(&self, file_id: &str, bs: Bytes) -> Result<String> {
+        // B2 requires part number must between [1..=10000]
+        let part_number = self.part_sha1_array.len() + 1;
+        let sha1 = format_sha1(&bs);
+
+        let resp = self
+            .core
+            .b2_upload_part(
+                file_id,
+                part_number,
+                bs.len() as u64,
+                &sha1,
+                AsyncBody::Bytes(bs),
+            )
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(sha1)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Write for B2Writer {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        if self.file_id.is_none()
+            && self.buffer.is_empty()
+            && self.op.content_length().unwrap_or_default() == bs.len() as u64
+        {
+            return self.write_oneshot(bs).await;
+        }
+
+        // Ignore empty bytes
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.buffer.push(bs);
+        // Return directly if the buffer is not full
+        if self.buffer.len() <= self.buffer_size {
+            return Ok(());
+        }
+
+        // Large file requires at least two parts, so we only start it
+        // while we are sure that there is more data than one part.
+        let file_id = match &self.file_id {
+            Some(file_id) => file_id.clone(),
+            None => match self.start_large_file().await {
+                Ok(file_id) => {
+                    self.file_id = Some(file_id.clone());
+                    file_id
+                }
+                Err(e) => {
+                    self.buffer.pop();
+                    return Err(e);
+                }
+            },
+        };
+
+        // Only upload `buffer_size` bytes so that there are always some data
+        // left for the last part.
+        let bs = self.buffer.peak_exact(self.buffer_size);
+
+        match self.write_part(&file_id, bs).await {
+            Ok(sha1) => {
+                self.buffer.take(self.buffer_size);
+                self.part_sha1_array.push(sha1);
+                Ok(())
+            }
+            Err(e) => {
+                // If the upload fails, we should pop the given bs to make sure
+                // write is re-enter safe.
+                self.buffer.pop();
+                Err(e)
+            }
+        }
+    }
+
+    async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "Write::sink is not supported",
+        ))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.buffer.clear();
+
+        let file_id = if let Some(file_id) = &self.file_id {
+            file_id
+        } else {
+            return Ok(());
+        };
+
+        let resp = self.core.b2_cancel_large_file(file_id).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let file_id = match &self.file_id {
+            Some(file_id) => file_id.clone(),
+            None => {
+                // The whole content fits in one request, upload it directly.
+                if !self.buffer.is_empty() {
+                    let bs = self.buffer.peak_exact(self.buffer.len());
+                    self.write_oneshot(bs).await?;
+                    self.buffer.clear();
+                }
+                return Ok(());
+            }
+        };
+
+        // Make sure internal buffer has been flushed.
+        if !self.buffer.is_empty() {
+            let bs = self.buffer.peak_exact(self.buffer.len());
+
+            let sha1 = self.write_part(&file_id, bs).await?;
+            self.buffer.clear();
+            self.part_sha1_array.push(sha1);
+        }
+
+        let resp = self
+            .core
+            .b2_finish_large_file(&file_id, &self.part_sha1_array)
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+/// Format the hex encoded sha1 checksum of given content which is required
+/// by every upload in b2.
+fn format_sha1(bs: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(bs);
+
+    format!("{:x}", hasher.finalize())
+}
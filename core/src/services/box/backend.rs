@@ -0,0 +1,406 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+use tokio::sync::Mutex;
+
+use super::core::parse_box_item;
+use super::core::BoxCore;
+use super::core::BoxItem;
+use super::core::BoxJwtKey;
+use super::core::BoxSigner;
+use super::error::parse_error;
+use super::pager::BoxPager;
+use super::writer::BoxWriter;
+use crate::raw::*;
+use crate::*;
+
+/// [Box](https://www.box.com/) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct BoxBuilder {
+    root: Option<String>,
+
+    access_token: Option<String>,
+
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    enterprise_id: Option<String>,
+    user_id: Option<String>,
+
+    public_key_id: Option<String>,
+    private_key: Option<String>,
+    passphrase: Option<String>,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for BoxBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Builder")
+            .field("root", &self.root)
+            .field("client_id", &self.client_id)
+            .field("enterprise_id", &self.enterprise_id)
+            .field("user_id", &self.user_id)
+            .field("public_key_id", &self.public_key_id)
+            .finish()
+    }
+}
+
+impl BoxBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string());
+        }
+        self
+    }
+
+    /// Set the access token, for example the developer token of the app.
+    ///
+    /// NOTE: this token will be expired in 1 hour, please use JWT or client
+    /// credentials for long term access.
+    pub fn access_token(&mut self, access_token: &str) -> &mut Self {
+        if !access_token.is_empty() {
+            self.access_token = Some(access_token.to_string());
+        }
+        self
+    }
+
+    /// Set the client id of the Box app.
+    pub fn client_id(&mut self, client_id: &str) -> &mut Self {
+        if !client_id.is_empty() {
+            self.client_id = Some(client_id.to_string());
+        }
+        self
+    }
+
+    /// Set the client secret of the Box app.
+    pub fn client_secret(&mut self, client_secret: &str) -> &mut Self {
+        if !client_secret.is_empty() {
+            self.client_secret = Some(client_secret.to_string());
+        }
+        self
+    }
+
+    /// Set the enterprise id to authenticate as the service account of
+    /// the enterprise.
+    pub fn enterprise_id(&mut self, enterprise_id: &str) -> &mut Self {
+        if !enterprise_id.is_empty() {
+            self.enterprise_id = Some(enterprise_id.to_string());
+        }
+        self
+    }
+
+    /// Set the user id to authenticate as the user.
+    ///
+    /// `user_id` will take precedence over `enterprise_id` if both are set.
+    pub fn user_id(&mut self, user_id: &str) -> &mut Self {
+        if !user_id.is_empty() {
+            self.user_id = Some(user_id.to_string());
+        }
+        self
+    }
+
+    /// Set the id of the public key registered in the Box app.
+    ///
+    /// JWT auth will be used if this is set.
+    pub fn public_key_id(&mut self, public_key_id: &str) -> &mut Self {
+        if !public_key_id.is_empty() {
+            self.public_key_id = Some(public_key_id.to_string());
+        }
+        self
+    }
+
+    /// Set the private key in PEM format used to sign the JWT assertion.
+    pub fn private_key(&mut self, private_key: &str) -> &mut Self {
+        if !private_key.is_empty() {
+            self.private_key = Some(private_key.to_string());
+        }
+        self
+    }
+
+    /// Set the passphrase of the encrypted private key.
+    pub fn passphrase(&mut self, passphrase: &str) -> &mut Self {
+        if !passphrase.is_empty() {
+            self.passphrase = Some(passphrase.to_string());
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for BoxBuilder {
+    const SCHEME: Scheme = Scheme::Box;
+    type Accessor = BoxBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = BoxBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("access_token").map(|v| builder.access_token(v));
+        map.get("client_id").map(|v| builder.client_id(v));
+        map.get("client_secret").map(|v| builder.client_secret(v));
+        map.get("enterprise_id").map(|v| builder.enterprise_id(v));
+        map.get("user_id").map(|v| builder.user_id(v));
+        map.get("public_key_id").map(|v| builder.public_key_id(v));
+        map.get("private_key").map(|v| builder.private_key(v));
+        map.get("passphrase").map(|v| builder.passphrase(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Box)
+            })?
+        };
+
+        let signer = if let Some(access_token) = self.access_token.take() {
+            BoxSigner {
+                access_token,
+                ..Default::default()
+            }
+        } else {
+            let client_id = self.client_id.take().ok_or_else(|| {
+                Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "access_token or client_id must be set",
+                )
+                .with_context("service", Scheme::Box)
+            })?;
+            let client_secret = self.client_secret.take().ok_or_else(|| {
+                Error::new(ErrorKind::ConfigInvalid, "client_secret is required")
+                    .with_context("service", Scheme::Box)
+            })?;
+
+            let (subject_type, subject_id) = match (self.user_id.take(), self.enterprise_id.take())
+            {
+                (Some(user_id), _) => ("user", user_id),
+                (None, Some(enterprise_id)) => ("enterprise", enterprise_id),
+                (None, None) => {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "enterprise_id or user_id must be set",
+                    )
+                    .with_context("service", Scheme::Box))
+                }
+            };
+
+            let jwt_key = match (self.public_key_id.take(), self.private_key.take()) {
+                (Some(public_key_id), Some(private_key)) => Some(
+                    BoxJwtKey::parse(&public_key_id, &private_key, self.passphrase.as_deref())
+                        .map_err(|err| err.with_context("service", Scheme::Box))?,
+                ),
+                (None, None) => None,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "public_key_id and private_key must be set together",
+                    )
+                    .with_context("service", Scheme::Box))
+                }
+            };
+
+            BoxSigner {
+                client_id,
+                client_secret,
+                subject_type: subject_type.to_string(),
+                subject_id,
+                jwt_key,
+                ..Default::default()
+            }
+        };
+
+        Ok(BoxBackend {
+            core: Arc::new(BoxCore {
+                root,
+                client,
+                signer: Arc::new(Mutex::new(signer)),
+                path_cache: Arc::default(),
+            }),
+        })
+    }
+}
+
+/// Backend for box services.
+#[derive(Clone, Debug)]
+pub struct BoxBackend {
+    core: Arc<BoxCore>,
+}
+
+#[async_trait]
+impl Accessor for BoxBackend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = BoxWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = BoxPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut ma = AccessorInfo::default();
+        ma.set_scheme(Scheme::Box)
+            .set_root(&self.core.root)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_without_content_length: true,
+
+                create_dir: true,
+                delete: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_delimiter_slash: true,
+
+                ..Default::default()
+            });
+
+        ma
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.core.ensure_dir(path).await?;
+
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let item = match self.core.resolve(path).await? {
+            Some(item) if !item.is_dir => item,
+            _ => return Err(Error::new(ErrorKind::NotFound, "file not found in box")),
+        };
+
+        let resp = self.core.box_download(&item.id, args.range()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Ok((
+            RpWrite::default(),
+            BoxWriter::new(self.core.clone(), args, path.to_string()),
+        ))
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        let item = self
+            .core
+            .resolve(path)
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "path not found in box"))?;
+
+        if item.is_dir {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+        if path.ends_with('/') {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                "the path is a file but a dir is expected",
+            ));
+        }
+
+        let resp = self.core.box_get_file(&item.id).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let item: BoxItem =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+                Ok(RpStat::new(parse_box_item(&item)?))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        // The root folder of box can't be deleted.
+        if build_rooted_abs_path(&self.core.root, path) == "/" {
+            return Ok(RpDelete::default());
+        }
+
+        let item = match self.core.resolve(path).await? {
+            Some(item) => item,
+            // Deleting a not exist path should succeed.
+            None => return Ok(RpDelete::default()),
+        };
+
+        let resp = self.core.box_delete(&item).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                self.core.remove_path_cache(path).await;
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((
+            RpList::default(),
+            BoxPager::new(self.core.clone(), path, args.limit()),
+        ))
+    }
+}
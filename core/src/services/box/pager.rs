@@ -0,0 +1,129 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::core::parse_box_item;
+use super::core::BoxCore;
+use super::core::BoxItemCollection;
+use super::core::BoxPathItem;
+use super::core::BOX_LIST_LIMIT;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct BoxPager {
+    core: Arc<BoxCore>,
+    path: String,
+    limit: usize,
+
+    folder_id: Option<String>,
+    marker: String,
+    done: bool,
+}
+
+impl BoxPager {
+    pub fn new(core: Arc<BoxCore>, path: &str, limit: Option<usize>) -> Self {
+        BoxPager {
+            core,
+            path: path.to_string(),
+            limit: limit.unwrap_or(BOX_LIST_LIMIT).min(BOX_LIST_LIMIT),
+
+            folder_id: None,
+            marker: String::new(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for BoxPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let folder_id = match &self.folder_id {
+            Some(folder_id) => folder_id.clone(),
+            None => match self.core.resolve(&self.path).await? {
+                Some(item) if item.is_dir => {
+                    self.folder_id = Some(item.id.clone());
+                    item.id
+                }
+                // Listing a not exist dir should return empty.
+                _ => {
+                    self.done = true;
+                    return Ok(None);
+                }
+            },
+        };
+
+        let resp = self
+            .core
+            .box_list_folder(&folder_id, self.limit, &self.marker)
+            .await?;
+        if resp.status() != StatusCode::OK {
+            let err = parse_error(resp).await?;
+            // The folder could be deleted after resolved.
+            if err.kind() == ErrorKind::NotFound {
+                self.done = true;
+                return Ok(None);
+            }
+            return Err(err);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: BoxItemCollection =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        match output.next_marker {
+            Some(marker) if !marker.is_empty() => self.marker = marker,
+            _ => self.done = true,
+        }
+
+        let parent = if self.path == "/" { "" } else { &self.path };
+
+        let mut entries = Vec::with_capacity(output.entries.len());
+        for item in output.entries {
+            let meta = parse_box_item(&item)?;
+
+            let path = if item.is_dir() {
+                format!("{parent}{}/", item.name)
+            } else {
+                format!("{parent}{}", item.name)
+            };
+
+            // Cache the item so that later operations on this path don't
+            // need to resolve it again.
+            self.core
+                .insert_path_cache(
+                    &path,
+                    BoxPathItem {
+                        is_dir: item.is_dir(),
+                        id: item.id,
+                    },
+                )
+                .await;
+            entries.push(oio::Entry::new(&path, meta));
+        }
+
+        Ok(Some(entries))
+    }
+}
@@ -0,0 +1,299 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine;
+use bytes::Bytes;
+use http::StatusCode;
+use sha1::Digest;
+use sha1::Sha1;
+
+use super::core::BoxCore;
+use super::core::BoxFiles;
+use super::core::BoxPathItem;
+use super::core::BoxUploadPart;
+use super::core::BoxUploadSession;
+use super::core::BoxUploadedPart;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// Box only allows files not smaller than 20 MiB to be uploaded via upload
+/// session.
+const BOX_CHUNKED_UPLOAD_MIN_SIZE: u64 = 20 * 1024 * 1024;
+
+pub struct BoxWriter {
+    core: Arc<BoxCore>,
+    op: OpWrite,
+    path: String,
+
+    session: Option<BoxUploadSession>,
+    total: u64,
+    offset: u64,
+    parts: Vec<BoxUploadPart>,
+    hasher: Sha1,
+    buffer: oio::VectorCursor,
+}
+
+impl BoxWriter {
+    pub fn new(core: Arc<BoxCore>, op: OpWrite, path: String) -> Self {
+        BoxWriter {
+            core,
+            op,
+            path,
+
+            session: None,
+            total: 0,
+            offset: 0,
+            parts: vec![],
+            hasher: Sha1::new(),
+            buffer: oio::VectorCursor::new(),
+        }
+    }
+
+    /// Resolve the target of upload.
+    ///
+    /// Returns the file id if the file exists, otherwise returns the id of
+    /// the parent folder which will be created if not exist.
+    async fn prepare(&self) -> Result<(Option<String>, String, &str)> {
+        let (parent, name) = match self.path.rsplit_once('/') {
+            Some((parent, name)) => (format!("{parent}/"), name),
+            None => ("/".to_string(), self.path.as_str()),
+        };
+
+        match self.core.resolve(&self.path).await? {
+            Some(item) if item.is_dir => Err(Error::new(
+                ErrorKind::IsADirectory,
+                "the path to write is a directory",
+            )),
+            Some(item) => Ok((Some(item.id), String::new(), name)),
+            None => Ok((None, self.core.ensure_dir(&parent).await?, name)),
+        }
+    }
+
+    async fn write_oneshot(&self, bs: Bytes) -> Result<()> {
+        let (file_id, parent_id, name) = self.prepare().await?;
+
+        let resp = self
+            .core
+            .box_upload(file_id.as_deref(), &parent_id, name, bs)
+            .await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK | StatusCode::CREATED => self.finish(resp.into_body()).await,
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn create_session(&self, size: u64) -> Result<BoxUploadSession> {
+        let (file_id, parent_id, name) = self.prepare().await?;
+
+        let resp = self
+            .core
+            .box_create_upload_session(file_id.as_deref(), &parent_id, name, size)
+            .await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::CREATED => {
+                let bs = resp.into_body().bytes().await?;
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Upload the first `size` bytes in buffer as a part.
+    async fn write_part(&mut self, session_id: &str, size: usize) -> Result<()> {
+        let bs = self.buffer.peak_exact(size);
+
+        let resp = self
+            .core
+            .box_upload_part(session_id, self.offset, self.total, bs.clone())
+            .await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                let body = resp.into_body().bytes().await?;
+                let output: BoxUploadedPart =
+                    serde_json::from_slice(&body).map_err(new_json_deserialize_error)?;
+
+                self.hasher.update(&bs);
+                self.buffer.take(size);
+                self.offset += size as u64;
+                self.parts.push(output.part);
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Cache the id of uploaded file.
+    async fn finish(&self, body: IncomingAsyncBody) -> Result<()> {
+        let bs = body.bytes().await?;
+        let output: BoxFiles = serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        if let Some(file) = output.entries.into_iter().next() {
+            self.core
+                .insert_path_cache(
+                    &self.path,
+                    BoxPathItem {
+                        id: file.id,
+                        is_dir: false,
+                    },
+                )
+                .await;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl oio::Write for BoxWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        // Start the upload session before the first write if the file is
+        // large enough.
+        if self.session.is_none() && self.buffer.is_empty() {
+            match self.op.content_length() {
+                Some(size) if size >= BOX_CHUNKED_UPLOAD_MIN_SIZE => {
+                    self.session = Some(self.create_session(size).await?);
+                    self.total = size;
+                }
+                Some(size) if size == bs.len() as u64 => return self.write_oneshot(bs).await,
+                _ => {}
+            }
+        }
+
+        // Ignore empty bytes
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.buffer.push(bs);
+
+        // Buffer all content until close if upload session is not used.
+        let (session_id, part_size) = match &self.session {
+            Some(session) => (session.id.clone(), session.part_size),
+            None => return Ok(()),
+        };
+        // Return directly if the buffer is not full
+        if self.buffer.len() < part_size {
+            return Ok(());
+        }
+
+        match self.write_part(&session_id, part_size).await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // If the upload fails, we should pop the given bs to make sure
+                // write is re-enter safe.
+                self.buffer.pop();
+                Err(e)
+            }
+        }
+    }
+
+    async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "Write::sink is not supported",
+        ))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.buffer.clear();
+
+        let session = if let Some(session) = self.session.take() {
+            session
+        } else {
+            return Ok(());
+        };
+
+        let resp = self.core.box_abort_upload_session(&session.id).await?;
+        match resp.status() {
+            StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if self.session.is_none() {
+            let size = self.buffer.len() as u64;
+            // Nothing has been written.
+            if size == 0 && self.op.content_length().is_some() {
+                return Ok(());
+            }
+
+            if size < BOX_CHUNKED_UPLOAD_MIN_SIZE {
+                let bs = if self.buffer.is_empty() {
+                    Bytes::new()
+                } else {
+                    self.buffer.peak_exact(self.buffer.len())
+                };
+                self.write_oneshot(bs).await?;
+                self.buffer.clear();
+                return Ok(());
+            }
+
+            self.session = Some(self.create_session(size).await?);
+            self.total = size;
+        }
+
+        let (session_id, part_size) = match &self.session {
+            Some(session) => (session.id.clone(), session.part_size),
+            None => unreachable!("session must be created"),
+        };
+
+        // Make sure every part except the last one has exactly `part_size` bytes.
+        while !self.buffer.is_empty() {
+            let size = self.buffer.len().min(part_size);
+            self.write_part(&session_id, size).await?;
+        }
+
+        let digest = general_purpose::STANDARD.encode(self.hasher.clone().finalize());
+        let resp = self
+            .core
+            .box_commit_upload_session(&session_id, &self.parts, &digest)
+            .await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::CREATED => {
+                self.finish(resp.into_body()).await?;
+                self.session = None;
+                Ok(())
+            }
+            // Box is still processing the uploaded parts, we should retry
+            // the commit later.
+            StatusCode::ACCEPTED => {
+                resp.into_body().consume().await?;
+                Err(
+                    Error::new(ErrorKind::Unexpected, "upload session is still processing")
+                        .set_temporary(),
+                )
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
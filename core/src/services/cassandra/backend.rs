@@ -0,0 +1,444 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use scylla::prepared_statement::PreparedStatement;
+use scylla::transport::errors::QueryError;
+use scylla::transport::iterator::NextRowError;
+use scylla::Session;
+use scylla::SessionBuilder;
+use tokio::sync::OnceCell;
+
+use crate::raw::adapters::kv;
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_CASSANDRA_ENDPOINTS: &str = "127.0.0.1:9042";
+/// Cassandra suggests to keep cells smaller than 1 MiB, so we split values
+/// into chunks of 512 KiB by default.
+const DEFAULT_CHUNK_SIZE: usize = 512 * 1024;
+
+/// [Cassandra](https://cassandra.apache.org/) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Clone, Default)]
+pub struct CassandraBuilder {
+    /// network address of the cassandra nodes.
+    ///
+    /// default is "127.0.0.1:9042"
+    endpoints: Option<String>,
+    /// the keyspace of the table.
+    keyspace: Option<String>,
+    /// the table to store data.
+    table: Option<String>,
+    /// the username to connect cassandra.
+    ///
+    /// default is None
+    username: Option<String>,
+    /// the password for authentication.
+    ///
+    /// default is None
+    password: Option<String>,
+    /// the max size of value stored in one row.
+    ///
+    /// default is 512 KiB
+    chunk_size: Option<usize>,
+    /// the working directory of the cassandra service. Can be "/path/to/dir"
+    ///
+    /// default is "/"
+    root: Option<String>,
+}
+
+impl Debug for CassandraBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+
+        ds.field("endpoints", &self.endpoints);
+        ds.field("keyspace", &self.keyspace);
+        ds.field("table", &self.table);
+        ds.field("username", &self.username);
+        if self.password.is_some() {
+            ds.field("password", &"<redacted>");
+        }
+        ds.field("chunk_size", &self.chunk_size);
+        ds.field("root", &self.root);
+        ds.finish()
+    }
+}
+
+impl CassandraBuilder {
+    /// set the network address of cassandra nodes.
+    ///
+    /// multiple endpoints can be separated by `,`, for example:
+    /// `127.0.0.1:9042,127.0.0.2:9042`.
+    ///
+    /// default: "127.0.0.1:9042"
+    pub fn endpoints(&mut self, endpoints: &str) -> &mut Self {
+        if !endpoints.is_empty() {
+            self.endpoints = Some(endpoints.to_owned());
+        }
+        self
+    }
+
+    /// set the keyspace of the table.
+    pub fn keyspace(&mut self, keyspace: &str) -> &mut Self {
+        if !keyspace.is_empty() {
+            self.keyspace = Some(keyspace.to_owned());
+        }
+        self
+    }
+
+    /// set the table to store data.
+    pub fn table(&mut self, table: &str) -> &mut Self {
+        if !table.is_empty() {
+            self.table = Some(table.to_owned());
+        }
+        self
+    }
+
+    /// set the username for cassandra
+    ///
+    /// default: no username
+    pub fn username(&mut self, username: &str) -> &mut Self {
+        if !username.is_empty() {
+            self.username = Some(username.to_owned());
+        }
+        self
+    }
+
+    /// set the password for cassandra
+    ///
+    /// default: no password
+    pub fn password(&mut self, password: &str) -> &mut Self {
+        if !password.is_empty() {
+            self.password = Some(password.to_owned());
+        }
+        self
+    }
+
+    /// set the max size of value stored in one row, larger values will be
+    /// split into multiple rows.
+    ///
+    /// default: 512 KiB
+    pub fn chunk_size(&mut self, chunk_size: usize) -> &mut Self {
+        if chunk_size > 0 {
+            self.chunk_size = Some(chunk_size);
+        }
+        self
+    }
+
+    /// set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_owned());
+        }
+        self
+    }
+}
+
+impl Builder for CassandraBuilder {
+    const SCHEME: Scheme = Scheme::Cassandra;
+    type Accessor = CassandraBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = CassandraBuilder::default();
+
+        map.get("endpoints").map(|v| builder.endpoints(v));
+        map.get("keyspace").map(|v| builder.keyspace(v));
+        map.get("table").map(|v| builder.table(v));
+        map.get("username").map(|v| builder.username(v));
+        map.get("password").map(|v| builder.password(v));
+        map.get("chunk_size")
+            .map(|v| v.parse::<usize>().map(|v| builder.chunk_size(v)));
+        map.get("root").map(|v| builder.root(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let endpoints = self
+            .endpoints
+            .clone()
+            .unwrap_or_else(|| DEFAULT_CASSANDRA_ENDPOINTS.to_string());
+        let endpoints: Vec<String> = endpoints
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        let keyspace = self.keyspace.clone().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "keyspace is required but not set")
+                .with_context("service", Scheme::Cassandra)
+        })?;
+        let table = self.table.clone().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "table is required but not set")
+                .with_context("service", Scheme::Cassandra)
+        })?;
+
+        let root = normalize_root(
+            self.root
+                .clone()
+                .unwrap_or_else(|| "/".to_string())
+                .as_str(),
+        );
+
+        Ok(CassandraBackend::new(Adapter {
+            endpoints,
+            keyspace,
+            table,
+            username: self.username.clone(),
+            password: self.password.clone(),
+            chunk_size: self.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+            connection: OnceCell::new(),
+        })
+        .with_root(&root))
+    }
+}
+
+/// Backend for cassandra services.
+pub type CassandraBackend = kv::Backend<Adapter>;
+
+pub struct Adapter {
+    endpoints: Vec<String>,
+    keyspace: String,
+    table: String,
+    username: Option<String>,
+    password: Option<String>,
+    chunk_size: usize,
+
+    connection: OnceCell<Connection>,
+}
+
+// implement `Debug` manually, or password may be leaked.
+impl Debug for Adapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Adapter");
+        ds.field("endpoints", &self.endpoints.join(","));
+        ds.field("keyspace", &self.keyspace);
+        ds.field("table", &self.table);
+        ds.field("chunk_size", &self.chunk_size);
+        ds.finish()
+    }
+}
+
+/// Session and prepared statements of cassandra.
+///
+/// All statements are prepared, so that the driver is able to route
+/// requests to the replicas of the key directly.
+struct Connection {
+    session: Session,
+    get: PreparedStatement,
+    set: PreparedStatement,
+    trim: PreparedStatement,
+    delete: PreparedStatement,
+}
+
+impl Adapter {
+    async fn connection(&self) -> Result<&Connection> {
+        self.connection
+            .get_or_try_init(|| async {
+                let mut builder = SessionBuilder::new();
+                for endpoint in &self.endpoints {
+                    builder = builder.known_node(endpoint);
+                }
+                if let Some(username) = &self.username {
+                    builder = builder.user(username, self.password.clone().unwrap_or_default());
+                }
+
+                let session = builder.build().await.map_err(|e| {
+                    Error::new(ErrorKind::ConfigInvalid, "connect to cassandra failed")
+                        .with_context("service", Scheme::Cassandra)
+                        .with_context("endpoints", self.endpoints.join(","))
+                        .set_source(e)
+                })?;
+
+                let table = format!(
+                    "{}.{}",
+                    quote_ident(&self.keyspace),
+                    quote_ident(&self.table)
+                );
+                let get = session
+                    .prepare(format!("SELECT value FROM {table} WHERE key = ?"))
+                    .await
+                    .map_err(parse_query_error)?;
+                let set = session
+                    .prepare(format!(
+                        "INSERT INTO {table} (key, chunk, value) VALUES (?, ?, ?)"
+                    ))
+                    .await
+                    .map_err(parse_query_error)?;
+                let trim = session
+                    .prepare(format!("DELETE FROM {table} WHERE key = ? AND chunk >= ?"))
+                    .await
+                    .map_err(parse_query_error)?;
+                let delete = session
+                    .prepare(format!("DELETE FROM {table} WHERE key = ?"))
+                    .await
+                    .map_err(parse_query_error)?;
+
+                Ok(Connection {
+                    session,
+                    get,
+                    set,
+                    trim,
+                    delete,
+                })
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl kv::Adapter for Adapter {
+    fn metadata(&self) -> kv::Metadata {
+        kv::Metadata::new(
+            Scheme::Cassandra,
+            &format!("{}.{}", self.keyspace, self.table),
+            Capability {
+                read: true,
+                write: true,
+
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Read all chunks of the key in order.
+    ///
+    /// Rows are fetched page by page, so we will not hit the limit of
+    /// response size even if the value is very large.
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let conn = self.connection().await?;
+
+        let mut rows = conn
+            .session
+            .execute_iter(conn.get.clone(), (path,))
+            .await
+            .map_err(parse_query_error)?
+            .into_typed::<(Vec<u8>,)>();
+
+        let mut value = None;
+        while let Some((chunk,)) = rows.try_next().await.map_err(parse_next_row_error)? {
+            value.get_or_insert_with(Vec::new).extend_from_slice(&chunk);
+        }
+        Ok(value)
+    }
+
+    /// Write the value chunk by chunk, and then remove the stale chunks
+    /// left by the previous value.
+    async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
+        let conn = self.connection().await?;
+
+        let chunks = split_chunks(value, self.chunk_size);
+        for (idx, chunk) in chunks.iter().enumerate() {
+            conn.session
+                .execute(&conn.set, (path, idx as i32, chunk.to_vec()))
+                .await
+                .map_err(parse_query_error)?;
+        }
+
+        conn.session
+            .execute(&conn.trim, (path, chunks.len() as i32))
+            .await
+            .map_err(parse_query_error)?;
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let conn = self.connection().await?;
+
+        conn.session
+            .execute(&conn.delete, (path,))
+            .await
+            .map_err(parse_query_error)?;
+        Ok(())
+    }
+}
+
+/// Split value into chunks of at most `chunk_size` bytes.
+///
+/// Empty value will still take one empty chunk so that we can tell it
+/// from a not existing key.
+fn split_chunks(value: &[u8], chunk_size: usize) -> Vec<&[u8]> {
+    if value.is_empty() {
+        return vec![value];
+    }
+    value.chunks(chunk_size).collect()
+}
+
+/// Quote the identifier so that it can be used in cql safely.
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn parse_query_error(e: QueryError) -> Error {
+    let retryable = matches!(
+        e,
+        QueryError::IoError(_) | QueryError::TimeoutError | QueryError::RequestTimeout(_)
+    );
+
+    let err = Error::new(ErrorKind::Unexpected, "error from cassandra").set_source(e);
+    if retryable {
+        err.set_temporary()
+    } else {
+        err
+    }
+}
+
+fn parse_next_row_error(e: NextRowError) -> Error {
+    match e {
+        NextRowError::QueryError(e) => parse_query_error(e),
+        NextRowError::FromRowError(e) => {
+            Error::new(ErrorKind::Unexpected, "unexpected row from cassandra").set_source(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_chunks() {
+        let cases: Vec<(&str, usize, usize)> = vec![
+            ("empty", 0, 1),
+            ("smaller than chunk", 1, 1),
+            ("equal to chunk", 4, 1),
+            ("larger than chunk", 5, 2),
+            ("multiple chunks", 12, 3),
+        ];
+
+        for (name, len, expected) in cases {
+            let value = vec![0; len];
+            let chunks = split_chunks(&value, 4);
+            assert_eq!(chunks.len(), expected, "{name}");
+            assert_eq!(chunks.concat(), value, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_quote_ident() {
+        assert_eq!(quote_ident("opendal"), "\"opendal\"");
+        assert_eq!(quote_ident("a\"b"), "\"a\"\"b\"");
+    }
+}
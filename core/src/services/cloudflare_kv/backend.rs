@@ -0,0 +1,426 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use http::header;
+use http::Request;
+use http::StatusCode;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use serde::Deserialize;
+
+use super::error::parse_error;
+use crate::raw::adapters::kv;
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_CLOUDFLARE_ENDPOINT: &str = "https://api.cloudflare.com/client/v4";
+/// The max number of keys returned by one list request.
+const DEFAULT_SCAN_LIMIT: usize = 1000;
+/// Workers KV doesn't accept ttl less than 60 seconds.
+const MIN_TTL: Duration = Duration::from_secs(60);
+
+/// [Cloudflare Workers KV](https://developers.cloudflare.com/kv/) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct CloudflareKvBuilder {
+    /// The api token used for authentication.
+    token: Option<String>,
+    /// The account id of the namespace.
+    account_id: Option<String>,
+    /// The id of the KV namespace.
+    namespace_id: Option<String>,
+    /// The endpoint of cloudflare api.
+    endpoint: Option<String>,
+    /// The default ttl for write operations.
+    default_ttl: Option<Duration>,
+    /// Root within this backend.
+    root: Option<String>,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for CloudflareKvBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+
+        if self.token.is_some() {
+            ds.field("token", &"<redacted>");
+        }
+        ds.field("account_id", &self.account_id);
+        ds.field("namespace_id", &self.namespace_id);
+        ds.field("endpoint", &self.endpoint);
+        ds.field("default_ttl", &self.default_ttl);
+        ds.field("root", &self.root);
+        ds.finish()
+    }
+}
+
+impl CloudflareKvBuilder {
+    /// Set the api token used for authentication.
+    ///
+    /// The token must have the `Workers KV Storage` permission.
+    pub fn token(&mut self, token: &str) -> &mut Self {
+        if !token.is_empty() {
+            self.token = Some(token.to_string())
+        }
+        self
+    }
+
+    /// Set the account id of the namespace.
+    pub fn account_id(&mut self, account_id: &str) -> &mut Self {
+        if !account_id.is_empty() {
+            self.account_id = Some(account_id.to_string())
+        }
+        self
+    }
+
+    /// Set the id of the KV namespace.
+    pub fn namespace_id(&mut self, namespace_id: &str) -> &mut Self {
+        if !namespace_id.is_empty() {
+            self.namespace_id = Some(namespace_id.to_string())
+        }
+        self
+    }
+
+    /// Set the endpoint of cloudflare api.
+    ///
+    /// default: "https://api.cloudflare.com/client/v4"
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+        self
+    }
+
+    /// Set the default ttl for write operations.
+    ///
+    /// If set, every written key will be expired after the ttl. Workers KV
+    /// requires the ttl to be at least 60 seconds.
+    pub fn default_ttl(&mut self, ttl: Duration) -> &mut Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for CloudflareKvBuilder {
+    const SCHEME: Scheme = Scheme::CloudflareKv;
+    type Accessor = CloudflareKvBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = CloudflareKvBuilder::default();
+
+        map.get("token").map(|v| builder.token(v));
+        map.get("account_id").map(|v| builder.account_id(v));
+        map.get("namespace_id").map(|v| builder.namespace_id(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("default_ttl").map(|v| {
+            v.parse::<u64>()
+                .map(|v| builder.default_ttl(Duration::from_secs(v)))
+        });
+        map.get("root").map(|v| builder.root(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let token = self.token.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "token is required but not set")
+                .with_context("service", Scheme::CloudflareKv)
+        })?;
+        let account_id = self.account_id.take().ok_or_else(|| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "account_id is required but not set",
+            )
+            .with_context("service", Scheme::CloudflareKv)
+        })?;
+        let namespace_id = self.namespace_id.take().ok_or_else(|| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "namespace_id is required but not set",
+            )
+            .with_context("service", Scheme::CloudflareKv)
+        })?;
+
+        if let Some(ttl) = self.default_ttl {
+            if ttl < MIN_TTL {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "default_ttl must be at least 60 seconds",
+                )
+                .with_context("service", Scheme::CloudflareKv));
+            }
+        }
+
+        let authorization = format_authorization_by_bearer(&token)?;
+
+        let endpoint = self
+            .endpoint
+            .take()
+            .unwrap_or_else(|| DEFAULT_CLOUDFLARE_ENDPOINT.to_string());
+        let url_prefix =
+            format!("{endpoint}/accounts/{account_id}/storage/kv/namespaces/{namespace_id}");
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::CloudflareKv)
+            })?
+        };
+
+        let root = normalize_root(self.root.take().unwrap_or_else(|| "/".to_string()).as_str());
+
+        Ok(CloudflareKvBackend::new(Adapter {
+            authorization,
+            namespace_id,
+            url_prefix,
+            default_ttl: self.default_ttl,
+            client,
+        })
+        .with_root(&root))
+    }
+}
+
+/// Backend for cloudflare kv services.
+pub type CloudflareKvBackend = kv::Backend<Adapter>;
+
+#[derive(Clone)]
+pub struct Adapter {
+    authorization: String,
+    namespace_id: String,
+    url_prefix: String,
+    default_ttl: Option<Duration>,
+    client: HttpClient,
+}
+
+// implement `Debug` manually, or token may be leaked.
+impl Debug for Adapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Adapter");
+        ds.field("namespace_id", &self.namespace_id);
+        ds.field("default_ttl", &self.default_ttl);
+        ds.finish()
+    }
+}
+
+impl Adapter {
+    /// Keys could contain `/`, so we need to encode all of them.
+    fn value_url(&self, key: &str) -> String {
+        format!(
+            "{}/values/{}",
+            self.url_prefix,
+            utf8_percent_encode(key, NON_ALPHANUMERIC)
+        )
+    }
+}
+
+#[async_trait]
+impl kv::Adapter for Adapter {
+    fn metadata(&self) -> kv::Metadata {
+        kv::Metadata::new(
+            Scheme::CloudflareKv,
+            &self.namespace_id,
+            Capability {
+                read: true,
+                write: true,
+                list: true,
+
+                ..Default::default()
+            },
+        )
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let req = Request::get(self.value_url(path))
+            .header(header::AUTHORIZATION, &self.authorization)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => Ok(Some(resp.into_body().bytes().await?.to_vec())),
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(None)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
+        let mut url = self.value_url(path);
+        if let Some(ttl) = self.default_ttl {
+            url.push_str(&format!("?expiration_ttl={}", ttl.as_secs()));
+        }
+
+        let req = Request::put(url)
+            .header(header::AUTHORIZATION, &self.authorization)
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .header(header::CONTENT_LENGTH, value.len())
+            .body(AsyncBody::Bytes(bytes::Bytes::copy_from_slice(value)))
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let req = Request::delete(self.value_url(path))
+            .header(header::AUTHORIZATION, &self.authorization)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// List keys by prefix page by page, every page returns a cursor to
+    /// continue with, and the cursor will be empty at the last page.
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut cursor = String::new();
+
+        loop {
+            let mut url = format!(
+                "{}/keys?limit={DEFAULT_SCAN_LIMIT}&prefix={}",
+                self.url_prefix,
+                utf8_percent_encode(path, NON_ALPHANUMERIC)
+            );
+            if !cursor.is_empty() {
+                url.push_str(&format!(
+                    "&cursor={}",
+                    utf8_percent_encode(&cursor, NON_ALPHANUMERIC)
+                ));
+            }
+
+            let req = Request::get(url)
+                .header(header::AUTHORIZATION, &self.authorization)
+                .body(AsyncBody::Empty)
+                .map_err(new_request_build_error)?;
+
+            let resp = self.client.send(req).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let output: ListKeysResponse =
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+            keys.extend(output.result.into_iter().map(|v| v.name));
+
+            cursor = output.result_info.cursor.unwrap_or_default();
+            if cursor.is_empty() {
+                break;
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+/// Ref: <https://developers.cloudflare.com/api/operations/workers-kv-namespace-list-a-namespace'-s-keys>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct ListKeysResponse {
+    result: Vec<ListKeysResult>,
+    result_info: ListKeysResultInfo,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct ListKeysResult {
+    name: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct ListKeysResultInfo {
+    cursor: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_list_keys_response() {
+        let content = r#"{
+            "errors": [],
+            "messages": [],
+            "result": [
+                {
+                    "expiration": 1577836800,
+                    "metadata": {"someMetadataKey": "someMetadataValue"},
+                    "name": "path/to/file"
+                }
+            ],
+            "success": true,
+            "result_info": {
+                "count": 1,
+                "cursor": "6Ck1la0VxJ0djhidm1MdX2FyDGxLKVeeHZZmORS_8XeSuhz9SjIJRaSa2lnsF01tQOHrfTGAP3R5X1Kv5iVUuMbNKhWNAXHOl6ePB0TUL8nw"
+            }
+        }"#;
+
+        let output: ListKeysResponse =
+            serde_json::from_str(content).expect("response must be valid");
+        assert_eq!(output.result.len(), 1);
+        assert_eq!(output.result[0].name, "path/to/file");
+        assert!(output.result_info.cursor.is_some());
+    }
+}
@@ -35,6 +35,14 @@ use crate::raw::*;
 use crate::services::cos::appender::CosAppender;
 use crate::*;
 
+/// COS requires every part except the last one to be at least 1 MiB, we use
+/// 8 MiB by default to reduce the number of parts.
+///
+/// ref: <https://www.tencentcloud.com/document/product/436/14112>
+const DEFAULT_WRITE_MIN_SIZE: usize = 8 * 1024 * 1024;
+/// The default concurrency of multipart upload.
+const DEFAULT_WRITE_CONCURRENCY: usize = 1;
+
 /// Huawei Cloud COS services support.
 #[doc = include_str!("docs.md")]
 #[derive(Default, Clone)]
@@ -43,8 +51,13 @@ pub struct CosBuilder {
     endpoint: Option<String>,
     secret_id: Option<String>,
     secret_key: Option<String>,
+    security_token: Option<String>,
     bucket: Option<String>,
     http_client: Option<HttpClient>,
+    /// the size of each part, and the range is 1MB ~ 5 GB.
+    write_min_size: Option<usize>,
+    /// the number of parts that could be uploaded concurrently.
+    write_concurrency: Option<usize>,
 
     disable_config_load: bool,
 }
@@ -56,6 +69,7 @@ impl Debug for CosBuilder {
             .field("endpoint", &self.endpoint)
             .field("secret_id", &"<redacted>")
             .field("secret_key", &"<redacted>")
+            .field("security_token", &"<redacted>")
             .field("bucket", &self.bucket)
             .finish()
     }
@@ -120,6 +134,40 @@ impl CosBuilder {
         self
     }
 
+    /// Set security_token for this backend.
+    ///
+    /// It's required when using the temporary credentials issued by CAM.
+    ///
+    /// - If security_token is set, we will take user's input first.
+    /// - If not, we will try to load it from environment.
+    pub fn security_token(&mut self, security_token: &str) -> &mut Self {
+        if !security_token.is_empty() {
+            self.security_token = Some(security_token.to_string());
+        }
+
+        self
+    }
+
+    /// Set the minimum size of unsized write, it should be greater than 1 MB.
+    ///
+    /// Reference: [COS Multipart upload](https://www.tencentcloud.com/document/product/436/14112)
+    pub fn write_min_size(&mut self, write_min_size: usize) -> &mut Self {
+        self.write_min_size = Some(write_min_size);
+
+        self
+    }
+
+    /// Set the number of parts that could be uploaded concurrently in
+    /// multipart upload, default to 1.
+    ///
+    /// opendal will buffer at most `write_concurrency * write_min_size`
+    /// bytes in memory for every writer.
+    pub fn write_concurrency(&mut self, write_concurrency: usize) -> &mut Self {
+        self.write_concurrency = Some(write_concurrency);
+
+        self
+    }
+
     /// Disable config load so that opendal will not load config from
     /// environment.
     ///
@@ -155,6 +203,11 @@ impl Builder for CosBuilder {
         map.get("endpoint").map(|v| builder.endpoint(v));
         map.get("secret_id").map(|v| builder.secret_id(v));
         map.get("secret_key").map(|v| builder.secret_key(v));
+        map.get("security_token").map(|v| builder.security_token(v));
+        map.get("write_min_size")
+            .map(|v| builder.write_min_size(v.parse::<usize>().unwrap()));
+        map.get("write_concurrency")
+            .map(|v| builder.write_concurrency(v.parse::<usize>().unwrap()));
 
         builder
     }
@@ -214,11 +267,31 @@ impl Builder for CosBuilder {
         if let Some(v) = self.secret_key.take() {
             cfg.secret_key = Some(v);
         }
+        if let Some(v) = self.security_token.take() {
+            cfg.security_token = Some(v);
+        }
 
         let cred_loader = TencentCosCredentialLoader::new(client.client(), cfg);
 
         let signer = TencentCosSigner::new();
 
+        let write_min_size = self.write_min_size.unwrap_or(DEFAULT_WRITE_MIN_SIZE);
+        if write_min_size < 1024 * 1024 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The write minimum buffer size is misconfigured",
+            )
+            .with_context("service", Scheme::Cos));
+        }
+        let write_concurrency = self.write_concurrency.unwrap_or(DEFAULT_WRITE_CONCURRENCY);
+        if write_concurrency == 0 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The write concurrency is misconfigured",
+            )
+            .with_context("service", Scheme::Cos));
+        }
+
         debug!("backend build finished");
         Ok(CosBackend {
             core: Arc::new(CosCore {
@@ -228,6 +301,8 @@ impl Builder for CosBuilder {
                 signer,
                 loader: cred_loader,
                 client,
+                write_min_size,
+                write_concurrency,
             }),
         })
     }
@@ -268,6 +343,7 @@ impl Accessor for CosBackend {
                 write: true,
                 write_with_content_type: true,
                 write_with_cache_control: true,
+                write_without_content_length: true,
 
                 append: true,
                 append_with_cache_control: true,
@@ -331,13 +407,6 @@ impl Accessor for CosBackend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        if args.content_length().is_none() {
-            return Err(Error::new(
-                ErrorKind::Unsupported,
-                "write without content length is not supported",
-            ));
-        }
-
         Ok((
             RpWrite::default(),
             CosWriter::new(self.core.clone(), args, path.to_string()),
@@ -14,34 +14,61 @@
 // KIND, either express or implied.  See the License for the
 // specific language governing permissions and limitations
 // under the License.
-
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bytes::Buf;
 use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
 use http::StatusCode;
 
-use super::core::CosCore;
+use super::core::*;
 use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+type PartFuture = BoxFuture<'static, (usize, Bytes, Result<CompleteMultipartUploadRequestPart>)>;
+
 pub struct CosWriter {
     core: Arc<CosCore>,
 
     op: OpWrite,
     path: String,
+    upload_id: Option<String>,
+
+    parts: Vec<CompleteMultipartUploadRequestPart>,
+    /// The number of parts that have been submitted.
+    part_number: usize,
+    /// Parts that are uploading, at most `write_concurrency` parts.
+    futures: FuturesUnordered<PartFuture>,
+    buffer: oio::VectorCursor,
+    buffer_size: usize,
 }
 
+/// `PartFuture` is not `Sync`, but it will only be used via `&mut self`,
+/// so it's safe to share the writer between threads.
+unsafe impl Sync for CosWriter {}
+
 impl CosWriter {
     pub fn new(core: Arc<CosCore>, op: OpWrite, path: String) -> Self {
-        CosWriter { core, op, path }
+        let buffer_size = core.write_min_size;
+        CosWriter {
+            core,
+            op,
+            path,
+
+            upload_id: None,
+            parts: vec![],
+            part_number: 0,
+            futures: FuturesUnordered::new(),
+            buffer: oio::VectorCursor::new(),
+            buffer_size,
+        }
     }
-}
 
-#[async_trait]
-impl oio::Write for CosWriter {
-    async fn write(&mut self, bs: Bytes) -> Result<()> {
+    async fn write_oneshot(&self, bs: Bytes) -> Result<()> {
         let mut req = self.core.cos_put_object_request(
             &self.path,
             Some(bs.len()),
@@ -65,6 +92,148 @@ impl oio::Write for CosWriter {
         }
     }
 
+    async fn initiate_upload(&self) -> Result<String> {
+        let resp = self
+            .core
+            .cos_initiate_multipart_upload(
+                &self.path,
+                self.op.content_type(),
+                self.op.cache_control(),
+            )
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let result: InitiateMultipartUploadResult =
+                    quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+                Ok(result.upload_id)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Submit a part to upload, the part will be uploaded while polling
+    /// `futures`.
+    fn submit_part(&mut self, upload_id: &str, part_number: usize, bs: Bytes) {
+        let core = self.core.clone();
+        let path = self.path.clone();
+        let upload_id = upload_id.to_string();
+
+        self.futures.push(Box::pin(async move {
+            let res = write_part(&core, &path, &upload_id, part_number, bs.clone()).await;
+            (part_number, bs, res)
+        }));
+    }
+
+    /// Wait for one uploading part to finish.
+    ///
+    /// The failed part will be submitted again so that it could be retried
+    /// in the next call.
+    async fn wait_part(&mut self, upload_id: &str) -> Result<()> {
+        let (part_number, bs, res) = match self.futures.next().await {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        match res {
+            Ok(part) => {
+                self.parts.push(part);
+                Ok(())
+            }
+            Err(e) => {
+                self.submit_part(upload_id, part_number, bs);
+                Err(e)
+            }
+        }
+    }
+}
+
+async fn write_part(
+    core: &CosCore,
+    path: &str,
+    upload_id: &str,
+    part_number: usize,
+    bs: Bytes,
+) -> Result<CompleteMultipartUploadRequestPart> {
+    let resp = core
+        .cos_upload_part_request(
+            path,
+            upload_id,
+            part_number,
+            bs.len() as u64,
+            AsyncBody::Bytes(bs),
+        )
+        .await?;
+
+    let status = resp.status();
+
+    match status {
+        StatusCode::OK => {
+            let etag = parse_etag(resp.headers())?
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "ETag not present in returning response",
+                    )
+                })?
+                .to_string();
+
+            resp.into_body().consume().await?;
+
+            Ok(CompleteMultipartUploadRequestPart { part_number, etag })
+        }
+        _ => Err(parse_error(resp).await?),
+    }
+}
+
+#[async_trait]
+impl oio::Write for CosWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let upload_id = match &self.upload_id {
+            Some(upload_id) => upload_id.to_string(),
+            None => {
+                if self.op.content_length().unwrap_or_default() == bs.len() as u64 {
+                    return self.write_oneshot(bs).await;
+                } else {
+                    let upload_id = self.initiate_upload().await?;
+                    self.upload_id = Some(upload_id.clone());
+                    upload_id
+                }
+            }
+        };
+
+        // Make sure we have room for the next part before accepting the
+        // given bs so that write is re-enter safe.
+        if self.futures.len() >= self.core.write_concurrency {
+            self.wait_part(&upload_id).await?;
+        }
+
+        // Ignore empty bytes
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.buffer.push(bs);
+        // Return directly if the buffer is not full
+        if self.buffer.len() <= self.buffer_size {
+            return Ok(());
+        }
+
+        let bs = self.buffer.peak_at_least(self.buffer_size);
+        self.buffer.take(bs.len());
+
+        // COS requires part number must between [1..=10000]
+        self.part_number += 1;
+        self.submit_part(&upload_id, self.part_number, bs);
+
+        Ok(())
+    }
+
     async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
         Err(Error::new(
             ErrorKind::Unsupported,
@@ -73,10 +242,67 @@ impl oio::Write for CosWriter {
     }
 
     async fn abort(&mut self) -> Result<()> {
-        Ok(())
+        let upload_id = if let Some(upload_id) = &self.upload_id {
+            upload_id
+        } else {
+            return Ok(());
+        };
+
+        // Drop all uploading parts.
+        self.futures = FuturesUnordered::new();
+
+        let resp = self
+            .core
+            .cos_abort_multipart_upload(&self.path, upload_id)
+            .await?;
+        match resp.status() {
+            // COS returns code 204 if abort succeeds.
+            StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
     }
 
     async fn close(&mut self) -> Result<()> {
-        Ok(())
+        let upload_id = if let Some(upload_id) = &self.upload_id {
+            upload_id.to_string()
+        } else {
+            return Ok(());
+        };
+
+        // Make sure internal buffer has been flushed.
+        if !self.buffer.is_empty() {
+            let bs = self.buffer.peak_exact(self.buffer.len());
+            self.buffer.clear();
+
+            self.part_number += 1;
+            self.submit_part(&upload_id, self.part_number, bs);
+        }
+
+        // Wait for all parts to finish.
+        while !self.futures.is_empty() {
+            self.wait_part(&upload_id).await?;
+        }
+
+        // Parts could be finished out of order.
+        self.parts.sort_by_key(|v| v.part_number);
+
+        let resp = self
+            .core
+            .cos_complete_multipart_upload(&self.path, &upload_id, &self.parts)
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
     }
 }
@@ -0,0 +1,454 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use async_trait::async_trait;
+use http::header;
+use http::Request;
+use http::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::error::parse_error;
+use crate::raw::adapters::kv;
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_CLOUDFLARE_ENDPOINT: &str = "https://api.cloudflare.com/client/v4";
+/// The max number of keys returned by one scan query.
+const DEFAULT_SCAN_LIMIT: usize = 1000;
+
+/// [Cloudflare D1](https://developers.cloudflare.com/d1/) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct D1Builder {
+    /// The api token used for authentication.
+    token: Option<String>,
+    /// The account id of the database.
+    account_id: Option<String>,
+    /// The id of the D1 database.
+    database_id: Option<String>,
+    /// The table to store values.
+    table: Option<String>,
+    /// The endpoint of cloudflare api.
+    endpoint: Option<String>,
+    /// Root within this backend.
+    root: Option<String>,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for D1Builder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+
+        if self.token.is_some() {
+            ds.field("token", &"<redacted>");
+        }
+        ds.field("account_id", &self.account_id);
+        ds.field("database_id", &self.database_id);
+        ds.field("table", &self.table);
+        ds.field("endpoint", &self.endpoint);
+        ds.field("root", &self.root);
+        ds.finish()
+    }
+}
+
+impl D1Builder {
+    /// Set the api token used for authentication.
+    ///
+    /// The token must have the `D1 Edit` permission.
+    pub fn token(&mut self, token: &str) -> &mut Self {
+        if !token.is_empty() {
+            self.token = Some(token.to_string())
+        }
+        self
+    }
+
+    /// Set the account id of the database.
+    pub fn account_id(&mut self, account_id: &str) -> &mut Self {
+        if !account_id.is_empty() {
+            self.account_id = Some(account_id.to_string())
+        }
+        self
+    }
+
+    /// Set the id of the D1 database.
+    pub fn database_id(&mut self, database_id: &str) -> &mut Self {
+        if !database_id.is_empty() {
+            self.database_id = Some(database_id.to_string())
+        }
+        self
+    }
+
+    /// Set the table to store values.
+    ///
+    /// The table must have a `key` column of text as primary key and a
+    /// `value` column of blob.
+    pub fn table(&mut self, table: &str) -> &mut Self {
+        if !table.is_empty() {
+            self.table = Some(table.to_string())
+        }
+        self
+    }
+
+    /// Set the endpoint of cloudflare api.
+    ///
+    /// default: "https://api.cloudflare.com/client/v4"
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+        self
+    }
+
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for D1Builder {
+    const SCHEME: Scheme = Scheme::D1;
+    type Accessor = D1Backend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = D1Builder::default();
+
+        map.get("token").map(|v| builder.token(v));
+        map.get("account_id").map(|v| builder.account_id(v));
+        map.get("database_id").map(|v| builder.database_id(v));
+        map.get("table").map(|v| builder.table(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("root").map(|v| builder.root(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let token = self.token.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "token is required but not set")
+                .with_context("service", Scheme::D1)
+        })?;
+        let account_id = self.account_id.take().ok_or_else(|| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "account_id is required but not set",
+            )
+            .with_context("service", Scheme::D1)
+        })?;
+        let database_id = self.database_id.take().ok_or_else(|| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "database_id is required but not set",
+            )
+            .with_context("service", Scheme::D1)
+        })?;
+        let table = self.table.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "table is required but not set")
+                .with_context("service", Scheme::D1)
+        })?;
+
+        let authorization = format_authorization_by_bearer(&token)?;
+
+        let endpoint = self
+            .endpoint
+            .take()
+            .unwrap_or_else(|| DEFAULT_CLOUDFLARE_ENDPOINT.to_string());
+        let url = format!("{endpoint}/accounts/{account_id}/d1/database/{database_id}/query");
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::D1)
+            })?
+        };
+
+        let root = normalize_root(self.root.take().unwrap_or_else(|| "/".to_string()).as_str());
+
+        Ok(D1Backend::new(Adapter {
+            authorization,
+            database_id,
+            table: quote_ident(&table),
+            url,
+            client,
+        })
+        .with_root(&root))
+    }
+}
+
+/// Backend for cloudflare d1 services.
+pub type D1Backend = kv::Backend<Adapter>;
+
+#[derive(Clone)]
+pub struct Adapter {
+    authorization: String,
+    database_id: String,
+    /// The quoted table name.
+    table: String,
+    url: String,
+    client: HttpClient,
+}
+
+// implement `Debug` manually, or token may be leaked.
+impl Debug for Adapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Adapter");
+        ds.field("database_id", &self.database_id);
+        ds.field("table", &self.table);
+        ds.finish()
+    }
+}
+
+impl Adapter {
+    /// Execute the sql with params and return the rows of result.
+    async fn query(&self, sql: &str, params: Vec<Value>) -> Result<Vec<HashMap<String, Value>>> {
+        let body =
+            serde_json::to_vec(&QueryRequest { sql, params }).map_err(new_json_serialize_error)?;
+
+        let req = Request::post(&self.url)
+            .header(header::AUTHORIZATION, &self.authorization)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(AsyncBody::Bytes(body.into()))
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: QueryResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+        if !output.success {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                &String::from_utf8_lossy(&bs),
+            ));
+        }
+
+        Ok(output
+            .result
+            .into_iter()
+            .next()
+            .map(|v| v.results)
+            .unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl kv::Adapter for Adapter {
+    fn metadata(&self) -> kv::Metadata {
+        kv::Metadata::new(
+            Scheme::D1,
+            &self.database_id,
+            Capability {
+                read: true,
+                write: true,
+                list: true,
+
+                ..Default::default()
+            },
+        )
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let sql = format!("SELECT value FROM {} WHERE key = ?1", self.table);
+
+        let rows = self.query(&sql, vec![Value::from(path)]).await?;
+        match rows.into_iter().next() {
+            None => Ok(None),
+            Some(mut row) => parse_blob(row.remove("value").unwrap_or_default()).map(Some),
+        }
+    }
+
+    async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
+        let sql = format!(
+            "INSERT INTO {} (key, value) VALUES (?1, ?2) \
+             ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+            self.table
+        );
+
+        // Blobs are passed as arrays of bytes in json.
+        self.query(&sql, vec![Value::from(path), Value::from(value)])
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let sql = format!("DELETE FROM {} WHERE key = ?1", self.table);
+
+        self.query(&sql, vec![Value::from(path)]).await?;
+        Ok(())
+    }
+
+    /// Scan keys by prefix page by page, `LIKE` is case-insensitive in
+    /// sqlite, so we compare the prefix directly.
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        let sql = format!(
+            "SELECT key FROM {} \
+             WHERE substr(key, 1, length(?1)) = ?1 AND key > ?2 \
+             ORDER BY key LIMIT {DEFAULT_SCAN_LIMIT}",
+            self.table
+        );
+
+        let mut keys: Vec<String> = Vec::new();
+        loop {
+            let start_after = keys.last().cloned().unwrap_or_default();
+            let rows = self
+                .query(&sql, vec![Value::from(path), Value::from(start_after)])
+                .await?;
+            let size = rows.len();
+
+            for mut row in rows {
+                match row.remove("key") {
+                    Some(Value::String(key)) => keys.push(key),
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::Unexpected,
+                            "key returned by d1 is not a string",
+                        ))
+                    }
+                }
+            }
+
+            if size < DEFAULT_SCAN_LIMIT {
+                break;
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+/// Ref: <https://developers.cloudflare.com/api/operations/cloudflare-d1-query-database>
+#[derive(Debug, Serialize)]
+struct QueryRequest<'a> {
+    sql: &'a str,
+    params: Vec<Value>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct QueryResponse {
+    result: Vec<QueryResult>,
+    success: bool,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct QueryResult {
+    results: Vec<HashMap<String, Value>>,
+}
+
+/// Parse the blob returned by d1, which is an array of bytes.
+///
+/// Values written by others could be stored as text, we will return its
+/// bytes directly.
+fn parse_blob(v: Value) -> Result<Vec<u8>> {
+    match v {
+        Value::Null => Ok(Vec::new()),
+        Value::String(s) => Ok(s.into_bytes()),
+        Value::Array(vs) => vs
+            .into_iter()
+            .map(|v| {
+                v.as_u64()
+                    .and_then(|v| u8::try_from(v).ok())
+                    .ok_or_else(|| {
+                        Error::new(ErrorKind::Unexpected, "blob returned by d1 is invalid")
+                    })
+            })
+            .collect(),
+        _ => Err(Error::new(
+            ErrorKind::Unexpected,
+            "value returned by d1 is not a blob",
+        )),
+    }
+}
+
+/// Quote the identifier so that it can be used in sql safely.
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_query_response() {
+        let content = r#"{
+            "errors": [],
+            "messages": [],
+            "result": [
+                {
+                    "meta": {
+                        "changed_db": false,
+                        "changes": 0,
+                        "duration": 0.1,
+                        "last_row_id": 0,
+                        "rows_read": 1,
+                        "rows_written": 0,
+                        "size_after": 16384
+                    },
+                    "results": [
+                        {"key": "path/to/file", "value": [104, 105]}
+                    ],
+                    "success": true
+                }
+            ],
+            "success": true
+        }"#;
+
+        let mut output: QueryResponse =
+            serde_json::from_str(content).expect("response must be valid");
+        assert!(output.success);
+        let mut row = output.result.remove(0).results.remove(0);
+        assert_eq!(row["key"], "path/to/file");
+        assert_eq!(parse_blob(row.remove("value").unwrap()).unwrap(), b"hi");
+    }
+
+    #[test]
+    fn test_parse_blob() {
+        assert_eq!(parse_blob(Value::Null).unwrap(), b"");
+        assert_eq!(parse_blob(Value::from("hello")).unwrap(), b"hello");
+        assert!(parse_blob(Value::from(vec![256])).is_err());
+        assert!(parse_blob(Value::from(1)).is_err());
+    }
+}
@@ -140,4 +140,24 @@ mod tests {
         let b2 = DashmapBuilder::default().build().unwrap();
         assert_ne!(b1.info().name(), b2.info().name())
     }
+
+    #[test]
+    fn test_scan_with_prefix() {
+        let adapter = Adapter {
+            inner: DashMap::default(),
+        };
+        for path in ["dir/a", "dir/b", "dir_c", "other/d"] {
+            typed_kv::Adapter::blocking_set(&adapter, path, typed_kv::Value::new_dir()).unwrap();
+        }
+
+        let mut keys = typed_kv::Adapter::blocking_scan(&adapter, "dir/").unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["dir/a", "dir/b"]);
+        assert_eq!(
+            typed_kv::Adapter::blocking_scan(&adapter, "")
+                .unwrap()
+                .len(),
+            4
+        );
+    }
 }
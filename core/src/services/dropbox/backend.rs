@@ -21,8 +21,11 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use http::StatusCode;
 
+use super::core::parse_dropbox_metadata;
 use super::core::DropboxCore;
+use super::core::DropboxMetadataResponse;
 use super::error::parse_error;
+use super::pager::DropboxPager;
 use super::writer::DropboxWriter;
 use crate::raw::*;
 use crate::*;
@@ -38,7 +41,7 @@ impl Accessor for DropboxBackend {
     type BlockingReader = ();
     type Writer = DropboxWriter;
     type BlockingWriter = ();
-    type Pager = ();
+    type Pager = DropboxPager;
     type BlockingPager = ();
     type Appender = ();
 
@@ -47,19 +50,57 @@ impl Accessor for DropboxBackend {
         ma.set_scheme(Scheme::Dropbox)
             .set_root(&self.core.root)
             .set_capability(Capability {
+                stat: true,
+
                 read: true,
+                read_can_next: true,
+                read_with_range: true,
+
                 write: true,
+                write_without_content_length: true,
+
+                create_dir: true,
                 delete: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_delimiter_slash: true,
+
                 ..Default::default()
             });
         ma
     }
 
-    async fn read(&self, path: &str, _args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self.core.dropbox_get(path).await?;
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        // Root always exists.
+        if self.core.build_path(path).is_empty() {
+            return Ok(RpCreateDir::default());
+        }
+
+        let resp = self.core.dropbox_create_folder(path).await?;
         let status = resp.status();
+
         match status {
             StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCreateDir::default())
+            }
+            _ => {
+                let err = parse_error(resp).await?;
+                // Creating an existing dir should succeed.
+                match err.kind() {
+                    ErrorKind::AlreadyExists => Ok(RpCreateDir::default()),
+                    _ => Err(err),
+                }
+            }
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let resp = self.core.dropbox_get(path, args.range()).await?;
+        let status = resp.status();
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
                 let meta = parse_into_metadata(path, resp.headers())?;
                 Ok((RpRead::with_metadata(meta), resp.into_body()))
             }
@@ -68,26 +109,66 @@ impl Accessor for DropboxBackend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        if args.content_length().is_none() {
-            return Err(Error::new(
-                ErrorKind::Unsupported,
-                "write without content length is not supported",
-            ));
-        }
         Ok((
             RpWrite::default(),
             DropboxWriter::new(self.core.clone(), args, String::from(path)),
         ))
     }
 
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Dropbox doesn't support getting metadata of the root.
+        if self.core.build_path(path).is_empty() {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let resp = self.core.dropbox_get_metadata(path).await?;
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let output: DropboxMetadataResponse =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+                let meta = parse_dropbox_metadata(&output)?;
+
+                if path.ends_with('/') && !meta.mode().is_dir() {
+                    return Err(Error::new(
+                        ErrorKind::NotFound,
+                        "the path is a file but a dir is expected",
+                    ));
+                }
+
+                Ok(RpStat::new(meta))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
         let resp = self.core.dropbox_delete(path).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::OK => Ok(RpDelete::default()),
-            _ => Err(parse_error(resp).await?),
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => {
+                let err = parse_error(resp).await?;
+                // Deleting a not exist path should succeed.
+                match err.kind() {
+                    ErrorKind::NotFound => Ok(RpDelete::default()),
+                    _ => Err(err),
+                }
+            }
         }
     }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((
+            RpList::default(),
+            DropboxPager::new(self.core.clone(), path, args.limit()),
+        ))
+    }
 }
@@ -20,8 +20,11 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+use tokio::sync::Mutex;
+
 use super::backend::DropboxBackend;
 use super::core::DropboxCore;
+use super::core::DropboxSigner;
 use crate::raw::*;
 use crate::*;
 
@@ -31,21 +34,34 @@ use crate::*;
 ///
 /// This service can be used to:
 ///
+/// - [x] stat
 /// - [x] read
 /// - [x] write
+/// - [x] create_dir
 /// - [x] delete
 /// - [ ] copy
-/// - [ ] create
-/// - [ ] list
 /// - [ ] rename
+/// - [x] list
+/// - [ ] ~~scan~~
+/// - [ ] ~~presign~~
+/// - [ ] blocking
 ///
 /// # Notes
 ///
+/// Files larger than 150 MiB or written without content length will be
+/// uploaded via [upload session](https://www.dropbox.com/developers/documentation/http/documentation#files-upload_session-start).
 ///
 /// # Configuration
 ///
-/// - `access_token`: set the access_token for google drive api
 /// - `root`: Set the work directory for backend
+/// - `access_token`: Set the access_token for dropbox api
+/// - `refresh_token`: Set the refresh_token for dropbox api
+/// - `client_id`: Set the client_id of the dropbox app
+/// - `client_secret`: Set the client_secret of the dropbox app
+///
+/// Either `access_token` or `refresh_token` with `client_id` and
+/// `client_secret` must be set. The access token will be refreshed
+/// automatically if `refresh_token` is set.
 ///
 /// You can refer to [`DropboxBuilder`]'s docs for more information
 ///
@@ -55,7 +71,6 @@ use crate::*;
 ///
 /// ```
 /// use anyhow::Result;
-/// use opendal::raw::OpWrite;
 /// use opendal::services::Dropbox;
 /// use opendal::Operator;
 ///
@@ -63,7 +78,11 @@ use crate::*;
 /// async fn main() -> Result<()> {
 ///     // create backend builder
 ///     let mut builder = Dropbox::default();
-///     builder.access_token("<token>");
+///     builder
+///         .root("/opendal")
+///         .refresh_token("<refresh_token>")
+///         .client_id("<client_id>")
+///         .client_secret("<client_secret>");
 ///
 ///     let op: Operator = Operator::new(builder)?.finish();
 ///     Ok(())
@@ -72,27 +91,80 @@ use crate::*;
 
 #[derive(Default)]
 pub struct DropboxBuilder {
-    access_token: Option<String>,
     root: Option<String>,
+
+    access_token: Option<String>,
+
+    refresh_token: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+
     http_client: Option<HttpClient>,
 }
 
 impl Debug for DropboxBuilder {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Builder").finish()
+        f.debug_struct("Builder")
+            .field("root", &self.root)
+            .field("client_id", &self.client_id)
+            .finish()
     }
 }
 
 impl DropboxBuilder {
-    /// default: no access token, which leads to failure
+    /// Set the root directory for dropbox.
+    ///
+    /// Default to `/` if not set.
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string());
+        }
+        self
+    }
+
+    /// Access token is used for temporary access to the Dropbox API.
+    ///
+    /// You can get the access token from [Dropbox App Console](https://www.dropbox.com/developers/apps)
+    ///
+    /// NOTE: this token will be expired in 4 hours. If you are trying to
+    /// use dropbox for a long time, please set `refresh_token` instead.
     pub fn access_token(&mut self, access_token: &str) -> &mut Self {
-        self.access_token = Some(access_token.to_string());
+        if !access_token.is_empty() {
+            self.access_token = Some(access_token.to_string());
+        }
         self
     }
 
-    /// default: no root path, which leads to failure
-    pub fn root(&mut self, root: &str) -> &mut Self {
-        self.root = Some(root.to_string());
+    /// Refresh token is used for long term access to the Dropbox API.
+    ///
+    /// You can get the refresh token via OAuth 2.0 Flow of Dropbox.
+    ///
+    /// OpenDAL will use this refresh token to get a new access token when
+    /// the old one is expired.
+    pub fn refresh_token(&mut self, refresh_token: &str) -> &mut Self {
+        if !refresh_token.is_empty() {
+            self.refresh_token = Some(refresh_token.to_string());
+        }
+        self
+    }
+
+    /// Set the client id for Dropbox.
+    ///
+    /// This is required for OAuth 2.0 Flow to refresh the access token.
+    pub fn client_id(&mut self, client_id: &str) -> &mut Self {
+        if !client_id.is_empty() {
+            self.client_id = Some(client_id.to_string());
+        }
+        self
+    }
+
+    /// Set the client secret for Dropbox.
+    ///
+    /// This is required for OAuth 2.0 Flow with refresh the access token.
+    pub fn client_secret(&mut self, client_secret: &str) -> &mut Self {
+        if !client_secret.is_empty() {
+            self.client_secret = Some(client_secret.to_string());
+        }
         self
     }
 
@@ -114,7 +186,11 @@ impl Builder for DropboxBuilder {
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = Self::default();
+        map.get("root").map(|v| builder.root(v));
         map.get("access_token").map(|v| builder.access_token(v));
+        map.get("refresh_token").map(|v| builder.refresh_token(v));
+        map.get("client_id").map(|v| builder.client_id(v));
+        map.get("client_secret").map(|v| builder.client_secret(v));
         builder
     }
 
@@ -128,21 +204,56 @@ impl Builder for DropboxBuilder {
                     .with_context("service", Scheme::Dropbox)
             })?
         };
-        let token = match self.access_token.clone() {
-            Some(access_token) => access_token,
-            None => {
+
+        let signer = match (self.access_token.take(), self.refresh_token.take()) {
+            (Some(access_token), None) => DropboxSigner {
+                access_token,
+                ..Default::default()
+            },
+            (None, Some(refresh_token)) => {
+                let client_id = self.client_id.take().ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "client_id must be set when refresh_token is set",
+                    )
+                    .with_context("service", Scheme::Dropbox)
+                })?;
+                let client_secret = self.client_secret.take().ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "client_secret must be set when refresh_token is set",
+                    )
+                    .with_context("service", Scheme::Dropbox)
+                })?;
+
+                DropboxSigner {
+                    refresh_token,
+                    client_id,
+                    client_secret,
+                    ..Default::default()
+                }
+            }
+            (Some(_), Some(_)) => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "access_token and refresh_token can not be set at the same time",
+                )
+                .with_context("service", Scheme::Dropbox))
+            }
+            (None, None) => {
                 return Err(Error::new(
                     ErrorKind::ConfigInvalid,
-                    "access_token is required",
-                ))
+                    "access_token or refresh_token must be set",
+                )
+                .with_context("service", Scheme::Dropbox))
             }
         };
 
         Ok(DropboxBackend {
             core: Arc::new(DropboxCore {
                 root,
-                token,
                 client,
+                signer: Arc::new(Mutex::new(signer)),
             }),
         })
     }
@@ -18,49 +18,122 @@
 use std::default::Default;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::sync::Arc;
 
 use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
 use http::header;
-use http::request::Builder;
 use http::Request;
 use http::Response;
+use http::StatusCode;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
 use serde::Deserialize;
 use serde::Serialize;
+use tokio::sync::Mutex;
 
-use crate::raw::build_rooted_abs_path;
-use crate::raw::new_json_serialize_error;
-use crate::raw::new_request_build_error;
-use crate::raw::AsyncBody;
-use crate::raw::HttpClient;
-use crate::raw::IncomingAsyncBody;
-use crate::types::Result;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+const DROPBOX_API_ENDPOINT: &str = "https://api.dropboxapi.com";
+const DROPBOX_CONTENT_ENDPOINT: &str = "https://content.dropboxapi.com";
 
 pub struct DropboxCore {
-    pub token: String,
-    pub client: HttpClient,
     pub root: String,
+    pub client: HttpClient,
+    pub signer: Arc<Mutex<DropboxSigner>>,
 }
 
 impl Debug for DropboxCore {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut de = f.debug_struct("DropboxCore");
+        de.field("root", &self.root);
         de.finish()
     }
 }
 
 impl DropboxCore {
-    pub async fn dropbox_get(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let url: String = "https://content.dropboxapi.com/2/files/download".to_string();
+    /// Build the path that dropbox api accepts.
+    ///
+    /// Dropbox uses `""` to represent the root and doesn't accept paths
+    /// ending with `/`.
+    pub fn build_path(&self, path: &str) -> String {
+        let path = build_rooted_abs_path(&self.root, path);
+        path.trim_end_matches('/').to_string()
+    }
+
+    /// Sign the request with the access token.
+    ///
+    /// The access token will be refreshed via the refresh token if it's
+    /// expired.
+    pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        let mut signer = self.signer.lock().await;
+
+        if !signer.refresh_token.is_empty() && signer.expires_in <= Utc::now() {
+            let body = format!(
+                "grant_type=refresh_token&refresh_token={}&client_id={}&client_secret={}",
+                utf8_percent_encode(&signer.refresh_token, NON_ALPHANUMERIC),
+                utf8_percent_encode(&signer.client_id, NON_ALPHANUMERIC),
+                utf8_percent_encode(&signer.client_secret, NON_ALPHANUMERIC),
+            );
+
+            let request = Request::post(format!("{DROPBOX_API_ENDPOINT}/oauth2/token"))
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .header(header::CONTENT_LENGTH, body.len())
+                .body(AsyncBody::Bytes(Bytes::from(body)))
+                .map_err(new_request_build_error)?;
+
+            let resp = self.client.send(request).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let token: DropboxTokenResponse =
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+            signer.access_token = token.access_token;
+            // Refresh the token a bit earlier to avoid using an expired token.
+            signer.expires_in =
+                Utc::now() + Duration::seconds(token.expires_in as i64) - Duration::seconds(120);
+        }
+
+        let value = format_authorization_by_bearer(&signer.access_token)?
+            .parse()
+            .map_err(|_| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "access token contains invalid header value",
+                )
+            })?;
+        req.headers_mut().insert(header::AUTHORIZATION, value);
+        Ok(())
+    }
+
+    pub async fn dropbox_get(
+        &self,
+        path: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{DROPBOX_CONTENT_ENDPOINT}/2/files/download");
         let download_args = DropboxDownloadArgs {
-            path: build_rooted_abs_path(&self.root, path),
+            path: self.build_path(path),
         };
-        let request_payload =
-            serde_json::to_string(&download_args).map_err(new_json_serialize_error)?;
-        let request = self
-            .build_auth_header(Request::post(&url))
-            .header("Dropbox-API-Arg", request_payload)
+
+        let mut req =
+            Request::post(&url).header("Dropbox-API-Arg", build_dropbox_api_arg(&download_args)?);
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+
+        let mut request = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
+
+        self.sign(&mut request).await?;
         self.client.send(request).await
     }
 
@@ -68,63 +141,243 @@ impl DropboxCore {
         &self,
         path: &str,
         size: Option<usize>,
-        content_type: Option<&str>,
         body: AsyncBody,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let url = "https://content.dropboxapi.com/2/files/upload".to_string();
-        let args = DropboxUploadArgs {
-            path: build_rooted_abs_path(&self.root, path),
+        let url = format!("{DROPBOX_CONTENT_ENDPOINT}/2/files/upload");
+        let args = DropboxCommitInfo {
+            path: self.build_path(path),
             ..Default::default()
         };
-        let mut request_builder = Request::post(&url);
-        if let Some(size) = size {
-            request_builder = request_builder.header(header::CONTENT_LENGTH, size);
-        }
-        if let Some(mime) = content_type {
-            request_builder = request_builder.header(header::CONTENT_TYPE, mime);
-        }
-        let request = self
-            .build_auth_header(request_builder)
-            .header(
-                "Dropbox-API-Arg",
-                serde_json::to_string(&args).map_err(new_json_serialize_error)?,
-            )
-            .body(body)
-            .map_err(new_request_build_error)?;
 
-        self.client.send(request).await
+        self.dropbox_content_request(&url, &args, size, body).await
+    }
+
+    pub async fn dropbox_upload_session_start(&self) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{DROPBOX_CONTENT_ENDPOINT}/2/files/upload_session/start");
+        let args = DropboxUploadSessionStartArgs { close: false };
+
+        self.dropbox_content_request(&url, &args, Some(0), AsyncBody::Empty)
+            .await
+    }
+
+    pub async fn dropbox_upload_session_append(
+        &self,
+        session_id: &str,
+        offset: u64,
+        size: usize,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{DROPBOX_CONTENT_ENDPOINT}/2/files/upload_session/append_v2");
+        let args = DropboxUploadSessionAppendArgs {
+            cursor: DropboxUploadSessionCursor {
+                session_id: session_id.to_string(),
+                offset,
+            },
+            close: false,
+        };
+
+        self.dropbox_content_request(&url, &args, Some(size), body)
+            .await
+    }
+
+    pub async fn dropbox_upload_session_finish(
+        &self,
+        session_id: &str,
+        offset: u64,
+        path: &str,
+        size: usize,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{DROPBOX_CONTENT_ENDPOINT}/2/files/upload_session/finish");
+        let args = DropboxUploadSessionFinishArgs {
+            cursor: DropboxUploadSessionCursor {
+                session_id: session_id.to_string(),
+                offset,
+            },
+            commit: DropboxCommitInfo {
+                path: self.build_path(path),
+                ..Default::default()
+            },
+        };
+
+        self.dropbox_content_request(&url, &args, Some(size), body)
+            .await
     }
 
     pub async fn dropbox_delete(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let url = "https://api.dropboxapi.com/2/files/delete_v2".to_string();
+        let url = format!("{DROPBOX_API_ENDPOINT}/2/files/delete_v2");
         let args = DropboxDeleteArgs {
-            path: build_rooted_abs_path(&self.root, path),
+            path: self.build_path(path),
+        };
+
+        self.dropbox_rpc_request(&url, &args).await
+    }
+
+    pub async fn dropbox_get_metadata(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{DROPBOX_API_ENDPOINT}/2/files/get_metadata");
+        let args = DropboxMetadataArgs {
+            path: self.build_path(path),
+        };
+
+        self.dropbox_rpc_request(&url, &args).await
+    }
+
+    pub async fn dropbox_create_folder(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{DROPBOX_API_ENDPOINT}/2/files/create_folder_v2");
+        let args = DropboxCreateFolderArgs {
+            path: self.build_path(path),
+            autorename: false,
         };
 
-        let bs = Bytes::from(serde_json::to_string(&args).map_err(new_json_serialize_error)?);
+        self.dropbox_rpc_request(&url, &args).await
+    }
+
+    pub async fn dropbox_list_folder(
+        &self,
+        path: &str,
+        limit: Option<usize>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{DROPBOX_API_ENDPOINT}/2/files/list_folder");
+        let args = DropboxListFolderArgs {
+            path: self.build_path(path),
+            recursive: false,
+            limit,
+        };
 
-        let request = self
-            .build_auth_header(Request::post(&url))
+        self.dropbox_rpc_request(&url, &args).await
+    }
+
+    pub async fn dropbox_list_folder_continue(
+        &self,
+        cursor: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{DROPBOX_API_ENDPOINT}/2/files/list_folder/continue");
+        let args = DropboxListFolderContinueArgs {
+            cursor: cursor.to_string(),
+        };
+
+        self.dropbox_rpc_request(&url, &args).await
+    }
+
+    /// Send a rpc request which carries its arguments in the json body.
+    async fn dropbox_rpc_request<T: Serialize>(
+        &self,
+        url: &str,
+        args: &T,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let bs = Bytes::from(serde_json::to_vec(args).map_err(new_json_serialize_error)?);
+
+        let mut request = Request::post(url)
             .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_LENGTH, bs.len())
             .body(AsyncBody::Bytes(bs))
             .map_err(new_request_build_error)?;
+
+        self.sign(&mut request).await?;
         self.client.send(request).await
     }
 
-    fn build_auth_header(&self, mut req: Builder) -> Builder {
-        let auth_header_content = format!("Bearer {}", self.token);
-        req = req.header(header::AUTHORIZATION, auth_header_content);
-        req
+    /// Send a content upload request which carries its arguments in the
+    /// `Dropbox-API-Arg` header.
+    async fn dropbox_content_request<T: Serialize>(
+        &self,
+        url: &str,
+        args: &T,
+        size: Option<usize>,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::post(url)
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .header("Dropbox-API-Arg", build_dropbox_api_arg(args)?);
+        if let Some(size) = size {
+            req = req.header(header::CONTENT_LENGTH, size);
+        }
+
+        let mut request = req.body(body).map_err(new_request_build_error)?;
+
+        self.sign(&mut request).await?;
+        self.client.send(request).await
+    }
+}
+
+/// DropboxSigner holds the access token and refreshes it if needed.
+pub struct DropboxSigner {
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: String,
+
+    pub access_token: String,
+    pub expires_in: DateTime<Utc>,
+}
+
+impl Default for DropboxSigner {
+    fn default() -> Self {
+        DropboxSigner {
+            client_id: String::new(),
+            client_secret: String::new(),
+            refresh_token: String::new(),
+
+            access_token: String::new(),
+            expires_in: DateTime::<Utc>::MIN_UTC,
+        }
     }
 }
 
+/// Build the value of `Dropbox-API-Arg` header.
+///
+/// The header value must be ascii, so all non-ascii chars (and `0x7F`) must
+/// be escaped as `\uXXXX`.
+///
+/// Ref: <https://www.dropbox.com/developers/reference/json-encoding>
+fn build_dropbox_api_arg<T: Serialize>(args: &T) -> Result<String> {
+    let s = serde_json::to_string(args).map_err(new_json_serialize_error)?;
+
+    let mut v = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii() && c != '\x7f' {
+            v.push(c);
+        } else {
+            let mut buf = [0; 2];
+            for u in c.encode_utf16(&mut buf) {
+                v.push_str(&format!("\\u{u:04x}"));
+            }
+        }
+    }
+    Ok(v)
+}
+
+/// Parse dropbox metadata into opendal's metadata.
+pub fn parse_dropbox_metadata(meta: &DropboxMetadataResponse) -> Result<Metadata> {
+    let mut md = if meta.tag == "folder" {
+        Metadata::new(EntryMode::DIR)
+    } else {
+        Metadata::new(EntryMode::FILE)
+    };
+
+    if let Some(size) = meta.size {
+        md.set_content_length(size);
+    }
+    if !meta.server_modified.is_empty() {
+        md.set_last_modified(parse_datetime_from_rfc3339(&meta.server_modified)?);
+    }
+
+    Ok(md)
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct DropboxTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 struct DropboxDownloadArgs {
     path: String,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-struct DropboxUploadArgs {
+struct DropboxCommitInfo {
     path: String,
     mode: String,
     mute: bool,
@@ -132,14 +385,9 @@ struct DropboxUploadArgs {
     strict_conflict: bool,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-struct DropboxDeleteArgs {
-    path: String,
-}
-
-impl Default for DropboxUploadArgs {
+impl Default for DropboxCommitInfo {
     fn default() -> Self {
-        DropboxUploadArgs {
+        DropboxCommitInfo {
             mode: "overwrite".to_string(),
             path: "".to_string(),
             mute: true,
@@ -148,3 +396,138 @@ impl Default for DropboxUploadArgs {
         }
     }
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxUploadSessionStartArgs {
+    close: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxUploadSessionCursor {
+    session_id: String,
+    offset: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxUploadSessionAppendArgs {
+    cursor: DropboxUploadSessionCursor,
+    close: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxUploadSessionFinishArgs {
+    cursor: DropboxUploadSessionCursor,
+    commit: DropboxCommitInfo,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct DropboxUploadSessionStartResponse {
+    pub session_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxDeleteArgs {
+    path: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxMetadataArgs {
+    path: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxCreateFolderArgs {
+    path: String,
+    autorename: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxListFolderArgs {
+    path: String,
+    recursive: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxListFolderContinueArgs {
+    cursor: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct DropboxMetadataResponse {
+    #[serde(rename(deserialize = ".tag"))]
+    pub tag: String,
+    pub name: String,
+    pub path_display: String,
+    pub server_modified: String,
+    pub size: Option<u64>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct DropboxListFolderResponse {
+    pub entries: Vec<DropboxMetadataResponse>,
+    pub cursor: String,
+    pub has_more: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_dropbox_api_arg() {
+        let args = DropboxDownloadArgs {
+            path: "/测试/a\x7fb.txt".to_string(),
+        };
+
+        assert_eq!(
+            build_dropbox_api_arg(&args).expect("must succeed"),
+            r#"{"path":"/\u6d4b\u8bd5/a\u007fb.txt"}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_list_folder_response() {
+        let content = r#"{
+            "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+            "entries": [
+                {
+                    ".tag": "file",
+                    "client_modified": "2015-05-12T15:50:38Z",
+                    "content_hash": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                    "id": "id:a4ayc_80_OEAAAAAAAAAXw",
+                    "name": "Prime_Numbers.txt",
+                    "path_display": "/Homework/math/Prime_Numbers.txt",
+                    "path_lower": "/homework/math/prime_numbers.txt",
+                    "rev": "a1c10ce0dd78",
+                    "server_modified": "2015-05-12T15:50:38Z",
+                    "size": 7212
+                },
+                {
+                    ".tag": "folder",
+                    "id": "id:a4ayc_80_OEAAAAAAAAAXz",
+                    "name": "math",
+                    "path_display": "/Homework/math",
+                    "path_lower": "/homework/math"
+                }
+            ],
+            "has_more": false
+        }"#;
+
+        let output: DropboxListFolderResponse =
+            serde_json::from_str(content).expect("response must be valid");
+        assert!(!output.has_more);
+        assert_eq!(output.entries.len(), 2);
+
+        let file = parse_dropbox_metadata(&output.entries[0]).expect("must succeed");
+        assert_eq!(file.mode(), EntryMode::FILE);
+        assert_eq!(file.content_length(), 7212);
+
+        let dir = parse_dropbox_metadata(&output.entries[1]).expect("must succeed");
+        assert_eq!(dir.mode(), EntryMode::DIR);
+    }
+}
@@ -43,37 +43,69 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
     let bs = body.bytes().await?;
 
+    let (message, summary) = match serde_json::from_slice::<DropboxError>(&bs) {
+        Ok(dropbox_error) => (
+            dropbox_error.error_summary.clone(),
+            dropbox_error.error_summary,
+        ),
+        Err(_) => (String::from_utf8_lossy(&bs).into_owned(), String::new()),
+    };
+
     let (kind, retryable) = match parts.status {
         StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
-        StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        // Dropbox returns 409 for endpoint-specific errors, the detail is
+        // carried by `error_summary` like `path/not_found/...`.
+        StatusCode::CONFLICT => (parse_error_summary(&summary), false),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
         | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
         _ => (ErrorKind::Unexpected, false),
     };
-    let dropbox_error =
-        serde_json::from_slice::<DropboxError>(&bs).map_err(new_json_deserialize_error);
-    match dropbox_error {
-        Ok(dropbox_error) => {
-            let mut err = Error::new(kind, dropbox_error.error_summary.as_ref())
-                .with_context("response", format!("{parts:?}"));
 
-            if retryable {
-                err = err.set_temporary();
-            }
+    let mut err = Error::new(kind, &message).with_context("response", format!("{parts:?}"));
 
-            Ok(err)
-        }
-        Err(_err) => {
-            let mut err = Error::new(kind, &String::from_utf8_lossy(&bs))
-                .with_context("response", format!("{parts:?}"));
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+/// Parse the `error_summary` of dropbox conflict error into [`ErrorKind`].
+fn parse_error_summary(summary: &str) -> ErrorKind {
+    if summary.contains("not_found") {
+        ErrorKind::NotFound
+    } else if summary.contains("conflict") {
+        ErrorKind::AlreadyExists
+    } else if summary.contains("not_folder") {
+        ErrorKind::NotADirectory
+    } else if summary.contains("no_write_permission") {
+        ErrorKind::PermissionDenied
+    } else {
+        ErrorKind::Unexpected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            if retryable {
-                err = err.set_temporary();
-            }
+    #[test]
+    fn test_parse_error_summary() {
+        let cases = vec![
+            ("path/not_found/..", ErrorKind::NotFound),
+            ("path_lookup/not_found/...", ErrorKind::NotFound),
+            ("path/conflict/folder/...", ErrorKind::AlreadyExists),
+            ("path/not_folder/..", ErrorKind::NotADirectory),
+            ("path/no_write_permission/..", ErrorKind::PermissionDenied),
+            ("too_many_write_operations/..", ErrorKind::Unexpected),
+        ];
 
-            Ok(err)
+        for (summary, expected) in cases {
+            assert_eq!(parse_error_summary(summary), expected, "{summary}");
         }
     }
 }
@@ -0,0 +1,105 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::core::parse_dropbox_metadata;
+use super::core::DropboxCore;
+use super::core::DropboxListFolderResponse;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct DropboxPager {
+    core: Arc<DropboxCore>,
+    path: String,
+    limit: Option<usize>,
+
+    cursor: Option<String>,
+    done: bool,
+}
+
+impl DropboxPager {
+    pub fn new(core: Arc<DropboxCore>, path: &str, limit: Option<usize>) -> Self {
+        DropboxPager {
+            core,
+            path: path.to_string(),
+            limit,
+
+            cursor: None,
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for DropboxPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let resp = match &self.cursor {
+            Some(cursor) => self.core.dropbox_list_folder_continue(cursor).await?,
+            None => {
+                self.core
+                    .dropbox_list_folder(&self.path, self.limit)
+                    .await?
+            }
+        };
+
+        if resp.status() != StatusCode::OK {
+            let err = parse_error(resp).await?;
+            // Listing a not exist dir should return empty.
+            if err.kind() == ErrorKind::NotFound {
+                self.done = true;
+                return Ok(None);
+            }
+            return Err(err);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: DropboxListFolderResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        if output.has_more {
+            self.cursor = Some(output.cursor);
+        } else {
+            self.done = true;
+        }
+
+        let parent = if self.path == "/" { "" } else { &self.path };
+
+        let mut entries = Vec::with_capacity(output.entries.len());
+        for item in output.entries {
+            let meta = parse_dropbox_metadata(&item)?;
+
+            let path = if meta.mode().is_dir() {
+                format!("{parent}{}/", item.name)
+            } else {
+                format!("{parent}{}", item.name)
+            };
+
+            entries.push(oio::Entry::new(&path, meta));
+        }
+
+        Ok(Some(entries))
+    }
+}
@@ -22,33 +22,47 @@ use bytes::Bytes;
 use http::StatusCode;
 
 use super::core::DropboxCore;
+use super::core::DropboxUploadSessionStartResponse;
 use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+/// Dropbox doesn't allow uploading files larger than 150 MiB in one request.
+const DROPBOX_UPLOAD_MAX_SIZE: usize = 150 * 1024 * 1024;
+/// The size of every chunk appended into the upload session.
+///
+/// Dropbox recommends chunks to be multiple of 4 MiB.
+const DROPBOX_UPLOAD_SESSION_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
 pub struct DropboxWriter {
     core: Arc<DropboxCore>,
     op: OpWrite,
     path: String,
+
+    session_id: Option<String>,
+    offset: u64,
+    buffer: oio::VectorCursor,
+    buffer_size: usize,
 }
 
 impl DropboxWriter {
     pub fn new(core: Arc<DropboxCore>, op: OpWrite, path: String) -> Self {
-        DropboxWriter { core, op, path }
+        DropboxWriter {
+            core,
+            op,
+            path,
+
+            session_id: None,
+            offset: 0,
+            buffer: oio::VectorCursor::new(),
+            buffer_size: DROPBOX_UPLOAD_SESSION_CHUNK_SIZE,
+        }
     }
-}
 
-#[async_trait]
-impl oio::Write for DropboxWriter {
-    async fn write(&mut self, bs: Bytes) -> Result<()> {
+    async fn write_oneshot(&self, bs: Bytes) -> Result<()> {
         let resp = self
             .core
-            .dropbox_update(
-                &self.path,
-                Some(bs.len()),
-                self.op.content_type(),
-                AsyncBody::Bytes(bs),
-            )
+            .dropbox_update(&self.path, Some(bs.len()), AsyncBody::Bytes(bs))
             .await?;
         let status = resp.status();
         match status {
@@ -60,6 +74,84 @@ impl oio::Write for DropboxWriter {
         }
     }
 
+    async fn start_session(&self) -> Result<String> {
+        let resp = self.core.dropbox_upload_session_start().await?;
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let output: DropboxUploadSessionStartResponse =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+                Ok(output.session_id)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn append_session(&self, session_id: &str, bs: Bytes) -> Result<()> {
+        let resp = self
+            .core
+            .dropbox_upload_session_append(session_id, self.offset, bs.len(), AsyncBody::Bytes(bs))
+            .await?;
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Write for DropboxWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let session_id = match &self.session_id {
+            Some(session_id) => session_id.clone(),
+            None => {
+                let size = bs.len();
+                if self.op.content_length().unwrap_or_default() == size as u64
+                    && size <= DROPBOX_UPLOAD_MAX_SIZE
+                {
+                    return self.write_oneshot(bs).await;
+                }
+
+                let session_id = self.start_session().await?;
+                self.session_id = Some(session_id.clone());
+                session_id
+            }
+        };
+
+        // Ignore empty bytes
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.buffer.push(bs);
+        // Return directly if the buffer is not full
+        if self.buffer.len() <= self.buffer_size {
+            return Ok(());
+        }
+
+        let bs = self.buffer.peak_exact(self.buffer_size);
+        let size = bs.len();
+
+        match self.append_session(&session_id, bs).await {
+            Ok(()) => {
+                self.buffer.take(size);
+                self.offset += size as u64;
+                Ok(())
+            }
+            Err(e) => {
+                // If the upload fails, we should pop the given bs to make sure
+                // write is re-enter safe.
+                self.buffer.pop();
+                Err(e)
+            }
+        }
+    }
+
     async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
         Err(Error::new(
             ErrorKind::Unsupported,
@@ -67,11 +159,56 @@ impl oio::Write for DropboxWriter {
         ))
     }
 
+    /// Dropbox doesn't provide api to cancel an upload session, the
+    /// session will be expired after 7 days.
     async fn abort(&mut self) -> Result<()> {
+        self.session_id = None;
+        self.offset = 0;
+        self.buffer.clear();
         Ok(())
     }
 
     async fn close(&mut self) -> Result<()> {
-        Ok(())
+        let session_id = if let Some(session_id) = &self.session_id {
+            session_id.clone()
+        } else {
+            return Ok(());
+        };
+
+        // Make sure every request carries at most `buffer_size` bytes.
+        while self.buffer.len() > self.buffer_size {
+            let bs = self.buffer.peak_exact(self.buffer_size);
+            self.append_session(&session_id, bs).await?;
+            self.buffer.take(self.buffer_size);
+            self.offset += self.buffer_size as u64;
+        }
+
+        let bs = if self.buffer.is_empty() {
+            Bytes::new()
+        } else {
+            self.buffer.peak_exact(self.buffer.len())
+        };
+        let size = bs.len();
+
+        let resp = self
+            .core
+            .dropbox_upload_session_finish(
+                &session_id,
+                self.offset,
+                &self.path,
+                size,
+                AsyncBody::Bytes(bs),
+            )
+            .await?;
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                self.buffer.clear();
+                self.session_id = None;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
     }
 }
@@ -0,0 +1,373 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::debug;
+use reqsign::AwsConfig;
+use reqsign::AwsDefaultLoader;
+use reqsign::AwsV4Signer;
+
+use super::core::DynamodbCore;
+use super::pager::DynamodbPager;
+use super::writer::DynamodbWriter;
+use crate::raw::*;
+use crate::*;
+
+/// The max size of an item in dynamodb is 400 KB including the attribute
+/// names and metadata, so we leave some space for them.
+const MAX_CHUNK_SIZE: usize = 350 * 1024;
+
+/// [DynamoDB](https://aws.amazon.com/dynamodb/) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct DynamodbBuilder {
+    table: Option<String>,
+    region: Option<String>,
+    endpoint: Option<String>,
+    access_key_id: Option<String>,
+    secret_access_key: Option<String>,
+    chunk_size: Option<usize>,
+    root: Option<String>,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for DynamodbBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+
+        ds.field("table", &self.table);
+        ds.field("region", &self.region);
+        ds.field("endpoint", &self.endpoint);
+        if self.access_key_id.is_some() {
+            ds.field("access_key_id", &"<redacted>");
+        }
+        if self.secret_access_key.is_some() {
+            ds.field("secret_access_key", &"<redacted>");
+        }
+        ds.field("chunk_size", &self.chunk_size);
+        ds.field("root", &self.root);
+        ds.finish()
+    }
+}
+
+impl DynamodbBuilder {
+    /// Set the table of this backend.
+    ///
+    /// The table must use a string partition key named `pk` and a string
+    /// sort key named `sk`.
+    pub fn table(&mut self, table: &str) -> &mut Self {
+        if !table.is_empty() {
+            self.table = Some(table.to_string());
+        }
+        self
+    }
+
+    /// Set the region of this backend.
+    ///
+    /// If not set, we will try to load it from env like `AWS_REGION`.
+    pub fn region(&mut self, region: &str) -> &mut Self {
+        if !region.is_empty() {
+            self.region = Some(region.to_string());
+        }
+        self
+    }
+
+    /// Set the endpoint of this backend.
+    ///
+    /// default: `https://dynamodb.{region}.amazonaws.com`
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            // Trim trailing `/` so that we can accept `http://127.0.0.1:8000/`
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        }
+        self
+    }
+
+    /// Set the access_key_id of this backend.
+    ///
+    /// - If access_key_id is set, we will take user's input first.
+    /// - If not, we will try to load it from environment.
+    pub fn access_key_id(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.access_key_id = Some(v.to_string())
+        }
+        self
+    }
+
+    /// Set the secret_access_key of this backend.
+    ///
+    /// - If secret_access_key is set, we will take user's input first.
+    /// - If not, we will try to load it from environment.
+    pub fn secret_access_key(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.secret_access_key = Some(v.to_string())
+        }
+        self
+    }
+
+    /// Set the max size of value stored in one item, larger values will
+    /// be split into chunks.
+    ///
+    /// default and max: 350 KiB
+    pub fn chunk_size(&mut self, chunk_size: usize) -> &mut Self {
+        if chunk_size > 0 {
+            self.chunk_size = Some(chunk_size);
+        }
+        self
+    }
+
+    /// Set root of this backend.
+    ///
+    /// All operations will happen under this root.
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for DynamodbBuilder {
+    const SCHEME: Scheme = Scheme::Dynamodb;
+    type Accessor = DynamodbBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = DynamodbBuilder::default();
+
+        map.get("table").map(|v| builder.table(v));
+        map.get("region").map(|v| builder.region(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("access_key_id").map(|v| builder.access_key_id(v));
+        map.get("secret_access_key")
+            .map(|v| builder.secret_access_key(v));
+        map.get("chunk_size")
+            .map(|v| v.parse::<usize>().map(|v| builder.chunk_size(v)));
+        map.get("root").map(|v| builder.root(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        debug!("backend build started: {:?}", &self);
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", &root);
+
+        let table = self.table.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "table is required but not set")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Dynamodb)
+        })?;
+
+        let chunk_size = self.chunk_size.unwrap_or(MAX_CHUNK_SIZE);
+        if chunk_size > MAX_CHUNK_SIZE {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "chunk_size must not be larger than 350 KiB",
+            )
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Dynamodb));
+        }
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Dynamodb)
+            })?
+        };
+
+        let mut cfg = AwsConfig::default().from_profile().from_env();
+        if let Some(v) = self.region.take() {
+            cfg.region = Some(v);
+        }
+        if let Some(v) = self.access_key_id.take() {
+            cfg.access_key_id = Some(v)
+        }
+        if let Some(v) = self.secret_access_key.take() {
+            cfg.secret_access_key = Some(v)
+        }
+
+        let region = cfg.region.clone().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "region is missing")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Dynamodb)
+        })?;
+        debug!("backend use region: {region}");
+
+        let endpoint = self
+            .endpoint
+            .take()
+            .unwrap_or_else(|| format!("https://dynamodb.{region}.amazonaws.com"));
+        debug!("backend use endpoint: {endpoint}");
+
+        let loader = AwsDefaultLoader::new(client.client(), cfg);
+        let signer = AwsV4Signer::new("dynamodb", &region);
+
+        debug!("backend build finished");
+        Ok(DynamodbBackend {
+            core: Arc::new(DynamodbCore {
+                table,
+                endpoint,
+                root,
+                chunk_size,
+                signer,
+                loader,
+                client,
+            }),
+        })
+    }
+}
+
+/// Backend for dynamodb services.
+#[derive(Debug, Clone)]
+pub struct DynamodbBackend {
+    core: Arc<DynamodbCore>,
+}
+
+#[async_trait]
+impl Accessor for DynamodbBackend {
+    type Reader = oio::Cursor;
+    type BlockingReader = ();
+    type Writer = DynamodbWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = DynamodbPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Dynamodb)
+            .set_root(&self.core.root)
+            .set_name(&self.core.table)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_seek: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_without_content_length: true,
+                write_with_content_type: true,
+
+                create_dir: true,
+                delete: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_start_after: true,
+                list_with_delimiter_slash: true,
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        let p = build_rooted_abs_path(&self.core.root, path);
+
+        self.core.create_dir(&p).await?;
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let p = build_rooted_abs_path(&self.core.root, path);
+
+        let bs =
+            self.core.read(&p, args.range()).await?.ok_or_else(|| {
+                Error::new(ErrorKind::NotFound, "dynamodb doesn't have this path")
+            })?;
+
+        Ok((RpRead::new(bs.len() as u64), oio::Cursor::from(bs)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let p = build_rooted_abs_path(&self.core.root, path);
+
+        Ok((
+            RpWrite::default(),
+            DynamodbWriter::new(self.core.clone(), p, args),
+        ))
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        let p = build_rooted_abs_path(&self.core.root, path);
+
+        if p == self.core.root {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        match self.core.stat(&p).await? {
+            Some(meta) => Ok(RpStat::new(meta)),
+            None => Err(Error::new(
+                ErrorKind::NotFound,
+                "dynamodb doesn't have this path",
+            )),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let p = build_rooted_abs_path(&self.core.root, path);
+
+        if p == self.core.root {
+            return Ok(RpDelete::default());
+        }
+
+        self.core.delete(&p).await?;
+        Ok(RpDelete::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        if args.delimiter() != "/" {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "dynamodb only support list with delimiter `/`",
+            ));
+        }
+
+        let p = build_rooted_abs_path(&self.core.root, path);
+        let start_after = args
+            .start_after()
+            .map(|v| build_rooted_abs_path(&self.core.root, v));
+
+        Ok((
+            RpList::default(),
+            DynamodbPager::new(self.core.clone(), p, args.limit(), start_after),
+        ))
+    }
+}
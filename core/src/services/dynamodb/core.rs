@@ -0,0 +1,666 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use bytes::Bytes;
+use chrono::Utc;
+use http::header::CONTENT_LENGTH;
+use http::header::CONTENT_TYPE;
+use http::Request;
+use http::StatusCode;
+use reqsign::AwsCredential;
+use reqsign::AwsDefaultLoader;
+use reqsign::AwsV4Signer;
+use serde::de::DeserializeOwned;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+mod constants {
+    pub const X_AMZ_TARGET: &str = "x-amz-target";
+    pub const X_AMZ_CONTENT_SHA_256: &str = "x-amz-content-sha256";
+    pub const CONTENT_TYPE_AMZ_JSON: &str = "application/x-amz-json-1.0";
+    pub const TARGET_PREFIX: &str = "DynamoDB_20120810";
+
+    pub const PK: &str = "pk";
+    pub const SK: &str = "sk";
+    pub const VALUE: &str = "value";
+    pub const CONTENT_LENGTH: &str = "content_length";
+    pub const CONTENT_TYPE: &str = "content_type";
+    pub const LAST_MODIFIED: &str = "last_modified";
+    pub const CHUNKS: &str = "chunks";
+    pub const CHUNK_SIZE: &str = "chunk_size";
+    pub const VERSION: &str = "version";
+}
+
+/// Project all attributes except the value.
+const METADATA_PROJECTION: &str = "#pk, #sk, #cl, #ct, #lm, #chunks, #cs, #version";
+
+/// Item is a collection of attributes in dynamodb.
+pub type Item = HashMap<String, AttributeValue>;
+
+/// AttributeValue of dynamodb, only types used by us are listed here.
+///
+/// Ref: <https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_AttributeValue.html>
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttributeValue {
+    /// String type.
+    S(String),
+    /// Number type, which is sent as string.
+    N(String),
+    /// Binary type, which is encoded by base64.
+    B(String),
+}
+
+impl AttributeValue {
+    fn string(v: &str) -> Self {
+        AttributeValue::S(v.to_string())
+    }
+
+    fn number(v: u64) -> Self {
+        AttributeValue::N(v.to_string())
+    }
+
+    fn binary(v: &[u8]) -> Self {
+        AttributeValue::B(BASE64_STANDARD.encode(v))
+    }
+}
+
+pub struct DynamodbCore {
+    pub table: String,
+    pub endpoint: String,
+    pub root: String,
+    pub chunk_size: usize,
+
+    pub signer: AwsV4Signer,
+    pub loader: AwsDefaultLoader,
+    pub client: HttpClient,
+}
+
+impl Debug for DynamodbCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamodbCore")
+            .field("table", &self.table)
+            .field("endpoint", &self.endpoint)
+            .field("root", &self.root)
+            .field("chunk_size", &self.chunk_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DynamodbCore {
+    async fn load_credential(&self) -> Result<AwsCredential> {
+        let cred = self
+            .loader
+            .load()
+            .await
+            .map_err(new_request_credential_error)?;
+
+        if let Some(cred) = cred {
+            Ok(cred)
+        } else {
+            // Mark this error as temporary since it could be caused by AWS STS.
+            Err(Error::new(
+                ErrorKind::PermissionDenied,
+                "no valid credential found, please check configuration or try again",
+            )
+            .set_temporary())
+        }
+    }
+
+    /// Send a request of given operation to dynamodb.
+    ///
+    /// Unlike s3, dynamodb doesn't accept unsigned payload, so we need to
+    /// calculate the sha256 of body before signing.
+    async fn call<T: Serialize, R: DeserializeOwned>(
+        &self,
+        operation: &str,
+        body: &T,
+    ) -> Result<R> {
+        let bs = serde_json::to_vec(body).map_err(new_json_serialize_error)?;
+
+        let mut req = Request::post(&self.endpoint)
+            .header(
+                constants::X_AMZ_TARGET,
+                format!("{}.{operation}", constants::TARGET_PREFIX),
+            )
+            .header(CONTENT_TYPE, constants::CONTENT_TYPE_AMZ_JSON)
+            .header(CONTENT_LENGTH, bs.len())
+            .header(
+                constants::X_AMZ_CONTENT_SHA_256,
+                format!("{:x}", Sha256::digest(&bs)),
+            )
+            .body(AsyncBody::Bytes(Bytes::from(bs)))
+            .map_err(new_request_build_error)?;
+
+        let cred = self.load_credential().await?;
+        self.signer
+            .sign(&mut req, &cred)
+            .map_err(new_request_sign_error)?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn get_item(&self, key: Item, projection: Option<&str>) -> Result<Option<Item>> {
+        let req = GetItemRequest {
+            table_name: &self.table,
+            key,
+            consistent_read: true,
+            projection_expression: projection,
+            expression_attribute_names: projection.map(|_| metadata_attribute_names()),
+        };
+
+        let resp: GetItemResponse = self.call("GetItem", &req).await?;
+        Ok(resp.item)
+    }
+
+    async fn put_item(&self, item: Item, condition: Option<&str>) -> Result<()> {
+        let req = PutItemRequest {
+            table_name: &self.table,
+            item,
+            condition_expression: condition,
+        };
+
+        let _: IgnoredAny = self.call("PutItem", &req).await?;
+        Ok(())
+    }
+
+    async fn delete_item(&self, key: Item) -> Result<()> {
+        let req = DeleteItemRequest {
+            table_name: &self.table,
+            key,
+        };
+
+        let _: IgnoredAny = self.call("DeleteItem", &req).await?;
+        Ok(())
+    }
+
+    /// Get the metadata of given rooted path.
+    pub async fn stat(&self, path: &str) -> Result<Option<Metadata>> {
+        let item = self
+            .get_item(entry_key(path), Some(METADATA_PROJECTION))
+            .await?;
+
+        item.map(|item| parse_metadata(&item)).transpose()
+    }
+
+    /// Read the given range of the object.
+    ///
+    /// Only the chunks that overlap with the range will be fetched.
+    pub async fn read(&self, path: &str, range: BytesRange) -> Result<Option<Vec<u8>>> {
+        let item = match self.get_item(entry_key(path), None).await? {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+
+        let length = parse_number(&item, constants::CONTENT_LENGTH)?;
+        let (start, end) = match (range.offset(), range.size()) {
+            (Some(offset), Some(size)) => (offset, offset.saturating_add(size)),
+            (Some(offset), None) => (offset, length),
+            (None, Some(size)) => (length.saturating_sub(size), length),
+            (None, None) => (0, length),
+        };
+        let end = end.min(length);
+        let (offset, size) = (start as usize, end.saturating_sub(start) as usize);
+        if size == 0 {
+            return Ok(Some(Vec::new()));
+        }
+
+        if let Some(value) = item.get(constants::VALUE) {
+            let bs = parse_binary(value)?;
+            let start = offset.min(bs.len());
+            let end = (offset + size).min(bs.len());
+            return Ok(Some(bs[start..end].to_vec()));
+        }
+        // Dirs don't have value nor chunks.
+        if !item.contains_key(constants::VERSION) {
+            return Ok(Some(Vec::new()));
+        }
+
+        let version = parse_string(&item, constants::VERSION)?;
+        let chunk_size = parse_number(&item, constants::CHUNK_SIZE)? as usize;
+        let chunks = parse_number(&item, constants::CHUNKS)? as usize;
+        if chunk_size == 0 {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "chunk size of item is zero")
+                    .with_context("path", path),
+            );
+        }
+
+        let first = offset / chunk_size;
+        let last = ((offset + size - 1) / chunk_size).min(chunks.saturating_sub(1));
+
+        let mut bs = Vec::with_capacity(size);
+        for idx in first..=last {
+            let chunk = self
+                .get_item(chunk_key(path, &version, idx), None)
+                .await?
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::NotFound, "object has been changed while reading")
+                        .with_context("path", path)
+                })?;
+            let value = chunk.get(constants::VALUE).ok_or_else(|| {
+                Error::new(ErrorKind::Unexpected, "chunk doesn't have value")
+                    .with_context("path", path)
+            })?;
+            bs.extend_from_slice(&parse_binary(value)?);
+        }
+
+        let start = (offset - first * chunk_size).min(bs.len());
+        let end = (start + size).min(bs.len());
+        Ok(Some(bs[start..end].to_vec()))
+    }
+
+    /// Write the object with given content.
+    ///
+    /// Values larger than `chunk_size` will be split into chunks stored in
+    /// their own partition. Chunks are tagged by a new version every time,
+    /// so readers will never see a mix of old and new content.
+    pub async fn write(&self, path: &str, bs: &[u8], content_type: Option<&str>) -> Result<()> {
+        let old = self
+            .get_item(entry_key(path), Some(METADATA_PROJECTION))
+            .await?;
+
+        let mut item = entry_key(path);
+        item.insert(
+            constants::CONTENT_LENGTH.to_string(),
+            AttributeValue::number(bs.len() as u64),
+        );
+        item.insert(
+            constants::LAST_MODIFIED.to_string(),
+            AttributeValue::string(&Utc::now().to_rfc3339()),
+        );
+        if let Some(v) = content_type {
+            item.insert(
+                constants::CONTENT_TYPE.to_string(),
+                AttributeValue::string(v),
+            );
+        }
+
+        if bs.len() <= self.chunk_size {
+            item.insert(constants::VALUE.to_string(), AttributeValue::binary(bs));
+        } else {
+            let version = Uuid::new_v4().to_string();
+            let mut chunks = 0;
+            for (idx, chunk) in bs.chunks(self.chunk_size).enumerate() {
+                let mut chunk_item = chunk_key(path, &version, idx);
+                chunk_item.insert(constants::VALUE.to_string(), AttributeValue::binary(chunk));
+                self.put_item(chunk_item, None).await?;
+                chunks += 1;
+            }
+
+            item.insert(
+                constants::CHUNKS.to_string(),
+                AttributeValue::number(chunks),
+            );
+            item.insert(
+                constants::CHUNK_SIZE.to_string(),
+                AttributeValue::number(self.chunk_size as u64),
+            );
+            item.insert(
+                constants::VERSION.to_string(),
+                AttributeValue::string(&version),
+            );
+        }
+
+        self.put_item(item, None).await?;
+        self.create_parents(path).await?;
+
+        if let Some(old) = old {
+            self.delete_chunks(path, &old).await?;
+        }
+        Ok(())
+    }
+
+    /// Create the dir and all its parents.
+    pub async fn create_dir(&self, path: &str) -> Result<()> {
+        let mut item = entry_key(path);
+        item.insert(
+            constants::CONTENT_LENGTH.to_string(),
+            AttributeValue::number(0),
+        );
+        item.insert(
+            constants::LAST_MODIFIED.to_string(),
+            AttributeValue::string(&Utc::now().to_rfc3339()),
+        );
+        self.put_item(item, None).await?;
+
+        self.create_parents(path).await
+    }
+
+    /// Create parent dirs of given path from bottom to top.
+    ///
+    /// We will stop at the first dir that already exists, since its parents
+    /// must have been created at the same time.
+    async fn create_parents(&self, path: &str) -> Result<()> {
+        let mut dir = get_parent(path);
+        while dir.len() > self.root.len() {
+            let mut item = entry_key(dir);
+            item.insert(
+                constants::CONTENT_LENGTH.to_string(),
+                AttributeValue::number(0),
+            );
+            item.insert(
+                constants::LAST_MODIFIED.to_string(),
+                AttributeValue::string(&Utc::now().to_rfc3339()),
+            );
+
+            match self.put_item(item, Some("attribute_not_exists(pk)")).await {
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::ConditionNotMatch => break,
+                Err(err) => return Err(err),
+            }
+
+            dir = get_parent(dir);
+        }
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, path: &str) -> Result<()> {
+        if path.ends_with('/') {
+            return self.delete_item(entry_key(path)).await;
+        }
+
+        let old = self
+            .get_item(entry_key(path), Some(METADATA_PROJECTION))
+            .await?;
+        self.delete_item(entry_key(path)).await?;
+
+        if let Some(old) = old {
+            self.delete_chunks(path, &old).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete chunks referred by the given entry item.
+    async fn delete_chunks(&self, path: &str, item: &Item) -> Result<()> {
+        if !item.contains_key(constants::VERSION) {
+            return Ok(());
+        }
+
+        let version = parse_string(item, constants::VERSION)?;
+        let chunks = parse_number(item, constants::CHUNKS)? as usize;
+        for idx in 0..chunks {
+            self.delete_item(chunk_key(path, &version, idx)).await?;
+        }
+        Ok(())
+    }
+
+    /// List entries of the dir page by page.
+    ///
+    /// Returns the entries and the key to continue with.
+    pub async fn list(
+        &self,
+        dir: &str,
+        limit: Option<usize>,
+        exclusive_start_key: Option<Item>,
+    ) -> Result<(Vec<(String, Metadata)>, Option<Item>)> {
+        let req = QueryRequest {
+            table_name: &self.table,
+            key_condition_expression: "#pk = :pk",
+            expression_attribute_names: metadata_attribute_names(),
+            expression_attribute_values: HashMap::from([(
+                ":pk".to_string(),
+                AttributeValue::string(dir),
+            )]),
+            projection_expression: METADATA_PROJECTION,
+            consistent_read: true,
+            limit,
+            exclusive_start_key,
+        };
+
+        let resp: QueryResponse = self.call("Query", &req).await?;
+
+        let mut entries = Vec::with_capacity(resp.items.len());
+        for item in resp.items {
+            let name = parse_string(&item, constants::SK)?;
+            entries.push((format!("{dir}{name}"), parse_metadata(&item)?));
+        }
+        Ok((entries, resp.last_evaluated_key))
+    }
+}
+
+/// Build the key of the entry of given rooted path.
+///
+/// Entries are stored in the partition of their parent, so that all
+/// children of a dir can be listed by one query.
+fn entry_key(path: &str) -> Item {
+    child_key(get_parent(path), get_basename(path))
+}
+
+/// Build the key of the child with given name in the dir.
+pub fn child_key(dir: &str, name: &str) -> Item {
+    HashMap::from([
+        (constants::PK.to_string(), AttributeValue::string(dir)),
+        (constants::SK.to_string(), AttributeValue::string(name)),
+    ])
+}
+
+/// Build the key of the chunk of given rooted path.
+///
+/// Chunks are stored in the partition named by the file path, which will
+/// never conflict with dirs since dirs always end with `/`.
+fn chunk_key(path: &str, version: &str, idx: usize) -> Item {
+    HashMap::from([
+        (constants::PK.to_string(), AttributeValue::string(path)),
+        (
+            constants::SK.to_string(),
+            AttributeValue::S(format!("{version}/{idx:010}")),
+        ),
+    ])
+}
+
+fn metadata_attribute_names() -> HashMap<&'static str, &'static str> {
+    HashMap::from([
+        ("#pk", constants::PK),
+        ("#sk", constants::SK),
+        ("#cl", constants::CONTENT_LENGTH),
+        ("#ct", constants::CONTENT_TYPE),
+        ("#lm", constants::LAST_MODIFIED),
+        ("#chunks", constants::CHUNKS),
+        ("#cs", constants::CHUNK_SIZE),
+        ("#version", constants::VERSION),
+    ])
+}
+
+fn parse_metadata(item: &Item) -> Result<Metadata> {
+    let name = parse_string(item, constants::SK)?;
+
+    let mut meta = if name.ends_with('/') {
+        Metadata::new(EntryMode::DIR)
+    } else {
+        Metadata::new(EntryMode::FILE)
+    };
+    meta.set_content_length(parse_number(item, constants::CONTENT_LENGTH)?);
+    meta.set_last_modified(parse_datetime_from_rfc3339(&parse_string(
+        item,
+        constants::LAST_MODIFIED,
+    )?)?);
+    if item.contains_key(constants::CONTENT_TYPE) {
+        meta.set_content_type(&parse_string(item, constants::CONTENT_TYPE)?);
+    }
+
+    Ok(meta)
+}
+
+fn parse_string(item: &Item, name: &str) -> Result<String> {
+    match item.get(name) {
+        Some(AttributeValue::S(v)) => Ok(v.clone()),
+        v => Err(
+            Error::new(ErrorKind::Unexpected, "attribute is not a valid string")
+                .with_context("attribute", name)
+                .with_context("value", format!("{v:?}")),
+        ),
+    }
+}
+
+fn parse_number(item: &Item, name: &str) -> Result<u64> {
+    match item.get(name) {
+        Some(AttributeValue::N(v)) => v.parse::<u64>().map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "attribute is not a valid number")
+                .with_context("attribute", name)
+                .set_source(e)
+        }),
+        v => Err(
+            Error::new(ErrorKind::Unexpected, "attribute is not a valid number")
+                .with_context("attribute", name)
+                .with_context("value", format!("{v:?}")),
+        ),
+    }
+}
+
+fn parse_binary(value: &AttributeValue) -> Result<Vec<u8>> {
+    match value {
+        AttributeValue::B(v) => BASE64_STANDARD.decode(v).map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "attribute is not valid base64").set_source(e)
+        }),
+        v => Err(
+            Error::new(ErrorKind::Unexpected, "attribute is not a valid binary")
+                .with_context("value", format!("{v:?}")),
+        ),
+    }
+}
+
+/// Ref: <https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_GetItem.html>
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetItemRequest<'a> {
+    table_name: &'a str,
+    key: Item,
+    consistent_read: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    projection_expression: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expression_attribute_names: Option<HashMap<&'static str, &'static str>>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct GetItemResponse {
+    item: Option<Item>,
+}
+
+/// Ref: <https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_PutItem.html>
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct PutItemRequest<'a> {
+    table_name: &'a str,
+    item: Item,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    condition_expression: Option<&'a str>,
+}
+
+/// Ref: <https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_DeleteItem.html>
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeleteItemRequest<'a> {
+    table_name: &'a str,
+    key: Item,
+}
+
+/// Ref: <https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_Query.html>
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct QueryRequest<'a> {
+    table_name: &'a str,
+    key_condition_expression: &'a str,
+    expression_attribute_names: HashMap<&'static str, &'static str>,
+    expression_attribute_values: Item,
+    projection_expression: &'a str,
+    consistent_read: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclusive_start_key: Option<Item>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct QueryResponse {
+    items: Vec<Item>,
+    last_evaluated_key: Option<Item>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_key() {
+        let cases = vec![
+            ("file", "/path/to/file", "/path/to/", "file"),
+            ("dir", "/path/to/dir/", "/path/to/", "dir/"),
+            ("top level file", "/file", "/", "file"),
+        ];
+
+        for (name, input, pk, sk) in cases {
+            let key = entry_key(input);
+            assert_eq!(key[constants::PK], AttributeValue::string(pk), "{name}");
+            assert_eq!(key[constants::SK], AttributeValue::string(sk), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_deserialize_query_response() {
+        let content = r#"{
+            "Count": 1,
+            "Items": [
+                {
+                    "pk": {"S": "/path/to/"},
+                    "sk": {"S": "file"},
+                    "content_length": {"N": "1024"},
+                    "content_type": {"S": "text/plain"},
+                    "last_modified": {"S": "2023-07-10T08:00:00+00:00"}
+                }
+            ],
+            "LastEvaluatedKey": {
+                "pk": {"S": "/path/to/"},
+                "sk": {"S": "file"}
+            },
+            "ScannedCount": 1
+        }"#;
+
+        let resp: QueryResponse = serde_json::from_str(content).expect("response must be valid");
+        assert_eq!(resp.items.len(), 1);
+        assert!(resp.last_evaluated_key.is_some());
+
+        let meta = parse_metadata(&resp.items[0]).expect("metadata must be valid");
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), 1024);
+        assert_eq!(meta.content_type(), Some("text/plain"));
+    }
+}
@@ -0,0 +1,123 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::de;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// DynamodbError is the error returned by dynamodb service.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct DynamodbError {
+    #[serde(rename = "__type")]
+    ty: String,
+    #[serde(alias = "Message")]
+    message: String,
+}
+
+/// Parse error response into Error.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    let (mut kind, mut retryable) = match parts.status {
+        StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let (message, dynamodb_err) = de::from_slice::<DynamodbError>(&bs)
+        .map(|err| (format!("{err:?}"), Some(err)))
+        .unwrap_or_else(|_| (String::from_utf8_lossy(&bs).into_owned(), None));
+
+    if let Some(dynamodb_err) = dynamodb_err {
+        (kind, retryable) =
+            parse_dynamodb_error_type(&dynamodb_err.ty).unwrap_or((kind, retryable));
+    }
+
+    let mut err = Error::new(kind, &message).with_context("response", format!("{parts:?}"));
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+/// Returns the `Error kind` of this type and whether the error is retryable.
+///
+/// The type looks like `com.amazonaws.dynamodb.v20120810#ResourceNotFoundException`.
+/// All possible error types: <https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Programming.Errors.html>
+fn parse_dynamodb_error_type(ty: &str) -> Option<(ErrorKind, bool)> {
+    let name = ty.rsplit('#').next().unwrap_or(ty);
+
+    match name {
+        "ConditionalCheckFailedException" => Some((ErrorKind::ConditionNotMatch, false)),
+        "AccessDeniedException" | "UnrecognizedClientException" => {
+            Some((ErrorKind::PermissionDenied, false))
+        }
+        "ProvisionedThroughputExceededException"
+        | "RequestLimitExceeded"
+        | "ThrottlingException" => Some((ErrorKind::RateLimited, true)),
+        "InternalServerError" | "ServiceUnavailable" => Some((ErrorKind::Unexpected, true)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dynamodb_error_type() {
+        let cases = vec![
+            (
+                "condition",
+                "com.amazonaws.dynamodb.v20120810#ConditionalCheckFailedException",
+                Some((ErrorKind::ConditionNotMatch, false)),
+            ),
+            (
+                "throttling",
+                "com.amazonaws.dynamodb.v20120810#ProvisionedThroughputExceededException",
+                Some((ErrorKind::RateLimited, true)),
+            ),
+            (
+                "without prefix",
+                "AccessDeniedException",
+                Some((ErrorKind::PermissionDenied, false)),
+            ),
+            (
+                "unknown",
+                "com.amazonaws.dynamodb.v20120810#ValidationException",
+                None,
+            ),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(parse_dynamodb_error_type(input), expected, "{name}");
+        }
+    }
+}
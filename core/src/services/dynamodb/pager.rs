@@ -0,0 +1,85 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::core::child_key;
+use super::core::DynamodbCore;
+use super::core::Item;
+use crate::raw::*;
+use crate::*;
+
+/// Pager that lists the children of a dir via query page by page.
+pub struct DynamodbPager {
+    core: Arc<DynamodbCore>,
+    path: String,
+    limit: Option<usize>,
+    exclusive_start_key: Option<Item>,
+    done: bool,
+}
+
+impl DynamodbPager {
+    pub fn new(
+        core: Arc<DynamodbCore>,
+        path: String,
+        limit: Option<usize>,
+        start_after: Option<String>,
+    ) -> Self {
+        // Children are sorted by their names in the partition of the dir,
+        // so we can start after the name directly.
+        let exclusive_start_key = start_after
+            .as_deref()
+            .and_then(|v| v.strip_prefix(path.as_str()))
+            .filter(|v| !v.is_empty())
+            .map(|v| child_key(&path, v));
+
+        Self {
+            core,
+            path,
+            limit,
+            exclusive_start_key,
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for DynamodbPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let (items, last_evaluated_key) = self
+            .core
+            .list(&self.path, self.limit, self.exclusive_start_key.take())
+            .await?;
+        if last_evaluated_key.is_none() {
+            self.done = true;
+        }
+        self.exclusive_start_key = last_evaluated_key;
+
+        let entries = items
+            .into_iter()
+            .map(|(path, meta)| oio::Entry::new(&build_rel_path(&self.core.root, &path), meta))
+            .collect();
+
+        Ok(Some(entries))
+    }
+}
@@ -0,0 +1,448 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use etcd_client::Certificate;
+use etcd_client::Client;
+use etcd_client::ConnectOptions;
+use etcd_client::GetOptions;
+use etcd_client::Identity;
+use etcd_client::PutOptions;
+use etcd_client::TlsOptions;
+use tokio::sync::OnceCell;
+
+use crate::raw::adapters::kv;
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_ETCD_ENDPOINTS: &str = "http://127.0.0.1:2379";
+/// The max number of keys returned by one range request while listing.
+const DEFAULT_SCAN_LIMIT: i64 = 1000;
+
+/// [Etcd](https://etcd.io/) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Clone, Default)]
+pub struct EtcdBuilder {
+    /// network address of the Etcd services.
+    /// If use https, must set TLS options: `ca_path`, `cert_path`, `key_path`.
+    /// e.g. "127.0.0.1:23790,127.0.0.1:23791,127.0.0.1:23792" or "http://127.0.0.1:23790,http://127.0.0.1:23791,http://127.0.0.1:23792" or "https://127.0.0.1:23790,https://127.0.0.1:23791,https://127.0.0.1:23792"
+    ///
+    /// default is "http://127.0.0.1:2379"
+    endpoints: Option<String>,
+    /// the username to connect etcd service.
+    ///
+    /// default is None
+    username: Option<String>,
+    /// the password for authentication
+    ///
+    /// default is None
+    password: Option<String>,
+    /// the working directory of the etcd service. Can be "/path/to/dir"
+    ///
+    /// default is "/"
+    root: Option<String>,
+    /// certificate authority file path
+    ///
+    /// default is None
+    ca_path: Option<String>,
+    /// cert path
+    ///
+    /// default is None
+    cert_path: Option<String>,
+    /// key path
+    ///
+    /// default is None
+    key_path: Option<String>,
+    /// The default ttl for put operations.
+    default_ttl: Option<Duration>,
+}
+
+impl Debug for EtcdBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+
+        ds.field("root", &self.root);
+        if let Some(endpoints) = self.endpoints.clone() {
+            ds.field("endpoints", &endpoints);
+        }
+        if let Some(username) = self.username.clone() {
+            ds.field("username", &username);
+        }
+        if self.password.is_some() {
+            ds.field("password", &"<redacted>");
+        }
+        if let Some(ca_path) = self.ca_path.clone() {
+            ds.field("ca_path", &ca_path);
+        }
+        if let Some(cert_path) = self.cert_path.clone() {
+            ds.field("cert_path", &cert_path);
+        }
+        if self.key_path.is_some() {
+            ds.field("key_path", &"<redacted>");
+        }
+        ds.finish()
+    }
+}
+
+impl EtcdBuilder {
+    /// set the network address of etcd service.
+    ///
+    /// multiple endpoints can be separated by `,`.
+    ///
+    /// default: "http://127.0.0.1:2379"
+    pub fn endpoints(&mut self, endpoints: &str) -> &mut Self {
+        if !endpoints.is_empty() {
+            self.endpoints = Some(endpoints.to_owned());
+        }
+        self
+    }
+
+    /// set the username for etcd
+    ///
+    /// default: no username
+    pub fn username(&mut self, username: &str) -> &mut Self {
+        if !username.is_empty() {
+            self.username = Some(username.to_owned());
+        }
+        self
+    }
+
+    /// set the password for etcd
+    ///
+    /// default: no password
+    pub fn password(&mut self, password: &str) -> &mut Self {
+        if !password.is_empty() {
+            self.password = Some(password.to_owned());
+        }
+        self
+    }
+
+    /// set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_owned());
+        }
+        self
+    }
+
+    /// Set the certificate authority file path.
+    ///
+    /// default is None
+    pub fn ca_path(&mut self, ca_path: &str) -> &mut Self {
+        if !ca_path.is_empty() {
+            self.ca_path = Some(ca_path.to_string())
+        }
+        self
+    }
+
+    /// Set the certificate file path.
+    ///
+    /// default is None
+    pub fn cert_path(&mut self, cert_path: &str) -> &mut Self {
+        if !cert_path.is_empty() {
+            self.cert_path = Some(cert_path.to_string())
+        }
+        self
+    }
+
+    /// Set the key file path.
+    ///
+    /// default is None
+    pub fn key_path(&mut self, key_path: &str) -> &mut Self {
+        if !key_path.is_empty() {
+            self.key_path = Some(key_path.to_string())
+        }
+        self
+    }
+
+    /// Set the default ttl for etcd services.
+    ///
+    /// If set, every write will be attached to a new lease with this ttl,
+    /// and the key will be removed by etcd after the lease expired.
+    pub fn default_ttl(&mut self, ttl: Duration) -> &mut Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+}
+
+impl Builder for EtcdBuilder {
+    const SCHEME: Scheme = Scheme::Etcd;
+    type Accessor = EtcdBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = EtcdBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("endpoints").map(|v| builder.endpoints(v));
+        map.get("username").map(|v| builder.username(v));
+        map.get("password").map(|v| builder.password(v));
+        map.get("ca_path").map(|v| builder.ca_path(v));
+        map.get("cert_path").map(|v| builder.cert_path(v));
+        map.get("key_path").map(|v| builder.key_path(v));
+        map.get("default_ttl").map(|v| {
+            v.parse::<u64>()
+                .map(|v| builder.default_ttl(Duration::from_secs(v)))
+        });
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let endpoints = self
+            .endpoints
+            .clone()
+            .unwrap_or_else(|| DEFAULT_ETCD_ENDPOINTS.to_string());
+        let endpoints: Vec<String> = endpoints
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        let mut options = ConnectOptions::new();
+
+        if let Some(ca_path) = &self.ca_path {
+            let ca = std::fs::read(ca_path).map_err(|e| {
+                Error::new(ErrorKind::ConfigInvalid, "read ca file failed")
+                    .with_context("service", Scheme::Etcd)
+                    .with_context("ca_path", ca_path)
+                    .set_source(e)
+            })?;
+            let mut tls_options = TlsOptions::new().ca_certificate(Certificate::from_pem(ca));
+
+            match (&self.cert_path, &self.key_path) {
+                (Some(cert_path), Some(key_path)) => {
+                    let cert = std::fs::read(cert_path).map_err(|e| {
+                        Error::new(ErrorKind::ConfigInvalid, "read cert file failed")
+                            .with_context("service", Scheme::Etcd)
+                            .with_context("cert_path", cert_path)
+                            .set_source(e)
+                    })?;
+                    let key = std::fs::read(key_path).map_err(|e| {
+                        Error::new(ErrorKind::ConfigInvalid, "read key file failed")
+                            .with_context("service", Scheme::Etcd)
+                            .with_context("key_path", key_path)
+                            .set_source(e)
+                    })?;
+                    tls_options = tls_options.identity(Identity::from_pem(cert, key));
+                }
+                (None, None) => {}
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "cert_path and key_path must be set together",
+                    )
+                    .with_context("service", Scheme::Etcd))
+                }
+            }
+
+            options = options.with_tls(tls_options);
+        }
+
+        if let Some(username) = &self.username {
+            options =
+                options.with_user(username.clone(), self.password.clone().unwrap_or_default());
+        }
+
+        let root = normalize_root(
+            self.root
+                .clone()
+                .unwrap_or_else(|| "/".to_string())
+                .as_str(),
+        );
+
+        Ok(EtcdBackend::new(Adapter {
+            endpoints,
+            options,
+            client: OnceCell::new(),
+            default_ttl: self.default_ttl,
+        })
+        .with_root(&root))
+    }
+}
+
+/// Backend for etcd services.
+pub type EtcdBackend = kv::Backend<Adapter>;
+
+#[derive(Clone)]
+pub struct Adapter {
+    endpoints: Vec<String>,
+    options: ConnectOptions,
+    client: OnceCell<Client>,
+
+    default_ttl: Option<Duration>,
+}
+
+// implement `Debug` manually, or password may be leaked.
+impl Debug for Adapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Adapter");
+        ds.field("endpoints", &self.endpoints.join(","));
+        ds.finish()
+    }
+}
+
+impl Adapter {
+    async fn client(&self) -> Result<Client> {
+        Ok(self
+            .client
+            .get_or_try_init(|| async {
+                Client::connect(&self.endpoints, Some(self.options.clone()))
+                    .await
+                    .map_err(|e| {
+                        Error::new(ErrorKind::ConfigInvalid, "connect to etcd failed")
+                            .with_context("service", Scheme::Etcd)
+                            .with_context("endpoints", self.endpoints.join(","))
+                            .set_source(e)
+                    })
+            })
+            .await?
+            .clone())
+    }
+}
+
+#[async_trait]
+impl kv::Adapter for Adapter {
+    fn metadata(&self) -> kv::Metadata {
+        kv::Metadata::new(
+            Scheme::Etcd,
+            &self.endpoints.join(","),
+            Capability {
+                read: true,
+                write: true,
+                list: true,
+
+                ..Default::default()
+            },
+        )
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut client = self.client().await?;
+        let resp = client.get(key, None).await.map_err(parse_etcd_error)?;
+        Ok(resp.kvs().first().map(|kv| kv.value().to_vec()))
+    }
+
+    async fn set(&self, key: &str, value: &[u8]) -> Result<()> {
+        let mut client = self.client().await?;
+
+        let options = match self.default_ttl {
+            Some(ttl) => {
+                let lease = client
+                    .lease_grant(ttl.as_secs() as i64, None)
+                    .await
+                    .map_err(parse_etcd_error)?;
+                Some(PutOptions::new().with_lease(lease.id()))
+            }
+            None => None,
+        };
+
+        client
+            .put(key, value, options)
+            .await
+            .map_err(parse_etcd_error)?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let mut client = self.client().await?;
+        client.delete(key, None).await.map_err(parse_etcd_error)?;
+        Ok(())
+    }
+
+    /// Scan keys via range requests with `keys_only` page by page, so that
+    /// large directories will not exceed the grpc message size limit.
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        let mut client = self.client().await?;
+        let end = prefix_range_end(path.as_bytes());
+
+        let mut res = Vec::new();
+        let mut start = path.as_bytes().to_vec();
+        loop {
+            let options = GetOptions::new()
+                .with_range(end.clone())
+                .with_keys_only()
+                .with_limit(DEFAULT_SCAN_LIMIT);
+            let resp = client
+                .get(start.clone(), Some(options))
+                .await
+                .map_err(parse_etcd_error)?;
+
+            for kv in resp.kvs() {
+                let key = kv.key_str().map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "store key is not valid utf-8 string")
+                        .set_source(err)
+                })?;
+                res.push(key.to_string());
+            }
+
+            match resp.kvs().last() {
+                // The smallest key after the last one is itself with a `\0` appended.
+                Some(kv) if resp.more() => {
+                    start = kv.key().to_vec();
+                    start.push(0);
+                }
+                _ => return Ok(res),
+            }
+        }
+    }
+}
+
+/// Calculate the range end of given prefix, all keys with this prefix
+/// are in range `[prefix, end)`.
+///
+/// `\0` means the whole key space after prefix if there is no such end.
+fn prefix_range_end(prefix: &[u8]) -> Vec<u8> {
+    for (i, v) in prefix.iter().enumerate().rev() {
+        if *v < 0xFF {
+            let mut end = prefix[..=i].to_vec();
+            end[i] = *v + 1;
+            return end;
+        }
+    }
+
+    vec![0]
+}
+
+fn parse_etcd_error(e: etcd_client::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "error from etcd").set_source(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_range_end() {
+        let cases = vec![
+            ("normal", b"/path/to/".to_vec(), b"/path/to0".to_vec()),
+            ("empty", b"".to_vec(), vec![0]),
+            ("trailing 0xff", vec![b'a', 0xFF], vec![b'b']),
+            ("all 0xff", vec![0xFF, 0xFF], vec![0]),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(prefix_range_end(&input), expected, "{name}")
+        }
+    }
+}
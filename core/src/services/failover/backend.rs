@@ -0,0 +1,501 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+
+use crate::raw::*;
+use crate::*;
+
+/// Default duration that an unhealthy region will be skipped.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// ReadPreference decides which region will be tried first while reading.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReadPreference {
+    /// Try the primary first and then replicas in the order they were added.
+    #[default]
+    Primary,
+    /// Try regions with the lowest observed latency first.
+    Nearest,
+}
+
+/// Failover service support which reads across regions with automatic failover.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct FailoverBuilder {
+    primary: Option<Operator>,
+    replicas: Vec<Operator>,
+    read_preference: ReadPreference,
+    cooldown: Option<Duration>,
+}
+
+impl FailoverBuilder {
+    /// Set the operator of primary region.
+    ///
+    /// All writes will be sent to the primary.
+    pub fn primary(&mut self, op: Operator) -> &mut Self {
+        self.primary = Some(op);
+        self
+    }
+
+    /// Add an operator of replica region.
+    pub fn replica(&mut self, op: Operator) -> &mut Self {
+        self.replicas.push(op);
+        self
+    }
+
+    /// Set how to pick the region to read from.
+    ///
+    /// Default to [`ReadPreference::Primary`].
+    pub fn read_preference(&mut self, v: ReadPreference) -> &mut Self {
+        self.read_preference = v;
+        self
+    }
+
+    /// Set how long an unhealthy region will be skipped before trying
+    /// it again.
+    ///
+    /// Default to `30s`.
+    pub fn cooldown(&mut self, v: Duration) -> &mut Self {
+        self.cooldown = Some(v);
+        self
+    }
+}
+
+impl Builder for FailoverBuilder {
+    const SCHEME: Scheme = Scheme::Failover;
+    type Accessor = FailoverBackend;
+
+    /// Failover can't be built from map since it's composed by operators,
+    /// the returning builder doesn't have any regions.
+    fn from_map(_: HashMap<String, String>) -> Self {
+        Self::default()
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let primary = match &self.primary {
+            Some(v) => v,
+            None => {
+                return Err(
+                    Error::new(ErrorKind::ConfigInvalid, "failover requires a primary")
+                        .with_operation("Builder::build")
+                        .with_context("service", Scheme::Failover),
+                )
+            }
+        };
+
+        let regions = std::iter::once(primary)
+            .chain(self.replicas.iter())
+            .map(|v| Region {
+                accessor: v.inner().clone(),
+                state: Mutex::default(),
+            })
+            .collect();
+
+        Ok(FailoverBackend {
+            regions: Arc::new(regions),
+            read_preference: self.read_preference,
+            cooldown: self.cooldown.unwrap_or(DEFAULT_COOLDOWN),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Region {
+    accessor: FusedAccessor,
+    state: Mutex<RegionState>,
+}
+
+#[derive(Debug, Default)]
+struct RegionState {
+    /// The region will be skipped until this instant.
+    unhealthy_until: Option<Instant>,
+    /// Moving average of observed latency, `None` means not observed yet.
+    latency: Option<Duration>,
+}
+
+/// Backend is used to serve `Accessor` support for failover.
+///
+/// The first region is the primary.
+#[derive(Debug, Clone)]
+pub struct FailoverBackend {
+    regions: Arc<Vec<Region>>,
+    read_preference: ReadPreference,
+    cooldown: Duration,
+}
+
+impl FailoverBackend {
+    fn primary(&self) -> &FusedAccessor {
+        &self.regions[0].accessor
+    }
+
+    /// Returns the index of regions in the order they should be tried.
+    ///
+    /// Healthy regions come first, unhealthy regions are kept at last so
+    /// that we still have a chance while all regions are unhealthy.
+    fn candidates(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let (mut healthy, unhealthy): (Vec<_>, Vec<_>) = (0..self.regions.len()).partition(|idx| {
+            match self.regions[*idx].state.lock().unhealthy_until {
+                Some(until) => until <= now,
+                None => true,
+            }
+        });
+
+        if self.read_preference == ReadPreference::Nearest {
+            // Regions that have not been observed will be tried first.
+            healthy.sort_by_key(|idx| self.regions[*idx].state.lock().latency.unwrap_or_default());
+        }
+
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    /// Record a successful call and mark the region as healthy.
+    fn on_success(&self, idx: usize, elapsed: Duration) {
+        let mut state = self.regions[idx].state.lock();
+        state.unhealthy_until = None;
+        state.latency = Some(match state.latency {
+            Some(v) => (v * 4 + elapsed) / 5,
+            None => elapsed,
+        });
+    }
+
+    /// Record a failed call, returns `true` if we should fail over to
+    /// the next region.
+    fn on_failure(&self, idx: usize, err: &Error) -> bool {
+        let failover = err.is_temporary()
+            || matches!(
+                err.kind(),
+                ErrorKind::Unexpected
+                    | ErrorKind::RateLimited
+                    | ErrorKind::ContentTruncated
+                    | ErrorKind::ContentIncomplete
+            );
+        if failover {
+            self.regions[idx].state.lock().unhealthy_until = Some(Instant::now() + self.cooldown);
+        }
+        failover
+    }
+}
+
+#[async_trait]
+impl Accessor for FailoverBackend {
+    type Reader = oio::Reader;
+    type BlockingReader = oio::BlockingReader;
+    type Writer = oio::Writer;
+    type BlockingWriter = oio::BlockingWriter;
+    type Appender = oio::Appender;
+    type Pager = oio::Pager;
+    type BlockingPager = oio::BlockingPager;
+
+    fn info(&self) -> AccessorInfo {
+        let caps: Vec<Capability> = self
+            .regions
+            .iter()
+            .map(|v| v.accessor.info().capability())
+            .collect();
+        let all = |f: fn(&Capability) -> bool| caps.iter().all(f);
+        let p = caps[0];
+
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Failover)
+            .set_root("/")
+            .set_capability(Capability {
+                stat: all(|c| c.stat),
+                stat_has_permission: all(|c| c.stat_has_permission),
+                stat_has_user_metadata: all(|c| c.stat_has_user_metadata),
+
+                read: all(|c| c.read),
+                read_can_seek: all(|c| c.read_can_seek),
+                read_can_next: all(|c| c.read_can_next),
+                read_with_range: all(|c| c.read_with_range),
+
+                write: p.write,
+                write_without_content_length: p.write_without_content_length,
+                write_with_content_type: p.write_with_content_type,
+                write_with_content_disposition: p.write_with_content_disposition,
+                write_with_cache_control: p.write_with_cache_control,
+                write_with_permission: p.write_with_permission,
+                write_with_user_metadata: p.write_with_user_metadata,
+                append: p.append,
+                append_with_content_type: p.append_with_content_type,
+                append_with_content_disposition: p.append_with_content_disposition,
+                append_with_cache_control: p.append_with_cache_control,
+                create_dir: p.create_dir,
+                delete: p.delete,
+
+                list: all(|c| c.list),
+                list_with_delimiter_slash: all(|c| c.list_with_delimiter_slash),
+                list_without_delimiter: all(|c| c.list_without_delimiter),
+
+                presign: p.presign,
+                presign_read: p.presign_read,
+                presign_stat: p.presign_stat,
+                presign_write: p.presign_write,
+
+                blocking: all(|c| c.blocking),
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.primary().create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let mut last_err = None;
+        for idx in self.candidates() {
+            let start = Instant::now();
+            match self.regions[idx].accessor.read(path, args.clone()).await {
+                Ok(v) => {
+                    self.on_success(idx, start.elapsed());
+                    return Ok(v);
+                }
+                Err(err) if self.on_failure(idx, &err) => last_err = Some(err),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.expect("failover must have at least one region"))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.primary().write(path, args).await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.primary().append(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let mut last_err = None;
+        for idx in self.candidates() {
+            let start = Instant::now();
+            match self.regions[idx].accessor.stat(path, args.clone()).await {
+                Ok(v) => {
+                    self.on_success(idx, start.elapsed());
+                    return Ok(v);
+                }
+                Err(err) if self.on_failure(idx, &err) => last_err = Some(err),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.expect("failover must have at least one region"))
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.primary().delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let mut last_err = None;
+        for idx in self.candidates() {
+            let start = Instant::now();
+            match self.regions[idx].accessor.list(path, args.clone()).await {
+                Ok(v) => {
+                    self.on_success(idx, start.elapsed());
+                    return Ok(v);
+                }
+                Err(err) if self.on_failure(idx, &err) => last_err = Some(err),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.expect("failover must have at least one region"))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.primary().presign(path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.primary().blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let mut last_err = None;
+        for idx in self.candidates() {
+            let start = Instant::now();
+            match self.regions[idx].accessor.blocking_read(path, args.clone()) {
+                Ok(v) => {
+                    self.on_success(idx, start.elapsed());
+                    return Ok(v);
+                }
+                Err(err) if self.on_failure(idx, &err) => last_err = Some(err),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.expect("failover must have at least one region"))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.primary().blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let mut last_err = None;
+        for idx in self.candidates() {
+            let start = Instant::now();
+            match self.regions[idx].accessor.blocking_stat(path, args.clone()) {
+                Ok(v) => {
+                    self.on_success(idx, start.elapsed());
+                    return Ok(v);
+                }
+                Err(err) if self.on_failure(idx, &err) => last_err = Some(err),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.expect("failover must have at least one region"))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.primary().blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        let mut last_err = None;
+        for idx in self.candidates() {
+            let start = Instant::now();
+            match self.regions[idx].accessor.blocking_list(path, args.clone()) {
+                Ok(v) => {
+                    self.on_success(idx, start.elapsed());
+                    return Ok(v);
+                }
+                Err(err) if self.on_failure(idx, &err) => last_err = Some(err),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.expect("failover must have at least one region"))
+    }
+}
+
+#[cfg(all(test, feature = "services-mock"))]
+mod tests {
+    use super::*;
+    use crate::services::Mock;
+    use crate::services::MockHandle;
+
+    fn new_mock() -> (Operator, MockHandle) {
+        let builder = Mock::default();
+        let handle = builder.handle();
+        (Operator::new(builder).unwrap().finish(), handle)
+    }
+
+    #[tokio::test]
+    async fn test_read_failover() {
+        let (primary, primary_handle) = new_mock();
+        let (replica, replica_handle) = new_mock();
+        primary.write("a", "data").await.unwrap();
+        replica.write("a", "data").await.unwrap();
+
+        let mut builder = FailoverBuilder::default();
+        builder.primary(primary).replica(replica);
+        let op = Operator::new(builder).unwrap().finish();
+
+        primary_handle.fail_next(Operation::Read, ErrorKind::Unexpected);
+        assert_eq!(op.read("a").await.unwrap(), b"data");
+        assert_eq!(replica_handle.calls_of(Operation::Read).len(), 1);
+
+        // Primary is unhealthy now, reads go to replica first.
+        replica_handle.clear_calls();
+        primary_handle.clear_calls();
+        assert_eq!(op.read("a").await.unwrap(), b"data");
+        assert_eq!(primary_handle.calls_of(Operation::Read).len(), 0);
+        assert_eq!(replica_handle.calls_of(Operation::Read).len(), 1);
+
+        // NotFound won't fail over.
+        replica_handle.fail_next(Operation::Stat, ErrorKind::NotFound);
+        let err = op.stat("a").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_recover() {
+        let (primary, primary_handle) = new_mock();
+        let (replica, replica_handle) = new_mock();
+        primary.blocking().write("a", "primary").unwrap();
+        replica.blocking().write("a", "replica").unwrap();
+
+        let mut builder = FailoverBuilder::default();
+        builder
+            .primary(primary)
+            .replica(replica)
+            .cooldown(Duration::from_millis(10));
+        let op = Operator::new(builder).unwrap().finish().blocking();
+
+        primary_handle.fail_temporary(Operation::BlockingRead, 1);
+        assert_eq!(op.read("a").unwrap(), b"replica");
+        assert_eq!(op.read("a").unwrap(), b"replica");
+
+        // Primary rejoins after cooldown.
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(op.read("a").unwrap(), b"primary");
+        assert_eq!(op.read("a").unwrap(), b"primary");
+        assert_eq!(replica_handle.calls_of(Operation::BlockingRead).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_write_primary_only() {
+        let (primary, _) = new_mock();
+        let (replica, replica_handle) = new_mock();
+
+        let mut builder = FailoverBuilder::default();
+        builder.primary(primary.clone()).replica(replica);
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("a", "data").await.unwrap();
+        assert_eq!(primary.read("a").await.unwrap(), b"data");
+        assert_eq!(replica_handle.calls_of(Operation::Write).len(), 0);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let backend = {
+            let mut builder = FailoverBuilder::default();
+            builder
+                .primary(new_mock().0)
+                .replica(new_mock().0)
+                .read_preference(ReadPreference::Nearest);
+            builder.build().unwrap()
+        };
+
+        backend.on_success(0, Duration::from_millis(100));
+        backend.on_success(1, Duration::from_millis(10));
+        assert_eq!(backend.candidates(), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_build_without_primary() {
+        let err = FailoverBuilder::default().build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+}
@@ -0,0 +1,334 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use foundationdb::api::NetworkAutoStop;
+use foundationdb::Database;
+use foundationdb::FdbError;
+use foundationdb::RangeOption;
+use foundationdb::TransactionCommitError;
+use futures::TryStreamExt;
+use once_cell::sync::OnceCell;
+
+use crate::raw::adapters::kv;
+use crate::raw::*;
+use crate::*;
+
+/// FoundationDB limits the value size to 100KB.
+const DEFAULT_CHUNK_SIZE: usize = 100_000;
+
+/// Subspace of the index keys: `0x00 + path` => number of chunks in
+/// big-endian `u32`. Only this subspace will be scanned while listing.
+const INDEX_SUBSPACE: u8 = 0x00;
+/// Subspace of the data keys: `0x01 + path + 0x00 + chunk index` => chunk.
+///
+/// `0x00` will never show up in a valid utf-8 path, so chunks of different
+/// paths will not be mixed up.
+const DATA_SUBSPACE: u8 = 0x01;
+
+/// The fdb network can only be booted once in a process, and it must be
+/// kept running until all databases are dropped.
+static FDB_NETWORK: OnceCell<NetworkAutoStop> = OnceCell::new();
+
+/// [FoundationDB](https://www.foundationdb.org/) service support.
+#[doc = include_str!("docs.md")]
+#[derive(Clone, Default)]
+pub struct FoundationdbBuilder {
+    /// the working directory of the service. Can be "/path/to/dir"
+    ///
+    /// default is "/"
+    root: Option<String>,
+    /// the path to the cluster file of FoundationDB.
+    ///
+    /// default is None, the default cluster file will be used.
+    config_path: Option<String>,
+}
+
+impl Debug for FoundationdbBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+        ds.field("root", &self.root);
+        ds.field("config_path", &self.config_path);
+        ds.finish()
+    }
+}
+
+impl FoundationdbBuilder {
+    /// set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_owned());
+        }
+        self
+    }
+
+    /// Set the path to the cluster file of FoundationDB.
+    ///
+    /// default: the default cluster file of FoundationDB
+    pub fn config_path(&mut self, path: &str) -> &mut Self {
+        if !path.is_empty() {
+            self.config_path = Some(path.to_owned());
+        }
+        self
+    }
+}
+
+impl Builder for FoundationdbBuilder {
+    const SCHEME: Scheme = Scheme::Foundationdb;
+    type Accessor = FoundationdbBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = FoundationdbBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("config_path").map(|v| builder.config_path(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        // Safety: the network is booted only once and never stopped before
+        // the process exits.
+        FDB_NETWORK.get_or_init(|| unsafe { foundationdb::boot() });
+
+        let db = Database::new(self.config_path.as_deref()).map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "open foundationdb failed")
+                .with_context("service", Scheme::Foundationdb)
+                .with_context("config_path", self.config_path.clone().unwrap_or_default())
+                .set_source(e)
+        })?;
+
+        let root = normalize_root(
+            self.root
+                .clone()
+                .unwrap_or_else(|| "/".to_string())
+                .as_str(),
+        );
+
+        Ok(FoundationdbBackend::new(Adapter {
+            db: Arc::new(db),
+            config_path: self.config_path.clone(),
+        })
+        .with_root(&root))
+    }
+}
+
+/// Backend for FoundationDB services.
+pub type FoundationdbBackend = kv::Backend<Adapter>;
+
+#[derive(Clone)]
+pub struct Adapter {
+    db: Arc<Database>,
+    config_path: Option<String>,
+}
+
+impl Debug for Adapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Adapter");
+        ds.field("config_path", &self.config_path);
+        ds.finish()
+    }
+}
+
+#[async_trait]
+impl kv::Adapter for Adapter {
+    fn metadata(&self) -> kv::Metadata {
+        kv::Metadata::new(
+            Scheme::Foundationdb,
+            self.config_path.as_deref().unwrap_or("foundationdb"),
+            Capability {
+                read: true,
+                write: true,
+                list: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let trx = self.db.create_trx().map_err(parse_fdb_error)?;
+
+        let index = match trx
+            .get(&index_key(path), false)
+            .await
+            .map_err(parse_fdb_error)?
+        {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let count = decode_chunks_count(&index).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "invalid chunks index of foundationdb",
+            )
+            .with_context("path", path)
+        })?;
+
+        let (begin, end) = data_range(path);
+        let mut chunks = Box::pin(trx.get_ranges_keyvalues(RangeOption::from((begin, end)), false));
+
+        let mut bs = Vec::new();
+        let mut read = 0;
+        while let Some(kv) = chunks.try_next().await.map_err(parse_fdb_error)? {
+            bs.extend_from_slice(kv.value());
+            read += 1;
+        }
+
+        if read != count {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "chunks of foundationdb are corrupted",
+            )
+            .with_context("path", path)
+            .with_context("expected", count.to_string())
+            .with_context("actual", read.to_string()));
+        }
+
+        Ok(Some(bs))
+    }
+
+    async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
+        let trx = self.db.create_trx().map_err(parse_fdb_error)?;
+
+        // Remove chunks of the old value which could have more chunks.
+        let (begin, end) = data_range(path);
+        trx.clear_range(&begin, &end);
+
+        let mut count = 0;
+        for (idx, chunk) in value.chunks(DEFAULT_CHUNK_SIZE).enumerate() {
+            trx.set(&data_key(path, idx as u32), chunk);
+            count += 1;
+        }
+        trx.set(&index_key(path), &encode_chunks_count(count));
+
+        trx.commit().await.map_err(parse_fdb_commit_error)?;
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let trx = self.db.create_trx().map_err(parse_fdb_error)?;
+
+        let (begin, end) = data_range(path);
+        trx.clear_range(&begin, &end);
+        trx.clear(&index_key(path));
+
+        trx.commit().await.map_err(parse_fdb_commit_error)?;
+        Ok(())
+    }
+
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        let trx = self.db.create_trx().map_err(parse_fdb_error)?;
+
+        let begin = index_key(path);
+        let mut end = begin.clone();
+        // All keys of the index subspace with this prefix are smaller than
+        // `prefix + 0xff` since `0xff` never shows up in a valid utf-8 path.
+        end.push(0xff);
+
+        let mut kvs = Box::pin(trx.get_ranges_keyvalues(RangeOption::from((begin, end)), true));
+
+        let mut res = Vec::new();
+        while let Some(kv) = kvs.try_next().await.map_err(parse_fdb_error)? {
+            let key = String::from_utf8(kv.key()[1..].to_vec()).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "store key is not valid utf-8 string")
+                    .set_source(err)
+            })?;
+            res.push(key);
+        }
+
+        Ok(res)
+    }
+}
+
+fn index_key(path: &str) -> Vec<u8> {
+    let mut key = Vec::with_capacity(1 + path.len());
+    key.push(INDEX_SUBSPACE);
+    key.extend_from_slice(path.as_bytes());
+    key
+}
+
+fn data_key(path: &str, idx: u32) -> Vec<u8> {
+    let mut key = Vec::with_capacity(1 + path.len() + 1 + 4);
+    key.push(DATA_SUBSPACE);
+    key.extend_from_slice(path.as_bytes());
+    key.push(0x00);
+    key.extend_from_slice(&idx.to_be_bytes());
+    key
+}
+
+/// Returns the range `[begin, end)` which contains all chunks of this path.
+fn data_range(path: &str) -> (Vec<u8>, Vec<u8>) {
+    let mut begin = Vec::with_capacity(1 + path.len() + 1);
+    begin.push(DATA_SUBSPACE);
+    begin.extend_from_slice(path.as_bytes());
+
+    let mut end = begin.clone();
+    begin.push(0x00);
+    end.push(0x01);
+    (begin, end)
+}
+
+fn encode_chunks_count(count: u32) -> [u8; 4] {
+    count.to_be_bytes()
+}
+
+fn decode_chunks_count(bs: &[u8]) -> Option<usize> {
+    let bs: [u8; 4] = bs.try_into().ok()?;
+    Some(u32::from_be_bytes(bs) as usize)
+}
+
+fn parse_fdb_error(e: FdbError) -> Error {
+    Error::new(ErrorKind::Unexpected, "error from foundationdb").set_source(e)
+}
+
+fn parse_fdb_commit_error(e: TransactionCommitError) -> Error {
+    parse_fdb_error(FdbError::from(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_key_in_range() {
+        let (begin, end) = data_range("abc");
+
+        for idx in [0, 1, 255, 256, u32::MAX] {
+            let key = data_key("abc", idx);
+            assert!(begin <= key && key < end, "chunk {idx} must be in range");
+        }
+
+        // Chunks of path with the same prefix must not be in range.
+        let key = data_key("abcd", 0);
+        assert!(!(begin <= key && key < end));
+        let key = data_key("ab", 0);
+        assert!(!(begin <= key && key < end));
+    }
+
+    #[test]
+    fn test_chunks_count() {
+        assert_eq!(decode_chunks_count(&encode_chunks_count(3)), Some(3));
+        assert_eq!(decode_chunks_count(b"abc"), None);
+    }
+}
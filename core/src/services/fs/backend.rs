@@ -21,16 +21,30 @@ use std::io::SeekFrom;
 use std::path::Path;
 use std::path::PathBuf;
 
+#[cfg(feature = "services-fs")]
 use async_compat::Compat;
 use async_trait::async_trait;
+use bytes::Bytes;
 use chrono::DateTime;
 use log::debug;
 use uuid::Uuid;
 
+#[cfg(feature = "services-fs")]
 use super::appender::FsAppender;
+use super::copy::copy_file;
 use super::error::parse_io_error;
+use super::etag::EtagConfig;
+use super::etag::EtagMode;
+#[cfg(feature = "services-fs")]
+use super::file::FsFile;
 use super::pager::FsPager;
+#[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+use super::uring::Uring;
+#[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+use super::uring::UringFile;
 use super::writer::FsWriter;
+use super::xattr::get_user_metadata;
+use super::xattr::set_user_metadata;
 use crate::raw::*;
 use crate::*;
 
@@ -41,6 +55,11 @@ pub struct FsBuilder {
     root: Option<PathBuf>,
     atomic_write_dir: Option<PathBuf>,
     enable_path_check: bool,
+    symlink_mode: Option<String>,
+    etag_mode: Option<String>,
+    enable_etag_cache: bool,
+    #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+    enable_io_uring: bool,
 }
 
 impl FsBuilder {
@@ -77,6 +96,77 @@ impl FsBuilder {
 
         self
     }
+
+    /// Set how symlinks are treated during stat, list and read.
+    ///
+    /// Available values:
+    ///
+    /// - `follow`: Follow symlinks and return the metadata of their
+    ///   targets, this is the default behavior.
+    /// - `preserve`: Return symlinks as [`EntryMode::Unknown`] entries with
+    ///   [`Metadata::symlink_target`] set in stat and list. Read will
+    ///   still follow symlinks.
+    /// - `error`: Return an error while visiting symlinks.
+    ///
+    /// Only the last component of the path will be checked, symlinks in
+    /// parent dirs are always followed.
+    pub fn symlink_mode(&mut self, mode: &str) -> &mut Self {
+        if !mode.is_empty() {
+            self.symlink_mode = Some(mode.to_string());
+        }
+
+        self
+    }
+
+    /// Set how etag of files are computed in stat and list.
+    ///
+    /// Available values:
+    ///
+    /// - `none`: Don't return etag, this is the default behavior.
+    /// - `fast`: Build etag from last modified time and size of files
+    ///   without reading their content.
+    /// - `content`: Build etag from md5 of file content, which requires
+    ///   reading the whole file.
+    pub fn etag_mode(&mut self, mode: &str) -> &mut Self {
+        if !mode.is_empty() {
+            self.etag_mode = Some(mode.to_string());
+        }
+
+        self
+    }
+
+    /// Cache content hash of files in xattr for etag mode `content`.
+    ///
+    /// Cached hash will be reused until the last modified time or size of
+    /// the file changed, so unchanged files don't need to be read again.
+    ///
+    /// # Notes
+    ///
+    /// This option only takes effect on linux, and will be ignored if
+    /// the underlying fs doesn't support xattr.
+    pub fn enable_etag_cache(&mut self) -> &mut Self {
+        self.enable_etag_cache = true;
+
+        self
+    }
+
+    /// Serve async read and write via io_uring instead of tokio's blocking
+    /// thread pool.
+    ///
+    /// All files will share one io_uring instance which is driven by a
+    /// dedicated thread.
+    ///
+    /// # Notes
+    ///
+    /// This option is only available on linux with feature
+    /// `services-fs-io-uring` enabled. Build will fail if io_uring is not
+    /// supported by current kernel.
+    #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+    pub fn enable_io_uring(&mut self) -> &mut Self {
+        self.enable_io_uring = true;
+
+        self
+    }
 }
 
 impl Builder for FsBuilder {
@@ -89,6 +179,15 @@ impl Builder for FsBuilder {
         map.get("root").map(|v| builder.root(v));
         map.get("atomic_write_dir")
             .map(|v| builder.atomic_write_dir(v));
+        map.get("symlink_mode").map(|v| builder.symlink_mode(v));
+        map.get("etag_mode").map(|v| builder.etag_mode(v));
+        map.get("enable_etag_cache")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.enable_etag_cache());
+        #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+        map.get("enable_io_uring")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.enable_io_uring());
 
         builder
     }
@@ -105,6 +204,24 @@ impl Builder for FsBuilder {
         }?;
         debug!("backend use root {}", root.to_string_lossy());
 
+        let symlink_mode = match self.symlink_mode.take() {
+            Some(v) => SymlinkMode::parse(&v).map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Fs)
+            })?,
+            None => SymlinkMode::Follow,
+        };
+        let etag = EtagConfig {
+            mode: match self.etag_mode.take() {
+                Some(v) => EtagMode::parse(&v).map_err(|err| {
+                    err.with_operation("Builder::build")
+                        .with_context("service", Scheme::Fs)
+                })?,
+                None => EtagMode::None,
+            },
+            enable_cache: self.enable_etag_cache,
+        };
+
         // If root dir is not exist, we must create it.
         if let Err(e) = std::fs::metadata(&root) {
             if e.kind() == std::io::ErrorKind::NotFound {
@@ -161,21 +278,161 @@ impl Builder for FsBuilder {
             })
             .unwrap_or(Ok(None))?;
 
+        #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+        let uring = if self.enable_io_uring {
+            let uring = Uring::new().map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "setup io_uring failed")
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::Fs)
+                    .set_source(e)
+            })?;
+            Some(std::sync::Arc::new(uring))
+        } else {
+            None
+        };
+
         debug!("backend build finished: {:?}", &self);
         Ok(FsBackend {
             root,
             atomic_write_dir,
             enable_path_check: self.enable_path_check,
+            symlink_mode,
+            etag,
+            #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+            uring,
         })
     }
 }
 
+/// SymlinkMode decides how symlinks are treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymlinkMode {
+    /// Follow symlinks to their targets.
+    Follow,
+    /// Report symlinks as entries with their targets.
+    Preserve,
+    /// Return an error while visiting symlinks.
+    Error,
+}
+
+impl SymlinkMode {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "follow" => Ok(SymlinkMode::Follow),
+            "preserve" => Ok(SymlinkMode::Preserve),
+            "error" => Ok(SymlinkMode::Error),
+            _ => Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "symlink_mode must be one of follow, preserve and error",
+            )
+            .with_context("symlink_mode", s)),
+        }
+    }
+}
+
+/// Build the error returned while visiting symlinks in [`SymlinkMode::Error`].
+pub fn new_symlink_error(path: &Path) -> Error {
+    Error::new(ErrorKind::Unsupported, "symlink is not allowed")
+        .with_context("symlink_mode", "error")
+        .with_context("path", path.to_string_lossy())
+}
+
+/// Fill POSIX permission and ownership of given fs metadata.
+#[cfg(unix)]
+fn fill_permission(m: Metadata, meta: &std::fs::Metadata) -> Metadata {
+    use std::os::unix::fs::MetadataExt;
+
+    m.with_permission(meta.mode() & 0o7777)
+        .with_uid(meta.uid())
+        .with_gid(meta.gid())
+}
+
+#[cfg(not(unix))]
+fn fill_permission(m: Metadata, _: &std::fs::Metadata) -> Metadata {
+    m
+}
+
+/// Build the permissions to set while writing.
+#[cfg(unix)]
+fn build_permissions(permission: u32) -> Result<std::fs::Permissions> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Ok(std::fs::Permissions::from_mode(permission))
+}
+
+#[cfg(not(unix))]
+fn build_permissions(_: u32) -> Result<std::fs::Permissions> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "write with permission is only supported on unix",
+    ))
+}
+
+/// Copy file with its user metadata, user metadata will be replaced if
+/// given.
+fn copy_file_with_user_metadata(
+    from: &Path,
+    to: &Path,
+    user_metadata: Option<&HashMap<String, String>>,
+) -> Result<()> {
+    copy_file(from, to).map_err(parse_io_error)?;
+
+    match user_metadata {
+        Some(v) => set_user_metadata(to, v),
+        None => match get_user_metadata(from)? {
+            Some(v) => set_user_metadata(to, &v),
+            None => Ok(()),
+        },
+    }
+}
+
+/// Write bytes at given offset of the file without truncating it, the
+/// file will be created if not exist.
+fn write_file_at(p: &Path, offset: u64, bs: &[u8]) -> Result<()> {
+    use std::io::Seek;
+    use std::io::Write;
+
+    let mut f = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(p)
+        .map_err(parse_io_error)?;
+    f.seek(SeekFrom::Start(offset)).map_err(parse_io_error)?;
+    f.write_all(bs).map_err(parse_io_error)
+}
+
+/// Truncate or extend the existing file to given size.
+fn truncate_file(p: &Path, size: u64) -> Result<()> {
+    let f = std::fs::OpenOptions::new()
+        .write(true)
+        .open(p)
+        .map_err(parse_io_error)?;
+    f.set_len(size).map_err(parse_io_error)
+}
+
+/// Run blocking fs operations in tokio's blocking thread pool.
+#[cfg(feature = "services-fs")]
+pub(super) async fn run_blocking<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await.map_err(|err| {
+        Error::new(ErrorKind::Unexpected, "blocking task failed to complete").set_source(err)
+    })?
+}
+
 /// Backend is used to serve `Accessor` support for posix alike fs.
 #[derive(Debug, Clone)]
 pub struct FsBackend {
     root: PathBuf,
     atomic_write_dir: Option<PathBuf>,
     enable_path_check: bool,
+    symlink_mode: SymlinkMode,
+    etag: EtagConfig,
+    #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+    uring: Option<std::sync::Arc<Uring>>,
 }
 
 #[inline]
@@ -187,6 +444,23 @@ fn tmp_file_of(path: &str) -> String {
 }
 
 impl FsBackend {
+    /// Wrap the opened file into [`FsFile`], files will be served by
+    /// io_uring if it's enabled.
+    #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+    #[cfg(feature = "services-fs")]
+    async fn wrap_file(&self, f: tokio::fs::File) -> FsFile {
+        match &self.uring {
+            Some(uring) => FsFile::Uring(UringFile::new(uring.clone(), f.into_std().await)),
+            None => FsFile::Tokio(f),
+        }
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "services-fs-io-uring")))]
+    #[cfg(feature = "services-fs")]
+    async fn wrap_file(&self, f: tokio::fs::File) -> FsFile {
+        FsFile::Tokio(f)
+    }
+
     // Synchronously build write path and ensure the parent dirs created
     fn blocking_ensure_write_abs_path(parent: &Path, path: &str) -> Result<PathBuf> {
         let p = parent.join(path);
@@ -214,6 +488,7 @@ impl FsBackend {
     }
 
     // Build write path and ensure the parent dirs created
+    #[cfg(feature = "services-fs")]
     async fn ensure_write_abs_path(parent: &Path, path: &str) -> Result<PathBuf> {
         let p = parent.join(path);
 
@@ -244,12 +519,24 @@ impl FsBackend {
 
 #[async_trait]
 impl Accessor for FsBackend {
-    type Reader = oio::into_reader::FdReader<Compat<tokio::fs::File>>;
+    #[cfg(feature = "services-fs")]
+    type Reader = oio::into_reader::FdReader<Compat<FsFile>>;
+    #[cfg(not(feature = "services-fs"))]
+    type Reader = ();
     type BlockingReader = oio::into_blocking_reader::FdReader<std::fs::File>;
-    type Writer = FsWriter<tokio::fs::File>;
+    #[cfg(feature = "services-fs")]
+    type Writer = FsWriter<FsFile>;
+    #[cfg(not(feature = "services-fs"))]
+    type Writer = ();
     type BlockingWriter = FsWriter<std::fs::File>;
+    #[cfg(feature = "services-fs")]
     type Appender = FsAppender<tokio::fs::File>;
+    #[cfg(not(feature = "services-fs"))]
+    type Appender = ();
+    #[cfg(feature = "services-fs")]
     type Pager = Option<FsPager<tokio::fs::ReadDir>>;
+    #[cfg(not(feature = "services-fs"))]
+    type Pager = ();
     type BlockingPager = Option<FsPager<std::fs::ReadDir>>;
 
     fn info(&self) -> AccessorInfo {
@@ -258,6 +545,8 @@ impl Accessor for FsBackend {
             .set_root(&self.root.to_string_lossy())
             .set_capability(Capability {
                 stat: true,
+                stat_has_permission: cfg!(unix),
+                stat_has_user_metadata: cfg!(target_os = "linux"),
 
                 read: true,
                 read_can_seek: true,
@@ -265,16 +554,21 @@ impl Accessor for FsBackend {
 
                 write: true,
                 write_without_content_length: true,
+                write_with_permission: cfg!(unix),
+                write_with_user_metadata: cfg!(target_os = "linux"),
                 create_dir: true,
                 delete: true,
 
-                append: true,
+                append: cfg!(feature = "services-fs"),
 
                 list: true,
                 list_with_delimiter_slash: true,
 
                 copy: true,
+                copy_with_user_metadata: cfg!(target_os = "linux"),
                 rename: true,
+                write_at: true,
+                truncate: true,
                 blocking: true,
 
                 ..Default::default()
@@ -283,6 +577,7 @@ impl Accessor for FsBackend {
         am
     }
 
+    #[cfg(feature = "services-fs")]
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let p = self.root.join(path.trim_end_matches('/'));
 
@@ -302,11 +597,21 @@ impl Accessor for FsBackend {
     /// - open file first, and than use `seek`. (100ns)
     ///
     /// Benchmark could be found [here](https://gist.github.com/Xuanwo/48f9cfbc3022ea5f865388bb62e1a70f)
+    #[cfg(feature = "services-fs")]
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         use oio::ReadExt;
 
         let p = self.root.join(path.trim_end_matches('/'));
 
+        if self.symlink_mode == SymlinkMode::Error {
+            let meta = tokio::fs::symlink_metadata(&p)
+                .await
+                .map_err(parse_io_error)?;
+            if meta.file_type().is_symlink() {
+                return Err(new_symlink_error(&p));
+            }
+        }
+
         let mut f = tokio::fs::OpenOptions::new()
             .read(true)
             .open(&p)
@@ -336,7 +641,7 @@ impl Accessor for FsBackend {
             f.seek(SeekFrom::End(0)).await.map_err(parse_io_error)?
         };
 
-        let f = Compat::new(f);
+        let f = Compat::new(self.wrap_file(f).await);
 
         let br = args.range();
         let (start, end) = match (br.offset(), br.size()) {
@@ -365,7 +670,8 @@ impl Accessor for FsBackend {
         Ok((RpRead::new(end - start), r))
     }
 
-    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+    #[cfg(feature = "services-fs")]
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         let (target_path, tmp_path) = if let Some(atomic_write_dir) = &self.atomic_write_dir {
             let target_path = Self::ensure_write_abs_path(&self.root, path).await?;
             let tmp_path =
@@ -385,9 +691,24 @@ impl Accessor for FsBackend {
             .await
             .map_err(parse_io_error)?;
 
+        // Set permission explicitly since the mode of open is affected by
+        // umask and will not be applied to existing files.
+        if let Some(permission) = args.permission() {
+            f.set_permissions(build_permissions(permission)?)
+                .await
+                .map_err(parse_io_error)?;
+        }
+        if let Some(user_metadata) = args.user_metadata() {
+            let p = tmp_path.as_ref().unwrap_or(&target_path).clone();
+            let user_metadata = user_metadata.clone();
+            run_blocking(move || set_user_metadata(&p, &user_metadata)).await?;
+        }
+        let f = self.wrap_file(f).await;
+
         Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
     }
 
+    #[cfg(feature = "services-fs")]
     async fn append(&self, path: &str, _: OpAppend) -> Result<(RpAppend, Self::Appender)> {
         let path = Self::ensure_write_abs_path(&self.root, path).await?;
 
@@ -402,7 +723,8 @@ impl Accessor for FsBackend {
         Ok((RpAppend::new(), FsAppender::new(f)))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
+    #[cfg(feature = "services-fs")]
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
         let from = self.root.join(from.trim_end_matches('/'));
 
         // try to get the metadata of the source file to ensure it exists
@@ -410,11 +732,13 @@ impl Accessor for FsBackend {
 
         let to = Self::ensure_write_abs_path(&self.root, to.trim_end_matches('/')).await?;
 
-        tokio::fs::copy(from, to).await.map_err(parse_io_error)?;
+        run_blocking(move || copy_file_with_user_metadata(&from, &to, args.user_metadata()))
+            .await?;
 
         Ok(RpCopy::default())
     }
 
+    #[cfg(feature = "services-fs")]
     async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
         let from = self.root.join(from.trim_end_matches('/'));
 
@@ -428,10 +752,31 @@ impl Accessor for FsBackend {
         Ok(RpRename::default())
     }
 
+    #[cfg(feature = "services-fs")]
     async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let meta = tokio::fs::metadata(&p).await.map_err(parse_io_error)?;
+        let meta = match self.symlink_mode {
+            SymlinkMode::Follow => tokio::fs::metadata(&p).await,
+            _ => tokio::fs::symlink_metadata(&p).await,
+        }
+        .map_err(parse_io_error)?;
+
+        if meta.file_type().is_symlink() {
+            if self.symlink_mode == SymlinkMode::Error {
+                return Err(new_symlink_error(&p));
+            }
+
+            let target = tokio::fs::read_link(&p).await.map_err(parse_io_error)?;
+            let m = Metadata::new(EntryMode::Unknown)
+                .with_symlink_target(target.to_string_lossy().to_string())
+                .with_last_modified(
+                    meta.modified()
+                        .map(DateTime::from)
+                        .map_err(parse_io_error)?,
+                );
+            return Ok(RpStat::new(fill_permission(m, &meta)));
+        }
 
         if self.enable_path_check && meta.is_dir() != path.ends_with('/') {
             return Err(Error::new(
@@ -454,10 +799,21 @@ impl Accessor for FsBackend {
                     .map(DateTime::from)
                     .map_err(parse_io_error)?,
             );
+        let mut m = fill_permission(m, &meta);
+        let etag = self.etag;
+        let (user_metadata, etag) =
+            run_blocking(move || Ok((get_user_metadata(&p)?, etag.compute(&p, &meta)?))).await?;
+        if let Some(user_metadata) = user_metadata {
+            m.set_user_metadata(user_metadata);
+        }
+        if let Some(etag) = etag {
+            m.set_etag(&etag);
+        }
 
         Ok(RpStat::new(m))
     }
 
+    #[cfg(feature = "services-fs")]
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
         let p = self.root.join(path.trim_end_matches('/'));
 
@@ -478,6 +834,7 @@ impl Accessor for FsBackend {
         }
     }
 
+    #[cfg(feature = "services-fs")]
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
         let p = self.root.join(path.trim_end_matches('/'));
 
@@ -492,11 +849,29 @@ impl Accessor for FsBackend {
             }
         };
 
-        let rd = FsPager::new(&self.root, f, args.limit());
+        let rd = FsPager::new(&self.root, f, args.limit(), self.symlink_mode, self.etag);
 
         Ok((RpList::default(), Some(rd)))
     }
 
+    #[cfg(feature = "services-fs")]
+    async fn write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        let p = Self::ensure_write_abs_path(&self.root, path).await?;
+
+        run_blocking(move || write_file_at(&p, args.offset(), &bs)).await?;
+
+        Ok(RpWriteAt::default())
+    }
+
+    #[cfg(feature = "services-fs")]
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let p = self.root.join(path);
+
+        run_blocking(move || truncate_file(&p, args.size())).await?;
+
+        Ok(RpTruncate::default())
+    }
+
     fn blocking_create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let p = self.root.join(path.trim_end_matches('/'));
 
@@ -510,6 +885,13 @@ impl Accessor for FsBackend {
 
         let p = self.root.join(path.trim_end_matches('/'));
 
+        if self.symlink_mode == SymlinkMode::Error {
+            let meta = std::fs::symlink_metadata(&p).map_err(parse_io_error)?;
+            if meta.file_type().is_symlink() {
+                return Err(new_symlink_error(&p));
+            }
+        }
+
         let mut f = std::fs::OpenOptions::new()
             .read(true)
             .open(p)
@@ -565,7 +947,7 @@ impl Accessor for FsBackend {
         Ok((RpRead::new(end - start), r))
     }
 
-    fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
         let (target_path, tmp_path) = if let Some(atomic_write_dir) = &self.atomic_write_dir {
             let target_path = Self::blocking_ensure_write_abs_path(&self.root, path)?;
             let tmp_path =
@@ -584,10 +966,20 @@ impl Accessor for FsBackend {
             .open(tmp_path.as_ref().unwrap_or(&target_path))
             .map_err(parse_io_error)?;
 
+        // Set permission explicitly since the mode of open is affected by
+        // umask and will not be applied to existing files.
+        if let Some(permission) = args.permission() {
+            f.set_permissions(build_permissions(permission)?)
+                .map_err(parse_io_error)?;
+        }
+        if let Some(user_metadata) = args.user_metadata() {
+            set_user_metadata(tmp_path.as_ref().unwrap_or(&target_path), user_metadata)?;
+        }
+
         Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
     }
 
-    fn blocking_copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
         let from = self.root.join(from.trim_end_matches('/'));
 
         // try to get the metadata of the source file to ensure it exists
@@ -595,7 +987,7 @@ impl Accessor for FsBackend {
 
         let to = Self::blocking_ensure_write_abs_path(&self.root, to.trim_end_matches('/'))?;
 
-        std::fs::copy(from, to).map_err(parse_io_error)?;
+        copy_file_with_user_metadata(&from, &to, args.user_metadata())?;
 
         Ok(RpCopy::default())
     }
@@ -616,7 +1008,27 @@ impl Accessor for FsBackend {
     fn blocking_stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let meta = std::fs::metadata(p).map_err(parse_io_error)?;
+        let meta = match self.symlink_mode {
+            SymlinkMode::Follow => std::fs::metadata(&p),
+            _ => std::fs::symlink_metadata(&p),
+        }
+        .map_err(parse_io_error)?;
+
+        if meta.file_type().is_symlink() {
+            if self.symlink_mode == SymlinkMode::Error {
+                return Err(new_symlink_error(&p));
+            }
+
+            let target = std::fs::read_link(&p).map_err(parse_io_error)?;
+            let m = Metadata::new(EntryMode::Unknown)
+                .with_symlink_target(target.to_string_lossy().to_string())
+                .with_last_modified(
+                    meta.modified()
+                        .map(DateTime::from)
+                        .map_err(parse_io_error)?,
+                );
+            return Ok(RpStat::new(fill_permission(m, &meta)));
+        }
 
         if self.enable_path_check && meta.is_dir() != path.ends_with('/') {
             return Err(Error::new(
@@ -639,6 +1051,13 @@ impl Accessor for FsBackend {
                     .map(DateTime::from)
                     .map_err(parse_io_error)?,
             );
+        let mut m = fill_permission(m, &meta);
+        if let Some(user_metadata) = get_user_metadata(&p)? {
+            m.set_user_metadata(user_metadata);
+        }
+        if let Some(etag) = self.etag.compute(&p, &meta)? {
+            m.set_etag(&etag);
+        }
 
         Ok(RpStat::new(m))
     }
@@ -677,10 +1096,26 @@ impl Accessor for FsBackend {
             }
         };
 
-        let rd = FsPager::new(&self.root, f, args.limit());
+        let rd = FsPager::new(&self.root, f, args.limit(), self.symlink_mode, self.etag);
 
         Ok((RpList::default(), Some(rd)))
     }
+
+    fn blocking_write_at(&self, path: &str, args: OpWriteAt, bs: Bytes) -> Result<RpWriteAt> {
+        let p = Self::blocking_ensure_write_abs_path(&self.root, path)?;
+
+        write_file_at(&p, args.offset(), &bs)?;
+
+        Ok(RpWriteAt::default())
+    }
+
+    fn blocking_truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let p = self.root.join(path);
+
+        truncate_file(&p, args.size())?;
+
+        Ok(RpTruncate::default())
+    }
 }
 
 #[cfg(test)]
@@ -701,4 +1136,108 @@ mod tests {
             assert!(tmp_file.starts_with(expected_prefix));
         }
     }
+
+    #[test]
+    fn test_parse_symlink_mode() {
+        assert_eq!(SymlinkMode::parse("follow").unwrap(), SymlinkMode::Follow);
+        assert_eq!(
+            SymlinkMode::parse("Preserve").unwrap(),
+            SymlinkMode::Preserve
+        );
+        assert_eq!(SymlinkMode::parse("error").unwrap(), SymlinkMode::Error);
+        assert_eq!(
+            SymlinkMode::parse("ignore").unwrap_err().kind(),
+            ErrorKind::ConfigInvalid
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_mode() {
+        use oio::BlockingPage;
+
+        let root = std::env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("file"), "hello").unwrap();
+        std::os::unix::fs::symlink("file", root.join("link")).unwrap();
+
+        let build = |mode: &str| {
+            let mut builder = FsBuilder::default();
+            builder.root(&root.to_string_lossy()).symlink_mode(mode);
+            builder.build().unwrap()
+        };
+        let list = |backend: &FsBackend| {
+            let (_, pager) = backend.blocking_list("/", OpList::new()).unwrap();
+            let mut entries = pager.unwrap().next().map(|v| v.unwrap_or_default());
+            if let Ok(entries) = &mut entries {
+                entries.sort_by(|a, b| a.path().cmp(b.path()));
+            }
+            entries
+        };
+
+        let backend = build("follow");
+        let meta = backend
+            .blocking_stat("link", OpStat::new())
+            .unwrap()
+            .into_metadata();
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), 5);
+        let entries = list(&backend).unwrap();
+        assert_eq!(entries[1].path(), "link");
+        assert_eq!(entries[1].mode(), EntryMode::FILE);
+
+        let backend = build("preserve");
+        let meta = backend
+            .blocking_stat("link", OpStat::new())
+            .unwrap()
+            .into_metadata();
+        assert_eq!(meta.mode(), EntryMode::Unknown);
+        assert_eq!(meta.symlink_target(), Some("file"));
+        let entry = list(&backend).unwrap().remove(1).into_entry();
+        let meta = entry.metadata().as_ref().unwrap();
+        assert_eq!(meta.mode(), EntryMode::Unknown);
+        assert_eq!(meta.symlink_target(), Some("file"));
+
+        let backend = build("error");
+        let err = backend.blocking_stat("link", OpStat::new()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = backend
+            .blocking_read("link", OpRead::new())
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert_eq!(list(&backend).unwrap_err().kind(), ErrorKind::Unsupported);
+        assert!(backend.blocking_stat("file", OpStat::new()).is_ok());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_etag_mode() {
+        use oio::BlockingPage;
+
+        let root = std::env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("dir")).unwrap();
+        std::fs::write(root.join("file"), "hello").unwrap();
+
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy()).etag_mode("content");
+        let backend = builder.build().unwrap();
+
+        let meta = backend
+            .blocking_stat("file", OpStat::new())
+            .unwrap()
+            .into_metadata();
+        assert_eq!(meta.etag(), Some("\"5d41402abc4b2a76b9719d911017c592\""));
+
+        let (_, pager) = backend.blocking_list("/", OpList::new()).unwrap();
+        let mut entries = pager.unwrap().next().unwrap().unwrap();
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+        assert_eq!(entries[0].path(), "dir/");
+        let meta = entries.remove(1).into_entry().metadata().clone().unwrap();
+        assert_eq!(meta.content_length(), 5);
+        assert_eq!(meta.etag(), Some("\"5d41402abc4b2a76b9719d911017c592\""));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
 }
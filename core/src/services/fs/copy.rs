@@ -0,0 +1,154 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::path::Path;
+
+/// Copy the content and permissions of file `from` to `to`, returns the
+/// copied size.
+///
+/// On linux, we will try reflink via `FICLONE` first which makes copy on
+/// btrfs and xfs nearly instant, and then `copy_file_range` which copies
+/// data inside kernel. Read and write will be used if both of them are
+/// not supported by the underlying fs.
+pub fn copy_file(from: &Path, to: &Path) -> io::Result<u64> {
+    let mut src = File::open(from)?;
+    let meta = src.metadata()?;
+    if !meta.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the source path is not an existing regular file",
+        ));
+    }
+
+    let mut dst = OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(to)?;
+    dst.set_permissions(meta.permissions())?;
+
+    #[cfg(target_os = "linux")]
+    {
+        if linux::ficlone(&src, &dst).is_ok() {
+            return Ok(meta.len());
+        }
+        if let Some(n) = linux::copy_file_range(&src, &dst)? {
+            return Ok(n);
+        }
+    }
+
+    io::copy(&mut src, &mut dst)
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+    use std::ptr;
+
+    /// Share the extents of `src` with `dst` via `FICLONE`.
+    pub fn ficlone(src: &File, dst: &File) -> io::Result<()> {
+        // SAFETY: both fds are valid during the call.
+        let ret = unsafe { libc::ioctl(dst.as_raw_fd(), libc::FICLONE, src.as_raw_fd()) };
+        if ret == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Copy all content from `src` to `dst` via `copy_file_range`.
+    ///
+    /// Returns `None` if nothing has been copied and callers should fall
+    /// back to read and write.
+    pub fn copy_file_range(src: &File, dst: &File) -> io::Result<Option<u64>> {
+        let mut written = 0u64;
+
+        loop {
+            // SAFETY: both fds are valid during the call, and null offsets
+            // mean the file offsets will be used and updated.
+            let ret = unsafe {
+                libc::copy_file_range(
+                    src.as_raw_fd(),
+                    ptr::null_mut(),
+                    dst.as_raw_fd(),
+                    ptr::null_mut(),
+                    // Copy at most 1 GiB per call to avoid overflow on 32-bit.
+                    1 << 30,
+                    0,
+                )
+            };
+
+            match ret {
+                -1 => {
+                    let err = io::Error::last_os_error();
+                    // Fall back only if nothing has been copied, otherwise
+                    // the file offsets have been changed.
+                    if written == 0 && is_fallback_error(&err) {
+                        return Ok(None);
+                    }
+                    return Err(err);
+                }
+                // Some special files like procfs report zero length, let
+                // read and write handle them.
+                0 if written == 0 => return Ok(None),
+                0 => return Ok(Some(written)),
+                n => written += n as u64,
+            }
+        }
+    }
+
+    fn is_fallback_error(err: &io::Error) -> bool {
+        matches!(
+            err.raw_os_error(),
+            Some(libc::ENOSYS)
+                | Some(libc::EXDEV)
+                | Some(libc::EOPNOTSUPP)
+                | Some(libc::EINVAL)
+                | Some(libc::EPERM)
+                | Some(libc::EBADF)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_file() {
+        let dir = std::env::temp_dir().join(format!("opendal-copy-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create dir must succeed");
+
+        for size in [0, 1, 4 * 1024 * 1024 + 3] {
+            let content: Vec<u8> = (0..size).map(|v| (v % 251) as u8).collect();
+            let from = dir.join(format!("from-{size}"));
+            let to = dir.join(format!("to-{size}"));
+            std::fs::write(&from, &content).expect("write must succeed");
+            // Existing content must be truncated.
+            std::fs::write(&to, vec![1; size + 10]).expect("write must succeed");
+
+            let n = copy_file(&from, &to).expect("copy must succeed");
+            assert_eq!(n, size as u64);
+            assert_eq!(std::fs::read(&to).expect("read must succeed"), content);
+        }
+
+        std::fs::remove_dir_all(&dir).expect("remove dir must succeed");
+    }
+}
@@ -0,0 +1,171 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Etag support for fs.
+//!
+//! Fs doesn't store etag of files, so we compute them from metadata or
+//! content of files while serving `stat` and `list`.
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use md5::Digest;
+use md5::Md5;
+
+use super::error::parse_io_error;
+use super::xattr::get_etag_cache;
+use super::xattr::set_etag_cache;
+use crate::*;
+
+/// EtagMode decides how etag of files will be computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EtagMode {
+    /// Don't return etag.
+    None,
+    /// Build etag from last modified time and size, no content will be read.
+    Fast,
+    /// Build etag from md5 of file content.
+    Content,
+}
+
+impl EtagMode {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(EtagMode::None),
+            "fast" => Ok(EtagMode::Fast),
+            "content" => Ok(EtagMode::Content),
+            _ => Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "etag_mode must be one of none, fast and content",
+            )
+            .with_context("etag_mode", s)),
+        }
+    }
+}
+
+/// EtagConfig carries all options to compute etag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EtagConfig {
+    pub mode: EtagMode,
+    /// Cache content hash in xattr so that unchanged files don't need to
+    /// be read again.
+    pub enable_cache: bool,
+}
+
+impl EtagConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.mode != EtagMode::None
+    }
+
+    /// Compute etag of the file on `path` with its metadata.
+    ///
+    /// Returns `None` if etag is disabled or `path` is not a file.
+    pub fn compute(&self, path: &Path, meta: &std::fs::Metadata) -> Result<Option<String>> {
+        if !self.is_enabled() || !meta.is_file() {
+            return Ok(None);
+        }
+
+        let mtime = meta
+            .modified()
+            .map_err(parse_io_error)?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        // Content won't be changed if both mtime and size are the same.
+        let fingerprint = format!("{:x}-{:x}", mtime, meta.len());
+
+        if self.mode == EtagMode::Fast {
+            return Ok(Some(format!("\"{fingerprint}\"")));
+        }
+
+        if self.enable_cache {
+            if let Some(v) = get_etag_cache(path)? {
+                if let Some((fp, hash)) = v.split_once(':') {
+                    if fp == fingerprint {
+                        return Ok(Some(format!("\"{hash}\"")));
+                    }
+                }
+            }
+        }
+
+        let mut f = File::open(path).map_err(parse_io_error)?;
+        let mut hasher = Md5::new();
+        io::copy(&mut f, &mut hasher).map_err(parse_io_error)?;
+        let hash = format!("{:x}", hasher.finalize());
+
+        if self.enable_cache {
+            // Cache is best effort, failing to write it should not fail stat.
+            let _ = set_etag_cache(path, &format!("{fingerprint}:{hash}"));
+        }
+
+        Ok(Some(format!("\"{hash}\"")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_etag_mode() {
+        assert_eq!(EtagMode::parse("none").unwrap(), EtagMode::None);
+        assert_eq!(EtagMode::parse("Fast").unwrap(), EtagMode::Fast);
+        assert_eq!(EtagMode::parse("content").unwrap(), EtagMode::Content);
+        assert_eq!(
+            EtagMode::parse("sha256").unwrap_err().kind(),
+            ErrorKind::ConfigInvalid
+        );
+    }
+
+    #[test]
+    fn test_compute_etag() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&dir).unwrap();
+        let p = dir.join("file");
+        std::fs::write(&p, "Hello, World!").unwrap();
+        let meta = std::fs::metadata(&p).unwrap();
+
+        let cfg = EtagConfig {
+            mode: EtagMode::Content,
+            enable_cache: true,
+        };
+        let etag = cfg.compute(&p, &meta).unwrap();
+        assert_eq!(
+            etag.as_deref(),
+            Some("\"65a8e27d8879283831b664bd8b7f0ad4\"")
+        );
+        // Served from cache if supported, must be the same.
+        assert_eq!(cfg.compute(&p, &meta).unwrap(), etag);
+
+        let cfg = EtagConfig {
+            mode: EtagMode::Fast,
+            enable_cache: false,
+        };
+        let etag = cfg.compute(&p, &meta).unwrap().unwrap();
+        assert!(etag.starts_with('"') && etag.ends_with("-d\""));
+
+        let cfg = EtagConfig {
+            mode: EtagMode::None,
+            enable_cache: false,
+        };
+        assert_eq!(cfg.compute(&p, &meta).unwrap(), None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
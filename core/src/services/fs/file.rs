@@ -0,0 +1,111 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::io;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use tokio::io::AsyncRead;
+use tokio::io::AsyncSeek;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+
+#[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+use super::uring::UringFile;
+
+/// FsFile is the file used by fs async operations.
+///
+/// Files will be served by io_uring if it's enabled, otherwise tokio's
+/// file will be used.
+pub enum FsFile {
+    Tokio(tokio::fs::File),
+    #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+    Uring(UringFile),
+}
+
+impl FsFile {
+    pub async fn sync_all(&self) -> io::Result<()> {
+        match self {
+            FsFile::Tokio(f) => f.sync_all().await,
+            #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+            FsFile::Uring(f) => f.sync_all().await,
+        }
+    }
+}
+
+impl AsyncRead for FsFile {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            FsFile::Tokio(f) => Pin::new(f).poll_read(cx, buf),
+            #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+            FsFile::Uring(f) => Pin::new(f).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncSeek for FsFile {
+    fn start_seek(self: Pin<&mut Self>, pos: SeekFrom) -> io::Result<()> {
+        match self.get_mut() {
+            FsFile::Tokio(f) => Pin::new(f).start_seek(pos),
+            #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+            FsFile::Uring(f) => Pin::new(f).start_seek(pos),
+        }
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        match self.get_mut() {
+            FsFile::Tokio(f) => Pin::new(f).poll_complete(cx),
+            #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+            FsFile::Uring(f) => Pin::new(f).poll_complete(cx),
+        }
+    }
+}
+
+impl AsyncWrite for FsFile {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            FsFile::Tokio(f) => Pin::new(f).poll_write(cx, buf),
+            #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+            FsFile::Uring(f) => Pin::new(f).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            FsFile::Tokio(f) => Pin::new(f).poll_flush(cx),
+            #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+            FsFile::Uring(f) => Pin::new(f).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            FsFile::Tokio(f) => Pin::new(f).poll_shutdown(cx),
+            #[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+            FsFile::Uring(f) => Pin::new(f).poll_shutdown(cx),
+        }
+    }
+}
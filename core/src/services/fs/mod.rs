@@ -18,7 +18,15 @@
 mod backend;
 pub use backend::FsBuilder as Fs;
 
+#[cfg(feature = "services-fs")]
 mod appender;
+mod copy;
 mod error;
+mod etag;
+#[cfg(feature = "services-fs")]
+mod file;
 mod pager;
+#[cfg(all(target_os = "linux", feature = "services-fs-io-uring"))]
+mod uring;
 mod writer;
+mod xattr;
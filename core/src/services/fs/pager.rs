@@ -15,12 +15,21 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::fs::FileType;
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 
+#[cfg(feature = "services-fs")]
 use async_trait::async_trait;
+use chrono::DateTime;
 
+use super::backend::new_symlink_error;
+#[cfg(feature = "services-fs")]
+use super::backend::run_blocking;
+use super::backend::SymlinkMode;
 use super::error::parse_io_error;
+use super::etag::EtagConfig;
 use crate::raw::*;
 use crate::EntryMode;
 use crate::Metadata;
@@ -31,18 +40,78 @@ pub struct FsPager<P> {
 
     size: usize,
     rd: P,
+    symlink_mode: SymlinkMode,
+    etag: EtagConfig,
 }
 
 impl<P> FsPager<P> {
-    pub fn new(root: &Path, rd: P, limit: Option<usize>) -> Self {
+    pub fn new(
+        root: &Path,
+        rd: P,
+        limit: Option<usize>,
+        symlink_mode: SymlinkMode,
+        etag: EtagConfig,
+    ) -> Self {
         Self {
             root: root.to_owned(),
             size: limit.unwrap_or(1000),
             rd,
+            symlink_mode,
+            etag,
         }
     }
 }
 
+/// Build file entry with its etag and the metadata used to compute etag.
+fn build_file_entry_with_etag(rel_path: &str, path: &Path, etag: EtagConfig) -> Result<oio::Entry> {
+    let meta = std::fs::metadata(path).map_err(parse_io_error)?;
+
+    let mut m = Metadata::new(EntryMode::FILE)
+        .with_content_length(meta.len())
+        .with_last_modified(
+            meta.modified()
+                .map(DateTime::from)
+                .map_err(parse_io_error)?,
+        );
+    if let Some(etag) = etag.compute(path, &meta)? {
+        m.set_etag(&etag);
+    }
+
+    Ok(oio::Entry::new(rel_path, m))
+}
+
+/// Build entry by the file type of its target, broken symlinks will be
+/// returned as unknown entries.
+fn build_entry(rel_path: &str, file_type: io::Result<FileType>) -> Result<oio::Entry> {
+    let file_type = match file_type {
+        Ok(ft) => Some(ft),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(parse_io_error(err)),
+    };
+
+    let d = match file_type {
+        Some(ft) if ft.is_file() => oio::Entry::new(rel_path, Metadata::new(EntryMode::FILE)),
+        // Make sure we are returning the correct path.
+        Some(ft) if ft.is_dir() => {
+            oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
+        }
+        _ => oio::Entry::new(rel_path, Metadata::new(EntryMode::Unknown)),
+    };
+
+    Ok(d)
+}
+
+/// Build entry for symlink which preserves its target.
+fn build_symlink_entry(rel_path: &str, target: io::Result<PathBuf>) -> Result<oio::Entry> {
+    let target = target.map_err(parse_io_error)?;
+
+    Ok(oio::Entry::new(
+        rel_path,
+        Metadata::new(EntryMode::Unknown).with_symlink_target(target.to_string_lossy().to_string()),
+    ))
+}
+
+#[cfg(feature = "services-fs")]
 #[async_trait]
 impl oio::Page for FsPager<tokio::fs::ReadDir> {
     async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
@@ -69,13 +138,28 @@ impl oio::Page for FsPager<tokio::fs::ReadDir> {
             // the target file type.
             let file_type = de.file_type().await.map_err(parse_io_error)?;
 
-            let d = if file_type.is_file() {
-                oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
-            } else if file_type.is_dir() {
-                // Make sure we are returning the correct path.
-                oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
+            let d = if !file_type.is_symlink() {
+                build_entry(&rel_path, Ok(file_type))?
             } else {
-                oio::Entry::new(&rel_path, Metadata::new(EntryMode::Unknown))
+                match self.symlink_mode {
+                    SymlinkMode::Follow => build_entry(
+                        &rel_path,
+                        tokio::fs::metadata(&entry_path)
+                            .await
+                            .map(|m| m.file_type()),
+                    )?,
+                    SymlinkMode::Preserve => {
+                        build_symlink_entry(&rel_path, tokio::fs::read_link(&entry_path).await)?
+                    }
+                    SymlinkMode::Error => return Err(new_symlink_error(&entry_path)),
+                }
+            };
+            let d = if self.etag.is_enabled() && d.mode() == EntryMode::FILE {
+                let etag = self.etag;
+                run_blocking(move || build_file_entry_with_etag(&rel_path, &entry_path, etag))
+                    .await?
+            } else {
+                d
             };
 
             oes.push(d)
@@ -110,13 +194,24 @@ impl oio::BlockingPage for FsPager<std::fs::ReadDir> {
             // the target file type.
             let file_type = de.file_type().map_err(parse_io_error)?;
 
-            let d = if file_type.is_file() {
-                oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
-            } else if file_type.is_dir() {
-                // Make sure we are returning the correct path.
-                oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
+            let d = if !file_type.is_symlink() {
+                build_entry(&rel_path, Ok(file_type))?
+            } else {
+                match self.symlink_mode {
+                    SymlinkMode::Follow => build_entry(
+                        &rel_path,
+                        std::fs::metadata(&entry_path).map(|m| m.file_type()),
+                    )?,
+                    SymlinkMode::Preserve => {
+                        build_symlink_entry(&rel_path, std::fs::read_link(&entry_path))?
+                    }
+                    SymlinkMode::Error => return Err(new_symlink_error(&entry_path)),
+                }
+            };
+            let d = if self.etag.is_enabled() && d.mode() == EntryMode::FILE {
+                build_file_entry_with_etag(&rel_path, &entry_path, self.etag)?
             } else {
-                oio::Entry::new(&rel_path, Metadata::new(EntryMode::Unknown))
+                d
             };
 
             oes.push(d)
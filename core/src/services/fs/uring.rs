@@ -0,0 +1,491 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::cmp::min;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fs::File;
+use std::future::Future;
+use std::io;
+use std::io::SeekFrom;
+use std::os::unix::io::AsRawFd;
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+use std::thread;
+
+use bytes::Bytes;
+use futures::channel::oneshot;
+use io_uring::opcode;
+use io_uring::types;
+use io_uring::IoUring;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncSeek;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+
+/// The number of registered buffers, which is also the max number of
+/// inflight operations.
+const URING_ENTRIES: u32 = 32;
+/// The size of every registered buffer, read and write larger than this
+/// will be split.
+const URING_BUFFER_SIZE: usize = 256 * 1024;
+
+enum Op {
+    Read {
+        file: Arc<File>,
+        offset: u64,
+        len: usize,
+        tx: oneshot::Sender<io::Result<Bytes>>,
+    },
+    Write {
+        file: Arc<File>,
+        offset: u64,
+        bs: Bytes,
+        tx: oneshot::Sender<io::Result<usize>>,
+    },
+}
+
+/// Uring sends file I/O to a dedicated thread which drives an io_uring
+/// instance with registered buffers.
+pub struct Uring {
+    tx: Mutex<mpsc::Sender<Op>>,
+}
+
+impl Debug for Uring {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Uring")
+            .field("entries", &URING_ENTRIES)
+            .field("buffer_size", &URING_BUFFER_SIZE)
+            .finish()
+    }
+}
+
+impl Uring {
+    pub fn new() -> io::Result<Self> {
+        let ring = IoUring::new(URING_ENTRIES)?;
+
+        let mut buf = vec![0; URING_ENTRIES as usize * URING_BUFFER_SIZE];
+        let iovecs: Vec<libc::iovec> = buf
+            .chunks_mut(URING_BUFFER_SIZE)
+            .map(|v| libc::iovec {
+                iov_base: v.as_mut_ptr() as *mut libc::c_void,
+                iov_len: v.len(),
+            })
+            .collect();
+        // SAFETY: buffers are moved into driver together with the ring, and
+        // the heap allocation will not be touched until the ring is dropped.
+        unsafe { ring.submitter().register_buffers(&iovecs)? };
+
+        let (tx, rx) = mpsc::channel();
+        let driver = Driver {
+            ring,
+            buf,
+            rx,
+            pending: VecDeque::new(),
+            inflight: (0..URING_ENTRIES).map(|_| None).collect(),
+            free: (0..URING_ENTRIES as usize).collect(),
+        };
+        thread::Builder::new()
+            .name("opendal-fs-uring".to_string())
+            .spawn(move || driver.run())?;
+
+        Ok(Self { tx: Mutex::new(tx) })
+    }
+
+    fn send(&self, op: Op) -> io::Result<()> {
+        self.tx
+            .lock()
+            .expect("lock must succeed")
+            .send(op)
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "io_uring driver has exited"))
+    }
+
+    fn read(
+        &self,
+        file: Arc<File>,
+        offset: u64,
+        len: usize,
+    ) -> io::Result<oneshot::Receiver<io::Result<Bytes>>> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Op::Read {
+            file,
+            offset,
+            len: min(len, URING_BUFFER_SIZE),
+            tx,
+        })?;
+        Ok(rx)
+    }
+
+    fn write(
+        &self,
+        file: Arc<File>,
+        offset: u64,
+        bs: &[u8],
+    ) -> io::Result<oneshot::Receiver<io::Result<usize>>> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Op::Write {
+            file,
+            offset,
+            bs: Bytes::copy_from_slice(&bs[..min(bs.len(), URING_BUFFER_SIZE)]),
+            tx,
+        })?;
+        Ok(rx)
+    }
+}
+
+struct Driver {
+    ring: IoUring,
+    /// Registered buffers, every operation takes one of them.
+    buf: Vec<u8>,
+    rx: mpsc::Receiver<Op>,
+    pending: VecDeque<Op>,
+    /// Inflight operations indexed by the buffer they are using.
+    ///
+    /// Operations hold the file so that the fd will not be closed before
+    /// completion.
+    inflight: Vec<Option<Op>>,
+    free: Vec<usize>,
+}
+
+impl Driver {
+    fn run(mut self) {
+        loop {
+            // Block until new operations come if we are idle.
+            if self.pending.is_empty() && self.free.len() == self.inflight.len() {
+                match self.rx.recv() {
+                    Ok(op) => self.pending.push_back(op),
+                    // All senders have been dropped.
+                    Err(_) => return,
+                }
+            }
+            while let Ok(op) = self.rx.try_recv() {
+                self.pending.push_back(op);
+            }
+
+            while !self.pending.is_empty() && !self.free.is_empty() {
+                let idx = self.free.pop().expect("free buffer must exist");
+                let op = self.pending.pop_front().expect("pending op must exist");
+                self.push(idx, op);
+            }
+
+            match self.ring.submit_and_wait(1) {
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.fail(err);
+                    return;
+                }
+            }
+
+            self.complete();
+        }
+    }
+
+    fn push(&mut self, idx: usize, op: Op) {
+        let buf = &mut self.buf[idx * URING_BUFFER_SIZE..(idx + 1) * URING_BUFFER_SIZE];
+
+        let entry = match &op {
+            Op::Read {
+                file, offset, len, ..
+            } => opcode::ReadFixed::new(
+                types::Fd(file.as_raw_fd()),
+                buf.as_mut_ptr(),
+                *len as u32,
+                idx as u16,
+            )
+            .offset(*offset)
+            .build(),
+            Op::Write {
+                file, offset, bs, ..
+            } => {
+                buf[..bs.len()].copy_from_slice(bs);
+                opcode::WriteFixed::new(
+                    types::Fd(file.as_raw_fd()),
+                    buf.as_ptr(),
+                    bs.len() as u32,
+                    idx as u16,
+                )
+                .offset(*offset)
+                .build()
+            }
+        }
+        .user_data(idx as u64);
+
+        // SAFETY: the buffer and the file are kept alive until completion.
+        unsafe {
+            self.ring
+                .submission()
+                .push(&entry)
+                .expect("submission queue must have room for every buffer")
+        };
+        self.inflight[idx] = Some(op);
+    }
+
+    fn complete(&mut self) {
+        let Driver {
+            ring,
+            buf,
+            inflight,
+            free,
+            ..
+        } = self;
+
+        for cqe in ring.completion() {
+            let idx = cqe.user_data() as usize;
+            let op = inflight[idx].take().expect("completed op must be inflight");
+            free.push(idx);
+
+            let res = if cqe.result() < 0 {
+                Err(io::Error::from_raw_os_error(-cqe.result()))
+            } else {
+                Ok(cqe.result() as usize)
+            };
+
+            // The receiver could be dropped, ignore the send error.
+            match op {
+                Op::Read { tx, .. } => {
+                    let start = idx * URING_BUFFER_SIZE;
+                    let _ = tx.send(res.map(|n| Bytes::copy_from_slice(&buf[start..start + n])));
+                }
+                Op::Write { tx, .. } => {
+                    let _ = tx.send(res);
+                }
+            }
+        }
+    }
+
+    /// Fail all inflight and pending operations.
+    fn fail(&mut self, err: io::Error) {
+        let ops = self
+            .inflight
+            .iter_mut()
+            .filter_map(|v| v.take())
+            .chain(self.pending.drain(..));
+
+        for op in ops {
+            let err = io::Error::new(err.kind(), err.to_string());
+            match op {
+                Op::Read { tx, .. } => {
+                    let _ = tx.send(Err(err));
+                }
+                Op::Write { tx, .. } => {
+                    let _ = tx.send(Err(err));
+                }
+            }
+        }
+    }
+}
+
+/// UringFile implements tokio's async file traits via [`Uring`].
+pub struct UringFile {
+    uring: Arc<Uring>,
+    file: Arc<File>,
+    pos: u64,
+
+    read: Option<oneshot::Receiver<io::Result<Bytes>>>,
+    write: Option<oneshot::Receiver<io::Result<usize>>>,
+}
+
+impl UringFile {
+    pub fn new(uring: Arc<Uring>, file: File) -> Self {
+        Self {
+            uring,
+            file: Arc::new(file),
+            pos: 0,
+            read: None,
+            write: None,
+        }
+    }
+
+    pub async fn sync_all(&self) -> io::Result<()> {
+        tokio::fs::File::from_std(self.file.try_clone()?)
+            .sync_all()
+            .await
+    }
+}
+
+fn poll_result<T>(
+    cx: &mut Context<'_>,
+    rx: &mut oneshot::Receiver<io::Result<T>>,
+) -> Poll<io::Result<T>> {
+    match ready!(Pin::new(rx).poll(cx)) {
+        Ok(res) => Poll::Ready(res),
+        Err(_) => Poll::Ready(Err(io::Error::new(
+            io::ErrorKind::Other,
+            "io_uring driver has exited",
+        ))),
+    }
+}
+
+impl AsyncRead for UringFile {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let rx = match &mut this.read {
+            Some(rx) => rx,
+            None => this.read.insert(this.uring.read(
+                this.file.clone(),
+                this.pos,
+                buf.remaining(),
+            )?),
+        };
+        let res = ready!(poll_result(cx, rx));
+        this.read = None;
+
+        let bs = res?;
+        let n = min(bs.len(), buf.remaining());
+        buf.put_slice(&bs[..n]);
+        this.pos += n as u64;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncSeek for UringFile {
+    fn start_seek(self: Pin<&mut Self>, pos: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => {
+                this.pos = n;
+                return Ok(());
+            }
+            SeekFrom::End(n) => (this.file.metadata()?.len(), n),
+            SeekFrom::Current(n) => (this.pos, n),
+        };
+
+        let pos = if offset >= 0 {
+            base.checked_add(offset as u64)
+        } else {
+            base.checked_sub(offset.unsigned_abs())
+        };
+        match pos {
+            Some(n) => {
+                this.pos = n;
+                Ok(())
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.pos))
+    }
+}
+
+impl AsyncWrite for UringFile {
+    /// # Notes
+    ///
+    /// The content of `buf` will be copied while submitting, callers must
+    /// pass the same buf while polling a pending write.
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let rx = match &mut this.write {
+            Some(rx) => rx,
+            None => this
+                .write
+                .insert(this.uring.write(this.file.clone(), this.pos, buf)?),
+        };
+        let res = ready!(poll_result(cx, rx));
+        this.write = None;
+
+        let n = res?;
+        this.pos += n as u64;
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncSeekExt;
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_uring_file() {
+        let uring = match Uring::new() {
+            Ok(v) => Arc::new(v),
+            Err(err) => {
+                println!("io_uring is not supported, skip: {err}");
+                return;
+            }
+        };
+
+        let path = std::env::temp_dir().join(format!("opendal-uring-{}", uuid::Uuid::new_v4()));
+        let content: Vec<u8> = (0..URING_BUFFER_SIZE * 3 + 7)
+            .map(|v| (v % 251) as u8)
+            .collect();
+
+        let f = File::create(&path).expect("create must succeed");
+        let mut f = UringFile::new(uring.clone(), f);
+        f.write_all(&content).await.expect("write must succeed");
+        f.sync_all().await.expect("sync must succeed");
+
+        let f = File::open(&path).expect("open must succeed");
+        let mut f = UringFile::new(uring, f);
+        let mut bs = Vec::new();
+        f.read_to_end(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, content);
+
+        let pos = f.seek(SeekFrom::End(-7)).await.expect("seek must succeed");
+        assert_eq!(pos, content.len() as u64 - 7);
+        let mut bs = Vec::new();
+        f.read_to_end(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, &content[content.len() - 7..]);
+
+        f.seek(SeekFrom::Start(0)).await.expect("seek must succeed");
+        assert!(f.seek(SeekFrom::Current(-1)).await.is_err());
+
+        std::fs::remove_file(&path).expect("remove must succeed");
+    }
+}
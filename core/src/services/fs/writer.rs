@@ -20,12 +20,17 @@ use std::io::SeekFrom;
 use std::io::Write;
 use std::path::PathBuf;
 
+#[cfg(feature = "services-fs")]
 use async_trait::async_trait;
 use bytes::Bytes;
+#[cfg(feature = "services-fs")]
 use tokio::io::AsyncSeekExt;
+#[cfg(feature = "services-fs")]
 use tokio::io::AsyncWriteExt;
 
 use super::error::parse_io_error;
+#[cfg(feature = "services-fs")]
+use super::file::FsFile;
 use crate::raw::*;
 use crate::*;
 
@@ -47,8 +52,9 @@ impl<F> FsWriter<F> {
     }
 }
 
+#[cfg(feature = "services-fs")]
 #[async_trait]
-impl oio::Write for FsWriter<tokio::fs::File> {
+impl oio::Write for FsWriter<FsFile> {
     /// # Notes
     ///
     /// File could be partial written, so we will seek to start to make sure
@@ -0,0 +1,267 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+//! User metadata support via extended attributes.
+//!
+//! User metadata `key: value` will be stored as xattr `user.key` so that
+//! they could be read by other tools like `getfattr`. Xattrs under
+//! `user.opendal.` are used by opendal itself and won't be returned as
+//! user metadata.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+#[cfg(target_os = "linux")]
+use super::error::parse_io_error;
+use crate::*;
+
+/// Read user metadata of given path.
+///
+/// Returns `None` if xattr is not supported on current platform.
+#[cfg(target_os = "linux")]
+pub fn get_user_metadata(path: &Path) -> Result<Option<HashMap<String, String>>> {
+    linux::get_user_metadata(path)
+        .map(Some)
+        .map_err(parse_io_error)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_user_metadata(_: &Path) -> Result<Option<HashMap<String, String>>> {
+    Ok(None)
+}
+
+/// Replace user metadata of given path by `meta`.
+#[cfg(target_os = "linux")]
+pub fn set_user_metadata(path: &Path, meta: &HashMap<String, String>) -> Result<()> {
+    linux::set_user_metadata(path, meta).map_err(parse_io_error)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_user_metadata(_: &Path, _: &HashMap<String, String>) -> Result<()> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "user metadata is only supported on linux",
+    ))
+}
+
+/// Read cached etag of given path.
+///
+/// Returns `None` if not cached or xattr is not supported on current platform.
+#[cfg(target_os = "linux")]
+pub fn get_etag_cache(path: &Path) -> Result<Option<String>> {
+    linux::get_internal_attr(path, ETAG_CACHE_KEY).map_err(parse_io_error)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_etag_cache(_: &Path) -> Result<Option<String>> {
+    Ok(None)
+}
+
+/// Cache etag of given path.
+#[cfg(target_os = "linux")]
+pub fn set_etag_cache(path: &Path, value: &str) -> Result<()> {
+    linux::set_internal_attr(path, ETAG_CACHE_KEY, value).map_err(parse_io_error)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_etag_cache(_: &Path, _: &str) -> Result<()> {
+    Ok(())
+}
+
+/// Key of the xattr to cache etag, it's stored under the internal
+/// prefix so that it won't be returned as user metadata.
+#[cfg(target_os = "linux")]
+const ETAG_CACHE_KEY: &str = "etag";
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::collections::HashMap;
+    use std::ffi::CString;
+    use std::io;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    const USER_PREFIX: &str = "user.";
+    /// Prefix of xattrs used by opendal itself.
+    const INTERNAL_PREFIX: &str = "user.opendal.";
+
+    fn to_cstring(bs: &[u8]) -> io::Result<CString> {
+        CString::new(bs).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+    }
+
+    /// Call `f` with a growing buffer until the value fits in.
+    ///
+    /// `f` follows the convention of xattr syscalls: returns the size of
+    /// value if the buffer is empty, and fails with `ERANGE` if the buffer
+    /// is too small.
+    fn read_value(f: impl Fn(&mut [u8]) -> isize) -> io::Result<Vec<u8>> {
+        loop {
+            let size = f(&mut []);
+            if size < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut buf = vec![0; size as usize];
+            let n = f(&mut buf);
+            if n >= 0 {
+                buf.truncate(n as usize);
+                return Ok(buf);
+            }
+
+            let err = io::Error::last_os_error();
+            // Value has been enlarged between two calls, retry.
+            if err.raw_os_error() != Some(libc::ERANGE) {
+                return Err(err);
+            }
+        }
+    }
+
+    fn list_user_keys(path: &CString) -> io::Result<Vec<CString>> {
+        // SAFETY: path is valid c string and buf is valid during the call.
+        let names = match read_value(|buf| unsafe {
+            libc::listxattr(path.as_ptr(), buf.as_mut_ptr() as *mut _, buf.len())
+        }) {
+            Ok(v) => v,
+            // Treat fs that doesn't support xattr as no user metadata.
+            Err(err) if err.raw_os_error() == Some(libc::ENOTSUP) => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+
+        names
+            .split(|b| *b == 0)
+            .filter(|name| {
+                name.starts_with(USER_PREFIX.as_bytes())
+                    && !name.starts_with(INTERNAL_PREFIX.as_bytes())
+            })
+            .map(to_cstring)
+            .collect()
+    }
+
+    pub fn get_user_metadata(path: &Path) -> io::Result<HashMap<String, String>> {
+        let path = to_cstring(path.as_os_str().as_bytes())?;
+
+        let mut meta = HashMap::new();
+        for name in list_user_keys(&path)? {
+            // SAFETY: path and name are valid c strings and buf is valid
+            // during the call.
+            let value = match read_value(|buf| unsafe {
+                libc::getxattr(
+                    path.as_ptr(),
+                    name.as_ptr(),
+                    buf.as_mut_ptr() as *mut _,
+                    buf.len(),
+                )
+            }) {
+                Ok(v) => v,
+                // Attr has been removed after listing.
+                Err(err) if err.raw_os_error() == Some(libc::ENODATA) => continue,
+                Err(err) => return Err(err),
+            };
+
+            // User metadata must be valid utf-8, skip attrs set by others.
+            let (Ok(key), Ok(value)) = (name.into_string(), String::from_utf8(value)) else {
+                continue;
+            };
+            meta.insert(key[USER_PREFIX.len()..].to_string(), value);
+        }
+
+        Ok(meta)
+    }
+
+    pub fn get_internal_attr(path: &Path, key: &str) -> io::Result<Option<String>> {
+        let path = to_cstring(path.as_os_str().as_bytes())?;
+        let name = to_cstring(format!("{INTERNAL_PREFIX}{key}").as_bytes())?;
+
+        // SAFETY: path and name are valid c strings and buf is valid
+        // during the call.
+        match read_value(|buf| unsafe {
+            libc::getxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                buf.as_mut_ptr() as *mut _,
+                buf.len(),
+            )
+        }) {
+            Ok(v) => Ok(String::from_utf8(v).ok()),
+            Err(err)
+                if matches!(
+                    err.raw_os_error(),
+                    Some(libc::ENODATA) | Some(libc::ENOTSUP)
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn set_internal_attr(path: &Path, key: &str, value: &str) -> io::Result<()> {
+        let path = to_cstring(path.as_os_str().as_bytes())?;
+        let name = to_cstring(format!("{INTERNAL_PREFIX}{key}").as_bytes())?;
+
+        // SAFETY: path and name are valid c strings and value is valid
+        // during the call.
+        let ret = unsafe {
+            libc::setxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                value.as_ptr() as *const _,
+                value.len(),
+                0,
+            )
+        };
+        if ret == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    pub fn set_user_metadata(path: &Path, meta: &HashMap<String, String>) -> io::Result<()> {
+        let path = to_cstring(path.as_os_str().as_bytes())?;
+
+        for name in list_user_keys(&path)? {
+            // SAFETY: path and name are valid c strings.
+            let ret = unsafe { libc::removexattr(path.as_ptr(), name.as_ptr()) };
+            if ret == -1 {
+                let err = io::Error::last_os_error();
+                if err.raw_os_error() != Some(libc::ENODATA) {
+                    return Err(err);
+                }
+            }
+        }
+
+        for (key, value) in meta {
+            let name = to_cstring(format!("{USER_PREFIX}{key}").as_bytes())?;
+            // SAFETY: path and name are valid c strings and value is valid
+            // during the call.
+            let ret = unsafe {
+                libc::setxattr(
+                    path.as_ptr(),
+                    name.as_ptr(),
+                    value.as_ptr() as *const _,
+                    value.len(),
+                    0,
+                )
+            };
+            if ret == -1 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -32,6 +32,7 @@ use http::Uri;
 use log::debug;
 use suppaftp::list::File;
 use suppaftp::types::FileType;
+use suppaftp::types::Mode;
 use suppaftp::types::Response;
 use suppaftp::FtpError;
 use suppaftp::FtpStream;
@@ -52,6 +53,7 @@ pub struct FtpBuilder {
     root: Option<String>,
     user: Option<String>,
     password: Option<String>,
+    enable_active_mode: bool,
 }
 
 impl Debug for FtpBuilder {
@@ -107,6 +109,16 @@ impl FtpBuilder {
 
         self
     }
+
+    /// Enable active mode for ftp backend.
+    ///
+    /// OpenDAL uses passive mode by default, which works with most servers
+    /// behind firewalls. Enable active mode only if the server doesn't
+    /// support passive mode.
+    pub fn enable_active_mode(&mut self) -> &mut Self {
+        self.enable_active_mode = true;
+        self
+    }
 }
 
 impl Builder for FtpBuilder {
@@ -169,6 +181,7 @@ impl Builder for FtpBuilder {
             user,
             password,
             enable_secure,
+            enable_active_mode: self.enable_active_mode,
             pool: OnceCell::new(),
         })
     }
@@ -180,6 +193,9 @@ impl Builder for FtpBuilder {
         map.get("endpoint").map(|v| builder.endpoint(v));
         map.get("user").map(|v| builder.user(v));
         map.get("password").map(|v| builder.password(v));
+        map.get("enable_active_mode")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.enable_active_mode());
 
         builder
     }
@@ -191,6 +207,7 @@ pub struct Manager {
     user: String,
     password: String,
     enable_secure: bool,
+    enable_active_mode: bool,
 }
 
 #[async_trait]
@@ -230,6 +247,11 @@ impl bb8::ManageConnection for Manager {
 
         ftp_stream.transfer_type(FileType::Binary).await?;
 
+        // Passive mode is used by default.
+        if self.enable_active_mode {
+            ftp_stream.set_mode(Mode::Active);
+        }
+
         Ok(ftp_stream)
     }
 
@@ -251,6 +273,7 @@ pub struct FtpBackend {
     user: String,
     password: String,
     enable_secure: bool,
+    enable_active_mode: bool,
     pool: OnceCell<bb8::Pool<Manager>>,
 }
 
@@ -458,6 +481,7 @@ impl FtpBackend {
                         user: self.user.to_string(),
                         password: self.password.to_string(),
                         enable_secure: self.enable_secure,
+                        enable_active_mode: self.enable_active_mode,
                     })
                     .await
             })
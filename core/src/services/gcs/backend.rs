@@ -16,11 +16,14 @@
 // under the License.
 
 use std::collections::HashMap;
+use std::env;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use http::header::HOST;
 use http::StatusCode;
 use log::debug;
@@ -30,10 +33,16 @@ use reqsign::GoogleTokenLoad;
 use reqsign::GoogleTokenLoader;
 use serde::Deserialize;
 use serde_json;
+use sha2::Digest;
+use sha2::Sha256;
 
 use super::core::GcsCore;
 use super::error::parse_error;
 use super::pager::GcsPager;
+use super::token::ExternalAccount;
+use super::token::GcsTokenLoader;
+use super::token::GcsTokenSource;
+use super::token::METADATA_SERVER_ENDPOINT;
 use super::writer::GcsWriter;
 use crate::raw::*;
 use crate::*;
@@ -66,11 +75,45 @@ const DEFAULT_WRITE_FIXED_SIZE: usize = 8 * 1024 * 1024;
 /// - `bucket`: Set the container name for backend
 /// - `endpoint`: Customizable endpoint setting
 /// - `credentials`: Credential string for GCS OAuth2
+/// - `credential_path`: Path to the service account key file for GCS OAuth2
+/// - `token`: Static access token for GCS OAuth2, skips token loading if set
 /// - `predefined_acl`: Predefined ACL for GCS
 /// - `default_storage_class`: Default storage class for GCS
+/// - `disable_vm_metadata`: Disable loading tokens from the GCE metadata server
+/// - `encryption_key`: Base64 encoded customer-supplied encryption key
+/// - `encryption_key_sha256`: Base64 encoded SHA256 of the encryption key
+/// - `encryption_algorithm`: Algorithm of the encryption key, default to `AES256`
 ///
 /// You can refer to [`GcsBuilder`]'s docs for more information
 ///
+/// # Authentication
+///
+/// Requests are signed with the first available credential in order:
+///
+/// - The static access token set via `token`.
+/// - The `external_account` credential (workload identity federation)
+///   loaded from `credential`, `credential_path` or the file pointed by
+///   `GOOGLE_APPLICATION_CREDENTIALS`. The subject token will be read from
+///   its `credential_source` and exchanged for access tokens via STS, with
+///   service account impersonation if configured.
+/// - The service account key loaded from the same places.
+/// - The tokens of `service_account` (`default` if not set) fetched from the
+///   GCE/GKE metadata server, which can be overridden by `GCE_METADATA_HOST`.
+///
+/// Tokens loaded from external accounts and metadata server will be
+/// refreshed before expiring.
+///
+/// # Customer-supplied Encryption Keys
+///
+/// Objects will be encrypted with the customer-supplied encryption key
+/// (CSEK) if `encryption_key` is set. The same key is required to read,
+/// stat and copy them, so all objects under the same operator are expected
+/// to share the key. Copy will send the key for both source and destination
+/// objects.
+///
+/// Presigned requests don't carry the key, callers need to send the
+/// `x-goog-encryption-*` headers by themselves.
+///
 /// # Example
 ///
 /// ## Via Builder
@@ -119,11 +162,18 @@ pub struct GcsBuilder {
     credential: Option<String>,
     /// credential path for GCS service.
     credential_path: Option<String>,
+    /// static access token for GCS service.
+    token: Option<String>,
 
     http_client: Option<HttpClient>,
     customed_token_loader: Option<Box<dyn GoogleTokenLoad>>,
     predefined_acl: Option<String>,
     default_storage_class: Option<String>,
+    disable_vm_metadata: bool,
+
+    encryption_key: Option<String>,
+    encryption_key_sha256: Option<String>,
+    encryption_algorithm: Option<String>,
 
     /// the fixed size writer uses to flush into underlying storage.
     write_fixed_size: Option<usize>,
@@ -198,6 +248,18 @@ impl GcsBuilder {
         self
     }
 
+    /// Set a static OAuth2 access token for GCS.
+    ///
+    /// If set, the token will be used as-is to sign requests and the
+    /// service account credentials will not be used to fetch tokens.
+    /// Presign still requires service account credentials.
+    pub fn token(&mut self, token: &str) -> &mut Self {
+        if !token.is_empty() {
+            self.token = Some(token.to_string())
+        };
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -245,6 +307,58 @@ impl GcsBuilder {
         self
     }
 
+    /// Disable loading tokens from the GCE/GKE metadata server.
+    ///
+    /// By default, opendal will fetch tokens of `service_account` from
+    /// the metadata server if there is no other credential.
+    pub fn disable_vm_metadata(&mut self) -> &mut Self {
+        self.disable_vm_metadata = true;
+        self
+    }
+
+    /// Set the customer-supplied encryption key for GCS.
+    ///
+    /// `v`: base64 encoded 256-bit AES key.
+    ///
+    /// Reference: [Customer-supplied encryption keys](https://cloud.google.com/storage/docs/encryption/customer-supplied-keys)
+    pub fn encryption_key(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.encryption_key = Some(v.to_string())
+        };
+        self
+    }
+
+    /// Set the SHA256 hash of the customer-supplied encryption key.
+    ///
+    /// `v`: base64 encoded SHA256 of the key, will be calculated from
+    /// `encryption_key` if not set.
+    pub fn encryption_key_sha256(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.encryption_key_sha256 = Some(v.to_string())
+        };
+        self
+    }
+
+    /// Set the algorithm of the customer-supplied encryption key.
+    ///
+    /// Only `AES256` is supported by GCS for now, which is also the default.
+    pub fn encryption_algorithm(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.encryption_algorithm = Some(v.to_string())
+        };
+        self
+    }
+
+    /// Enable encryption with customer-supplied key.
+    ///
+    /// `key` is the raw 256-bit AES key, its SHA256 will be calculated.
+    pub fn encryption_with_customer_key(&mut self, key: &[u8]) -> &mut Self {
+        self.encryption_algorithm = Some("AES256".to_string());
+        self.encryption_key = Some(BASE64_STANDARD.encode(key));
+        self.encryption_key_sha256 = Some(BASE64_STANDARD.encode(Sha256::digest(key).as_slice()));
+        self
+    }
+
     /// The buffer size should be a multiple of 256 KiB (256 x 1024 bytes), unless it's the last chunk that completes the upload.
     /// Larger chunk sizes typically make uploads faster, but note that there's a tradeoff between speed and memory usage.
     /// It's recommended that you use at least 8 MiB for the chunk size.
@@ -256,6 +370,72 @@ impl GcsBuilder {
     }
 }
 
+impl GcsBuilder {
+    /// Check if the customer-supplied encryption settings are consistent.
+    ///
+    /// The algorithm and SHA256 of key will be filled if not set.
+    fn check_encryption(&mut self) -> Result<()> {
+        let err = |msg: &str| {
+            Error::new(ErrorKind::ConfigInvalid, msg)
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Gcs)
+        };
+
+        let key = match &self.encryption_key {
+            Some(key) => key,
+            None => {
+                if self.encryption_key_sha256.is_some() || self.encryption_algorithm.is_some() {
+                    return Err(err("encryption_key is empty"));
+                }
+                return Ok(());
+            }
+        };
+
+        let key = BASE64_STANDARD
+            .decode(key)
+            .map_err(|e| err("encryption_key is not valid base64").set_source(e))?;
+        if key.len() != 32 {
+            return Err(err("encryption_key must be a 256-bit AES key"));
+        }
+
+        if self.encryption_algorithm.is_none() {
+            self.encryption_algorithm = Some("AES256".to_string());
+        }
+        if self.encryption_key_sha256.is_none() {
+            self.encryption_key_sha256 =
+                Some(BASE64_STANDARD.encode(Sha256::digest(key).as_slice()));
+        }
+
+        Ok(())
+    }
+
+    /// Load the `external_account` credential which is not supported by
+    /// the service account credential loader.
+    fn load_external_account(&self) -> Result<Option<ExternalAccount>> {
+        let content = if let Some(cred) = &self.credential {
+            Some(cred.clone())
+        } else {
+            self.credential_path
+                .clone()
+                .or_else(|| {
+                    env::var("GOOGLE_APPLICATION_CREDENTIALS")
+                        .ok()
+                        .filter(|v| !v.is_empty())
+                })
+                // Errors of reading credential file will be reported by
+                // the service account credential loader.
+                .and_then(|path| std::fs::read_to_string(path).ok())
+        };
+
+        match content {
+            Some(content) => {
+                ExternalAccount::parse(&content).map_err(|err| err.with_operation("Builder::build"))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
 impl Debug for GcsBuilder {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut ds = f.debug_struct("Builder");
@@ -266,6 +446,12 @@ impl Debug for GcsBuilder {
         if self.credential.is_some() {
             ds.field("credentials", &"<redacted>");
         }
+        if self.token.is_some() {
+            ds.field("token", &"<redacted>");
+        }
+        if self.encryption_key.is_some() {
+            ds.field("encryption_key", &"<redacted>");
+        }
         if self.predefined_acl.is_some() {
             ds.field("predefined_acl", &self.predefined_acl);
         }
@@ -285,10 +471,23 @@ impl Builder for GcsBuilder {
         map.get("bucket").map(|v| builder.bucket(v));
         map.get("endpoint").map(|v| builder.endpoint(v));
         map.get("credential").map(|v| builder.credential(v));
+        map.get("credential_path")
+            .map(|v| builder.credential_path(v));
+        map.get("token").map(|v| builder.token(v));
+        map.get("service_account")
+            .map(|v| builder.service_account(v));
         map.get("scope").map(|v| builder.scope(v));
         map.get("predefined_acl").map(|v| builder.predefined_acl(v));
         map.get("default_storage_class")
             .map(|v| builder.default_storage_class(v));
+        map.get("disable_vm_metadata")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.disable_vm_metadata());
+        map.get("encryption_key").map(|v| builder.encryption_key(v));
+        map.get("encryption_key_sha256")
+            .map(|v| builder.encryption_key_sha256(v));
+        map.get("encryption_algorithm")
+            .map(|v| builder.encryption_algorithm(v));
 
         builder
     }
@@ -299,6 +498,8 @@ impl Builder for GcsBuilder {
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
+        self.check_encryption()?;
+
         // Handle endpoint and bucket name
         let bucket = match self.bucket.is_empty() {
             false => Ok(&self.bucket),
@@ -309,7 +510,16 @@ impl Builder for GcsBuilder {
             ),
         }?;
 
-        // TODO: server side encryption
+        let build_value = |v: &Option<String>, key: &str| match v {
+            None => Ok(None),
+            Some(v) => build_header_value(v)
+                .map(Some)
+                .map_err(|err| err.with_context("key", key)),
+        };
+        let encryption_algorithm = build_value(&self.encryption_algorithm, "encryption_algorithm")?;
+        let encryption_key = build_value(&self.encryption_key, "encryption_key")?;
+        let encryption_key_sha256 =
+            build_value(&self.encryption_key_sha256, "encryption_key_sha256")?;
 
         let client = if let Some(client) = self.http_client.take() {
             client
@@ -344,13 +554,39 @@ impl Builder for GcsBuilder {
         if let Some(account) = &self.service_account {
             token_loader = token_loader.with_service_account(account);
         }
+        let mut has_credential = false;
         if let Ok(Some(cred)) = cred_loader.load() {
-            token_loader = token_loader.with_credentials(cred)
+            token_loader = token_loader.with_credentials(cred);
+            has_credential = true;
         }
         if let Some(loader) = self.customed_token_loader.take() {
-            token_loader = token_loader.with_customed_token_loader(loader)
+            token_loader = token_loader.with_customized_token_loader(loader);
+            has_credential = true;
         }
 
+        let gcs_token_source = if self.token.is_some() {
+            None
+        } else if let Some(account) = self.load_external_account()? {
+            Some(GcsTokenSource::ExternalAccount(account))
+        } else if !has_credential && !self.disable_vm_metadata {
+            let endpoint = env::var("GCE_METADATA_HOST")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| format!("http://{v}"))
+                .unwrap_or_else(|| METADATA_SERVER_ENDPOINT.to_string());
+            Some(GcsTokenSource::MetadataServer {
+                service_account: self
+                    .service_account
+                    .clone()
+                    .unwrap_or_else(|| "default".to_string()),
+                endpoint,
+            })
+        } else {
+            None
+        };
+        let gcs_token_loader =
+            gcs_token_source.map(|source| GcsTokenLoader::new(client.clone(), scope, source));
+
         let signer = GoogleSigner::new("storage");
 
         let write_fixed_size = self.write_fixed_size.unwrap_or(DEFAULT_WRITE_FIXED_SIZE);
@@ -372,9 +608,14 @@ impl Builder for GcsBuilder {
                 client,
                 signer,
                 token_loader,
+                token: self.token.take(),
+                gcs_token_loader,
                 credential_loader: cred_loader,
                 predefined_acl: self.predefined_acl.clone(),
                 default_storage_class: self.default_storage_class.clone(),
+                encryption_algorithm,
+                encryption_key,
+                encryption_key_sha256,
                 write_fixed_size,
             }),
         };
@@ -720,4 +961,26 @@ mod tests {
         assert_eq!(meta.etag, "CKWasoTgyPkCEAE=");
         assert_eq!(meta.content_type, "image/png");
     }
+
+    #[test]
+    fn test_check_encryption() {
+        let key = [1u8; 32];
+
+        let mut builder = GcsBuilder::default();
+        builder.encryption_key(&BASE64_STANDARD.encode(key));
+        builder.check_encryption().expect("must succeed");
+        assert_eq!(builder.encryption_algorithm.as_deref(), Some("AES256"));
+        assert_eq!(
+            builder.encryption_key_sha256,
+            Some(BASE64_STANDARD.encode(Sha256::digest(key).as_slice()))
+        );
+
+        let mut builder = GcsBuilder::default();
+        builder.encryption_key(&BASE64_STANDARD.encode([1u8; 16]));
+        assert!(builder.check_encryption().is_err());
+
+        let mut builder = GcsBuilder::default();
+        builder.encryption_key_sha256("sha256");
+        assert!(builder.check_encryption().is_err());
+    }
 }
@@ -20,15 +20,16 @@ use std::fmt::Formatter;
 use std::fmt::Write;
 use std::time::Duration;
 
-use backon::ExponentialBuilder;
-use backon::Retryable;
 use bytes::Bytes;
 use bytes::BytesMut;
+use http::header::HeaderName;
+use http::header::AUTHORIZATION;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_RANGE;
 use http::header::CONTENT_TYPE;
 use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
+use http::HeaderValue;
 use http::Request;
 use http::Response;
 use once_cell::sync::Lazy;
@@ -38,10 +39,22 @@ use reqsign::GoogleSigner;
 use reqsign::GoogleToken;
 use reqsign::GoogleTokenLoader;
 
+use super::token::GcsTokenLoader;
 use super::uri::percent_encode_path;
 use crate::raw::*;
 use crate::*;
 
+pub mod constants {
+    pub const X_GOOG_ENCRYPTION_ALGORITHM: &str = "x-goog-encryption-algorithm";
+    pub const X_GOOG_ENCRYPTION_KEY: &str = "x-goog-encryption-key";
+    pub const X_GOOG_ENCRYPTION_KEY_SHA256: &str = "x-goog-encryption-key-sha256";
+    pub const X_GOOG_COPY_SOURCE_ENCRYPTION_ALGORITHM: &str =
+        "x-goog-copy-source-encryption-algorithm";
+    pub const X_GOOG_COPY_SOURCE_ENCRYPTION_KEY: &str = "x-goog-copy-source-encryption-key";
+    pub const X_GOOG_COPY_SOURCE_ENCRYPTION_KEY_SHA256: &str =
+        "x-goog-copy-source-encryption-key-sha256";
+}
+
 pub struct GcsCore {
     pub endpoint: String,
     pub bucket: String,
@@ -50,11 +63,17 @@ pub struct GcsCore {
     pub client: HttpClient,
     pub signer: GoogleSigner,
     pub token_loader: GoogleTokenLoader,
+    pub token: Option<String>,
+    pub gcs_token_loader: Option<GcsTokenLoader>,
     pub credential_loader: GoogleCredentialLoader,
 
     pub predefined_acl: Option<String>,
     pub default_storage_class: Option<String>,
 
+    pub encryption_algorithm: Option<HeaderValue>,
+    pub encryption_key: Option<HeaderValue>,
+    pub encryption_key_sha256: Option<HeaderValue>,
+
     pub write_fixed_size: usize,
 }
 
@@ -73,10 +92,16 @@ static BACKOFF: Lazy<ExponentialBuilder> =
 
 impl GcsCore {
     async fn load_token(&self) -> Result<GoogleToken> {
-        let cred = { || self.token_loader.load() }
-            .retry(&*BACKOFF)
-            .await
-            .map_err(new_request_credential_error)?;
+        let mut backoff = BACKOFF.build();
+        let cred = loop {
+            match self.token_loader.load().await {
+                Ok(cred) => break cred,
+                Err(err) => match backoff.next() {
+                    Some(dur) => sleep(dur).await,
+                    None => return Err(new_request_credential_error(err)),
+                },
+            }
+        };
 
         if let Some(cred) = cred {
             Ok(cred)
@@ -105,6 +130,45 @@ impl GcsCore {
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        // Use the static access token directly if user provided one.
+        if let Some(token) = &self.token {
+            let value = format!("Bearer {token}").parse().map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "access token is invalid")
+                    .with_context("service", Scheme::Gcs)
+                    .set_source(err)
+            })?;
+            req.headers_mut().insert(AUTHORIZATION, value);
+            return Ok(());
+        }
+
+        // Use the tokens from metadata server or external account if
+        // there is no service account credential.
+        if let Some(loader) = &self.gcs_token_loader {
+            let mut backoff = BACKOFF.build();
+            let token = loop {
+                match loader.load().await {
+                    Ok(token) => break token,
+                    Err(err) if err.is_temporary() => match backoff.next() {
+                        Some(dur) => sleep(dur).await,
+                        None => return Err(err),
+                    },
+                    Err(err) => return Err(err),
+                }
+            };
+            let mut value: HeaderValue =
+                format_authorization_by_bearer(&token)?
+                    .parse()
+                    .map_err(|_| {
+                        Error::new(
+                            ErrorKind::Unexpected,
+                            "access token contains invalid header value",
+                        )
+                    })?;
+            value.set_sensitive(true);
+            req.headers_mut().insert(AUTHORIZATION, value);
+            return Ok(());
+        }
+
         let cred = self.load_token().await?;
 
         self.signer.sign(req, &cred).map_err(new_request_sign_error)
@@ -122,6 +186,59 @@ impl GcsCore {
     pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
         self.client.send(req).await
     }
+
+    /// Insert customer-supplied encryption key headers.
+    ///
+    /// Objects encrypted with CSEK can't be read or written without them.
+    pub fn insert_encryption_headers(&self, req: http::request::Builder) -> http::request::Builder {
+        self.insert_encryption_headers_with_names(
+            req,
+            [
+                constants::X_GOOG_ENCRYPTION_ALGORITHM,
+                constants::X_GOOG_ENCRYPTION_KEY,
+                constants::X_GOOG_ENCRYPTION_KEY_SHA256,
+            ],
+        )
+    }
+
+    /// Insert customer-supplied encryption key headers for the source
+    /// object of copy.
+    pub fn insert_copy_source_encryption_headers(
+        &self,
+        req: http::request::Builder,
+    ) -> http::request::Builder {
+        self.insert_encryption_headers_with_names(
+            req,
+            [
+                constants::X_GOOG_COPY_SOURCE_ENCRYPTION_ALGORITHM,
+                constants::X_GOOG_COPY_SOURCE_ENCRYPTION_KEY,
+                constants::X_GOOG_COPY_SOURCE_ENCRYPTION_KEY_SHA256,
+            ],
+        )
+    }
+
+    fn insert_encryption_headers_with_names(
+        &self,
+        mut req: http::request::Builder,
+        names: [&'static str; 3],
+    ) -> http::request::Builder {
+        let values = [
+            &self.encryption_algorithm,
+            &self.encryption_key,
+            &self.encryption_key_sha256,
+        ];
+
+        for (name, value) in names.into_iter().zip(values) {
+            if let Some(v) = value {
+                let mut v = v.clone();
+                v.set_sensitive(true);
+
+                req = req.header(HeaderName::from_static(name), v);
+            }
+        }
+
+        req
+    }
 }
 
 impl GcsCore {
@@ -153,6 +270,8 @@ impl GcsCore {
             req = req.header(http::header::RANGE, range.to_header());
         }
 
+        req = self.insert_encryption_headers(req);
+
         let req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
@@ -233,6 +352,8 @@ impl GcsCore {
 
         req = req.header(CONTENT_LENGTH, size.unwrap_or_default());
 
+        req = self.insert_encryption_headers(req);
+
         if let Some(storage_class) = &self.default_storage_class {
             req = req.header(CONTENT_TYPE, "multipart/related; boundary=my-boundary");
 
@@ -322,6 +443,8 @@ impl GcsCore {
             req = req.header(IF_MATCH, if_match);
         }
 
+        req = self.insert_encryption_headers(req);
+
         let req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
@@ -432,8 +555,14 @@ impl GcsCore {
             percent_encode_path(&dest)
         );
 
-        let mut req = Request::post(req_uri)
-            .header(CONTENT_LENGTH, 0)
+        let mut req = Request::post(req_uri).header(CONTENT_LENGTH, 0);
+
+        // Both source and destination objects are encrypted with the
+        // same customer-supplied key.
+        req = self.insert_encryption_headers(req);
+        req = self.insert_copy_source_encryption_headers(req);
+
+        let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
@@ -499,8 +628,11 @@ impl GcsCore {
             self.endpoint, self.bucket, p
         );
 
-        let mut req = Request::post(&url)
-            .header(CONTENT_LENGTH, 0)
+        let mut req = Request::post(&url).header(CONTENT_LENGTH, 0);
+
+        req = self.insert_encryption_headers(req);
+
+        let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
@@ -533,6 +665,8 @@ impl GcsCore {
             .header(CONTENT_LENGTH, size)
             .header(CONTENT_RANGE, range_header);
 
+        req = self.insert_encryption_headers(req);
+
         // Set body
         let req = req.body(body).map_err(new_request_build_error)?;
 
@@ -546,17 +680,18 @@ impl GcsCore {
         bs: Bytes,
     ) -> Result<Response<IncomingAsyncBody>> {
         let size = bs.len() as u64;
-        let mut req = Request::post(location)
-            .header(CONTENT_LENGTH, size)
-            .header(
-                CONTENT_RANGE,
-                format!(
-                    "bytes {}-{}/{}",
-                    written_bytes,
-                    written_bytes + size - 1,
-                    written_bytes + size
-                ),
-            )
+        let req = Request::post(location).header(CONTENT_LENGTH, size).header(
+            CONTENT_RANGE,
+            format!(
+                "bytes {}-{}/{}",
+                written_bytes,
+                written_bytes + size - 1,
+                written_bytes + size
+            ),
+        );
+
+        let mut req = self
+            .insert_encryption_headers(req)
             .body(AsyncBody::Bytes(bs))
             .map_err(new_request_build_error)?;
 
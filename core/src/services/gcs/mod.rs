@@ -21,5 +21,6 @@ pub use backend::GcsBuilder as Gcs;
 mod core;
 mod error;
 mod pager;
+mod token;
 mod uri;
 mod writer;
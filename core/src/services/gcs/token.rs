@@ -0,0 +1,455 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use http::header;
+use http::Request;
+use http::StatusCode;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::Mutex;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The endpoint of GCE metadata server.
+pub const METADATA_SERVER_ENDPOINT: &str = "http://metadata.google.internal";
+/// The scope required by service account impersonation.
+const CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// GcsTokenSource decides how to acquire OAuth2 access tokens which
+/// can't be handled by service account key files.
+pub enum GcsTokenSource {
+    /// Fetch tokens of the attached service account from the GCE/GKE
+    /// metadata server.
+    MetadataServer {
+        service_account: String,
+        endpoint: String,
+    },
+    /// Exchange the subject token of an external account for tokens via
+    /// workload identity federation.
+    ExternalAccount(ExternalAccount),
+}
+
+/// ExternalAccount is the `external_account` credential generated by
+/// `gcloud iam workload-identity-pools create-cred-config`.
+#[derive(Debug, Deserialize)]
+pub struct ExternalAccount {
+    audience: String,
+    subject_token_type: String,
+    token_url: String,
+    service_account_impersonation_url: Option<String>,
+    credential_source: CredentialSource,
+}
+
+#[derive(Debug, Deserialize)]
+struct CredentialSource {
+    file: Option<String>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    format: Option<CredentialSourceFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CredentialSourceFormat {
+    #[serde(rename = "type")]
+    format_type: String,
+    subject_token_field_name: Option<String>,
+}
+
+impl ExternalAccount {
+    /// Parse the credential content, returns `None` if it's not an
+    /// external account credential.
+    ///
+    /// The content could be either plain json or base64 encoded json.
+    pub fn parse(content: &str) -> Result<Option<Self>> {
+        let content = content.trim();
+        let bs = if content.starts_with('{') {
+            content.as_bytes().to_vec()
+        } else {
+            match general_purpose::STANDARD.decode(content) {
+                Ok(bs) => bs,
+                // Leave the content to service account credential loader.
+                Err(_) => return Ok(None),
+            }
+        };
+
+        let value: serde_json::Value = match serde_json::from_slice(&bs) {
+            Ok(v) => v,
+            Err(_) => return Ok(None),
+        };
+        if value.get("type").and_then(|v| v.as_str()) != Some("external_account") {
+            return Ok(None);
+        }
+
+        let account = serde_json::from_value(value).map_err(|err| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "external account credential is invalid",
+            )
+            .with_context("service", Scheme::Gcs)
+            .set_source(err)
+        })?;
+        Ok(Some(account))
+    }
+
+    /// Extract the subject token from the response or file content.
+    fn extract_subject_token(&self, content: &[u8]) -> Result<String> {
+        let format = match &self.credential_source.format {
+            Some(format) if format.format_type == "json" => format,
+            _ => return Ok(String::from_utf8_lossy(content).trim().to_string()),
+        };
+
+        let field = format
+            .subject_token_field_name
+            .as_deref()
+            .unwrap_or("access_token");
+        let value: serde_json::Value =
+            serde_json::from_slice(content).map_err(new_json_deserialize_error)?;
+        match value.get(field).and_then(|v| v.as_str()) {
+            Some(v) => Ok(v.to_string()),
+            None => Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "subject token field not found in credential source",
+            )
+            .with_context("subject_token_field_name", field)),
+        }
+    }
+}
+
+/// GcsTokenLoader loads OAuth2 access tokens and refreshes them before
+/// expiring.
+pub struct GcsTokenLoader {
+    client: HttpClient,
+    scope: String,
+    source: GcsTokenSource,
+    token: Mutex<GcsToken>,
+}
+
+impl Debug for GcsTokenLoader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let source = match self.source {
+            GcsTokenSource::MetadataServer { .. } => "metadata_server",
+            GcsTokenSource::ExternalAccount(_) => "external_account",
+        };
+
+        f.debug_struct("GcsTokenLoader")
+            .field("scope", &self.scope)
+            .field("source", &source)
+            .finish_non_exhaustive()
+    }
+}
+
+struct GcsToken {
+    access_token: String,
+    expires_in: DateTime<Utc>,
+}
+
+impl GcsTokenLoader {
+    pub fn new(client: HttpClient, scope: &str, source: GcsTokenSource) -> Self {
+        Self {
+            client,
+            scope: scope.to_string(),
+            source,
+            token: Mutex::new(GcsToken {
+                access_token: String::new(),
+                expires_in: DateTime::<Utc>::MIN_UTC,
+            }),
+        }
+    }
+
+    /// Load a valid access token, it will be refreshed if expired.
+    pub async fn load(&self) -> Result<String> {
+        let mut token = self.token.lock().await;
+
+        if token.expires_in <= Utc::now() {
+            let (access_token, expires_in) = match &self.source {
+                GcsTokenSource::MetadataServer {
+                    service_account,
+                    endpoint,
+                } => {
+                    self.load_from_metadata_server(service_account, endpoint)
+                        .await?
+                }
+                GcsTokenSource::ExternalAccount(account) => {
+                    self.load_from_external_account(account).await?
+                }
+            };
+
+            token.access_token = access_token;
+            // Refresh the token a bit earlier to avoid using an expired token.
+            token.expires_in = expires_in - Duration::seconds(120);
+        }
+
+        Ok(token.access_token.clone())
+    }
+
+    async fn load_from_metadata_server(
+        &self,
+        service_account: &str,
+        endpoint: &str,
+    ) -> Result<(String, DateTime<Utc>)> {
+        let req = self.build_metadata_server_request(service_account, endpoint)?;
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let resp: GcsTokenResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        Ok((
+            resp.access_token,
+            Utc::now() + Duration::seconds(resp.expires_in),
+        ))
+    }
+
+    fn build_metadata_server_request(
+        &self,
+        service_account: &str,
+        endpoint: &str,
+    ) -> Result<Request<AsyncBody>> {
+        let url = format!(
+            "{endpoint}/computeMetadata/v1/instance/service-accounts/{}/token?scopes={}",
+            utf8_percent_encode(service_account, NON_ALPHANUMERIC),
+            utf8_percent_encode(&self.scope, NON_ALPHANUMERIC)
+        );
+
+        Request::get(url)
+            .header("Metadata-Flavor", "Google")
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)
+    }
+
+    async fn load_from_external_account(
+        &self,
+        account: &ExternalAccount,
+    ) -> Result<(String, DateTime<Utc>)> {
+        let subject_token = self.load_subject_token(account).await?;
+
+        // Impersonation requires the federated token to have the
+        // cloud-platform scope.
+        let scope = if account.service_account_impersonation_url.is_some() {
+            CLOUD_PLATFORM_SCOPE
+        } else {
+            &self.scope
+        };
+        let req = self.build_sts_request(account, scope, &subject_token)?;
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let resp: GcsTokenResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        let url = match &account.service_account_impersonation_url {
+            Some(url) => url,
+            None => {
+                return Ok((
+                    resp.access_token,
+                    Utc::now() + Duration::seconds(resp.expires_in),
+                ))
+            }
+        };
+
+        let body = json!({ "scope": [self.scope] }).to_string();
+        let req = Request::post(url)
+            .header(
+                header::AUTHORIZATION,
+                format_authorization_by_bearer(&resp.access_token)?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(AsyncBody::Bytes(Bytes::from(body)))
+            .map_err(new_request_build_error)?;
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let resp: GcsImpersonationResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        Ok((
+            resp.access_token,
+            parse_datetime_from_rfc3339(&resp.expire_time)?,
+        ))
+    }
+
+    /// Load the subject token from credential source.
+    ///
+    /// The token will be loaded again while refreshing since it could be
+    /// rotated.
+    async fn load_subject_token(&self, account: &ExternalAccount) -> Result<String> {
+        let source = &account.credential_source;
+
+        if let Some(file) = &source.file {
+            let content = tokio::fs::read(file).await.map_err(|err| {
+                Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "read credential source file failed",
+                )
+                .with_context("file", file)
+                .set_source(err)
+            })?;
+            return account.extract_subject_token(&content);
+        }
+
+        if let Some(url) = &source.url {
+            let mut req = Request::get(url);
+            for (k, v) in &source.headers {
+                req = req.header(k, v);
+            }
+            let req = req
+                .body(AsyncBody::Empty)
+                .map_err(new_request_build_error)?;
+            let resp = self.client.send(req).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            return account.extract_subject_token(&bs);
+        }
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "only file and url credential sources are supported",
+        )
+        .with_context("service", Scheme::Gcs))
+    }
+
+    fn build_sts_request(
+        &self,
+        account: &ExternalAccount,
+        scope: &str,
+        subject_token: &str,
+    ) -> Result<Request<AsyncBody>> {
+        let body = format!(
+            "grant_type={}&requested_token_type={}&audience={}&scope={}&subject_token_type={}&subject_token={}",
+            utf8_percent_encode(
+                "urn:ietf:params:oauth:grant-type:token-exchange",
+                NON_ALPHANUMERIC
+            ),
+            utf8_percent_encode(
+                "urn:ietf:params:oauth:token-type:access_token",
+                NON_ALPHANUMERIC
+            ),
+            utf8_percent_encode(&account.audience, NON_ALPHANUMERIC),
+            utf8_percent_encode(scope, NON_ALPHANUMERIC),
+            utf8_percent_encode(&account.subject_token_type, NON_ALPHANUMERIC),
+            utf8_percent_encode(subject_token, NON_ALPHANUMERIC),
+        );
+
+        Request::post(&account.token_url)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(AsyncBody::Bytes(Bytes::from(body)))
+            .map_err(new_request_build_error)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GcsTokenResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GcsImpersonationResponse {
+    access_token: String,
+    expire_time: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_external_account() {
+        let content = r#"{
+  "type": "external_account",
+  "audience": "//iam.googleapis.com/projects/123/locations/global/workloadIdentityPools/pool/providers/provider",
+  "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+  "token_url": "https://sts.googleapis.com/v1/token",
+  "service_account_impersonation_url": "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/sa@project.iam.gserviceaccount.com:generateAccessToken",
+  "credential_source": {
+    "url": "http://localhost:5000/token",
+    "headers": {"Metadata": "True"},
+    "format": {"type": "json", "subject_token_field_name": "id_token"}
+  }
+}"#;
+
+        let account = ExternalAccount::parse(content)
+            .expect("must succeed")
+            .expect("must be external account");
+        assert_eq!(account.token_url, "https://sts.googleapis.com/v1/token");
+        assert_eq!(
+            account.credential_source.url.as_deref(),
+            Some("http://localhost:5000/token")
+        );
+        assert_eq!(
+            account
+                .extract_subject_token(br#"{"id_token":"subject"}"#)
+                .unwrap(),
+            "subject"
+        );
+
+        // Base64 encoded content should also be accepted.
+        let encoded = general_purpose::STANDARD.encode(content);
+        assert!(ExternalAccount::parse(&encoded).unwrap().is_some());
+
+        // Service account key files are left to reqsign.
+        let content = r#"{"type": "service_account", "client_email": "test"}"#;
+        assert!(ExternalAccount::parse(content).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_build_metadata_server_request() {
+        let loader = GcsTokenLoader::new(
+            HttpClient::new().unwrap(),
+            "https://www.googleapis.com/auth/devstorage.read_write",
+            GcsTokenSource::MetadataServer {
+                service_account: "default".to_string(),
+                endpoint: METADATA_SERVER_ENDPOINT.to_string(),
+            },
+        );
+
+        let req = loader
+            .build_metadata_server_request("default", METADATA_SERVER_ENDPOINT)
+            .unwrap();
+        assert_eq!(req.uri(), "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token?scopes=https%3A%2F%2Fwww%2Egoogleapis%2Ecom%2Fauth%2Fdevstorage%2Eread%5Fwrite");
+        assert_eq!(req.headers().get("Metadata-Flavor").unwrap(), "Google");
+    }
+}
@@ -35,11 +35,14 @@ pub struct GcsWriter {
     written: u64,
     buffer: oio::VectorCursor,
     write_fixed_size: usize,
+    /// Buffers of chunks will be taken from and returned back to the pool.
+    pool: BufferPool,
 }
 
 impl GcsWriter {
     pub fn new(core: Arc<GcsCore>, path: &str, op: OpWrite) -> Self {
         let write_fixed_size = core.write_fixed_size;
+        let pool = op.buffer_pool().cloned().unwrap_or_default();
         GcsWriter {
             core,
             path: path.to_string(),
@@ -49,6 +52,7 @@ impl GcsWriter {
             written: 0,
             buffer: oio::VectorCursor::new(),
             write_fixed_size,
+            pool,
         }
     }
 
@@ -145,12 +149,19 @@ impl oio::Write for GcsWriter {
             return Ok(());
         }
 
-        let bs = self.buffer.peak_exact(self.write_fixed_size);
+        let bs = self
+            .buffer
+            .peak_exact_with_pool(self.write_fixed_size, &self.pool);
 
-        match self.write_part(location, bs).await {
+        match self.write_part(location, bs.clone()).await {
             Ok(_) => {
                 self.buffer.take(self.write_fixed_size);
                 self.written += self.write_fixed_size as u64;
+                // The chunk has been uploaded, return its buffer back to
+                // the pool if nobody else holds it.
+                if let Ok(buf) = bs.try_into_mut() {
+                    self.pool.put(buf);
+                }
                 Ok(())
             }
             Err(e) => {
@@ -21,14 +21,14 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use http::StatusCode;
 
+use super::core::parse_gdrive_file;
 use super::core::GdriveCore;
+use super::core::GdriveFile;
 use super::error::parse_error;
+use super::pager::GdrivePager;
 use super::writer::GdriveWriter;
 use crate::raw::*;
-use crate::types::Result;
-use crate::Capability;
-use crate::Error;
-use crate::ErrorKind;
+use crate::*;
 
 #[derive(Clone, Debug)]
 pub struct GdriveBackend {
@@ -36,14 +36,9 @@ pub struct GdriveBackend {
 }
 
 impl GdriveBackend {
-    pub(crate) fn new(root: String, access_token: String, http_client: HttpClient) -> Self {
+    pub(crate) fn new(core: GdriveCore) -> Self {
         GdriveBackend {
-            core: Arc::new(GdriveCore {
-                root,
-                access_token,
-                client: http_client,
-                path_cache: Arc::default(),
-            }),
+            core: Arc::new(core),
         }
     }
 }
@@ -55,30 +50,56 @@ impl Accessor for GdriveBackend {
     type Writer = GdriveWriter;
     type BlockingWriter = ();
     type Appender = ();
-    type Pager = ();
+    type Pager = GdrivePager;
     type BlockingPager = ();
 
     fn info(&self) -> AccessorInfo {
         let mut ma = AccessorInfo::default();
-        ma.set_scheme(crate::Scheme::Gdrive)
+        ma.set_scheme(Scheme::Gdrive)
             .set_root(&self.core.root)
             .set_capability(Capability {
+                stat: true,
+
                 read: true,
+                read_can_next: true,
+                read_with_range: true,
+
                 write: true,
+                write_without_content_length: true,
+                write_with_content_type: true,
+
+                create_dir: true,
                 delete: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_delimiter_slash: true,
+
                 ..Default::default()
             });
 
         ma
     }
 
-    async fn read(&self, path: &str, _args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self.core.gdrive_get(path).await?;
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.core.ensure_dir(path).await?;
+
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let file_id = self
+            .core
+            .get_file_id_by_path(path)
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "path not found in google drive"))?;
+
+        let resp = self.core.gdrive_get(&file_id, args.range()).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::OK => {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
                 let meta = parse_into_metadata(path, resp.headers())?;
                 Ok((RpRead::with_metadata(meta), resp.into_body()))
             }
@@ -87,27 +108,78 @@ impl Accessor for GdriveBackend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        if args.content_length().is_none() {
-            return Err(Error::new(
-                ErrorKind::Unsupported,
-                "write without content length is not supported",
-            ));
-        }
-
         Ok((
             RpWrite::default(),
             GdriveWriter::new(self.core.clone(), args, String::from(path)),
         ))
     }
 
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let file_id = self
+            .core
+            .get_file_id_by_path(path)
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "path not found in google drive"))?;
+
+        let resp = self.core.gdrive_stat(&file_id).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let file: GdriveFile =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+                let meta = parse_gdrive_file(&file)?;
+
+                if path.ends_with('/') && !meta.mode().is_dir() {
+                    return Err(Error::new(
+                        ErrorKind::NotFound,
+                        "the path is a file but a dir is expected",
+                    ));
+                }
+
+                Ok(RpStat::new(meta))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.gdrive_delete(path).await?;
+        // The root of user's drive can't be deleted.
+        if build_rooted_abs_path(&self.core.root, path) == "/" {
+            return Ok(RpDelete::default());
+        }
+
+        let file_id = match self.core.get_file_id_by_path(path).await? {
+            Some(file_id) => file_id,
+            // Deleting a not exist path should succeed.
+            None => return Ok(RpDelete::default()),
+        };
+
+        let resp = self.core.gdrive_delete(&file_id).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::NO_CONTENT => Ok(RpDelete::default()),
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                self.core.remove_path_cache(path).await;
+                Ok(RpDelete::default())
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((
+            RpList::default(),
+            GdrivePager::new(self.core.clone(), path, args.limit()),
+        ))
+    }
 }
@@ -18,36 +18,62 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::sync::Arc;
 
 use log::debug;
+use reqsign::GoogleCredentialLoader;
+use reqsign::GoogleTokenLoader;
+use tokio::sync::Mutex;
 
 use super::backend::GdriveBackend;
+use super::core::GdriveCore;
+use super::core::GdriveSigner;
 use crate::raw::normalize_root;
 use crate::raw::HttpClient;
 use crate::Scheme;
 use crate::*;
 
+const DEFAULT_GDRIVE_SCOPE: &str = "https://www.googleapis.com/auth/drive";
+
 /// [GoogleDrive](https://drive.google.com/) backend support.
 ///
 /// # Capabilities
 ///
 /// This service can be used to:
 ///
+/// - [x] stat
 /// - [x] read
 /// - [x] write
+/// - [x] create_dir
 /// - [x] delete
 /// - [ ] copy
-/// - [ ] create
-/// - [ ] list
 /// - [ ] rename
+/// - [x] list
+/// - [ ] ~~scan~~
+/// - [ ] ~~presign~~
+/// - [ ] blocking
 ///
 /// # Notes
 ///
+/// Google Drive identifies files by id instead of path, OpenDAL resolves
+/// the id of every path and caches it internally. Please don't modify the
+/// same drive via other clients while using OpenDAL, or the cached ids
+/// could be outdated.
+///
+/// All files are uploaded via [resumable upload](https://developers.google.com/drive/api/guides/manage-uploads#resumable).
 ///
 /// # Configuration
 ///
-/// - `access_token`: set the access_token for google drive api
 /// - `root`: Set the work directory for backend
+/// - `access_token`: Set the access_token for google drive api
+/// - `refresh_token`: Set the refresh_token for google drive api
+/// - `client_id`: Set the client_id for google drive api
+/// - `client_secret`: Set the client_secret for google drive api
+/// - `credential`: Set the base64 encoded service account key
+/// - `credential_path`: Set the path to the service account key file
+///
+/// One of `access_token`, `refresh_token` with `client_id` and
+/// `client_secret`, or `credential`/`credential_path` must be set.
 ///
 /// You can refer to [`GdriveBuilder`]'s docs for more information
 ///
@@ -65,7 +91,11 @@ use crate::*;
 ///     // create backend builder
 ///     let mut builder = Gdrive::default();
 ///
-///     builder.access_token("xxx").root("/path/to/root");
+///     builder
+///         .root("/path/to/root")
+///         .refresh_token("<refresh_token>")
+///         .client_id("<client_id>")
+///         .client_secret("<client_secret>");
 ///
 ///     let op: Operator = Operator::new(builder)?.finish();
 ///
@@ -79,27 +109,95 @@ use crate::*;
 /// ```
 #[derive(Default)]
 pub struct GdriveBuilder {
-    access_token: Option<String>,
     root: Option<String>,
+
+    access_token: Option<String>,
+
+    refresh_token: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+
+    credential: Option<String>,
+    credential_path: Option<String>,
+
     http_client: Option<HttpClient>,
 }
 
 impl Debug for GdriveBuilder {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Backend").field("root", &self.root).finish()
+        f.debug_struct("Backend")
+            .field("root", &self.root)
+            .field("client_id", &self.client_id)
+            .field("credential_path", &self.credential_path)
+            .finish()
     }
 }
 
 impl GdriveBuilder {
-    /// default: no access token, which leads to failure
+    /// Set root path of GoogleDrive folder.
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        self.root = Some(root.to_string());
+        self
+    }
+
+    /// Access token is used for temporary access to the GoogleDrive API.
+    ///
+    /// NOTE: this token will be expired in 1 hour. If you are trying to use
+    /// GoogleDrive for a long time, please set `refresh_token` instead.
     pub fn access_token(&mut self, access_token: &str) -> &mut Self {
-        self.access_token = Some(access_token.to_string());
+        if !access_token.is_empty() {
+            self.access_token = Some(access_token.to_string());
+        }
         self
     }
 
-    /// Set root path of GoogleDrive folder.
-    pub fn root(&mut self, root: &str) -> &mut Self {
-        self.root = Some(root.to_string());
+    /// Refresh token is used for long term access to the GoogleDrive API.
+    ///
+    /// OpenDAL will use this refresh token to get a new access token when
+    /// the old one is expired.
+    pub fn refresh_token(&mut self, refresh_token: &str) -> &mut Self {
+        if !refresh_token.is_empty() {
+            self.refresh_token = Some(refresh_token.to_string());
+        }
+        self
+    }
+
+    /// Set the client id of the OAuth 2.0 client for GoogleDrive.
+    ///
+    /// This is required for refreshing the access token.
+    pub fn client_id(&mut self, client_id: &str) -> &mut Self {
+        if !client_id.is_empty() {
+            self.client_id = Some(client_id.to_string());
+        }
+        self
+    }
+
+    /// Set the client secret of the OAuth 2.0 client for GoogleDrive.
+    ///
+    /// This is required for refreshing the access token.
+    pub fn client_secret(&mut self, client_secret: &str) -> &mut Self {
+        if !client_secret.is_empty() {
+            self.client_secret = Some(client_secret.to_string());
+        }
+        self
+    }
+
+    /// Set the base64 encoded service account key used for OAuth2.
+    ///
+    /// NOTE: files created by service account are stored in the drive of
+    /// the service account, not the user's.
+    pub fn credential(&mut self, credential: &str) -> &mut Self {
+        if !credential.is_empty() {
+            self.credential = Some(credential.to_string());
+        }
+        self
+    }
+
+    /// Set the path to the service account key file used for OAuth2.
+    pub fn credential_path(&mut self, path: &str) -> &mut Self {
+        if !path.is_empty() {
+            self.credential_path = Some(path.to_string());
+        }
         self
     }
 
@@ -125,6 +223,12 @@ impl Builder for GdriveBuilder {
 
         map.get("root").map(|v| builder.root(v));
         map.get("access_token").map(|v| builder.access_token(v));
+        map.get("refresh_token").map(|v| builder.refresh_token(v));
+        map.get("client_id").map(|v| builder.client_id(v));
+        map.get("client_secret").map(|v| builder.client_secret(v));
+        map.get("credential").map(|v| builder.credential(v));
+        map.get("credential_path")
+            .map(|v| builder.credential_path(v));
 
         builder
     }
@@ -142,9 +246,74 @@ impl Builder for GdriveBuilder {
             })?
         };
 
-        match self.access_token.clone() {
-            Some(access_token) => Ok(GdriveBackend::new(root, access_token, client)),
-            None => Err(Error::new(ErrorKind::ConfigInvalid, "access_token not set")),
-        }
+        let signer = if let Some(access_token) = self.access_token.take() {
+            GdriveSigner {
+                access_token,
+                ..Default::default()
+            }
+        } else if let Some(refresh_token) = self.refresh_token.take() {
+            let client_id = self.client_id.take().ok_or_else(|| {
+                Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "client_id must be set when refresh_token is set",
+                )
+                .with_context("service", Scheme::Gdrive)
+            })?;
+            let client_secret = self.client_secret.take().ok_or_else(|| {
+                Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "client_secret must be set when refresh_token is set",
+                )
+                .with_context("service", Scheme::Gdrive)
+            })?;
+
+            GdriveSigner {
+                refresh_token,
+                client_id,
+                client_secret,
+                ..Default::default()
+            }
+        } else if self.credential.is_some() || self.credential_path.is_some() {
+            let mut cred_loader = GoogleCredentialLoader::default();
+            if let Some(cred) = &self.credential {
+                cred_loader = cred_loader.with_content(cred);
+            }
+            if let Some(cred) = &self.credential_path {
+                cred_loader = cred_loader.with_path(cred);
+            }
+
+            let cred = cred_loader
+                .load()
+                .map_err(|err| {
+                    Error::new(ErrorKind::ConfigInvalid, "load credential failed")
+                        .with_context("service", Scheme::Gdrive)
+                        .set_source(err)
+                })?
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::ConfigInvalid, "no valid credential found")
+                        .with_context("service", Scheme::Gdrive)
+                })?;
+
+            let token_loader = GoogleTokenLoader::new(DEFAULT_GDRIVE_SCOPE, client.client())
+                .with_credentials(cred);
+
+            GdriveSigner {
+                token_loader: Some(token_loader),
+                ..Default::default()
+            }
+        } else {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "access_token, refresh_token or credential must be set",
+            )
+            .with_context("service", Scheme::Gdrive));
+        };
+
+        Ok(GdriveBackend::new(GdriveCore {
+            root,
+            client,
+            signer: Arc::new(Mutex::new(signer)),
+            path_cache: Arc::default(),
+        }))
     }
 }
@@ -20,30 +20,42 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
 use http::header;
-use http::request::Builder;
 use http::Request;
 use http::Response;
 use http::StatusCode;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use reqsign::GoogleSigner;
+use reqsign::GoogleTokenLoader;
 use serde::Deserialize;
+use serde_json::json;
 use tokio::sync::Mutex;
 
 use super::error::parse_error;
-use crate::raw::build_rooted_abs_path;
-use crate::raw::new_json_deserialize_error;
-use crate::raw::new_request_build_error;
-use crate::raw::percent_encode_path;
-use crate::raw::AsyncBody;
-use crate::raw::HttpClient;
-use crate::raw::IncomingAsyncBody;
-use crate::types::Result;
-use crate::Error;
-use crate::ErrorKind;
+use crate::raw::*;
+use crate::*;
+
+const GDRIVE_API_ENDPOINT: &str = "https://www.googleapis.com/drive/v3";
+const GDRIVE_UPLOAD_ENDPOINT: &str = "https://www.googleapis.com/upload/drive/v3";
+const GDRIVE_TOKEN_ENDPOINT: &str = "https://oauth2.googleapis.com/token";
+/// The file id alias of the root folder of user's drive.
+const GDRIVE_ROOT_ID: &str = "root";
+pub const GDRIVE_FOLDER_MIME_TYPE: &str = "application/vnd.google-apps.folder";
+/// The fields we need in the returning file resources.
+const GDRIVE_FILE_FIELDS: &str = "id,name,mimeType,size,modifiedTime";
 
 pub struct GdriveCore {
     pub root: String,
-    pub access_token: String,
     pub client: HttpClient,
+    pub signer: Arc<Mutex<GdriveSigner>>,
+    /// Cache the mapping from path to file id.
+    ///
+    /// The key is the rooted absolute path without the trailing `/`.
     pub path_cache: Arc<Mutex<HashMap<String, String>>>,
 }
 
@@ -56,163 +68,502 @@ impl Debug for GdriveCore {
 }
 
 impl GdriveCore {
-    async fn get_abs_root_id(&self) -> Result<String> {
-        let root = "root";
+    /// Build the key of path cache.
+    fn build_cache_key(&self, path: &str) -> String {
+        let path = build_rooted_abs_path(&self.root, path);
+        path.trim_end_matches('/').to_string()
+    }
 
-        if let Some(root_id) = self.path_cache.lock().await.get(root) {
-            return Ok(root_id.to_string());
+    /// Resolve the file id of given path.
+    ///
+    /// Returns `None` if the path doesn't exist. All ids resolved during
+    /// the lookup will be cached.
+    pub async fn get_file_id_by_path(&self, path: &str) -> Result<Option<String>> {
+        let path = self.build_cache_key(path);
+        if path.is_empty() {
+            return Ok(Some(GDRIVE_ROOT_ID.to_string()));
         }
 
-        let req = self
-            .sign(Request::get(
-                "https://www.googleapis.com/drive/v3/files/root",
-            ))
-            .body(AsyncBody::Empty)
-            .map_err(new_request_build_error)?;
-
-        let resp = self.client.send(req).await?;
-        let status = resp.status();
-
-        match status {
-            StatusCode::OK => {
-                let resp_body = &resp.into_body().bytes().await?;
+        if let Some(id) = self.path_cache.lock().await.get(&path) {
+            return Ok(Some(id.to_string()));
+        }
 
-                let gdrive_file: GdriveFile =
-                    serde_json::from_slice(resp_body).map_err(new_json_deserialize_error)?;
+        let mut parent_id = GDRIVE_ROOT_ID.to_string();
+        let mut current = String::new();
+        let items: Vec<&str> = path.split('/').filter(|v| !v.is_empty()).collect();
 
-                let root_id = gdrive_file.id;
+        for (idx, item) in items.iter().enumerate() {
+            current.push('/');
+            current.push_str(item);
 
-                let mut cache_guard = self.path_cache.lock().await;
-                cache_guard.insert(root.to_owned(), root_id.clone());
+            if let Some(id) = self.path_cache.lock().await.get(&current) {
+                parent_id = id.to_string();
+                continue;
+            }
 
-                Ok(root_id)
+            // Only the last item could be a file.
+            let folder_only = idx != items.len() - 1;
+            match self.gdrive_search(&parent_id, item, folder_only).await? {
+                Some(id) => {
+                    self.path_cache
+                        .lock()
+                        .await
+                        .insert(current.clone(), id.clone());
+                    parent_id = id;
+                }
+                None => return Ok(None),
             }
-            _ => Err(parse_error(resp).await?),
         }
+
+        Ok(Some(parent_id))
     }
 
-    async fn get_file_id_by_path(&self, file_path: &str) -> Result<String> {
-        let path = build_rooted_abs_path(&self.root, file_path);
+    /// Make sure all folders of given path exist and return the id of the
+    /// last folder.
+    pub async fn ensure_dir(&self, path: &str) -> Result<String> {
+        let path = self.build_cache_key(path);
 
-        if let Some(file_id) = self.path_cache.lock().await.get(&path) {
-            return Ok(file_id.to_string());
-        }
+        let mut parent_id = GDRIVE_ROOT_ID.to_string();
+        let mut current = String::new();
 
-        let mut parent_id = self.get_abs_root_id().await?;
-        let file_path_items: Vec<&str> = path.split('/').filter(|&x| !x.is_empty()).collect();
+        for item in path.split('/').filter(|v| !v.is_empty()) {
+            current.push('/');
+            current.push_str(item);
 
-        for (i, item) in file_path_items.iter().enumerate() {
-            let mut query = format!(
-                "name = '{}' and parents = '{}' and trashed = false",
-                item, parent_id
-            );
-            if i != file_path_items.len() - 1 {
-                query += "and mimeType = 'application/vnd.google-apps.folder'";
+            if let Some(id) = self.path_cache.lock().await.get(&current) {
+                parent_id = id.to_string();
+                continue;
             }
 
-            let req = self
-                .sign(Request::get(format!(
-                    "https://www.googleapis.com/drive/v3/files?q={}",
-                    percent_encode_path(&query)
-                )))
-                .body(AsyncBody::default())
-                .map_err(new_request_build_error)?;
+            let id = match self.gdrive_search(&parent_id, item, true).await? {
+                Some(id) => id,
+                None => self.gdrive_create_folder(&parent_id, item).await?,
+            };
+            self.path_cache
+                .lock()
+                .await
+                .insert(current.clone(), id.clone());
+            parent_id = id;
+        }
 
-            let resp = self.client.send(req).await?;
-            let status = resp.status();
+        Ok(parent_id)
+    }
 
-            if status == StatusCode::OK {
-                let resp_body = &resp.into_body().bytes().await?;
+    /// Insert the file id of given path into cache.
+    pub async fn insert_path_cache(&self, path: &str, id: &str) {
+        let path = self.build_cache_key(path);
+        self.path_cache.lock().await.insert(path, id.to_string());
+    }
 
-                let gdrive_file_list: GdriveFileList =
-                    serde_json::from_slice(resp_body).map_err(new_json_deserialize_error)?;
+    /// Remove the given path and all its children from cache.
+    pub async fn remove_path_cache(&self, path: &str) {
+        let path = self.build_cache_key(path);
+        let prefix = format!("{path}/");
 
-                if gdrive_file_list.files.len() != 1 {
-                    return Err(Error::new(ErrorKind::Unexpected, &format!("Please ensure that the file corresponding to the path exists and is unique. The response body is {}", String::from_utf8_lossy(resp_body))));
-                }
+        self.path_cache
+            .lock()
+            .await
+            .retain(|k, _| k != &path && !k.starts_with(&prefix));
+    }
 
-                parent_id = gdrive_file_list.files[0].id.clone();
-            } else {
-                return Err(parse_error(resp).await?);
+    /// Search the file id by name under given parent.
+    async fn gdrive_search(
+        &self,
+        parent_id: &str,
+        name: &str,
+        folder_only: bool,
+    ) -> Result<Option<String>> {
+        let mut query = format!(
+            "name = '{}' and '{}' in parents and trashed = false",
+            escape_query(name),
+            parent_id
+        );
+        if folder_only {
+            query += &format!(" and mimeType = '{GDRIVE_FOLDER_MIME_TYPE}'");
+        }
+
+        let mut req = Request::get(format!(
+            "{GDRIVE_API_ENDPOINT}/files?q={}&fields=files(id)",
+            utf8_percent_encode(&query, NON_ALPHANUMERIC)
+        ))
+        .body(AsyncBody::Empty)
+        .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let output: GdriveFileList =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+                // Google Drive allows files with the same name, we will
+                // always use the first one.
+                Ok(output.files.into_iter().next().map(|v| v.id))
             }
+            _ => Err(parse_error(resp).await?),
         }
+    }
 
-        let mut cache_guard = self.path_cache.lock().await;
-        cache_guard.insert(path, parent_id.clone());
+    async fn gdrive_create_folder(&self, parent_id: &str, name: &str) -> Result<String> {
+        let body = json!({
+            "name": name,
+            "mimeType": GDRIVE_FOLDER_MIME_TYPE,
+            "parents": [parent_id],
+        });
+        let bs = Bytes::from(serde_json::to_vec(&body).map_err(new_json_serialize_error)?);
+
+        let mut req = Request::post(format!("{GDRIVE_API_ENDPOINT}/files?fields=id"))
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_LENGTH, bs.len())
+            .body(AsyncBody::Bytes(bs))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
 
-        Ok(parent_id)
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let file: GdriveFile =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+                Ok(file.id)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
     }
 
-    pub async fn gdrive_get(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let url: String = format!(
-            "https://www.googleapis.com/drive/v3/files/{}?alt=media",
-            self.get_file_id_by_path(path).await?
-        );
+    pub async fn gdrive_stat(&self, file_id: &str) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::get(format!(
+            "{GDRIVE_API_ENDPOINT}/files/{file_id}?fields={GDRIVE_FILE_FIELDS}"
+        ))
+        .body(AsyncBody::Empty)
+        .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn gdrive_get(
+        &self,
+        file_id: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::get(format!("{GDRIVE_API_ENDPOINT}/files/{file_id}?alt=media"));
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        self.client.send(req).await
+    }
 
-        let req = self
-            .sign(Request::get(&url))
+    pub async fn gdrive_delete(&self, file_id: &str) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::delete(format!("{GDRIVE_API_ENDPOINT}/files/{file_id}"))
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
 
         self.client.send(req).await
     }
 
-    pub async fn gdrive_update(
+    pub async fn gdrive_list(
         &self,
-        path: &str,
-        size: Option<usize>,
-        content_type: Option<&str>,
-        body: AsyncBody,
+        parent_id: &str,
+        page_size: Option<usize>,
+        page_token: &str,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let url = format!(
-            "https://www.googleapis.com/upload/drive/v3/files/{}",
-            self.get_file_id_by_path(path).await?
+        let query = format!("'{parent_id}' in parents and trashed = false");
+        let mut url = format!(
+            "{GDRIVE_API_ENDPOINT}/files?q={}&fields=nextPageToken,files({GDRIVE_FILE_FIELDS})",
+            utf8_percent_encode(&query, NON_ALPHANUMERIC)
         );
+        if let Some(page_size) = page_size {
+            url.push_str(&format!("&pageSize={page_size}"));
+        }
+        if !page_token.is_empty() {
+            url.push_str(&format!(
+                "&pageToken={}",
+                utf8_percent_encode(page_token, NON_ALPHANUMERIC)
+            ));
+        }
 
-        let mut req = Request::patch(&url);
+        let mut req = Request::get(url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
 
-        if let Some(size) = size {
-            req = req.header(header::CONTENT_LENGTH, size)
-        }
+        self.client.send(req).await
+    }
 
+    /// Initiate a resumable upload session.
+    ///
+    /// The file will be updated if `file_id` is given, otherwise a new file
+    /// named `name` will be created under `parent_id`.
+    pub async fn gdrive_initiate_resumable_upload(
+        &self,
+        file_id: Option<&str>,
+        parent_id: &str,
+        name: &str,
+        content_type: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let (req, body) = match file_id {
+            Some(file_id) => (
+                Request::patch(format!(
+                    "{GDRIVE_UPLOAD_ENDPOINT}/files/{file_id}?uploadType=resumable&fields=id"
+                )),
+                json!({}),
+            ),
+            None => (
+                Request::post(format!(
+                    "{GDRIVE_UPLOAD_ENDPOINT}/files?uploadType=resumable&fields=id"
+                )),
+                json!({
+                    "name": name,
+                    "parents": [parent_id],
+                }),
+            ),
+        };
+        let bs = Bytes::from(serde_json::to_vec(&body).map_err(new_json_serialize_error)?);
+
+        let mut req = req
+            .header(header::CONTENT_TYPE, "application/json; charset=UTF-8")
+            .header(header::CONTENT_LENGTH, bs.len());
         if let Some(mime) = content_type {
-            req = req.header(header::CONTENT_TYPE, mime)
+            req = req.header("X-Upload-Content-Type", mime);
         }
 
-        let req = self.sign(req).body(body).map_err(new_request_build_error)?;
+        let mut req = req
+            .body(AsyncBody::Bytes(bs))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
 
         self.client.send(req).await
     }
 
-    pub async fn gdrive_delete(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let url = format!(
-            "https://www.googleapis.com/drive/v3/files/{}",
-            self.get_file_id_by_path(path).await?
-        );
-
-        let req = self
-            .sign(Request::delete(&url))
-            .body(AsyncBody::Empty)
+    /// Upload a chunk into the resumable upload session.
+    ///
+    /// `total` should only be set for the last chunk.
+    pub async fn gdrive_upload_chunk(
+        &self,
+        session_uri: &str,
+        offset: u64,
+        total: Option<u64>,
+        bs: Bytes,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let size = bs.len() as u64;
+        let total = total.map_or_else(|| "*".to_string(), |v| v.to_string());
+        let range = if size == 0 {
+            format!("bytes */{total}")
+        } else {
+            format!("bytes {}-{}/{total}", offset, offset + size - 1)
+        };
+
+        let mut req = Request::put(session_uri)
+            .header(header::CONTENT_LENGTH, size)
+            .header(header::CONTENT_RANGE, range)
+            .body(AsyncBody::Bytes(bs))
             .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
 
         self.client.send(req).await
     }
 
-    fn sign(&self, mut req: Builder) -> Builder {
-        let auth_header_content = format!("Bearer {}", self.access_token);
-        req = req.header(header::AUTHORIZATION, auth_header_content);
-        req
+    /// Sign the request with access token.
+    pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        let mut signer = self.signer.lock().await;
+
+        // Load token via service account if configured.
+        if let Some(loader) = &signer.token_loader {
+            let token = loader
+                .load()
+                .await
+                .map_err(new_request_credential_error)?
+                .ok_or_else(|| Error::new(ErrorKind::ConfigInvalid, "no valid credential found"))?;
+            return signer
+                .signer
+                .sign(req, &token)
+                .map_err(new_request_sign_error);
+        }
+
+        if !signer.refresh_token.is_empty() && signer.expires_in <= Utc::now() {
+            let body = format!(
+                "grant_type=refresh_token&refresh_token={}&client_id={}&client_secret={}",
+                utf8_percent_encode(&signer.refresh_token, NON_ALPHANUMERIC),
+                utf8_percent_encode(&signer.client_id, NON_ALPHANUMERIC),
+                utf8_percent_encode(&signer.client_secret, NON_ALPHANUMERIC),
+            );
+
+            let request = Request::post(GDRIVE_TOKEN_ENDPOINT)
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .header(header::CONTENT_LENGTH, body.len())
+                .body(AsyncBody::Bytes(Bytes::from(body)))
+                .map_err(new_request_build_error)?;
+
+            let resp = self.client.send(request).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let token: GdriveTokenResponse =
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+            signer.access_token = token.access_token;
+            // Refresh the token a bit earlier to avoid using an expired token.
+            signer.expires_in =
+                Utc::now() + Duration::seconds(token.expires_in as i64) - Duration::seconds(120);
+        }
+
+        let value = format_authorization_by_bearer(&signer.access_token)?
+            .parse()
+            .map_err(|_| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "access token contains invalid header value",
+                )
+            })?;
+        req.headers_mut().insert(header::AUTHORIZATION, value);
+        Ok(())
     }
 }
 
+/// GdriveSigner holds the credentials used to sign requests.
+///
+/// - Service account: `token_loader` will be used to load token.
+/// - OAuth2: `access_token` will be refreshed via `refresh_token` if
+///   it's expired.
+/// - Static access token: `access_token` will be used directly.
+pub struct GdriveSigner {
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: String,
+
+    pub access_token: String,
+    pub expires_in: DateTime<Utc>,
+
+    pub token_loader: Option<GoogleTokenLoader>,
+    pub signer: GoogleSigner,
+}
+
+impl Default for GdriveSigner {
+    fn default() -> Self {
+        GdriveSigner {
+            client_id: String::new(),
+            client_secret: String::new(),
+            refresh_token: String::new(),
+
+            access_token: String::new(),
+            expires_in: DateTime::<Utc>::MIN_UTC,
+
+            token_loader: None,
+            signer: GoogleSigner::new("drive"),
+        }
+    }
+}
+
+/// Escape `\` and `'` in the query string of google drive.
+///
+/// Ref: <https://developers.google.com/drive/api/guides/ref-search-terms>
+fn escape_query(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Parse google drive file into opendal's metadata.
+pub fn parse_gdrive_file(file: &GdriveFile) -> Result<Metadata> {
+    let mut meta = if file.mime_type == GDRIVE_FOLDER_MIME_TYPE {
+        Metadata::new(EntryMode::DIR)
+    } else {
+        Metadata::new(EntryMode::FILE)
+    };
+
+    if let Some(size) = &file.size {
+        meta.set_content_length(size.parse::<u64>().map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "parse content length").set_source(e)
+        })?);
+    }
+    if !file.mime_type.is_empty() && file.mime_type != GDRIVE_FOLDER_MIME_TYPE {
+        meta.set_content_type(&file.mime_type);
+    }
+    if !file.modified_time.is_empty() {
+        meta.set_last_modified(parse_datetime_from_rfc3339(&file.modified_time)?);
+    }
+
+    Ok(meta)
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct GdriveTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
 // refer to https://developers.google.com/drive/api/reference/rest/v3/files#File
-#[derive(Deserialize)]
-struct GdriveFile {
-    id: String,
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GdriveFile {
+    pub id: String,
+    pub name: String,
+    pub mime_type: String,
+    // Google Drive returns int64 as string.
+    pub size: Option<String>,
+    pub modified_time: String,
 }
 
 // refer to https://developers.google.com/drive/api/reference/rest/v3/files/list
-#[derive(Deserialize)]
-struct GdriveFileList {
-    files: Vec<GdriveFile>,
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GdriveFileList {
+    pub files: Vec<GdriveFile>,
+    pub next_page_token: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_query() {
+        assert_eq!(escape_query("abc"), "abc");
+        assert_eq!(escape_query("it's"), "it\\'s");
+        assert_eq!(escape_query("a\\b"), "a\\\\b");
+    }
+
+    #[test]
+    fn test_parse_file_list() {
+        let content = r#"{
+            "nextPageToken": "~!!~AI9FV7S",
+            "files": [
+                {
+                    "id": "1TVBwHzLYjIJnZ7RkTgPbLrhuzq0L0S6H",
+                    "name": "test.txt",
+                    "mimeType": "text/plain",
+                    "size": "1024",
+                    "modifiedTime": "2023-06-12T07:37:21.622Z"
+                },
+                {
+                    "id": "1XcNhYySX6hsdbGNphq9MYRXtSgtQqjt8",
+                    "name": "dir",
+                    "mimeType": "application/vnd.google-apps.folder",
+                    "modifiedTime": "2023-06-12T07:37:21.622Z"
+                }
+            ]
+        }"#;
+
+        let output: GdriveFileList = serde_json::from_str(content).expect("response must be valid");
+        assert_eq!(output.next_page_token.as_deref(), Some("~!!~AI9FV7S"));
+        assert_eq!(output.files.len(), 2);
+
+        let file = parse_gdrive_file(&output.files[0]).expect("must succeed");
+        assert_eq!(file.mode(), EntryMode::FILE);
+        assert_eq!(file.content_length(), 1024);
+        assert_eq!(file.content_type(), Some("text/plain"));
+
+        let dir = parse_gdrive_file(&output.files[1]).expect("must succeed");
+        assert_eq!(dir.mode(), EntryMode::DIR);
+    }
 }
@@ -19,6 +19,7 @@ mod backend;
 mod builder;
 mod core;
 mod error;
+mod pager;
+mod writer;
 
 pub use builder::GdriveBuilder as Gdrive;
-mod writer;
@@ -0,0 +1,113 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::core::parse_gdrive_file;
+use super::core::GdriveCore;
+use super::core::GdriveFileList;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct GdrivePager {
+    core: Arc<GdriveCore>,
+    path: String,
+    limit: Option<usize>,
+
+    parent_id: Option<String>,
+    page_token: String,
+    done: bool,
+}
+
+impl GdrivePager {
+    pub fn new(core: Arc<GdriveCore>, path: &str, limit: Option<usize>) -> Self {
+        GdrivePager {
+            core,
+            path: path.to_string(),
+            limit,
+
+            parent_id: None,
+            page_token: String::new(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for GdrivePager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let parent_id = match &self.parent_id {
+            Some(parent_id) => parent_id.clone(),
+            None => match self.core.get_file_id_by_path(&self.path).await? {
+                Some(parent_id) => {
+                    self.parent_id = Some(parent_id.clone());
+                    parent_id
+                }
+                // Listing a not exist dir should return empty.
+                None => {
+                    self.done = true;
+                    return Ok(None);
+                }
+            },
+        };
+
+        let resp = self
+            .core
+            .gdrive_list(&parent_id, self.limit, &self.page_token)
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: GdriveFileList =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        match output.next_page_token {
+            Some(token) if !token.is_empty() => self.page_token = token,
+            _ => self.done = true,
+        }
+
+        let parent = if self.path == "/" { "" } else { &self.path };
+
+        let mut entries = Vec::with_capacity(output.files.len());
+        for file in output.files {
+            let meta = parse_gdrive_file(&file)?;
+
+            let path = if meta.mode().is_dir() {
+                format!("{parent}{}/", file.name)
+            } else {
+                format!("{parent}{}", file.name)
+            };
+
+            // Cache the id so that later operations on this path don't need
+            // to resolve it again.
+            self.core.insert_path_cache(&path, &file.id).await;
+            entries.push(oio::Entry::new(&path, meta));
+        }
+
+        Ok(Some(entries))
+    }
+}
@@ -22,39 +22,98 @@ use bytes::Bytes;
 use http::StatusCode;
 
 use super::core::GdriveCore;
+use super::core::GdriveFile;
 use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+/// The size of every chunk uploaded into the resumable session.
+///
+/// Google Drive requires chunks to be multiple of 256 KiB except the last one.
+const GDRIVE_UPLOAD_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
 pub struct GdriveWriter {
     core: Arc<GdriveCore>,
     op: OpWrite,
     path: String,
+
+    session_uri: Option<String>,
+    offset: u64,
+    buffer: oio::VectorCursor,
+    buffer_size: usize,
 }
 
 impl GdriveWriter {
     pub fn new(core: Arc<GdriveCore>, op: OpWrite, path: String) -> Self {
-        GdriveWriter { core, op, path }
+        GdriveWriter {
+            core,
+            op,
+            path,
+
+            session_uri: None,
+            offset: 0,
+            buffer: oio::VectorCursor::new(),
+            buffer_size: GDRIVE_UPLOAD_CHUNK_SIZE,
+        }
     }
-}
 
-#[async_trait]
-impl oio::Write for GdriveWriter {
-    async fn write(&mut self, bs: Bytes) -> Result<()> {
+    /// Initiate a resumable upload session for the path.
+    ///
+    /// The existing file will be updated in place, otherwise a new file will
+    /// be created with all its parent folders.
+    async fn initiate_upload(&self) -> Result<String> {
+        let file_id = self.core.get_file_id_by_path(&self.path).await?;
+
+        let (parent, name) = match self.path.rsplit_once('/') {
+            Some((parent, name)) => (format!("{parent}/"), name),
+            None => ("/".to_string(), self.path.as_str()),
+        };
+        let parent_id = match &file_id {
+            Some(_) => String::new(),
+            None => self.core.ensure_dir(&parent).await?,
+        };
+
         let resp = self
             .core
-            .gdrive_update(
-                &self.path,
-                Some(bs.len()),
+            .gdrive_initiate_resumable_upload(
+                file_id.as_deref(),
+                &parent_id,
+                name,
                 self.op.content_type(),
-                AsyncBody::Bytes(bs),
             )
             .await?;
 
         let status = resp.status();
-
         match status {
             StatusCode::OK => {
+                let session_uri = parse_location(resp.headers())?
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::Unexpected,
+                            "location not present in returning response",
+                        )
+                    })?
+                    .to_string();
+                resp.into_body().consume().await?;
+
+                Ok(session_uri)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Upload a chunk that is not the last one.
+    async fn write_chunk(&self, session_uri: &str, bs: Bytes) -> Result<()> {
+        let resp = self
+            .core
+            .gdrive_upload_chunk(session_uri, self.offset, None, bs)
+            .await?;
+
+        let status = resp.status();
+        match status {
+            // Google Drive returns `308 Resume Incomplete` if the upload
+            // is not finished yet.
+            StatusCode::PERMANENT_REDIRECT => {
                 resp.into_body().consume().await?;
                 Ok(())
             }
@@ -62,6 +121,76 @@ impl oio::Write for GdriveWriter {
         }
     }
 
+    /// Upload the last chunk to finish the upload session.
+    async fn write_last_chunk(&self, session_uri: &str, bs: Bytes) -> Result<()> {
+        let total = self.offset + bs.len() as u64;
+        let resp = self
+            .core
+            .gdrive_upload_chunk(session_uri, self.offset, Some(total), bs)
+            .await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK | StatusCode::CREATED => {
+                let bs = resp.into_body().bytes().await?;
+                let file: GdriveFile =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+                self.core.insert_path_cache(&self.path, &file.id).await;
+
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Write for GdriveWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let session_uri = match &self.session_uri {
+            Some(session_uri) => session_uri.clone(),
+            None => {
+                let session_uri = self.initiate_upload().await?;
+
+                // Finish the upload directly if all content is given.
+                if self.op.content_length().unwrap_or_default() == bs.len() as u64 {
+                    return self.write_last_chunk(&session_uri, bs).await;
+                }
+
+                self.session_uri = Some(session_uri.clone());
+                session_uri
+            }
+        };
+
+        // Ignore empty bytes
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.buffer.push(bs);
+        // Return directly if the buffer is not full
+        if self.buffer.len() <= self.buffer_size {
+            return Ok(());
+        }
+
+        let bs = self.buffer.peak_exact(self.buffer_size);
+        let size = bs.len();
+
+        match self.write_chunk(&session_uri, bs).await {
+            Ok(()) => {
+                self.buffer.take(size);
+                self.offset += size as u64;
+                Ok(())
+            }
+            Err(e) => {
+                // If the upload fails, we should pop the given bs to make sure
+                // write is re-enter safe.
+                self.buffer.pop();
+                Err(e)
+            }
+        }
+    }
+
     async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
         Err(Error::new(
             ErrorKind::Unsupported,
@@ -69,11 +198,39 @@ impl oio::Write for GdriveWriter {
         ))
     }
 
+    /// The resumable upload session will be expired after a week if it's
+    /// not finished.
     async fn abort(&mut self) -> Result<()> {
+        self.session_uri = None;
+        self.offset = 0;
+        self.buffer.clear();
         Ok(())
     }
 
     async fn close(&mut self) -> Result<()> {
+        let session_uri = if let Some(session_uri) = &self.session_uri {
+            session_uri.clone()
+        } else {
+            return Ok(());
+        };
+
+        // Make sure every chunk except the last one has exactly `buffer_size` bytes.
+        while self.buffer.len() > self.buffer_size {
+            let bs = self.buffer.peak_exact(self.buffer_size);
+            self.write_chunk(&session_uri, bs).await?;
+            self.buffer.take(self.buffer_size);
+            self.offset += self.buffer_size as u64;
+        }
+
+        let bs = if self.buffer.is_empty() {
+            Bytes::new()
+        } else {
+            self.buffer.peak_exact(self.buffer.len())
+        };
+        self.write_last_chunk(&session_uri, bs).await?;
+
+        self.buffer.clear();
+        self.session_uri = None;
         Ok(())
     }
 }
@@ -0,0 +1,336 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+
+use super::core::check_etag_condition;
+use super::core::GithubContents;
+use super::core::GithubCore;
+use super::error::parse_error;
+use super::pager::GithubPager;
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_ENDPOINT: &str = "https://api.github.com";
+
+/// [GitHub](https://github.com/) repository contents support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct GithubBuilder {
+    root: Option<String>,
+    repository: Option<String>,
+    endpoint: Option<String>,
+    token: Option<String>,
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for GithubBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Builder")
+            .field("root", &self.root)
+            .field("repository", &self.repository)
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+impl GithubBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string());
+        }
+        self
+    }
+
+    /// Set the repository in `owner/repo` or `owner/repo@ref` format.
+    ///
+    /// `ref` could be a branch, tag or commit sha. The default branch of the
+    /// repository will be used if `ref` is not specified.
+    pub fn repository(&mut self, repository: &str) -> &mut Self {
+        if !repository.is_empty() {
+            self.repository = Some(repository.to_string());
+        }
+        self
+    }
+
+    /// Set the endpoint of GitHub REST API.
+    ///
+    /// default: `https://api.github.com`
+    ///
+    /// For GitHub Enterprise Server, the endpoint should be like
+    /// `https://github.example.com/api/v3`.
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        }
+        self
+    }
+
+    /// Set the personal access token used to access GitHub.
+    ///
+    /// Token is required for private repositories, and can raise the rate
+    /// limit for public repositories.
+    pub fn token(&mut self, token: &str) -> &mut Self {
+        if !token.is_empty() {
+            self.token = Some(token.to_string());
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for GithubBuilder {
+    const SCHEME: Scheme = Scheme::Github;
+    type Accessor = GithubBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = GithubBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("repository").map(|v| builder.repository(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("token").map(|v| builder.token(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let repository = self.repository.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "repository is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Github)
+        })?;
+        let (owner, repo, reference) = parse_repository(&repository)?;
+
+        let endpoint = self
+            .endpoint
+            .take()
+            .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
+        debug!("backend use endpoint {}", endpoint);
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Github)
+            })?
+        };
+
+        Ok(GithubBackend {
+            core: Arc::new(GithubCore {
+                root,
+                endpoint,
+                owner,
+                repo,
+                reference,
+                token: self.token.take(),
+                client,
+            }),
+        })
+    }
+}
+
+/// Parse repository in `owner/repo` or `owner/repo@ref` format.
+fn parse_repository(repository: &str) -> Result<(String, String, Option<String>)> {
+    let (name, reference) = match repository.split_once('@') {
+        Some((name, reference)) => (name, Some(reference.to_string())),
+        None => (repository, None),
+    };
+
+    match name.split_once('/') {
+        Some((owner, repo))
+            if !owner.is_empty()
+                && !repo.is_empty()
+                && !repo.contains('/')
+                && reference.as_ref().map(|v| !v.is_empty()).unwrap_or(true) =>
+        {
+            Ok((owner.to_string(), repo.to_string(), reference))
+        }
+        _ => Err(Error::new(
+            ErrorKind::ConfigInvalid,
+            "repository must be in owner/repo or owner/repo@ref format",
+        )
+        .with_operation("Builder::build")
+        .with_context("service", Scheme::Github)
+        .with_context("repository", repository)),
+    }
+}
+
+/// Backend for GitHub services.
+#[derive(Clone, Debug)]
+pub struct GithubBackend {
+    core: Arc<GithubCore>,
+}
+
+#[async_trait]
+impl Accessor for GithubBackend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = ();
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = GithubPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut ma = AccessorInfo::default();
+        ma.set_scheme(Scheme::Github)
+            .set_root(&self.core.root)
+            .set_name(&format!("{}/{}", self.core.owner, self.core.repo))
+            .set_capability(Capability {
+                stat: true,
+                stat_with_if_match: true,
+                stat_with_if_none_match: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+                read_with_if_match: true,
+                read_with_if_none_match: true,
+
+                list: true,
+                list_with_delimiter_slash: true,
+
+                ..Default::default()
+            });
+
+        ma
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let content = match self.core.github_stat(path).await? {
+            Some(GithubContents::File(content)) if !content.is_dir() => content,
+            Some(_) => {
+                return Err(Error::new(
+                    ErrorKind::IsADirectory,
+                    "the path is a dir in github",
+                ))
+            }
+            None => return Err(Error::new(ErrorKind::NotFound, "file not found in github")),
+        };
+        check_etag_condition(&content.sha, args.if_match(), args.if_none_match())?;
+
+        let resp = if let Some(pointer) = content.lfs_pointer() {
+            let action = self.core.github_lfs_resolve(&pointer).await?;
+            self.core.github_lfs_download(&action, args.range()).await?
+        } else {
+            // Submodules don't have a download url.
+            let url = content.download_url.ok_or_else(|| {
+                Error::new(ErrorKind::Unsupported, "the path can't be downloaded")
+                    .with_context("type", &content.type_)
+            })?;
+            self.core.github_download(&url, args.range()).await?
+        };
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        match self.core.github_stat(path).await? {
+            Some(GithubContents::Dir(_)) => Ok(RpStat::new(Metadata::new(EntryMode::DIR))),
+            Some(GithubContents::File(content)) => {
+                if content.is_dir() {
+                    return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+                }
+                if path.ends_with('/') {
+                    return Err(Error::new(
+                        ErrorKind::NotFound,
+                        "the path is a file but a dir is expected",
+                    ));
+                }
+
+                let meta = content.to_metadata();
+                check_etag_condition(&content.sha, args.if_match(), args.if_none_match())?;
+
+                Ok(RpStat::new(meta))
+            }
+            None => Err(Error::new(ErrorKind::NotFound, "path not found in github")),
+        }
+    }
+
+    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((RpList::default(), GithubPager::new(self.core.clone(), path)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_repository() {
+        let cases = vec![
+            ("apache/opendal", Some(("apache", "opendal", None))),
+            (
+                "apache/opendal@main",
+                Some(("apache", "opendal", Some("main"))),
+            ),
+            (
+                "apache/opendal@release/v0.38",
+                Some(("apache", "opendal", Some("release/v0.38"))),
+            ),
+            ("apache", None),
+            ("apache/", None),
+            ("apache/opendal/core", None),
+            ("apache/opendal@", None),
+        ];
+
+        for (input, expected) in cases {
+            let actual = parse_repository(input).ok();
+            let expected = expected.map(|(owner, repo, reference)| {
+                (
+                    owner.to_string(),
+                    repo.to_string(),
+                    reference.map(|v| v.to_string()),
+                )
+            });
+            assert_eq!(actual, expected, "{input}");
+        }
+    }
+}
@@ -0,0 +1,474 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use http::header;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The version of GitHub REST API we are using.
+const GITHUB_API_VERSION: &str = "2022-11-28";
+/// The web endpoint of github.com, used to locate the LFS server.
+const GITHUB_WEB_ENDPOINT: &str = "https://github.com";
+/// The first line of a git lfs pointer file.
+///
+/// Ref: <https://github.com/git-lfs/git-lfs/blob/main/docs/spec.md>
+const LFS_POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+/// Git lfs pointer files must be less than 1024 bytes.
+const LFS_POINTER_MAX_SIZE: u64 = 1024;
+
+pub struct GithubCore {
+    pub root: String,
+    pub endpoint: String,
+    pub owner: String,
+    pub repo: String,
+    /// The branch, tag or commit sha to read from.
+    ///
+    /// The default branch of the repository will be used if not set.
+    pub reference: Option<String>,
+    pub token: Option<String>,
+    pub client: HttpClient,
+}
+
+impl Debug for GithubCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GithubCore")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .field("owner", &self.owner)
+            .field("repo", &self.repo)
+            .field("reference", &self.reference)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GithubCore {
+    fn sign(&self, req: &mut Request<AsyncBody>) -> Result<()> {
+        let headers = req.headers_mut();
+
+        headers.insert(
+            header::USER_AGENT,
+            format!("opendal/{VERSION} (service github)")
+                .parse()
+                .map_err(|_| Error::new(ErrorKind::Unexpected, "invalid user agent"))?,
+        );
+        if let Some(token) = &self.token {
+            headers.insert(
+                header::AUTHORIZATION,
+                format_authorization_by_bearer(token)?
+                    .parse()
+                    .map_err(|_| Error::new(ErrorKind::Unexpected, "invalid token"))?,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// The LFS server of the repository.
+    ///
+    /// GitHub Enterprise Server serves REST API under `/api/v3`, we strip
+    /// it to get the web endpoint. Otherwise, we fall back to github.com.
+    fn lfs_endpoint(&self) -> String {
+        let web = self
+            .endpoint
+            .strip_suffix("/api/v3")
+            .unwrap_or(GITHUB_WEB_ENDPOINT);
+
+        format!(
+            "{web}/{}/{}.git/info/lfs/objects/batch",
+            self.owner, self.repo
+        )
+    }
+
+    pub async fn github_get_contents(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+        let p = p.trim_end_matches('/');
+
+        let mut url = format!(
+            "{}/repos/{}/{}/contents",
+            self.endpoint, self.owner, self.repo
+        );
+        if !p.is_empty() {
+            url.push('/');
+            url.push_str(&percent_encode_path(p));
+        }
+        if let Some(reference) = &self.reference {
+            url.push_str(&format!(
+                "?ref={}",
+                utf8_percent_encode(reference, NON_ALPHANUMERIC)
+            ));
+        }
+
+        let mut req = Request::get(&url)
+            .header(header::ACCEPT, "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", GITHUB_API_VERSION)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    /// Get the contents of given path.
+    ///
+    /// Returns `None` if the path doesn't exist.
+    pub async fn github_stat(&self, path: &str) -> Result<Option<GithubContents>> {
+        let resp = self.github_get_contents(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let contents: GithubContents =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+                Ok(Some(contents))
+            }
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(None)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Download the raw content of the file via the `download_url`
+    /// returned by contents API.
+    pub async fn github_download(
+        &self,
+        url: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::get(url);
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    /// Resolve the download action of given lfs object via the
+    /// [batch API](https://github.com/git-lfs/git-lfs/blob/main/docs/api/batch.md).
+    pub async fn github_lfs_resolve(&self, pointer: &LfsPointer) -> Result<LfsAction> {
+        let body = json!({
+            "operation": "download",
+            "transfers": ["basic"],
+            "objects": [{"oid": pointer.oid, "size": pointer.size}],
+        });
+        let bs = serde_json::to_vec(&body).map_err(new_json_serialize_error)?;
+
+        let mut req = Request::post(self.lfs_endpoint())
+            .header(header::ACCEPT, "application/vnd.git-lfs+json")
+            .header(header::CONTENT_TYPE, "application/vnd.git-lfs+json")
+            .header(
+                header::USER_AGENT,
+                format!("opendal/{VERSION} (service github)"),
+            );
+        // LFS server only accepts basic auth, the username is not checked.
+        if let Some(token) = &self.token {
+            req = req.header(
+                header::AUTHORIZATION,
+                format_authorization_by_basic("x-access-token", token)?,
+            );
+        }
+
+        let req = req
+            .body(AsyncBody::Bytes(bs.into()))
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: LfsBatchResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        let object = output
+            .objects
+            .into_iter()
+            .find(|v| v.oid == pointer.oid)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Unexpected, "lfs object is missing in response")
+                    .with_context("oid", &pointer.oid)
+            })?;
+
+        if let Some(err) = object.error {
+            let kind = match err.code {
+                404 | 410 => ErrorKind::NotFound,
+                _ => ErrorKind::Unexpected,
+            };
+            return Err(Error::new(kind, &err.message).with_context("oid", &pointer.oid));
+        }
+
+        object.actions.and_then(|v| v.download).ok_or_else(|| {
+            Error::new(ErrorKind::Unexpected, "lfs object is not downloadable")
+                .with_context("oid", &pointer.oid)
+        })
+    }
+
+    /// Download the lfs object via the resolved action.
+    ///
+    /// The action carries its own auth, so we should not sign it.
+    pub async fn github_lfs_download(
+        &self,
+        action: &LfsAction,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::get(&action.href);
+        for (k, v) in &action.header {
+            req = req.header(k, v);
+        }
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+}
+
+/// Check the etag of the file against the conditions.
+///
+/// GitHub doesn't support conditional requests on contents, so we check
+/// them at client side.
+pub fn check_etag_condition(
+    etag: &str,
+    if_match: Option<&str>,
+    if_none_match: Option<&str>,
+) -> Result<()> {
+    if let Some(v) = if_match {
+        if v != etag {
+            return Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "etag doesn't match if_match condition",
+            ));
+        }
+    }
+    if let Some(v) = if_none_match {
+        if v == etag {
+            return Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "etag matches if_none_match condition",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// The response of contents API.
+///
+/// A list of entries will be returned for dir, and a single entry for others.
+///
+/// Ref: <https://docs.github.com/en/rest/repos/contents#get-repository-content>
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum GithubContents {
+    Dir(Vec<GithubContent>),
+    File(GithubContent),
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct GithubContent {
+    /// One of `file`, `dir`, `symlink` and `submodule`.
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub size: u64,
+    pub download_url: Option<String>,
+    /// Base64 encoded content, only returned for files up to 1 MiB.
+    pub content: Option<String>,
+}
+
+impl GithubContent {
+    pub fn is_dir(&self) -> bool {
+        self.type_ == "dir"
+    }
+
+    /// Parse the content as a git lfs pointer.
+    ///
+    /// Returns `None` if this file is not a lfs pointer.
+    pub fn lfs_pointer(&self) -> Option<LfsPointer> {
+        if self.type_ != "file" || self.size >= LFS_POINTER_MAX_SIZE {
+            return None;
+        }
+
+        // The content is split into multiple lines by GitHub.
+        let content: String = self.content.as_ref()?.split('\n').collect();
+        let bs = general_purpose::STANDARD.decode(content).ok()?;
+        let content = String::from_utf8(bs).ok()?;
+
+        let mut lines = content.lines();
+        if lines.next()? != LFS_POINTER_VERSION {
+            return None;
+        }
+
+        let (mut oid, mut size) = (None, None);
+        for line in lines {
+            if let Some(v) = line.strip_prefix("oid sha256:") {
+                oid = Some(v.to_string());
+            } else if let Some(v) = line.strip_prefix("size ") {
+                size = v.parse().ok();
+            }
+        }
+
+        Some(LfsPointer {
+            oid: oid?,
+            size: size?,
+        })
+    }
+
+    /// Parse the content into metadata.
+    ///
+    /// The size of lfs object will be used for lfs pointers.
+    pub fn to_metadata(&self) -> Metadata {
+        if self.is_dir() {
+            return Metadata::new(EntryMode::DIR);
+        }
+
+        let size = self.lfs_pointer().map(|v| v.size).unwrap_or(self.size);
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        meta.set_content_length(size);
+        // The blob sha changes every time the file content changes,
+        // so it's fine to be used as etag.
+        meta.set_etag(&self.sha);
+        meta
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LfsPointer {
+    pub oid: String,
+    pub size: u64,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct LfsBatchResponse {
+    objects: Vec<LfsObject>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct LfsObject {
+    oid: String,
+    actions: Option<LfsActions>,
+    error: Option<LfsObjectError>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct LfsActions {
+    download: Option<LfsAction>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct LfsAction {
+    pub href: String,
+    pub header: HashMap<String, String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct LfsObjectError {
+    code: u16,
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_contents() {
+        let bs = r#"[
+            {"type": "file", "name": "normal_file", "path": "data/normal_file", "sha": "abc", "size": 262144,
+             "download_url": "https://raw.githubusercontent.com/apache/opendal/main/data/normal_file"},
+            {"type": "dir", "name": "normal_dir", "path": "data/normal_dir", "sha": "def", "size": 0,
+             "download_url": null}
+        ]"#;
+        let contents: GithubContents = serde_json::from_str(bs).expect("must be valid");
+        let entries = match contents {
+            GithubContents::Dir(entries) => entries,
+            GithubContents::File(_) => panic!("must be dir"),
+        };
+        assert_eq!(entries.len(), 2);
+        assert!(!entries[0].is_dir());
+        assert_eq!(entries[0].to_metadata().content_length(), 262144);
+        assert_eq!(entries[0].to_metadata().etag(), Some("abc"));
+        assert!(entries[1].is_dir());
+        assert_eq!(entries[1].to_metadata().mode(), EntryMode::DIR);
+
+        let bs = r#"{"type": "file", "name": "a", "path": "a", "sha": "abc", "size": 3, "content": "YWJj\n"}"#;
+        let contents: GithubContents = serde_json::from_str(bs).expect("must be valid");
+        assert!(matches!(contents, GithubContents::File(v) if v.lfs_pointer().is_none()));
+    }
+
+    #[test]
+    fn test_lfs_pointer() {
+        let pointer = "version https://git-lfs.github.com/spec/v1\noid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\nsize 12345\n";
+        let encoded = general_purpose::STANDARD.encode(pointer);
+        // GitHub splits the encoded content into lines.
+        let (l, r) = encoded.split_at(60);
+
+        let content = GithubContent {
+            type_: "file".to_string(),
+            size: pointer.len() as u64,
+            sha: "abc".to_string(),
+            content: Some(format!("{l}\n{r}\n")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            content.lfs_pointer(),
+            Some(LfsPointer {
+                oid: "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393".to_string(),
+                size: 12345,
+            })
+        );
+        assert_eq!(content.to_metadata().content_length(), 12345);
+    }
+}
@@ -0,0 +1,121 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// GithubError is the error returned by GitHub REST API.
+///
+/// Ref: <https://docs.github.com/en/rest/overview/resources-in-the-rest-api#client-errors>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct GithubError {
+    message: String,
+    documentation_url: String,
+}
+
+/// Parse error response into Error.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    // GitHub returns `403 Forbidden` instead of `429 Too Many Requests` if
+    // the primary rate limit has been exceeded.
+    let rate_limited = parts
+        .headers
+        .get("x-ratelimit-remaining")
+        .map(|v| v == "0")
+        .unwrap_or_default();
+
+    let (kind, retryable) = match parts.status {
+        StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
+        StatusCode::FORBIDDEN if rate_limited => (ErrorKind::RateLimited, true),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let message = match serde_json::from_slice::<GithubError>(&bs) {
+        Ok(github_error) if !github_error.message.is_empty() => format!("{github_error:?}"),
+        _ => String::from_utf8_lossy(&bs).into_owned(),
+    };
+
+    let mut err = Error::new(kind, &message).with_context("response", format!("{parts:?}"));
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::raw::oio::into_stream;
+
+    #[tokio::test]
+    async fn test_parse_error() -> Result<()> {
+        let cases = vec![
+            (StatusCode::NOT_FOUND, "1", ErrorKind::NotFound, false),
+            (
+                StatusCode::FORBIDDEN,
+                "1",
+                ErrorKind::PermissionDenied,
+                false,
+            ),
+            (StatusCode::FORBIDDEN, "0", ErrorKind::RateLimited, true),
+            (StatusCode::BAD_GATEWAY, "1", ErrorKind::Unexpected, true),
+        ];
+
+        for (status, remaining, kind, temporary) in cases {
+            let body = bytes::Bytes::from(
+                r#"{"message": "Not Found", "documentation_url": "https://docs.github.com/rest"}"#,
+            );
+            let body = IncomingAsyncBody::new(
+                Box::new(into_stream::from_futures_stream(stream::iter(vec![Ok(
+                    body,
+                )]))),
+                None,
+            );
+            let resp = Response::builder()
+                .status(status)
+                .header("x-ratelimit-remaining", remaining)
+                .body(body)
+                .unwrap();
+
+            let err = parse_error(resp).await?;
+            assert_eq!(err.kind(), kind);
+            assert_eq!(err.is_temporary(), temporary);
+            assert!(err.to_string().contains("Not Found"));
+        }
+
+        Ok(())
+    }
+}
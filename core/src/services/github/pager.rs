@@ -0,0 +1,75 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::core::GithubContents;
+use super::core::GithubCore;
+use crate::raw::*;
+use crate::*;
+
+/// GithubPager lists a dir via contents API.
+///
+/// Contents API returns all entries (up to 1000) in one response, so there
+/// is only one page.
+pub struct GithubPager {
+    core: Arc<GithubCore>,
+    path: String,
+    done: bool,
+}
+
+impl GithubPager {
+    pub fn new(core: Arc<GithubCore>, path: &str) -> Self {
+        GithubPager {
+            core,
+            path: path.to_string(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for GithubPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+        self.done = true;
+
+        let entries = match self.core.github_stat(&self.path).await? {
+            Some(GithubContents::Dir(entries)) => entries,
+            // Listing a not exist dir or a file should return empty.
+            _ => return Ok(None),
+        };
+
+        let entries = entries
+            .into_iter()
+            .map(|content| {
+                let mut path = build_rel_path(&self.core.root, &format!("/{}", content.path));
+                if content.is_dir() {
+                    path.push('/');
+                }
+
+                oio::Entry::new(&path, content.to_metadata())
+            })
+            .collect();
+
+        Ok(Some(entries))
+    }
+}
@@ -0,0 +1,268 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::bson::Bson;
+use mongodb::bson::Document;
+use mongodb::error::GridFsErrorKind;
+use mongodb::gridfs::FilesCollectionDocument;
+use mongodb::gridfs::GridFsBucket;
+use mongodb::options::ClientOptions;
+use mongodb::options::FindOptions;
+use mongodb::options::GridFsBucketOptions;
+use mongodb::options::GridFsFindOptions;
+use mongodb::Collection;
+use tokio::sync::OnceCell;
+
+use crate::raw::*;
+use crate::*;
+
+/// The key in the metadata document of GridFS files to store content type.
+const METADATA_CONTENT_TYPE: &str = "content_type";
+
+pub struct GridfsCore {
+    pub connection_string: String,
+    pub database: String,
+    pub bucket: String,
+    pub chunk_size: u32,
+    pub root: String,
+
+    pub conn: OnceCell<(GridFsBucket, Collection<Document>)>,
+}
+
+// implement `Debug` manually, or password in connection string may be leaked.
+impl Debug for GridfsCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GridfsCore")
+            .field("database", &self.database)
+            .field("bucket", &self.bucket)
+            .field("chunk_size", &self.chunk_size)
+            .field("root", &self.root)
+            .finish()
+    }
+}
+
+impl GridfsCore {
+    /// Returns the GridFS bucket and the chunks collection of it, the
+    /// connection will be established at the first call.
+    async fn conn(&self) -> Result<&(GridFsBucket, Collection<Document>)> {
+        self.conn
+            .get_or_try_init(|| async {
+                let options = ClientOptions::parse(&self.connection_string)
+                    .await
+                    .map_err(|e| {
+                        Error::new(ErrorKind::ConfigInvalid, "connection string is invalid")
+                            .with_context("service", Scheme::Gridfs)
+                            .set_source(e)
+                    })?;
+                let client = mongodb::Client::with_options(options).map_err(parse_mongodb_error)?;
+                let db = client.database(&self.database);
+
+                let bucket = db.gridfs_bucket(
+                    GridFsBucketOptions::builder()
+                        .bucket_name(self.bucket.clone())
+                        .chunk_size_bytes(self.chunk_size)
+                        .build(),
+                );
+                let chunks = db.collection::<Document>(&format!("{}.chunks", self.bucket));
+
+                Ok((bucket, chunks))
+            })
+            .await
+    }
+
+    pub async fn bucket(&self) -> Result<&GridFsBucket> {
+        Ok(&self.conn().await?.0)
+    }
+
+    /// Find the latest revision of the file with given absolute path.
+    pub async fn find_file(&self, path: &str) -> Result<Option<FilesCollectionDocument>> {
+        let options = GridFsFindOptions::builder()
+            .sort(doc! { "uploadDate": -1 })
+            .limit(1)
+            .build();
+
+        let mut cursor = self
+            .bucket()
+            .await?
+            .find(doc! { "filename": path }, options)
+            .await
+            .map_err(parse_mongodb_error)?;
+
+        cursor.try_next().await.map_err(parse_mongodb_error)
+    }
+
+    /// Find at most `limit` files whose name starts with `prefix` and is
+    /// greater than `start_after`, sorted by name.
+    pub async fn list_files(
+        &self,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<FilesCollectionDocument>> {
+        let mut filter = doc! { "$regex": format!("^{}", escape_regex(prefix)) };
+        if let Some(start_after) = start_after {
+            filter.insert("$gt", start_after);
+        }
+
+        let options = GridFsFindOptions::builder()
+            .sort(doc! { "filename": 1, "uploadDate": -1 })
+            .limit(limit as i64)
+            .build();
+
+        let cursor = self
+            .bucket()
+            .await?
+            .find(doc! { "filename": filter }, options)
+            .await
+            .map_err(parse_mongodb_error)?;
+
+        cursor.try_collect().await.map_err(parse_mongodb_error)
+    }
+
+    /// Find chunks of the file from the `start`th chunk to the `end`th chunk
+    /// (inclusive), sorted by their index.
+    pub async fn find_chunks(
+        &self,
+        id: Bson,
+        start: u64,
+        end: u64,
+    ) -> Result<mongodb::Cursor<Document>> {
+        let options = FindOptions::builder().sort(doc! { "n": 1 }).build();
+
+        self.conn()
+            .await?
+            .1
+            .find(
+                doc! {
+                    "files_id": id,
+                    "n": { "$gte": start as i64, "$lte": end as i64 },
+                },
+                options,
+            )
+            .await
+            .map_err(parse_mongodb_error)
+    }
+
+    /// Delete all revisions of the file with given absolute path except the
+    /// one with id `keep`.
+    pub async fn delete_files(&self, path: &str, keep: Option<&Bson>) -> Result<()> {
+        let mut filter = doc! { "filename": path };
+        if let Some(keep) = keep {
+            filter.insert("_id", doc! { "$ne": keep.clone() });
+        }
+
+        let bucket = self.bucket().await?;
+        let files: Vec<FilesCollectionDocument> = bucket
+            .find(filter, None)
+            .await
+            .map_err(parse_mongodb_error)?
+            .try_collect()
+            .await
+            .map_err(parse_mongodb_error)?;
+
+        for file in files {
+            match bucket.delete(file.id).await {
+                Ok(()) => {}
+                // The file could be deleted by others at the same time.
+                Err(e)
+                    if matches!(
+                        *e.kind,
+                        mongodb::error::ErrorKind::GridFs {
+                            0: GridFsErrorKind::FileNotFound { .. },
+                            ..
+                        }
+                    ) => {}
+                Err(e) => return Err(parse_mongodb_error(e)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Build metadata from the document in files collection.
+pub fn parse_file_metadata(file: &FilesCollectionDocument) -> Result<Metadata> {
+    let mut meta = if file.filename.as_deref().unwrap_or_default().ends_with('/') {
+        Metadata::new(EntryMode::DIR)
+    } else {
+        Metadata::new(EntryMode::FILE)
+    };
+
+    meta.set_content_length(file.length);
+    meta.set_last_modified(parse_datetime_from_from_timestamp_millis(
+        file.upload_date.timestamp_millis(),
+    )?);
+    if let Some(content_type) = file
+        .metadata
+        .as_ref()
+        .and_then(|v| v.get_str(METADATA_CONTENT_TYPE).ok())
+    {
+        meta.set_content_type(content_type);
+    }
+
+    Ok(meta)
+}
+
+/// Build the metadata document of GridFS files from write args.
+pub fn build_file_metadata(args: &OpWrite) -> Option<Document> {
+    args.content_type()
+        .map(|v| doc! { METADATA_CONTENT_TYPE: v })
+}
+
+/// Escape all regex meta characters so that `s` can be matched literally.
+pub fn escape_regex(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(
+            c,
+            '\\' | '^' | '$' | '.' | '|' | '?' | '*' | '+' | '(' | ')' | '[' | ']' | '{' | '}'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+pub fn parse_mongodb_error(e: mongodb::error::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "error from mongodb").set_source(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_regex() {
+        let cases = vec![
+            ("normal", "path/to/dir/", "path/to/dir/"),
+            ("empty", "", ""),
+            ("dot", "a.txt", "a\\.txt"),
+            ("brackets", "a[1](2){3}", "a\\[1\\]\\(2\\)\\{3\\}"),
+            ("others", "^a$b|c?d*e+f\\", "\\^a\\$b\\|c\\?d\\*e\\+f\\\\"),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(escape_regex(input), expected, "{name}")
+        }
+    }
+}
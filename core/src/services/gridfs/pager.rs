@@ -0,0 +1,93 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::core::parse_file_metadata;
+use super::core::GridfsCore;
+use crate::raw::*;
+use crate::*;
+
+/// The default number of files fetched in one page.
+const DEFAULT_PAGE_SIZE: usize = 1000;
+
+/// Pager that lists files by filename prefix page by page.
+///
+/// Every page starts after the last filename of previous page, so we don't
+/// need to keep the cursor alive between pages.
+pub struct GridfsPager {
+    core: Arc<GridfsCore>,
+    path: String,
+    limit: usize,
+    start_after: Option<String>,
+    done: bool,
+}
+
+impl GridfsPager {
+    pub fn new(
+        core: Arc<GridfsCore>,
+        path: String,
+        limit: Option<usize>,
+        start_after: Option<String>,
+    ) -> Self {
+        Self {
+            core,
+            path,
+            limit: limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            start_after,
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for GridfsPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let files = self
+            .core
+            .list_files(&self.path, self.start_after.as_deref(), self.limit)
+            .await?;
+        if files.len() < self.limit {
+            self.done = true;
+        }
+
+        let mut entries: Vec<oio::Entry> = Vec::with_capacity(files.len());
+        for file in files {
+            let filename = file.filename.clone().unwrap_or_default();
+            // Files are sorted by filename and then upload date in descending
+            // order, so the latest revision always comes first.
+            if self.start_after.as_deref() == Some(filename.as_str()) {
+                continue;
+            }
+
+            let meta = parse_file_metadata(&file)?;
+            entries.push(oio::Entry::new(
+                &build_rel_path(&self.core.root, &filename),
+                meta,
+            ));
+            self.start_after = Some(filename);
+        }
+
+        Ok(Some(entries))
+    }
+}
@@ -0,0 +1,162 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::min;
+use std::io;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Buf;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use mongodb::bson::Document;
+
+use super::core::parse_mongodb_error;
+use crate::raw::*;
+use crate::*;
+
+/// Reader that reads the content from chunks of a GridFS file.
+///
+/// Only the chunks covering the read range will be fetched.
+pub struct GridfsReader {
+    chunks: BoxStream<'static, mongodb::error::Result<Document>>,
+    buf: Bytes,
+    /// Bytes to skip in the first chunk.
+    skip: usize,
+    /// Bytes left to read.
+    remaining: u64,
+}
+
+/// `BoxStream` is not `Sync`, but the stream will only be polled via
+/// `&mut self`, so it's safe to share the reader between threads.
+unsafe impl Sync for GridfsReader {}
+
+impl GridfsReader {
+    pub fn new(
+        chunks: BoxStream<'static, mongodb::error::Result<Document>>,
+        skip: usize,
+        size: u64,
+    ) -> Self {
+        Self {
+            chunks,
+            buf: Bytes::new(),
+            skip,
+            remaining: size,
+        }
+    }
+
+    /// Returns an empty reader.
+    pub fn empty() -> Self {
+        Self::new(futures::stream::empty().boxed(), 0, 0)
+    }
+
+    /// Make sure `buf` is not empty unless all data has been read.
+    fn poll_fill_buf(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while self.buf.is_empty() && self.remaining > 0 {
+            let doc = match ready!(self.chunks.poll_next_unpin(cx)) {
+                Some(doc) => doc.map_err(parse_mongodb_error)?,
+                None => {
+                    return Poll::Ready(Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "chunks of gridfs file are missing",
+                    )
+                    .with_context("remaining", self.remaining.to_string())))
+                }
+            };
+
+            let data = doc.get_binary_generic("data").map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "chunk of gridfs file is invalid").set_source(e)
+            })?;
+
+            let start = min(self.skip, data.len());
+            let end = start + min((data.len() - start) as u64, self.remaining) as usize;
+            self.skip = 0;
+            self.remaining -= (end - start) as u64;
+            self.buf = Bytes::copy_from_slice(&data[start..end]);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl oio::Read for GridfsReader {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        ready!(self.poll_fill_buf(cx))?;
+
+        let n = min(buf.len(), self.buf.len());
+        buf[..n].copy_from_slice(&self.buf[..n]);
+        self.buf.advance(n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        let (_, _) = (cx, pos);
+
+        Poll::Ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "output reader doesn't support seeking",
+        )))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        ready!(self.poll_fill_buf(cx))?;
+
+        if self.buf.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Ready(Some(Ok(self.buf.split_off(0))))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+    use mongodb::bson::doc;
+    use mongodb::bson::spec::BinarySubtype;
+    use mongodb::bson::Binary;
+
+    use super::*;
+
+    fn chunk(data: &[u8]) -> mongodb::error::Result<Document> {
+        Ok(doc! { "data": Binary { subtype: BinarySubtype::Generic, bytes: data.to_vec() } })
+    }
+
+    #[tokio::test]
+    async fn test_read_range_of_chunks() {
+        let chunks = stream::iter(vec![chunk(b"0123"), chunk(b"4567"), chunk(b"89")]).boxed();
+        let mut r = GridfsReader::new(chunks, 2, 5);
+
+        let mut bs = Vec::new();
+        while let Some(data) = oio::ReadExt::next(&mut r).await {
+            bs.extend_from_slice(&data.expect("read must succeed"));
+        }
+        assert_eq!(bs, b"23456");
+    }
+
+    #[tokio::test]
+    async fn test_read_missing_chunks() {
+        let chunks = stream::iter(vec![chunk(b"0123")]).boxed();
+        let mut r = GridfsReader::new(chunks, 0, 8);
+
+        let mut buf = vec![0; 8];
+        assert_eq!(oio::ReadExt::read(&mut r, &mut buf).await.unwrap(), 4);
+        assert!(oio::ReadExt::read(&mut r, &mut buf).await.is_err());
+    }
+}
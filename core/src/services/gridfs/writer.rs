@@ -0,0 +1,76 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::AsyncWriteExt;
+use mongodb::gridfs::GridFsUploadStream;
+
+use super::core::parse_mongodb_error;
+use super::core::GridfsCore;
+use crate::raw::*;
+use crate::*;
+
+pub struct GridfsWriter {
+    core: Arc<GridfsCore>,
+    path: String,
+    stream: GridFsUploadStream,
+}
+
+/// `GridFsUploadStream` is not `Sync`, but it will only be used via
+/// `&mut self`, so it's safe to share the writer between threads.
+unsafe impl Sync for GridfsWriter {}
+
+impl GridfsWriter {
+    pub fn new(core: Arc<GridfsCore>, path: String, stream: GridFsUploadStream) -> Self {
+        GridfsWriter { core, path, stream }
+    }
+}
+
+#[async_trait]
+impl oio::Write for GridfsWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.stream.write_all(&bs).await.map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "write into gridfs upload stream").set_source(e)
+        })
+    }
+
+    async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "Write::sink is not supported",
+        ))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.stream.abort().await.map_err(parse_mongodb_error)
+    }
+
+    /// GridFS keeps all revisions of the same filename, so we remove the
+    /// old revisions after the new one has been uploaded.
+    async fn close(&mut self) -> Result<()> {
+        self.stream.close().await.map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "close gridfs upload stream").set_source(e)
+        })?;
+
+        self.core
+            .delete_files(&self.path, Some(self.stream.id()))
+            .await
+    }
+}
@@ -22,11 +22,15 @@ use std::io;
 use std::io::SeekFrom;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use log::debug;
 
 use super::error::parse_io_error;
+use super::kerberos::Kerberos;
+use super::kerberos::KerberosRenewal;
+use super::kerberos::DEFAULT_KERBEROS_RENEW_INTERVAL;
 use super::pager::HdfsPager;
 use super::writer::HdfsWriter;
 use crate::raw::*;
@@ -38,6 +42,11 @@ use crate::*;
 pub struct HdfsBuilder {
     root: Option<String>,
     name_node: Option<String>,
+    user: Option<String>,
+    kerberos_ticket_cache_path: Option<String>,
+    kerberos_principal: Option<String>,
+    kerberos_keytab: Option<String>,
+    kerberos_renew_interval: Option<Duration>,
 }
 
 impl HdfsBuilder {
@@ -68,6 +77,58 @@ impl HdfsBuilder {
 
         self
     }
+
+    /// Set the user to connect hdfs as.
+    ///
+    /// This is only used by simple authentication, the user of kerberos
+    /// tickets will be used if kerberos is enabled.
+    pub fn user(&mut self, user: &str) -> &mut Self {
+        if !user.is_empty() {
+            self.user = Some(user.to_string())
+        }
+
+        self
+    }
+
+    /// Set the kerberos ticket cache path of this backend.
+    ///
+    /// Tickets should be obtained via `kinit` before building if keytab is
+    /// not set. Otherwise, opendal will obtain tickets into this path.
+    pub fn kerberos_ticket_cache_path(&mut self, path: &str) -> &mut Self {
+        if !path.is_empty() {
+            self.kerberos_ticket_cache_path = Some(path.to_string())
+        }
+
+        self
+    }
+
+    /// Set the kerberos principal to login with keytab, for example
+    /// `hdfs/host@EXAMPLE.COM`.
+    pub fn kerberos_principal(&mut self, principal: &str) -> &mut Self {
+        if !principal.is_empty() {
+            self.kerberos_principal = Some(principal.to_string())
+        }
+
+        self
+    }
+
+    /// Set the kerberos keytab path to login with.
+    pub fn kerberos_keytab(&mut self, keytab: &str) -> &mut Self {
+        if !keytab.is_empty() {
+            self.kerberos_keytab = Some(keytab.to_string())
+        }
+
+        self
+    }
+
+    /// Set the interval to renew kerberos tickets.
+    ///
+    /// Default to 1 hour, it should be shorter than the ticket lifetime.
+    pub fn kerberos_renew_interval(&mut self, interval: Duration) -> &mut Self {
+        self.kerberos_renew_interval = Some(interval);
+
+        self
+    }
 }
 
 impl Builder for HdfsBuilder {
@@ -79,6 +140,16 @@ impl Builder for HdfsBuilder {
 
         map.get("root").map(|v| builder.root(v));
         map.get("name_node").map(|v| builder.name_node(v));
+        map.get("user").map(|v| builder.user(v));
+        map.get("kerberos_ticket_cache_path")
+            .map(|v| builder.kerberos_ticket_cache_path(v));
+        map.get("kerberos_principal")
+            .map(|v| builder.kerberos_principal(v));
+        map.get("kerberos_keytab")
+            .map(|v| builder.kerberos_keytab(v));
+        map.get("kerberos_renew_interval")
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|v| builder.kerberos_renew_interval(Duration::from_secs(v)));
 
         builder
     }
@@ -97,7 +168,39 @@ impl Builder for HdfsBuilder {
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
-        let client = hdrs::Client::connect(name_node).map_err(parse_io_error)?;
+        let mut builder = hdrs::ClientBuilder::new(name_node);
+        if let Some(user) = &self.user {
+            builder = builder.with_user(user);
+        }
+
+        let mut kerberos_renewal = None;
+        if self.kerberos_ticket_cache_path.is_some()
+            || self.kerberos_principal.is_some()
+            || self.kerberos_keytab.is_some()
+        {
+            let kerberos = Kerberos::new(
+                self.kerberos_principal.clone(),
+                self.kerberos_keytab.clone(),
+                self.kerberos_ticket_cache_path.clone(),
+            )?;
+            kerberos
+                .login()
+                .map_err(|err| err.with_operation("Builder::build"))?;
+            debug!(
+                "backend use kerberos ticket cache {}",
+                kerberos.ticket_cache_path()
+            );
+
+            builder = builder.with_kerberos_ticket_cache_path(kerberos.ticket_cache_path());
+            kerberos_renewal = Some(Arc::new(
+                kerberos.spawn_renewal(
+                    self.kerberos_renew_interval
+                        .unwrap_or(DEFAULT_KERBEROS_RENEW_INTERVAL),
+                )?,
+            ));
+        }
+
+        let client = builder.connect().map_err(parse_io_error)?;
 
         // Create root dir if not exist.
         if let Err(e) = client.metadata(&root) {
@@ -112,6 +215,7 @@ impl Builder for HdfsBuilder {
         Ok(HdfsBackend {
             root,
             client: Arc::new(client),
+            _kerberos_renewal: kerberos_renewal,
         })
     }
 }
@@ -121,6 +225,8 @@ impl Builder for HdfsBuilder {
 pub struct HdfsBackend {
     root: String,
     client: Arc<hdrs::Client>,
+    /// Keep renewing kerberos tickets until the backend is dropped.
+    _kerberos_renewal: Option<Arc<KerberosRenewal>>,
 }
 
 /// hdrs::Client is thread-safe.
@@ -0,0 +1,225 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::env;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::Duration;
+
+use log::debug;
+use log::warn;
+
+use crate::*;
+
+/// The default interval to refresh kerberos tickets.
+pub const DEFAULT_KERBEROS_RENEW_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Kerberos keeps the ticket cache used by libhdfs valid.
+///
+/// - With keytab, tickets will be obtained via `kinit -kt` and obtained
+///   again while renewing, so that they can live longer than the max
+///   renewable lifetime.
+/// - Without keytab, tickets in the cache are expected to be obtained by
+///   users and will be renewed via `kinit -R`.
+#[derive(Clone)]
+pub struct Kerberos {
+    principal: Option<String>,
+    keytab: Option<String>,
+    ticket_cache_path: String,
+}
+
+impl Debug for Kerberos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Kerberos")
+            .field("principal", &self.principal)
+            .field("ticket_cache_path", &self.ticket_cache_path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Kerberos {
+    /// Create a new kerberos config.
+    ///
+    /// The ticket cache path will be generated under temp dir if keytab
+    /// is used without ticket cache path.
+    pub fn new(
+        principal: Option<String>,
+        keytab: Option<String>,
+        ticket_cache_path: Option<String>,
+    ) -> Result<Self> {
+        let err = |msg: &str| {
+            Error::new(ErrorKind::ConfigInvalid, msg)
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Hdfs)
+        };
+
+        let ticket_cache_path = match (&principal, &keytab, ticket_cache_path) {
+            (Some(_), None, _) => return Err(err("kerberos_keytab is empty")),
+            (None, Some(_), _) => return Err(err("kerberos_principal is empty")),
+            (_, _, Some(path)) => path,
+            (Some(principal), Some(_), None) => {
+                let name = format!("krb5cc_opendal_{}", principal.replace(['/', '@'], "_"));
+                let path: PathBuf = env::temp_dir().join(name);
+                path.to_string_lossy().to_string()
+            }
+            (None, None, None) => return Err(err("kerberos_ticket_cache_path is empty")),
+        };
+
+        Ok(Self {
+            principal,
+            keytab,
+            ticket_cache_path,
+        })
+    }
+
+    /// The ticket cache path that libhdfs should use.
+    pub fn ticket_cache_path(&self) -> &str {
+        &self.ticket_cache_path
+    }
+
+    /// Obtain tickets with keytab, do nothing if keytab is not set.
+    pub fn login(&self) -> Result<()> {
+        let (principal, keytab) = match (&self.principal, &self.keytab) {
+            (Some(principal), Some(keytab)) => (principal, keytab),
+            _ => return Ok(()),
+        };
+
+        debug!("kerberos login as {principal} with keytab {keytab}");
+        self.run_kinit(&["-kt", keytab, "-c", &self.ticket_cache_path, principal])
+    }
+
+    /// Refresh tickets in the cache.
+    pub fn renew(&self) -> Result<()> {
+        if self.keytab.is_some() {
+            return self.login();
+        }
+
+        debug!("kerberos renew tickets in {}", self.ticket_cache_path);
+        self.run_kinit(&["-R", "-c", &self.ticket_cache_path])
+    }
+
+    fn run_kinit(&self, args: &[&str]) -> Result<()> {
+        let output = Command::new("kinit").args(args).output().map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "run kinit failed")
+                .with_context("service", Scheme::Hdfs)
+                .set_source(err)
+        })?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(Error::new(ErrorKind::PermissionDenied, "kinit failed")
+                .with_context("service", Scheme::Hdfs)
+                .with_context("ticket_cache_path", &self.ticket_cache_path)
+                .with_context("status", output.status.to_string())
+                .with_context(
+                    "stderr",
+                    String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                ))
+        }
+    }
+
+    /// Spawn a thread to refresh tickets periodically.
+    ///
+    /// The thread will be signaled to stop once the returned
+    /// [`KerberosRenewal`] is dropped, which happens when the backend (and
+    /// all operators built from it) is dropped. The thread is detached
+    /// instead of joined, so dropping never waits for a running kinit.
+    pub fn spawn_renewal(&self, interval: Duration) -> Result<KerberosRenewal> {
+        let (tx, rx) = mpsc::channel::<()>();
+        let kerberos = self.clone();
+
+        thread::Builder::new()
+            .name("opendal-hdfs-kerberos".to_string())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(interval) {
+                    // The old tickets may still be valid, try again in next
+                    // round.
+                    if let Err(err) = kerberos.renew() {
+                        warn!("kerberos renew tickets failed: {err}");
+                    }
+                }
+            })
+            .map_err(|err| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "spawn kerberos renewal thread failed",
+                )
+                .with_context("service", Scheme::Hdfs)
+                .set_source(err)
+            })?;
+
+        Ok(KerberosRenewal { _stop: tx })
+    }
+}
+
+/// KerberosRenewal stops the renewal thread while dropping.
+///
+/// Dropping the sender disconnects the channel, which wakes up the thread
+/// and makes it exit before running kinit again. A kinit that is already
+/// running will be finished by the thread in the background, we don't wait
+/// for it since the backend could be dropped inside an async runtime.
+#[derive(Debug)]
+pub struct KerberosRenewal {
+    _stop: mpsc::Sender<()>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kerberos_new() {
+        let kerberos = Kerberos::new(
+            Some("hdfs/host@EXAMPLE.COM".to_string()),
+            Some("/etc/hdfs.keytab".to_string()),
+            None,
+        )
+        .expect("must succeed");
+        assert!(kerberos
+            .ticket_cache_path()
+            .ends_with("krb5cc_opendal_hdfs_host_EXAMPLE.COM"));
+
+        let kerberos =
+            Kerberos::new(None, None, Some("/tmp/krb5cc_1000".to_string())).expect("must succeed");
+        assert_eq!(kerberos.ticket_cache_path(), "/tmp/krb5cc_1000");
+        // Nothing to do without keytab.
+        kerberos.login().expect("must succeed");
+
+        assert!(Kerberos::new(Some("hdfs@EXAMPLE.COM".to_string()), None, None).is_err());
+        assert!(Kerberos::new(None, Some("/etc/hdfs.keytab".to_string()), None).is_err());
+    }
+
+    #[test]
+    fn test_kerberos_renewal_stop() {
+        let kerberos =
+            Kerberos::new(None, None, Some("/tmp/krb5cc_1000".to_string())).expect("must succeed");
+
+        let renewal = kerberos
+            .spawn_renewal(Duration::from_secs(3600))
+            .expect("must succeed");
+        let start = std::time::Instant::now();
+        // Dropping must not wait for the next renewal.
+        drop(renewal);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
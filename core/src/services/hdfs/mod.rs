@@ -19,5 +19,6 @@ mod backend;
 pub use backend::HdfsBuilder as Hdfs;
 
 mod error;
+mod kerberos;
 mod pager;
 mod writer;
@@ -18,11 +18,15 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use http::header;
+use http::header::HeaderName;
 use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
+use http::HeaderMap;
+use http::HeaderValue;
 use http::Request;
 use http::Response;
 use http::StatusCode;
@@ -55,13 +59,27 @@ use crate::*;
 /// Only `read` ans `stat` are supported. We can use this service to visit any
 /// HTTP Server like nginx, caddy.
 ///
+/// `stat` is implemented via `HEAD` request. For servers that don't allow
+/// `HEAD` (returns `405 Method Not Allowed`), we will fall back to a `GET`
+/// request that only fetches the first byte.
+///
 /// # Configuration
 ///
 /// - `endpoint`: set the endpoint for http
 /// - `root`: Set the work directory for backend
+/// - `username`/`password`: Set the basic auth for backend
+/// - `token`: Set the bearer token for backend
+/// - `header.<name>`: Set an extra header sent with every request, for
+///   example `header.x-api-key`
 ///
 /// You can refer to [`HttpBuilder`]'s docs for more information
 ///
+/// # Request Hook
+///
+/// For servers using custom auth or pre-signed URL schemes, users can set a
+/// hook via [`HttpBuilder::request_hook`] to mutate every request before
+/// sending, including its uri and headers.
+///
 /// # Example
 ///
 /// ## Via Builder
@@ -77,6 +95,8 @@ use crate::*;
 ///     let mut builder = Http::default();
 ///
 ///     builder.endpoint("127.0.0.1");
+///     // set extra header for every request
+///     builder.header("x-api-key", "<api_key>");
 ///
 ///     let op: Operator = Operator::new(builder)?.finish();
 ///     Ok(())
@@ -89,14 +109,25 @@ pub struct HttpBuilder {
     password: Option<String>,
     token: Option<String>,
     root: Option<String>,
+    headers: Vec<(String, String)>,
+    request_hook: Option<HttpRequestHook>,
     http_client: Option<HttpClient>,
 }
 
+/// The hook to mutate requests before sending.
+type HttpRequestHook = Arc<dyn Fn(&mut Request<AsyncBody>) -> Result<()> + Send + Sync>;
+
 impl Debug for HttpBuilder {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut de = f.debug_struct("Builder");
         de.field("endpoint", &self.endpoint);
         de.field("root", &self.root);
+        // Values of headers could be secrets.
+        de.field(
+            "headers",
+            &self.headers.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+        );
+        de.field("request_hook", &self.request_hook.is_some());
 
         de.finish()
     }
@@ -157,6 +188,30 @@ impl HttpBuilder {
         self
     }
 
+    /// Set an extra header which will be sent with every request.
+    ///
+    /// Headers with the same name will all be sent. Authorization set by
+    /// `username` or `token` will be replaced if `authorization` is set here.
+    pub fn header(&mut self, key: &str, value: &str) -> &mut Self {
+        if !key.is_empty() {
+            self.headers.push((key.to_string(), value.to_string()));
+        }
+        self
+    }
+
+    /// Set a hook to mutate every request before sending.
+    ///
+    /// The hook will be called after all headers are set, so it could be
+    /// used to sign requests or rewrite uri for pre-signed url schemes.
+    /// Returning an error will abort the request.
+    pub fn request_hook<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(&mut Request<AsyncBody>) -> Result<()> + Send + Sync + 'static,
+    {
+        self.request_hook = Some(Arc::new(hook));
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -182,6 +237,16 @@ impl Builder for HttpBuilder {
         map.get("password").map(|v| builder.password(v));
         map.get("token").map(|v| builder.token(v));
 
+        let mut headers: Vec<_> = map
+            .iter()
+            .filter_map(|(k, v)| k.strip_prefix("header.").map(|k| (k, v)))
+            .collect();
+        // Keep the order stable since HashMap is unordered.
+        headers.sort();
+        for (k, v) in headers {
+            builder.header(k, v);
+        }
+
         builder
     }
 
@@ -219,10 +284,30 @@ impl Builder for HttpBuilder {
             auth = Some(format_authorization_by_bearer(token)?)
         }
 
+        let mut headers = HeaderMap::new();
+        for (k, v) in &self.headers {
+            let name = HeaderName::from_bytes(k.as_bytes()).map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "header name is invalid")
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::Http)
+                    .with_context("header", k)
+                    .set_source(err)
+            })?;
+            let mut value = build_header_value(v).map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Http)
+                    .with_context("header", k)
+            })?;
+            value.set_sensitive(true);
+            headers.append(name, value);
+        }
+
         debug!("backend build finished: {:?}", &self);
         Ok(HttpBackend {
             endpoint: endpoint.to_string(),
             authorization: auth,
+            headers,
+            request_hook: self.request_hook.clone(),
             root,
             client,
         })
@@ -237,6 +322,8 @@ pub struct HttpBackend {
     client: HttpClient,
 
     authorization: Option<String>,
+    headers: HeaderMap<HeaderValue>,
+    request_hook: Option<HttpRequestHook>,
 }
 
 impl Debug for HttpBackend {
@@ -315,12 +402,70 @@ impl Accessor for HttpBackend {
             StatusCode::NOT_FOUND | StatusCode::FORBIDDEN if path.ends_with('/') => {
                 Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
             }
+            // Some servers and CDNs don't allow HEAD, try GET instead.
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED => {
+                resp.into_body().consume().await?;
+                self.stat_via_get(path, args).await
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
 }
 
 impl HttpBackend {
+    /// Apply extra headers and request hook before sending.
+    async fn send(&self, mut req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        let mut name = None;
+        for (k, v) in self.headers.iter() {
+            // Headers configured by users take precedence over the built-in ones,
+            // but all values of the same configured name should be kept.
+            if name != Some(k) {
+                req.headers_mut().remove(k);
+                name = Some(k);
+            }
+            req.headers_mut().append(k, v.clone());
+        }
+
+        if let Some(hook) = &self.request_hook {
+            hook(&mut req)?;
+        }
+
+        self.client.send(req).await
+    }
+
+    /// Stat the file by fetching the first byte of it.
+    async fn stat_via_get(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let resp = self
+            .http_get(
+                path,
+                BytesRange::new(Some(0), Some(1)),
+                args.if_match(),
+                args.if_none_match(),
+            )
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                resp.into_body().consume().await?;
+                Ok(RpStat::new(meta))
+            }
+            StatusCode::PARTIAL_CONTENT => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                // The content length of partial response is the size of
+                // range, use the total size in content range instead.
+                if let Some(size) = parse_content_range(resp.headers())?.and_then(|v| v.size()) {
+                    meta.set_content_length(size);
+                }
+                resp.into_body().consume().await?;
+                Ok(RpStat::new(meta))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
     async fn http_get(
         &self,
         path: &str,
@@ -354,7 +499,7 @@ impl HttpBackend {
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     async fn http_head(
@@ -385,7 +530,7 @@ impl HttpBackend {
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 }
 
@@ -394,9 +539,11 @@ mod tests {
     use anyhow::Result;
     use wiremock::matchers::basic_auth;
     use wiremock::matchers::bearer_token;
+    use wiremock::matchers::header;
     use wiremock::matchers::headers;
     use wiremock::matchers::method;
     use wiremock::matchers::path;
+    use wiremock::matchers::query_param;
     use wiremock::Mock;
     use wiremock::MockServer;
     use wiremock::ResponseTemplate;
@@ -512,6 +659,38 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_stat_fallback_to_get() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/hello"))
+            .respond_with(ResponseTemplate::new(405))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .and(headers("range", vec!["bytes=0-0"]))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 0-0/128")
+                    .set_body_string("H"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        let op = Operator::new(builder)?.finish();
+        let bs = op.stat("hello").await?;
+
+        assert_eq!(bs.mode(), EntryMode::FILE);
+        assert_eq!(bs.content_length(), 128);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_read_with() -> Result<()> {
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
@@ -561,4 +740,66 @@ mod tests {
         assert_eq!(bs.content_length(), 128);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_read_with_extra_headers() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .and(header("x-api-key", "your_key"))
+            .and(headers("authorization", vec!["Custom your_token"]))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "13")
+                    .set_body_string("Hello, World!"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut map = HashMap::new();
+        map.insert("endpoint".to_string(), mock_server.uri());
+        map.insert("token".to_string(), "ignored".to_string());
+        map.insert("header.x-api-key".to_string(), "your_key".to_string());
+        map.insert(
+            "header.authorization".to_string(),
+            "Custom your_token".to_string(),
+        );
+        let op = Operator::new(HttpBuilder::from_map(map))?.finish();
+
+        let bs = op.read("hello").await?;
+
+        assert_eq!(bs, b"Hello, World!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_with_request_hook() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/hello"))
+            .and(query_param("signature", "signed"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "128"))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        builder.request_hook(|req| {
+            let uri = format!("{}?signature=signed", req.uri());
+            *req.uri_mut() = uri.parse().map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "uri is invalid").set_source(err)
+            })?;
+            Ok(())
+        });
+        let op = Operator::new(builder)?.finish();
+        let bs = op.stat("hello").await?;
+
+        assert_eq!(bs.content_length(), 128);
+        Ok(())
+    }
 }
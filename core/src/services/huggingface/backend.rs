@@ -0,0 +1,296 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+
+use super::core::HuggingfaceCore;
+use super::core::RepoType;
+use super::error::parse_error;
+use super::pager::HuggingfacePager;
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_ENDPOINT: &str = "https://huggingface.co";
+const DEFAULT_REVISION: &str = "main";
+
+/// [Hugging Face Hub](https://huggingface.co/) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct HuggingfaceBuilder {
+    root: Option<String>,
+    repo_type: Option<String>,
+    repo_id: Option<String>,
+    revision: Option<String>,
+    endpoint: Option<String>,
+    token: Option<String>,
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for HuggingfaceBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Builder")
+            .field("root", &self.root)
+            .field("repo_type", &self.repo_type)
+            .field("repo_id", &self.repo_id)
+            .field("revision", &self.revision)
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+impl HuggingfaceBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string());
+        }
+        self
+    }
+
+    /// Set the type of the repository, could be `model`, `dataset` or `space`.
+    ///
+    /// default: "model"
+    pub fn repo_type(&mut self, repo_type: &str) -> &mut Self {
+        if !repo_type.is_empty() {
+            self.repo_type = Some(repo_type.to_string());
+        }
+        self
+    }
+
+    /// Set the id of the repository, like `openai-community/gpt2`.
+    pub fn repo_id(&mut self, repo_id: &str) -> &mut Self {
+        if !repo_id.is_empty() {
+            self.repo_id = Some(repo_id.trim_matches('/').to_string());
+        }
+        self
+    }
+
+    /// Set the revision to read from, could be a branch, tag or commit sha.
+    ///
+    /// Pin the revision to a commit sha to make sure the content won't change.
+    ///
+    /// default: "main"
+    pub fn revision(&mut self, revision: &str) -> &mut Self {
+        if !revision.is_empty() {
+            self.revision = Some(revision.to_string());
+        }
+        self
+    }
+
+    /// Set the endpoint of Hugging Face Hub.
+    ///
+    /// default: `https://huggingface.co`
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        }
+        self
+    }
+
+    /// Set the user access token used to access Hugging Face Hub.
+    ///
+    /// Token is required for private and gated repositories.
+    pub fn token(&mut self, token: &str) -> &mut Self {
+        if !token.is_empty() {
+            self.token = Some(token.to_string());
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for HuggingfaceBuilder {
+    const SCHEME: Scheme = Scheme::Huggingface;
+    type Accessor = HuggingfaceBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = HuggingfaceBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("repo_type").map(|v| builder.repo_type(v));
+        map.get("repo_id").map(|v| builder.repo_id(v));
+        map.get("revision").map(|v| builder.revision(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("token").map(|v| builder.token(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let repo_type = match self.repo_type.take() {
+            Some(repo_type) => RepoType::parse(&repo_type).map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Huggingface)
+            })?,
+            None => RepoType::Model,
+        };
+
+        let repo_id = self.repo_id.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "repo_id is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Huggingface)
+        })?;
+
+        let revision = self
+            .revision
+            .take()
+            .unwrap_or_else(|| DEFAULT_REVISION.to_string());
+
+        let endpoint = self
+            .endpoint
+            .take()
+            .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
+        debug!("backend use endpoint {}", endpoint);
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Huggingface)
+            })?
+        };
+
+        Ok(HuggingfaceBackend {
+            core: Arc::new(HuggingfaceCore {
+                root,
+                endpoint,
+                repo_type,
+                repo_id,
+                revision,
+                token: self.token.take(),
+                client,
+            }),
+        })
+    }
+}
+
+/// Backend for Hugging Face services.
+#[derive(Clone, Debug)]
+pub struct HuggingfaceBackend {
+    core: Arc<HuggingfaceCore>,
+}
+
+#[async_trait]
+impl Accessor for HuggingfaceBackend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = ();
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = HuggingfacePager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut ma = AccessorInfo::default();
+        ma.set_scheme(Scheme::Huggingface)
+            .set_root(&self.core.root)
+            .set_name(&self.core.repo_id)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                list: true,
+                list_with_delimiter_slash: true,
+                list_without_delimiter: true,
+
+                ..Default::default()
+            });
+
+        ma
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        // Files stored in LFS will be redirected to the CDN by resolve API.
+        let resp = self.core.hf_resolve(path, args.range()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        if build_abs_path(&self.core.root, path).is_empty() {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let status = self
+            .core
+            .hf_stat(path)
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "path not found in huggingface"))?;
+
+        if !status.is_dir() && path.ends_with('/') {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                "the path is a file but a dir is expected",
+            ));
+        }
+
+        Ok(RpStat::new(status.to_metadata()?))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let recursive = match args.delimiter() {
+            "/" => false,
+            "" => true,
+            v => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "huggingface only support delimiter `/` or empty",
+                )
+                .with_context("delimiter", v))
+            }
+        };
+
+        Ok((
+            RpList::default(),
+            HuggingfacePager::new(self.core.clone(), path, recursive),
+        ))
+    }
+}
@@ -0,0 +1,374 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use http::header;
+use http::HeaderMap;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use serde::Deserialize;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The type of the repository on Hugging Face Hub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoType {
+    Model,
+    Dataset,
+    Space,
+}
+
+impl RepoType {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "model" => Ok(RepoType::Model),
+            "dataset" => Ok(RepoType::Dataset),
+            "space" => Ok(RepoType::Space),
+            _ => Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "repo_type must be one of model, dataset and space",
+            )
+            .with_context("repo_type", s)),
+        }
+    }
+
+    /// The url prefix of the repository used by resolve API.
+    fn url_prefix(&self) -> &'static str {
+        match self {
+            RepoType::Model => "",
+            RepoType::Dataset => "datasets/",
+            RepoType::Space => "spaces/",
+        }
+    }
+
+    /// The api prefix of the repository used by hub API.
+    fn api_prefix(&self) -> &'static str {
+        match self {
+            RepoType::Model => "models",
+            RepoType::Dataset => "datasets",
+            RepoType::Space => "spaces",
+        }
+    }
+}
+
+pub struct HuggingfaceCore {
+    pub root: String,
+    pub endpoint: String,
+    pub repo_type: RepoType,
+    pub repo_id: String,
+    /// The branch, tag or commit sha to read from.
+    pub revision: String,
+    pub token: Option<String>,
+    pub client: HttpClient,
+}
+
+impl Debug for HuggingfaceCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HuggingfaceCore")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .field("repo_type", &self.repo_type)
+            .field("repo_id", &self.repo_id)
+            .field("revision", &self.revision)
+            .finish_non_exhaustive()
+    }
+}
+
+impl HuggingfaceCore {
+    fn sign(&self, req: &mut Request<AsyncBody>) -> Result<()> {
+        let headers = req.headers_mut();
+
+        headers.insert(
+            header::USER_AGENT,
+            format!("opendal/{VERSION} (service huggingface)")
+                .parse()
+                .map_err(|_| Error::new(ErrorKind::Unexpected, "invalid user agent"))?,
+        );
+        if let Some(token) = &self.token {
+            headers.insert(
+                header::AUTHORIZATION,
+                format_authorization_by_bearer(token)?
+                    .parse()
+                    .map_err(|_| Error::new(ErrorKind::Unexpected, "invalid token"))?,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Revision like `refs/pr/1` must be encoded as a single path segment.
+    fn encoded_revision(&self) -> String {
+        utf8_percent_encode(&self.revision, NON_ALPHANUMERIC).to_string()
+    }
+
+    /// Build the path relative to the repository root, without the
+    /// leading and trailing `/`.
+    fn build_repo_path(&self, path: &str) -> String {
+        build_abs_path(&self.root, path)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    pub async fn hf_resolve(
+        &self,
+        path: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = self.build_repo_path(path);
+
+        let url = format!(
+            "{}/{}{}/resolve/{}/{}",
+            self.endpoint,
+            self.repo_type.url_prefix(),
+            self.repo_id,
+            self.encoded_revision(),
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::get(&url);
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn hf_path_info(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = self.build_repo_path(path);
+
+        let url = format!(
+            "{}/api/{}/{}/paths-info/{}",
+            self.endpoint,
+            self.repo_type.api_prefix(),
+            self.repo_id,
+            self.encoded_revision()
+        );
+        let body = format!(
+            "paths={}&expand=True",
+            utf8_percent_encode(&p, NON_ALPHANUMERIC)
+        );
+
+        let mut req = Request::post(&url)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(AsyncBody::Bytes(body.into()))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    /// Get the status of given path.
+    ///
+    /// Returns `None` if the path doesn't exist.
+    pub async fn hf_stat(&self, path: &str) -> Result<Option<HuggingfaceStatus>> {
+        let resp = self.hf_path_info(path).await?;
+
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let statuses: Vec<HuggingfaceStatus> =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        let p = self.build_repo_path(path);
+        Ok(statuses.into_iter().find(|v| v.path == p))
+    }
+
+    /// List the tree of given path.
+    ///
+    /// `next` is the url of next page returned in the `Link` header.
+    pub async fn hf_list(
+        &self,
+        path: &str,
+        recursive: bool,
+        next: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = match next {
+            Some(next) => next.to_string(),
+            None => {
+                let p = self.build_repo_path(path);
+
+                let mut url = format!(
+                    "{}/api/{}/{}/tree/{}",
+                    self.endpoint,
+                    self.repo_type.api_prefix(),
+                    self.repo_id,
+                    self.encoded_revision()
+                );
+                if !p.is_empty() {
+                    url.push('/');
+                    url.push_str(&percent_encode_path(&p));
+                }
+                if recursive {
+                    url.push_str("?recursive=true");
+                }
+                url
+            }
+        };
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+}
+
+/// Parse the url of next page from the `Link` header.
+///
+/// The header looks like `<https://huggingface.co/api/...?cursor=xxx>; rel="next"`.
+pub fn parse_next_link(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get(header::LINK)?.to_str().ok()?;
+
+    link.split(',').find_map(|v| {
+        let (url, params) = v.split_once(';')?;
+        if !params.split(';').any(|p| p.trim() == r#"rel="next""#) {
+            return None;
+        }
+        Some(
+            url.trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_string(),
+        )
+    })
+}
+
+/// The status of a path returned by paths-info and tree API.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HuggingfaceStatus {
+    /// Either `file` or `directory`.
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub oid: String,
+    pub size: u64,
+    pub path: String,
+    pub lfs: Option<HuggingfaceLfs>,
+    pub last_commit: Option<HuggingfaceLastCommit>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct HuggingfaceLfs {
+    pub oid: String,
+    pub size: u64,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct HuggingfaceLastCommit {
+    pub date: String,
+}
+
+impl HuggingfaceStatus {
+    pub fn is_dir(&self) -> bool {
+        self.type_ == "directory"
+    }
+
+    pub fn to_metadata(&self) -> Result<Metadata> {
+        if self.is_dir() {
+            return Ok(Metadata::new(EntryMode::DIR));
+        }
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        match &self.lfs {
+            Some(lfs) => {
+                meta.set_content_length(lfs.size);
+                meta.set_etag(&lfs.oid);
+            }
+            None => {
+                meta.set_content_length(self.size);
+                meta.set_etag(&self.oid);
+            }
+        }
+        if let Some(last_commit) = &self.last_commit {
+            meta.set_last_modified(parse_datetime_from_rfc3339(&last_commit.date)?);
+        }
+
+        Ok(meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_status() -> Result<()> {
+        let bs = r#"[
+            {"type": "file", "oid": "a1b2", "size": 262144, "path": "testdata/normal_file",
+             "lastCommit": {"id": "c3d4", "title": "init", "date": "2023-07-19T08:21:58.000Z"}},
+            {"type": "file", "oid": "e5f6", "size": 4242, "path": "testdata/model.bin",
+             "lfs": {"oid": "0a1b2c", "size": 4242, "pointerSize": 132}},
+            {"type": "directory", "oid": "g7h8", "size": 0, "path": "testdata/normal_dir"}
+        ]"#;
+        let statuses: Vec<HuggingfaceStatus> =
+            serde_json::from_str(bs).map_err(new_json_deserialize_error)?;
+
+        let meta = statuses[0].to_metadata()?;
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), 262144);
+        assert_eq!(meta.etag(), Some("a1b2"));
+        assert_eq!(
+            meta.last_modified(),
+            Some(parse_datetime_from_rfc3339("2023-07-19T08:21:58.000Z")?)
+        );
+
+        let meta = statuses[1].to_metadata()?;
+        assert_eq!(meta.content_length(), 4242);
+        assert_eq!(meta.etag(), Some("0a1b2c"));
+
+        assert!(statuses[2].is_dir());
+        assert_eq!(statuses[2].to_metadata()?.mode(), EntryMode::DIR);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_next_link() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_next_link(&headers), None);
+
+        headers.insert(
+            header::LINK,
+            HeaderValue::from_static(
+                r#"<https://huggingface.co/api/models/gpt2/tree/main?cursor=ZXlKbWFXeGw>; rel="next""#,
+            ),
+        );
+        assert_eq!(
+            parse_next_link(&headers).as_deref(),
+            Some("https://huggingface.co/api/models/gpt2/tree/main?cursor=ZXlKbWFXeGw")
+        );
+    }
+}
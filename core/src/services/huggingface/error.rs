@@ -0,0 +1,125 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// HuggingfaceError is the error returned by Hugging Face Hub.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct HuggingfaceError {
+    error: String,
+}
+
+/// Parse error response into Error.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    // Hub returns `X-Error-Code` like `RepoNotFound`, `RevisionNotFound`,
+    // `EntryNotFound` and `GatedRepo` to describe the error.
+    let error_code = parts
+        .headers
+        .get("x-error-code")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+
+    let (kind, retryable) = match (parts.status, error_code) {
+        (_, "RepoNotFound" | "RevisionNotFound" | "EntryNotFound") => (ErrorKind::NotFound, false),
+        (_, "GatedRepo") => (ErrorKind::PermissionDenied, false),
+        (StatusCode::NOT_FOUND, _) => (ErrorKind::NotFound, false),
+        (StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN, _) => {
+            (ErrorKind::PermissionDenied, false)
+        }
+        (StatusCode::TOO_MANY_REQUESTS, _) => (ErrorKind::RateLimited, true),
+        (
+            StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT,
+            _,
+        ) => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let message = match serde_json::from_slice::<HuggingfaceError>(&bs) {
+        Ok(hf_error) if !hf_error.error.is_empty() => format!("{hf_error:?}"),
+        _ => String::from_utf8_lossy(&bs).into_owned(),
+    };
+
+    let mut err = Error::new(kind, &message).with_context("response", format!("{parts:?}"));
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::raw::oio::into_stream;
+
+    #[tokio::test]
+    async fn test_parse_error() -> Result<()> {
+        let cases = vec![
+            // Hub returns 401 for not exist repos to avoid leaking private repos.
+            (
+                StatusCode::UNAUTHORIZED,
+                "RepoNotFound",
+                ErrorKind::NotFound,
+            ),
+            (StatusCode::NOT_FOUND, "EntryNotFound", ErrorKind::NotFound),
+            (
+                StatusCode::FORBIDDEN,
+                "GatedRepo",
+                ErrorKind::PermissionDenied,
+            ),
+            (StatusCode::UNAUTHORIZED, "", ErrorKind::PermissionDenied),
+        ];
+
+        for (status, code, kind) in cases {
+            let body = bytes::Bytes::from(r#"{"error": "Repository Not Found"}"#);
+            let body = IncomingAsyncBody::new(
+                Box::new(into_stream::from_futures_stream(stream::iter(vec![Ok(
+                    body,
+                )]))),
+                None,
+            );
+            let resp = Response::builder()
+                .status(status)
+                .header("x-error-code", code)
+                .body(body)
+                .unwrap();
+
+            let err = parse_error(resp).await?;
+            assert_eq!(err.kind(), kind, "{status} {code}");
+            assert!(err.to_string().contains("Repository Not Found"));
+        }
+
+        Ok(())
+    }
+}
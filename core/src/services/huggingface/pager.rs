@@ -0,0 +1,101 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::core::parse_next_link;
+use super::core::HuggingfaceCore;
+use super::core::HuggingfaceStatus;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct HuggingfacePager {
+    core: Arc<HuggingfaceCore>,
+    path: String,
+    recursive: bool,
+
+    next: Option<String>,
+    done: bool,
+}
+
+impl HuggingfacePager {
+    pub fn new(core: Arc<HuggingfaceCore>, path: &str, recursive: bool) -> Self {
+        HuggingfacePager {
+            core,
+            path: path.to_string(),
+            recursive,
+
+            next: None,
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for HuggingfacePager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let resp = self
+            .core
+            .hf_list(&self.path, self.recursive, self.next.as_deref())
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {}
+            // Listing a not exist dir should return empty.
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                self.done = true;
+                return Ok(None);
+            }
+            _ => return Err(parse_error(resp).await?),
+        }
+
+        self.next = parse_next_link(resp.headers());
+        if self.next.is_none() {
+            self.done = true;
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let statuses: Vec<HuggingfaceStatus> =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        let mut entries = Vec::with_capacity(statuses.len());
+        for status in statuses {
+            // Only files will be returned while listing without delimiter.
+            if self.recursive && status.is_dir() {
+                continue;
+            }
+
+            let mut path = build_rel_path(&self.core.root, &format!("/{}", status.path));
+            if status.is_dir() {
+                path.push('/');
+            }
+
+            entries.push(oio::Entry::new(&path, status.to_metadata()?));
+        }
+
+        Ok(Some(entries))
+    }
+}
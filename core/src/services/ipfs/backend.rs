@@ -450,26 +450,32 @@ impl oio::Page for DirStream {
             Error::new(ErrorKind::Unexpected, "deserialize protobuf from response").set_source(e)
         })?;
 
+        // Links without name are chunks of a file instead of dir entries.
         let names = pb_node
             .links
             .into_iter()
-            .map(|v| v.name.unwrap())
+            .filter_map(|v| v.name)
             .collect::<Vec<String>>();
 
+        // Entries' path should be relative to root instead of current dir.
+        let parent = if self.path == "/" { "" } else { &self.path };
+
         let mut oes = Vec::with_capacity(names.len());
 
-        for mut name in names {
+        for name in names {
+            let mut path = format!("{parent}{name}");
+
             let meta = self
                 .backend
-                .stat(&name, OpStat::new())
+                .stat(&path, OpStat::new())
                 .await?
                 .into_metadata();
 
             if meta.mode().is_dir() {
-                name += "/";
+                path += "/";
             }
 
-            oes.push(oio::Entry::new(&name, meta))
+            oes.push(oio::Entry::new(&path, meta))
         }
 
         self.consumed = true;
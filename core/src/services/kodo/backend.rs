@@ -0,0 +1,419 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+
+use super::core::KodoCore;
+use super::core::StatOutput;
+use super::error::parse_error;
+use super::error::KODO_STATUS_NOT_FOUND;
+use super::pager::KodoPager;
+use super::writer::KodoWriter;
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_UPLOAD_ENDPOINT: &str = "https://upload.qiniup.com";
+const DEFAULT_RS_ENDPOINT: &str = "https://rs.qiniuapi.com";
+const DEFAULT_RSF_ENDPOINT: &str = "https://rsf.qiniuapi.com";
+
+/// [Qiniu Kodo](https://www.qiniu.com/products/kodo) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct KodoBuilder {
+    root: Option<String>,
+    bucket: Option<String>,
+    domain: Option<String>,
+    access_key: Option<String>,
+    secret_key: Option<String>,
+    upload_endpoint: Option<String>,
+    rs_endpoint: Option<String>,
+    rsf_endpoint: Option<String>,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for KodoBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+
+        ds.field("root", &self.root);
+        ds.field("bucket", &self.bucket);
+        ds.field("domain", &self.domain);
+        ds.field("upload_endpoint", &self.upload_endpoint);
+        ds.field("rs_endpoint", &self.rs_endpoint);
+        ds.field("rsf_endpoint", &self.rsf_endpoint);
+        if self.access_key.is_some() {
+            ds.field("access_key", &"<redacted>");
+        }
+        if self.secret_key.is_some() {
+            ds.field("secret_key", &"<redacted>");
+        }
+        ds.finish()
+    }
+}
+
+impl KodoBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+        self
+    }
+
+    /// Set the bucket name.
+    pub fn bucket(&mut self, bucket: &str) -> &mut Self {
+        if !bucket.is_empty() {
+            self.bucket = Some(bucket.to_string())
+        }
+        self
+    }
+
+    /// Set the domain bound to the bucket, objects will be downloaded via
+    /// this domain.
+    ///
+    /// For example: `https://cdn.example.com`
+    pub fn domain(&mut self, domain: &str) -> &mut Self {
+        if !domain.is_empty() {
+            self.domain = Some(domain.trim_end_matches('/').to_string())
+        }
+        self
+    }
+
+    /// Set the access key of Qiniu.
+    pub fn access_key(&mut self, access_key: &str) -> &mut Self {
+        if !access_key.is_empty() {
+            self.access_key = Some(access_key.to_string())
+        }
+        self
+    }
+
+    /// Set the secret key of Qiniu.
+    pub fn secret_key(&mut self, secret_key: &str) -> &mut Self {
+        if !secret_key.is_empty() {
+            self.secret_key = Some(secret_key.to_string())
+        }
+        self
+    }
+
+    /// Set the endpoint for uploading objects.
+    ///
+    /// default: "https://upload.qiniup.com", which will route to the region
+    /// of bucket automatically.
+    pub fn upload_endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.upload_endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+        self
+    }
+
+    /// Set the endpoint for managing objects, like stat and delete.
+    ///
+    /// default: "https://rs.qiniuapi.com"
+    pub fn rs_endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.rs_endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+        self
+    }
+
+    /// Set the endpoint for listing objects.
+    ///
+    /// default: "https://rsf.qiniuapi.com"
+    pub fn rsf_endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.rsf_endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for KodoBuilder {
+    const SCHEME: Scheme = Scheme::Kodo;
+    type Accessor = KodoBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = KodoBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("bucket").map(|v| builder.bucket(v));
+        map.get("domain").map(|v| builder.domain(v));
+        map.get("access_key").map(|v| builder.access_key(v));
+        map.get("secret_key").map(|v| builder.secret_key(v));
+        map.get("upload_endpoint")
+            .map(|v| builder.upload_endpoint(v));
+        map.get("rs_endpoint").map(|v| builder.rs_endpoint(v));
+        map.get("rsf_endpoint").map(|v| builder.rsf_endpoint(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        debug!("backend build started: {:?}", self);
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let bucket = self.bucket.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "bucket is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Kodo)
+        })?;
+        debug!("backend use bucket {}", bucket);
+
+        let domain = self.domain.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "domain is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Kodo)
+        })?;
+        debug!("backend use domain {}", domain);
+
+        let access_key = self.access_key.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "access_key is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Kodo)
+        })?;
+        let secret_key = self.secret_key.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "secret_key is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Kodo)
+        })?;
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Kodo)
+            })?
+        };
+
+        debug!("backend build finished");
+        Ok(KodoBackend {
+            core: Arc::new(KodoCore {
+                root,
+                bucket,
+                domain,
+                upload_endpoint: self
+                    .upload_endpoint
+                    .take()
+                    .unwrap_or_else(|| DEFAULT_UPLOAD_ENDPOINT.to_string()),
+                rs_endpoint: self
+                    .rs_endpoint
+                    .take()
+                    .unwrap_or_else(|| DEFAULT_RS_ENDPOINT.to_string()),
+                rsf_endpoint: self
+                    .rsf_endpoint
+                    .take()
+                    .unwrap_or_else(|| DEFAULT_RSF_ENDPOINT.to_string()),
+                access_key,
+                secret_key,
+                client,
+            }),
+        })
+    }
+}
+
+/// Backend for Qiniu Kodo services.
+#[derive(Debug, Clone)]
+pub struct KodoBackend {
+    core: Arc<KodoCore>,
+}
+
+#[async_trait]
+impl Accessor for KodoBackend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = KodoWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = KodoPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Kodo)
+            .set_root(&self.core.root)
+            .set_name(&self.core.bucket)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_with_content_type: true,
+
+                create_dir: true,
+                delete: true,
+                copy: true,
+                rename: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_delimiter_slash: true,
+                list_without_delimiter: true,
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        let resp = self
+            .core
+            .kodo_upload_object(path, None, bytes::Bytes::new())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCreateDir::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let resp = self.core.kodo_get_object(path, args.range()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if args.content_length().is_none() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write without content length is not supported",
+            ));
+        }
+
+        Ok((
+            RpWrite::default(),
+            KodoWriter::new(self.core.clone(), args, path.to_string()),
+        ))
+    }
+
+    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.kodo_copy_object(from, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCopy::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
+        let resp = self.core.kodo_move_object(from, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpRename::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let resp = self.core.kodo_stat_object(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let output: StatOutput =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+                Ok(RpStat::new(output.into_metadata(path)))
+            }
+            v if v.as_u16() == KODO_STATUS_NOT_FOUND && path.ends_with('/') => {
+                Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.kodo_delete_object(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            v if v.as_u16() == KODO_STATUS_NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((
+            RpList::default(),
+            KodoPager::new(self.core.clone(), path, args.delimiter(), args.limit()),
+        ))
+    }
+}
@@ -0,0 +1,428 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::time::Duration;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::TimeZone;
+use chrono::Utc;
+use hmac::Hmac;
+use hmac::Mac;
+use http::header;
+use http::HeaderValue;
+use http::Request;
+use http::Response;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use serde::Deserialize;
+use serde::Serialize;
+use sha1::Sha1;
+
+use crate::raw::*;
+use crate::*;
+
+/// The expiration of upload token and download token we generated.
+const KODO_TOKEN_EXPIRE: Duration = Duration::from_secs(3600);
+
+pub struct KodoCore {
+    pub root: String,
+    pub bucket: String,
+    /// The domain bound to the bucket which is used to download objects.
+    pub domain: String,
+    pub upload_endpoint: String,
+    pub rs_endpoint: String,
+    pub rsf_endpoint: String,
+    pub access_key: String,
+    pub secret_key: String,
+    pub client: HttpClient,
+}
+
+impl Debug for KodoCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KodoCore")
+            .field("root", &self.root)
+            .field("bucket", &self.bucket)
+            .field("domain", &self.domain)
+            .field("upload_endpoint", &self.upload_endpoint)
+            .field("rs_endpoint", &self.rs_endpoint)
+            .field("rsf_endpoint", &self.rsf_endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl KodoCore {
+    #[inline]
+    pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        self.client.send(req).await
+    }
+
+    /// Sign the data with secret key, returns the url safe base64 encoded
+    /// HMAC-SHA1 digest.
+    fn hmac_sign(&self, data: &[u8]) -> String {
+        let mut mac = Hmac::<Sha1>::new_from_slice(self.secret_key.as_bytes())
+            .expect("hmac can take key of any size");
+        mac.update(data);
+        general_purpose::URL_SAFE.encode(mac.finalize().into_bytes())
+    }
+
+    /// Sign the management request with `QBox` token.
+    ///
+    /// The signed content is `<path>?<query>\n` since we never send form
+    /// body in management requests.
+    ///
+    /// Ref: <https://developer.qiniu.com/kodo/1201/access-token>
+    fn sign(&self, req: &mut Request<AsyncBody>) -> Result<()> {
+        let path_and_query = req
+            .uri()
+            .path_and_query()
+            .map(|v| v.as_str())
+            .unwrap_or("/");
+        let sign = self.hmac_sign(format!("{path_and_query}\n").as_bytes());
+
+        let value = format!("QBox {}:{sign}", self.access_key);
+        req.headers_mut().insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&value).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "header value is invalid").set_source(err)
+            })?,
+        );
+
+        Ok(())
+    }
+
+    /// Generate the upload token which allows to overwrite given key.
+    ///
+    /// Ref: <https://developer.qiniu.com/kodo/1208/upload-token>
+    pub fn upload_token(&self, key: &str, now: DateTime<Utc>) -> String {
+        let deadline = now.timestamp() + KODO_TOKEN_EXPIRE.as_secs() as i64;
+        let policy = serde_json::to_string(&PutPolicy {
+            scope: format!("{}:{key}", self.bucket),
+            deadline,
+        })
+        .expect("put policy must be valid json");
+        let policy = general_purpose::URL_SAFE.encode(policy);
+
+        format!(
+            "{}:{}:{policy}",
+            self.access_key,
+            self.hmac_sign(policy.as_bytes())
+        )
+    }
+
+    /// Build the download url of given key with download token, the token
+    /// is ignored by public buckets.
+    ///
+    /// Ref: <https://developer.qiniu.com/kodo/1202/download-token>
+    pub fn download_url(&self, key: &str, expire: Duration, now: DateTime<Utc>) -> String {
+        let deadline = now.timestamp() + expire.as_secs() as i64;
+        let url = format!("{}/{}?e={deadline}", self.domain, percent_encode_path(key));
+        let token = format!("{}:{}", self.access_key, self.hmac_sign(url.as_bytes()));
+
+        format!("{url}&token={token}")
+    }
+}
+
+impl KodoCore {
+    pub fn kodo_get_object_request(
+        &self,
+        path: &str,
+        range: BytesRange,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+        let url = self.download_url(&p, KODO_TOKEN_EXPIRE, Utc::now());
+
+        let mut req = Request::get(&url);
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+
+        req.body(AsyncBody::Empty).map_err(new_request_build_error)
+    }
+
+    pub async fn kodo_get_object(
+        &self,
+        path: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let req = self.kodo_get_object_request(path, range)?;
+
+        self.send(req).await
+    }
+
+    /// Upload the object via form upload.
+    ///
+    /// Ref: <https://developer.qiniu.com/kodo/1312/upload>
+    pub async fn kodo_upload_object(
+        &self,
+        path: &str,
+        content_type: Option<&str>,
+        body: Bytes,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut file = FormDataPart::new("file").header(
+            header::CONTENT_DISPOSITION,
+            "form-data; name=\"file\"; filename=\"file\""
+                .parse()
+                .expect("must be valid header value"),
+        );
+        if let Some(v) = content_type {
+            file = file.header(
+                header::CONTENT_TYPE,
+                HeaderValue::from_str(v).map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "content type is invalid").set_source(err)
+                })?,
+            );
+        }
+
+        let multipart = Multipart::new()
+            .part(FormDataPart::new("token").content(self.upload_token(&p, Utc::now())))
+            .part(FormDataPart::new("key").content(p))
+            .part(file.content(body));
+
+        let req = multipart.apply(Request::post(&self.upload_endpoint))?;
+
+        self.send(req).await
+    }
+
+    pub async fn kodo_stat_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/stat/{}", self.rs_endpoint, self.entry_uri(&p));
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    pub async fn kodo_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/delete/{}", self.rs_endpoint, self.entry_uri(&p));
+
+        self.kodo_rs_post(&url).await
+    }
+
+    pub async fn kodo_copy_object(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let from = build_abs_path(&self.root, from);
+        let to = build_abs_path(&self.root, to);
+
+        let url = format!(
+            "{}/copy/{}/{}/force/true",
+            self.rs_endpoint,
+            self.entry_uri(&from),
+            self.entry_uri(&to)
+        );
+
+        self.kodo_rs_post(&url).await
+    }
+
+    pub async fn kodo_move_object(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let from = build_abs_path(&self.root, from);
+        let to = build_abs_path(&self.root, to);
+
+        let url = format!(
+            "{}/move/{}/{}/force/true",
+            self.rs_endpoint,
+            self.entry_uri(&from),
+            self.entry_uri(&to)
+        );
+
+        self.kodo_rs_post(&url).await
+    }
+
+    /// List objects with given prefix.
+    ///
+    /// Ref: <https://developer.qiniu.com/kodo/1284/list>
+    pub async fn kodo_list_objects(
+        &self,
+        path: &str,
+        marker: &str,
+        delimiter: &str,
+        limit: Option<usize>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}/list?bucket={}&prefix={}",
+            self.rsf_endpoint,
+            utf8_percent_encode(&self.bucket, NON_ALPHANUMERIC),
+            utf8_percent_encode(&p, NON_ALPHANUMERIC)
+        );
+        if !delimiter.is_empty() {
+            url.push_str(&format!(
+                "&delimiter={}",
+                utf8_percent_encode(delimiter, NON_ALPHANUMERIC)
+            ));
+        }
+        if !marker.is_empty() {
+            url.push_str(&format!(
+                "&marker={}",
+                utf8_percent_encode(marker, NON_ALPHANUMERIC)
+            ));
+        }
+        if let Some(limit) = limit {
+            url.push_str(&format!("&limit={limit}"));
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    async fn kodo_rs_post(&self, url: &str) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::post(url)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    /// The encoded entry uri of given key, which is url safe base64 of
+    /// `<bucket>:<key>`.
+    fn entry_uri(&self, key: &str) -> String {
+        general_purpose::URL_SAFE.encode(format!("{}:{key}", self.bucket))
+    }
+}
+
+/// Ref: <https://developer.qiniu.com/kodo/1206/put-policy>
+#[derive(Debug, Serialize)]
+struct PutPolicy {
+    /// `<bucket>:<key>` means we are allowed to overwrite the key.
+    scope: String,
+    deadline: i64,
+}
+
+/// Ref: <https://developer.qiniu.com/kodo/1308/stat>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StatOutput {
+    pub fsize: u64,
+    pub hash: String,
+    pub mime_type: String,
+    /// The put time in the unit of 100 nanoseconds.
+    pub put_time: i64,
+    pub md5: Option<String>,
+}
+
+impl StatOutput {
+    pub fn into_metadata(self, path: &str) -> Metadata {
+        let mode = if path.ends_with('/') {
+            EntryMode::DIR
+        } else {
+            EntryMode::FILE
+        };
+
+        let mut m = Metadata::new(mode)
+            .with_content_length(self.fsize)
+            .with_etag(self.hash);
+        if !self.mime_type.is_empty() {
+            m.set_content_type(&self.mime_type);
+        }
+        if let Some(v) = self.md5 {
+            m.set_content_md5(&v);
+        }
+        if let Some(v) = parse_put_time(self.put_time) {
+            m.set_last_modified(v);
+        }
+        m
+    }
+}
+
+/// Convert put time in the unit of 100 nanoseconds to datetime.
+pub fn parse_put_time(v: i64) -> Option<DateTime<Utc>> {
+    let secs = v / 10_000_000;
+    let nsecs = (v % 10_000_000) * 100;
+
+    Utc.timestamp_opt(secs, nsecs as u32).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn core() -> KodoCore {
+        KodoCore {
+            root: "/".to_string(),
+            bucket: "my-bucket".to_string(),
+            domain: "http://dn.example.com".to_string(),
+            upload_endpoint: "https://upload.qiniup.com".to_string(),
+            rs_endpoint: "https://rs.qiniuapi.com".to_string(),
+            rsf_endpoint: "https://rsf.qiniuapi.com".to_string(),
+            access_key: "MY_ACCESS_KEY".to_string(),
+            secret_key: "MY_SECRET_KEY".to_string(),
+            client: HttpClient::new().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_sign() {
+        let core = core();
+
+        let mut req = Request::post("https://rs.qiniuapi.com/delete/bXktYnVja2V0OmtleQ==")
+            .body(AsyncBody::Empty)
+            .unwrap();
+        core.sign(&mut req).unwrap();
+        assert_eq!(
+            req.headers()[header::AUTHORIZATION],
+            "QBox MY_ACCESS_KEY:N18zT73lzvwPhmDJZnA3bJ8W-_o="
+        );
+    }
+
+    #[test]
+    fn test_upload_token() {
+        let now = Utc.timestamp_opt(1_451_487_600, 0).unwrap();
+        assert_eq!(core().upload_token("sunflower.jpg", now), "MY_ACCESS_KEY:DBQNyXcLE40OV3U9xHEWA-AMlcU=:eyJzY29wZSI6Im15LWJ1Y2tldDpzdW5mbG93ZXIuanBnIiwiZGVhZGxpbmUiOjE0NTE0OTEyMDB9");
+    }
+
+    #[test]
+    fn test_download_url() {
+        let now = Utc.timestamp_opt(1_451_487_600, 0).unwrap();
+        assert_eq!(
+            core().download_url("dir/sunflower.jpg", Duration::from_secs(3600), now),
+            "http://dn.example.com/dir/sunflower.jpg?e=1451491200&token=MY_ACCESS_KEY:OqCRho2E1z0OLVSwoMzjynWyh7U="
+        );
+    }
+
+    #[test]
+    fn test_parse_put_time() {
+        assert_eq!(
+            parse_put_time(16_000_000_001_234_567).unwrap().to_rfc3339(),
+            "2020-09-13T12:26:40.123456700+00:00"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// The object doesn't exist.
+pub const KODO_STATUS_NOT_FOUND: u16 = 612;
+/// The object already exists.
+const KODO_STATUS_ALREADY_EXISTS: u16 = 614;
+/// The bucket doesn't exist.
+const KODO_STATUS_BUCKET_NOT_FOUND: u16 = 631;
+/// The request is throttled.
+const KODO_STATUS_TOO_MANY_REQUESTS: u16 = 573;
+/// The server is unavailable.
+const KODO_STATUS_UNAVAILABLE: u16 = 599;
+
+/// KodoError is the error returned by Qiniu Kodo.
+///
+/// Ref: <https://developer.qiniu.com/kodo/3928/error-responses>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct KodoError {
+    error: String,
+}
+
+/// Parse error response into Error.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    let (kind, retryable) = match parts.status.as_u16() {
+        KODO_STATUS_NOT_FOUND | KODO_STATUS_BUCKET_NOT_FOUND => (ErrorKind::NotFound, false),
+        KODO_STATUS_ALREADY_EXISTS => (ErrorKind::AlreadyExists, false),
+        KODO_STATUS_TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
+        KODO_STATUS_UNAVAILABLE => (ErrorKind::Unexpected, true),
+        _ => match parts.status {
+            StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                (ErrorKind::PermissionDenied, false)
+            }
+            StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
+            StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+            _ => (ErrorKind::Unexpected, false),
+        },
+    };
+
+    let message = match serde_json::from_slice::<KodoError>(&bs) {
+        Ok(kodo_error) if !kodo_error.error.is_empty() => format!("{kodo_error:?}"),
+        _ => String::from_utf8_lossy(&bs).into_owned(),
+    };
+
+    let mut err = Error::new(kind, &message).with_context("response", format!("{parts:?}"));
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::raw::oio::into_stream;
+
+    #[tokio::test]
+    async fn test_parse_error() -> Result<()> {
+        let cases = vec![
+            (612, ErrorKind::NotFound, false),
+            (614, ErrorKind::AlreadyExists, false),
+            (401, ErrorKind::PermissionDenied, false),
+            (573, ErrorKind::RateLimited, true),
+            (599, ErrorKind::Unexpected, true),
+        ];
+
+        for (status, kind, temporary) in cases {
+            let body = bytes::Bytes::from(r#"{"error": "no such file or directory"}"#);
+            let body = IncomingAsyncBody::new(
+                Box::new(into_stream::from_futures_stream(stream::iter(vec![Ok(
+                    body,
+                )]))),
+                None,
+            );
+            let resp = Response::builder().status(status).body(body).unwrap();
+
+            let err = parse_error(resp).await?;
+            assert_eq!(err.kind(), kind);
+            assert_eq!(err.is_temporary(), temporary);
+            assert!(err.to_string().contains("no such file or directory"));
+        }
+
+        Ok(())
+    }
+}
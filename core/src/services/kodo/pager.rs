@@ -0,0 +1,158 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::core::parse_put_time;
+use super::core::KodoCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct KodoPager {
+    core: Arc<KodoCore>,
+    path: String,
+    delimiter: String,
+    limit: Option<usize>,
+
+    marker: String,
+    done: bool,
+}
+
+impl KodoPager {
+    pub fn new(core: Arc<KodoCore>, path: &str, delimiter: &str, limit: Option<usize>) -> Self {
+        Self {
+            core,
+            path: path.to_string(),
+            delimiter: delimiter.to_string(),
+            limit,
+
+            marker: "".to_string(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for KodoPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let resp = self
+            .core
+            .kodo_list_objects(&self.path, &self.marker, &self.delimiter, self.limit)
+            .await?;
+
+        if resp.status() != http::StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: ListOutput = serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        // The marker will be empty if there are no more objects.
+        self.done = output.marker.is_empty();
+        self.marker = output.marker;
+
+        let mut entries = Vec::with_capacity(output.common_prefixes.len() + output.items.len());
+
+        for prefix in output.common_prefixes {
+            let de = oio::Entry::new(
+                &build_rel_path(&self.core.root, &prefix),
+                Metadata::new(EntryMode::DIR),
+            );
+
+            entries.push(de);
+        }
+
+        for object in output.items {
+            if object.key.ends_with('/') {
+                continue;
+            }
+
+            let mut meta = Metadata::new(EntryMode::FILE);
+            meta.set_content_length(object.fsize);
+            meta.set_etag(&object.hash);
+            if !object.mime_type.is_empty() {
+                meta.set_content_type(&object.mime_type);
+            }
+            if let Some(v) = parse_put_time(object.put_time) {
+                meta.set_last_modified(v);
+            }
+
+            let de = oio::Entry::new(&build_rel_path(&self.core.root, &object.key), meta);
+
+            entries.push(de);
+        }
+
+        Ok(Some(entries))
+    }
+}
+
+/// Ref: <https://developer.qiniu.com/kodo/1284/list>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ListOutput {
+    marker: String,
+    common_prefixes: Vec<String>,
+    items: Vec<ListItem>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ListItem {
+    key: String,
+    fsize: u64,
+    hash: String,
+    mime_type: String,
+    put_time: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_list_output() {
+        let content = r#"{
+            "marker": "eyJjIjowLCJrIjoiZGlyL2IudHh0In0=",
+            "commonPrefixes": ["dir/sub/"],
+            "items": [
+                {
+                    "key": "dir/a.txt",
+                    "hash": "FvT9pUXnLrHyhrjgNk1L7ZkqXDRZ",
+                    "fsize": 1024,
+                    "mimeType": "text/plain",
+                    "putTime": 16000000000000000,
+                    "type": 0,
+                    "status": 0
+                }
+            ]
+        }"#;
+
+        let output: ListOutput = serde_json::from_str(content).expect("output must be valid");
+        assert_eq!(output.marker, "eyJjIjowLCJrIjoiZGlyL2IudHh0In0=");
+        assert_eq!(output.common_prefixes, vec!["dir/sub/"]);
+        assert_eq!(output.items[0].key, "dir/a.txt");
+        assert_eq!(output.items[0].fsize, 1024);
+        assert_eq!(output.items[0].mime_type, "text/plain");
+    }
+}
@@ -28,6 +28,14 @@ use crate::raw::adapters::kv;
 use crate::raw::*;
 use crate::*;
 
+/// Memcached limits the item size to 1 MiB by default, the item overhead
+/// (key and flags) is included, so we leave some room for it.
+const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024 - 1024;
+
+/// The value stored at the original key when the content has been split
+/// into chunks. It's followed by the number of chunks in big-endian `u64`.
+const CHUNKS_MAGIC: &[u8] = b"\0opendal-memcached-chunks\0";
+
 /// [Memcached](https://memcached.org/) service support.
 ///
 /// # Capabilities
@@ -51,6 +59,7 @@ use crate::*;
 /// - `root`: Set the working directory of `OpenDAL`
 /// - `endpoint`: Set the network address of memcached server
 /// - `default_ttl`: Set the ttl for memcached service.
+/// - `chunk_size`: Set the max size of one item, larger values will be split into chunks.
 ///
 /// You can refer to [`MemcachedBuilder`]'s docs for more information
 ///
@@ -85,6 +94,8 @@ pub struct MemcachedBuilder {
     root: Option<String>,
     /// The default ttl for put operations.
     default_ttl: Option<Duration>,
+    /// The max size of one item, default to `1 MiB - 1 KiB`.
+    chunk_size: Option<usize>,
 }
 
 impl MemcachedBuilder {
@@ -113,6 +124,18 @@ impl MemcachedBuilder {
         self.default_ttl = Some(ttl);
         self
     }
+
+    /// Set the max size of one item stored in memcached.
+    ///
+    /// Memcached rejects items larger than its item size limit (`-I`, 1 MiB
+    /// by default). Values larger than `chunk_size` will be split into
+    /// multiple items.
+    pub fn chunk_size(&mut self, chunk_size: usize) -> &mut Self {
+        if chunk_size > 0 {
+            self.chunk_size = Some(chunk_size);
+        }
+        self
+    }
 }
 
 impl Builder for MemcachedBuilder {
@@ -124,6 +147,12 @@ impl Builder for MemcachedBuilder {
 
         map.get("root").map(|v| builder.root(v));
         map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("default_ttl").map(|v| {
+            v.parse::<u64>()
+                .map(|v| builder.default_ttl(Duration::from_secs(v)))
+        });
+        map.get("chunk_size")
+            .map(|v| v.parse::<usize>().map(|v| builder.chunk_size(v)));
 
         builder
     }
@@ -189,6 +218,7 @@ impl Builder for MemcachedBuilder {
             endpoint,
             conn,
             default_ttl: self.default_ttl,
+            chunk_size: self.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
         })
         .with_root(&root))
     }
@@ -201,6 +231,7 @@ pub type MemcachedBackend = kv::Backend<Adapter>;
 pub struct Adapter {
     endpoint: String,
     default_ttl: Option<Duration>,
+    chunk_size: usize,
     conn: OnceCell<bb8::Pool<MemcacheConnectionManager>>,
 }
 
@@ -245,31 +276,94 @@ impl kv::Adapter for Adapter {
 
     async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
         let mut conn = self.conn().await?;
+        let key = percent_encode_path(key);
+
+        let bs = match conn.get(&key).await? {
+            Some(bs) => bs,
+            None => return Ok(None),
+        };
+        let count = match decode_chunks_header(&bs) {
+            Some(count) => count,
+            None => return Ok(Some(bs)),
+        };
 
-        conn.get(&percent_encode_path(key)).await
+        let mut buf = Vec::new();
+        for idx in 0..count {
+            match conn.get(&build_chunk_key(&key, idx)).await? {
+                Some(chunk) => buf.extend_from_slice(&chunk),
+                // Chunks could be evicted separately, treat the whole value
+                // as missing in this case.
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(buf))
     }
 
     async fn set(&self, key: &str, value: &[u8]) -> Result<()> {
         let mut conn = self.conn().await?;
+        let key = percent_encode_path(key);
+        // Set expiration to 0 if ttl not set.
+        let expiration = self
+            .default_ttl
+            .map(|v| v.as_secs() as u32)
+            .unwrap_or_default();
+
+        if value.len() <= self.chunk_size {
+            return conn.set(&key, value, expiration).await;
+        }
 
-        conn.set(
-            &percent_encode_path(key),
-            value,
-            // Set expiration to 0 if ttl not set.
-            self.default_ttl
-                .map(|v| v.as_secs() as u32)
-                .unwrap_or_default(),
-        )
-        .await
+        // Write all chunks before the header so that readers will never
+        // see a partial value.
+        let mut count = 0;
+        for (idx, chunk) in value.chunks(self.chunk_size).enumerate() {
+            conn.set(&build_chunk_key(&key, idx), chunk, expiration)
+                .await?;
+            count += 1;
+        }
+        conn.set(&key, &encode_chunks_header(count), expiration)
+            .await
     }
 
     async fn delete(&self, key: &str) -> Result<()> {
         let mut conn = self.conn().await?;
+        let key = percent_encode_path(key);
+
+        if let Some(count) = conn
+            .get(&key)
+            .await?
+            .and_then(|bs| decode_chunks_header(&bs))
+        {
+            for idx in 0..count {
+                conn.delete(&build_chunk_key(&key, idx)).await?;
+            }
+        }
 
-        conn.delete(&percent_encode_path(key)).await
+        conn.delete(&key).await
     }
 }
 
+fn build_chunk_key(key: &str, idx: usize) -> String {
+    format!("{key}.chunk.{idx}")
+}
+
+fn encode_chunks_header(count: usize) -> Vec<u8> {
+    let mut bs = Vec::with_capacity(CHUNKS_MAGIC.len() + 8);
+    bs.extend_from_slice(CHUNKS_MAGIC);
+    bs.extend_from_slice(&(count as u64).to_be_bytes());
+    bs
+}
+
+/// Returns the number of chunks if given value is a chunks header.
+fn decode_chunks_header(bs: &[u8]) -> Option<usize> {
+    if bs.len() != CHUNKS_MAGIC.len() + 8 || !bs.starts_with(CHUNKS_MAGIC) {
+        return None;
+    }
+
+    let mut count = [0; 8];
+    count.copy_from_slice(&bs[CHUNKS_MAGIC.len()..]);
+    Some(u64::from_be_bytes(count) as usize)
+}
+
 /// A `bb8::ManageConnection` for `memcache_async::ascii::Protocol`.
 ///
 /// Most code is borrowed from [bb8-memcached](https://github.com/dqminh/bb8-memcached/blob/master/src/client.rs).
@@ -311,3 +405,18 @@ impl bb8::ManageConnection for MemcacheConnectionManager {
 pub fn parse_io_error(err: std::io::Error) -> Error {
     Error::new(ErrorKind::Unexpected, &err.kind().to_string()).set_source(err)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_header() {
+        let bs = encode_chunks_header(3);
+        assert_eq!(decode_chunks_header(&bs), Some(3));
+
+        assert_eq!(decode_chunks_header(b"hello, world"), None);
+        assert_eq!(decode_chunks_header(CHUNKS_MAGIC), None);
+        assert_eq!(decode_chunks_header(&[bs.as_slice(), b"x"].concat()), None);
+    }
+}
@@ -17,7 +17,10 @@
 
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use parking_lot::Mutex;
@@ -30,6 +33,12 @@ use crate::*;
 #[derive(Default)]
 pub struct MemoryBuilder {
     root: Option<String>,
+    max_capacity: Option<u64>,
+    max_entries: Option<usize>,
+    time_to_live: Option<Duration>,
+
+    /// The error of invalid config from map, returned while building.
+    config_error: Option<Error>,
 }
 
 impl MemoryBuilder {
@@ -38,6 +47,44 @@ impl MemoryBuilder {
         self.root = Some(path.into());
         self
     }
+
+    /// Set the max capacity in bytes of all entries.
+    ///
+    /// Least recently used entries will be evicted while exceeding. The
+    /// size of an entry is the length of its path and content plus the
+    /// size of its metadata.
+    pub fn max_capacity(&mut self, v: u64) -> &mut Self {
+        if v != 0 {
+            self.max_capacity = Some(v);
+        }
+        self
+    }
+
+    /// Set the max number of entries.
+    ///
+    /// Least recently used entries will be evicted while exceeding.
+    pub fn max_entries(&mut self, v: usize) -> &mut Self {
+        if v != 0 {
+            self.max_entries = Some(v);
+        }
+        self
+    }
+
+    /// Set the time to live of every entry since it has been written.
+    ///
+    /// Expired entries will be treated as not exist and removed lazily.
+    pub fn time_to_live(&mut self, v: Duration) -> &mut Self {
+        if !v.is_zero() {
+            self.time_to_live = Some(v);
+        }
+        self
+    }
+
+    fn with_config_error(&mut self, err: Error) -> &mut Self {
+        // Keep the first error.
+        self.config_error.get_or_insert(err);
+        self
+    }
 }
 
 impl Builder for MemoryBuilder {
@@ -48,25 +95,210 @@ impl Builder for MemoryBuilder {
         let mut builder = Self::default();
 
         map.get("root").map(|v| builder.root(v));
+        if let Some(v) = map.get("max_capacity") {
+            match parse_config("max_capacity", v) {
+                Ok(v) => builder.max_capacity(v),
+                Err(err) => builder.with_config_error(err),
+            };
+        }
+        if let Some(v) = map.get("max_entries") {
+            match parse_config("max_entries", v) {
+                Ok(v) => builder.max_entries(v),
+                Err(err) => builder.with_config_error(err),
+            };
+        }
+        if let Some(v) = map.get("time_to_live") {
+            match parse_config("time_to_live", v) {
+                Ok(v) => builder.time_to_live(Duration::from_secs(v)),
+                Err(err) => builder.with_config_error(err),
+            };
+        }
 
         builder
     }
 
     fn build(&mut self) -> Result<Self::Accessor> {
-        let adapter = Adapter {
-            inner: Arc::new(Mutex::new(BTreeMap::default())),
-        };
+        if let Some(err) = self.config_error.take() {
+            return Err(err);
+        }
+
+        let adapter = Adapter::new(self);
 
         Ok(MemoryBackend::new(adapter).with_root(self.root.as_deref().unwrap_or_default()))
     }
 }
 
+fn parse_config<T>(key: &'static str, v: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    v.parse::<T>().map_err(|err| {
+        Error::new(ErrorKind::ConfigInvalid, "config is invalid")
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Memory)
+            .with_context(key, v)
+            .set_source(err)
+    })
+}
+
 /// Backend is used to serve `Accessor` support in memory.
 pub type MemoryBackend = typed_kv::Backend<Adapter>;
 
 #[derive(Debug, Clone)]
 pub struct Adapter {
-    inner: Arc<Mutex<BTreeMap<String, typed_kv::Value>>>,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Adapter {
+    fn new(builder: &MemoryBuilder) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                max_capacity: builder.max_capacity,
+                max_entries: builder.max_entries,
+                time_to_live: builder.time_to_live,
+                ..Default::default()
+            })),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    value: typed_kv::Value,
+    /// The tick of last access, used as the key in `Inner::lru`.
+    tick: u64,
+    expire_at: Option<Instant>,
+}
+
+impl Entry {
+    fn is_expired(&self, now: Option<Instant>) -> bool {
+        match (self.expire_at, now) {
+            (Some(expire_at), Some(now)) => expire_at <= now,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    max_capacity: Option<u64>,
+    max_entries: Option<usize>,
+    time_to_live: Option<Duration>,
+
+    map: BTreeMap<String, Entry>,
+    /// Keys ordered by their last access, the first one is the least
+    /// recently used.
+    lru: BTreeMap<u64, String>,
+    tick: u64,
+    /// Total size of all entries.
+    size: u64,
+}
+
+impl Inner {
+    fn entry_size(path: &str, value: &typed_kv::Value) -> u64 {
+        (path.len() + value.size()) as u64
+    }
+
+    /// Returns current time only if entries could expire.
+    ///
+    /// `Instant::now()` panics on `wasm32-unknown-unknown`, so we must not
+    /// call it if `time_to_live` is not set.
+    fn now(&self) -> Option<Instant> {
+        self.time_to_live.map(|_| Instant::now())
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, path: &str) -> Option<typed_kv::Value> {
+        let now = self.now();
+        match self.map.get(path) {
+            None => return None,
+            Some(entry) if entry.is_expired(now) => {
+                self.remove(path);
+                return None;
+            }
+            Some(_) => {}
+        }
+
+        let tick = self.next_tick();
+        let entry = self.map.get_mut(path).expect("entry must exist");
+        let key = self.lru.remove(&entry.tick).expect("lru key must exist");
+        self.lru.insert(tick, key);
+        entry.tick = tick;
+
+        Some(entry.value.clone())
+    }
+
+    fn insert(&mut self, path: &str, value: typed_kv::Value) {
+        self.remove(path);
+
+        let tick = self.next_tick();
+        self.size += Self::entry_size(path, &value);
+        self.lru.insert(tick, path.to_string());
+        self.map.insert(
+            path.to_string(),
+            Entry {
+                value,
+                tick,
+                expire_at: self.time_to_live.map(|v| Instant::now() + v),
+            },
+        );
+
+        self.evict();
+    }
+
+    fn remove(&mut self, path: &str) {
+        if let Some(entry) = self.map.remove(path) {
+            self.lru.remove(&entry.tick);
+            self.size -= Self::entry_size(path, &entry.value);
+        }
+    }
+
+    /// Evict least recently used entries until limits are satisfied.
+    fn evict(&mut self) {
+        while self
+            .max_entries
+            .map(|v| self.map.len() > v)
+            .unwrap_or_default()
+            || self.max_capacity.map(|v| self.size > v).unwrap_or_default()
+        {
+            let key = match self.lru.values().next() {
+                Some(key) => key.clone(),
+                None => break,
+            };
+            self.remove(&key);
+        }
+    }
+
+    fn scan(&mut self, path: &str) -> Vec<String> {
+        let now = self.now();
+        let mut keys = Vec::new();
+        let mut expired = Vec::new();
+
+        let entries: Box<dyn Iterator<Item = (&String, &Entry)>> = if path.is_empty() {
+            Box::new(self.map.iter())
+        } else {
+            let right_range = format!("{}0", &path[..path.len() - 1]);
+            Box::new(self.map.range(path.to_string()..right_range))
+        };
+        for (k, entry) in entries {
+            if entry.is_expired(now) {
+                expired.push(k.to_string());
+            } else {
+                keys.push(k.to_string());
+            }
+        }
+
+        for k in expired {
+            self.remove(&k);
+        }
+
+        keys
+    }
 }
 
 #[async_trait]
@@ -89,10 +321,7 @@ impl typed_kv::Adapter for Adapter {
     }
 
     fn blocking_get(&self, path: &str) -> Result<Option<typed_kv::Value>> {
-        match self.inner.lock().get(path) {
-            None => Ok(None),
-            Some(bs) => Ok(Some(bs.to_owned())),
-        }
+        Ok(self.inner.lock().get(path))
     }
 
     async fn set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
@@ -100,7 +329,7 @@ impl typed_kv::Adapter for Adapter {
     }
 
     fn blocking_set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
-        self.inner.lock().insert(path.to_string(), value);
+        self.inner.lock().insert(path, value);
 
         Ok(())
     }
@@ -120,18 +349,7 @@ impl typed_kv::Adapter for Adapter {
     }
 
     fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
-        let inner = self.inner.lock();
-        let keys: Vec<_> = if path.is_empty() {
-            inner.keys().cloned().collect()
-        } else {
-            let right_range = format!("{}0", &path[..path.len() - 1]);
-            inner
-                .range(path.to_string()..right_range)
-                .map(|(k, _)| k.to_string())
-                .collect()
-        };
-
-        Ok(keys)
+        Ok(self.inner.lock().scan(path))
     }
 }
 
@@ -148,4 +366,82 @@ mod tests {
         let b2 = MemoryBuilder::default().build().unwrap();
         assert_ne!(b1.info().name(), b2.info().name())
     }
+
+    fn new_value(size: usize) -> typed_kv::Value {
+        typed_kv::Value {
+            metadata: Metadata::new(EntryMode::FILE).with_content_length(size as u64),
+            value: vec![0; size].into(),
+        }
+    }
+
+    #[test]
+    fn test_evict_by_max_entries() {
+        use typed_kv::Adapter as _;
+
+        let a = Adapter::new(MemoryBuilder::default().max_entries(2));
+
+        a.blocking_set("a", new_value(1)).unwrap();
+        a.blocking_set("b", new_value(1)).unwrap();
+        // Access a to make b the least recently used one.
+        assert!(a.blocking_get("a").unwrap().is_some());
+        a.blocking_set("c", new_value(1)).unwrap();
+
+        assert!(a.blocking_get("a").unwrap().is_some());
+        assert!(a.blocking_get("b").unwrap().is_none());
+        assert!(a.blocking_get("c").unwrap().is_some());
+        assert_eq!(a.blocking_scan("").unwrap(), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_evict_by_max_capacity() {
+        use typed_kv::Adapter as _;
+
+        let size = Inner::entry_size("a", &new_value(100));
+        let a = Adapter::new(MemoryBuilder::default().max_capacity(size * 2));
+
+        a.blocking_set("a", new_value(100)).unwrap();
+        a.blocking_set("b", new_value(100)).unwrap();
+        assert_eq!(a.inner.lock().size, size * 2);
+
+        // Overwriting should not be counted twice.
+        a.blocking_set("a", new_value(100)).unwrap();
+        assert!(a.blocking_get("b").unwrap().is_some());
+
+        a.blocking_set("c", new_value(100)).unwrap();
+        assert!(a.blocking_get("a").unwrap().is_none());
+        assert_eq!(a.inner.lock().size, size * 2);
+
+        // Entries larger than capacity will not be kept.
+        a.blocking_set("d", new_value(1000)).unwrap();
+        assert!(a.blocking_scan("").unwrap().is_empty());
+        assert_eq!(a.inner.lock().size, 0);
+    }
+
+    #[test]
+    fn test_time_to_live() {
+        use typed_kv::Adapter as _;
+
+        let a = Adapter::new(MemoryBuilder::default().time_to_live(Duration::from_millis(50)));
+
+        a.blocking_set("dir/a", new_value(1)).unwrap();
+        assert!(a.blocking_get("dir/a").unwrap().is_some());
+        assert_eq!(a.blocking_scan("dir/").unwrap(), vec!["dir/a"]);
+
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(a.blocking_scan("dir/").unwrap().is_empty());
+        assert!(a.blocking_get("dir/a").unwrap().is_none());
+        assert_eq!(a.inner.lock().size, 0);
+    }
+
+    #[test]
+    fn test_from_map_invalid_config() {
+        for key in ["max_capacity", "max_entries", "time_to_live"] {
+            let map = HashMap::from([(key.to_string(), "abc".to_string())]);
+            let err = MemoryBuilder::from_map(map).build().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid, "key: {key}");
+        }
+
+        let map = HashMap::from([("time_to_live".to_string(), "60".to_string())]);
+        assert!(MemoryBuilder::from_map(map).build().is_ok());
+    }
 }
@@ -0,0 +1,496 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use parking_lot::Mutex;
+
+use crate::raw::adapters::typed_kv;
+use crate::raw::*;
+use crate::*;
+
+/// Programmable mock service support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct MockBuilder {
+    root: Option<String>,
+    handle: MockHandle,
+}
+
+impl MockBuilder {
+    /// Set the root for mock.
+    pub fn root(&mut self, path: &str) -> &mut Self {
+        self.root = Some(path.into());
+        self
+    }
+
+    /// Get the handle of the service that will be built by this builder.
+    ///
+    /// The handle can be cloned freely and keeps working after the
+    /// service has been built.
+    pub fn handle(&self) -> MockHandle {
+        self.handle.clone()
+    }
+}
+
+impl Builder for MockBuilder {
+    const SCHEME: Scheme = Scheme::Mock;
+    type Accessor = MockBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = Self::default();
+
+        map.get("root").map(|v| builder.root(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let inner = typed_kv::Backend::new(Adapter::default())
+            .with_root(self.root.as_deref().unwrap_or_default());
+
+        Ok(MockBackend {
+            inner,
+            handle: self.handle.clone(),
+        })
+    }
+}
+
+/// A call that reached the mock service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockCall {
+    operation: Operation,
+    path: String,
+}
+
+impl MockCall {
+    /// The operation of this call.
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// The path of this call.
+    ///
+    /// For `copy` and `rename`, this is the source path.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+/// Handle to script responses, inject failures and inspect calls of a
+/// mock service.
+#[derive(Debug, Clone, Default)]
+pub struct MockHandle {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    calls: Vec<MockCall>,
+    failures: Vec<Failure>,
+    stats: HashMap<String, Metadata>,
+    reads: HashMap<String, Bytes>,
+}
+
+#[derive(Debug)]
+struct Failure {
+    operation: Operation,
+    path: Option<String>,
+    kind: ErrorKind,
+    temporary: bool,
+    times: usize,
+}
+
+impl MockHandle {
+    /// Fail the next call of given operation with given error kind.
+    pub fn fail_next(&self, op: Operation, kind: ErrorKind) {
+        self.push_failure(op, None, kind, false, 1);
+    }
+
+    /// Fail the next `times` calls of given operation on given path with
+    /// given error kind.
+    pub fn fail_path(&self, op: Operation, path: &str, kind: ErrorKind, times: usize) {
+        self.push_failure(op, Some(path.to_string()), kind, false, times);
+    }
+
+    /// Fail the next `times` calls of given operation with temporary
+    /// errors, which is useful to test retry behaviors.
+    pub fn fail_temporary(&self, op: Operation, times: usize) {
+        self.push_failure(op, None, ErrorKind::Unexpected, true, times);
+    }
+
+    fn push_failure(
+        &self,
+        operation: Operation,
+        path: Option<String>,
+        kind: ErrorKind,
+        temporary: bool,
+        times: usize,
+    ) {
+        if times == 0 {
+            return;
+        }
+
+        self.state.lock().failures.push(Failure {
+            operation,
+            path,
+            kind,
+            temporary,
+            times,
+        });
+    }
+
+    /// Respond `stat` and `blocking_stat` on given path with given
+    /// metadata instead of the stored one.
+    pub fn script_stat(&self, path: &str, meta: Metadata) {
+        self.state.lock().stats.insert(path.to_string(), meta);
+    }
+
+    /// Respond `read` and `blocking_read` on given path with given content
+    /// instead of the stored one.
+    pub fn script_read(&self, path: &str, content: impl Into<Bytes>) {
+        self.state
+            .lock()
+            .reads
+            .insert(path.to_string(), content.into());
+    }
+
+    /// Remove all scripted responses and pending failures.
+    pub fn reset(&self) {
+        let mut state = self.state.lock();
+        state.failures.clear();
+        state.stats.clear();
+        state.reads.clear();
+    }
+
+    /// Get all calls that reached the service in order.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state.lock().calls.clone()
+    }
+
+    /// Get calls of given operation that reached the service in order.
+    pub fn calls_of(&self, op: Operation) -> Vec<MockCall> {
+        self.state
+            .lock()
+            .calls
+            .iter()
+            .filter(|v| v.operation == op)
+            .cloned()
+            .collect()
+    }
+
+    /// Clear all recorded calls.
+    pub fn clear_calls(&self) {
+        self.state.lock().calls.clear();
+    }
+
+    /// Record the call and return the injected failure if matched.
+    fn call(&self, op: Operation, path: &str) -> Result<()> {
+        let mut state = self.state.lock();
+        state.calls.push(MockCall {
+            operation: op,
+            path: path.to_string(),
+        });
+
+        let idx = state
+            .failures
+            .iter()
+            .position(|v| v.operation == op && v.path.as_ref().map(|p| p == path).unwrap_or(true));
+        let idx = match idx {
+            Some(idx) => idx,
+            None => return Ok(()),
+        };
+
+        let failure = &mut state.failures[idx];
+        failure.times -= 1;
+        let mut err = Error::new(failure.kind, "mock injected failure")
+            .with_operation(op)
+            .with_context("path", path);
+        if failure.temporary {
+            err = err.set_temporary();
+        }
+        if failure.times == 0 {
+            state.failures.remove(idx);
+        }
+
+        Err(err)
+    }
+
+    fn scripted_stat(&self, path: &str) -> Option<Metadata> {
+        self.state.lock().stats.get(path).cloned()
+    }
+
+    fn scripted_read(&self, path: &str) -> Option<Bytes> {
+        self.state.lock().reads.get(path).cloned()
+    }
+}
+
+/// Backend is used to serve `Accessor` support for mock.
+#[derive(Debug, Clone)]
+pub struct MockBackend {
+    inner: typed_kv::Backend<Adapter>,
+    handle: MockHandle,
+}
+
+#[async_trait]
+impl Accessor for MockBackend {
+    type Reader = oio::Cursor;
+    type BlockingReader = oio::Cursor;
+    type Writer = <typed_kv::Backend<Adapter> as Accessor>::Writer;
+    type BlockingWriter = <typed_kv::Backend<Adapter> as Accessor>::BlockingWriter;
+    type Appender = ();
+    type Pager = <typed_kv::Backend<Adapter> as Accessor>::Pager;
+    type BlockingPager = <typed_kv::Backend<Adapter> as Accessor>::BlockingPager;
+
+    fn info(&self) -> AccessorInfo {
+        self.inner.info()
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.handle.call(Operation::CreateDir, path)?;
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.handle.call(Operation::Read, path)?;
+        match self.handle.scripted_read(path) {
+            Some(bs) => {
+                let bs = args.range().apply_on_bytes(bs);
+                Ok((RpRead::new(bs.len() as u64), oio::Cursor::from(bs)))
+            }
+            None => self.inner.read(path, args).await,
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.handle.call(Operation::Write, path)?;
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.handle.call(Operation::Stat, path)?;
+        match self.handle.scripted_stat(path) {
+            Some(meta) => Ok(RpStat::new(meta)),
+            None => self.inner.stat(path, args).await,
+        }
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.handle.call(Operation::Delete, path)?;
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.handle.call(Operation::List, path)?;
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.handle.call(Operation::BlockingCreateDir, path)?;
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.handle.call(Operation::BlockingRead, path)?;
+        match self.handle.scripted_read(path) {
+            Some(bs) => {
+                let bs = args.range().apply_on_bytes(bs);
+                Ok((RpRead::new(bs.len() as u64), oio::Cursor::from(bs)))
+            }
+            None => self.inner.blocking_read(path, args),
+        }
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.handle.call(Operation::BlockingWrite, path)?;
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.handle.call(Operation::BlockingStat, path)?;
+        match self.handle.scripted_stat(path) {
+            Some(meta) => Ok(RpStat::new(meta)),
+            None => self.inner.blocking_stat(path, args),
+        }
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.handle.call(Operation::BlockingDelete, path)?;
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.handle.call(Operation::BlockingList, path)?;
+        self.inner.blocking_list(path, args)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Adapter {
+    inner: Arc<Mutex<BTreeMap<String, typed_kv::Value>>>,
+}
+
+#[async_trait]
+impl typed_kv::Adapter for Adapter {
+    fn info(&self) -> typed_kv::Info {
+        typed_kv::Info::new(
+            Scheme::Mock,
+            &format!("{:?}", &self.inner as *const _),
+            typed_kv::Capability {
+                get: true,
+                set: true,
+                delete: true,
+                scan: true,
+            },
+        )
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<typed_kv::Value>> {
+        self.blocking_get(path)
+    }
+
+    fn blocking_get(&self, path: &str) -> Result<Option<typed_kv::Value>> {
+        Ok(self.inner.lock().get(path).cloned())
+    }
+
+    async fn set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
+        self.blocking_set(path, value)
+    }
+
+    fn blocking_set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
+        self.inner.lock().insert(path.to_string(), value);
+
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        self.blocking_delete(path)
+    }
+
+    fn blocking_delete(&self, path: &str) -> Result<()> {
+        self.inner.lock().remove(path);
+
+        Ok(())
+    }
+
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        self.blocking_scan(path)
+    }
+
+    fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
+        Ok(self
+            .inner
+            .lock()
+            .keys()
+            .filter(|k| k.starts_with(path))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fail_next() {
+        let builder = MockBuilder::default();
+        let handle = builder.handle();
+        let op = Operator::new(builder).unwrap().finish();
+
+        handle.fail_next(Operation::Write, ErrorKind::PermissionDenied);
+        let err = op.write("test", "hello").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        // Only the next call should fail.
+        op.write("test", "hello").await.unwrap();
+        assert_eq!(op.read("test").await.unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_fail_path() {
+        let builder = MockBuilder::default();
+        let handle = builder.handle();
+        let op = Operator::new(builder).unwrap().finish().blocking();
+
+        op.write("a", "a").unwrap();
+        op.write("b", "b").unwrap();
+        handle.fail_path(Operation::BlockingStat, "a", ErrorKind::NotFound, 2);
+
+        assert_eq!(op.stat("a").unwrap_err().kind(), ErrorKind::NotFound);
+        assert!(op.stat("b").is_ok());
+        assert_eq!(op.stat("a").unwrap_err().kind(), ErrorKind::NotFound);
+        assert!(op.stat("a").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fail_temporary() {
+        let builder = MockBuilder::default();
+        let handle = builder.handle();
+        let op = Operator::new(builder).unwrap().finish();
+
+        handle.fail_temporary(Operation::Stat, 1);
+        let err = op.stat("test").await.unwrap_err();
+        assert!(err.is_temporary());
+    }
+
+    #[tokio::test]
+    async fn test_scripted_responses() {
+        let builder = MockBuilder::default();
+        let handle = builder.handle();
+        let op = Operator::new(builder).unwrap().finish();
+
+        handle.script_stat(
+            "test",
+            Metadata::new(EntryMode::FILE).with_content_length(1024),
+        );
+        handle.script_read("test", "Hello, World!");
+
+        let meta = op.stat("test").await.unwrap();
+        assert_eq!(meta.content_length(), 1024);
+        assert_eq!(op.read("test").await.unwrap(), b"Hello, World!");
+        assert_eq!(op.range_read("test", 7..12).await.unwrap(), b"World");
+
+        handle.reset();
+        assert_eq!(
+            op.stat("test").await.unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn test_calls() {
+        let builder = MockBuilder::default();
+        let handle = builder.handle();
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("test", "hello").await.unwrap();
+        op.delete("test").await.unwrap();
+
+        let calls = handle.calls_of(Operation::Write);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].path(), "test");
+        assert_eq!(handle.calls_of(Operation::Delete).len(), 1);
+
+        handle.clear_calls();
+        assert!(handle.calls().is_empty());
+    }
+}
@@ -29,19 +29,71 @@ mod azdfs;
 #[cfg(feature = "services-azdfs")]
 pub use azdfs::Azdfs;
 
+#[cfg(feature = "services-azfile")]
+mod azfile;
+#[cfg(feature = "services-azfile")]
+pub use azfile::Azfile;
+
+#[cfg(feature = "services-b2")]
+mod b2;
+#[cfg(feature = "services-b2")]
+pub use b2::B2;
+
+#[cfg(feature = "services-box")]
+mod r#box;
+#[cfg(feature = "services-box")]
+pub use self::r#box::BoxStorage;
+
+#[cfg(feature = "services-cassandra")]
+mod cassandra;
+#[cfg(feature = "services-cassandra")]
+pub use self::cassandra::Cassandra;
+
+#[cfg(feature = "services-cloudflare-kv")]
+mod cloudflare_kv;
+#[cfg(feature = "services-cloudflare-kv")]
+pub use self::cloudflare_kv::CloudflareKv;
+
 #[cfg(feature = "services-cos")]
 mod cos;
 #[cfg(feature = "services-cos")]
 pub use cos::Cos;
 
+#[cfg(feature = "services-d1")]
+mod d1;
+#[cfg(feature = "services-d1")]
+pub use self::d1::D1;
+
 #[cfg(feature = "services-dashmap")]
 mod dashmap;
 #[cfg(feature = "services-dashmap")]
 pub use self::dashmap::Dashmap;
 
-#[cfg(feature = "services-fs")]
+#[cfg(feature = "services-dynamodb")]
+mod dynamodb;
+#[cfg(feature = "services-dynamodb")]
+pub use dynamodb::Dynamodb;
+
+#[cfg(feature = "services-etcd")]
+mod etcd;
+#[cfg(feature = "services-etcd")]
+pub use self::etcd::Etcd;
+
+#[cfg(feature = "services-failover")]
+mod failover;
+#[cfg(feature = "services-failover")]
+pub use failover::Failover;
+#[cfg(feature = "services-failover")]
+pub use failover::ReadPreference;
+
+#[cfg(feature = "services-foundationdb")]
+mod foundationdb;
+#[cfg(feature = "services-foundationdb")]
+pub use self::foundationdb::Foundationdb;
+
+#[cfg(feature = "services-fs-blocking")]
 mod fs;
-#[cfg(feature = "services-fs")]
+#[cfg(feature = "services-fs-blocking")]
 pub use fs::Fs;
 
 #[cfg(feature = "services-ftp")]
@@ -59,6 +111,16 @@ mod ghac;
 #[cfg(feature = "services-ghac")]
 pub use ghac::Ghac;
 
+#[cfg(feature = "services-github")]
+mod github;
+#[cfg(feature = "services-github")]
+pub use github::Github;
+
+#[cfg(feature = "services-gridfs")]
+mod gridfs;
+#[cfg(feature = "services-gridfs")]
+pub use self::gridfs::Gridfs;
+
 #[cfg(feature = "services-hdfs")]
 mod hdfs;
 #[cfg(feature = "services-hdfs")]
@@ -69,6 +131,11 @@ mod http;
 #[cfg(feature = "services-http")]
 pub use self::http::Http;
 
+#[cfg(feature = "services-huggingface")]
+mod huggingface;
+#[cfg(feature = "services-huggingface")]
+pub use huggingface::Huggingface;
+
 #[cfg(feature = "services-ipfs")]
 mod ipfs;
 #[cfg(feature = "services-ipfs")]
@@ -79,6 +146,11 @@ mod ipmfs;
 #[cfg(feature = "services-ipmfs")]
 pub use ipmfs::Ipmfs;
 
+#[cfg(feature = "services-kodo")]
+mod kodo;
+#[cfg(feature = "services-kodo")]
+pub use kodo::Kodo;
+
 #[cfg(feature = "services-memcached")]
 mod memcached;
 #[cfg(feature = "services-memcached")]
@@ -89,21 +161,60 @@ mod memory;
 #[cfg(feature = "services-memory")]
 pub use memory::Memory;
 
+#[cfg(feature = "services-mock")]
+mod mock;
+#[cfg(feature = "services-mock")]
+pub use mock::Mock;
+#[cfg(feature = "services-mock")]
+pub use mock::MockCall;
+#[cfg(feature = "services-mock")]
+pub use mock::MockHandle;
+
 #[cfg(feature = "services-moka")]
 mod moka;
 #[cfg(feature = "services-moka")]
 pub use self::moka::Moka;
 
+#[cfg(feature = "services-mysql")]
+mod mysql;
+#[cfg(feature = "services-mysql")]
+pub use self::mysql::Mysql;
+
+#[cfg(feature = "services-nfs")]
+mod nfs;
+#[cfg(feature = "services-nfs")]
+pub use self::nfs::Nfs;
+
 #[cfg(feature = "services-obs")]
 mod obs;
 #[cfg(feature = "services-obs")]
 pub use obs::Obs;
 
+#[cfg(feature = "services-oci")]
+mod oci;
+#[cfg(feature = "services-oci")]
+pub use oci::Oci;
+
 #[cfg(feature = "services-oss")]
 mod oss;
 #[cfg(feature = "services-oss")]
 pub use oss::Oss;
 
+#[cfg(feature = "services-postgresql")]
+mod postgresql;
+#[cfg(feature = "services-postgresql")]
+pub use self::postgresql::Postgresql;
+
+#[cfg(feature = "services-rados")]
+mod rados;
+#[cfg(feature = "services-rados")]
+pub use self::rados::Rados;
+
+#[cfg(feature = "services-redb")]
+mod redb;
+#[cfg(feature = "services-redb")]
+pub use self::redb::Redb;
+
 #[cfg(feature = "services-redis")]
 mod redis;
 #[cfg(feature = "services-redis")]
@@ -119,21 +230,68 @@ mod s3;
 #[cfg(feature = "services-s3")]
 pub use s3::S3;
 
+#[cfg(feature = "services-seafile")]
+mod seafile;
+#[cfg(feature = "services-seafile")]
+pub use seafile::Seafile;
+
+#[cfg(feature = "services-seaweedfs")]
+mod seaweedfs;
+#[cfg(feature = "services-seaweedfs")]
+pub use seaweedfs::Seaweedfs;
+
 #[cfg(feature = "services-sftp")]
 mod sftp;
 #[cfg(feature = "services-sftp")]
 pub use sftp::Sftp;
 
+#[cfg(feature = "services-shard")]
+mod shard;
+#[cfg(feature = "services-shard")]
+pub use shard::Shard;
+
+#[cfg(feature = "services-sim")]
+mod sim;
+#[cfg(feature = "services-sim")]
+pub use sim::Latency;
+#[cfg(feature = "services-sim")]
+pub use sim::Sim;
+
 #[cfg(feature = "services-sled")]
 mod sled;
 #[cfg(feature = "services-sled")]
 pub use self::sled::Sled;
 
+#[cfg(feature = "services-sqlite")]
+mod sqlite;
+#[cfg(feature = "services-sqlite")]
+pub use self::sqlite::Sqlite;
+
 #[cfg(feature = "services-supabase")]
 mod supabase;
 #[cfg(feature = "services-supabase")]
 pub use supabase::Supabase;
 
+#[cfg(feature = "services-tar")]
+mod tar;
+#[cfg(feature = "services-tar")]
+pub use self::tar::Tar;
+
+#[cfg(feature = "services-tikv")]
+mod tikv;
+#[cfg(feature = "services-tikv")]
+pub use self::tikv::Tikv;
+
+#[cfg(feature = "services-tos")]
+mod tos;
+#[cfg(feature = "services-tos")]
+pub use tos::Tos;
+
+#[cfg(feature = "services-union")]
+mod union;
+#[cfg(feature = "services-union")]
+pub use union::Union;
+
 #[cfg(feature = "services-wasabi")]
 mod wasabi;
 #[cfg(feature = "services-wasabi")]
@@ -164,7 +322,22 @@ pub use dropbox::Dropbox;
 #[cfg(feature = "services-webhdfs")]
 pub use webhdfs::Webhdfs;
 
+#[cfg(feature = "services-yandex-disk")]
+mod yandex_disk;
+#[cfg(feature = "services-yandex-disk")]
+pub use yandex_disk::YandexDisk;
+
+#[cfg(feature = "services-zip")]
+mod zip;
+#[cfg(feature = "services-zip")]
+pub use self::zip::Zip;
+
 #[cfg(feature = "services-vercel-artifacts")]
 mod vercel_artifacts;
 #[cfg(feature = "services-vercel-artifacts")]
 pub use vercel_artifacts::VercelArtifacts;
+
+#[cfg(feature = "services-vercel-blob")]
+mod vercel_blob;
+#[cfg(feature = "services-vercel-blob")]
+pub use vercel_blob::VercelBlob;
@@ -0,0 +1,83 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::core::MysqlCore;
+use crate::raw::*;
+use crate::*;
+
+/// The default number of keys fetched in one page.
+const DEFAULT_PAGE_SIZE: usize = 1000;
+
+/// Pager that lists keys by prefix page by page.
+///
+/// Every page starts after the last key of previous page, so no cursor
+/// needs to be kept alive between pages.
+pub struct MysqlPager {
+    core: Arc<MysqlCore>,
+    path: String,
+    limit: usize,
+    start_after: Option<String>,
+    done: bool,
+}
+
+impl MysqlPager {
+    pub fn new(
+        core: Arc<MysqlCore>,
+        path: String,
+        limit: Option<usize>,
+        start_after: Option<String>,
+    ) -> Self {
+        Self {
+            core,
+            path,
+            limit: limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            start_after,
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for MysqlPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let rows = self
+            .core
+            .list(&self.path, self.start_after.as_deref(), self.limit)
+            .await?;
+        if rows.len() < self.limit {
+            self.done = true;
+        }
+        if let Some((key, _)) = rows.last() {
+            self.start_after = Some(key.clone());
+        }
+
+        let entries = rows
+            .into_iter()
+            .map(|(key, meta)| oio::Entry::new(&build_rel_path(&self.core.root, &key), meta))
+            .collect();
+
+        Ok(Some(entries))
+    }
+}
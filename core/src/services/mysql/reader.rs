@@ -0,0 +1,126 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::min;
+use std::io;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Buf;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+
+use super::core::MysqlCore;
+use crate::raw::*;
+use crate::*;
+
+/// The max size of value fetched in one query.
+const DEFAULT_READ_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// Reader that streams the value of a key chunk by chunk, so that large
+/// values will not be loaded into memory at once.
+pub struct MysqlReader {
+    chunks: BoxStream<'static, Result<Bytes>>,
+    buf: Bytes,
+}
+
+/// `BoxStream` is not `Sync`, but the stream will only be polled via
+/// `&mut self`, so it's safe to share the reader between threads.
+unsafe impl Sync for MysqlReader {}
+
+impl MysqlReader {
+    pub fn new(core: Arc<MysqlCore>, path: String, offset: u64, size: u64) -> Self {
+        let chunks = futures::stream::try_unfold(
+            (core, path, offset, size),
+            |(core, path, offset, remaining)| async move {
+                if remaining == 0 {
+                    return Ok(None);
+                }
+
+                let size = min(remaining, DEFAULT_READ_CHUNK_SIZE);
+                let bs = core.get_range(&path, offset, size).await?.ok_or_else(|| {
+                    Error::new(ErrorKind::NotFound, "key has been removed while reading")
+                        .with_context("path", &path)
+                })?;
+                if bs.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "value has been truncated while reading",
+                    )
+                    .with_context("path", &path)
+                    .with_context("remaining", remaining.to_string()));
+                }
+
+                let n = bs.len() as u64;
+                Ok(Some((
+                    Bytes::from(bs),
+                    (core, path, offset + n, remaining - n),
+                )))
+            },
+        );
+
+        Self {
+            chunks: chunks.boxed(),
+            buf: Bytes::new(),
+        }
+    }
+
+    /// Make sure `buf` is not empty unless all data has been read.
+    fn poll_fill_buf(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while self.buf.is_empty() {
+            match ready!(self.chunks.poll_next_unpin(cx)) {
+                Some(bs) => self.buf = bs?,
+                None => break,
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl oio::Read for MysqlReader {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        ready!(self.poll_fill_buf(cx))?;
+
+        let n = min(buf.len(), self.buf.len());
+        buf[..n].copy_from_slice(&self.buf[..n]);
+        self.buf.advance(n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        let (_, _) = (cx, pos);
+
+        Poll::Ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "output reader doesn't support seeking",
+        )))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        ready!(self.poll_fill_buf(cx))?;
+
+        if self.buf.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Ready(Some(Ok(self.buf.split_off(0))))
+        }
+    }
+}
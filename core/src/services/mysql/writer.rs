@@ -0,0 +1,72 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use super::core::MysqlCore;
+use crate::raw::*;
+use crate::*;
+
+/// Writer that buffers all content in memory and stores it at `close`,
+/// since the value will be written in one statement.
+pub struct MysqlWriter {
+    core: Arc<MysqlCore>,
+    path: String,
+    content_type: Option<String>,
+    buf: Vec<u8>,
+}
+
+impl MysqlWriter {
+    pub fn new(core: Arc<MysqlCore>, path: String, args: OpWrite) -> Self {
+        MysqlWriter {
+            core,
+            path,
+            content_type: args.content_type().map(|v| v.to_string()),
+            buf: Vec::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Write for MysqlWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.buf.extend_from_slice(&bs);
+        Ok(())
+    }
+
+    async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "Write::sink is not supported",
+        ))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.buf.clear();
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let buf = std::mem::take(&mut self.buf);
+        self.core
+            .set(&self.path, &buf, self.content_type.as_deref())
+            .await
+    }
+}
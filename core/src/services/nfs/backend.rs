@@ -0,0 +1,417 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use log::debug;
+use tokio::sync::OnceCell;
+
+use super::core::NfsCore;
+use super::pager::NfsPager;
+use super::writer::NfsWriter;
+use crate::raw::*;
+use crate::*;
+
+/// The default port of portmapper.
+const DEFAULT_PORTMAP_PORT: u16 = 111;
+
+/// [NFS](https://www.rfc-editor.org/rfc/rfc1813) services support via a
+/// userspace NFSv3 client.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct NfsBuilder {
+    root: Option<String>,
+    endpoint: Option<String>,
+    export: Option<String>,
+    uid: Option<u32>,
+    gid: Option<u32>,
+    mount_port: Option<u16>,
+    nfs_port: Option<u16>,
+    attr_cache_ttl: Option<Duration>,
+}
+
+impl Debug for NfsBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Builder")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .field("export", &self.export)
+            .field("uid", &self.uid)
+            .field("gid", &self.gid)
+            .field("mount_port", &self.mount_port)
+            .field("nfs_port", &self.nfs_port)
+            .field("attr_cache_ttl", &self.attr_cache_ttl)
+            .finish()
+    }
+}
+
+impl NfsBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string());
+        }
+        self
+    }
+
+    /// Set the network address of nfs server, like `tcp://127.0.0.1:111`.
+    ///
+    /// The port is the port of portmapper, default to `111`.
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.to_string());
+        }
+        self
+    }
+
+    /// Set the path of the export to mount, like `/srv/nfs`.
+    pub fn export(&mut self, export: &str) -> &mut Self {
+        if !export.is_empty() {
+            self.export = Some(export.to_string());
+        }
+        self
+    }
+
+    /// Set the uid sent via `AUTH_UNIX`.
+    ///
+    /// default: 0
+    pub fn uid(&mut self, uid: u32) -> &mut Self {
+        self.uid = Some(uid);
+        self
+    }
+
+    /// Set the gid sent via `AUTH_UNIX`.
+    ///
+    /// default: 0
+    pub fn gid(&mut self, gid: u32) -> &mut Self {
+        self.gid = Some(gid);
+        self
+    }
+
+    /// Set the port of mount service, portmapper will not be queried for
+    /// it if set.
+    pub fn mount_port(&mut self, port: u16) -> &mut Self {
+        self.mount_port = Some(port);
+        self
+    }
+
+    /// Set the port of nfs service, portmapper will not be queried for it
+    /// if set.
+    pub fn nfs_port(&mut self, port: u16) -> &mut Self {
+        self.nfs_port = Some(port);
+        self
+    }
+
+    /// Set how long file handles and attributes are cached.
+    ///
+    /// Cached attributes save the LOOKUP calls of every path component,
+    /// but changes made by other clients will be invisible until the cache
+    /// expires.
+    ///
+    /// default: no cache
+    pub fn attr_cache_ttl(&mut self, ttl: Duration) -> &mut Self {
+        if !ttl.is_zero() {
+            self.attr_cache_ttl = Some(ttl);
+        }
+        self
+    }
+}
+
+impl Builder for NfsBuilder {
+    const SCHEME: Scheme = Scheme::Nfs;
+    type Accessor = NfsBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = NfsBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("export").map(|v| builder.export(v));
+        map.get("uid")
+            .and_then(|v| v.parse::<u32>().ok())
+            .map(|v| builder.uid(v));
+        map.get("gid")
+            .and_then(|v| v.parse::<u32>().ok())
+            .map(|v| builder.gid(v));
+        map.get("mount_port")
+            .and_then(|v| v.parse::<u16>().ok())
+            .map(|v| builder.mount_port(v));
+        map.get("nfs_port")
+            .and_then(|v| v.parse::<u16>().ok())
+            .map(|v| builder.nfs_port(v));
+        map.get("attr_cache_ttl")
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|v| builder.attr_cache_ttl(Duration::from_secs(v)));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let endpoint = self.endpoint.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Nfs)
+        })?;
+        let (host, portmap_port) = parse_endpoint(&endpoint)?;
+        debug!("backend use endpoint {}:{}", host, portmap_port);
+
+        let export = self.export.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "export is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Nfs)
+        })?;
+        debug!("backend use export {}", export);
+
+        Ok(NfsBackend {
+            core: Arc::new(NfsCore {
+                root,
+                host,
+                portmap_port,
+                mount_port: self.mount_port,
+                nfs_port: self.nfs_port,
+                export,
+                uid: self.uid.unwrap_or_default(),
+                gid: self.gid.unwrap_or_default(),
+                attr_cache_ttl: self.attr_cache_ttl,
+
+                state: OnceCell::new(),
+                attr_cache: Mutex::default(),
+            }),
+        })
+    }
+}
+
+/// Parse endpoint into host and port, only `tcp` is supported.
+fn parse_endpoint(endpoint: &str) -> Result<(String, u16)> {
+    let uri = http::Uri::try_from(endpoint).map_err(|err| {
+        Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+            .with_context("service", Scheme::Nfs)
+            .with_context("endpoint", endpoint)
+            .set_source(err)
+    })?;
+
+    // If scheme is none, we will use tcp by default.
+    if let Some(scheme) = uri.scheme_str() {
+        if scheme != "tcp" {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "endpoint is using invalid scheme")
+                    .with_context("service", Scheme::Nfs)
+                    .with_context("endpoint", endpoint)
+                    .with_context("scheme", scheme.to_string()),
+            );
+        }
+    }
+
+    let host = uri.host().ok_or_else(|| {
+        Error::new(ErrorKind::ConfigInvalid, "endpoint doesn't have host")
+            .with_context("service", Scheme::Nfs)
+            .with_context("endpoint", endpoint)
+    })?;
+
+    Ok((
+        host.to_string(),
+        uri.port_u16().unwrap_or(DEFAULT_PORTMAP_PORT),
+    ))
+}
+
+/// Backend for NFS services.
+#[derive(Clone, Debug)]
+pub struct NfsBackend {
+    core: Arc<NfsCore>,
+}
+
+#[async_trait]
+impl Accessor for NfsBackend {
+    type Reader = oio::Cursor;
+    type BlockingReader = ();
+    type Writer = NfsWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = NfsPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut ma = AccessorInfo::default();
+        ma.set_scheme(Scheme::Nfs)
+            .set_root(&self.core.root)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_seek: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_without_content_length: true,
+
+                create_dir: true,
+                delete: true,
+                rename: true,
+
+                list: true,
+                list_with_delimiter_slash: true,
+
+                ..Default::default()
+            });
+
+        ma
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        let p = build_abs_path(&self.core.root, path);
+
+        self.core.nfs_create_dir_all(&p).await?;
+
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let p = build_abs_path(&self.core.root, path);
+
+        let (fh, attr) = self
+            .core
+            .nfs_lookup(&p)
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "file is not found"))?;
+        if attr.is_dir() {
+            return Err(Error::new(ErrorKind::IsADirectory, "path is a dir"));
+        }
+
+        let size = attr.size;
+        let br = args.range();
+        // `None` end means read until the end of file.
+        let (start, end) = match (br.offset(), br.size()) {
+            // Read a specific range.
+            (Some(offset), Some(size)) => (offset, Some(offset + size)),
+            // Read from offset.
+            (Some(offset), None) => (offset, None),
+            // Read the last size bytes.
+            (None, Some(n)) => (size.saturating_sub(n), None),
+            // Read the whole file.
+            (None, None) => (0, None),
+        };
+
+        let read_size = self.core.read_size().await?;
+        let mut buf = Vec::new();
+        let mut offset = start;
+        loop {
+            let count = match end {
+                Some(end) if offset >= end => break,
+                Some(end) => (end - offset).min(read_size as u64) as u32,
+                None => read_size,
+            };
+
+            let (data, eof) = self.core.nfs_read(&fh, offset, count).await?;
+            offset += data.len() as u64;
+            buf.extend_from_slice(&data);
+            if eof || data.is_empty() {
+                break;
+            }
+        }
+
+        Ok((RpRead::new(buf.len() as u64), oio::Cursor::from(buf)))
+    }
+
+    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let p = build_abs_path(&self.core.root, path);
+
+        Ok((RpWrite::default(), NfsWriter::new(self.core.clone(), p)))
+    }
+
+    async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
+        let from = build_abs_path(&self.core.root, from);
+        let to = build_abs_path(&self.core.root, to);
+
+        self.core.nfs_rename(&from, &to).await?;
+
+        Ok(RpRename::default())
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let p = build_abs_path(&self.core.root, path);
+
+        let (_, attr) = self
+            .core
+            .nfs_lookup(&p)
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "path is not found"))?;
+
+        if !attr.is_dir() && path.ends_with('/') {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                "the path is a file but a dir is expected",
+            ));
+        }
+
+        Ok(RpStat::new(attr.to_metadata()))
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let p = build_abs_path(&self.core.root, path);
+
+        self.core.nfs_remove(&p).await?;
+
+        Ok(RpDelete::default())
+    }
+
+    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Pager)> {
+        let p = build_abs_path(&self.core.root, path);
+
+        Ok((RpList::default(), NfsPager::new(self.core.clone(), &p)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_endpoint() {
+        let cases = vec![
+            ("127.0.0.1", Some(("127.0.0.1", 111))),
+            ("tcp://127.0.0.1:1111", Some(("127.0.0.1", 1111))),
+            ("nfs.example.com:111", Some(("nfs.example.com", 111))),
+            ("udp://127.0.0.1:111", None),
+        ];
+
+        for (input, expected) in cases {
+            let actual = parse_endpoint(input).ok();
+            assert_eq!(
+                actual,
+                expected.map(|(host, port)| (host.to_string(), port)),
+                "{input}"
+            );
+        }
+    }
+}
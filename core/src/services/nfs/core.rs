@@ -0,0 +1,786 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use bb8::PooledConnection;
+use bb8::RunError;
+use bytes::Bytes;
+use tokio::sync::OnceCell;
+
+use super::error::parse_nfs_status;
+use super::error::NFS3ERR_EXIST;
+use super::error::NFS3ERR_NOENT;
+use super::error::NFS3ERR_NOTDIR;
+use super::error::NFS3_OK;
+use super::rpc::*;
+use crate::*;
+
+const PMAPPROC_GETPORT: u32 = 3;
+const MOUNTPROC3_MNT: u32 = 1;
+
+const NFSPROC3_NULL: u32 = 0;
+const NFSPROC3_GETATTR: u32 = 1;
+const NFSPROC3_LOOKUP: u32 = 3;
+const NFSPROC3_READ: u32 = 6;
+const NFSPROC3_WRITE: u32 = 7;
+const NFSPROC3_CREATE: u32 = 8;
+const NFSPROC3_MKDIR: u32 = 9;
+const NFSPROC3_REMOVE: u32 = 12;
+const NFSPROC3_RMDIR: u32 = 13;
+const NFSPROC3_RENAME: u32 = 14;
+const NFSPROC3_READDIRPLUS: u32 = 17;
+const NFSPROC3_FSINFO: u32 = 19;
+const NFSPROC3_COMMIT: u32 = 21;
+
+const IPPROTO_TCP: u32 = 6;
+const NF3DIR: u32 = 2;
+const UNSTABLE: u32 = 0;
+const CREATE_UNCHECKED: u32 = 0;
+const DONT_CHANGE: u32 = 0;
+const SET_TO_SERVER_TIME: u32 = 1;
+
+/// The max size of one READ or WRITE if server doesn't tell us.
+const DEFAULT_TRANSFER_SIZE: u32 = 64 * 1024;
+/// The max size of one READ or WRITE we will send, even if server allows
+/// larger one.
+pub const MAX_TRANSFER_SIZE: u32 = 1024 * 1024;
+/// The max number of bytes returned by one READDIRPLUS call.
+const READDIR_MAX_COUNT: u32 = 64 * 1024;
+/// The max number of entries kept in attribute cache, expired entries will
+/// be purged once exceeded.
+const ATTR_CACHE_CAPACITY: usize = 10 * 1024;
+
+pub type FileHandle = Bytes;
+
+/// Attributes of a file, only the fields we care about in `fattr3` are kept.
+#[derive(Clone, Debug)]
+pub struct Fattr {
+    pub ftype: u32,
+    pub size: u64,
+    pub mtime: (u32, u32),
+}
+
+impl Fattr {
+    fn decode(dec: &mut XdrDecoder) -> Result<Self> {
+        let ftype = dec.get_u32()?;
+        // mode, nlink, uid, gid
+        dec.get_fixed(16)?;
+        let size = dec.get_u64()?;
+        // used, rdev, fsid, fileid, atime
+        dec.get_fixed(40)?;
+        let mtime = (dec.get_u32()?, dec.get_u32()?);
+        // ctime
+        dec.get_fixed(8)?;
+
+        Ok(Self { ftype, size, mtime })
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.ftype == NF3DIR
+    }
+
+    pub fn to_metadata(&self) -> Metadata {
+        let mut m = if self.is_dir() {
+            Metadata::new(EntryMode::DIR)
+        } else {
+            Metadata::new(EntryMode::FILE)
+        };
+        m.set_content_length(self.size);
+        m.set_last_modified((UNIX_EPOCH + Duration::new(self.mtime.0 as u64, self.mtime.1)).into());
+        m
+    }
+}
+
+fn decode_post_op_attr(dec: &mut XdrDecoder) -> Result<Option<Fattr>> {
+    if dec.get_bool()? {
+        Ok(Some(Fattr::decode(dec)?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn decode_post_op_fh(dec: &mut XdrDecoder) -> Result<Option<FileHandle>> {
+    if dec.get_bool()? {
+        Ok(Some(dec.get_opaque()?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn skip_wcc_data(dec: &mut XdrDecoder) -> Result<()> {
+    // pre_op_attr: size, mtime and ctime.
+    if dec.get_bool()? {
+        dec.get_fixed(24)?;
+    }
+    decode_post_op_attr(dec)?;
+    Ok(())
+}
+
+/// Parse the FSINFO result into the max size of READ and WRITE.
+fn parse_fsinfo(dec: &mut XdrDecoder) -> Result<(u32, u32)> {
+    let status = dec.get_u32()?;
+    decode_post_op_attr(dec)?;
+    if status != NFS3_OK {
+        return Err(parse_nfs_status(status));
+    }
+
+    let rtmax = dec.get_u32()?;
+    // rtpref, rtmult
+    dec.get_fixed(8)?;
+    let wtmax = dec.get_u32()?;
+
+    let normalize = |v: u32| match v {
+        0 => DEFAULT_TRANSFER_SIZE,
+        v => v.min(MAX_TRANSFER_SIZE),
+    };
+    Ok((normalize(rtmax), normalize(wtmax)))
+}
+
+/// Encode `sattr3` that only sets mode and optionally truncates the file.
+fn encode_sattr(enc: &mut XdrEncoder, mode: u32, truncate: bool) {
+    enc.put_bool(true).put_u32(mode);
+    // uid and gid
+    enc.put_bool(false).put_bool(false);
+    enc.put_bool(truncate);
+    if truncate {
+        enc.put_u64(0);
+    }
+    enc.put_u32(DONT_CHANGE).put_u32(SET_TO_SERVER_TIME);
+}
+
+/// Split path into parent and name, the returning parent doesn't have a
+/// trailing `/`.
+fn split_path(path: &str) -> (&str, &str) {
+    let path = path.trim_end_matches('/');
+    match path.rfind('/') {
+        Some(idx) => (&path[..idx], &path[idx + 1..]),
+        None => ("", path),
+    }
+}
+
+/// The entry returned by READDIRPLUS.
+pub struct DirEntry {
+    pub name: String,
+    pub cookie: u64,
+    pub attr: Option<Fattr>,
+}
+
+/// The result of one READDIRPLUS call.
+pub struct ReadDir {
+    pub entries: Vec<DirEntry>,
+    pub cookie_verf: Bytes,
+    pub eof: bool,
+}
+
+pub struct NfsCore {
+    pub root: String,
+    pub host: String,
+    pub portmap_port: u16,
+    pub mount_port: Option<u16>,
+    pub nfs_port: Option<u16>,
+    pub export: String,
+    pub uid: u32,
+    pub gid: u32,
+    pub attr_cache_ttl: Option<Duration>,
+
+    pub state: OnceCell<NfsState>,
+    pub attr_cache: Mutex<HashMap<String, (Instant, FileHandle, Fattr)>>,
+}
+
+impl Debug for NfsCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NfsCore")
+            .field("root", &self.root)
+            .field("host", &self.host)
+            .field("export", &self.export)
+            .field("uid", &self.uid)
+            .field("gid", &self.gid)
+            .field("attr_cache_ttl", &self.attr_cache_ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+pub struct NfsState {
+    root_fh: FileHandle,
+    pool: bb8::Pool<Manager>,
+    read_size: u32,
+    write_size: u32,
+}
+
+impl NfsCore {
+    async fn state(&self) -> Result<&NfsState> {
+        self.state
+            .get_or_try_init(|| async {
+                let mount_port = match self.mount_port {
+                    Some(port) => port,
+                    None => self.get_port(MOUNT_PROGRAM, MOUNT_VERSION).await?,
+                };
+                let nfs_port = match self.nfs_port {
+                    Some(port) => port,
+                    None => self.get_port(NFS_PROGRAM, NFS_VERSION).await?,
+                };
+
+                let root_fh = self.mount(mount_port).await?;
+                let pool = bb8::Pool::builder()
+                    .max_size(64)
+                    .build(Manager {
+                        addr: format!("{}:{}", self.host, nfs_port),
+                        uid: self.uid,
+                        gid: self.gid,
+                    })
+                    .await?;
+
+                // Ask server for the preferred transfer sizes.
+                let (read_size, write_size) = {
+                    let mut conn = get_conn(&pool).await?;
+                    let args = XdrEncoder::new().put_opaque(&root_fh).finish();
+                    let mut dec = conn
+                        .call(NFS_PROGRAM, NFS_VERSION, NFSPROC3_FSINFO, &args)
+                        .await?;
+                    parse_fsinfo(&mut dec)?
+                };
+
+                Ok(NfsState {
+                    root_fh,
+                    pool,
+                    read_size,
+                    write_size,
+                })
+            })
+            .await
+    }
+
+    /// Query the port of given program via portmapper.
+    async fn get_port(&self, program: u32, version: u32) -> Result<u16> {
+        let addr = format!("{}:{}", self.host, self.portmap_port);
+        let mut conn = RpcConnection::connect(&addr, self.uid, self.gid).await?;
+
+        let args = XdrEncoder::new()
+            .put_u32(program)
+            .put_u32(version)
+            .put_u32(IPPROTO_TCP)
+            .put_u32(0)
+            .finish();
+        let mut dec = conn
+            .call(PMAP_PROGRAM, PMAP_VERSION, PMAPPROC_GETPORT, &args)
+            .await?;
+
+        match dec.get_u32()? {
+            0 => Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "program is not registered in portmapper",
+            )
+            .with_context("service", Scheme::Nfs)
+            .with_context("endpoint", &addr)
+            .with_context("program", program.to_string())),
+            port => Ok(port as u16),
+        }
+    }
+
+    /// Mount the export and returns the file handle of it.
+    async fn mount(&self, port: u16) -> Result<FileHandle> {
+        let addr = format!("{}:{}", self.host, port);
+        let mut conn = RpcConnection::connect(&addr, self.uid, self.gid).await?;
+
+        let args = XdrEncoder::new().put_string(&self.export).finish();
+        let mut dec = conn
+            .call(MOUNT_PROGRAM, MOUNT_VERSION, MOUNTPROC3_MNT, &args)
+            .await?;
+
+        let status = dec.get_u32()?;
+        if status != NFS3_OK {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "mount export failed")
+                .with_context("service", Scheme::Nfs)
+                .with_context("export", &self.export)
+                .set_source(parse_nfs_status(status)));
+        }
+
+        dec.get_opaque()
+    }
+
+    /// The max size of one READ call.
+    pub async fn read_size(&self) -> Result<u32> {
+        Ok(self.state().await?.read_size)
+    }
+
+    /// The max size of one WRITE call.
+    pub async fn write_size(&self) -> Result<u32> {
+        Ok(self.state().await?.write_size)
+    }
+
+    async fn call(&self, procedure: u32, args: &[u8]) -> Result<XdrDecoder> {
+        let mut conn = self.conn().await?;
+        conn.call(NFS_PROGRAM, NFS_VERSION, procedure, args).await
+    }
+
+    async fn conn(&self) -> Result<PooledConnection<'_, Manager>> {
+        get_conn(&self.state().await?.pool).await
+    }
+
+    fn cache_get(&self, path: &str) -> Option<(FileHandle, Fattr)> {
+        let ttl = self.attr_cache_ttl?;
+        let mut cache = self.attr_cache.lock().expect("lock must succeed");
+
+        match cache.get(path) {
+            Some((at, fh, attr)) if at.elapsed() < ttl => Some((fh.clone(), attr.clone())),
+            Some(_) => {
+                cache.remove(path);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn cache_put(&self, path: &str, fh: &FileHandle, attr: &Fattr) {
+        let ttl = match self.attr_cache_ttl {
+            Some(ttl) => ttl,
+            None => return,
+        };
+        let mut cache = self.attr_cache.lock().expect("lock must succeed");
+
+        if cache.len() >= ATTR_CACHE_CAPACITY {
+            cache.retain(|_, (at, _, _)| at.elapsed() < ttl);
+            if cache.len() >= ATTR_CACHE_CAPACITY {
+                cache.clear();
+            }
+        }
+        cache.insert(path.to_string(), (Instant::now(), fh.clone(), attr.clone()));
+    }
+
+    /// Remove the cached attributes of given path and all its children.
+    pub fn invalidate(&self, path: &str) {
+        if self.attr_cache_ttl.is_none() {
+            return;
+        }
+        let path = path.trim_end_matches('/');
+        let prefix = format!("{path}/");
+        let mut cache = self.attr_cache.lock().expect("lock must succeed");
+
+        cache.retain(|k, _| k != path && !k.starts_with(&prefix));
+    }
+
+    async fn nfs_getattr(&self, fh: &FileHandle) -> Result<Fattr> {
+        let args = XdrEncoder::new().put_opaque(fh).finish();
+        let mut dec = self.call(NFSPROC3_GETATTR, &args).await?;
+
+        let status = dec.get_u32()?;
+        if status != NFS3_OK {
+            return Err(parse_nfs_status(status));
+        }
+        Fattr::decode(&mut dec)
+    }
+
+    /// Lookup `name` inside `dir`, returns `None` if not exist.
+    async fn nfs_lookup_child(
+        &self,
+        dir: &FileHandle,
+        name: &str,
+    ) -> Result<Option<(FileHandle, Fattr)>> {
+        let args = XdrEncoder::new().put_opaque(dir).put_string(name).finish();
+        let mut dec = self.call(NFSPROC3_LOOKUP, &args).await?;
+
+        let status = dec.get_u32()?;
+        match status {
+            NFS3_OK => {}
+            // The parent is a file, treat as not found.
+            NFS3ERR_NOENT | NFS3ERR_NOTDIR => return Ok(None),
+            _ => return Err(parse_nfs_status(status)),
+        }
+
+        let fh = dec.get_opaque()?;
+        let attr = match decode_post_op_attr(&mut dec)? {
+            Some(attr) => attr,
+            None => self.nfs_getattr(&fh).await?,
+        };
+        Ok(Some((fh, attr)))
+    }
+
+    /// Lookup the file handle and attributes of given path.
+    ///
+    /// The path is relative to the export, for example: `path/to/file`.
+    pub async fn nfs_lookup(&self, path: &str) -> Result<Option<(FileHandle, Fattr)>> {
+        let path = path.trim_end_matches('/');
+        if let Some(v) = self.cache_get(path) {
+            return Ok(Some(v));
+        }
+
+        let mut fh = self.state().await?.root_fh.clone();
+        if path.is_empty() {
+            let attr = self.nfs_getattr(&fh).await?;
+            return Ok(Some((fh, attr)));
+        }
+
+        let mut attr = None;
+        let mut cur = String::with_capacity(path.len());
+        for name in path.split('/').filter(|v| !v.is_empty()) {
+            if !cur.is_empty() {
+                cur.push('/');
+            }
+            cur.push_str(name);
+
+            let (child, child_attr) = match self.cache_get(&cur) {
+                Some(v) => v,
+                None => match self.nfs_lookup_child(&fh, name).await? {
+                    Some(v) => {
+                        self.cache_put(&cur, &v.0, &v.1);
+                        v
+                    }
+                    None => return Ok(None),
+                },
+            };
+            fh = child;
+            attr = Some(child_attr);
+        }
+
+        Ok(attr.map(|attr| (fh, attr)))
+    }
+
+    /// Create given dir and all its parents, returns the file handle of it.
+    pub async fn nfs_create_dir_all(&self, path: &str) -> Result<FileHandle> {
+        let path = path.trim_end_matches('/');
+        if let Some((fh, attr)) = self.cache_get(path) {
+            if attr.is_dir() {
+                return Ok(fh);
+            }
+        }
+
+        let mut fh = self.state().await?.root_fh.clone();
+        let mut cur = String::with_capacity(path.len());
+        for name in path.split('/').filter(|v| !v.is_empty()) {
+            if !cur.is_empty() {
+                cur.push('/');
+            }
+            cur.push_str(name);
+
+            let (child, attr) = match self.nfs_lookup_child(&fh, name).await? {
+                Some(v) => v,
+                None => self.nfs_mkdir(&fh, name).await?,
+            };
+            if !attr.is_dir() {
+                return Err(Error::new(
+                    ErrorKind::NotADirectory,
+                    "parent of the path is not a dir",
+                )
+                .with_context("path", &cur));
+            }
+            self.cache_put(&cur, &child, &attr);
+            fh = child;
+        }
+
+        Ok(fh)
+    }
+
+    async fn nfs_mkdir(&self, dir: &FileHandle, name: &str) -> Result<(FileHandle, Fattr)> {
+        let mut enc = XdrEncoder::new();
+        enc.put_opaque(dir).put_string(name);
+        encode_sattr(&mut enc, 0o755, false);
+        let mut dec = self.call(NFSPROC3_MKDIR, &enc.finish()).await?;
+
+        let status = dec.get_u32()?;
+        let created = match status {
+            NFS3_OK => match (decode_post_op_fh(&mut dec)?, decode_post_op_attr(&mut dec)?) {
+                (Some(fh), Some(attr)) => Some((fh, attr)),
+                _ => None,
+            },
+            // The dir could be created by others at the same time.
+            NFS3ERR_EXIST => None,
+            _ => return Err(parse_nfs_status(status)),
+        };
+
+        match created {
+            Some(v) => Ok(v),
+            None => self.nfs_lookup_child(dir, name).await?.ok_or_else(|| {
+                Error::new(ErrorKind::Unexpected, "dir is not found after created")
+                    .with_context("name", name)
+                    .set_temporary()
+            }),
+        }
+    }
+
+    /// Create or truncate the file, its parents will be created if not
+    /// exist.
+    pub async fn nfs_create(&self, path: &str) -> Result<FileHandle> {
+        let (parent, name) = split_path(path);
+        let dir = self.nfs_create_dir_all(parent).await?;
+
+        let mut enc = XdrEncoder::new();
+        enc.put_opaque(&dir)
+            .put_string(name)
+            .put_u32(CREATE_UNCHECKED);
+        encode_sattr(&mut enc, 0o644, true);
+        let mut dec = self.call(NFSPROC3_CREATE, &enc.finish()).await?;
+
+        let status = dec.get_u32()?;
+        if status != NFS3_OK {
+            return Err(parse_nfs_status(status));
+        }
+        self.invalidate(path);
+
+        match decode_post_op_fh(&mut dec)? {
+            Some(fh) => Ok(fh),
+            None => self
+                .nfs_lookup_child(&dir, name)
+                .await?
+                .map(|(fh, _)| fh)
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::Unexpected, "file is not found after created")
+                        .with_context("path", path)
+                        .set_temporary()
+                }),
+        }
+    }
+
+    /// Read at most `count` bytes at `offset`, returns the data and whether
+    /// the end of file is reached.
+    pub async fn nfs_read(
+        &self,
+        fh: &FileHandle,
+        offset: u64,
+        count: u32,
+    ) -> Result<(Bytes, bool)> {
+        let args = XdrEncoder::new()
+            .put_opaque(fh)
+            .put_u64(offset)
+            .put_u32(count)
+            .finish();
+        let mut dec = self.call(NFSPROC3_READ, &args).await?;
+
+        let status = dec.get_u32()?;
+        decode_post_op_attr(&mut dec)?;
+        if status != NFS3_OK {
+            return Err(parse_nfs_status(status));
+        }
+
+        let _count = dec.get_u32()?;
+        let eof = dec.get_bool()?;
+        let data = dec.get_opaque()?;
+        Ok((data, eof))
+    }
+
+    /// Write data at `offset` without syncing, returns the number of bytes
+    /// written and the write verifier.
+    pub async fn nfs_write(
+        &self,
+        fh: &FileHandle,
+        offset: u64,
+        data: &[u8],
+    ) -> Result<(u32, Bytes)> {
+        let args = XdrEncoder::new()
+            .put_opaque(fh)
+            .put_u64(offset)
+            .put_u32(data.len() as u32)
+            .put_u32(UNSTABLE)
+            .put_opaque(data)
+            .finish();
+        let mut dec = self.call(NFSPROC3_WRITE, &args).await?;
+
+        let status = dec.get_u32()?;
+        skip_wcc_data(&mut dec)?;
+        if status != NFS3_OK {
+            return Err(parse_nfs_status(status));
+        }
+
+        let count = dec.get_u32()?;
+        let _committed = dec.get_u32()?;
+        let verf = dec.get_fixed(8)?;
+        Ok((count, verf))
+    }
+
+    /// Flush all unstable writes to disk, returns the write verifier.
+    pub async fn nfs_commit(&self, fh: &FileHandle) -> Result<Bytes> {
+        let args = XdrEncoder::new()
+            .put_opaque(fh)
+            .put_u64(0)
+            .put_u32(0)
+            .finish();
+        let mut dec = self.call(NFSPROC3_COMMIT, &args).await?;
+
+        let status = dec.get_u32()?;
+        skip_wcc_data(&mut dec)?;
+        if status != NFS3_OK {
+            return Err(parse_nfs_status(status));
+        }
+
+        dec.get_fixed(8)
+    }
+
+    /// Remove given file or dir, returns `Ok(())` if not exist.
+    pub async fn nfs_remove(&self, path: &str) -> Result<()> {
+        let (parent, name) = split_path(path);
+        let dir = match self.nfs_lookup(parent).await? {
+            Some((fh, _)) => fh,
+            None => return Ok(()),
+        };
+
+        let procedure = if path.ends_with('/') {
+            NFSPROC3_RMDIR
+        } else {
+            NFSPROC3_REMOVE
+        };
+        let args = XdrEncoder::new().put_opaque(&dir).put_string(name).finish();
+        let mut dec = self.call(procedure, &args).await?;
+        self.invalidate(path);
+
+        let status = dec.get_u32()?;
+        skip_wcc_data(&mut dec)?;
+        match status {
+            NFS3_OK | NFS3ERR_NOENT => Ok(()),
+            _ => Err(parse_nfs_status(status)),
+        }
+    }
+
+    pub async fn nfs_rename(&self, from: &str, to: &str) -> Result<()> {
+        let (from_parent, from_name) = split_path(from);
+        let from_dir = match self.nfs_lookup(from_parent).await? {
+            Some((fh, _)) => fh,
+            None => {
+                return Err(Error::new(ErrorKind::NotFound, "source is not found")
+                    .with_context("from", from))
+            }
+        };
+        let (to_parent, to_name) = split_path(to);
+        let to_dir = self.nfs_create_dir_all(to_parent).await?;
+
+        let args = XdrEncoder::new()
+            .put_opaque(&from_dir)
+            .put_string(from_name)
+            .put_opaque(&to_dir)
+            .put_string(to_name)
+            .finish();
+        let mut dec = self.call(NFSPROC3_RENAME, &args).await?;
+        self.invalidate(from);
+        self.invalidate(to);
+
+        let status = dec.get_u32()?;
+        if status != NFS3_OK {
+            return Err(parse_nfs_status(status));
+        }
+        Ok(())
+    }
+
+    pub async fn nfs_readdirplus(
+        &self,
+        dir: &FileHandle,
+        cookie: u64,
+        cookie_verf: &[u8],
+    ) -> Result<ReadDir> {
+        let args = XdrEncoder::new()
+            .put_opaque(dir)
+            .put_u64(cookie)
+            .put_fixed(cookie_verf)
+            .put_u32(READDIR_MAX_COUNT)
+            .put_u32(READDIR_MAX_COUNT)
+            .finish();
+        let mut dec = self.call(NFSPROC3_READDIRPLUS, &args).await?;
+
+        let status = dec.get_u32()?;
+        decode_post_op_attr(&mut dec)?;
+        if status != NFS3_OK {
+            return Err(parse_nfs_status(status));
+        }
+
+        let cookie_verf = dec.get_fixed(8)?;
+        let mut entries = Vec::new();
+        while dec.get_bool()? {
+            let _fileid = dec.get_u64()?;
+            let name = dec.get_string()?;
+            let cookie = dec.get_u64()?;
+            let attr = decode_post_op_attr(&mut dec)?;
+            let _fh = decode_post_op_fh(&mut dec)?;
+
+            entries.push(DirEntry { name, cookie, attr });
+        }
+        let eof = dec.get_bool()?;
+
+        Ok(ReadDir {
+            entries,
+            cookie_verf,
+            eof,
+        })
+    }
+
+    /// Get attributes of `name` inside `dir`, used while READDIRPLUS
+    /// doesn't return attributes.
+    pub async fn nfs_lookup_attr(&self, dir: &FileHandle, name: &str) -> Result<Option<Fattr>> {
+        Ok(self
+            .nfs_lookup_child(dir, name)
+            .await?
+            .map(|(_, attr)| attr))
+    }
+}
+
+async fn get_conn(pool: &bb8::Pool<Manager>) -> Result<PooledConnection<'_, Manager>> {
+    pool.get().await.map_err(|err| match err {
+        RunError::User(err) => err,
+        RunError::TimedOut => {
+            Error::new(ErrorKind::Unexpected, "connection request: timeout").set_temporary()
+        }
+    })
+}
+
+pub struct Manager {
+    addr: String,
+    uid: u32,
+    gid: u32,
+}
+
+#[async_trait]
+impl bb8::ManageConnection for Manager {
+    type Connection = RpcConnection;
+    type Error = Error;
+
+    async fn connect(&self) -> std::result::Result<Self::Connection, Self::Error> {
+        RpcConnection::connect(&self.addr, self.uid, self.gid).await
+    }
+
+    async fn is_valid(&self, conn: &mut Self::Connection) -> std::result::Result<(), Self::Error> {
+        conn.call(NFS_PROGRAM, NFS_VERSION, NFSPROC3_NULL, &[])
+            .await
+            .map(|_| ())
+    }
+
+    fn has_broken(&self, conn: &mut Self::Connection) -> bool {
+        conn.is_broken()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_path() {
+        let cases = vec![
+            ("file", ("", "file")),
+            ("dir/", ("", "dir")),
+            ("path/to/file", ("path/to", "file")),
+            ("path/to/dir/", ("path/to", "dir")),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(split_path(input), expected, "{input}");
+        }
+    }
+}
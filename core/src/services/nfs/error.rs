@@ -0,0 +1,102 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io;
+
+use crate::Error;
+use crate::ErrorKind;
+
+pub const NFS3_OK: u32 = 0;
+pub const NFS3ERR_PERM: u32 = 1;
+pub const NFS3ERR_NOENT: u32 = 2;
+pub const NFS3ERR_IO: u32 = 5;
+pub const NFS3ERR_ACCES: u32 = 13;
+pub const NFS3ERR_EXIST: u32 = 17;
+pub const NFS3ERR_NOTDIR: u32 = 20;
+pub const NFS3ERR_ISDIR: u32 = 21;
+pub const NFS3ERR_NOTEMPTY: u32 = 66;
+pub const NFS3ERR_STALE: u32 = 70;
+pub const NFS3ERR_NOTSUPP: u32 = 10004;
+pub const NFS3ERR_JUKEBOX: u32 = 10008;
+
+/// Parse the `nfsstat3` returned by nfs server into error.
+pub fn parse_nfs_status(status: u32) -> Error {
+    let (kind, retryable) = match status {
+        NFS3ERR_NOENT => (ErrorKind::NotFound, false),
+        NFS3ERR_PERM | NFS3ERR_ACCES => (ErrorKind::PermissionDenied, false),
+        NFS3ERR_EXIST => (ErrorKind::AlreadyExists, false),
+        NFS3ERR_NOTDIR => (ErrorKind::NotADirectory, false),
+        NFS3ERR_ISDIR => (ErrorKind::IsADirectory, false),
+        NFS3ERR_NOTSUPP => (ErrorKind::Unsupported, false),
+        // The file handle could be stale while the file is replaced by
+        // others, retry will lookup it again.
+        NFS3ERR_IO | NFS3ERR_STALE | NFS3ERR_JUKEBOX => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let mut err = Error::new(kind, "nfs server returns error")
+        .with_context("status", nfs_status_name(status));
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    err
+}
+
+fn nfs_status_name(status: u32) -> String {
+    match status {
+        NFS3ERR_PERM => "NFS3ERR_PERM".to_string(),
+        NFS3ERR_NOENT => "NFS3ERR_NOENT".to_string(),
+        NFS3ERR_IO => "NFS3ERR_IO".to_string(),
+        NFS3ERR_ACCES => "NFS3ERR_ACCES".to_string(),
+        NFS3ERR_EXIST => "NFS3ERR_EXIST".to_string(),
+        NFS3ERR_NOTDIR => "NFS3ERR_NOTDIR".to_string(),
+        NFS3ERR_ISDIR => "NFS3ERR_ISDIR".to_string(),
+        NFS3ERR_NOTEMPTY => "NFS3ERR_NOTEMPTY".to_string(),
+        NFS3ERR_STALE => "NFS3ERR_STALE".to_string(),
+        NFS3ERR_NOTSUPP => "NFS3ERR_NOTSUPP".to_string(),
+        NFS3ERR_JUKEBOX => "NFS3ERR_JUKEBOX".to_string(),
+        v => v.to_string(),
+    }
+}
+
+/// Parse io errors happened while talking with nfs server.
+///
+/// All of them are retryable since the connection will be re-established.
+pub fn parse_io_error(err: io::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, &err.kind().to_string())
+        .set_source(err)
+        .set_temporary()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nfs_status() {
+        let err = parse_nfs_status(NFS3ERR_NOENT);
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(!err.is_temporary());
+
+        let err = parse_nfs_status(NFS3ERR_JUKEBOX);
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+        assert!(err.to_string().contains("NFS3ERR_JUKEBOX"));
+    }
+}
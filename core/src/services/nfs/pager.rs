@@ -0,0 +1,111 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use super::core::FileHandle;
+use super::core::NfsCore;
+use crate::raw::*;
+use crate::*;
+
+pub struct NfsPager {
+    core: Arc<NfsCore>,
+    /// The dir to list, relative to the export, for example: `path/to/dir/`.
+    path: String,
+
+    dir: Option<FileHandle>,
+    cookie: u64,
+    cookie_verf: Bytes,
+    done: bool,
+}
+
+impl NfsPager {
+    pub fn new(core: Arc<NfsCore>, path: &str) -> Self {
+        NfsPager {
+            core,
+            path: path.to_string(),
+
+            dir: None,
+            cookie: 0,
+            cookie_verf: Bytes::from_static(&[0; 8]),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for NfsPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let dir = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => match self.core.nfs_lookup(&self.path).await? {
+                Some((fh, attr)) if attr.is_dir() => {
+                    self.dir = Some(fh.clone());
+                    fh
+                }
+                // Listing a not exist dir should return empty.
+                _ => {
+                    self.done = true;
+                    return Ok(None);
+                }
+            },
+        };
+
+        let output = self
+            .core
+            .nfs_readdirplus(&dir, self.cookie, &self.cookie_verf)
+            .await?;
+        self.done = output.eof;
+        self.cookie_verf = output.cookie_verf;
+
+        let mut entries = Vec::with_capacity(output.entries.len());
+        for entry in output.entries {
+            self.cookie = entry.cookie;
+
+            if entry.name == "." || entry.name == ".." {
+                continue;
+            }
+
+            // Server is allowed to omit attributes in READDIRPLUS.
+            let attr = match entry.attr {
+                Some(attr) => attr,
+                None => match self.core.nfs_lookup_attr(&dir, &entry.name).await? {
+                    Some(attr) => attr,
+                    // The entry has been removed by others.
+                    None => continue,
+                },
+            };
+
+            let mut path =
+                build_rel_path(&self.core.root, &format!("/{}{}", self.path, entry.name));
+            if attr.is_dir() {
+                path.push('/');
+            }
+
+            entries.push(oio::Entry::new(&path, attr.to_metadata()));
+        }
+
+        Ok(Some(entries))
+    }
+}
@@ -0,0 +1,411 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use bytes::Buf;
+use bytes::Bytes;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+use super::core::MAX_TRANSFER_SIZE;
+use super::error::parse_io_error;
+use crate::*;
+
+pub const PMAP_PROGRAM: u32 = 100000;
+pub const PMAP_VERSION: u32 = 2;
+pub const MOUNT_PROGRAM: u32 = 100005;
+pub const MOUNT_VERSION: u32 = 3;
+pub const NFS_PROGRAM: u32 = 100003;
+pub const NFS_VERSION: u32 = 3;
+
+const RPC_VERSION: u32 = 2;
+const MSG_CALL: u32 = 0;
+const MSG_REPLY: u32 = 1;
+const MSG_ACCEPTED: u32 = 0;
+const ACCEPT_SUCCESS: u32 = 0;
+const AUTH_NONE: u32 = 0;
+const AUTH_UNIX: u32 = 1;
+/// The highest bit of record mark means this is the last fragment.
+const LAST_FRAGMENT: u32 = 0x8000_0000;
+/// The max size of one record we will accept, which is the max data size
+/// of READ plus enough room for rpc and nfs headers.
+const MAX_RECORD_SIZE: usize = MAX_TRANSFER_SIZE as usize + 64 * 1024;
+
+/// The machine name carried by `AUTH_UNIX` credential.
+const MACHINE_NAME: &str = "opendal";
+
+/// XdrEncoder encodes values into [XDR](https://www.rfc-editor.org/rfc/rfc4506).
+#[derive(Default)]
+pub struct XdrEncoder {
+    buf: Vec<u8>,
+}
+
+impl XdrEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn put_u32(&mut self, v: u32) -> &mut Self {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+        self
+    }
+
+    pub fn put_u64(&mut self, v: u64) -> &mut Self {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+        self
+    }
+
+    pub fn put_bool(&mut self, v: bool) -> &mut Self {
+        self.put_u32(v as u32)
+    }
+
+    /// Put fixed-length opaque data, the length is not encoded.
+    pub fn put_fixed(&mut self, bs: &[u8]) -> &mut Self {
+        self.buf.extend_from_slice(bs);
+        self.buf.resize(self.buf.len() + padding(bs.len()), 0);
+        self
+    }
+
+    /// Put variable-length opaque data.
+    pub fn put_opaque(&mut self, bs: &[u8]) -> &mut Self {
+        self.put_u32(bs.len() as u32);
+        self.put_fixed(bs)
+    }
+
+    pub fn put_string(&mut self, s: &str) -> &mut Self {
+        self.put_opaque(s.as_bytes())
+    }
+
+    pub fn finish(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+/// XdrDecoder decodes values from [XDR](https://www.rfc-editor.org/rfc/rfc4506).
+pub struct XdrDecoder {
+    bs: Bytes,
+}
+
+impl XdrDecoder {
+    pub fn new(bs: Bytes) -> Self {
+        Self { bs }
+    }
+
+    fn ensure(&self, n: usize) -> Result<()> {
+        if self.bs.remaining() < n {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "xdr data is incomplete, the response must be malformed",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn get_u32(&mut self) -> Result<u32> {
+        self.ensure(4)?;
+        Ok(self.bs.get_u32())
+    }
+
+    pub fn get_u64(&mut self) -> Result<u64> {
+        self.ensure(8)?;
+        Ok(self.bs.get_u64())
+    }
+
+    pub fn get_bool(&mut self) -> Result<bool> {
+        Ok(self.get_u32()? != 0)
+    }
+
+    /// Get fixed-length opaque data.
+    pub fn get_fixed(&mut self, n: usize) -> Result<Bytes> {
+        self.ensure(n + padding(n))?;
+        let bs = self.bs.split_to(n);
+        self.bs.advance(padding(n));
+        Ok(bs)
+    }
+
+    /// Get variable-length opaque data.
+    pub fn get_opaque(&mut self) -> Result<Bytes> {
+        let n = self.get_u32()? as usize;
+        self.get_fixed(n)
+    }
+
+    pub fn get_string(&mut self) -> Result<String> {
+        let bs = self.get_opaque()?;
+        String::from_utf8(bs.to_vec()).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "xdr string is not valid utf-8").set_source(err)
+        })
+    }
+}
+
+fn padding(n: usize) -> usize {
+    (4 - n % 4) % 4
+}
+
+/// RpcConnection is a [ONC RPC](https://www.rfc-editor.org/rfc/rfc5531)
+/// connection over tcp, all calls are authorized via `AUTH_UNIX`.
+pub struct RpcConnection {
+    io: TcpStream,
+    xid: u32,
+    credential: Vec<u8>,
+    broken: bool,
+}
+
+impl RpcConnection {
+    pub async fn connect(addr: &str, uid: u32, gid: u32) -> Result<Self> {
+        let io = TcpStream::connect(addr).await.map_err(parse_io_error)?;
+        // Nagle's algorithm will delay our small calls.
+        io.set_nodelay(true).map_err(parse_io_error)?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let credential = XdrEncoder::new()
+            .put_u32(now.as_secs() as u32)
+            .put_string(MACHINE_NAME)
+            .put_u32(uid)
+            .put_u32(gid)
+            // No auxiliary gids.
+            .put_u32(0)
+            .finish();
+
+        Ok(Self {
+            io,
+            xid: now.subsec_nanos(),
+            credential,
+            broken: false,
+        })
+    }
+
+    /// Returns true if the connection is in an unknown state since the
+    /// last call failed in the middle.
+    pub fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    /// Call given procedure and returns the decoder of results.
+    pub async fn call(
+        &mut self,
+        program: u32,
+        version: u32,
+        procedure: u32,
+        args: &[u8],
+    ) -> Result<XdrDecoder> {
+        self.xid = self.xid.wrapping_add(1);
+        let xid = self.xid;
+
+        let mut enc = XdrEncoder::new();
+        // The record mark will be filled after the call is built.
+        enc.put_u32(0)
+            .put_u32(xid)
+            .put_u32(MSG_CALL)
+            .put_u32(RPC_VERSION)
+            .put_u32(program)
+            .put_u32(version)
+            .put_u32(procedure)
+            .put_u32(AUTH_UNIX)
+            .put_opaque(&self.credential)
+            .put_u32(AUTH_NONE)
+            .put_u32(0)
+            .put_fixed(args);
+        let mut buf = enc.finish();
+        let mark = LAST_FRAGMENT | (buf.len() - 4) as u32;
+        buf[..4].copy_from_slice(&mark.to_be_bytes());
+
+        // Mark as broken until the whole reply has been read.
+        self.broken = true;
+        self.io.write_all(&buf).await.map_err(parse_io_error)?;
+        let reply = read_record(&mut self.io).await?;
+        self.broken = false;
+
+        parse_reply(xid, reply)
+    }
+}
+
+/// Read a whole record which could be split into multiple fragments.
+///
+/// Records larger than [`MAX_RECORD_SIZE`] will be rejected before reading
+/// their content, so that a broken server can't make us allocate unbounded
+/// memory.
+async fn read_record<R: AsyncRead + Unpin>(r: &mut R) -> Result<Bytes> {
+    let mut buf = Vec::new();
+    loop {
+        let mark = r.read_u32().await.map_err(parse_io_error)?;
+        let size = (mark & !LAST_FRAGMENT) as usize;
+
+        let start = buf.len();
+        if start + size > MAX_RECORD_SIZE {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "rpc record exceeds the max size")
+                    .with_context("fragment_size", size.to_string())
+                    .with_context("record_size", (start + size).to_string())
+                    .with_context("max_size", MAX_RECORD_SIZE.to_string()),
+            );
+        }
+
+        buf.resize(start + size, 0);
+        r.read_exact(&mut buf[start..])
+            .await
+            .map_err(parse_io_error)?;
+
+        if mark & LAST_FRAGMENT != 0 {
+            return Ok(Bytes::from(buf));
+        }
+    }
+}
+
+fn parse_reply(xid: u32, reply: Bytes) -> Result<XdrDecoder> {
+    let mut dec = XdrDecoder::new(reply);
+
+    let reply_xid = dec.get_u32()?;
+    if reply_xid != xid {
+        return Err(
+            Error::new(ErrorKind::Unexpected, "rpc reply doesn't match the call")
+                .with_context("xid", xid.to_string())
+                .with_context("reply_xid", reply_xid.to_string()),
+        );
+    }
+    if dec.get_u32()? != MSG_REPLY {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "rpc message is not a reply",
+        ));
+    }
+
+    if dec.get_u32()? != MSG_ACCEPTED {
+        // The call is denied because of rpc version mismatch or auth error.
+        return Err(Error::new(
+            ErrorKind::PermissionDenied,
+            "rpc call is denied by server",
+        ));
+    }
+
+    // Skip the verifier returned by server.
+    let _flavor = dec.get_u32()?;
+    let _body = dec.get_opaque()?;
+
+    let stat = dec.get_u32()?;
+    if stat != ACCEPT_SUCCESS {
+        return Err(
+            Error::new(ErrorKind::Unexpected, "rpc call is not executed by server")
+                .with_context("accept_stat", stat.to_string()),
+        );
+    }
+
+    Ok(dec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xdr_roundtrip() {
+        let bs = XdrEncoder::new()
+            .put_u32(1)
+            .put_u64(u64::MAX)
+            .put_bool(true)
+            .put_string("hello")
+            .put_fixed(&[1, 2, 3])
+            .finish();
+        // 4 + 8 + 4 + (4 + 5 + 3) + (3 + 1)
+        assert_eq!(bs.len(), 32);
+
+        let mut dec = XdrDecoder::new(Bytes::from(bs));
+        assert_eq!(dec.get_u32().unwrap(), 1);
+        assert_eq!(dec.get_u64().unwrap(), u64::MAX);
+        assert!(dec.get_bool().unwrap());
+        assert_eq!(dec.get_string().unwrap(), "hello");
+        assert_eq!(dec.get_fixed(3).unwrap().as_ref(), &[1, 2, 3]);
+        assert!(dec.get_u32().is_err());
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        let reply = XdrEncoder::new()
+            .put_u32(42)
+            .put_u32(MSG_REPLY)
+            .put_u32(MSG_ACCEPTED)
+            .put_u32(AUTH_NONE)
+            .put_opaque(&[])
+            .put_u32(ACCEPT_SUCCESS)
+            .put_u32(7)
+            .finish();
+
+        let mut dec = parse_reply(42, Bytes::from(reply.clone())).unwrap();
+        assert_eq!(dec.get_u32().unwrap(), 7);
+
+        assert!(parse_reply(43, Bytes::from(reply)).is_err());
+    }
+
+    fn fragment(bs: &[u8], last: bool) -> Vec<u8> {
+        let mut mark = bs.len() as u32;
+        if last {
+            mark |= LAST_FRAGMENT;
+        }
+        let mut buf = mark.to_be_bytes().to_vec();
+        buf.extend_from_slice(bs);
+        buf
+    }
+
+    #[tokio::test]
+    async fn test_read_record() {
+        let mut bs = fragment(b"hello, ", false);
+        bs.extend(fragment(b"world", true));
+        // Data of next record should not be consumed.
+        bs.extend(fragment(b"next", true));
+
+        let mut r = bs.as_slice();
+        let record = read_record(&mut r).await.unwrap();
+        assert_eq!(record.as_ref(), b"hello, world");
+        let record = read_record(&mut r).await.unwrap();
+        assert_eq!(record.as_ref(), b"next");
+    }
+
+    #[tokio::test]
+    async fn test_read_record_truncated() {
+        let bs = fragment(b"hello", true);
+
+        let mut r = &bs[..bs.len() - 1];
+        assert!(read_record(&mut r).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_record_too_large() {
+        // Only the record mark is sent, the record must be rejected without
+        // waiting for the content.
+        let mark = LAST_FRAGMENT | (MAX_RECORD_SIZE as u32 + 1);
+        let bs = mark.to_be_bytes();
+
+        let err = read_record(&mut bs.as_slice()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[tokio::test]
+    async fn test_read_record_too_many_fragments() {
+        let chunk = vec![0; MAX_RECORD_SIZE / 2];
+        let mut bs = fragment(&chunk, false);
+        bs.extend(fragment(&chunk, false));
+        bs.extend(fragment(b"x", true));
+
+        let err = read_record(&mut bs.as_slice()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
@@ -0,0 +1,134 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use super::core::FileHandle;
+use super::core::NfsCore;
+use crate::raw::*;
+use crate::*;
+
+/// NfsWriter creates the file on the first write, sends data via unstable
+/// WRITE calls, and commits them while closing.
+pub struct NfsWriter {
+    core: Arc<NfsCore>,
+    /// The file to write, relative to the export.
+    path: String,
+
+    fh: Option<FileHandle>,
+    offset: u64,
+    verf: Option<Bytes>,
+}
+
+impl NfsWriter {
+    pub fn new(core: Arc<NfsCore>, path: String) -> Self {
+        NfsWriter {
+            core,
+            path,
+
+            fh: None,
+            offset: 0,
+            verf: None,
+        }
+    }
+
+    async fn fh(&mut self) -> Result<FileHandle> {
+        match &self.fh {
+            Some(fh) => Ok(fh.clone()),
+            None => {
+                let fh = self.core.nfs_create(&self.path).await?;
+                self.fh = Some(fh.clone());
+                Ok(fh)
+            }
+        }
+    }
+
+    /// Unstable writes will be lost if server restarted before commit, we
+    /// can detect it by the changed write verifier.
+    fn check_verf(&mut self, verf: Bytes) -> Result<()> {
+        match &self.verf {
+            Some(v) if v != &verf => Err(Error::new(
+                ErrorKind::Unexpected,
+                "nfs server restarted while writing, written data could be lost",
+            )
+            .with_context("path", &self.path)),
+            Some(_) => Ok(()),
+            None => {
+                self.verf = Some(verf);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Write for NfsWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let fh = self.fh().await?;
+        let write_size = self.core.write_size().await? as usize;
+
+        let mut bs = bs;
+        while !bs.is_empty() {
+            let size = bs.len().min(write_size);
+            let (n, verf) = self.core.nfs_write(&fh, self.offset, &bs[..size]).await?;
+            self.check_verf(verf)?;
+
+            // Server could write less than we sent.
+            let n = (n as usize).min(size);
+            if n == 0 {
+                return Err(
+                    Error::new(ErrorKind::Unexpected, "nfs server doesn't write any data")
+                        .with_context("path", &self.path)
+                        .set_temporary(),
+                );
+            }
+            self.offset += n as u64;
+            bs = bs.slice(n..);
+        }
+
+        Ok(())
+    }
+
+    async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "Write::sink is not supported",
+        ))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        if self.fh.take().is_some() {
+            self.core.nfs_remove(&self.path).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        // Create an empty file if nothing has been written.
+        let fh = self.fh().await?;
+
+        let verf = self.core.nfs_commit(&fh).await?;
+        self.check_verf(verf)?;
+        self.core.invalidate(&self.path);
+
+        Ok(())
+    }
+}
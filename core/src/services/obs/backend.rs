@@ -60,6 +60,7 @@ use crate::*;
 /// - `endpoint`: Customizable endpoint setting
 /// - `access_key_id`: Set the access_key_id for backend.
 /// - `secret_access_key`: Set the secret_access_key for backend.
+/// - `security_token`: Set the security_token for backend, used with temporary credentials.
 ///
 /// You can refer to [`ObsBuilder`]'s docs for more information
 ///
@@ -98,6 +99,7 @@ pub struct ObsBuilder {
     endpoint: Option<String>,
     access_key_id: Option<String>,
     secret_access_key: Option<String>,
+    security_token: Option<String>,
     bucket: Option<String>,
     http_client: Option<HttpClient>,
 }
@@ -109,6 +111,7 @@ impl Debug for ObsBuilder {
             .field("endpoint", &self.endpoint)
             .field("access_key_id", &"<redacted>")
             .field("secret_access_key", &"<redacted>")
+            .field("security_token", &"<redacted>")
             .field("bucket", &self.bucket)
             .finish()
     }
@@ -164,6 +167,17 @@ impl ObsBuilder {
         self
     }
 
+    /// Set security_token of this backend.
+    ///
+    /// It's required when using the temporary AK/SK obtained from IAM.
+    pub fn security_token(&mut self, security_token: &str) -> &mut Self {
+        if !security_token.is_empty() {
+            self.security_token = Some(security_token.to_string());
+        }
+
+        self
+    }
+
     /// Set bucket of this backend.
     /// The param is required.
     pub fn bucket(&mut self, bucket: &str) -> &mut Self {
@@ -199,6 +213,7 @@ impl Builder for ObsBuilder {
         map.get("access_key_id").map(|v| builder.access_key_id(v));
         map.get("secret_access_key")
             .map(|v| builder.secret_access_key(v));
+        map.get("security_token").map(|v| builder.security_token(v));
 
         builder
     }
@@ -233,14 +248,8 @@ impl Builder for ObsBuilder {
             None => "https".to_string(),
         };
 
-        let (endpoint, is_obs_default) = {
-            let host = uri.host().unwrap_or_default().to_string();
-            if host.starts_with("obs.") && host.ends_with(".myhuaweicloud.com") {
-                (format!("{bucket}.{host}"), true)
-            } else {
-                (host, false)
-            }
-        };
+        let (endpoint, is_obs_default) =
+            parse_endpoint_host(uri.host().unwrap_or_default(), &bucket);
         debug!("backend use endpoint {}", &endpoint);
 
         let client = if let Some(client) = self.http_client.take() {
@@ -255,7 +264,7 @@ impl Builder for ObsBuilder {
         let config = HuaweicloudObsConfig {
             access_key_id: self.access_key_id.take(),
             secret_access_key: self.secret_access_key.take(),
-            security_token: None,
+            security_token: self.security_token.take(),
         };
 
         let cred_loader = HuaweicloudObsCredentialLoader::new(config);
@@ -496,3 +505,52 @@ impl Accessor for ObsBackend {
         ))
     }
 }
+
+/// Build the host of OBS endpoint and check whether it's an OBS default domain.
+///
+/// The default domain of OBS is like `obs.cn-north-4.myhuaweicloud.com`, the
+/// bucket name will be prepended as virtual host. Users could also pass an
+/// endpoint already contains the bucket name like
+/// `bucket.obs.cn-north-4.myhuaweicloud.com`. Other hosts will be treated as
+/// user domain names bound to the bucket.
+fn parse_endpoint_host(host: &str, bucket: &str) -> (String, bool) {
+    let host = host
+        .strip_prefix(&format!("{bucket}."))
+        .filter(|v| v.starts_with("obs."))
+        .unwrap_or(host);
+
+    if host.starts_with("obs.") && host.ends_with(".myhuaweicloud.com") {
+        (format!("{bucket}.{host}"), true)
+    } else {
+        (host.to_string(), false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_endpoint_host() {
+        let cases = vec![
+            (
+                "obs.cn-north-4.myhuaweicloud.com",
+                ("test.obs.cn-north-4.myhuaweicloud.com", true),
+            ),
+            (
+                "test.obs.cn-north-4.myhuaweicloud.com",
+                ("test.obs.cn-north-4.myhuaweicloud.com", true),
+            ),
+            ("obs.example.com", ("obs.example.com", false)),
+            ("test.example.com", ("test.example.com", false)),
+        ];
+
+        for (input, (host, is_obs_default)) in cases {
+            assert_eq!(
+                parse_endpoint_host(input, "test"),
+                (host.to_string(), is_obs_default),
+                "{input}"
+            );
+        }
+    }
+}
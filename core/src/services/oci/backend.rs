@@ -0,0 +1,441 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+use tokio::sync::Mutex;
+
+use super::core::parse_private_key;
+use super::core::OciCore;
+use super::core::OciSigner;
+use super::error::parse_error;
+use super::pager::OciPager;
+use super::writer::OciWriter;
+use crate::raw::*;
+use crate::*;
+
+/// [Oracle Cloud Infrastructure Object Storage](https://www.oracle.com/cloud/storage/object-storage/) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct OciBuilder {
+    root: Option<String>,
+    namespace: Option<String>,
+    bucket: Option<String>,
+    region: Option<String>,
+    endpoint: Option<String>,
+
+    tenancy: Option<String>,
+    user: Option<String>,
+    fingerprint: Option<String>,
+    private_key: Option<String>,
+    passphrase: Option<String>,
+    enable_instance_principal: bool,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for OciBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+
+        ds.field("root", &self.root);
+        ds.field("namespace", &self.namespace);
+        ds.field("bucket", &self.bucket);
+        ds.field("region", &self.region);
+        ds.field("endpoint", &self.endpoint);
+        ds.field("tenancy", &self.tenancy);
+        ds.field("user", &self.user);
+        ds.field("fingerprint", &self.fingerprint);
+        ds.field("enable_instance_principal", &self.enable_instance_principal);
+        if self.private_key.is_some() {
+            ds.field("private_key", &"<redacted>");
+        }
+        if self.passphrase.is_some() {
+            ds.field("passphrase", &"<redacted>");
+        }
+        ds.finish()
+    }
+}
+
+impl OciBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+        self
+    }
+
+    /// Set the object storage namespace of the tenancy.
+    pub fn namespace(&mut self, namespace: &str) -> &mut Self {
+        if !namespace.is_empty() {
+            self.namespace = Some(namespace.to_string())
+        }
+        self
+    }
+
+    /// Set the bucket name.
+    pub fn bucket(&mut self, bucket: &str) -> &mut Self {
+        if !bucket.is_empty() {
+            self.bucket = Some(bucket.to_string())
+        }
+        self
+    }
+
+    /// Set the region of bucket, like `us-ashburn-1`.
+    pub fn region(&mut self, region: &str) -> &mut Self {
+        if !region.is_empty() {
+            self.region = Some(region.to_string())
+        }
+        self
+    }
+
+    /// Set the endpoint of object storage.
+    ///
+    /// default: "https://objectstorage.<region>.oraclecloud.com"
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+        self
+    }
+
+    /// Set the OCID of the tenancy for API key auth.
+    pub fn tenancy(&mut self, tenancy: &str) -> &mut Self {
+        if !tenancy.is_empty() {
+            self.tenancy = Some(tenancy.to_string())
+        }
+        self
+    }
+
+    /// Set the OCID of the user for API key auth.
+    pub fn user(&mut self, user: &str) -> &mut Self {
+        if !user.is_empty() {
+            self.user = Some(user.to_string())
+        }
+        self
+    }
+
+    /// Set the fingerprint of the API key, like
+    /// `12:34:56:78:90:ab:cd:ef:12:34:56:78:90:ab:cd:ef`.
+    pub fn fingerprint(&mut self, fingerprint: &str) -> &mut Self {
+        if !fingerprint.is_empty() {
+            self.fingerprint = Some(fingerprint.to_string())
+        }
+        self
+    }
+
+    /// Set the private key of the API key in PEM format.
+    pub fn private_key(&mut self, private_key: &str) -> &mut Self {
+        if !private_key.is_empty() {
+            self.private_key = Some(private_key.to_string())
+        }
+        self
+    }
+
+    /// Set the passphrase of the private key if it's encrypted.
+    pub fn passphrase(&mut self, passphrase: &str) -> &mut Self {
+        if !passphrase.is_empty() {
+            self.passphrase = Some(passphrase.to_string())
+        }
+        self
+    }
+
+    /// Enable instance principal to sign requests with the identity of
+    /// current compute instance instead of API key.
+    pub fn enable_instance_principal(&mut self) -> &mut Self {
+        self.enable_instance_principal = true;
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for OciBuilder {
+    const SCHEME: Scheme = Scheme::Oci;
+    type Accessor = OciBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = OciBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("namespace").map(|v| builder.namespace(v));
+        map.get("bucket").map(|v| builder.bucket(v));
+        map.get("region").map(|v| builder.region(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("tenancy").map(|v| builder.tenancy(v));
+        map.get("user").map(|v| builder.user(v));
+        map.get("fingerprint").map(|v| builder.fingerprint(v));
+        map.get("private_key").map(|v| builder.private_key(v));
+        map.get("passphrase").map(|v| builder.passphrase(v));
+        map.get("enable_instance_principal")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.enable_instance_principal());
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        debug!("backend build started: {:?}", self);
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let namespace = self.namespace.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "namespace is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Oci)
+        })?;
+        debug!("backend use namespace {}", namespace);
+
+        let bucket = self.bucket.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "bucket is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Oci)
+        })?;
+        debug!("backend use bucket {}", bucket);
+
+        let region = self.region.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "region is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Oci)
+        })?;
+        debug!("backend use region {}", region);
+
+        let endpoint = self
+            .endpoint
+            .take()
+            .unwrap_or_else(|| format!("https://objectstorage.{region}.oraclecloud.com"));
+        debug!("backend use endpoint {}", endpoint);
+
+        let signer = if self.enable_instance_principal {
+            OciSigner {
+                instance_principal: true,
+                ..Default::default()
+            }
+        } else {
+            let (tenancy, user, fingerprint, private_key) = match (
+                self.tenancy.take(),
+                self.user.take(),
+                self.fingerprint.take(),
+                self.private_key.take(),
+            ) {
+                (Some(tenancy), Some(user), Some(fingerprint), Some(private_key)) => {
+                    (tenancy, user, fingerprint, private_key)
+                }
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "tenancy, user, fingerprint and private_key are required unless instance principal is enabled",
+                    )
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::Oci))
+                }
+            };
+
+            let private_key =
+                parse_private_key(&private_key, self.passphrase.as_deref()).map_err(|err| {
+                    err.with_operation("Builder::build")
+                        .with_context("service", Scheme::Oci)
+                })?;
+
+            OciSigner {
+                key_id: format!("{tenancy}/{user}/{fingerprint}"),
+                private_key: Some(private_key),
+                ..Default::default()
+            }
+        };
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Oci)
+            })?
+        };
+
+        debug!("backend build finished");
+        Ok(OciBackend {
+            core: Arc::new(OciCore {
+                root,
+                endpoint,
+                region,
+                namespace,
+                bucket,
+                signer: Mutex::new(signer),
+                client,
+            }),
+        })
+    }
+}
+
+/// Backend for OCI Object Storage services.
+#[derive(Debug, Clone)]
+pub struct OciBackend {
+    core: Arc<OciCore>,
+}
+
+#[async_trait]
+impl Accessor for OciBackend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = OciWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = OciPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Oci)
+            .set_root(&self.core.root)
+            .set_name(&self.core.bucket)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_without_content_length: true,
+                write_with_content_type: true,
+                write_with_content_disposition: true,
+                write_with_cache_control: true,
+
+                create_dir: true,
+                delete: true,
+                rename: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_delimiter_slash: true,
+                list_without_delimiter: true,
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        let resp = self
+            .core
+            .oci_put_object(path, &OpWrite::default(), bytes::Bytes::new())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCreateDir::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let resp = self.core.oci_get_object(path, args.range()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Ok((
+            RpWrite::default(),
+            OciWriter::new(self.core.clone(), path, args),
+        ))
+    }
+
+    async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
+        let resp = self.core.oci_rename_object(from, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpRename::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let resp = self.core.oci_head_object(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.oci_delete_object(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((
+            RpList::default(),
+            OciPager::new(self.core.clone(), path, args.delimiter(), args.limit()),
+        ))
+    }
+}
@@ -0,0 +1,111 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// OciError is the error returned by OCI Object Storage.
+///
+/// Ref: <https://docs.oracle.com/en-us/iaas/Content/API/References/apierrors.htm>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct OciError {
+    code: String,
+    message: String,
+}
+
+/// Parse error response into Error.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    let (kind, retryable) = match parts.status {
+        StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        StatusCode::PRECONDITION_FAILED | StatusCode::NOT_MODIFIED => {
+            (ErrorKind::ConditionNotMatch, false)
+        }
+        // OCI returns `409 ConcurrentObjectUpdate` while the object is
+        // being updated by another request.
+        StatusCode::CONFLICT => (ErrorKind::Unexpected, true),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let message = match serde_json::from_slice::<OciError>(&bs) {
+        Ok(oci_error) if !oci_error.code.is_empty() => format!("{oci_error:?}"),
+        _ => String::from_utf8_lossy(&bs).into_owned(),
+    };
+
+    let mut err = Error::new(kind, &message).with_context("response", format!("{parts:?}"));
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::raw::oio::into_stream;
+
+    #[tokio::test]
+    async fn test_parse_error() -> Result<()> {
+        let cases = vec![
+            (404, ErrorKind::NotFound, false),
+            (401, ErrorKind::PermissionDenied, false),
+            (412, ErrorKind::ConditionNotMatch, false),
+            (409, ErrorKind::Unexpected, true),
+            (429, ErrorKind::RateLimited, true),
+            (503, ErrorKind::Unexpected, true),
+        ];
+
+        for (status, kind, temporary) in cases {
+            let body = bytes::Bytes::from(
+                r#"{"code": "ObjectNotFound", "message": "The object 'test' does not exist"}"#,
+            );
+            let body = IncomingAsyncBody::new(
+                Box::new(into_stream::from_futures_stream(stream::iter(vec![Ok(
+                    body,
+                )]))),
+                None,
+            );
+            let resp = Response::builder().status(status).body(body).unwrap();
+
+            let err = parse_error(resp).await?;
+            assert_eq!(err.kind(), kind);
+            assert_eq!(err.is_temporary(), temporary);
+            assert!(err.to_string().contains("does not exist"));
+        }
+
+        Ok(())
+    }
+}
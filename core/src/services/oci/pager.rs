@@ -0,0 +1,169 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::core::OciCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct OciPager {
+    core: Arc<OciCore>,
+    path: String,
+    delimiter: String,
+    limit: Option<usize>,
+
+    start: String,
+    done: bool,
+}
+
+impl OciPager {
+    pub fn new(core: Arc<OciCore>, path: &str, delimiter: &str, limit: Option<usize>) -> Self {
+        Self {
+            core,
+            path: path.to_string(),
+            delimiter: delimiter.to_string(),
+            limit,
+
+            start: "".to_string(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for OciPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let resp = self
+            .core
+            .oci_list_objects(&self.path, &self.start, &self.delimiter, self.limit)
+            .await?;
+
+        if resp.status() != http::StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: ListObjectsOutput =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        // `nextStartWith` will be absent if there are no more objects.
+        match output.next_start_with {
+            Some(start) if !start.is_empty() => self.start = start,
+            _ => self.done = true,
+        }
+
+        let mut entries = Vec::with_capacity(output.prefixes.len() + output.objects.len());
+
+        for prefix in output.prefixes {
+            let de = oio::Entry::new(
+                &build_rel_path(&self.core.root, &prefix),
+                Metadata::new(EntryMode::DIR),
+            );
+
+            entries.push(de);
+        }
+
+        for object in output.objects {
+            if object.name.ends_with('/') {
+                continue;
+            }
+
+            let mut meta = Metadata::new(EntryMode::FILE);
+            meta.set_content_length(object.size);
+            if let Some(v) = &object.etag {
+                meta.set_etag(v);
+            }
+            if let Some(v) = &object.md5 {
+                meta.set_content_md5(v);
+            }
+            if let Some(v) = &object.time_modified {
+                meta.set_last_modified(parse_datetime_from_rfc3339(v)?);
+            }
+
+            let de = oio::Entry::new(&build_rel_path(&self.core.root, &object.name), meta);
+
+            entries.push(de);
+        }
+
+        Ok(Some(entries))
+    }
+}
+
+/// Ref: <https://docs.oracle.com/en-us/iaas/api/#/en/objectstorage/20160918/datatypes/ListObjects>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ListObjectsOutput {
+    objects: Vec<ObjectSummary>,
+    prefixes: Vec<String>,
+    next_start_with: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ObjectSummary {
+    name: String,
+    size: u64,
+    etag: Option<String>,
+    md5: Option<String>,
+    time_modified: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_list_objects_output() {
+        let content = r#"{
+            "objects": [
+                {
+                    "name": "dir/a.txt",
+                    "size": 1024,
+                    "etag": "0e3f0c8f-5d8a-4c1b-9e1e-3c1a3b1d2e4f",
+                    "md5": "1B2M2Y8AsgTpgAmY7PhCfg==",
+                    "timeModified": "2023-06-01T08:00:00.123Z"
+                }
+            ],
+            "prefixes": ["dir/sub/"],
+            "nextStartWith": "dir/b.txt"
+        }"#;
+
+        let output: ListObjectsOutput =
+            serde_json::from_str(content).expect("output must be valid");
+        assert_eq!(output.next_start_with.as_deref(), Some("dir/b.txt"));
+        assert_eq!(output.prefixes, vec!["dir/sub/"]);
+        assert_eq!(output.objects[0].name, "dir/a.txt");
+        assert_eq!(output.objects[0].size, 1024);
+        assert_eq!(
+            output.objects[0].md5.as_deref(),
+            Some("1B2M2Y8AsgTpgAmY7PhCfg==")
+        );
+        assert_eq!(
+            output.objects[0].time_modified.as_deref(),
+            Some("2023-06-01T08:00:00.123Z")
+        );
+    }
+}
@@ -19,6 +19,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -39,6 +40,7 @@ use crate::raw::*;
 use crate::*;
 
 const DEFAULT_WRITE_MIN_SIZE: usize = 8 * 1024 * 1024;
+const DEFAULT_WRITE_CONCURRENCY: usize = 1;
 const DEFAULT_BATCH_MAX_OPERATIONS: usize = 1000;
 /// Aliyun Object Storage Service (OSS) support
 #[doc = include_str!("docs.md")]
@@ -57,12 +59,23 @@ pub struct OssBuilder {
     // authenticate options
     access_key_id: Option<String>,
     access_key_secret: Option<String>,
+    security_token: Option<String>,
+    role_arn: Option<String>,
+    role_session_name: Option<String>,
+    oidc_provider_arn: Option<String>,
+    oidc_token_file: Option<String>,
+    sts_endpoint: Option<String>,
 
     http_client: Option<HttpClient>,
     /// the size of each part, and the range is 5MB ~ 5 GB.
     write_min_size: Option<usize>,
+    /// the number of parts that could be uploaded concurrently.
+    write_concurrency: Option<usize>,
     /// batch_max_operations
     batch_max_operations: Option<usize>,
+
+    /// The error of invalid config from map, returned while building.
+    config_error: Option<Error>,
 }
 
 impl Debug for OssBuilder {
@@ -148,6 +161,84 @@ impl OssBuilder {
         self
     }
 
+    /// Set security_token of this backend.
+    ///
+    /// It's required when using the temporary credentials issued by STS.
+    pub fn security_token(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.security_token = Some(v.to_string())
+        }
+
+        self
+    }
+
+    /// Set role_arn of this backend.
+    ///
+    /// If role_arn is set, we will assume this RAM role via STS to get the
+    /// temporary credentials. It's usually used along with `oidc_provider_arn`
+    /// and `oidc_token_file` under RRSA (RAM Roles for Service Accounts).
+    ///
+    /// - If role_arn is set, we will take user's input first.
+    /// - If not, we will try to load it from environment.
+    pub fn role_arn(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.role_arn = Some(v.to_string())
+        }
+
+        self
+    }
+
+    /// Set role_session_name of this backend.
+    ///
+    /// Default to `resign` if not set.
+    pub fn role_session_name(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.role_session_name = Some(v.to_string())
+        }
+
+        self
+    }
+
+    /// Set oidc_provider_arn of this backend.
+    ///
+    /// - If oidc_provider_arn is set, we will take user's input first.
+    /// - If not, we will try to load it from environment.
+    pub fn oidc_provider_arn(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.oidc_provider_arn = Some(v.to_string())
+        }
+
+        self
+    }
+
+    /// Set oidc_token_file of this backend.
+    ///
+    /// - If oidc_token_file is set, we will take user's input first.
+    /// - If not, we will try to load it from environment.
+    pub fn oidc_token_file(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.oidc_token_file = Some(v.to_string())
+        }
+
+        self
+    }
+
+    /// Set sts_endpoint of this backend.
+    ///
+    /// Default to `sts.aliyuncs.com` if not set. Users in VPC could set it
+    /// to the VPC endpoint like `sts-vpc.cn-hangzhou.aliyuncs.com`. `https`
+    /// is always used, so the scheme can be omitted.
+    ///
+    /// The endpoint is used by `AssumeRoleWithOIDC` while loading
+    /// credentials with `role_arn`, `oidc_provider_arn` and `oidc_token_file`.
+    pub fn sts_endpoint(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.sts_endpoint = Some(v.to_string())
+        }
+
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -241,12 +332,43 @@ impl OssBuilder {
         self
     }
 
+    /// Set the number of parts that could be uploaded concurrently in
+    /// multipart upload, default to 1.
+    ///
+    /// opendal will buffer at most `write_concurrency * write_min_size`
+    /// bytes in memory for every writer.
+    pub fn write_concurrency(&mut self, write_concurrency: usize) -> &mut Self {
+        self.write_concurrency = Some(write_concurrency);
+
+        self
+    }
+
     /// Set maximum batch operations of this backend.
     pub fn batch_max_operations(&mut self, batch_max_operations: usize) -> &mut Self {
         self.batch_max_operations = Some(batch_max_operations);
 
         self
     }
+
+    fn with_config_error(&mut self, err: Error) -> &mut Self {
+        // Keep the first error.
+        self.config_error.get_or_insert(err);
+        self
+    }
+}
+
+fn parse_config<T>(key: &'static str, v: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    v.parse::<T>().map_err(|err| {
+        Error::new(ErrorKind::ConfigInvalid, "config is invalid")
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Oss)
+            .with_context(key, v)
+            .set_source(err)
+    })
 }
 
 impl Builder for OssBuilder {
@@ -264,20 +386,47 @@ impl Builder for OssBuilder {
         map.get("access_key_id").map(|v| builder.access_key_id(v));
         map.get("access_key_secret")
             .map(|v| builder.access_key_secret(v));
+        map.get("security_token").map(|v| builder.security_token(v));
+        map.get("role_arn").map(|v| builder.role_arn(v));
+        map.get("role_session_name")
+            .map(|v| builder.role_session_name(v));
+        map.get("oidc_provider_arn")
+            .map(|v| builder.oidc_provider_arn(v));
+        map.get("oidc_token_file")
+            .map(|v| builder.oidc_token_file(v));
+        map.get("sts_endpoint").map(|v| builder.sts_endpoint(v));
         map.get("server_side_encryption")
             .map(|v| builder.server_side_encryption(v));
         map.get("server_side_encryption_key_id")
             .map(|v| builder.server_side_encryption_key_id(v));
-        map.get("write_min_size")
-            .map(|v| builder.write_min_size(v.parse::<usize>().unwrap()));
-        map.get("batch_max_operations")
-            .map(|v| builder.batch_max_operations(v.parse::<usize>().unwrap()));
+        if let Some(v) = map.get("write_min_size") {
+            match parse_config("write_min_size", v) {
+                Ok(v) => builder.write_min_size(v),
+                Err(err) => builder.with_config_error(err),
+            };
+        }
+        if let Some(v) = map.get("write_concurrency") {
+            match parse_config("write_concurrency", v) {
+                Ok(v) => builder.write_concurrency(v),
+                Err(err) => builder.with_config_error(err),
+            };
+        }
+        if let Some(v) = map.get("batch_max_operations") {
+            match parse_config("batch_max_operations", v) {
+                Ok(v) => builder.batch_max_operations(v),
+                Err(err) => builder.with_config_error(err),
+            };
+        }
         builder
     }
 
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
 
+        if let Some(err) = self.config_error.take() {
+            return Err(err);
+        }
+
         let root = normalize_root(&self.root.clone().unwrap_or_default());
         debug!("backend use root {}", &root);
 
@@ -339,6 +488,33 @@ impl Builder for OssBuilder {
             cfg.access_key_secret = Some(v);
         }
 
+        if let Some(v) = self.security_token.take() {
+            cfg.security_token = Some(v);
+        }
+
+        if let Some(v) = self.role_arn.take() {
+            cfg.role_arn = Some(v);
+        }
+
+        if let Some(v) = self.role_session_name.take() {
+            cfg.role_session_name = v;
+        }
+
+        if let Some(v) = self.oidc_provider_arn.take() {
+            cfg.oidc_provider_arn = Some(v);
+        }
+
+        if let Some(v) = self.oidc_token_file.take() {
+            cfg.oidc_token_file = Some(v);
+        }
+
+        if let Some(v) = self.sts_endpoint.take() {
+            // reqsign will add `https://` by itself.
+            let v = v.trim_start_matches("https://").trim_end_matches('/');
+            debug!("backend use sts endpoint: {v}");
+            cfg.sts_endpoint = Some(v.to_string());
+        }
+
         let loader = AliyunLoader::new(client.client(), cfg);
 
         let signer = AliyunOssSigner::new(bucket);
@@ -351,6 +527,14 @@ impl Builder for OssBuilder {
             )
             .with_context("service", Scheme::Oss));
         }
+        let write_concurrency = self.write_concurrency.unwrap_or(DEFAULT_WRITE_CONCURRENCY);
+        if write_concurrency == 0 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The write concurrency is misconfigured",
+            )
+            .with_context("service", Scheme::Oss));
+        }
         let batch_max_operations = self
             .batch_max_operations
             .unwrap_or(DEFAULT_BATCH_MAX_OPERATIONS);
@@ -369,6 +553,7 @@ impl Builder for OssBuilder {
                 server_side_encryption,
                 server_side_encryption_key_id,
                 write_min_size,
+                write_concurrency,
                 batch_max_operations,
             }),
         })
@@ -641,3 +826,25 @@ impl Accessor for OssBackend {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_map_invalid_config() {
+        for (key, value) in [
+            ("write_min_size", "abc"),
+            ("write_concurrency", "abc"),
+            ("write_concurrency", "0"),
+            ("batch_max_operations", "-1"),
+        ] {
+            let map = HashMap::from([
+                ("bucket".to_string(), "test".to_string()),
+                (key.to_string(), value.to_string()),
+            ]);
+            let err = OssBuilder::from_map(map).build().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid, "{key}: {value}");
+        }
+    }
+}
@@ -65,6 +65,7 @@ pub struct OssCore {
     pub loader: AliyunLoader,
     pub signer: AliyunOssSigner,
     pub write_min_size: usize,
+    pub write_concurrency: usize,
     pub batch_max_operations: usize,
 }
 
@@ -87,11 +88,7 @@ impl OssCore {
             .await
             .map_err(new_request_credential_error)?;
 
-        if let Some(cred) = cred {
-            Ok(Some(cred))
-        } else {
-            Ok(None)
-        }
+        Ok(cred)
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
@@ -20,6 +20,9 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use bytes::Buf;
 use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
 use http::StatusCode;
 
 use super::core::*;
@@ -27,6 +30,8 @@ use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+type PartFuture = BoxFuture<'static, (usize, Bytes, Result<MultipartUploadPart>)>;
+
 pub struct OssWriter {
     core: Arc<OssCore>,
 
@@ -35,10 +40,18 @@ pub struct OssWriter {
     upload_id: Option<String>,
 
     parts: Vec<MultipartUploadPart>,
+    /// The number of parts that have been submitted.
+    part_number: usize,
+    /// Parts that are uploading, at most `write_concurrency` parts.
+    futures: FuturesUnordered<PartFuture>,
     buffer: oio::VectorCursor,
     buffer_size: usize,
 }
 
+/// `PartFuture` is not `Sync`, but it will only be used via `&mut self`,
+/// so it's safe to share the writer between threads.
+unsafe impl Sync for OssWriter {}
+
 impl OssWriter {
     pub fn new(core: Arc<OssCore>, path: &str, op: OpWrite) -> Self {
         let buffer_size = core.write_min_size;
@@ -49,6 +62,8 @@ impl OssWriter {
 
             upload_id: None,
             parts: vec![],
+            part_number: 0,
+            futures: FuturesUnordered::new(),
             buffer: oio::VectorCursor::new(),
             buffer_size,
         }
@@ -93,39 +108,77 @@ impl OssWriter {
         }
     }
 
-    async fn write_part(&self, upload_id: &str, bs: Bytes) -> Result<MultipartUploadPart> {
-        // Aliyun OSS requires part number must between [1..=10000]
-        let part_number = self.parts.len() + 1;
-        let mut req = self
-            .core
-            .oss_upload_part_request(
-                &self.path,
-                upload_id,
-                part_number,
-                false,
-                Some(bs.len() as u64),
-                AsyncBody::Bytes(bs),
-            )
-            .await?;
+    /// Submit a part to upload, the part will be uploaded while polling
+    /// `futures`.
+    fn submit_part(&mut self, upload_id: &str, part_number: usize, bs: Bytes) {
+        let core = self.core.clone();
+        let path = self.path.clone();
+        let upload_id = upload_id.to_string();
 
-        self.core.sign(&mut req).await?;
+        self.futures.push(Box::pin(async move {
+            let res = write_part(&core, &path, &upload_id, part_number, bs.clone()).await;
+            (part_number, bs, res)
+        }));
+    }
 
-        let resp = self.core.send(req).await?;
-        match resp.status() {
-            StatusCode::OK => {
-                let etag = parse_etag(resp.headers())?
-                    .ok_or_else(|| {
-                        Error::new(
-                            ErrorKind::Unexpected,
-                            "ETag not present in returning response",
-                        )
-                    })?
-                    .to_string();
-                resp.into_body().consume().await?;
-                Ok(MultipartUploadPart { part_number, etag })
+    /// Wait for one uploading part to finish.
+    ///
+    /// The failed part will be submitted again so that it could be retried
+    /// in the next call.
+    async fn wait_part(&mut self, upload_id: &str) -> Result<()> {
+        let (part_number, bs, res) = match self.futures.next().await {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        match res {
+            Ok(part) => {
+                self.parts.push(part);
+                Ok(())
             }
-            _ => Err(parse_error(resp).await?),
+            Err(e) => {
+                self.submit_part(upload_id, part_number, bs);
+                Err(e)
+            }
+        }
+    }
+}
+
+async fn write_part(
+    core: &OssCore,
+    path: &str,
+    upload_id: &str,
+    part_number: usize,
+    bs: Bytes,
+) -> Result<MultipartUploadPart> {
+    let mut req = core
+        .oss_upload_part_request(
+            path,
+            upload_id,
+            part_number,
+            false,
+            Some(bs.len() as u64),
+            AsyncBody::Bytes(bs),
+        )
+        .await?;
+
+    core.sign(&mut req).await?;
+
+    let resp = core.send(req).await?;
+    match resp.status() {
+        StatusCode::OK => {
+            let etag = parse_etag(resp.headers())?
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "ETag not present in returning response",
+                    )
+                })?
+                .to_string();
+            resp.into_body().consume().await?;
+            Ok(MultipartUploadPart { part_number, etag })
         }
+        _ => Err(parse_error(resp).await?),
     }
 }
 
@@ -133,18 +186,24 @@ impl OssWriter {
 impl oio::Write for OssWriter {
     async fn write(&mut self, bs: Bytes) -> Result<()> {
         let upload_id = match &self.upload_id {
-            Some(upload_id) => upload_id,
+            Some(upload_id) => upload_id.to_string(),
             None => {
                 if self.op.content_length().unwrap_or_default() == bs.len() as u64 {
                     return self.write_oneshot(bs).await;
                 } else {
                     let upload_id = self.initiate_upload().await?;
-                    self.upload_id = Some(upload_id);
-                    self.upload_id.as_deref().unwrap()
+                    self.upload_id = Some(upload_id.clone());
+                    upload_id
                 }
             }
         };
 
+        // Make sure we have room for the next part before accepting the
+        // given bs so that write is re-enter safe.
+        if self.futures.len() >= self.core.write_concurrency {
+            self.wait_part(&upload_id).await?;
+        }
+
         // Ignore empty bytes
         if bs.is_empty() {
             return Ok(());
@@ -157,21 +216,13 @@ impl oio::Write for OssWriter {
         }
 
         let bs = self.buffer.peak_at_least(self.buffer_size);
-        let size = bs.len();
+        self.buffer.take(bs.len());
 
-        match self.write_part(upload_id, bs).await {
-            Ok(part) => {
-                self.buffer.take(size);
-                self.parts.push(part);
-                Ok(())
-            }
-            Err(e) => {
-                // If the upload fails, we should pop the given bs to make sure
-                // write is re-enter safe.
-                self.buffer.pop();
-                Err(e)
-            }
-        }
+        // Aliyun OSS requires part number must between [1..=10000]
+        self.part_number += 1;
+        self.submit_part(&upload_id, self.part_number, bs);
+
+        Ok(())
     }
 
     async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
@@ -191,7 +242,7 @@ impl oio::Write for OssWriter {
 
     async fn close(&mut self) -> Result<()> {
         let upload_id = if let Some(upload_id) = &self.upload_id {
-            upload_id
+            upload_id.to_string()
         } else {
             return Ok(());
         };
@@ -199,21 +250,23 @@ impl oio::Write for OssWriter {
         // Make sure internal buffer has been flushed.
         if !self.buffer.is_empty() {
             let bs = self.buffer.peak_exact(self.buffer.len());
+            self.buffer.clear();
 
-            match self.write_part(upload_id, bs).await {
-                Ok(part) => {
-                    self.buffer.clear();
-                    self.parts.push(part);
-                }
-                Err(e) => {
-                    return Err(e);
-                }
-            }
+            self.part_number += 1;
+            self.submit_part(&upload_id, self.part_number, bs);
         }
 
+        // Wait for all parts to finish.
+        while !self.futures.is_empty() {
+            self.wait_part(&upload_id).await?;
+        }
+
+        // Parts could be finished out of order.
+        self.parts.sort_by_key(|v| v.part_number);
+
         let resp = self
             .core
-            .oss_complete_multipart_upload_request(&self.path, upload_id, false, &self.parts)
+            .oss_complete_multipart_upload_request(&self.path, &upload_id, false, &self.parts)
             .await?;
         match resp.status() {
             StatusCode::OK => {
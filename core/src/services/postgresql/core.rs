@@ -0,0 +1,226 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use chrono::DateTime;
+use chrono::Utc;
+use sqlx::postgres::PgConnectOptions;
+use sqlx::PgPool;
+use tokio::sync::OnceCell;
+
+use crate::*;
+
+type MetadataRow = (String, i64, Option<String>, DateTime<Utc>);
+
+pub struct PostgresqlCore {
+    pub config: PgConnectOptions,
+    pub table: String,
+    pub root: String,
+
+    pub pool: OnceCell<PgPool>,
+}
+
+impl Debug for PostgresqlCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostgresqlCore")
+            .field("table", &self.table)
+            .field("root", &self.root)
+            .finish()
+    }
+}
+
+impl PostgresqlCore {
+    async fn pool(&self) -> Result<&PgPool> {
+        self.pool
+            .get_or_try_init(|| async {
+                PgPool::connect_with(self.config.clone())
+                    .await
+                    .map_err(parse_sqlx_error)
+            })
+            .await
+    }
+
+    /// Get metadata of given path without reading its value.
+    pub async fn get_metadata(&self, path: &str) -> Result<Option<Metadata>> {
+        let sql = format!(
+            "SELECT key, content_length, content_type, last_modified FROM {} WHERE key = $1",
+            quote_ident(&self.table)
+        );
+
+        let row: Option<MetadataRow> = sqlx::query_as(&sql)
+            .bind(path)
+            .fetch_optional(self.pool().await?)
+            .await
+            .map_err(parse_sqlx_error)?;
+
+        Ok(row.map(parse_metadata_row).map(|(_, meta)| meta))
+    }
+
+    /// Read `size` bytes of the value from `offset`.
+    ///
+    /// Only the requested range will be transferred from the server.
+    pub async fn get_range(&self, path: &str, offset: u64, size: u64) -> Result<Option<Vec<u8>>> {
+        let sql = format!(
+            "SELECT substring(value FROM $2 FOR $3) FROM {} WHERE key = $1",
+            quote_ident(&self.table)
+        );
+
+        // Postgres limits the size of bytea to 1 GiB, so `i32` is enough.
+        let row: Option<(Vec<u8>,)> = sqlx::query_as(&sql)
+            .bind(path)
+            .bind((offset + 1) as i32)
+            .bind(size as i32)
+            .fetch_optional(self.pool().await?)
+            .await
+            .map_err(parse_sqlx_error)?;
+
+        Ok(row.map(|(bs,)| bs))
+    }
+
+    pub async fn set(&self, path: &str, value: &[u8], content_type: Option<&str>) -> Result<()> {
+        let sql = format!(
+            "INSERT INTO {} (key, value, content_length, content_type, last_modified) \
+             VALUES ($1, $2, $3, $4, now()) \
+             ON CONFLICT (key) DO UPDATE SET \
+             value = EXCLUDED.value, \
+             content_length = EXCLUDED.content_length, \
+             content_type = EXCLUDED.content_type, \
+             last_modified = EXCLUDED.last_modified",
+            quote_ident(&self.table)
+        );
+
+        sqlx::query(&sql)
+            .bind(path)
+            .bind(value)
+            .bind(value.len() as i64)
+            .bind(content_type)
+            .execute(self.pool().await?)
+            .await
+            .map_err(parse_sqlx_error)?;
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, path: &str) -> Result<()> {
+        let sql = format!("DELETE FROM {} WHERE key = $1", quote_ident(&self.table));
+
+        sqlx::query(&sql)
+            .bind(path)
+            .execute(self.pool().await?)
+            .await
+            .map_err(parse_sqlx_error)?;
+
+        Ok(())
+    }
+
+    /// List at most `limit` keys start with `prefix` and greater than
+    /// `start_after`, sorted by key.
+    pub async fn list(
+        &self,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<(String, Metadata)>> {
+        let sql = format!(
+            "SELECT key, content_length, content_type, last_modified FROM {} \
+             WHERE key LIKE $1 ESCAPE '\\' AND key > $2 ORDER BY key LIMIT $3",
+            quote_ident(&self.table)
+        );
+
+        let rows: Vec<MetadataRow> = sqlx::query_as(&sql)
+            .bind(format!("{}%", escape_like(prefix)))
+            .bind(start_after.unwrap_or_default())
+            .bind(limit as i64)
+            .fetch_all(self.pool().await?)
+            .await
+            .map_err(parse_sqlx_error)?;
+
+        Ok(rows.into_iter().map(parse_metadata_row).collect())
+    }
+}
+
+fn parse_metadata_row(row: MetadataRow) -> (String, Metadata) {
+    let (key, content_length, content_type, last_modified) = row;
+
+    let mut meta = if key.ends_with('/') {
+        Metadata::new(EntryMode::DIR)
+    } else {
+        Metadata::new(EntryMode::FILE)
+    };
+    meta.set_content_length(content_length as u64);
+    meta.set_last_modified(last_modified);
+    if let Some(v) = content_type {
+        meta.set_content_type(&v);
+    }
+
+    (key, meta)
+}
+
+/// Quote the identifier so that it can be used in sql safely.
+pub fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// Escape `\`, `%` and `_` so that `s` can be matched literally by `LIKE`.
+pub fn escape_like(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+pub fn parse_sqlx_error(e: sqlx::Error) -> Error {
+    let retryable = matches!(e, sqlx::Error::PoolTimedOut | sqlx::Error::Io(_));
+
+    let err = Error::new(ErrorKind::Unexpected, "error from postgresql").set_source(e);
+    if retryable {
+        err.set_temporary()
+    } else {
+        err
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_ident() {
+        assert_eq!(quote_ident("opendal"), "\"opendal\"");
+        assert_eq!(quote_ident("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn test_escape_like() {
+        let cases = vec![
+            ("normal", "path/to/dir/", "path/to/dir/"),
+            ("empty", "", ""),
+            ("wildcards", "a%b_c", "a\\%b\\_c"),
+            ("backslash", "a\\b", "a\\\\b"),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(escape_like(input), expected, "{name}")
+        }
+    }
+}
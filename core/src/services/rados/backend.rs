@@ -0,0 +1,309 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ceph::ceph::connect_to_ceph;
+use ceph::ceph::IoCtx;
+use ceph::ceph::Pool;
+use ceph::ceph::Rados;
+use ceph::error::RadosError;
+
+use crate::raw::adapters::kv;
+use crate::Result;
+use crate::*;
+
+/// The default ceph config file used to locate monitors and keyrings.
+const DEFAULT_CONFIG_FILE: &str = "/etc/ceph/ceph.conf";
+/// The default ceph user to connect as.
+const DEFAULT_USER: &str = "admin";
+/// The default stripe size: 4 MiB, which matches the default rados
+/// object layout.
+const DEFAULT_STRIPE_SIZE: usize = 4 * 1024 * 1024;
+/// errno returned by librados while the object is not exist.
+const ENOENT: i32 = 2;
+
+/// Ceph RADOS service support.
+#[doc = include_str!("docs.md")]
+#[derive(Clone, Default)]
+pub struct RadosBuilder {
+    /// The path to the ceph config file.
+    config_file: Option<String>,
+    /// The ceph user used to connect.
+    user: Option<String>,
+    /// The rados pool to store objects.
+    pool: Option<String>,
+    /// The size of every single read or write against rados.
+    stripe_size: Option<usize>,
+    /// the working directory of the service. Can be "/path/to/dir"
+    ///
+    /// default is "/"
+    root: Option<String>,
+}
+
+impl Debug for RadosBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("RadosBuilder");
+        ds.field("config_file", &self.config_file);
+        ds.field("user", &self.user);
+        ds.field("pool", &self.pool);
+        ds.field("stripe_size", &self.stripe_size);
+        ds.field("root", &self.root);
+        ds.finish()
+    }
+}
+
+impl RadosBuilder {
+    /// Set the path to the ceph config file.
+    ///
+    /// default: "/etc/ceph/ceph.conf"
+    pub fn config_file(&mut self, config_file: &str) -> &mut Self {
+        if !config_file.is_empty() {
+            self.config_file = Some(config_file.to_string());
+        }
+        self
+    }
+
+    /// Set the ceph user to connect as, without the `client.` prefix.
+    ///
+    /// default: "admin"
+    pub fn user(&mut self, user: &str) -> &mut Self {
+        if !user.is_empty() {
+            self.user = Some(user.to_string());
+        }
+        self
+    }
+
+    /// Set the rados pool to store objects in.
+    pub fn pool(&mut self, pool: &str) -> &mut Self {
+        if !pool.is_empty() {
+            self.pool = Some(pool.to_string());
+        }
+        self
+    }
+
+    /// Set the stripe size. Objects larger than this will be read and
+    /// written in stripes of this size.
+    ///
+    /// default: 4 MiB
+    pub fn stripe_size(&mut self, stripe_size: usize) -> &mut Self {
+        if stripe_size > 0 {
+            self.stripe_size = Some(stripe_size);
+        }
+        self
+    }
+
+    /// set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string());
+        }
+        self
+    }
+}
+
+impl Builder for RadosBuilder {
+    const SCHEME: Scheme = Scheme::Rados;
+    type Accessor = RadosBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = RadosBuilder::default();
+
+        map.get("config_file").map(|v| builder.config_file(v));
+        map.get("user").map(|v| builder.user(v));
+        map.get("pool").map(|v| builder.pool(v));
+        map.get("stripe_size")
+            .and_then(|v| v.parse::<usize>().ok())
+            .map(|v| builder.stripe_size(v));
+        map.get("root").map(|v| builder.root(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let pool = self.pool.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "pool is required but not set")
+                .with_context("service", Scheme::Rados)
+        })?;
+        let config_file = self
+            .config_file
+            .take()
+            .unwrap_or_else(|| DEFAULT_CONFIG_FILE.to_string());
+        let user = self.user.take().unwrap_or_else(|| DEFAULT_USER.to_string());
+
+        let cluster = connect_to_ceph(&user, &config_file).map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "connect to ceph cluster")
+                .with_context("service", Scheme::Rados)
+                .with_context("config_file", &config_file)
+                .with_context("user", &user)
+                .set_source(e)
+        })?;
+        let ioctx = cluster.get_rados_ioctx(&pool).map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "open rados pool")
+                .with_context("service", Scheme::Rados)
+                .with_context("pool", &pool)
+                .set_source(e)
+        })?;
+
+        Ok(RadosBackend::new(Adapter {
+            pool,
+            stripe_size: self.stripe_size.unwrap_or(DEFAULT_STRIPE_SIZE),
+            ioctx: Arc::new(ioctx),
+            cluster: Arc::new(cluster),
+        })
+        .with_root(self.root.as_deref().unwrap_or_default()))
+    }
+}
+
+/// Backend for rados services.
+pub type RadosBackend = kv::Backend<Adapter>;
+
+#[derive(Clone)]
+pub struct Adapter {
+    pool: String,
+    stripe_size: usize,
+    // ioctx must be dropped before the cluster handle it's created from.
+    ioctx: Arc<IoCtx>,
+    #[allow(dead_code)]
+    cluster: Arc<Rados>,
+}
+
+/// librados handles are thread-safe.
+unsafe impl Send for Adapter {}
+unsafe impl Sync for Adapter {}
+
+impl Debug for Adapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Adapter");
+        ds.field("pool", &self.pool);
+        ds.field("stripe_size", &self.stripe_size);
+        ds.finish()
+    }
+}
+
+#[async_trait]
+impl kv::Adapter for Adapter {
+    fn metadata(&self) -> kv::Metadata {
+        kv::Metadata::new(
+            Scheme::Rados,
+            &self.pool,
+            Capability {
+                read: true,
+                write: true,
+                list: true,
+                blocking: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        self.blocking_get(path)
+    }
+
+    /// Read the whole object stripe by stripe, so that large objects never
+    /// hit the size limit of a single rados op.
+    fn blocking_get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let size = match self.ioctx.rados_object_stat(path) {
+            Ok((size, _)) => size as usize,
+            Err(e) if is_not_found(&e) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut value = Vec::with_capacity(size);
+        while value.len() < size {
+            let mut buf = Vec::with_capacity(self.stripe_size.min(size - value.len()));
+            let n = self
+                .ioctx
+                .rados_object_read(path, &mut buf, value.len() as u64)?;
+            // The object has been truncated while reading.
+            if n <= 0 {
+                break;
+            }
+            value.extend_from_slice(&buf);
+        }
+
+        Ok(Some(value))
+    }
+
+    async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
+        self.blocking_set(path, value)
+    }
+
+    /// The first stripe replaces the whole object, and the following
+    /// stripes are written at their offsets.
+    fn blocking_set(&self, path: &str, value: &[u8]) -> Result<()> {
+        let mut stripes = value.chunks(self.stripe_size);
+
+        self.ioctx
+            .rados_object_write_full(path, stripes.next().unwrap_or_default())?;
+
+        let mut offset = self.stripe_size as u64;
+        for stripe in stripes {
+            self.ioctx.rados_object_write(path, stripe, offset)?;
+            offset += stripe.len() as u64;
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        self.blocking_delete(path)
+    }
+
+    fn blocking_delete(&self, path: &str) -> Result<()> {
+        match self.ioctx.rados_object_remove(path) {
+            Ok(()) => Ok(()),
+            Err(e) if is_not_found(&e) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        self.blocking_scan(path)
+    }
+
+    /// Rados doesn't support listing by prefix, so we have to iterate
+    /// all objects in the pool and filter them by ourselves.
+    fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
+        let ctx = self.ioctx.rados_list_pool_objects()?;
+
+        let mut res: Vec<String> = Pool { ctx }
+            .map(|obj| obj.name)
+            .filter(|name| name.starts_with(path))
+            .collect();
+        res.sort();
+
+        Ok(res)
+    }
+}
+
+fn is_not_found(e: &RadosError) -> bool {
+    matches!(e, RadosError::ApiError(errno) if *errno as i32 == ENOENT)
+}
+
+impl From<RadosError> for Error {
+    fn from(e: RadosError) -> Self {
+        Error::new(ErrorKind::Unexpected, "got rados error").set_source(e)
+    }
+}
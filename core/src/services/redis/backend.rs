@@ -25,6 +25,7 @@ use async_trait::async_trait;
 use http::Uri;
 use redis::aio::ConnectionManager;
 use redis::AsyncCommands;
+use redis::AsyncIter;
 use redis::Client;
 use redis::ConnectionAddr;
 use redis::ConnectionInfo;
@@ -159,6 +160,10 @@ impl Builder for RedisBuilder {
         map.get("password").map(|v| builder.password(v));
         map.get("db")
             .map(|v| v.parse::<i64>().map(|v| builder.db(v)));
+        map.get("default_ttl").map(|v| {
+            v.parse::<u64>()
+                .map(|v| builder.default_ttl(Duration::from_secs(v)))
+        });
 
         builder
     }
@@ -279,6 +284,7 @@ impl kv::Adapter for Adapter {
                 read: true,
                 write: true,
                 create_dir: true,
+                list: true,
 
                 ..Default::default()
             },
@@ -311,6 +317,32 @@ impl kv::Adapter for Adapter {
         conn.append(key, value).await?;
         Ok(())
     }
+
+    /// Scan keys via `SCAN` with `MATCH` so that redis will not be blocked
+    /// like `KEYS`.
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        let mut conn = self.conn().await?;
+        let pattern = format!("{}*", escape_pattern(path));
+
+        let mut iter: AsyncIter<String> = conn.scan_match(pattern).await?;
+        let mut keys = Vec::new();
+        while let Some(key) = iter.next_item().await {
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+}
+
+/// Escape the glob-style special chars so that keys will be matched by prefix.
+fn escape_pattern(path: &str) -> String {
+    let mut s = String::with_capacity(path.len());
+    for c in path.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            s.push('\\');
+        }
+        s.push(c);
+    }
+    s
 }
 
 impl From<RedisError> for Error {
@@ -320,3 +352,23 @@ impl From<RedisError> for Error {
             .set_temporary()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_pattern() {
+        let cases = vec![
+            ("", ""),
+            ("path/to/dir/", "path/to/dir/"),
+            ("a*b?c", "a\\*b\\?c"),
+            ("[abc]", "\\[abc\\]"),
+            ("a\\b", "a\\\\b"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(escape_pattern(input), expected, "{input}");
+        }
+    }
+}
@@ -21,6 +21,8 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use rocksdb::Direction;
+use rocksdb::IteratorMode;
 use rocksdb::DB;
 
 use crate::raw::adapters::kv;
@@ -65,6 +67,7 @@ impl Builder for RocksdbBuilder {
         let mut builder = RocksdbBuilder::default();
 
         map.get("datadir").map(|v| builder.datadir(v));
+        map.get("root").map(|v| builder.root(v));
 
         builder
     }
@@ -81,7 +84,8 @@ impl Builder for RocksdbBuilder {
                 .set_source(e)
         })?;
 
-        Ok(RocksdbBackend::new(Adapter { db: Arc::new(db) }))
+        Ok(RocksdbBackend::new(Adapter { db: Arc::new(db) })
+            .with_root(self.root.as_deref().unwrap_or_default()))
     }
 }
 
@@ -110,6 +114,8 @@ impl kv::Adapter for Adapter {
             Capability {
                 read: true,
                 write: true,
+                list: true,
+                blocking: true,
                 ..Default::default()
             },
         )
@@ -138,6 +144,33 @@ impl kv::Adapter for Adapter {
     fn blocking_delete(&self, path: &str) -> Result<()> {
         Ok(self.db.delete(path)?)
     }
+
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        self.blocking_scan(path)
+    }
+
+    /// Iterate keys from `path` in order and stop at the first key that
+    /// doesn't share the prefix, since no prefix extractor is configured.
+    fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
+        let it = self
+            .db
+            .iterator(IteratorMode::From(path.as_bytes(), Direction::Forward));
+        let mut res = Vec::default();
+
+        for i in it {
+            let (key, _) = i?;
+            if !key.starts_with(path.as_bytes()) {
+                break;
+            }
+
+            res.push(String::from_utf8(key.into_vec()).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "store key is not valid utf-8 string")
+                    .set_source(err)
+            })?);
+        }
+
+        Ok(res)
+    }
 }
 
 impl From<rocksdb::Error> for Error {
@@ -30,15 +30,20 @@ use log::debug;
 use md5::Digest;
 use md5::Md5;
 use once_cell::sync::Lazy;
+use reqsign::AwsAssumeRoleLoader;
 use reqsign::AwsConfig;
 use reqsign::AwsCredentialLoad;
-use reqsign::AwsLoader;
+use reqsign::AwsDefaultLoader;
 use reqsign::AwsV4Signer;
 
 use super::core::*;
+use super::credential::CredentialChain;
+use super::credential::EcsCredentialLoad;
 use super::error::parse_error;
 use super::error::parse_s3_error_code;
 use super::pager::S3Pager;
+use super::region::S3RegionDetector;
+use super::select::S3SelectReader;
 use super::writer::S3Writer;
 use crate::raw::*;
 use crate::*;
@@ -55,7 +60,15 @@ static ENDPOINT_TEMPLATES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new
 });
 
 const DEFAULT_WRITE_MIN_SIZE: usize = 8 * 1024 * 1024;
+const DEFAULT_WRITE_CONCURRENCY: usize = 1;
 const DEFAULT_BATCH_MAX_OPERATIONS: usize = 1000;
+
+/// Cloudflare R2 will return `Internal Error` while deleting more than 700
+/// objects in one batch.
+const R2_BATCH_MAX_OPERATIONS: usize = 700;
+/// Endpoint suffix of Cloudflare R2, jurisdiction specific endpoints like
+/// `https://<account_id>.eu.r2.cloudflarestorage.com` are also covered.
+const R2_ENDPOINT_SUFFIX: &str = ".r2.cloudflarestorage.com";
 /// Aws S3 and compatible services (including minio, digitalocean space, Tencent Cloud Object Storage(COS) and so on) support.
 /// For more information about s3-compatible services, refer to [Compatible Services](#compatible-services).
 #[doc = include_str!("docs.md")]
@@ -69,6 +82,9 @@ pub struct S3Builder {
     region: Option<String>,
     role_arn: Option<String>,
     external_id: Option<String>,
+    role_session_name: Option<String>,
+    web_identity_token_file: Option<String>,
+    profile: Option<String>,
     access_key_id: Option<String>,
     secret_access_key: Option<String>,
     server_side_encryption: Option<String>,
@@ -77,6 +93,8 @@ pub struct S3Builder {
     server_side_encryption_customer_key: Option<String>,
     server_side_encryption_customer_key_md5: Option<String>,
     default_storage_class: Option<String>,
+    default_acl: Option<String>,
+    checksum_algorithm: Option<String>,
 
     /// temporary credentials, check the official [doc](https://docs.aws.amazon.com/IAM/latest/UserGuide/id_credentials_temp.html) for detail
     security_token: Option<String>,
@@ -85,6 +103,11 @@ pub struct S3Builder {
     disable_ec2_metadata: bool,
     allow_anonymous: bool,
     enable_virtual_host_style: bool,
+    force_path_style: bool,
+    enable_exact_buf_write: bool,
+    enable_requester_pays: bool,
+    disable_list_objects_v2: bool,
+    disable_region_detection: bool,
 
     http_client: Option<HttpClient>,
     customed_credential_load: Option<Box<dyn AwsCredentialLoad>>,
@@ -92,6 +115,8 @@ pub struct S3Builder {
     /// the part size of s3 multipart upload, which should be 5 MiB to 5 GiB.
     /// There is no minimum size limit on the last part of your multipart upload
     write_min_size: Option<usize>,
+    /// the number of parts that could be uploaded concurrently.
+    write_concurrency: Option<usize>,
     /// batch_max_operations
     batch_max_operations: Option<usize>,
 }
@@ -150,12 +175,14 @@ impl S3Builder {
         self
     }
 
-    /// Region represent the signing region of this endpoint. This is required
-    /// if you are using the default AWS S3 endpoint.
+    /// Region represent the signing region of this endpoint.
     ///
-    /// If using a custom endpoint,
     /// - If region is set, we will take user's input first.
     /// - If not, the default `us-east-1` will be used.
+    ///
+    /// For AWS S3 endpoints, the real region of bucket will be detected
+    /// from responses if the region is unset or wrong, see
+    /// [`S3Builder::disable_region_detection`].
     pub fn region(&mut self, region: &str) -> &mut Self {
         if !region.is_empty() {
             self.region = Some(region.to_string())
@@ -189,6 +216,13 @@ impl S3Builder {
     }
 
     /// Set role_arn for this backend.
+    ///
+    /// - If `web_identity_token_file` is set, we will call
+    ///   `AssumeRoleWithWebIdentity` with this role.
+    /// - Otherwise, we will call `AssumeRole` with this role by using the
+    ///   credentials loaded from other sources.
+    ///
+    /// Credentials returned by STS will be refreshed before expiry.
     pub fn role_arn(&mut self, v: &str) -> &mut Self {
         if !v.is_empty() {
             self.role_arn = Some(v.to_string())
@@ -206,6 +240,48 @@ impl S3Builder {
         self
     }
 
+    /// Set role_session_name for this backend.
+    ///
+    /// The session name used while assuming role, default to `reqsign`.
+    pub fn role_session_name(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.role_session_name = Some(v.to_string())
+        }
+
+        self
+    }
+
+    /// Set web_identity_token_file for this backend.
+    ///
+    /// The path of OIDC token file used by `AssumeRoleWithWebIdentity`,
+    /// which is required along with `role_arn`.
+    ///
+    /// - If not set, we will try to load it from env `AWS_WEB_IDENTITY_TOKEN_FILE`
+    ///   which is injected by IRSA on EKS.
+    pub fn web_identity_token_file(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.web_identity_token_file = Some(v.to_string())
+        }
+
+        self
+    }
+
+    /// Set profile for this backend.
+    ///
+    /// The profile to load from shared config and credentials files
+    /// (`~/.aws/config` and `~/.aws/credentials`).
+    ///
+    /// - If not set, we will try to load it from env `AWS_PROFILE`,
+    ///   and fallback to `default`.
+    /// - It will be ignored if `disable_config_load` is set.
+    pub fn profile(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.profile = Some(v.to_string())
+        }
+
+        self
+    }
+
     /// Set default storage_class for this backend.
     ///
     /// Available values:
@@ -218,6 +294,9 @@ impl S3Builder {
     /// - `REDUCED_REDUNDANCY`
     /// - `STANDARD`
     /// - `STANDARD_IA`
+    ///
+    /// The storage class set by `write_with(path, bs).storage_class(v)`
+    /// takes precedence over this value.
     pub fn default_storage_class(&mut self, v: &str) -> &mut Self {
         if !v.is_empty() {
             self.default_storage_class = Some(v.to_string())
@@ -226,6 +305,44 @@ impl S3Builder {
         self
     }
 
+    /// Set default canned acl for this backend.
+    ///
+    /// Available values:
+    /// - `private`
+    /// - `public-read`
+    /// - `public-read-write`
+    /// - `authenticated-read`
+    /// - `aws-exec-read`
+    /// - `bucket-owner-read`
+    /// - `bucket-owner-full-control`
+    ///
+    /// The acl set by `write_with(path, bs).acl(v)` takes precedence
+    /// over this value.
+    pub fn default_acl(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.default_acl = Some(v.to_string())
+        }
+
+        self
+    }
+
+    /// Set the additional checksum algorithm for this backend.
+    ///
+    /// Available values:
+    /// - `crc32c`
+    /// - `sha256`
+    ///
+    /// If set, opendal will send the checksum of every uploaded content
+    /// so that s3 can verify the integrity, and the checksum will be
+    /// returned by `stat` in [`Metadata::checksum`].
+    pub fn checksum_algorithm(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.checksum_algorithm = Some(v.to_string())
+        }
+
+        self
+    }
+
     /// Set server_side_encryption for this backend.
     ///
     /// Available values: `AES256`, `aws:kms`.
@@ -313,6 +430,7 @@ impl S3Builder {
     /// # Args
     ///
     /// `v`: MD5 digest of key specified in `server_side_encryption_customer_key`.
+    /// It will be calculated from the key if not set.
     ///
     /// # Note
     ///
@@ -413,7 +531,11 @@ impl S3Builder {
     }
 
     /// Allow anonymous will allow opendal to send request without signing
-    /// when credential is not loaded.
+    /// when credential is not loaded or failed to load.
+    ///
+    /// This is useful for accessing public buckets. Use it along with
+    /// `disable_config_load` and `disable_ec2_metadata` to skip credential
+    /// loading entirely.
     pub fn allow_anonymous(&mut self) -> &mut Self {
         self.allow_anonymous = true;
         self
@@ -429,7 +551,67 @@ impl S3Builder {
         self
     }
 
+    /// Force path style so that opendal will send API requests in path
+    /// style and use the endpoint exactly as given.
+    ///
+    /// By default, opendal will trim the bucket name from endpoint like
+    /// `https://bucket_name.s3.us-east-1.amazonaws.com`. This option
+    /// disables this behavior for S3 compatible services whose host
+    /// happens to start with the bucket name, and it also allows buckets
+    /// with dot(.) in their names.
+    ///
+    /// This option can't be used along with `enable_virtual_host_style`.
+    pub fn force_path_style(&mut self) -> &mut Self {
+        self.force_path_style = true;
+        self
+    }
+
+    /// Enable exact buf write so that opendal will upload every part of
+    /// multipart upload in exactly `write_min_size` bytes except the last one.
+    ///
+    /// Some services like Cloudflare R2 require all non-trailing parts to
+    /// have the same size. This option will be enabled automatically for
+    /// R2 endpoints.
+    pub fn enable_exact_buf_write(&mut self) -> &mut Self {
+        self.enable_exact_buf_write = true;
+        self
+    }
+
+    /// Enable requester pays so that opendal will send `x-amz-request-payer`
+    /// in all requests.
+    ///
+    /// Requester pays buckets will reject requests without this header,
+    /// and the requester will be charged for the requests and data transfer.
+    pub fn enable_requester_pays(&mut self) -> &mut Self {
+        self.enable_requester_pays = true;
+        self
+    }
+
+    /// Disable list objects v2 so that opendal will list objects via
+    /// [ListObjects](https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListObjects.html)
+    /// with marker-based pagination.
+    ///
+    /// Some legacy S3 compatible services like old Ceph RGW don't
+    /// implement `ListObjectsV2`, enable this option for them.
+    pub fn disable_list_objects_v2(&mut self) -> &mut Self {
+        self.disable_list_objects_v2 = true;
+        self
+    }
+
+    /// Disable detecting the region of AWS S3 bucket from responses.
+    ///
+    /// By default, opendal will send requests to the right regional
+    /// endpoint if AWS S3 responds that the bucket lives in another region.
+    pub fn disable_region_detection(&mut self) -> &mut Self {
+        self.disable_region_detection = true;
+        self
+    }
+
     /// Adding a customed credential load for service.
+    ///
+    /// The customed credential load will be tried before the default
+    /// credential chain, which is still used as the fallback. Loaded
+    /// credential will be cached until it expires.
     pub fn customed_credential_load(&mut self, cred: Box<dyn AwsCredentialLoad>) -> &mut Self {
         self.customed_credential_load = Some(cred);
         self
@@ -462,6 +644,82 @@ impl S3Builder {
         true
     }
 
+    /// Check if the server side encryption settings are consistent.
+    ///
+    /// The md5 of customer key will be calculated if not set, so that
+    /// SSE-C could be configured via `from_map` with key only.
+    fn check_server_side_encryption(&mut self) -> Result<()> {
+        let err = |msg: &str| {
+            Error::new(ErrorKind::ConfigInvalid, msg)
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::S3)
+        };
+
+        if self.server_side_encryption_aws_kms_key_id.is_some()
+            && self.server_side_encryption.as_deref() != Some("aws:kms")
+        {
+            return Err(err(
+                "server_side_encryption_aws_kms_key_id requires server_side_encryption to be aws:kms",
+            ));
+        }
+
+        let key = match &self.server_side_encryption_customer_key {
+            Some(key) => key,
+            None => {
+                if self.server_side_encryption_customer_algorithm.is_some()
+                    || self.server_side_encryption_customer_key_md5.is_some()
+                {
+                    return Err(err("server_side_encryption_customer_key is empty"));
+                }
+                return Ok(());
+            }
+        };
+
+        // SSE-C can't be used along with SSE-S3 or SSE-KMS.
+        if self.server_side_encryption.is_some() {
+            return Err(err(
+                "server_side_encryption can't be used along with server_side_encryption_customer_key",
+            ));
+        }
+        if self.server_side_encryption_customer_algorithm.is_none() {
+            return Err(err("server_side_encryption_customer_algorithm is empty"));
+        }
+        if self.server_side_encryption_customer_key_md5.is_none() {
+            let key = BASE64_STANDARD.decode(key).map_err(|e| {
+                err("server_side_encryption_customer_key is not valid base64").set_source(e)
+            })?;
+            self.server_side_encryption_customer_key_md5 =
+                Some(BASE64_STANDARD.encode(Md5::digest(key).as_slice()));
+        }
+
+        Ok(())
+    }
+
+    /// Check if the endpoint is a Cloudflare R2 endpoint.
+    fn is_r2_endpoint(&self) -> bool {
+        match &self.endpoint {
+            Some(endpoint) => {
+                let host = endpoint
+                    .trim_start_matches("https://")
+                    .trim_start_matches("http://");
+                let host = host.split('/').next().unwrap_or_default();
+
+                host.ends_with(R2_ENDPOINT_SUFFIX)
+            }
+            None => false,
+        }
+    }
+
+    /// Build the default credential chain of aws.
+    fn build_default_loader(&self, client: &HttpClient, cfg: AwsConfig) -> AwsDefaultLoader {
+        let loader = AwsDefaultLoader::new(client.client(), cfg);
+        if self.disable_ec2_metadata {
+            loader.with_disable_ec2_metadata()
+        } else {
+            loader
+        }
+    }
+
     /// Build endpoint with given region.
     fn build_endpoint(&self, region: &str) -> String {
         let bucket = {
@@ -482,8 +740,11 @@ impl S3Builder {
             None => "https://s3.amazonaws.com".to_string(),
         };
 
-        // If endpoint contains bucket name, we should trim them.
-        endpoint = endpoint.replace(&format!("//{bucket}."), "//");
+        // If endpoint contains bucket name, we should trim them unless
+        // users want to use the endpoint as it.
+        if !self.force_path_style {
+            endpoint = endpoint.replacen(&format!("//{bucket}."), "//", 1);
+        }
 
         // Update with endpoint templates.
         endpoint = if let Some(template) = ENDPOINT_TEMPLATES.get(endpoint.as_str()) {
@@ -496,7 +757,7 @@ impl S3Builder {
 
         // Apply virtual host style.
         if self.enable_virtual_host_style {
-            endpoint = endpoint.replace("//", &format!("//{bucket}."))
+            endpoint = endpoint.replacen("//", &format!("//{bucket}."), 1)
         } else {
             write!(endpoint, "/{bucket}").expect("write into string must succeed");
         };
@@ -506,11 +767,28 @@ impl S3Builder {
 
     /// set the minimum size of unsized write, it should be greater than 5 MB.
     /// Reference: [Amazon S3 multipart upload limits](https://docs.aws.amazon.com/AmazonS3/latest/userguide/qfacts.html)
+    ///
+    /// The part size of unsized write will grow to 1/1000 of the bytes
+    /// written so far, so that uploads can go beyond `10000 * write_min_size`
+    /// bytes. The part size won't grow if `enable_exact_buf_write` is set.
     pub fn write_min_size(&mut self, write_min_size: usize) -> &mut Self {
         self.write_min_size = Some(write_min_size);
 
         self
     }
+
+    /// Set the number of parts that could be uploaded concurrently in
+    /// multipart upload, default to 1.
+    ///
+    /// Higher concurrency could speed up uploading to services with high
+    /// latency like cross-region s3, but opendal will buffer at most
+    /// `write_concurrency * write_min_size` bytes in memory for every writer.
+    pub fn write_concurrency(&mut self, write_concurrency: usize) -> &mut Self {
+        self.write_concurrency = Some(write_concurrency);
+
+        self
+    }
+
     /// Set maximum batch operations of this backend.
     pub fn batch_max_operations(&mut self, batch_max_operations: usize) -> &mut Self {
         self.batch_max_operations = Some(batch_max_operations);
@@ -536,6 +814,11 @@ impl Builder for S3Builder {
         map.get("security_token").map(|v| builder.security_token(v));
         map.get("role_arn").map(|v| builder.role_arn(v));
         map.get("external_id").map(|v| builder.external_id(v));
+        map.get("role_session_name")
+            .map(|v| builder.role_session_name(v));
+        map.get("web_identity_token_file")
+            .map(|v| builder.web_identity_token_file(v));
+        map.get("profile").map(|v| builder.profile(v));
         map.get("server_side_encryption")
             .map(|v| builder.server_side_encryption(v));
         map.get("server_side_encryption_aws_kms_key_id")
@@ -555,13 +838,34 @@ impl Builder for S3Builder {
         map.get("enable_virtual_host_style")
             .filter(|v| *v == "on" || *v == "true")
             .map(|_| builder.enable_virtual_host_style());
+        map.get("force_path_style")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.force_path_style());
+        map.get("enable_exact_buf_write")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.enable_exact_buf_write());
+        map.get("enable_requester_pays")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.enable_requester_pays());
+        map.get("disable_list_objects_v2")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.disable_list_objects_v2());
+        map.get("disable_region_detection")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.disable_region_detection());
         map.get("allow_anonymous")
             .filter(|v| *v == "on" || *v == "true")
             .map(|_| builder.allow_anonymous());
         map.get("default_storage_class")
             .map(|v: &String| builder.default_storage_class(v));
+        map.get("default_acl")
+            .map(|v: &String| builder.default_acl(v));
+        map.get("checksum_algorithm")
+            .map(|v: &String| builder.checksum_algorithm(v));
         map.get("write_min_size")
             .map(|v| builder.write_min_size(v.parse().expect("input must be a number")));
+        map.get("write_concurrency")
+            .map(|v| builder.write_concurrency(v.parse().expect("input must be a number")));
         map.get("batch_max_operations")
             .map(|v| builder.batch_max_operations(v.parse().expect("input must be a number")));
 
@@ -574,6 +878,17 @@ impl Builder for S3Builder {
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", &root);
 
+        self.check_server_side_encryption()?;
+
+        if self.enable_virtual_host_style && self.force_path_style {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "enable_virtual_host_style can't be used along with force_path_style",
+            )
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::S3));
+        }
+
         // Handle bucket name.
         let bucket = if self.is_bucket_valid() {
             Ok(&self.bucket)
@@ -592,6 +907,26 @@ impl Builder for S3Builder {
             ),
         };
 
+        let default_acl = match &self.default_acl {
+            None => None,
+            Some(v) => Some(build_header_value(v).map_err(|err| err.with_context("key", "acl"))?),
+        };
+
+        let checksum_algorithm = match self.checksum_algorithm.as_deref() {
+            None => None,
+            Some(v) if v.eq_ignore_ascii_case("crc32c") => Some(ChecksumAlgorithm::Crc32c),
+            Some(v) if v.eq_ignore_ascii_case("sha256") => Some(ChecksumAlgorithm::Sha256),
+            Some(v) => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "checksum_algorithm is not supported",
+                )
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::S3)
+                .with_context("checksum_algorithm", v))
+            }
+        };
+
         let server_side_encryption = match &self.server_side_encryption {
             None => None,
             Some(v) => Some(
@@ -643,7 +978,14 @@ impl Builder for S3Builder {
 
         let mut cfg = AwsConfig::default();
         if !self.disable_config_load {
-            cfg = cfg.from_profile();
+            if let Some(v) = self.profile.take() {
+                cfg.profile = v;
+            }
+            // Profiles are not available on wasm32 since there is no home dir.
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                cfg = cfg.from_profile();
+            }
             cfg = cfg.from_env();
         }
 
@@ -666,20 +1008,26 @@ impl Builder for S3Builder {
         if let Some(v) = self.external_id.take() {
             cfg.external_id = Some(v)
         }
+        if let Some(v) = self.role_session_name.take() {
+            cfg.role_session_name = v
+        }
+        if let Some(v) = self.web_identity_token_file.take() {
+            cfg.web_identity_token_file = Some(v)
+        }
+
+        let is_r2 = self.is_r2_endpoint();
 
         if cfg.region.is_none() {
-            // AWS S3 requires region to be set.
-            if self.endpoint.is_none()
-                || self.endpoint.as_deref() == Some("https://s3.amazonaws.com")
-            {
-                return Err(Error::new(ErrorKind::ConfigInvalid, "region is missing")
-                    .with_operation("Builder::build")
-                    .with_context("service", Scheme::S3));
+            // Cloudflare R2 only accepts `auto` as region.
+            if is_r2 {
+                cfg.region = Some("auto".to_string());
             }
+        }
 
-            // For other compatible services, if we don't know region
-            // after loading from builder and env, we can use `us-east-1`
-            // as default.
+        if cfg.region.is_none() {
+            // If we don't know region after loading from builder and env,
+            // we can use `us-east-1` as default. The real region of AWS S3
+            // bucket will be detected from responses.
             cfg.region = Some("us-east-1".to_string());
         }
 
@@ -690,13 +1038,54 @@ impl Builder for S3Builder {
         let endpoint = self.build_endpoint(&region);
         debug!("backend use endpoint: {endpoint}");
 
-        let mut loader = AwsLoader::new(client.client(), cfg);
-        if self.disable_ec2_metadata {
-            loader = loader.with_disable_ec2_metadata();
-        }
+        // Only AWS S3 endpoints could be switched to other regions.
+        let region_detector = if self.disable_region_detection || is_r2 {
+            None
+        } else {
+            let template =
+                S3RegionDetector::build_template(&self.build_endpoint("{region}"), &region);
+            S3RegionDetector::new(&endpoint, &region, &template)
+        };
+
+        // Container credentials are only used while there are no static
+        // keys or roles, which follows the order of aws default credential chain.
+        let load_container_credentials =
+            !self.disable_config_load && cfg.access_key_id.is_none() && cfg.role_arn.is_none();
+
+        // Customed credential load will be tried first, and the default
+        // credential chain is always used as the fallback.
+        let mut loaders: Vec<Box<dyn AwsCredentialLoad>> = Vec::new();
         if let Some(v) = self.customed_credential_load.take() {
-            loader = loader.with_customed_credential_loader(v);
+            loaders.push(v);
+        }
+        if let Some(v) = load_container_credentials
+            .then(EcsCredentialLoad::from_env)
+            .flatten()
+        {
+            debug!("backend use container credentials: {v:?}");
+            loaders.push(Box::new(v));
+        }
+        // Default credential chain doesn't assume role, so we assume role
+        // with it as the source credential while no static keys or web
+        // identity token are provided.
+        if cfg.role_arn.is_some()
+            && cfg.access_key_id.is_none()
+            && cfg.web_identity_token_file.is_none()
+        {
+            let source = self.build_default_loader(&client, cfg.clone());
+            let loader = AwsAssumeRoleLoader::new(client.client(), cfg.clone(), Box::new(source))
+                .map_err(|err| {
+                Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "The assume role loader is misconfigured",
+                )
+                .with_context("service", Scheme::S3)
+                .set_source(err)
+            })?;
+            loaders.push(Box::new(loader));
         }
+        loaders.push(Box::new(self.build_default_loader(&client, cfg)));
+        let loader: Box<dyn AwsCredentialLoad> = Box::new(CredentialChain::new(loaders));
 
         let signer = AwsV4Signer::new("s3", &region);
         let write_min_size = self.write_min_size.unwrap_or(DEFAULT_WRITE_MIN_SIZE);
@@ -707,9 +1096,21 @@ impl Builder for S3Builder {
             )
             .with_context("service", Scheme::S3));
         }
-        let batch_max_operations = self
-            .batch_max_operations
-            .unwrap_or(DEFAULT_BATCH_MAX_OPERATIONS);
+        let write_concurrency = self.write_concurrency.unwrap_or(DEFAULT_WRITE_CONCURRENCY);
+        if write_concurrency == 0 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The write concurrency is misconfigured",
+            )
+            .with_context("service", Scheme::S3));
+        }
+        let batch_max_operations = self.batch_max_operations.unwrap_or(if is_r2 {
+            R2_BATCH_MAX_OPERATIONS
+        } else {
+            DEFAULT_BATCH_MAX_OPERATIONS
+        });
+        // R2 requires all non-trailing parts to have the same size.
+        let enable_exact_buf_write = self.enable_exact_buf_write || is_r2;
         debug!("backend build finished");
         Ok(S3Backend {
             core: Arc::new(S3Core {
@@ -722,11 +1123,18 @@ impl Builder for S3Builder {
                 server_side_encryption_customer_key,
                 server_side_encryption_customer_key_md5,
                 default_storage_class,
+                default_acl,
+                checksum_algorithm,
                 allow_anonymous: self.allow_anonymous,
+                enable_requester_pays: self.enable_requester_pays,
+                disable_list_objects_v2: self.disable_list_objects_v2,
                 signer,
+                region_detector,
                 loader,
                 client,
                 write_min_size,
+                write_concurrency,
+                enable_exact_buf_write,
                 batch_max_operations,
             }),
         })
@@ -769,11 +1177,17 @@ impl Accessor for S3Backend {
 
                 write: true,
                 write_with_cache_control: true,
+                write_with_storage_class: true,
+                write_with_acl: true,
                 write_with_content_type: true,
                 write_without_content_length: true,
                 create_dir: true,
                 delete: true,
+                delete_with_version: true,
                 copy: true,
+                copy_with_content_type: true,
+                copy_with_user_metadata: true,
+                copy_with_tags: true,
 
                 list: true,
                 list_with_limit: true,
@@ -789,6 +1203,9 @@ impl Accessor for S3Backend {
                 batch: true,
                 batch_max_operations: Some(self.core.batch_max_operations),
 
+                restore: true,
+                select: true,
+
                 ..Default::default()
             });
 
@@ -796,9 +1213,16 @@ impl Accessor for S3Backend {
     }
 
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
-        let mut req =
-            self.core
-                .s3_put_object_request(path, Some(0), None, None, None, AsyncBody::Empty)?;
+        let mut req = self.core.s3_put_object_request(
+            path,
+            Some(0),
+            None,
+            None,
+            None,
+            None,
+            None,
+            AsyncBody::Empty,
+        )?;
 
         self.core.sign(&mut req).await?;
 
@@ -845,8 +1269,23 @@ impl Accessor for S3Backend {
         ))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.s3_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        // S3 drops all metadata of source object while replacing metadata,
+        // stat source object to carry them over.
+        let source = if args.is_metadata_replaced() {
+            let resp = self.core.s3_head_object(from, None, None).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+            Some(parse_into_metadata(from, resp.headers())?)
+        } else {
+            None
+        };
+
+        let resp = self
+            .core
+            .s3_copy_object(from, to, &args, source.as_ref())
+            .await?;
 
         let status = resp.status();
 
@@ -876,7 +1315,39 @@ impl Accessor for S3Backend {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::OK => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+
+                // S3 will not return storage class for objects in STANDARD.
+                let storage_class = resp
+                    .headers()
+                    .get(constants::X_AMZ_STORAGE_CLASS)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("STANDARD");
+                meta.set_storage_class(storage_class);
+
+                // S3 will only return restore status for archived objects
+                // that have been requested to restore.
+                if let Some(v) = resp
+                    .headers()
+                    .get(constants::X_AMZ_RESTORE)
+                    .and_then(|v| v.to_str().ok())
+                {
+                    meta.set_restore_status(v);
+                }
+
+                // S3 will only return checksum if the object is uploaded
+                // with the same checksum algorithm.
+                if let Some(v) = self.core.checksum_algorithm.and_then(|algo| {
+                    resp.headers()
+                        .get(algo.header_name())
+                        .and_then(|v| v.to_str().ok())
+                }) {
+                    meta.set_checksum(v);
+                }
+
+                Ok(RpStat::new(meta))
+            }
             StatusCode::NOT_FOUND if path.ends_with('/') => {
                 Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
             }
@@ -884,8 +1355,8 @@ impl Accessor for S3Backend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.s3_delete_object(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.s3_delete_object(path, args.version()).await?;
 
         let status = resp.status();
 
@@ -908,6 +1379,39 @@ impl Accessor for S3Backend {
         ))
     }
 
+    async fn restore(&self, path: &str, args: OpRestore) -> Result<RpRestore> {
+        let resp = self
+            .core
+            .s3_restore_object(path, args.days(), args.tier())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            // S3 returns `202 Accepted` if the restore is initiated and
+            // `200 OK` if the object has been restored.
+            StatusCode::OK | StatusCode::ACCEPTED => {
+                resp.into_body().consume().await?;
+                Ok(RpRestore::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        let resp = self.core.s3_select_object_content(path, &args).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => Ok((
+                RpSelect::default(),
+                Box::new(S3SelectReader::new(resp.into_body())) as oio::Reader,
+            )),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         // We will not send this request out, just for signing.
         let mut req = match args.operation() {
@@ -923,10 +1427,16 @@ impl Accessor for S3Backend {
                 v.if_none_match(),
                 v.if_match(),
             )?,
-            PresignOperation::Write(_) => {
-                self.core
-                    .s3_put_object_request(path, None, None, None, None, AsyncBody::Empty)?
-            }
+            PresignOperation::Write(_) => self.core.s3_put_object_request(
+                path,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                AsyncBody::Empty,
+            )?,
         };
 
         self.core.sign_query(&mut req, args.expire()).await?;
@@ -1047,5 +1557,89 @@ mod tests {
             let endpoint = b.build_endpoint("us-east-2");
             assert_eq!(endpoint, "https://test.s3.us-east-2.amazonaws.com");
         }
+
+        // Endpoint that starts with bucket name should be kept as it
+        // while forcing path style.
+        let cases = vec![
+            (
+                "test",
+                "http://test.local:9000",
+                "http://test.local:9000/test",
+            ),
+            (
+                "test",
+                "http://127.0.0.1:9000",
+                "http://127.0.0.1:9000/test",
+            ),
+            (
+                "test.xyz",
+                "http://127.0.0.1:9000",
+                "http://127.0.0.1:9000/test.xyz",
+            ),
+        ];
+
+        for (bucket, endpoint, expected) in cases {
+            let mut b = S3Builder::default();
+            b.bucket(bucket);
+            b.endpoint(endpoint);
+            b.force_path_style();
+
+            assert_eq!(b.build_endpoint("us-east-2"), expected);
+        }
+    }
+
+    #[test]
+    fn test_check_server_side_encryption() {
+        let mut b = S3Builder::default();
+        b.server_side_encryption_with_customer_key("AES256", "customer_key".as_bytes());
+        let md5 = b.server_side_encryption_customer_key_md5.clone();
+        assert!(b.check_server_side_encryption().is_ok());
+        assert_eq!(b.server_side_encryption_customer_key_md5, md5);
+
+        // The md5 of customer key should be calculated if not set.
+        let mut b = S3Builder::default();
+        b.server_side_encryption_customer_algorithm("AES256");
+        b.server_side_encryption_customer_key(&BASE64_STANDARD.encode("customer_key"));
+        assert!(b.check_server_side_encryption().is_ok());
+        assert_eq!(b.server_side_encryption_customer_key_md5, md5);
+
+        let mut b = S3Builder::default();
+        b.server_side_encryption_customer_key(&BASE64_STANDARD.encode("customer_key"));
+        assert!(b.check_server_side_encryption().is_err());
+
+        let mut b = S3Builder::default();
+        b.server_side_encryption_with_s3_key();
+        b.server_side_encryption_with_customer_key("AES256", "customer_key".as_bytes());
+        assert!(b.check_server_side_encryption().is_err());
+
+        let mut b = S3Builder::default();
+        b.server_side_encryption_with_customer_managed_kms_key("aws_kms_key_id");
+        assert!(b.check_server_side_encryption().is_ok());
+
+        let mut b = S3Builder::default();
+        b.server_side_encryption_aws_kms_key_id("aws_kms_key_id");
+        assert!(b.check_server_side_encryption().is_err());
+    }
+
+    #[test]
+    fn test_is_r2_endpoint() {
+        let endpoint_cases = vec![
+            (Some("https://account_id.r2.cloudflarestorage.com"), true),
+            (Some("https://account_id.eu.r2.cloudflarestorage.com"), true),
+            (Some("account_id.r2.cloudflarestorage.com/"), true),
+            (Some("https://s3.amazonaws.com"), false),
+            (Some("https://r2.cloudflarestorage.com.example.com"), false),
+            (None, false),
+        ];
+
+        for (endpoint, expected) in endpoint_cases {
+            let mut b = S3Builder::default();
+            b.bucket("test");
+            if let Some(endpoint) = endpoint {
+                b.endpoint(endpoint);
+            }
+
+            assert_eq!(b.is_r2_endpoint(), expected, "endpoint: {endpoint:?}");
+        }
     }
 }
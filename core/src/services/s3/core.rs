@@ -19,8 +19,11 @@ use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Write;
+use std::sync::Arc;
 use std::time::Duration;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use bytes::Bytes;
 use http::header::HeaderName;
 use http::header::CACHE_CONTROL;
@@ -33,16 +36,27 @@ use http::HeaderValue;
 use http::Request;
 use http::Response;
 use reqsign::AwsCredential;
-use reqsign::AwsLoader;
+use reqsign::AwsCredentialLoad;
 use reqsign::AwsV4Signer;
 use serde::Deserialize;
 use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
 
+use super::region::try_clone_request;
+use super::region::S3DetectedRegion;
+use super::region::S3RegionDetector;
+use super::select::SelectObjectContentRequest;
 use crate::raw::*;
 use crate::*;
 
-mod constants {
+pub mod constants {
     pub const X_AMZ_COPY_SOURCE: &str = "x-amz-copy-source";
+    pub const X_AMZ_COPY_SOURCE_IF_MATCH: &str = "x-amz-copy-source-if-match";
+    pub const X_AMZ_METADATA_DIRECTIVE: &str = "x-amz-metadata-directive";
+    pub const X_AMZ_TAGGING_DIRECTIVE: &str = "x-amz-tagging-directive";
+    pub const X_AMZ_TAGGING: &str = "x-amz-tagging";
+    pub const X_AMZ_META_PREFIX: &str = "x-amz-meta-";
 
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION: &str = "x-amz-server-side-encryption";
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
@@ -54,6 +68,14 @@ mod constants {
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID: &str =
         "x-amz-server-side-encryption-aws-kms-key-id";
     pub const X_AMZ_STORAGE_CLASS: &str = "x-amz-storage-class";
+    pub const X_AMZ_ACL: &str = "x-amz-acl";
+    pub const X_AMZ_RESTORE: &str = "x-amz-restore";
+    pub const X_AMZ_REQUEST_PAYER: &str = "x-amz-request-payer";
+    pub const X_AMZ_CHECKSUM_ALGORITHM: &str = "x-amz-checksum-algorithm";
+    pub const X_AMZ_SDK_CHECKSUM_ALGORITHM: &str = "x-amz-sdk-checksum-algorithm";
+    pub const X_AMZ_CHECKSUM_MODE: &str = "x-amz-checksum-mode";
+    pub const X_AMZ_CHECKSUM_CRC32C: &str = "x-amz-checksum-crc32c";
+    pub const X_AMZ_CHECKSUM_SHA256: &str = "x-amz-checksum-sha256";
 
     pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
         "x-amz-copy-source-server-side-encryption-customer-algorithm";
@@ -76,12 +98,23 @@ pub struct S3Core {
     pub server_side_encryption_customer_key: Option<HeaderValue>,
     pub server_side_encryption_customer_key_md5: Option<HeaderValue>,
     pub default_storage_class: Option<HeaderValue>,
+    pub default_acl: Option<HeaderValue>,
+    /// Additional checksum to send along with the uploaded content.
+    pub checksum_algorithm: Option<ChecksumAlgorithm>,
     pub allow_anonymous: bool,
+    /// Declare that the requester will pay for the requests and data
+    /// transfer of requester pays buckets.
+    pub enable_requester_pays: bool,
+    /// List objects via ListObjects (v1) instead of ListObjectsV2.
+    pub disable_list_objects_v2: bool,
 
     pub signer: AwsV4Signer,
-    pub loader: AwsLoader,
+    pub region_detector: Option<S3RegionDetector>,
+    pub loader: Box<dyn AwsCredentialLoad>,
     pub client: HttpClient,
     pub write_min_size: usize,
+    pub write_concurrency: usize,
+    pub enable_exact_buf_write: bool,
     pub batch_max_operations: usize,
 }
 
@@ -98,11 +131,17 @@ impl Debug for S3Core {
 impl S3Core {
     /// If credential is not found, we will not sign the request.
     async fn load_credential(&self) -> Result<Option<AwsCredential>> {
-        let cred = self
-            .loader
-            .load()
-            .await
-            .map_err(new_request_credential_error)?;
+        let fut = self.loader.load_credential(self.client.client());
+        // Credential loaders are not Send on wasm32.
+        #[cfg(target_arch = "wasm32")]
+        let fut = send_wrapper::SendWrapper::new(fut);
+        let cred = match fut.await {
+            Ok(cred) => cred,
+            // If allow_anonymous has been set, we will fallback to send
+            // request without signing instead of failing.
+            Err(_) if self.allow_anonymous => return Ok(None),
+            Err(err) => return Err(new_request_credential_error(err)),
+        };
 
         if let Some(cred) = cred {
             Ok(Some(cred))
@@ -120,36 +159,103 @@ impl S3Core {
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        // Requester pays header must be signed, so we insert it here to
+        // cover all requests.
+        if self.enable_requester_pays {
+            req.headers_mut().insert(
+                HeaderName::from_static(constants::X_AMZ_REQUEST_PAYER),
+                HeaderValue::from_static("requester"),
+            );
+        }
+
+        // Send requests to the detected region directly.
+        let detected = self.rewrite_to_detected_region(req)?;
+
         let cred = if let Some(cred) = self.load_credential().await? {
             cred
         } else {
             return Ok(());
         };
 
-        self.signer.sign(req, &cred).map_err(new_request_sign_error)
+        let signer = detected.as_ref().map(|v| &v.signer).unwrap_or(&self.signer);
+        signer.sign(req, &cred).map_err(new_request_sign_error)
     }
 
     pub async fn sign_query<T>(&self, req: &mut Request<T>, duration: Duration) -> Result<()> {
+        // Presigned requests could be sent by others without any header,
+        // so requester pays should be declared in query instead.
+        if self.enable_requester_pays {
+            let uri = req.uri().to_string();
+            let sep = if uri.contains('?') { '&' } else { '?' };
+            *req.uri_mut() = format!("{uri}{sep}{}=requester", constants::X_AMZ_REQUEST_PAYER)
+                .parse()
+                .map_err(|err: http::uri::InvalidUri| new_request_build_error(err.into()))?;
+        }
+
+        let detected = self.rewrite_to_detected_region(req)?;
+
         let cred = if let Some(cred) = self.load_credential().await? {
             cred
         } else {
             return Ok(());
         };
 
-        self.signer
+        let signer = detected.as_ref().map(|v| &v.signer).unwrap_or(&self.signer);
+        signer
             .sign_query(req, duration, &cred)
             .map_err(new_request_sign_error)
     }
 
-    #[inline]
+    fn rewrite_to_detected_region<T>(
+        &self,
+        req: &mut Request<T>,
+    ) -> Result<Option<Arc<S3DetectedRegion>>> {
+        let detector = match &self.region_detector {
+            Some(detector) => detector,
+            None => return Ok(None),
+        };
+
+        match detector.detected() {
+            Some(detected) => {
+                detector.rewrite(req, &detected)?;
+                Ok(Some(detected))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Send the request, it will be sent again to the right region if
+    /// the region of bucket is detected to be different.
     pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        let detector = match &self.region_detector {
+            Some(detector) => detector,
+            None => return self.client.send(req).await,
+        };
+
+        let retry_req = try_clone_request(&req);
+        let resp = self.client.send(req).await?;
+
+        let detected = match detector.detect(&resp) {
+            Some(detected) => detected,
+            None => return Ok(resp),
+        };
+        // Requests with stream body can't be sent again, but the following
+        // requests will be sent to the right region.
+        let mut req = match retry_req {
+            Some(req) => req,
+            None => return Ok(resp),
+        };
+        resp.into_body().consume().await?;
+
+        detector.rewrite(&mut req, &detected)?;
+        self.sign(&mut req).await?;
         self.client.send(req).await
     }
 
     /// # Note
     ///
     /// header like X_AMZ_SERVER_SIDE_ENCRYPTION doesn't need to set while
-    /// get or stat.
+    /// get or stat, and will be rejected by upload part.
     pub fn insert_sse_headers(
         &self,
         mut req: http::request::Builder,
@@ -209,6 +315,42 @@ impl S3Core {
 }
 
 impl S3Core {
+    /// Set the additional checksum header of given body.
+    ///
+    /// Only bytes body will be checksummed, streaming body and empty body
+    /// used by presign will be sent as is.
+    pub fn insert_checksum_header(
+        &self,
+        mut req: http::request::Builder,
+        body: &AsyncBody,
+    ) -> http::request::Builder {
+        if let (Some(algo), AsyncBody::Bytes(bs)) = (self.checksum_algorithm, body) {
+            req = self.insert_computed_checksum_header(req, &algo.checksum(bs));
+        }
+
+        req
+    }
+
+    /// Set the additional checksum header with checksum that has been
+    /// computed while writing, so that we don't need to read the body again.
+    pub fn insert_computed_checksum_header(
+        &self,
+        mut req: http::request::Builder,
+        checksum: &str,
+    ) -> http::request::Builder {
+        if let Some(algo) = self.checksum_algorithm {
+            // Declare the algorithm like aws sdk does, some s3 compatible
+            // services depend on it to find out the checksum header.
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_SDK_CHECKSUM_ALGORITHM),
+                algo.as_str(),
+            );
+            req = req.header(HeaderName::from_static(algo.header_name()), checksum);
+        }
+
+        req
+    }
+
     pub fn s3_head_object_request(
         &self,
         path: &str,
@@ -318,6 +460,7 @@ impl S3Core {
         self.send(req).await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn s3_put_object_request(
         &self,
         path: &str,
@@ -325,6 +468,8 @@ impl S3Core {
         content_type: Option<&str>,
         content_disposition: Option<&str>,
         cache_control: Option<&str>,
+        storage_class: Option<&str>,
+        acl: Option<&str>,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
@@ -349,13 +494,29 @@ impl S3Core {
             req = req.header(CACHE_CONTROL, cache_control)
         }
 
-        // Set storage class header
-        if let Some(v) = &self.default_storage_class {
+        // Set storage class header, the storage class of this write
+        // takes precedence over the default one.
+        if let Some(v) = storage_class {
             req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
+        } else if let Some(v) = &self.default_storage_class {
+            req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
+        }
+
+        // Set canned acl header, the acl of this write takes precedence
+        // over the default one.
+        if let Some(v) = acl {
+            req = req.header(HeaderName::from_static(constants::X_AMZ_ACL), v);
+        } else if let Some(v) = &self.default_acl {
+            req = req.header(HeaderName::from_static(constants::X_AMZ_ACL), v);
         }
 
         // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        //
+        // UploadPart only accepts SSE-C headers, the encryption settings of
+        // SSE-S3 and SSE-KMS have been set while initiating.
+        req = self.insert_sse_headers(req, false);
+
+        req = self.insert_checksum_header(req, &body);
 
         // Set body
         let req = req.body(body).map_err(new_request_build_error)?;
@@ -371,15 +532,31 @@ impl S3Core {
     ) -> Result<Response<IncomingAsyncBody>> {
         let mut req = self.s3_head_object_request(path, if_none_match, if_match)?;
 
+        // Ask s3 to return the additional checksum of this object.
+        if self.checksum_algorithm.is_some() {
+            req.headers_mut().insert(
+                HeaderName::from_static(constants::X_AMZ_CHECKSUM_MODE),
+                HeaderValue::from_static("ENABLED"),
+            );
+        }
+
         self.sign(&mut req).await?;
 
         self.send(req).await
     }
 
-    pub async fn s3_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    pub async fn s3_delete_object(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        if let Some(version) = version {
+            write!(url, "?versionId={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::delete(&url)
             .body(AsyncBody::Empty)
@@ -390,10 +567,17 @@ impl S3Core {
         self.send(req).await
     }
 
+    /// Copy object from `from` to `to`.
+    ///
+    /// `source_meta` is the metadata of source object, which is required while
+    /// replacing metadata. S3 doesn't keep any metadata of source object
+    /// with `REPLACE` directive, so we need to carry them over.
     pub async fn s3_copy_object(
         &self,
         from: &str,
         to: &str,
+        args: &OpCopy,
+        source_meta: Option<&Metadata>,
     ) -> Result<Response<IncomingAsyncBody>> {
         let from = build_abs_path(&self.root, from);
         let to = build_abs_path(&self.root, to);
@@ -442,6 +626,49 @@ impl S3Core {
             )
         }
 
+        // Replace the metadata of target object if required, otherwise
+        // S3 will copy all metadata from source object.
+        if args.is_metadata_replaced() {
+            req = req.header(constants::X_AMZ_METADATA_DIRECTIVE, "REPLACE");
+
+            if let Some(v) = args
+                .content_type()
+                .or_else(|| source_meta.and_then(|v| v.content_type()))
+            {
+                req = req.header(CONTENT_TYPE, v);
+            }
+            if let Some(meta) = source_meta {
+                if let Some(v) = meta.cache_control() {
+                    req = req.header(CACHE_CONTROL, v);
+                }
+                if let Some(v) = meta.content_disposition() {
+                    req = req.header(CONTENT_DISPOSITION, v);
+                }
+                // Make sure the source object is not changed since stat.
+                if let Some(v) = meta.etag() {
+                    req = req.header(constants::X_AMZ_COPY_SOURCE_IF_MATCH, v);
+                }
+            }
+
+            if let Some(user_metadata) = args.user_metadata() {
+                for (k, v) in user_metadata {
+                    req = req.header(format!("{}{k}", constants::X_AMZ_META_PREFIX), v);
+                }
+            }
+        }
+
+        if let Some(tags) = args.tags() {
+            let tagging = tags
+                .iter()
+                .map(|(k, v)| format!("{}={}", percent_encode_path(k), percent_encode_path(v)))
+                .collect::<Vec<_>>()
+                .join("&");
+
+            req = req
+                .header(constants::X_AMZ_TAGGING_DIRECTIVE, "REPLACE")
+                .header(constants::X_AMZ_TAGGING, tagging);
+        }
+
         let mut req = req
             .header(constants::X_AMZ_COPY_SOURCE, percent_encode_path(&source))
             .body(AsyncBody::Empty)
@@ -500,12 +727,54 @@ impl S3Core {
         self.send(req).await
     }
 
+    /// List objects via [ListObjects](https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListObjects.html)
+    /// which uses `marker` instead of `continuation-token` for pagination.
+    ///
+    /// `start_after` will be used as `marker` if `marker` is empty since
+    /// they have the same semantics.
+    pub async fn s3_list_objects_v1(
+        &self,
+        path: &str,
+        marker: &str,
+        delimiter: &str,
+        limit: Option<usize>,
+        start_after: Option<String>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!("{}?prefix={}", self.endpoint, percent_encode_path(&p));
+        if !delimiter.is_empty() {
+            write!(url, "&delimiter={delimiter}").expect("write into string must succeed");
+        }
+        if let Some(limit) = limit {
+            write!(url, "&max-keys={limit}").expect("write into string must succeed");
+        }
+        if !marker.is_empty() {
+            write!(url, "&marker={}", percent_encode_path(marker))
+                .expect("write into string must succeed");
+        } else if let Some(start_after) = start_after {
+            let start_after = build_abs_path(&self.root, &start_after);
+            write!(url, "&marker={}", percent_encode_path(&start_after))
+                .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
     pub async fn s3_initiate_multipart_upload(
         &self,
         path: &str,
         content_type: Option<&str>,
         content_disposition: Option<&str>,
         cache_control: Option<&str>,
+        storage_class: Option<&str>,
+        acl: Option<&str>,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
@@ -525,9 +794,29 @@ impl S3Core {
             req = req.header(CACHE_CONTROL, cache_control)
         }
 
-        // Set storage class header
-        if let Some(v) = &self.default_storage_class {
+        // Set storage class header, the storage class of this write
+        // takes precedence over the default one.
+        if let Some(v) = storage_class {
             req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
+        } else if let Some(v) = &self.default_storage_class {
+            req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
+        }
+
+        // Set canned acl header, the acl of this write takes precedence
+        // over the default one.
+        if let Some(v) = acl {
+            req = req.header(HeaderName::from_static(constants::X_AMZ_ACL), v);
+        } else if let Some(v) = &self.default_acl {
+            req = req.header(HeaderName::from_static(constants::X_AMZ_ACL), v);
+        }
+
+        // Declare the checksum algorithm so that every part must carry
+        // the checksum.
+        if let Some(algo) = self.checksum_algorithm {
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_CHECKSUM_ALGORITHM),
+                algo.as_str(),
+            );
         }
 
         // Set SSE headers.
@@ -548,6 +837,7 @@ impl S3Core {
         upload_id: &str,
         part_number: usize,
         size: Option<u64>,
+        checksum: Option<&str>,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
@@ -569,6 +859,11 @@ impl S3Core {
         // Set SSE headers.
         req = self.insert_sse_headers(req, true);
 
+        req = match checksum {
+            Some(checksum) => self.insert_computed_checksum_header(req, checksum),
+            None => self.insert_checksum_header(req, &body),
+        };
+
         // Set body
         let req = req.body(body).map_err(new_request_build_error)?;
 
@@ -593,7 +888,9 @@ impl S3Core {
         let req = Request::post(&url);
 
         // Set SSE headers.
-        let req = self.insert_sse_headers(req, true);
+        //
+        // CompleteMultipartUpload only accepts SSE-C headers.
+        let req = self.insert_sse_headers(req, false);
 
         let content = quick_xml::se::to_string(&CompleteMultipartUploadRequest {
             part: parts.to_vec(),
@@ -613,6 +910,81 @@ impl S3Core {
         self.send(req).await
     }
 
+    /// Initiate the restoration of an archived object.
+    ///
+    /// Reference: [RestoreObject](https://docs.aws.amazon.com/AmazonS3/latest/API/API_RestoreObject.html)
+    pub async fn s3_restore_object(
+        &self,
+        path: &str,
+        days: Option<u32>,
+        tier: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?restore", self.endpoint, percent_encode_path(&p));
+
+        let req = Request::post(&url);
+
+        let content = quick_xml::se::to_string(&RestoreRequest {
+            days,
+            glacier_job_parameters: tier.map(|v| GlacierJobParameters {
+                tier: v.to_string(),
+            }),
+        })
+        .map_err(new_xml_deserialize_error)?;
+        // Make sure content length has been set to avoid post with chunked encoding.
+        let req = req.header(CONTENT_LENGTH, content.len());
+        // Set content-type to `application/xml` to avoid mixed with form post.
+        let req = req.header(CONTENT_TYPE, "application/xml");
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    /// Select the records matched by given expression.
+    ///
+    /// Reference: [SelectObjectContent](https://docs.aws.amazon.com/AmazonS3/latest/API/API_SelectObjectContent.html)
+    pub async fn s3_select_object_content(
+        &self,
+        path: &str,
+        args: &OpSelect,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?select&select-type=2",
+            self.endpoint,
+            percent_encode_path(&p)
+        );
+
+        let req = Request::post(&url);
+
+        // Set SSE headers.
+        //
+        // SelectObjectContent only accepts SSE-C headers.
+        let req = self.insert_sse_headers(req, false);
+
+        let content = quick_xml::se::to_string(&SelectObjectContentRequest::new(args)?)
+            .map_err(new_xml_deserialize_error)?;
+        // Make sure content length has been set to avoid post with chunked encoding.
+        let req = req.header(CONTENT_LENGTH, content.len());
+        // Set content-type to `application/xml` to avoid mixed with form post.
+        let req = req.header(CONTENT_TYPE, "application/xml");
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
     /// Abort an on-going multipart upload.
     pub async fn s3_abort_multipart_upload(
         &self,
@@ -684,6 +1056,22 @@ pub struct CompleteMultipartUploadRequest {
     pub part: Vec<CompleteMultipartUploadRequestPart>,
 }
 
+/// Request of RestoreObject
+#[derive(Default, Debug, Serialize)]
+#[serde(rename = "RestoreRequest", rename_all = "PascalCase")]
+pub struct RestoreRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glacier_job_parameters: Option<GlacierJobParameters>,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GlacierJobParameters {
+    pub tier: String,
+}
+
 #[derive(Clone, Default, Debug, Serialize)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct CompleteMultipartUploadRequestPart {
@@ -719,6 +1107,77 @@ pub struct CompleteMultipartUploadRequestPart {
     /// ref: <https://github.com/tafia/quick-xml/issues/362>
     #[serde(rename = "ETag")]
     pub etag: String,
+    #[serde(rename = "ChecksumCRC32C", skip_serializing_if = "Option::is_none")]
+    pub checksum_crc32c: Option<String>,
+    #[serde(rename = "ChecksumSHA256", skip_serializing_if = "Option::is_none")]
+    pub checksum_sha256: Option<String>,
+}
+
+/// Additional checksum algorithms supported by s3.
+///
+/// Reference: [Checking object integrity](https://docs.aws.amazon.com/AmazonS3/latest/userguide/checking-object-integrity.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    Crc32c,
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    /// The value used in `x-amz-checksum-algorithm`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Crc32c => "CRC32C",
+            ChecksumAlgorithm::Sha256 => "SHA256",
+        }
+    }
+
+    /// The header that carries the checksum.
+    pub fn header_name(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Crc32c => constants::X_AMZ_CHECKSUM_CRC32C,
+            ChecksumAlgorithm::Sha256 => constants::X_AMZ_CHECKSUM_SHA256,
+        }
+    }
+
+    /// Calculate the base64 encoded checksum of given content.
+    pub fn checksum(&self, bs: &[u8]) -> String {
+        let mut hasher = self.hasher();
+        hasher.update(bs);
+        hasher.finish()
+    }
+
+    /// Create a hasher to calculate the checksum incrementally.
+    pub fn hasher(&self) -> ChecksumHasher {
+        match self {
+            ChecksumAlgorithm::Crc32c => ChecksumHasher::Crc32c(0),
+            ChecksumAlgorithm::Sha256 => ChecksumHasher::Sha256(Sha256::new()),
+        }
+    }
+}
+
+/// ChecksumHasher calculates the checksum of content that flows in as
+/// chunks, without collecting them into a continuous buffer.
+pub enum ChecksumHasher {
+    Crc32c(u32),
+    Sha256(Sha256),
+}
+
+impl ChecksumHasher {
+    /// Feed a new chunk into hasher.
+    pub fn update(&mut self, bs: &[u8]) {
+        match self {
+            ChecksumHasher::Crc32c(v) => *v = crc32c::crc32c_append(*v, bs),
+            ChecksumHasher::Sha256(v) => v.update(bs),
+        }
+    }
+
+    /// Consume the hasher to get the base64 encoded checksum.
+    pub fn finish(self) -> String {
+        match self {
+            ChecksumHasher::Crc32c(v) => BASE64_STANDARD.encode(v.to_be_bytes()),
+            ChecksumHasher::Sha256(v) => BASE64_STANDARD.encode(v.finalize()),
+        }
+    }
 }
 
 /// Request of DeleteObjects.
@@ -784,6 +1243,35 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_checksum_algorithm() {
+        let cases = vec![
+            (ChecksumAlgorithm::Crc32c, "", "AAAAAA=="),
+            (ChecksumAlgorithm::Crc32c, "Hello, World!", "TVUQaA=="),
+            (
+                ChecksumAlgorithm::Sha256,
+                "",
+                "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=",
+            ),
+            (
+                ChecksumAlgorithm::Sha256,
+                "Hello, World!",
+                "3/1gIbsr1bCvZ2KQgJ7DpTGR3YHH9wpLKGiKNiGCmG8=",
+            ),
+        ];
+
+        for (algo, input, expected) in cases {
+            assert_eq!(algo.checksum(input.as_bytes()), expected, "{algo:?}");
+
+            // Checksum calculated chunk by chunk must be the same.
+            let mut hasher = algo.hasher();
+            for chunk in input.as_bytes().chunks(3) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finish(), expected, "{algo:?} incrementally");
+        }
+    }
+
     /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_CompleteMultipartUpload.html#API_CompleteMultipartUpload_Examples
     #[test]
     fn test_serialize_complete_multipart_upload_request() {
@@ -792,14 +1280,17 @@ mod tests {
                 CompleteMultipartUploadRequestPart {
                     part_number: 1,
                     etag: "\"a54357aff0632cce46d942af68356b38\"".to_string(),
+                    ..Default::default()
                 },
                 CompleteMultipartUploadRequestPart {
                     part_number: 2,
                     etag: "\"0c78aef83f66abc1fa1e8477f296d394\"".to_string(),
+                    ..Default::default()
                 },
                 CompleteMultipartUploadRequestPart {
                     part_number: 3,
                     etag: "\"acbd18db4cc2f85cedef654fccc4a4d8\"".to_string(),
+                    ..Default::default()
                 },
             ],
         };
@@ -887,4 +1378,34 @@ mod tests {
         assert_eq!(out.error[0].code, "AccessDenied");
         assert_eq!(out.error[0].message, "Access Denied");
     }
+
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_RestoreObject.html#API_RestoreObject_Examples
+    #[test]
+    fn test_serialize_restore_request() {
+        let req = RestoreRequest {
+            days: Some(2),
+            glacier_job_parameters: Some(GlacierJobParameters {
+                tier: "Bulk".to_string(),
+            }),
+        };
+
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+
+        pretty_assertions::assert_eq!(
+            actual,
+            r#"<RestoreRequest>
+                <Days>2</Days>
+                <GlacierJobParameters>
+                    <Tier>Bulk</Tier>
+                </GlacierJobParameters>
+            </RestoreRequest>"#
+                // Cleanup space and new line
+                .replace([' ', '\n'], "")
+        );
+
+        // Days must be omitted while restoring from intelligent tiering.
+        let req = RestoreRequest::default();
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+        assert_eq!(actual, "<RestoreRequest/>");
+    }
 }
@@ -0,0 +1,411 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use http::header;
+use log::debug;
+use parking_lot::Mutex;
+use reqsign::AwsCredential;
+use reqsign::AwsCredentialLoad;
+use serde::Deserialize;
+
+use crate::raw::*;
+
+/// The endpoint of ECS container credentials provider.
+const ECS_CONTAINER_CREDENTIALS_ENDPOINT: &str = "http://169.254.170.2";
+
+/// EcsCredentialLoad loads credentials from the container credentials
+/// provider of ECS and EKS Pod Identity.
+///
+/// Credentials returned by this loader carry an expiration time, they will
+/// be cached and refreshed before expiry.
+///
+/// Ref: <https://docs.aws.amazon.com/sdkref/latest/guide/feature-container-credentials.html>
+pub struct EcsCredentialLoad {
+    endpoint: String,
+    authorization_token: Option<String>,
+    authorization_token_file: Option<String>,
+
+    credential: Mutex<Option<AwsCredential>>,
+}
+
+impl Debug for EcsCredentialLoad {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EcsCredentialLoad")
+            .field("endpoint", &self.endpoint)
+            .field("authorization_token_file", &self.authorization_token_file)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EcsCredentialLoad {
+    /// Create a loader from env.
+    ///
+    /// Returns `None` if container credentials provider is not available.
+    pub fn from_env() -> Option<Self> {
+        Self::from_envs(&env::vars().collect())
+    }
+
+    fn from_envs(envs: &HashMap<String, String>) -> Option<Self> {
+        let endpoint = match envs.get("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI") {
+            Some(v) => format!("{ECS_CONTAINER_CREDENTIALS_ENDPOINT}{v}"),
+            None => envs.get("AWS_CONTAINER_CREDENTIALS_FULL_URI")?.to_string(),
+        };
+
+        Some(EcsCredentialLoad {
+            endpoint,
+            authorization_token: envs.get("AWS_CONTAINER_AUTHORIZATION_TOKEN").cloned(),
+            authorization_token_file: envs.get("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE").cloned(),
+            credential: Mutex::default(),
+        })
+    }
+
+    /// The token file takes precedence since it will be rotated.
+    fn load_authorization_token(&self) -> anyhow::Result<Option<String>> {
+        if let Some(path) = &self.authorization_token_file {
+            let token = std::fs::read_to_string(path)?;
+            return Ok(Some(token.trim().to_string()));
+        }
+
+        Ok(self.authorization_token.clone())
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl AwsCredentialLoad for EcsCredentialLoad {
+    async fn load_credential(
+        &self,
+        client: reqwest::Client,
+    ) -> anyhow::Result<Option<AwsCredential>> {
+        if let Some(cred) = self.credential.lock().clone() {
+            if cred.is_valid() {
+                return Ok(Some(cred));
+            }
+        }
+
+        let mut req = client.get(&self.endpoint);
+        if let Some(token) = self.load_authorization_token()? {
+            req = req.header(header::AUTHORIZATION, token);
+        }
+
+        let resp = req.send().await?;
+        let status = resp.status();
+        let bs = resp.bytes().await?;
+        if !status.is_success() {
+            return Err(anyhow!(
+                "load credential from container credentials provider failed: {status} {}",
+                String::from_utf8_lossy(&bs)
+            ));
+        }
+
+        let output: EcsCredentialOutput = serde_json::from_slice(&bs)?;
+        let cred = output.into_credential()?;
+        *self.credential.lock() = Some(cred.clone());
+        Ok(Some(cred))
+    }
+}
+
+/// CredentialChain tries loaders in order and uses the first credential
+/// loaded.
+///
+/// Errors of all loaders except the last one are logged and skipped, so the
+/// result of the last loader (usually the default credential chain) will be
+/// returned if no other loader could load a credential.
+///
+/// Loaded credential will be cached until it expires.
+pub struct CredentialChain {
+    loaders: Vec<Box<dyn AwsCredentialLoad>>,
+
+    credential: Mutex<Option<AwsCredential>>,
+}
+
+impl Debug for CredentialChain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialChain")
+            .field("loaders", &self.loaders.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CredentialChain {
+    /// Create a new chain with given loaders.
+    pub fn new(loaders: Vec<Box<dyn AwsCredentialLoad>>) -> Self {
+        Self {
+            loaders,
+            credential: Mutex::default(),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl AwsCredentialLoad for CredentialChain {
+    async fn load_credential(
+        &self,
+        client: reqwest::Client,
+    ) -> anyhow::Result<Option<AwsCredential>> {
+        if let Some(cred) = self.credential.lock().clone() {
+            if cred.is_valid() {
+                return Ok(Some(cred));
+            }
+        }
+
+        let (last, loaders) = match self.loaders.split_last() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let mut cred = None;
+        for loader in loaders {
+            match loader.load_credential(client.clone()).await {
+                Ok(Some(v)) => {
+                    cred = Some(v);
+                    break;
+                }
+                Ok(None) => {}
+                Err(err) => debug!("load credential failed, try next loader: {err:?}"),
+            }
+        }
+        let cred = match cred {
+            Some(v) => Some(v),
+            None => last.load_credential(client).await?,
+        };
+
+        *self.credential.lock() = cred.clone();
+        Ok(cred)
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct EcsCredentialOutput {
+    access_key_id: String,
+    secret_access_key: String,
+    token: Option<String>,
+    expiration: Option<String>,
+}
+
+impl EcsCredentialOutput {
+    fn into_credential(self) -> anyhow::Result<AwsCredential> {
+        let expires_in = match &self.expiration {
+            Some(v) => Some(parse_datetime_from_rfc3339(v)?),
+            None => None,
+        };
+
+        Ok(AwsCredential {
+            access_key_id: self.access_key_id,
+            secret_access_key: self.secret_access_key,
+            session_token: self.token,
+            expires_in,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    #[test]
+    fn test_from_envs() {
+        let envs = HashMap::new();
+        assert!(EcsCredentialLoad::from_envs(&envs).is_none());
+
+        let envs = HashMap::from([(
+            "AWS_CONTAINER_CREDENTIALS_RELATIVE_URI".to_string(),
+            "/v2/credentials/abc".to_string(),
+        )]);
+        let loader = EcsCredentialLoad::from_envs(&envs).expect("must be available");
+        assert_eq!(loader.endpoint, "http://169.254.170.2/v2/credentials/abc");
+        assert_eq!(loader.authorization_token, None);
+
+        let envs = HashMap::from([
+            (
+                "AWS_CONTAINER_CREDENTIALS_FULL_URI".to_string(),
+                "http://169.254.170.23/v1/credentials".to_string(),
+            ),
+            (
+                "AWS_CONTAINER_AUTHORIZATION_TOKEN".to_string(),
+                "token".to_string(),
+            ),
+        ]);
+        let loader = EcsCredentialLoad::from_envs(&envs).expect("must be available");
+        assert_eq!(loader.endpoint, "http://169.254.170.23/v1/credentials");
+        assert_eq!(loader.authorization_token.as_deref(), Some("token"));
+    }
+
+    #[tokio::test]
+    async fn test_load_credential_cached() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/credentials"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let loader = EcsCredentialLoad {
+            endpoint: format!("{}/credentials", server.uri()),
+            authorization_token: None,
+            authorization_token_file: None,
+            credential: Mutex::new(Some(AwsCredential {
+                access_key_id: "ACCESS_KEY_ID".to_string(),
+                secret_access_key: "SECRET_ACCESS_KEY".to_string(),
+                session_token: None,
+                expires_in: Some(Utc::now() + chrono::Duration::hours(1)),
+            })),
+        };
+
+        let cred = loader
+            .load_credential(reqwest::Client::new())
+            .await
+            .expect("must be loaded from cache")
+            .expect("must be some");
+        assert_eq!(cred.access_key_id, "ACCESS_KEY_ID");
+
+        // Expired credential must be reloaded, mock expects only one call.
+        *loader.credential.lock() = Some(AwsCredential {
+            expires_in: Some(Utc::now()),
+            ..cred
+        });
+        assert!(loader
+            .load_credential(reqwest::Client::new())
+            .await
+            .is_err());
+    }
+
+    struct StaticCredentialLoad(anyhow::Result<Option<AwsCredential>>, Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl AwsCredentialLoad for StaticCredentialLoad {
+        async fn load_credential(
+            &self,
+            _: reqwest::Client,
+        ) -> anyhow::Result<Option<AwsCredential>> {
+            self.1.fetch_add(1, Ordering::Relaxed);
+            match &self.0 {
+                Ok(v) => Ok(v.clone()),
+                Err(err) => Err(anyhow!("{err}")),
+            }
+        }
+    }
+
+    fn credential(ak: &str) -> AwsCredential {
+        AwsCredential {
+            access_key_id: ak.to_string(),
+            secret_access_key: "SECRET_ACCESS_KEY".to_string(),
+            session_token: None,
+            expires_in: Some(Utc::now() + chrono::Duration::hours(1)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_credential_chain() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let chain = CredentialChain::new(vec![
+            Box::new(StaticCredentialLoad(Err(anyhow!("failed")), calls.clone())),
+            Box::new(StaticCredentialLoad(Ok(None), calls.clone())),
+            Box::new(StaticCredentialLoad(
+                Ok(Some(credential("CUSTOM"))),
+                calls.clone(),
+            )),
+            Box::new(StaticCredentialLoad(
+                Ok(Some(credential("DEFAULT"))),
+                calls.clone(),
+            )),
+        ]);
+
+        let cred = chain
+            .load_credential(reqwest::Client::new())
+            .await
+            .expect("must succeed")
+            .expect("must be some");
+        assert_eq!(cred.access_key_id, "CUSTOM");
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        // Credential should be cached.
+        chain
+            .load_credential(reqwest::Client::new())
+            .await
+            .expect("must succeed");
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn test_credential_chain_fallback() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let chain = CredentialChain::new(vec![
+            Box::new(StaticCredentialLoad(Err(anyhow!("failed")), calls.clone())),
+            Box::new(StaticCredentialLoad(
+                Ok(Some(credential("DEFAULT"))),
+                calls.clone(),
+            )),
+        ]);
+        let cred = chain
+            .load_credential(reqwest::Client::new())
+            .await
+            .expect("must succeed")
+            .expect("must be some");
+        assert_eq!(cred.access_key_id, "DEFAULT");
+
+        // Error of the last loader should be returned.
+        let chain = CredentialChain::new(vec![
+            Box::new(StaticCredentialLoad(Ok(None), calls.clone())),
+            Box::new(StaticCredentialLoad(Err(anyhow!("failed")), calls.clone())),
+        ]);
+        assert!(chain.load_credential(reqwest::Client::new()).await.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_credential_output() {
+        let content = r#"{
+            "AccessKeyId": "ACCESS_KEY_ID",
+            "Expiration": "2023-07-25T09:13:18Z",
+            "RoleArn": "TASK_ROLE_ARN",
+            "SecretAccessKey": "SECRET_ACCESS_KEY",
+            "Token": "SECURITY_TOKEN_STRING"
+        }"#;
+
+        let output: EcsCredentialOutput = serde_json::from_str(content).expect("must be valid");
+        let cred = output.into_credential().expect("must be valid");
+        assert_eq!(cred.access_key_id, "ACCESS_KEY_ID");
+        assert_eq!(cred.secret_access_key, "SECRET_ACCESS_KEY");
+        assert_eq!(cred.session_token.as_deref(), Some("SECURITY_TOKEN_STRING"));
+        assert_eq!(
+            cred.expires_in,
+            Some(Utc.with_ymd_and_hms(2023, 7, 25, 9, 13, 18).unwrap())
+        );
+    }
+}
@@ -19,6 +19,9 @@ mod backend;
 pub use backend::S3Builder as S3;
 
 mod core;
+mod credential;
 mod error;
 mod pager;
+mod region;
+mod select;
 mod writer;
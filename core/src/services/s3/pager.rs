@@ -72,16 +72,27 @@ impl oio::Page for S3Pager {
             return Ok(None);
         }
 
-        let resp = self
-            .core
-            .s3_list_objects(
-                &self.path,
-                &self.token,
-                &self.delimiter,
-                self.limit,
-                self.start_after.clone(),
-            )
-            .await?;
+        let resp = if self.core.disable_list_objects_v2 {
+            self.core
+                .s3_list_objects_v1(
+                    &self.path,
+                    &self.token,
+                    &self.delimiter,
+                    self.limit,
+                    self.start_after.clone(),
+                )
+                .await?
+        } else {
+            self.core
+                .s3_list_objects(
+                    &self.path,
+                    &self.token,
+                    &self.delimiter,
+                    self.limit,
+                    self.start_after.clone(),
+                )
+                .await?
+        };
 
         if resp.status() != http::StatusCode::OK {
             return Err(parse_error(resp).await?);
@@ -91,19 +102,25 @@ impl oio::Page for S3Pager {
 
         let output: Output = de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
 
+        let next_token = if self.core.disable_list_objects_v2 {
+            output.next_marker()
+        } else {
+            output.next_continuation_token.clone()
+        };
+
         // Try our best to check whether this list is done.
         //
         // - Check `is_truncated`
-        // - Check `next_continuation_token`
+        // - Check `next_continuation_token` or `next_marker`
         // - Check the length of `common_prefixes` and `contents` (very rarely case)
         self.done = if let Some(is_truncated) = output.is_truncated {
             !is_truncated
-        } else if let Some(next_continuation_token) = output.next_continuation_token.as_ref() {
-            next_continuation_token.is_empty()
+        } else if let Some(next_token) = next_token.as_ref() {
+            next_token.is_empty()
         } else {
             output.common_prefixes.is_empty() && output.contents.is_empty()
         };
-        self.token = output.next_continuation_token.clone().unwrap_or_default();
+        self.token = next_token.unwrap_or_default();
 
         let mut entries = Vec::with_capacity(output.common_prefixes.len() + output.contents.len());
 
@@ -129,6 +146,9 @@ impl oio::Page for S3Pager {
             meta.set_etag(&object.etag);
             meta.set_content_md5(object.etag.trim_matches('"'));
             meta.set_content_length(object.size);
+            if let Some(v) = &object.storage_class {
+                meta.set_storage_class(v);
+            }
 
             // object.last_modified provides more precious time that contains
             // nanosecond, let's trim them.
@@ -157,10 +177,30 @@ impl oio::Page for S3Pager {
 struct Output {
     is_truncated: Option<bool>,
     next_continuation_token: Option<String>,
+    /// Only returned by ListObjects (v1) while delimiter is specified.
+    next_marker: Option<String>,
     common_prefixes: Vec<OutputCommonPrefix>,
     contents: Vec<OutputContent>,
 }
 
+impl Output {
+    /// Get the marker for the next page of ListObjects (v1).
+    ///
+    /// `NextMarker` will only be returned while delimiter is specified,
+    /// otherwise we should use the last key (or common prefix) in this
+    /// page as the marker.
+    fn next_marker(&self) -> Option<String> {
+        if let Some(v) = &self.next_marker {
+            return Some(v.clone());
+        }
+
+        let last_key = self.contents.last().map(|v| v.key.as_str());
+        let last_prefix = self.common_prefixes.last().map(|v| v.prefix.as_str());
+
+        last_key.max(last_prefix).map(String::from)
+    }
+}
+
 #[derive(Default, Debug, Eq, PartialEq, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 struct OutputContent {
@@ -169,6 +209,7 @@ struct OutputContent {
     last_modified: String,
     #[serde(rename = "ETag")]
     etag: String,
+    storage_class: Option<String>,
 }
 
 #[derive(Default, Debug, Eq, PartialEq, Deserialize)]
@@ -234,14 +275,70 @@ mod tests {
                     size: 56,
                     etag: "\"d41d8cd98f00b204e9800998ecf8427e\"".to_string(),
                     last_modified: "2016-04-30T23:51:29.000Z".to_string(),
+                    storage_class: Some("STANDARD".to_string()),
                 },
                 OutputContent {
                     key: "photos/2007".to_string(),
                     size: 100,
                     last_modified: "2016-04-30T23:51:29.000Z".to_string(),
                     etag: "\"d41d8cd98f00b204e9800998ecf8427e\"".to_string(),
+                    storage_class: Some("STANDARD".to_string()),
                 }
             ]
         )
     }
+
+    #[test]
+    fn test_parse_list_output_v1() {
+        let bs = bytes::Bytes::from(
+            r#"<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>example-bucket</Name>
+  <Prefix>photos/</Prefix>
+  <Marker></Marker>
+  <MaxKeys>2</MaxKeys>
+  <IsTruncated>true</IsTruncated>
+  <Contents>
+    <Key>photos/2006</Key>
+    <LastModified>2016-04-30T23:51:29.000Z</LastModified>
+    <ETag>"d41d8cd98f00b204e9800998ecf8427e"</ETag>
+    <Size>56</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+  <Contents>
+    <Key>photos/2007</Key>
+    <LastModified>2016-04-30T23:51:29.000Z</LastModified>
+    <ETag>"d41d8cd98f00b204e9800998ecf8427e"</ETag>
+    <Size>100</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+</ListBucketResult>"#,
+        );
+
+        let out: Output = de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.is_truncated.unwrap());
+        assert!(out.next_marker.is_none());
+        // Use the last key as marker if `NextMarker` is not returned.
+        assert_eq!(out.next_marker(), Some("photos/2007".to_string()));
+
+        let bs = bytes::Bytes::from(
+            r#"<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>example-bucket</Name>
+  <Prefix>photos/</Prefix>
+  <Marker></Marker>
+  <NextMarker>photos/2006/February/</NextMarker>
+  <MaxKeys>2</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>true</IsTruncated>
+  <CommonPrefixes>
+    <Prefix>photos/2006/February/</Prefix>
+  </CommonPrefixes>
+</ListBucketResult>"#,
+        );
+
+        let out: Output = de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.is_truncated.unwrap());
+        assert_eq!(out.next_marker(), Some("photos/2006/February/".to_string()));
+    }
 }
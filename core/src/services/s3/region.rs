@@ -0,0 +1,256 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use http::header::AUTHORIZATION;
+use http::header::HOST;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use log::debug;
+use reqsign::AwsV4Signer;
+
+use crate::raw::*;
+use crate::*;
+
+/// The response header that carries the region of bucket.
+pub const X_AMZ_BUCKET_REGION: &str = "x-amz-bucket-region";
+/// The placeholder of region in endpoint template.
+const REGION_PLACEHOLDER: &str = "{region}";
+
+/// S3RegionDetector detects the region of bucket from the responses of
+/// AWS S3 and redirects requests to the right regional endpoint.
+///
+/// AWS S3 returns `301 Moved Permanently` or `400 Bad Request` with the
+/// `x-amz-bucket-region` header if requests are sent to the wrong region.
+/// The detected region will be cached so that following requests can be
+/// sent to the right endpoint directly.
+pub struct S3RegionDetector {
+    /// The endpoint that requests are built with.
+    endpoint: String,
+    /// The region that the backend is built with.
+    region: String,
+    /// The endpoint template with `{region}` placeholder.
+    template: String,
+    detected: RwLock<Option<Arc<S3DetectedRegion>>>,
+}
+
+/// The region detected from responses and its endpoint and signer.
+pub struct S3DetectedRegion {
+    pub region: String,
+    pub endpoint: String,
+    pub signer: AwsV4Signer,
+}
+
+impl S3RegionDetector {
+    /// Create a new detector, returns `None` if we don't know how to build
+    /// endpoints for other regions.
+    pub fn new(endpoint: &str, region: &str, template: &str) -> Option<Self> {
+        if !template.contains(REGION_PLACEHOLDER) {
+            return None;
+        }
+
+        Some(Self {
+            endpoint: endpoint.to_string(),
+            region: region.to_string(),
+            template: template.to_string(),
+            detected: RwLock::new(None),
+        })
+    }
+
+    /// Build the endpoint template from the endpoint of AWS S3 regional
+    /// endpoints like `https://s3.us-east-2.amazonaws.com`.
+    pub fn build_template(endpoint: &str, region: &str) -> String {
+        if endpoint.contains(REGION_PLACEHOLDER) {
+            return endpoint.to_string();
+        }
+
+        for pattern in [".{region}.amazonaws.com", "-{region}.amazonaws.com"] {
+            let from = pattern.replace(REGION_PLACEHOLDER, region);
+            if endpoint.contains(&from) {
+                return endpoint.replacen(&from, pattern, 1);
+            }
+        }
+
+        endpoint.to_string()
+    }
+
+    /// Get the detected region if any.
+    pub fn detected(&self) -> Option<Arc<S3DetectedRegion>> {
+        self.detected
+            .read()
+            .expect("lock must not be poisoned")
+            .clone()
+    }
+
+    /// Check the response, returns the detected region if requests should
+    /// be sent to another region.
+    pub fn detect<T>(&self, resp: &Response<T>) -> Option<Arc<S3DetectedRegion>> {
+        if !matches!(
+            resp.status(),
+            StatusCode::MOVED_PERMANENTLY
+                | StatusCode::TEMPORARY_REDIRECT
+                | StatusCode::BAD_REQUEST
+        ) {
+            return None;
+        }
+
+        let region = resp.headers().get(X_AMZ_BUCKET_REGION)?.to_str().ok()?;
+        let current = self.detected();
+        let current_region = current
+            .as_ref()
+            .map(|v| v.region.as_str())
+            .unwrap_or(&self.region);
+        if region.is_empty() || region == current_region {
+            return None;
+        }
+
+        debug!("s3 detected region of bucket: {region}");
+        let detected = Arc::new(S3DetectedRegion {
+            region: region.to_string(),
+            endpoint: self.template.replace(REGION_PLACEHOLDER, region),
+            signer: AwsV4Signer::new("s3", region),
+        });
+        *self.detected.write().expect("lock must not be poisoned") = Some(detected.clone());
+        Some(detected)
+    }
+
+    /// Rewrite the uri of request to the endpoint of detected region.
+    ///
+    /// Headers generated by previous signing will be removed so that
+    /// the request can be signed again.
+    pub fn rewrite<T>(&self, req: &mut Request<T>, detected: &S3DetectedRegion) -> Result<()> {
+        let uri = req.uri().to_string();
+        let uri = if let Some(path) = uri.strip_prefix(&self.endpoint) {
+            format!("{}{path}", detected.endpoint)
+        } else if uri.starts_with(&detected.endpoint) {
+            uri
+        } else {
+            return Ok(());
+        };
+
+        *req.uri_mut() = uri
+            .parse()
+            .map_err(|err: http::uri::InvalidUri| new_request_build_error(err.into()))?;
+
+        let headers = req.headers_mut();
+        headers.remove(HOST);
+        headers.remove(AUTHORIZATION);
+        headers.remove("x-amz-date");
+        headers.remove("x-amz-content-sha256");
+        headers.remove("x-amz-security-token");
+
+        Ok(())
+    }
+}
+
+/// Clone the request so that it can be sent again, returns `None` if the
+/// body is a stream.
+pub fn try_clone_request(req: &Request<AsyncBody>) -> Option<Request<AsyncBody>> {
+    let body = match req.body() {
+        AsyncBody::Empty => AsyncBody::Empty,
+        AsyncBody::Bytes(bs) => AsyncBody::Bytes(bs.clone()),
+        AsyncBody::Stream(_) => return None,
+    };
+
+    let mut new = Request::new(body);
+    *new.method_mut() = req.method().clone();
+    *new.uri_mut() = req.uri().clone();
+    *new.version_mut() = req.version();
+    *new.headers_mut() = req.headers().clone();
+    Some(new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_template() {
+        let cases = vec![
+            (
+                "https://s3.{region}.amazonaws.com/test",
+                "us-east-1",
+                "https://s3.{region}.amazonaws.com/test",
+            ),
+            (
+                "https://s3.us-east-2.amazonaws.com/test",
+                "us-east-2",
+                "https://s3.{region}.amazonaws.com/test",
+            ),
+            (
+                "https://test.s3-us-west-1.amazonaws.com",
+                "us-west-1",
+                "https://test.s3-{region}.amazonaws.com",
+            ),
+            (
+                "http://127.0.0.1:9000/test",
+                "us-east-1",
+                "http://127.0.0.1:9000/test",
+            ),
+        ];
+
+        for (endpoint, region, expected) in cases {
+            assert_eq!(
+                S3RegionDetector::build_template(endpoint, region),
+                expected,
+                "{endpoint}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_and_rewrite() {
+        let detector = S3RegionDetector::new(
+            "https://s3.us-east-1.amazonaws.com/test",
+            "us-east-1",
+            "https://s3.{region}.amazonaws.com/test",
+        )
+        .expect("must be detectable");
+
+        let resp = Response::builder()
+            .status(StatusCode::MOVED_PERMANENTLY)
+            .header(X_AMZ_BUCKET_REGION, "eu-west-1")
+            .body(())
+            .unwrap();
+        let detected = detector.detect(&resp).expect("region must be detected");
+        assert_eq!(detected.region, "eu-west-1");
+        assert_eq!(detected.endpoint, "https://s3.eu-west-1.amazonaws.com/test");
+        // The same region should not be detected again.
+        assert!(detector.detect(&resp).is_none());
+        assert!(detector.detected().is_some());
+
+        let mut req = Request::get("https://s3.us-east-1.amazonaws.com/test/a?b=c")
+            .header(HOST, "s3.us-east-1.amazonaws.com")
+            .header(AUTHORIZATION, "AWS4-HMAC-SHA256")
+            .body(AsyncBody::Empty)
+            .unwrap();
+        detector.rewrite(&mut req, &detected).unwrap();
+        assert_eq!(req.uri(), "https://s3.eu-west-1.amazonaws.com/test/a?b=c");
+        assert!(req.headers().get(HOST).is_none());
+        assert!(req.headers().get(AUTHORIZATION).is_none());
+
+        let resp = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .header(X_AMZ_BUCKET_REGION, "ap-east-1")
+            .body(())
+            .unwrap();
+        assert!(detector.detect(&resp).is_none());
+    }
+}
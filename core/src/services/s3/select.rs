@@ -0,0 +1,399 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::cmp::min;
+use std::io;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+use serde::Serialize;
+
+use crate::raw::*;
+use crate::*;
+
+/// Request of SelectObjectContent.
+///
+/// Reference: [SelectObjectContent](https://docs.aws.amazon.com/AmazonS3/latest/API/API_SelectObjectContent.html)
+#[derive(Default, Debug, Serialize)]
+#[serde(rename = "SelectObjectContentRequest", rename_all = "PascalCase")]
+pub struct SelectObjectContentRequest {
+    pub expression: String,
+    pub expression_type: String,
+    pub input_serialization: InputSerialization,
+    pub output_serialization: OutputSerialization,
+}
+
+impl SelectObjectContentRequest {
+    pub fn new(args: &OpSelect) -> Result<Self> {
+        let input_serialization = match args.input_format() {
+            SelectFormat::Csv => InputSerialization {
+                csv: Some(CsvInput {
+                    file_header_info: "USE".to_string(),
+                }),
+                ..Default::default()
+            },
+            SelectFormat::Json => InputSerialization {
+                json: Some(JsonInput {
+                    r#type: "LINES".to_string(),
+                }),
+                ..Default::default()
+            },
+            SelectFormat::Parquet => InputSerialization {
+                parquet: Some(Empty {}),
+                ..Default::default()
+            },
+        };
+
+        let output_serialization = match args.output_format() {
+            SelectFormat::Csv => OutputSerialization {
+                csv: Some(Empty {}),
+                ..Default::default()
+            },
+            SelectFormat::Json => OutputSerialization {
+                json: Some(Empty {}),
+                ..Default::default()
+            },
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "output format is not supported by s3 select",
+                ))
+            }
+        };
+
+        Ok(Self {
+            expression: args.expression().to_string(),
+            expression_type: "SQL".to_string(),
+            input_serialization,
+            output_serialization,
+        })
+    }
+}
+
+#[derive(Default, Debug, Serialize)]
+pub struct InputSerialization {
+    #[serde(rename = "CSV", skip_serializing_if = "Option::is_none")]
+    pub csv: Option<CsvInput>,
+    #[serde(rename = "JSON", skip_serializing_if = "Option::is_none")]
+    pub json: Option<JsonInput>,
+    #[serde(rename = "Parquet", skip_serializing_if = "Option::is_none")]
+    pub parquet: Option<Empty>,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CsvInput {
+    pub file_header_info: String,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct JsonInput {
+    pub r#type: String,
+}
+
+#[derive(Default, Debug, Serialize)]
+pub struct OutputSerialization {
+    #[serde(rename = "CSV", skip_serializing_if = "Option::is_none")]
+    pub csv: Option<Empty>,
+    #[serde(rename = "JSON", skip_serializing_if = "Option::is_none")]
+    pub json: Option<Empty>,
+}
+
+#[derive(Default, Debug, Serialize)]
+pub struct Empty {}
+
+/// The length of total length, headers length and prelude crc.
+const PRELUDE_LENGTH: usize = 12;
+/// The length of message crc.
+const MESSAGE_CRC_LENGTH: usize = 4;
+
+/// Message decoded from the [event stream](https://docs.aws.amazon.com/AmazonS3/latest/API/RESTSelectObjectAppendix.html)
+/// returned by SelectObjectContent.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Message {
+    message_type: String,
+    event_type: String,
+    error_code: String,
+    error_message: String,
+    payload: Bytes,
+}
+
+impl Message {
+    /// Decode a message from the head of the buffer.
+    ///
+    /// Returns `None` if the buffer doesn't contain a complete message.
+    fn decode(buf: &mut BytesMut) -> Result<Option<Message>> {
+        if buf.len() < PRELUDE_LENGTH {
+            return Ok(None);
+        }
+
+        let total_length = (&buf[0..4]).get_u32() as usize;
+        let headers_length = (&buf[4..8]).get_u32() as usize;
+        if total_length < PRELUDE_LENGTH + headers_length + MESSAGE_CRC_LENGTH {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "invalid message in s3 select event stream",
+            )
+            .with_context("total_length", total_length.to_string())
+            .with_context("headers_length", headers_length.to_string()));
+        }
+        if buf.len() < total_length {
+            return Ok(None);
+        }
+
+        let mut bs = buf.split_to(total_length).freeze();
+        bs.advance(PRELUDE_LENGTH);
+        let mut headers = bs.split_to(headers_length);
+        let payload = bs.split_to(bs.len() - MESSAGE_CRC_LENGTH);
+
+        let mut msg = Message {
+            payload,
+            ..Default::default()
+        };
+        while headers.has_remaining() {
+            let name_length = headers.get_u8() as usize;
+            let name = headers.split_to(name_length);
+            // All headers in s3 select response are string (7).
+            let value_type = headers.get_u8();
+            if value_type != 7 {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "invalid header type in s3 select event stream",
+                )
+                .with_context("value_type", value_type.to_string()));
+            }
+            let value_length = headers.get_u16() as usize;
+            let value = String::from_utf8_lossy(&headers.split_to(value_length)).to_string();
+
+            match name.as_ref() {
+                b":message-type" => msg.message_type = value,
+                b":event-type" => msg.event_type = value,
+                b":error-code" => msg.error_code = value,
+                b":error-message" => msg.error_message = value,
+                _ => {}
+            }
+        }
+
+        Ok(Some(msg))
+    }
+}
+
+/// S3SelectReader decodes the event stream returned by SelectObjectContent
+/// and only returns the payload of `Records` events.
+pub struct S3SelectReader {
+    inner: IncomingAsyncBody,
+
+    buf: BytesMut,
+    chunk: Option<Bytes>,
+    done: bool,
+}
+
+impl S3SelectReader {
+    pub fn new(inner: IncomingAsyncBody) -> Self {
+        Self {
+            inner,
+            buf: BytesMut::new(),
+            chunk: None,
+            done: false,
+        }
+    }
+}
+
+impl oio::Read for S3SelectReader {
+    fn poll_read(&mut self, cx: &mut Context<'_>, mut buf: &mut [u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let mut bs = match ready!(self.poll_next(cx)) {
+            Some(Ok(bs)) => bs,
+            Some(Err(err)) => return Poll::Ready(Err(err)),
+            None => return Poll::Ready(Ok(0)),
+        };
+
+        let amt = min(bs.len(), buf.len());
+        buf.put_slice(&bs[..amt]);
+        bs.advance(amt);
+        if !bs.is_empty() {
+            self.chunk = Some(bs);
+        }
+
+        Poll::Ready(Ok(amt))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        let (_, _) = (cx, pos);
+
+        Poll::Ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "s3 select reader doesn't support seeking",
+        )))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        if let Some(bs) = self.chunk.take() {
+            return Poll::Ready(Some(Ok(bs)));
+        }
+
+        loop {
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            if let Some(msg) = Message::decode(&mut self.buf)? {
+                if msg.message_type == "error" {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(Error::new(
+                        ErrorKind::Unexpected,
+                        &format!("{}: {}", msg.error_code, msg.error_message),
+                    )
+                    .with_operation("S3SelectReader::poll_next"))));
+                }
+
+                match msg.event_type.as_str() {
+                    "Records" if !msg.payload.is_empty() => {
+                        return Poll::Ready(Some(Ok(msg.payload)))
+                    }
+                    "End" => self.done = true,
+                    // Ignore `Stats`, `Progress` and `Cont` events.
+                    _ => {}
+                }
+                continue;
+            }
+
+            match ready!(self.inner.poll_next(cx)) {
+                Some(Ok(bs)) => self.buf.extend_from_slice(&bs),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => {
+                    // The stream must be ended by an `End` event, otherwise
+                    // the returning records are incomplete.
+                    return Poll::Ready(Some(Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "s3 select event stream ended without End event",
+                    )
+                    .set_temporary())));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_message(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+        let mut hs = vec![];
+        for (k, v) in headers {
+            hs.put_u8(k.len() as u8);
+            hs.put_slice(k.as_bytes());
+            hs.put_u8(7);
+            hs.put_u16(v.len() as u16);
+            hs.put_slice(v.as_bytes());
+        }
+
+        let total_length = PRELUDE_LENGTH + hs.len() + payload.len() + MESSAGE_CRC_LENGTH;
+        let mut bs = vec![];
+        bs.put_u32(total_length as u32);
+        bs.put_u32(hs.len() as u32);
+        // CRC is not checked.
+        bs.put_u32(0);
+        bs.put_slice(&hs);
+        bs.put_slice(payload);
+        bs.put_u32(0);
+        bs
+    }
+
+    #[test]
+    fn test_decode_message() {
+        let records = encode_message(
+            &[
+                (":message-type", "event"),
+                (":event-type", "Records"),
+                (":content-type", "application/octet-stream"),
+            ],
+            b"a,b\n",
+        );
+        let end = encode_message(&[(":message-type", "event"), (":event-type", "End")], b"");
+
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(&records[..10]);
+        assert_eq!(Message::decode(&mut buf).unwrap(), None);
+
+        buf.extend_from_slice(&records[10..]);
+        buf.extend_from_slice(&end);
+        let msg = Message::decode(&mut buf).unwrap().unwrap();
+        assert_eq!(msg.message_type, "event");
+        assert_eq!(msg.event_type, "Records");
+        assert_eq!(msg.payload, Bytes::from("a,b\n"));
+
+        let msg = Message::decode(&mut buf).unwrap().unwrap();
+        assert_eq!(msg.event_type, "End");
+        assert!(msg.payload.is_empty());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_decode_error_message() {
+        let error = encode_message(
+            &[
+                (":message-type", "error"),
+                (":error-code", "InvalidQuery"),
+                (":error-message", "The query is not valid."),
+            ],
+            b"",
+        );
+
+        let mut buf = BytesMut::from(error.as_slice());
+        let msg = Message::decode(&mut buf).unwrap().unwrap();
+        assert_eq!(msg.message_type, "error");
+        assert_eq!(msg.error_code, "InvalidQuery");
+        assert_eq!(msg.error_message, "The query is not valid.");
+    }
+
+    #[test]
+    fn test_serialize_select_object_content_request() {
+        let req = SelectObjectContentRequest::new(
+            &OpSelect::new("SELECT * FROM S3Object s WHERE s.age > '18'")
+                .with_input_format(SelectFormat::Csv)
+                .with_output_format(SelectFormat::Json),
+        )
+        .unwrap();
+
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+
+        pretty_assertions::assert_eq!(
+            actual,
+            "<SelectObjectContentRequest>\
+                <Expression>SELECT * FROM S3Object s WHERE s.age &gt; &apos;18&apos;</Expression>\
+                <ExpressionType>SQL</ExpressionType>\
+                <InputSerialization><CSV><FileHeaderInfo>USE</FileHeaderInfo></CSV></InputSerialization>\
+                <OutputSerialization><JSON/></OutputSerialization>\
+            </SelectObjectContentRequest>"
+        );
+
+        let res = SelectObjectContentRequest::new(
+            &OpSelect::new("SELECT * FROM S3Object").with_output_format(SelectFormat::Parquet),
+        );
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+    }
+}
@@ -15,11 +15,15 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Buf;
 use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
 use http::StatusCode;
 
 use super::core::*;
@@ -27,6 +31,24 @@ use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+/// AWS S3 allows at most 10000 parts in one multipart upload.
+const MAX_PARTS: u64 = 10000;
+/// AWS S3 allows at most 5 GiB in one part.
+const MAX_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+/// Part size of unsized writes will grow to `1 / PART_SIZE_GROWTH_FACTOR`
+/// of bytes that have been written.
+const PART_SIZE_GROWTH_FACTOR: u64 = 1000;
+
+type PartFuture = BoxFuture<
+    'static,
+    (
+        usize,
+        Bytes,
+        Option<String>,
+        Result<CompleteMultipartUploadRequestPart>,
+    ),
+>;
+
 pub struct S3Writer {
     core: Arc<S3Core>,
 
@@ -35,13 +57,42 @@ pub struct S3Writer {
     upload_id: Option<String>,
 
     parts: Vec<CompleteMultipartUploadRequestPart>,
+    /// The number of parts that have been submitted.
+    part_number: usize,
+    /// Parts that are uploading, at most `write_concurrency` parts.
+    futures: FuturesUnordered<PartFuture>,
     buffer: oio::VectorCursor,
     buffer_size: usize,
+    /// Buffers of parts will be taken from and returned back to the pool.
+    pool: BufferPool,
+    /// The total size of parts that have been submitted.
+    written: u64,
+
+    /// Hasher of the part that is being filled, bytes are fed into it
+    /// while they are buffered.
+    hasher: Option<ChecksumHasher>,
+    /// The number of bytes that have been fed into `hasher`.
+    hashed: usize,
+    /// Parts that have been filled but not submitted yet, along with
+    /// their size and checksum.
+    sealed: VecDeque<(usize, String)>,
 }
 
+/// `PartFuture` is not `Sync`, but it will only be used via `&mut self`,
+/// so it's safe to share the writer between threads.
+unsafe impl Sync for S3Writer {}
+
 impl S3Writer {
     pub fn new(core: Arc<S3Core>, path: &str, op: OpWrite) -> Self {
-        let buffer_size = core.write_min_size;
+        let mut buffer_size = core.write_min_size;
+        // Enlarge the part size for large files so that they can be
+        // uploaded in at most `MAX_PARTS` parts.
+        if let Some(size) = op.content_length() {
+            let min_part_size = (size + MAX_PARTS - 1) / MAX_PARTS;
+            buffer_size = buffer_size.max(min_part_size as usize);
+        }
+
+        let pool = op.buffer_pool().cloned().unwrap_or_default();
         S3Writer {
             core,
             path: path.to_string(),
@@ -49,8 +100,16 @@ impl S3Writer {
 
             upload_id: None,
             parts: vec![],
+            part_number: 0,
+            futures: FuturesUnordered::new(),
             buffer: oio::VectorCursor::new(),
             buffer_size,
+            pool,
+            written: 0,
+
+            hasher: None,
+            hashed: 0,
+            sealed: VecDeque::new(),
         }
     }
 
@@ -61,6 +120,8 @@ impl S3Writer {
             self.op.content_type(),
             self.op.content_disposition(),
             self.op.cache_control(),
+            self.op.storage_class(),
+            self.op.acl(),
             AsyncBody::Bytes(bs),
         )?;
 
@@ -87,6 +148,8 @@ impl S3Writer {
                 self.op.content_type(),
                 self.op.content_disposition(),
                 self.op.cache_control(),
+                self.op.storage_class(),
+                self.op.acl(),
             )
             .await?;
 
@@ -105,45 +168,229 @@ impl S3Writer {
         }
     }
 
-    async fn write_part(
-        &self,
+    /// Feed bytes that are going to be buffered into part hashers.
+    ///
+    /// Parts are sealed once `buffer_size` bytes have been fed, so that
+    /// every part's checksum is ready before it's submitted and the part
+    /// doesn't need to be read again.
+    fn feed_checksum(&mut self, mut bs: &[u8]) {
+        let algo = match self.core.checksum_algorithm {
+            Some(algo) => algo,
+            None => return,
+        };
+
+        while !bs.is_empty() {
+            let n = bs.len().min(self.buffer_size - self.hashed);
+            self.hasher
+                .get_or_insert_with(|| algo.hasher())
+                .update(&bs[..n]);
+            self.hashed += n;
+            bs = &bs[n..];
+
+            if self.hashed == self.buffer_size {
+                let hasher = self.hasher.take().expect("hasher must be initiated");
+                self.sealed.push_back((self.hashed, hasher.finish()));
+                self.hashed = 0;
+            }
+        }
+    }
+
+    /// Take the next part from buffer along with its checksum.
+    ///
+    /// If `last` is true, all remaining bytes will be taken as the last part
+    /// once sealed parts are exhausted.
+    fn take_part(&mut self, last: bool) -> (Bytes, Option<String>) {
+        let (bs, checksum) = if let Some((size, checksum)) = self.sealed.pop_front() {
+            (
+                self.buffer.peak_exact_with_pool(size, &self.pool),
+                Some(checksum),
+            )
+        } else if last {
+            self.hashed = 0;
+            (
+                self.buffer
+                    .peak_exact_with_pool(self.buffer.len(), &self.pool),
+                self.hasher.take().map(|v| v.finish()),
+            )
+        } else if self.core.enable_exact_buf_write {
+            (
+                self.buffer
+                    .peak_exact_with_pool(self.buffer_size, &self.pool),
+                None,
+            )
+        } else {
+            (
+                self.buffer
+                    .peak_at_least_with_pool(self.buffer_size, &self.pool),
+                None,
+            )
+        };
+        self.buffer.take(bs.len());
+
+        (bs, checksum)
+    }
+
+    /// Submit a part to upload, the part will be uploaded while polling
+    /// `futures`.
+    ///
+    /// The checksum is calculated while buffering, it will be reused by
+    /// retries and the complete request.
+    fn submit_part(
+        &mut self,
         upload_id: &str,
+        part_number: usize,
         bs: Bytes,
-    ) -> Result<CompleteMultipartUploadRequestPart> {
-        // AWS S3 requires part number must between [1..=10000]
-        let part_number = self.parts.len() + 1;
+        checksum: Option<String>,
+    ) {
+        let core = self.core.clone();
+        let path = self.path.clone();
+        let upload_id = upload_id.to_string();
+
+        self.futures.push(Box::pin(async move {
+            let res = write_part(
+                &core,
+                &path,
+                &upload_id,
+                part_number,
+                bs.clone(),
+                checksum.clone(),
+            )
+            .await;
+            (part_number, bs, checksum, res)
+        }));
+    }
 
-        let mut req = self.core.s3_upload_part_request(
-            &self.path,
-            upload_id,
-            part_number,
-            Some(bs.len() as u64),
-            AsyncBody::Bytes(bs),
-        )?;
+    /// Grow the part size of unsized writes with bytes written so far,
+    /// so that small objects stay cheap while large uploads won't exceed
+    /// `MAX_PARTS` parts.
+    ///
+    /// The part size will never grow in exact buf write mode.
+    fn grow_buffer_size(&mut self) {
+        if self.op.content_length().is_some() || self.core.enable_exact_buf_write {
+            return;
+        }
 
-        self.core.sign(&mut req).await?;
+        let size = (self.written / PART_SIZE_GROWTH_FACTOR).min(MAX_PART_SIZE);
+        self.buffer_size = self.buffer_size.max(size as usize);
+    }
 
-        let resp = self.core.send(req).await?;
+    /// Take the next part from buffer and submit it.
+    ///
+    /// Parts are exactly `buffer_size` in exact buf write mode or while
+    /// checksum is enabled, otherwise they could be larger to avoid copying
+    /// the buffered bytes.
+    fn submit_next_part(&mut self, upload_id: &str) {
+        let (bs, checksum) = self.take_part(false);
+        self.written += bs.len() as u64;
+        self.grow_buffer_size();
 
-        let status = resp.status();
+        // AWS S3 requires part number must between [1..=10000]
+        self.part_number += 1;
+        self.submit_part(upload_id, self.part_number, bs, checksum);
+    }
 
-        match status {
-            StatusCode::OK => {
-                let etag = parse_etag(resp.headers())?
-                    .ok_or_else(|| {
-                        Error::new(
-                            ErrorKind::Unexpected,
-                            "ETag not present in returning response",
-                        )
-                    })?
-                    .to_string();
+    /// Submit all full parts in buffer, at most `write_concurrency` parts
+    /// will be uploading at the same time.
+    async fn submit_full_parts(&mut self, upload_id: &str) -> Result<()> {
+        while self.buffer.len() > self.buffer_size {
+            if self.futures.len() >= self.core.write_concurrency {
+                self.wait_part(upload_id).await?;
+            }
+            self.submit_next_part(upload_id);
+        }
+        Ok(())
+    }
 
-                resp.into_body().consume().await?;
+    /// Wait for one uploading part to finish.
+    ///
+    /// The failed part will be submitted again so that it could be retried
+    /// in the next call.
+    async fn wait_part(&mut self, upload_id: &str) -> Result<()> {
+        let (part_number, bs, checksum, res) = match self.futures.next().await {
+            Some(v) => v,
+            None => return Ok(()),
+        };
 
-                Ok(CompleteMultipartUploadRequestPart { part_number, etag })
+        match res {
+            Ok(part) => {
+                self.parts.push(part);
+                // The part has been uploaded, return its buffer back to
+                // the pool if nobody else holds it.
+                if let Ok(buf) = bs.try_into_mut() {
+                    self.pool.put(buf);
+                }
+                Ok(())
             }
-            _ => Err(parse_error(resp).await?),
+            Err(e) => {
+                self.submit_part(upload_id, part_number, bs, checksum);
+                Err(e)
+            }
+        }
+    }
+}
+
+async fn write_part(
+    core: &S3Core,
+    path: &str,
+    upload_id: &str,
+    part_number: usize,
+    bs: Bytes,
+    checksum: Option<String>,
+) -> Result<CompleteMultipartUploadRequestPart> {
+    let mut req = core.s3_upload_part_request(
+        path,
+        upload_id,
+        part_number,
+        Some(bs.len() as u64),
+        checksum.as_deref(),
+        AsyncBody::Bytes(bs),
+    )?;
+
+    core.sign(&mut req).await?;
+
+    let resp = core.send(req).await?;
+
+    let status = resp.status();
+
+    match status {
+        StatusCode::OK => {
+            let etag = parse_etag(resp.headers())?
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "ETag not present in returning response",
+                    )
+                })?
+                .to_string();
+
+            let mut part = CompleteMultipartUploadRequestPart {
+                part_number,
+                etag,
+                ..Default::default()
+            };
+
+            // CompleteMultipartUpload requires the checksum of every part
+            // if checksum algorithm has been declared while initiating.
+            if let (Some(algo), Some(checksum)) = (core.checksum_algorithm, checksum) {
+                let checksum = match resp
+                    .headers()
+                    .get(algo.header_name())
+                    .and_then(|v| v.to_str().ok())
+                {
+                    Some(v) => v.to_string(),
+                    None => checksum,
+                };
+                match algo {
+                    ChecksumAlgorithm::Crc32c => part.checksum_crc32c = Some(checksum),
+                    ChecksumAlgorithm::Sha256 => part.checksum_sha256 = Some(checksum),
+                }
+            }
+
+            resp.into_body().consume().await?;
+
+            Ok(part)
         }
+        _ => Err(parse_error(resp).await?),
     }
 }
 
@@ -151,45 +398,42 @@ impl S3Writer {
 impl oio::Write for S3Writer {
     async fn write(&mut self, bs: Bytes) -> Result<()> {
         let upload_id = match &self.upload_id {
-            Some(upload_id) => upload_id,
+            Some(upload_id) => upload_id.to_string(),
             None => {
                 if self.op.content_length().unwrap_or_default() == bs.len() as u64 {
                     return self.write_oneshot(bs).await;
                 } else {
                     let upload_id = self.initiate_upload().await?;
-                    self.upload_id = Some(upload_id);
-                    self.upload_id.as_deref().unwrap()
+                    self.upload_id = Some(upload_id.clone());
+                    upload_id
                 }
             }
         };
 
+        // Submit full parts left by previous writes and make sure we have
+        // room for the next part before accepting the given bs, so that
+        // write is re-enter safe.
+        self.submit_full_parts(&upload_id).await?;
+        if self.futures.len() >= self.core.write_concurrency {
+            self.wait_part(&upload_id).await?;
+        }
+
         // Ignore empty bytes
         if bs.is_empty() {
             return Ok(());
         }
 
+        self.feed_checksum(&bs);
         self.buffer.push(bs);
-        // Return directly if the buffer is not full
-        if self.buffer.len() <= self.buffer_size {
-            return Ok(());
+        // Submit as many full parts as we have room for without waiting,
+        // the rest will be submitted by the next write or close.
+        while self.buffer.len() > self.buffer_size
+            && self.futures.len() < self.core.write_concurrency
+        {
+            self.submit_next_part(&upload_id);
         }
 
-        let bs = self.buffer.peak_at_least(self.buffer_size);
-        let size = bs.len();
-
-        match self.write_part(upload_id, bs).await {
-            Ok(part) => {
-                self.buffer.take(size);
-                self.parts.push(part);
-                Ok(())
-            }
-            Err(e) => {
-                // If the upload fails, we should pop the given bs to make sure
-                // write is re-enter safe.
-                self.buffer.pop();
-                Err(e)
-            }
-        }
+        Ok(())
     }
 
     async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
@@ -206,6 +450,9 @@ impl oio::Write for S3Writer {
             return Ok(());
         };
 
+        // Drop all uploading parts.
+        self.futures = FuturesUnordered::new();
+
         let resp = self
             .core
             .s3_abort_multipart_upload(&self.path, upload_id)
@@ -222,29 +469,36 @@ impl oio::Write for S3Writer {
 
     async fn close(&mut self) -> Result<()> {
         let upload_id = if let Some(upload_id) = &self.upload_id {
-            upload_id
+            upload_id.to_string()
         } else {
             return Ok(());
         };
 
-        // Make sure internal buffer has been flushed.
-        if !self.buffer.is_empty() {
-            let bs = self.buffer.peak_exact(self.buffer.len());
+        self.submit_full_parts(&upload_id).await?;
 
-            match self.write_part(upload_id, bs).await {
-                Ok(part) => {
-                    self.buffer.clear();
-                    self.parts.push(part);
-                }
-                Err(e) => {
-                    return Err(e);
-                }
+        // Make sure internal buffer has been flushed.
+        while !self.buffer.is_empty() {
+            if self.futures.len() >= self.core.write_concurrency {
+                self.wait_part(&upload_id).await?;
             }
+
+            let (bs, checksum) = self.take_part(true);
+
+            self.part_number += 1;
+            self.submit_part(&upload_id, self.part_number, bs, checksum);
+        }
+
+        // Wait for all parts to finish.
+        while !self.futures.is_empty() {
+            self.wait_part(&upload_id).await?;
         }
 
+        // Parts could be finished out of order.
+        self.parts.sort_by_key(|v| v.part_number);
+
         let resp = self
             .core
-            .s3_complete_multipart_upload(&self.path, upload_id, &self.parts)
+            .s3_complete_multipart_upload(&self.path, &upload_id, &self.parts)
             .await?;
 
         let status = resp.status();
@@ -0,0 +1,335 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+use tokio::sync::Mutex;
+
+use super::core::FileDetail;
+use super::core::SeafileCore;
+use super::core::SeafileSigner;
+use super::error::parse_error;
+use super::pager::SeafilePager;
+use super::writer::SeafileWriter;
+use crate::raw::*;
+use crate::*;
+
+/// [Seafile](https://www.seafile.com) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct SeafileBuilder {
+    root: Option<String>,
+    endpoint: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    repo_name: Option<String>,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for SeafileBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+
+        ds.field("root", &self.root);
+        ds.field("endpoint", &self.endpoint);
+        ds.field("username", &self.username);
+        ds.field("repo_name", &self.repo_name);
+        if self.password.is_some() {
+            ds.field("password", &"<redacted>");
+        }
+        ds.finish()
+    }
+}
+
+impl SeafileBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+        self
+    }
+
+    /// Set the endpoint of seafile server.
+    ///
+    /// For example: `https://seafile.example.com`
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+        self
+    }
+
+    /// Set the username, which is the email of account in most cases.
+    pub fn username(&mut self, username: &str) -> &mut Self {
+        if !username.is_empty() {
+            self.username = Some(username.to_string())
+        }
+        self
+    }
+
+    /// Set the password of account.
+    pub fn password(&mut self, password: &str) -> &mut Self {
+        if !password.is_empty() {
+            self.password = Some(password.to_string())
+        }
+        self
+    }
+
+    /// Set the name of library (repo) to work with.
+    pub fn repo_name(&mut self, repo_name: &str) -> &mut Self {
+        if !repo_name.is_empty() {
+            self.repo_name = Some(repo_name.to_string())
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for SeafileBuilder {
+    const SCHEME: Scheme = Scheme::Seafile;
+    type Accessor = SeafileBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = SeafileBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("username").map(|v| builder.username(v));
+        map.get("password").map(|v| builder.password(v));
+        map.get("repo_name").map(|v| builder.repo_name(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        debug!("backend build started: {:?}", self);
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let endpoint = self.endpoint.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Seafile)
+        })?;
+        debug!("backend use endpoint {}", endpoint);
+
+        let username = self.username.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "username is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Seafile)
+        })?;
+        let password = self.password.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "password is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Seafile)
+        })?;
+
+        let repo_name = self.repo_name.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "repo_name is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Seafile)
+        })?;
+        debug!("backend use repo_name {}", repo_name);
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Seafile)
+            })?
+        };
+
+        debug!("backend build finished");
+        Ok(SeafileBackend {
+            core: Arc::new(SeafileCore {
+                root,
+                endpoint,
+                username,
+                password,
+                repo_name,
+                signer: Mutex::new(SeafileSigner::default()),
+                client,
+            }),
+        })
+    }
+}
+
+/// Backend for Seafile services.
+#[derive(Debug, Clone)]
+pub struct SeafileBackend {
+    core: Arc<SeafileCore>,
+}
+
+#[async_trait]
+impl Accessor for SeafileBackend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = SeafileWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = SeafilePager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Seafile)
+            .set_root(&self.core.root)
+            .set_name(&self.core.repo_name)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+
+                create_dir: true,
+                delete: true,
+
+                list: true,
+                list_with_delimiter_slash: true,
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        let resp = self.core.seafile_create_dir(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::CREATED => {
+                resp.into_body().consume().await?;
+                Ok(RpCreateDir::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let resp = self.core.seafile_download(path, args.range()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if args.content_length().is_none() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write without content length is not supported",
+            ));
+        }
+
+        Ok((
+            RpWrite::default(),
+            SeafileWriter::new(self.core.clone(), path.to_string()),
+        ))
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        if path.ends_with('/') {
+            let resp = self.core.seafile_dir_detail(path).await?;
+
+            return match resp.status() {
+                StatusCode::OK => {
+                    resp.into_body().consume().await?;
+                    Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
+                }
+                _ => Err(parse_error(resp).await?),
+            };
+        }
+
+        let resp = self.core.seafile_file_detail(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let detail: FileDetail =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+                Ok(RpStat::new(detail.into_metadata()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.seafile_delete(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((
+            RpList::default(),
+            SeafilePager::new(self.core.clone(), path),
+        ))
+    }
+}
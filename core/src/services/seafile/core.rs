@@ -0,0 +1,425 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use bytes::Bytes;
+use chrono::TimeZone;
+use chrono::Utc;
+use http::header;
+use http::HeaderValue;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct SeafileCore {
+    pub root: String,
+    pub endpoint: String,
+    pub username: String,
+    pub password: String,
+    /// The name of the library (repo) we are working on.
+    pub repo_name: String,
+
+    pub signer: Mutex<SeafileSigner>,
+    pub client: HttpClient,
+}
+
+impl Debug for SeafileCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SeafileCore")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .field("username", &self.username)
+            .field("repo_name", &self.repo_name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// SeafileSigner caches the auth token and the id of library, both of
+/// them are fetched at the first request.
+#[derive(Default)]
+pub struct SeafileSigner {
+    pub auth_token: String,
+    pub repo_id: String,
+}
+
+impl SeafileCore {
+    #[inline]
+    pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        self.client.send(req).await
+    }
+
+    /// Get the auth token and the id of library.
+    ///
+    /// Ref: <https://download.seafile.com/published/web-api/home.md>
+    async fn auth_info(&self) -> Result<(String, String)> {
+        let mut signer = self.signer.lock().await;
+
+        if signer.auth_token.is_empty() {
+            let body = format!(
+                "username={}&password={}",
+                utf8_percent_encode(&self.username, NON_ALPHANUMERIC),
+                utf8_percent_encode(&self.password, NON_ALPHANUMERIC)
+            );
+
+            let req = Request::post(format!("{}/api2/auth-token/", self.endpoint))
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .header(header::CONTENT_LENGTH, body.len())
+                .body(AsyncBody::Bytes(Bytes::from(body)))
+                .map_err(new_request_build_error)?;
+
+            let resp = self.send(req).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let output: AuthTokenOutput =
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+            signer.auth_token = output.token;
+        }
+
+        if signer.repo_id.is_empty() {
+            let mut req = Request::get(format!("{}/api2/repos/", self.endpoint))
+                .body(AsyncBody::Empty)
+                .map_err(new_request_build_error)?;
+            sign(&mut req, &signer.auth_token)?;
+
+            let resp = self.send(req).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let repos: Vec<RepoInfo> =
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+            signer.repo_id = repos
+                .into_iter()
+                .find(|v| v.name == self.repo_name)
+                .map(|v| v.id)
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::ConfigInvalid, "library is not found")
+                        .with_context("service", Scheme::Seafile)
+                        .with_context("repo_name", &self.repo_name)
+                })?;
+        }
+
+        Ok((signer.auth_token.clone(), signer.repo_id.clone()))
+    }
+
+    /// Build the absolute path in library, which always starts with `/`
+    /// and never ends with `/`.
+    fn library_path(&self, path: &str) -> String {
+        let p = build_abs_path(&self.root, path);
+
+        format!("/{}", p.trim_end_matches('/'))
+    }
+
+    async fn seafile_get(&self, url: &str, token: &str) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::get(url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        sign(&mut req, token)?;
+
+        self.send(req).await
+    }
+
+    /// Fetch a link returned in the form of json string, like download
+    /// link and upload link.
+    async fn seafile_link(&self, url: &str, token: &str) -> Result<String> {
+        let resp = self.seafile_get(url, token).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        serde_json::from_slice(&bs).map_err(new_json_deserialize_error)
+    }
+
+    pub async fn seafile_download(
+        &self,
+        path: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let (token, repo_id) = self.auth_info().await?;
+
+        let url = format!(
+            "{}/api2/repos/{repo_id}/file/?p={}&reuse=1",
+            self.endpoint,
+            utf8_percent_encode(&self.library_path(path), NON_ALPHANUMERIC)
+        );
+        let link = self.seafile_link(&url, &token).await?;
+
+        let mut req = Request::get(&link);
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    /// Upload file via upload link, the parent dirs will be created
+    /// automatically by `relative_path`.
+    ///
+    /// Ref: <https://download.seafile.com/published/web-api/v2.1/file-upload.md>
+    pub async fn seafile_upload(
+        &self,
+        path: &str,
+        bs: Bytes,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let (token, repo_id) = self.auth_info().await?;
+
+        let url = format!("{}/api2/repos/{repo_id}/upload-link/?p=/", self.endpoint);
+        let link = self.seafile_link(&url, &token).await?;
+
+        let p = self.library_path(path);
+        let (parent, name) = p.rsplit_once('/').unwrap_or(("", &p));
+
+        let file = FormDataPart::new("file")
+            .header(
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_str(&format!(
+                    "form-data; name=\"file\"; filename=\"{}\"",
+                    name.replace('"', "\\\"")
+                ))
+                .map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "file name is invalid").set_source(err)
+                })?,
+            )
+            .content(bs);
+
+        let multipart = Multipart::new()
+            .part(FormDataPart::new("parent_dir").content("/"))
+            .part(
+                FormDataPart::new("relative_path")
+                    .content(parent.trim_start_matches('/').to_string()),
+            )
+            .part(FormDataPart::new("replace").content("1"))
+            .part(file);
+
+        let mut req = multipart.apply(Request::post(format!("{link}?ret-json=1")))?;
+        sign(&mut req, &token)?;
+
+        self.send(req).await
+    }
+
+    pub async fn seafile_file_detail(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let (token, repo_id) = self.auth_info().await?;
+
+        let url = format!(
+            "{}/api2/repos/{repo_id}/file/detail/?p={}",
+            self.endpoint,
+            utf8_percent_encode(&self.library_path(path), NON_ALPHANUMERIC)
+        );
+
+        self.seafile_get(&url, &token).await
+    }
+
+    pub async fn seafile_dir_detail(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let (token, repo_id) = self.auth_info().await?;
+
+        let url = format!(
+            "{}/api/v2.1/repos/{repo_id}/dir/detail/?path={}",
+            self.endpoint,
+            utf8_percent_encode(&self.library_path(path), NON_ALPHANUMERIC)
+        );
+
+        self.seafile_get(&url, &token).await
+    }
+
+    pub async fn seafile_list_dir(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let (token, repo_id) = self.auth_info().await?;
+
+        let url = format!(
+            "{}/api2/repos/{repo_id}/dir/?p={}",
+            self.endpoint,
+            utf8_percent_encode(&self.library_path(path), NON_ALPHANUMERIC)
+        );
+
+        self.seafile_get(&url, &token).await
+    }
+
+    pub async fn seafile_create_dir(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let (token, repo_id) = self.auth_info().await?;
+
+        let url = format!(
+            "{}/api2/repos/{repo_id}/dir/?p={}",
+            self.endpoint,
+            utf8_percent_encode(&self.library_path(path), NON_ALPHANUMERIC)
+        );
+
+        let body = "operation=mkdir&create_parents=true";
+        let mut req = Request::post(&url)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(AsyncBody::Bytes(Bytes::from(body)))
+            .map_err(new_request_build_error)?;
+        sign(&mut req, &token)?;
+
+        self.send(req).await
+    }
+
+    pub async fn seafile_delete(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let (token, repo_id) = self.auth_info().await?;
+
+        let kind = if path.ends_with('/') { "dir" } else { "file" };
+        let url = format!(
+            "{}/api2/repos/{repo_id}/{kind}/?p={}",
+            self.endpoint,
+            utf8_percent_encode(&self.library_path(path), NON_ALPHANUMERIC)
+        );
+
+        let mut req = Request::delete(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        sign(&mut req, &token)?;
+
+        self.send(req).await
+    }
+}
+
+fn sign(req: &mut Request<AsyncBody>, token: &str) -> Result<()> {
+    req.headers_mut().insert(
+        header::AUTHORIZATION,
+        HeaderValue::from_str(&format!("Token {token}")).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "auth token is invalid").set_source(err)
+        })?,
+    );
+
+    Ok(())
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct AuthTokenOutput {
+    token: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct RepoInfo {
+    id: String,
+    name: String,
+}
+
+/// Ref: <https://download.seafile.com/published/web-api/v2.1/file.md>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct FileDetail {
+    pub id: String,
+    pub size: u64,
+    /// The last modified time in unix timestamp.
+    pub mtime: i64,
+}
+
+impl FileDetail {
+    pub fn into_metadata(self) -> Metadata {
+        let mut m = Metadata::new(EntryMode::FILE)
+            .with_content_length(self.size)
+            .with_etag(self.id);
+        if let Some(v) = Utc.timestamp_opt(self.mtime, 0).single() {
+            m.set_last_modified(v);
+        }
+        m
+    }
+}
+
+/// Ref: <https://download.seafile.com/published/web-api/v2.1/directories.md>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct DirEntry {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub id: String,
+    pub name: String,
+    pub size: u64,
+    /// The last modified time in unix timestamp.
+    pub mtime: i64,
+}
+
+impl DirEntry {
+    pub fn is_dir(&self) -> bool {
+        self.type_ == "dir"
+    }
+
+    pub fn into_metadata(self) -> Metadata {
+        if self.is_dir() {
+            return Metadata::new(EntryMode::DIR);
+        }
+
+        FileDetail {
+            id: self.id,
+            size: self.size,
+            mtime: self.mtime,
+        }
+        .into_metadata()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_dir_entries() {
+        let content = r#"[
+            {
+                "permission": "rw",
+                "mtime": 1398148877,
+                "type": "dir",
+                "name": "sub",
+                "id": "0000000000000000000000000000000000000000"
+            },
+            {
+                "id": "0a8d8e8f8a3b0ec8e4d3e5a1ba6fc8a4c2b7f5f9",
+                "mtime": 1398148877,
+                "type": "file",
+                "name": "a.txt",
+                "size": 1024
+            }
+        ]"#;
+
+        let entries: Vec<DirEntry> = serde_json::from_str(content).expect("must be valid");
+        assert!(entries[0].is_dir());
+        assert_eq!(entries[0].name, "sub");
+
+        assert!(!entries[1].is_dir());
+        let meta = entries.into_iter().nth(1).unwrap().into_metadata();
+        assert_eq!(meta.content_length(), 1024);
+        assert_eq!(
+            meta.etag(),
+            Some("0a8d8e8f8a3b0ec8e4d3e5a1ba6fc8a4c2b7f5f9")
+        );
+        assert_eq!(
+            meta.last_modified(),
+            Some(Utc.timestamp_opt(1398148877, 0).unwrap())
+        );
+    }
+}
@@ -0,0 +1,117 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// SeafileError is the error returned by Seafile Web API.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct SeafileError {
+    error_msg: Option<String>,
+    detail: Option<String>,
+}
+
+/// Parse error response into Error.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    let (kind, retryable) = match parts.status {
+        StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let message = match serde_json::from_slice::<SeafileError>(&bs) {
+        Ok(SeafileError {
+            error_msg: Some(msg),
+            ..
+        })
+        | Ok(SeafileError {
+            detail: Some(msg), ..
+        }) => msg,
+        _ => String::from_utf8_lossy(&bs).into_owned(),
+    };
+
+    let mut err = Error::new(kind, &message).with_context("response", format!("{parts:?}"));
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::raw::oio::into_stream;
+
+    #[tokio::test]
+    async fn test_parse_error() -> Result<()> {
+        let cases = vec![
+            (
+                404,
+                r#"{"error_msg": "File not found"}"#,
+                ErrorKind::NotFound,
+                "File not found",
+            ),
+            (
+                401,
+                r#"{"detail": "Invalid token"}"#,
+                ErrorKind::PermissionDenied,
+                "Invalid token",
+            ),
+            (
+                503,
+                "Service Unavailable",
+                ErrorKind::Unexpected,
+                "Service Unavailable",
+            ),
+        ];
+
+        for (status, body, kind, message) in cases {
+            let body = IncomingAsyncBody::new(
+                Box::new(into_stream::from_futures_stream(stream::iter(vec![Ok(
+                    bytes::Bytes::from(body),
+                )]))),
+                None,
+            );
+            let resp = Response::builder().status(status).body(body).unwrap();
+
+            let err = parse_error(resp).await?;
+            assert_eq!(err.kind(), kind);
+            assert!(err.to_string().contains(message));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::core::DirEntry;
+use super::core::SeafileCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// SeafilePager lists a dir via directory API.
+///
+/// Directory API returns all entries in one response, so there is only
+/// one page.
+pub struct SeafilePager {
+    core: Arc<SeafileCore>,
+    path: String,
+    done: bool,
+}
+
+impl SeafilePager {
+    pub fn new(core: Arc<SeafileCore>, path: &str) -> Self {
+        SeafilePager {
+            core,
+            path: path.to_string(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for SeafilePager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+        self.done = true;
+
+        let resp = self.core.seafile_list_dir(&self.path).await?;
+
+        let entries: Vec<DirEntry> = match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?
+            }
+            // Listing a not exist dir should return empty.
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                return Ok(None);
+            }
+            _ => return Err(parse_error(resp).await?),
+        };
+
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                let mut path = format!("{}{}", self.path.trim_start_matches('/'), entry.name);
+                if entry.is_dir() {
+                    path.push('/');
+                }
+
+                oio::Entry::new(&path, entry.into_metadata())
+            })
+            .collect();
+
+        Ok(Some(entries))
+    }
+}
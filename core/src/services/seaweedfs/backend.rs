@@ -0,0 +1,288 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+
+use super::core::SeaweedfsCore;
+use super::core::SeaweedfsEntry;
+use super::error::parse_error;
+use super::pager::SeaweedfsPager;
+use super::writer::SeaweedfsWriter;
+use crate::raw::*;
+use crate::*;
+
+/// [SeaweedFS](https://github.com/seaweedfs/seaweedfs) services support via
+/// [Filer HTTP API](https://github.com/seaweedfs/seaweedfs/wiki/Filer-Server-API).
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct SeaweedfsBuilder {
+    root: Option<String>,
+    endpoint: Option<String>,
+    token: Option<String>,
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for SeaweedfsBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Builder")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+impl SeaweedfsBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string());
+        }
+        self
+    }
+
+    /// Set the endpoint of filer, like `http://127.0.0.1:8888`.
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        }
+        self
+    }
+
+    /// Set the JWT token used to access filer.
+    ///
+    /// Required if `jwt.filer_signing.key` is set in `security.toml` of
+    /// filer.
+    pub fn token(&mut self, token: &str) -> &mut Self {
+        if !token.is_empty() {
+            self.token = Some(token.to_string());
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for SeaweedfsBuilder {
+    const SCHEME: Scheme = Scheme::Seaweedfs;
+    type Accessor = SeaweedfsBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = SeaweedfsBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("token").map(|v| builder.token(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let endpoint = self.endpoint.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Seaweedfs)
+        })?;
+        debug!("backend use endpoint {}", endpoint);
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Seaweedfs)
+            })?
+        };
+
+        Ok(SeaweedfsBackend {
+            core: Arc::new(SeaweedfsCore {
+                root,
+                endpoint,
+                token: self.token.take(),
+                client,
+            }),
+        })
+    }
+}
+
+/// Backend for SeaweedFS services.
+#[derive(Clone, Debug)]
+pub struct SeaweedfsBackend {
+    core: Arc<SeaweedfsCore>,
+}
+
+#[async_trait]
+impl Accessor for SeaweedfsBackend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = SeaweedfsWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = SeaweedfsPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut ma = AccessorInfo::default();
+        ma.set_scheme(Scheme::Seaweedfs)
+            .set_root(&self.core.root)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_without_content_length: true,
+                write_with_content_type: true,
+
+                create_dir: true,
+                delete: true,
+                rename: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_delimiter_slash: true,
+
+                ..Default::default()
+            });
+
+        ma
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        let resp = self.core.seaweedfs_create_dir(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::CREATED => {
+                resp.into_body().consume().await?;
+                Ok(RpCreateDir::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let resp = self.core.seaweedfs_read(path, args.range()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Ok((
+            RpWrite::default(),
+            SeaweedfsWriter::new(self.core.clone(), args, path.to_string()),
+        ))
+    }
+
+    async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
+        let resp = self.core.seaweedfs_rename(from, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::CREATED | StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+                Ok(RpRename::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let resp = self.core.seaweedfs_get_metadata(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let entry: SeaweedfsEntry =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+                if !entry.is_dir() && path.ends_with('/') {
+                    return Err(Error::new(
+                        ErrorKind::NotFound,
+                        "the path is a file but a dir is expected",
+                    ));
+                }
+
+                Ok(RpStat::new(entry.to_metadata()?))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.seaweedfs_delete(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK
+            | StatusCode::ACCEPTED
+            | StatusCode::NO_CONTENT
+            | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((
+            RpList::default(),
+            SeaweedfsPager::new(self.core.clone(), path, args.limit()),
+        ))
+    }
+}
@@ -0,0 +1,315 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use bytes::Bytes;
+use http::header;
+use http::Request;
+use http::Response;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::*;
+
+/// The dir bit of `os.FileMode` in golang.
+const GO_MODE_DIR: u32 = 1 << 31;
+
+pub struct SeaweedfsCore {
+    pub root: String,
+    pub endpoint: String,
+    pub token: Option<String>,
+    pub client: HttpClient,
+}
+
+impl Debug for SeaweedfsCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SeaweedfsCore")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SeaweedfsCore {
+    fn sign(&self, req: &mut Request<AsyncBody>) -> Result<()> {
+        if let Some(token) = &self.token {
+            req.headers_mut().insert(
+                header::AUTHORIZATION,
+                format_authorization_by_bearer(token)?
+                    .parse()
+                    .map_err(|_| Error::new(ErrorKind::Unexpected, "invalid token"))?,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn build_url(&self, path: &str) -> String {
+        let p = build_rooted_abs_path(&self.root, path);
+
+        format!("{}{}", self.endpoint, percent_encode_path(&p))
+    }
+
+    /// Get the metadata of an entry via `?metadata=true`.
+    pub async fn seaweedfs_get_metadata(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}?metadata=true",
+            self.build_url(path.trim_end_matches('/'))
+        );
+
+        let mut req = Request::get(&url)
+            .header(header::ACCEPT, "application/json")
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn seaweedfs_read(
+        &self,
+        path: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::get(self.build_url(path));
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    /// Create or truncate the file with given content.
+    pub async fn seaweedfs_put(
+        &self,
+        path: &str,
+        args: &OpWrite,
+        bs: Bytes,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::put(self.build_url(path)).header(header::CONTENT_LENGTH, bs.len());
+        if let Some(content_type) = args.content_type() {
+            req = req.header(header::CONTENT_TYPE, content_type);
+        }
+
+        let mut req = req
+            .body(AsyncBody::Bytes(bs))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    /// Append content to the end of the file.
+    pub async fn seaweedfs_append(
+        &self,
+        path: &str,
+        bs: Bytes,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}?op=append", self.build_url(path));
+
+        let mut req = Request::post(&url)
+            .header(header::CONTENT_LENGTH, bs.len())
+            .body(AsyncBody::Bytes(bs))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    /// Filer creates a dir while posting an empty body to path ends with `/`.
+    pub async fn seaweedfs_create_dir(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::post(self.build_url(path))
+            .header(header::CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn seaweedfs_delete(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}?recursive=true",
+            self.build_url(path.trim_end_matches('/'))
+        );
+
+        let mut req = Request::delete(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn seaweedfs_rename(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let from = build_rooted_abs_path(&self.root, from);
+
+        let url = format!(
+            "{}?mv.from={}",
+            self.build_url(to),
+            percent_encode_path(&from)
+        );
+
+        let mut req = Request::post(&url)
+            .header(header::CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn seaweedfs_list(
+        &self,
+        path: &str,
+        limit: usize,
+        last_file_name: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut url = format!("{}?limit={limit}", self.build_url(path));
+        if !last_file_name.is_empty() {
+            url.push_str(&format!(
+                "&lastFileName={}",
+                percent_encode_path(last_file_name)
+            ));
+        }
+
+        let mut req = Request::get(&url)
+            .header(header::ACCEPT, "application/json")
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+}
+
+/// The entry returned by filer.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct SeaweedfsEntry {
+    pub full_path: String,
+    pub mtime: String,
+    pub mode: u32,
+    pub mime: String,
+    pub file_size: u64,
+}
+
+impl SeaweedfsEntry {
+    pub fn is_dir(&self) -> bool {
+        self.mode & GO_MODE_DIR != 0
+    }
+
+    pub fn to_metadata(&self) -> Result<Metadata> {
+        let mut meta = if self.is_dir() {
+            Metadata::new(EntryMode::DIR)
+        } else {
+            let mut meta = Metadata::new(EntryMode::FILE);
+            meta.set_content_length(self.file_size);
+            if !self.mime.is_empty() {
+                meta.set_content_type(&self.mime);
+            }
+            meta
+        };
+        if !self.mtime.is_empty() {
+            meta.set_last_modified(parse_datetime_from_rfc3339(&self.mtime)?);
+        }
+
+        Ok(meta)
+    }
+}
+
+/// The response of listing a dir.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct SeaweedfsListResponse {
+    pub path: String,
+    pub entries: Option<Vec<SeaweedfsEntry>>,
+    pub last_file_name: String,
+    pub should_display_load_more: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list_response() -> Result<()> {
+        let bs = r#"{
+            "Path": "/buckets/test",
+            "Entries": [
+                {
+                    "FullPath": "/buckets/test/dir",
+                    "Mtime": "2023-07-20T10:11:12+08:00",
+                    "Crtime": "2023-07-20T10:11:12+08:00",
+                    "Mode": 2147484141,
+                    "Uid": 0,
+                    "Gid": 0,
+                    "Mime": "",
+                    "TtlSec": 0,
+                    "FileSize": 0
+                },
+                {
+                    "FullPath": "/buckets/test/file.txt",
+                    "Mtime": "2023-07-20T10:11:13.123456789+08:00",
+                    "Crtime": "2023-07-20T10:11:13+08:00",
+                    "Mode": 432,
+                    "Uid": 0,
+                    "Gid": 0,
+                    "Mime": "text/plain",
+                    "TtlSec": 0,
+                    "FileSize": 42
+                }
+            ],
+            "Limit": 2,
+            "LastFileName": "file.txt",
+            "ShouldDisplayLoadMore": true
+        }"#;
+
+        let output: SeaweedfsListResponse =
+            serde_json::from_str(bs).map_err(new_json_deserialize_error)?;
+        assert!(output.should_display_load_more);
+        assert_eq!(output.last_file_name, "file.txt");
+
+        let entries = output.entries.unwrap_or_default();
+        assert!(entries[0].is_dir());
+        assert_eq!(entries[0].to_metadata()?.mode(), EntryMode::DIR);
+
+        let meta = entries[1].to_metadata()?;
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), 42);
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(
+            meta.last_modified(),
+            Some(parse_datetime_from_rfc3339(
+                "2023-07-20T02:11:13.123456789Z"
+            )?)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::core::SeaweedfsCore;
+use super::core::SeaweedfsListResponse;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The default number of entries returned by one list request.
+const SEAWEEDFS_LIST_LIMIT: usize = 1000;
+
+pub struct SeaweedfsPager {
+    core: Arc<SeaweedfsCore>,
+    path: String,
+    limit: usize,
+
+    last_file_name: String,
+    done: bool,
+}
+
+impl SeaweedfsPager {
+    pub fn new(core: Arc<SeaweedfsCore>, path: &str, limit: Option<usize>) -> Self {
+        SeaweedfsPager {
+            core,
+            path: path.to_string(),
+            limit: limit.unwrap_or(SEAWEEDFS_LIST_LIMIT),
+
+            last_file_name: String::new(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for SeaweedfsPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let resp = self
+            .core
+            .seaweedfs_list(&self.path, self.limit, &self.last_file_name)
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {}
+            // Listing a not exist dir should return empty.
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                self.done = true;
+                return Ok(None);
+            }
+            _ => return Err(parse_error(resp).await?),
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: SeaweedfsListResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        let entries = output.entries.unwrap_or_default();
+        if !output.should_display_load_more || entries.is_empty() {
+            self.done = true;
+        }
+        self.last_file_name = output.last_file_name;
+
+        let mut res = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let mut path = build_rel_path(&self.core.root, &entry.full_path);
+            if entry.is_dir() {
+                path.push('/');
+            }
+
+            res.push(oio::Entry::new(&path, entry.to_metadata()?));
+        }
+
+        Ok(Some(res))
+    }
+}
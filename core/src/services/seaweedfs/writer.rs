@@ -0,0 +1,145 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::StatusCode;
+
+use super::core::SeaweedfsCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The size of each chunk sent to filer while writing without
+/// content length.
+const SEAWEEDFS_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// SeaweedfsWriter creates the file with the first chunk, and appends the
+/// following chunks via `?op=append`.
+pub struct SeaweedfsWriter {
+    core: Arc<SeaweedfsCore>,
+    op: OpWrite,
+    path: String,
+
+    created: bool,
+    buffer: oio::VectorCursor,
+}
+
+impl SeaweedfsWriter {
+    pub fn new(core: Arc<SeaweedfsCore>, op: OpWrite, path: String) -> Self {
+        SeaweedfsWriter {
+            core,
+            op,
+            path,
+
+            created: false,
+            buffer: oio::VectorCursor::new(),
+        }
+    }
+
+    async fn write_chunk(&mut self, bs: Bytes) -> Result<()> {
+        let resp = if self.created {
+            self.core.seaweedfs_append(&self.path, bs).await?
+        } else {
+            self.core.seaweedfs_put(&self.path, &self.op, bs).await?
+        };
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK | StatusCode::CREATED => {
+                resp.into_body().consume().await?;
+                self.created = true;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Write for SeaweedfsWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        if !self.created && self.buffer.is_empty() {
+            if let Some(size) = self.op.content_length() {
+                if size == bs.len() as u64 {
+                    return self.write_chunk(bs).await;
+                }
+            }
+        }
+
+        // Ignore empty bytes
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.buffer.push(bs);
+        // Return directly if the buffer is not full
+        if self.buffer.len() < SEAWEEDFS_CHUNK_SIZE {
+            return Ok(());
+        }
+
+        let chunk = self.buffer.peak_exact(SEAWEEDFS_CHUNK_SIZE);
+        match self.write_chunk(chunk).await {
+            Ok(()) => {
+                self.buffer.take(SEAWEEDFS_CHUNK_SIZE);
+                Ok(())
+            }
+            Err(e) => {
+                // If the upload fails, we should pop the given bs to make sure
+                // write is re-enter safe.
+                self.buffer.pop();
+                Err(e)
+            }
+        }
+    }
+
+    async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "Write::sink is not supported",
+        ))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.buffer.clear();
+
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        // The file has been written via oneshot.
+        if self.created && self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        // Create an empty file if nothing has been written.
+        if self.buffer.is_empty() {
+            return self.write_chunk(Bytes::new()).await;
+        }
+
+        while !self.buffer.is_empty() {
+            let size = self.buffer.len().min(SEAWEEDFS_CHUNK_SIZE);
+            let chunk = self.buffer.peak_exact(size);
+            self.write_chunk(chunk).await?;
+            self.buffer.take(size);
+        }
+
+        Ok(())
+    }
+}
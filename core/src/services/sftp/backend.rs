@@ -197,6 +197,8 @@ impl Builder for SftpBuilder {
         map.get("key").map(|v| builder.key(v));
         map.get("known_hosts_strategy")
             .map(|v| builder.known_hosts_strategy(v));
+        map.get("enable_copy")
+            .map(|v| builder.enable_copy(v == "on" || v == "true"));
 
         builder
     }
@@ -25,6 +25,9 @@ use openssh_sftp_client::fs::ReadDir;
 use crate::raw::oio;
 use crate::Result;
 
+/// The max number of entries returned in one page.
+const DEFAULT_PAGE_SIZE: usize = 1000;
+
 pub struct SftpPager {
     dir: Pin<Box<ReadDir>>,
     prefix: String,
@@ -48,23 +51,29 @@ impl SftpPager {
 #[async_trait]
 impl oio::Page for SftpPager {
     async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
-        if self.limit == 0 {
-            return Ok(None);
-        }
+        let mut entries = Vec::new();
 
-        let item = self.dir.next().await;
+        // Collect entries in batch so that we don't need to poll the pager
+        // for every single entry, which is slow for large dirs.
+        while self.limit > 0 && entries.len() < DEFAULT_PAGE_SIZE {
+            match self.dir.next().await {
+                Some(Ok(e)) => {
+                    if e.filename().to_str() == Some(".") || e.filename().to_str() == Some("..") {
+                        continue;
+                    }
 
-        match item {
-            Some(Ok(e)) => {
-                if e.filename().to_str() == Some(".") || e.filename().to_str() == Some("..") {
-                    self.next().await
-                } else {
                     self.limit -= 1;
-                    Ok(Some(vec![map_entry(self.prefix.as_str(), e.clone())]))
+                    entries.push(map_entry(self.prefix.as_str(), e));
                 }
+                Some(Err(e)) => return Err(e.into()),
+                None => break,
             }
-            Some(Err(e)) => Err(e.into()),
-            None => Ok(None),
+        }
+
+        if entries.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(entries))
         }
     }
 }
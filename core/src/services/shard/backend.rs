@@ -0,0 +1,441 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use md5::Digest;
+use md5::Md5;
+
+use super::pager::ShardPager;
+use crate::raw::*;
+use crate::*;
+
+/// Default virtual nodes count of every shard on the hash ring.
+const DEFAULT_VIRTUAL_NODES: usize = 128;
+
+/// Shard service support which distributes paths across operators.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct ShardBuilder {
+    shards: Vec<(String, Operator)>,
+    virtual_nodes: Option<usize>,
+}
+
+impl ShardBuilder {
+    /// Add an operator as a shard with given name.
+    ///
+    /// The name decides which paths will be placed on this shard, so
+    /// it must be kept the same to find existing data.
+    pub fn shard(&mut self, name: &str, op: Operator) -> &mut Self {
+        self.shards.push((name.to_string(), op));
+        self
+    }
+
+    /// Set virtual nodes count of every shard on the hash ring.
+    ///
+    /// More virtual nodes will make paths distributed more evenly.
+    /// Changing this value will move paths between shards.
+    ///
+    /// Default to `128`.
+    pub fn virtual_nodes(&mut self, n: usize) -> &mut Self {
+        if n > 0 {
+            self.virtual_nodes = Some(n);
+        }
+        self
+    }
+}
+
+impl Builder for ShardBuilder {
+    const SCHEME: Scheme = Scheme::Shard;
+    type Accessor = ShardBackend;
+
+    /// Shard can't be built from map since it's composed by operators,
+    /// the returning builder doesn't have any shards.
+    fn from_map(_: HashMap<String, String>) -> Self {
+        Self::default()
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        if self.shards.is_empty() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "shard requires at least one shard",
+            )
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Shard));
+        }
+
+        let mut names = HashSet::new();
+        for (name, _) in &self.shards {
+            if !names.insert(name.as_str()) {
+                return Err(
+                    Error::new(ErrorKind::ConfigInvalid, "shard name is duplicated")
+                        .with_operation("Builder::build")
+                        .with_context("service", Scheme::Shard)
+                        .with_context("name", name),
+                );
+            }
+        }
+
+        let virtual_nodes = self.virtual_nodes.unwrap_or(DEFAULT_VIRTUAL_NODES);
+        let mut ring = Vec::with_capacity(self.shards.len() * virtual_nodes);
+        for (idx, (name, _)) in self.shards.iter().enumerate() {
+            for i in 0..virtual_nodes {
+                ring.push((hash(&format!("{name}#{i}")), idx));
+            }
+        }
+        ring.sort_unstable();
+
+        Ok(ShardBackend {
+            shards: self.shards.iter().map(|(_, v)| v.inner().clone()).collect(),
+            ring,
+        })
+    }
+}
+
+/// Hash the key into a point on the ring.
+///
+/// md5 is used instead of std hasher so that paths are placed the same
+/// across processes and rust versions.
+fn hash(key: &str) -> u64 {
+    let digest = Md5::digest(key.as_bytes());
+    let mut bs = [0; 8];
+    bs.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bs)
+}
+
+/// Backend is used to serve `Accessor` support for shard.
+#[derive(Debug, Clone)]
+pub struct ShardBackend {
+    shards: Vec<FusedAccessor>,
+    /// Points on the hash ring sorted by hash, with the index of their shard.
+    ring: Vec<(u64, usize)>,
+}
+
+impl ShardBackend {
+    /// Locate the shard of given path: the first point on the ring that
+    /// is not less than the path's hash, wrapping around to the start.
+    fn locate(&self, path: &str) -> &FusedAccessor {
+        let h = hash(path);
+        let idx = self.ring.partition_point(|(v, _)| *v < h);
+        let (_, shard) = self.ring[idx % self.ring.len()];
+        &self.shards[shard]
+    }
+}
+
+#[async_trait]
+impl Accessor for ShardBackend {
+    type Reader = oio::Reader;
+    type BlockingReader = oio::BlockingReader;
+    type Writer = oio::Writer;
+    type BlockingWriter = oio::BlockingWriter;
+    type Appender = oio::Appender;
+    type Pager = ShardPager<oio::Pager>;
+    type BlockingPager = ShardPager<oio::BlockingPager>;
+
+    fn info(&self) -> AccessorInfo {
+        let caps: Vec<Capability> = self.shards.iter().map(|v| v.info().capability()).collect();
+        let all = |f: fn(&Capability) -> bool| caps.iter().all(f);
+
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Shard)
+            .set_root("/")
+            .set_capability(Capability {
+                stat: all(|c| c.stat),
+                stat_has_permission: all(|c| c.stat_has_permission),
+                stat_has_user_metadata: all(|c| c.stat_has_user_metadata),
+
+                read: all(|c| c.read),
+                read_can_seek: all(|c| c.read_can_seek),
+                read_can_next: all(|c| c.read_can_next),
+                read_with_range: all(|c| c.read_with_range),
+
+                write: all(|c| c.write),
+                write_without_content_length: all(|c| c.write_without_content_length),
+                write_with_content_type: all(|c| c.write_with_content_type),
+                write_with_content_disposition: all(|c| c.write_with_content_disposition),
+                write_with_cache_control: all(|c| c.write_with_cache_control),
+                write_with_permission: all(|c| c.write_with_permission),
+                write_with_user_metadata: all(|c| c.write_with_user_metadata),
+                append: all(|c| c.append),
+                append_with_content_type: all(|c| c.append_with_content_type),
+                append_with_content_disposition: all(|c| c.append_with_content_disposition),
+                append_with_cache_control: all(|c| c.append_with_cache_control),
+                create_dir: all(|c| c.create_dir),
+                delete: all(|c| c.delete),
+
+                list: all(|c| c.list),
+                list_with_delimiter_slash: all(|c| c.list_with_delimiter_slash),
+                list_without_delimiter: all(|c| c.list_without_delimiter),
+
+                presign: all(|c| c.presign),
+                presign_read: all(|c| c.presign_read),
+                presign_stat: all(|c| c.presign_stat),
+                presign_write: all(|c| c.presign_write),
+
+                blocking: all(|c| c.blocking),
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        for shard in &self.shards {
+            shard.create_dir(path, args.clone()).await?;
+        }
+
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.locate(path).read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.locate(path).write(path, args).await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.locate(path).append(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !path.ends_with('/') {
+            return self.locate(path).stat(path, args).await;
+        }
+
+        let mut last_err = None;
+        for shard in &self.shards {
+            match shard.stat(path, args.clone()).await {
+                Err(err) if err.kind() == ErrorKind::NotFound => last_err = Some(err),
+                v => return v,
+            }
+        }
+
+        Err(last_err.expect("shard must have at least one shard"))
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        if !path.ends_with('/') {
+            return self.locate(path).delete(path, args).await;
+        }
+
+        for shard in &self.shards {
+            shard.delete(path, args.clone()).await?;
+        }
+
+        Ok(RpDelete::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let mut pagers = Vec::with_capacity(self.shards.len());
+        for shard in &self.shards {
+            match shard.list(path, args.clone()).await {
+                Ok((_, pager)) => pagers.push(pager),
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((RpList::default(), ShardPager::new(pagers)))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.locate(path).presign(path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        for shard in &self.shards {
+            shard.blocking_create_dir(path, args.clone())?;
+        }
+
+        Ok(RpCreateDir::default())
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.locate(path).blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.locate(path).blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !path.ends_with('/') {
+            return self.locate(path).blocking_stat(path, args);
+        }
+
+        let mut last_err = None;
+        for shard in &self.shards {
+            match shard.blocking_stat(path, args.clone()) {
+                Err(err) if err.kind() == ErrorKind::NotFound => last_err = Some(err),
+                v => return v,
+            }
+        }
+
+        Err(last_err.expect("shard must have at least one shard"))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        if !path.ends_with('/') {
+            return self.locate(path).blocking_delete(path, args);
+        }
+
+        for shard in &self.shards {
+            shard.blocking_delete(path, args.clone())?;
+        }
+
+        Ok(RpDelete::default())
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        let mut pagers = Vec::with_capacity(self.shards.len());
+        for shard in &self.shards {
+            match shard.blocking_list(path, args.clone()) {
+                Ok((_, pager)) => pagers.push(pager),
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((RpList::default(), ShardPager::new(pagers)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    fn new_memory() -> Operator {
+        Operator::new(Memory::default()).unwrap().finish()
+    }
+
+    #[tokio::test]
+    async fn test_distribute() {
+        let shards: Vec<Operator> = (0..3).map(|_| new_memory()).collect();
+
+        let mut builder = ShardBuilder::default();
+        for (i, shard) in shards.iter().enumerate() {
+            builder.shard(&format!("shard-{i}"), shard.clone());
+        }
+        let op = Operator::new(builder).unwrap().finish();
+
+        for i in 0..100 {
+            op.write(&format!("dir/{i}"), i.to_string()).await.unwrap();
+        }
+
+        // Every path is placed on exactly one shard.
+        let mut total = 0;
+        for shard in &shards {
+            let n = shard
+                .scan("dir/")
+                .await
+                .unwrap()
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap()
+                .len();
+            assert!(n > 0, "every shard should have some paths");
+            total += n;
+        }
+        assert_eq!(total, 100);
+
+        for i in 0..100 {
+            let bs = op.read(&format!("dir/{i}")).await.unwrap();
+            assert_eq!(bs, i.to_string().as_bytes());
+        }
+
+        let entries: Vec<_> = op.scan("dir/").await.unwrap().try_collect().await.unwrap();
+        assert_eq!(entries.len(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_dir() {
+        let shards: Vec<Operator> = (0..2).map(|_| new_memory()).collect();
+
+        let mut builder = ShardBuilder::default();
+        builder
+            .shard("a", shards[0].clone())
+            .shard("b", shards[1].clone());
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.create_dir("dir/sub/").await.unwrap();
+        for shard in &shards {
+            assert!(shard.is_exist("dir/sub/").await.unwrap());
+        }
+        assert!(op.stat("dir/sub/").await.unwrap().mode().is_dir());
+
+        let entries: Vec<_> = op.list("dir/").await.unwrap().try_collect().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path(), "dir/sub/");
+    }
+
+    #[test]
+    fn test_placement_stable() {
+        let new_backend = |names: &[&str]| {
+            let mut builder = ShardBuilder::default();
+            for name in names {
+                builder.shard(name, new_memory());
+            }
+            builder.build().unwrap()
+        };
+        let shard_of = |backend: &ShardBackend, path: &str| {
+            let target = backend.locate(path);
+            // Memory has no stable identity, compare by pointer instead.
+            backend
+                .shards
+                .iter()
+                .position(|v| std::ptr::eq(v, target))
+                .unwrap()
+        };
+
+        let before = new_backend(&["a", "b", "c"]);
+        let reordered = new_backend(&["c", "a", "b"]);
+        let added = new_backend(&["a", "b", "c", "d"]);
+
+        for i in 0..100 {
+            let path = format!("file-{i}");
+            let idx = shard_of(&before, &path);
+            let name = ["a", "b", "c"][idx];
+
+            // Reordering shards won't move paths.
+            assert_eq!(["c", "a", "b"][shard_of(&reordered, &path)], name);
+
+            // Adding a shard will only move paths to the new shard.
+            let moved = ["a", "b", "c", "d"][shard_of(&added, &path)];
+            assert!(moved == name || moved == "d");
+        }
+    }
+
+    #[test]
+    fn test_build_invalid() {
+        let err = ShardBuilder::default().build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let mut builder = ShardBuilder::default();
+        builder.shard("a", new_memory()).shard("a", new_memory());
+        let err = builder.build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+}
@@ -0,0 +1,90 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// ShardPager will return entries of all shards in order.
+///
+/// Files only live in one shard, but dirs could exist in every shard, so
+/// we only need to track dirs to make sure they are returned once.
+pub struct ShardPager<P> {
+    pagers: VecDeque<P>,
+    seen_dirs: HashSet<String>,
+}
+
+impl<P> ShardPager<P> {
+    pub fn new(pagers: Vec<P>) -> Self {
+        Self {
+            pagers: pagers.into(),
+            seen_dirs: HashSet::new(),
+        }
+    }
+
+    fn dedup(&mut self, entries: Vec<oio::Entry>) -> Vec<oio::Entry> {
+        entries
+            .into_iter()
+            .filter(|v| v.mode() != EntryMode::DIR || self.seen_dirs.insert(v.path().to_string()))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl oio::Page for ShardPager<oio::Pager> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        while let Some(pager) = self.pagers.front_mut() {
+            match pager.next().await? {
+                Some(entries) => {
+                    let entries = self.dedup(entries);
+                    if !entries.is_empty() {
+                        return Ok(Some(entries));
+                    }
+                }
+                None => {
+                    self.pagers.pop_front();
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl oio::BlockingPage for ShardPager<oio::BlockingPager> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        while let Some(pager) = self.pagers.front_mut() {
+            match pager.next()? {
+                Some(entries) => {
+                    let entries = self.dedup(entries);
+                    if !entries.is_empty() {
+                        return Ok(Some(entries));
+                    }
+                }
+                None => {
+                    self.pagers.pop_front();
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
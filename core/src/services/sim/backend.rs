@@ -0,0 +1,687 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use parking_lot::Mutex;
+
+use crate::raw::*;
+use crate::services::Memory;
+use crate::*;
+
+/// Latency describes the distribution of simulated latency for an operation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Latency {
+    /// Every call takes exactly the given duration.
+    Fixed(Duration),
+    /// Calls take a duration picked uniformly between `min` and `max`.
+    Uniform {
+        /// The minimum latency.
+        min: Duration,
+        /// The maximum latency.
+        max: Duration,
+    },
+    /// Calls take an exponentially distributed duration with given mean,
+    /// which is useful to simulate long tail latency.
+    ///
+    /// Samples are capped at 20 times of the mean.
+    Exponential(Duration),
+}
+
+impl Latency {
+    /// Sample a latency with given random number in `[0, 1)`.
+    fn sample(&self, r: f64) -> Duration {
+        match self {
+            Latency::Fixed(d) => *d,
+            Latency::Uniform { min, max } => *min + (*max - *min).mul_f64(r),
+            Latency::Exponential(mean) => mean.mul_f64((-(1.0 - r).ln()).min(20.0)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Rule {
+    latency: Option<Latency>,
+    fail_rate: f64,
+    fail_at: Vec<u64>,
+}
+
+/// Sim service support which injects deterministic latency and failures.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct SimBuilder {
+    inner: Option<Operator>,
+    seed: u64,
+    rules: HashMap<Operation, Rule>,
+    read_bandwidth: Option<u64>,
+    write_bandwidth: Option<u64>,
+}
+
+impl SimBuilder {
+    /// Set the operator to be wrapped.
+    ///
+    /// An in-memory operator will be used if not set.
+    pub fn inner(&mut self, op: Operator) -> &mut Self {
+        self.inner = Some(op);
+        self
+    }
+
+    /// Set the seed for all random decisions.
+    ///
+    /// The same seed with the same config always produces the same
+    /// latency and failures for the n-th call of an operation.
+    ///
+    /// Default to `0`.
+    pub fn seed(&mut self, seed: u64) -> &mut Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the latency distribution of given operation.
+    ///
+    /// Blocking operations are configured separately, for example
+    /// [`Operation::BlockingRead`] for blocking read.
+    pub fn latency(&mut self, op: Operation, latency: Latency) -> &mut Self {
+        self.rules.entry(op).or_default().latency = Some(latency);
+        self
+    }
+
+    /// Set the rate in `[0, 1]` that given operation fails.
+    pub fn fail_rate(&mut self, op: Operation, rate: f64) -> &mut Self {
+        self.rules.entry(op).or_default().fail_rate = rate;
+        self
+    }
+
+    /// Make the n-th (starting from `1`) call of given operation fail.
+    ///
+    /// This function can be called multiple times to fail more calls.
+    pub fn fail_at(&mut self, op: Operation, nth: u64) -> &mut Self {
+        self.rules.entry(op).or_default().fail_at.push(nth);
+        self
+    }
+
+    /// Cap the bandwidth of reading in bytes per second.
+    pub fn read_bandwidth(&mut self, v: u64) -> &mut Self {
+        self.read_bandwidth = Some(v);
+        self
+    }
+
+    /// Cap the bandwidth of writing and appending in bytes per second.
+    pub fn write_bandwidth(&mut self, v: u64) -> &mut Self {
+        self.write_bandwidth = Some(v);
+        self
+    }
+}
+
+impl Builder for SimBuilder {
+    const SCHEME: Scheme = Scheme::Sim;
+    type Accessor = SimBackend;
+
+    /// Latency and failures can't be built from map, the returning builder
+    /// wraps an in-memory operator.
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = SimBuilder::default();
+
+        map.get("seed")
+            .map(|v| v.parse::<u64>().map(|v| builder.seed(v)));
+        map.get("read_bandwidth")
+            .map(|v| v.parse::<u64>().map(|v| builder.read_bandwidth(v)));
+        map.get("write_bandwidth")
+            .map(|v| v.parse::<u64>().map(|v| builder.write_bandwidth(v)));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let invalid = |msg: &str| {
+            Error::new(ErrorKind::ConfigInvalid, msg)
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Sim)
+        };
+
+        for (op, rule) in self.rules.iter() {
+            if !(0.0..=1.0).contains(&rule.fail_rate) {
+                return Err(invalid("fail rate must be in [0, 1]")
+                    .with_context("operation", *op)
+                    .with_context("fail_rate", rule.fail_rate.to_string()));
+            }
+            if let Some(Latency::Uniform { min, max }) = rule.latency {
+                if min > max {
+                    return Err(invalid("uniform latency min must not be larger than max")
+                        .with_context("operation", *op));
+                }
+            }
+        }
+        if self.read_bandwidth == Some(0) || self.write_bandwidth == Some(0) {
+            return Err(invalid("bandwidth must be larger than 0"));
+        }
+
+        let inner = match &self.inner {
+            Some(op) => op.inner().clone(),
+            None => Operator::new(Memory::default())?.finish().inner().clone(),
+        };
+
+        Ok(SimBackend {
+            inner,
+            seed: self.seed,
+            rules: Arc::new(self.rules.clone()),
+            counters: Arc::default(),
+            read_bandwidth: self.read_bandwidth,
+            write_bandwidth: self.write_bandwidth,
+        })
+    }
+}
+
+/// Backend is used to serve `Accessor` support for sim.
+#[derive(Debug, Clone)]
+pub struct SimBackend {
+    inner: FusedAccessor,
+    seed: u64,
+    rules: Arc<HashMap<Operation, Rule>>,
+    counters: Arc<Mutex<HashMap<Operation, u64>>>,
+    read_bandwidth: Option<u64>,
+    write_bandwidth: Option<u64>,
+}
+
+impl SimBackend {
+    /// Decide the latency and failure of next call of given operation.
+    ///
+    /// Random numbers are derived from the seed, operation and call index
+    /// so that the result doesn't depend on how calls are interleaved.
+    fn plan(&self, op: Operation) -> (Duration, Option<Error>) {
+        let rule = match self.rules.get(&op) {
+            Some(rule) => rule,
+            None => return (Duration::ZERO, None),
+        };
+
+        let n = {
+            let mut counters = self.counters.lock();
+            let n = counters.entry(op).or_default();
+            *n += 1;
+            *n
+        };
+
+        let delay = rule
+            .latency
+            .map(|v| v.sample(self.random(op, n, 0)))
+            .unwrap_or_default();
+        let err = if rule.fail_at.contains(&n) || self.random(op, n, 1) < rule.fail_rate {
+            Some(
+                Error::new(ErrorKind::Unexpected, "simulated failure")
+                    .with_context("call", n.to_string())
+                    .set_temporary(),
+            )
+        } else {
+            None
+        };
+
+        (delay, err)
+    }
+
+    /// Returns a random number in `[0, 1)`.
+    fn random(&self, op: Operation, n: u64, salt: u64) -> f64 {
+        // FNV-1a of the operation name.
+        let op_hash = op.into_static().bytes().fold(0xcbf29ce484222325, |h, b| {
+            (h ^ b as u64).wrapping_mul(0x100000001b3)
+        });
+
+        let x =
+            splitmix64(self.seed ^ op_hash ^ splitmix64(n) ^ salt.wrapping_mul(0x9e3779b97f4a7c15));
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    async fn simulate(&self, op: Operation) -> Result<()> {
+        let (delay, err) = self.plan(op);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        err.map_or(Ok(()), Err)
+    }
+
+    fn blocking_simulate(&self, op: Operation) -> Result<()> {
+        let (delay, err) = self.plan(op);
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+        err.map_or(Ok(()), Err)
+    }
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+/// Returns the time to transfer given bytes under bandwidth.
+fn transfer_time(bandwidth: Option<u64>, size: u64) -> Option<Duration> {
+    match bandwidth {
+        Some(bw) if size > 0 => Some(Duration::from_secs_f64(size as f64 / bw as f64)),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl Accessor for SimBackend {
+    type Reader = SimReader<oio::Reader>;
+    type BlockingReader = SimReader<oio::BlockingReader>;
+    type Writer = SimWriter<oio::Writer>;
+    type BlockingWriter = SimWriter<oio::BlockingWriter>;
+    type Appender = SimWriter<oio::Appender>;
+    type Pager = oio::Pager;
+    type BlockingPager = oio::BlockingPager;
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Sim)
+            .set_root("/")
+            .set_capability(self.inner.info().capability());
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.simulate(Operation::CreateDir).await?;
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.simulate(Operation::Read).await?;
+        let (rp, r) = self.inner.read(path, args).await?;
+        Ok((rp, SimReader::new(r, self.read_bandwidth)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.simulate(Operation::Write).await?;
+        let (rp, w) = self.inner.write(path, args).await?;
+        Ok((rp, SimWriter::new(w, self.write_bandwidth)))
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.simulate(Operation::Append).await?;
+        let (rp, a) = self.inner.append(path, args).await?;
+        Ok((rp, SimWriter::new(a, self.write_bandwidth)))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.simulate(Operation::Copy).await?;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.simulate(Operation::Rename).await?;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.simulate(Operation::Stat).await?;
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.simulate(Operation::Delete).await?;
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.simulate(Operation::List).await?;
+        self.inner.list(path, args).await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner.presign(path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.blocking_simulate(Operation::BlockingCreateDir)?;
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.blocking_simulate(Operation::BlockingRead)?;
+        let (rp, r) = self.inner.blocking_read(path, args)?;
+        Ok((rp, SimReader::new(r, self.read_bandwidth)))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.blocking_simulate(Operation::BlockingWrite)?;
+        let (rp, w) = self.inner.blocking_write(path, args)?;
+        Ok((rp, SimWriter::new(w, self.write_bandwidth)))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.blocking_simulate(Operation::BlockingCopy)?;
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.blocking_simulate(Operation::BlockingRename)?;
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.blocking_simulate(Operation::BlockingStat)?;
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.blocking_simulate(Operation::BlockingDelete)?;
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.blocking_simulate(Operation::BlockingList)?;
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// SimReader caps the bandwidth of reading by holding each chunk until
+/// the time to transfer it passed.
+pub struct SimReader<R> {
+    inner: R,
+    bandwidth: Option<u64>,
+    pending: Option<Bytes>,
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl<R> SimReader<R> {
+    fn new(inner: R, bandwidth: Option<u64>) -> Self {
+        Self {
+            inner,
+            bandwidth,
+            pending: None,
+            sleep: None,
+        }
+    }
+
+    /// Hold the chunk until the time to transfer it passed.
+    fn hold(&mut self, bs: Bytes) {
+        if let Some(dur) = transfer_time(self.bandwidth, bs.len() as u64) {
+            self.sleep = Some(Box::pin(tokio::time::sleep(dur)));
+        }
+        self.pending = Some(bs);
+    }
+
+    /// Returns the chunk that is ready to be consumed.
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        if let Some(sleep) = &mut self.sleep {
+            ready!(sleep.as_mut().poll(cx));
+            self.sleep = None;
+        }
+        Poll::Ready(self.pending.take())
+    }
+}
+
+impl<R: oio::Read> oio::Read for SimReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if self.bandwidth.is_none() {
+            return self.inner.poll_read(cx, buf);
+        }
+
+        loop {
+            if let Some(mut bs) = ready!(self.poll_pending(cx)) {
+                let n = bs.len().min(buf.len());
+                buf[..n].copy_from_slice(&bs.split_to(n));
+                if !bs.is_empty() {
+                    self.pending = Some(bs);
+                }
+                return Poll::Ready(Ok(n));
+            }
+
+            let mut chunk = vec![0; buf.len()];
+            let n = ready!(self.inner.poll_read(cx, &mut chunk))?;
+            if n == 0 {
+                return Poll::Ready(Ok(0));
+            }
+            chunk.truncate(n);
+            self.hold(chunk.into());
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        // Chunks not consumed yet have been read from inner already.
+        let pos = match (pos, &self.pending) {
+            (SeekFrom::Current(off), Some(bs)) => SeekFrom::Current(off - bs.len() as i64),
+            _ => pos,
+        };
+        let offset = ready!(self.inner.poll_seek(cx, pos))?;
+
+        self.pending = None;
+        self.sleep = None;
+        Poll::Ready(Ok(offset))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        if self.bandwidth.is_none() {
+            return self.inner.poll_next(cx);
+        }
+
+        loop {
+            if let Some(bs) = ready!(self.poll_pending(cx)) {
+                return Poll::Ready(Some(Ok(bs)));
+            }
+
+            match ready!(self.inner.poll_next(cx)) {
+                Some(Ok(bs)) if !bs.is_empty() => self.hold(bs),
+                v => return Poll::Ready(v),
+            }
+        }
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for SimReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        if let Some(dur) = transfer_time(self.bandwidth, n as u64) {
+            std::thread::sleep(dur);
+        }
+        Ok(n)
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        let bs = self.inner.next();
+        if let Some(Ok(bs)) = &bs {
+            if let Some(dur) = transfer_time(self.bandwidth, bs.len() as u64) {
+                std::thread::sleep(dur);
+            }
+        }
+        bs
+    }
+}
+
+/// SimWriter caps the bandwidth of writing by waiting before each chunk.
+pub struct SimWriter<W> {
+    inner: W,
+    bandwidth: Option<u64>,
+}
+
+impl<W> SimWriter<W> {
+    fn new(inner: W, bandwidth: Option<u64>) -> Self {
+        Self { inner, bandwidth }
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for SimWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        if let Some(dur) = transfer_time(self.bandwidth, bs.len() as u64) {
+            tokio::time::sleep(dur).await;
+        }
+        self.inner.write(bs).await
+    }
+
+    async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
+        if let Some(dur) = transfer_time(self.bandwidth, size) {
+            tokio::time::sleep(dur).await;
+        }
+        self.inner.sink(size, s).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner.close().await
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for SimWriter<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        if let Some(dur) = transfer_time(self.bandwidth, bs.len() as u64) {
+            std::thread::sleep(dur);
+        }
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+#[async_trait]
+impl<A: oio::Append> oio::Append for SimWriter<A> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        if let Some(dur) = transfer_time(self.bandwidth, bs.len() as u64) {
+            tokio::time::sleep(dur).await;
+        }
+        self.inner.append(bs).await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    fn plans(seed: u64) -> Vec<(Duration, bool)> {
+        let backend = {
+            let mut builder = SimBuilder::default();
+            builder
+                .seed(seed)
+                .latency(
+                    Operation::Stat,
+                    Latency::Uniform {
+                        min: Duration::from_millis(10),
+                        max: Duration::from_millis(100),
+                    },
+                )
+                .fail_rate(Operation::Stat, 0.5);
+            builder.build().unwrap()
+        };
+
+        (0..32)
+            .map(|_| {
+                let (delay, err) = backend.plan(Operation::Stat);
+                (delay, err.is_some())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let expected = plans(42);
+        assert_eq!(expected, plans(42));
+        assert_ne!(expected, plans(43));
+
+        for (delay, _) in expected.iter() {
+            assert!(*delay >= Duration::from_millis(10));
+            assert!(*delay <= Duration::from_millis(100));
+        }
+        assert!(expected.iter().any(|(_, failed)| *failed));
+        assert!(expected.iter().any(|(_, failed)| !*failed));
+    }
+
+    #[test]
+    fn test_fail_at() {
+        let mut builder = SimBuilder::default();
+        builder.fail_at(Operation::BlockingStat, 2);
+        let op = Operator::new(builder).unwrap().finish().blocking();
+        op.write("a", "data").unwrap();
+
+        assert!(op.stat("a").is_ok());
+        let err = op.stat("a").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+        assert!(op.stat("a").is_ok());
+    }
+
+    #[test]
+    fn test_latency() {
+        let mut builder = SimBuilder::default();
+        builder.latency(
+            Operation::BlockingStat,
+            Latency::Fixed(Duration::from_millis(50)),
+        );
+        let op = Operator::new(builder).unwrap().finish().blocking();
+        op.write("a", "data").unwrap();
+
+        let start = Instant::now();
+        op.stat("a").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_read_bandwidth() {
+        let mut builder = SimBuilder::default();
+        builder.read_bandwidth(10_000);
+        let op = Operator::new(builder).unwrap().finish();
+        op.write("a", vec![0; 1000]).await.unwrap();
+
+        let start = Instant::now();
+        assert_eq!(op.read("a").await.unwrap().len(), 1000);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let mut builder = SimBuilder::default();
+        builder.fail_rate(Operation::Read, 1.5);
+        assert_eq!(
+            builder.build().unwrap_err().kind(),
+            ErrorKind::ConfigInvalid
+        );
+
+        let mut builder = SimBuilder::default();
+        builder.read_bandwidth(0);
+        assert_eq!(
+            builder.build().unwrap_err().kind(),
+            ErrorKind::ConfigInvalid
+        );
+    }
+}
@@ -0,0 +1,253 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::sqlite::SqliteConnectOptions;
+use tokio::sync::OnceCell;
+
+use super::core::SqliteCore;
+use super::pager::SqlitePager;
+use super::reader::SqliteReader;
+use super::writer::SqliteWriter;
+use crate::raw::*;
+use crate::*;
+
+/// [SQLite](https://www.sqlite.org/) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default, Clone, Debug)]
+pub struct SqliteBuilder {
+    connection_string: Option<String>,
+    table: Option<String>,
+    root: Option<String>,
+}
+
+impl SqliteBuilder {
+    /// Set the connection string of the sqlite service.
+    ///
+    /// The format of connection string looks like:
+    ///
+    /// - `sqlite:data.db`
+    /// - `sqlite:///path/to/data.db?mode=rwc`
+    ///
+    /// For more information, please refer to [SQLite URI Filenames](https://www.sqlite.org/uri.html).
+    pub fn connection_string(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.connection_string = Some(v.to_string());
+        }
+        self
+    }
+
+    /// Set the table name of the sqlite service to read/write.
+    pub fn table(&mut self, table: &str) -> &mut Self {
+        if !table.is_empty() {
+            self.table = Some(table.to_string());
+        }
+        self
+    }
+
+    /// set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_owned());
+        }
+        self
+    }
+}
+
+impl Builder for SqliteBuilder {
+    const SCHEME: Scheme = Scheme::Sqlite;
+    type Accessor = SqliteBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = SqliteBuilder::default();
+
+        map.get("connection_string")
+            .map(|v| builder.connection_string(v));
+        map.get("table").map(|v| builder.table(v));
+        map.get("root").map(|v| builder.root(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let connection_string = self.connection_string.take().ok_or_else(|| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "connection_string is required but not set",
+            )
+            .with_context("service", Scheme::Sqlite)
+        })?;
+        let config = SqliteConnectOptions::from_str(&connection_string).map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "connection_string is invalid")
+                .with_context("service", Scheme::Sqlite)
+                .set_source(e)
+        })?;
+
+        let table = self.table.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "table is required but not set")
+                .with_context("service", Scheme::Sqlite)
+        })?;
+
+        let root = normalize_root(self.root.take().unwrap_or_else(|| "/".to_string()).as_str());
+
+        Ok(SqliteBackend {
+            core: Arc::new(SqliteCore {
+                config,
+                table,
+                root,
+                pool: OnceCell::new(),
+            }),
+        })
+    }
+}
+
+/// Backend for SQLite services.
+#[derive(Debug, Clone)]
+pub struct SqliteBackend {
+    core: Arc<SqliteCore>,
+}
+
+#[async_trait]
+impl Accessor for SqliteBackend {
+    type Reader = SqliteReader;
+    type BlockingReader = ();
+    type Writer = SqliteWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = SqlitePager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Sqlite)
+            .set_root(&self.core.root)
+            .set_name(&self.core.table)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_without_content_length: true,
+                write_with_content_type: true,
+
+                create_dir: true,
+                delete: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_start_after: true,
+                list_without_delimiter: true,
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        let p = build_abs_path(&self.core.root, path);
+
+        self.core.set(&p, &[], None).await?;
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let p = build_abs_path(&self.core.root, path);
+
+        let meta = self
+            .core
+            .get_metadata(&p)
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "sqlite doesn't have this path"))?;
+        let length = meta.content_length();
+
+        let br = args.range();
+        let (start, end) = match (br.offset(), br.size()) {
+            (Some(offset), Some(size)) => (offset, offset.saturating_add(size)),
+            (Some(offset), None) => (offset, length),
+            (None, Some(size)) => (length.saturating_sub(size), length),
+            (None, None) => (0, length),
+        };
+        let end = end.min(length);
+        let size = end.saturating_sub(start);
+
+        Ok((
+            RpRead::new(size),
+            SqliteReader::new(self.core.clone(), p, start, size),
+        ))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let p = build_abs_path(&self.core.root, path);
+
+        Ok((
+            RpWrite::default(),
+            SqliteWriter::new(self.core.clone(), p, args),
+        ))
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        let p = build_abs_path(&self.core.root, path);
+
+        if p.is_empty() || p.ends_with('/') {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        match self.core.get_metadata(&p).await? {
+            Some(meta) => Ok(RpStat::new(meta)),
+            None => Err(Error::new(
+                ErrorKind::NotFound,
+                "sqlite doesn't have this path",
+            )),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let p = build_abs_path(&self.core.root, path);
+
+        self.core.delete(&p).await?;
+        Ok(RpDelete::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        if !args.delimiter().is_empty() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "sqlite doesn't support delimiter",
+            ));
+        }
+
+        let p = build_abs_path(&self.core.root, path);
+        let start_after = args
+            .start_after()
+            .map(|v| build_abs_path(&self.core.root, v));
+
+        Ok((
+            RpList::default(),
+            SqlitePager::new(self.core.clone(), p, args.limit(), start_after),
+        ))
+    }
+}
@@ -0,0 +1,248 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::debug;
+use tokio::sync::OnceCell;
+
+use super::index::TarIndex;
+use super::pager::TarPager;
+use crate::raw::*;
+use crate::services::Fs;
+use crate::*;
+
+/// Read-only tar archive services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default, Debug)]
+pub struct TarBuilder {
+    root: Option<String>,
+    path: Option<String>,
+    operator: Option<Operator>,
+}
+
+impl TarBuilder {
+    /// Set the working directory inside the tarball, all operations will
+    /// be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string());
+        }
+        self
+    }
+
+    /// Set the path of the tarball.
+    ///
+    /// It's a path on local fs by default, or a path inside the operator
+    /// if [`TarBuilder::operator`] is set.
+    pub fn path(&mut self, path: &str) -> &mut Self {
+        if !path.is_empty() {
+            self.path = Some(path.to_string());
+        }
+        self
+    }
+
+    /// Read the tarball from given operator instead of local fs.
+    pub fn operator(&mut self, op: Operator) -> &mut Self {
+        self.operator = Some(op);
+        self
+    }
+}
+
+impl Builder for TarBuilder {
+    const SCHEME: Scheme = Scheme::Tar;
+    type Accessor = TarBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = TarBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("path").map(|v| builder.path(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let path = self.path.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "path is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Tar)
+        })?;
+        debug!("backend use path {}", path);
+
+        let (op, path) = match self.operator.take() {
+            Some(op) => (op, path),
+            None => {
+                // Serve the local tarball via fs service rooted at its parent.
+                let p = Path::new(&path);
+                let (parent, name) = match (p.parent(), p.file_name()) {
+                    (Some(parent), Some(name)) => (parent, name),
+                    _ => {
+                        return Err(Error::new(ErrorKind::ConfigInvalid, "path is invalid")
+                            .with_operation("Builder::build")
+                            .with_context("service", Scheme::Tar)
+                            .with_context("path", &path))
+                    }
+                };
+
+                let mut builder = Fs::default();
+                builder.root(&parent.to_string_lossy());
+                let op = Operator::new(builder)
+                    .map_err(|err| {
+                        err.with_operation("Builder::build")
+                            .with_context("service", Scheme::Tar)
+                    })?
+                    .finish();
+
+                (op, name.to_string_lossy().to_string())
+            }
+        };
+
+        Ok(TarBackend {
+            root,
+            op,
+            path,
+            index: Arc::new(OnceCell::new()),
+        })
+    }
+}
+
+/// Backend for tar services.
+#[derive(Clone, Debug)]
+pub struct TarBackend {
+    root: String,
+    op: Operator,
+    path: String,
+    index: Arc<OnceCell<TarIndex>>,
+}
+
+impl TarBackend {
+    /// Load the index on first use, it will be reused until the backend is
+    /// dropped.
+    async fn index(&self) -> Result<&TarIndex> {
+        self.index
+            .get_or_try_init(|| TarIndex::load(&self.op, &self.path))
+            .await
+    }
+}
+
+#[async_trait]
+impl Accessor for TarBackend {
+    type Reader = Reader;
+    type BlockingReader = ();
+    type Writer = ();
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = TarPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut ma = AccessorInfo::default();
+        ma.set_scheme(Scheme::Tar)
+            .set_root(&self.root)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_seek: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                list: true,
+                list_with_delimiter_slash: true,
+                list_without_delimiter: true,
+
+                ..Default::default()
+            });
+
+        ma
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let p = build_abs_path(&self.root, path);
+
+        let entry = self
+            .index()
+            .await?
+            .get(&p)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "member is not found in tarball"))?;
+
+        let br = args.range();
+        let (start, end) = match (br.offset(), br.size()) {
+            // Read a specific range.
+            (Some(offset), Some(size)) => (offset, offset + size),
+            // Read from offset.
+            (Some(offset), None) => (offset, entry.size),
+            // Read the last size bytes.
+            (None, Some(size)) => (entry.size.saturating_sub(size), entry.size),
+            // Read the whole file.
+            (None, None) => (0, entry.size),
+        };
+        let (start, end) = (start.min(entry.size), end.min(entry.size));
+
+        let r = self
+            .op
+            .range_reader(&self.path, entry.offset + start..entry.offset + end)
+            .await?;
+
+        Ok((RpRead::new(end - start), r))
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let p = build_abs_path(&self.root, path);
+
+        let entry = self
+            .index()
+            .await?
+            .get(&p)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "member is not found in tarball"))?;
+
+        Ok(RpStat::new(entry.to_metadata()))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let p = build_abs_path(&self.root, path);
+
+        let entries = self
+            .index()
+            .await?
+            .list(&p, args.delimiter().is_empty())
+            .into_iter()
+            .map(|(k, v)| {
+                oio::Entry::new(
+                    &build_rel_path(&self.root, &format!("/{k}")),
+                    v.to_metadata(),
+                )
+            })
+            .collect();
+
+        Ok((RpList::default(), TarPager::new(entries)))
+    }
+}
@@ -0,0 +1,518 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::*;
+
+/// Tarball is made of 512 bytes blocks.
+const BLOCK_SIZE: u64 = 512;
+/// The tarball is fetched in windows of this size while loading index, so
+/// that we don't need to send a request for every header.
+const WINDOW_SIZE: u64 = 64 * 1024;
+
+/// TarEntry is a member in the tarball.
+#[derive(Clone, Debug)]
+pub struct TarEntry {
+    /// The offset of member's data in the tarball.
+    pub offset: u64,
+    pub size: u64,
+    pub mode: EntryMode,
+    pub mtime: Option<DateTime<Utc>>,
+}
+
+impl TarEntry {
+    fn dir() -> Self {
+        TarEntry {
+            offset: 0,
+            size: 0,
+            mode: EntryMode::DIR,
+            mtime: None,
+        }
+    }
+
+    pub fn to_metadata(&self) -> Metadata {
+        let mut m = Metadata::new(self.mode);
+        m.set_content_length(self.size);
+        if let Some(v) = self.mtime {
+            m.set_last_modified(v);
+        }
+        m
+    }
+}
+
+/// TarIndex holds all members of the tarball.
+///
+/// Paths of dirs end with `/`, for example: `path/to/dir/`.
+#[derive(Debug, Default)]
+pub struct TarIndex {
+    entries: BTreeMap<String, TarEntry>,
+}
+
+impl TarIndex {
+    /// Load index by walking through all headers in the tarball.
+    pub async fn load(op: &Operator, path: &str) -> Result<Self> {
+        let size = op.stat(path).await?.content_length();
+        let mut window = Window {
+            op,
+            path,
+            size,
+            start: 0,
+            buf: Bytes::new(),
+        };
+
+        let mut index = TarIndex::default();
+        let mut offset = 0;
+        // Extended headers that apply to the next member.
+        let mut long_name: Option<String> = None;
+        let mut long_link: Option<String> = None;
+        let mut pax: HashMap<String, String> = HashMap::new();
+
+        while offset + BLOCK_SIZE <= size {
+            let block = window.read(offset, BLOCK_SIZE).await?;
+            let header = match parse_header(&block)? {
+                Some(header) => header,
+                // Reach the end of archive.
+                None => break,
+            };
+
+            let data_offset = offset + BLOCK_SIZE;
+            let data_size = match pax.get("size") {
+                Some(v) => parse_pax_number(v)?,
+                None => header.size,
+            };
+            offset = data_offset + round_up(data_size);
+
+            match header.typeflag {
+                // GNU long name and long link.
+                b'L' => {
+                    long_name = Some(parse_str(&window.read(data_offset, data_size).await?));
+                    continue;
+                }
+                b'K' => {
+                    long_link = Some(parse_str(&window.read(data_offset, data_size).await?));
+                    continue;
+                }
+                // PAX extended header.
+                b'x' => {
+                    pax = parse_pax(&window.read(data_offset, data_size).await?)?;
+                    continue;
+                }
+                // PAX global header and GNU volume header.
+                b'g' | b'V' => continue,
+                _ => {}
+            }
+
+            let name = pax
+                .remove("path")
+                .or_else(|| long_name.take())
+                .unwrap_or(header.name);
+            let link = pax
+                .remove("linkpath")
+                .or_else(|| long_link.take())
+                .unwrap_or(header.link);
+            let mtime = match pax.remove("mtime") {
+                Some(v) => parse_pax_number(&v)?,
+                None => header.mtime,
+            };
+            pax.clear();
+            long_name = None;
+            long_link = None;
+
+            let mtime = UNIX_EPOCH
+                .checked_add(Duration::from_secs(mtime))
+                .map(DateTime::<Utc>::from);
+
+            match header.typeflag {
+                b'5' => index.insert_dir(&name),
+                // Hard link shares the data of the target.
+                b'1' => {
+                    let target = index.get(&normalize_name(&link)).cloned();
+                    if let Some(entry) = target.filter(|v| v.mode.is_file()) {
+                        index.insert(&name, entry);
+                    }
+                }
+                // Regular files and unknown types which should be treated as
+                // regular files by the spec.
+                b'0' | b'\0' | b'7' => index.insert(
+                    &name,
+                    TarEntry {
+                        offset: data_offset,
+                        size: data_size,
+                        mode: EntryMode::FILE,
+                        mtime,
+                    },
+                ),
+                // Symlinks and special files are not exposed.
+                _ => {}
+            }
+        }
+
+        Ok(index)
+    }
+
+    /// Get the entry of given path.
+    pub fn get(&self, path: &str) -> Option<&TarEntry> {
+        self.entries.get(path)
+    }
+
+    /// List entries under given dir, all descendants will be returned if
+    /// `recursive` is true.
+    pub fn list(&self, dir: &str, recursive: bool) -> Vec<(String, TarEntry)> {
+        self.entries
+            .range(dir.to_string()..)
+            .take_while(|(k, _)| k.starts_with(dir))
+            .filter(|(k, _)| {
+                let rest = &k[dir.len()..];
+                !rest.is_empty() && (recursive || !rest.trim_end_matches('/').contains('/'))
+            })
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    fn insert(&mut self, name: &str, entry: TarEntry) {
+        let path = normalize_name(name);
+        if path.is_empty() {
+            return;
+        }
+        let path = path.trim_end_matches('/');
+
+        self.insert_parents(path);
+        self.entries.insert(path.to_string(), entry);
+    }
+
+    fn insert_dir(&mut self, name: &str) {
+        let path = normalize_name(name);
+        let path = path.trim_end_matches('/');
+        if path.is_empty() {
+            return;
+        }
+
+        self.insert_parents(path);
+        self.entries
+            .entry(format!("{path}/"))
+            .or_insert_with(TarEntry::dir);
+    }
+
+    /// Insert all parents of given path as dirs since tarball could omit
+    /// them.
+    fn insert_parents(&mut self, path: &str) {
+        for (idx, _) in path.match_indices('/') {
+            self.entries
+                .entry(path[..=idx].to_string())
+                .or_insert_with(TarEntry::dir);
+        }
+    }
+}
+
+/// Window caches a range of the tarball.
+struct Window<'a> {
+    op: &'a Operator,
+    path: &'a str,
+    size: u64,
+
+    start: u64,
+    buf: Bytes,
+}
+
+impl Window<'_> {
+    async fn read(&mut self, offset: u64, size: u64) -> Result<Bytes> {
+        let end = offset + size;
+        if end > self.size {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "tarball is truncated, the archive must be malformed",
+            )
+            .with_context("path", self.path));
+        }
+
+        if offset < self.start || end > self.start + self.buf.len() as u64 {
+            let fetch_end = (offset + size.max(WINDOW_SIZE)).min(self.size);
+            self.buf = Bytes::from(self.op.range_read(self.path, offset..fetch_end).await?);
+            self.start = offset;
+        }
+
+        let begin = (offset - self.start) as usize;
+        Ok(self.buf.slice(begin..begin + size as usize))
+    }
+}
+
+struct Header {
+    name: String,
+    link: String,
+    size: u64,
+    mtime: u64,
+    typeflag: u8,
+}
+
+/// Parse a header block, returns `None` if it's an empty block which
+/// means the end of archive.
+fn parse_header(block: &[u8]) -> Result<Option<Header>> {
+    if block.iter().all(|v| *v == 0) {
+        return Ok(None);
+    }
+
+    let checksum = parse_number(&block[148..156])?;
+    let actual: u64 = block
+        .iter()
+        .enumerate()
+        .map(|(idx, v)| {
+            // Checksum field is treated as spaces while calculating.
+            if (148..156).contains(&idx) {
+                b' ' as u64
+            } else {
+                *v as u64
+            }
+        })
+        .sum();
+    if checksum != actual {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "tar header checksum mismatch, the archive must be malformed",
+        ));
+    }
+
+    let mut name = parse_str(&block[0..100]);
+    // ustar splits long names into prefix and name.
+    if &block[257..262] == b"ustar" {
+        let prefix = parse_str(&block[345..500]);
+        if !prefix.is_empty() {
+            name = format!("{prefix}/{name}");
+        }
+    }
+
+    Ok(Some(Header {
+        name,
+        link: parse_str(&block[157..257]),
+        size: parse_number(&block[124..136])?,
+        mtime: parse_number(&block[136..148])?,
+        typeflag: block[156],
+    }))
+}
+
+/// Parse numeric field, which is either octal string or base-256 encoded
+/// if the highest bit is set.
+fn parse_number(bs: &[u8]) -> Result<u64> {
+    if bs[0] & 0x80 != 0 {
+        return Ok(bs[1..]
+            .iter()
+            .fold((bs[0] & 0x7f) as u64, |acc, v| (acc << 8) | *v as u64));
+    }
+
+    let s = parse_str(bs);
+    let s = s.trim();
+    if s.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(s, 8).map_err(|err| {
+        Error::new(ErrorKind::Unexpected, "tar header contains invalid number")
+            .with_context("value", s)
+            .set_source(err)
+    })
+}
+
+/// Parse a NUL terminated string.
+fn parse_str(bs: &[u8]) -> String {
+    let end = bs.iter().position(|v| *v == 0).unwrap_or(bs.len());
+    String::from_utf8_lossy(&bs[..end]).to_string()
+}
+
+/// Parse PAX records in the format of `<length> <key>=<value>\n`.
+fn parse_pax(bs: &[u8]) -> Result<HashMap<String, String>> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::Unexpected,
+            "pax header is invalid, the archive must be malformed",
+        )
+    };
+
+    let mut records = HashMap::new();
+    let mut bs = bs;
+    while !bs.is_empty() && bs[0] != 0 {
+        let space = bs.iter().position(|v| *v == b' ').ok_or_else(invalid)?;
+        let len: usize = std::str::from_utf8(&bs[..space])
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > space && *v <= bs.len())
+            .ok_or_else(invalid)?;
+
+        let record = String::from_utf8_lossy(&bs[space + 1..len]);
+        let record = record.trim_end_matches('\n');
+        if let Some((key, value)) = record.split_once('=') {
+            records.insert(key.to_string(), value.to_string());
+        }
+        bs = &bs[len..];
+    }
+
+    Ok(records)
+}
+
+/// Parse numbers in PAX header which could have fractional part.
+fn parse_pax_number(s: &str) -> Result<u64> {
+    let s = s.split('.').next().unwrap_or_default();
+    s.parse().map_err(|err| {
+        Error::new(ErrorKind::Unexpected, "pax header contains invalid number")
+            .with_context("value", s)
+            .set_source(err)
+    })
+}
+
+/// Strip the leading `./` and `/` of member names.
+fn normalize_name(name: &str) -> String {
+    let mut name = name;
+    loop {
+        if let Some(v) = name.strip_prefix("./") {
+            name = v;
+        } else if let Some(v) = name.strip_prefix('/') {
+            name = v;
+        } else {
+            break;
+        }
+    }
+
+    if name == "." {
+        return String::new();
+    }
+    name.to_string()
+}
+
+fn round_up(size: u64) -> u64 {
+    (size + BLOCK_SIZE - 1) & !(BLOCK_SIZE - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(name: &str, link: &str, size: usize, typeflag: u8) -> Vec<u8> {
+        let mut block = vec![0; BLOCK_SIZE as usize];
+        block[..name.len()].copy_from_slice(name.as_bytes());
+        block[124..135].copy_from_slice(format!("{size:011o}").as_bytes());
+        block[136..147].copy_from_slice(format!("{:011o}", 1_600_000_000).as_bytes());
+        block[156] = typeflag;
+        block[157..157 + link.len()].copy_from_slice(link.as_bytes());
+        block[257..263].copy_from_slice(b"ustar\0");
+        block[263..265].copy_from_slice(b"00");
+
+        block[148..156].copy_from_slice(b"        ");
+        let checksum: u64 = block.iter().map(|v| *v as u64).sum();
+        block[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+        block
+    }
+
+    fn member(buf: &mut Vec<u8>, name: &str, content: &[u8], typeflag: u8) {
+        buf.extend(header(name, "", content.len(), typeflag));
+        buf.extend_from_slice(content);
+        buf.resize(round_up(buf.len() as u64) as usize, 0);
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let block = header("./dir/file", "", 1234, b'0');
+        let header = parse_header(&block).unwrap().unwrap();
+        assert_eq!(header.name, "./dir/file");
+        assert_eq!(header.size, 1234);
+        assert_eq!(header.mtime, 1_600_000_000);
+
+        assert!(parse_header(&[0; 512]).unwrap().is_none());
+
+        let mut block = block;
+        block[0] = b'x';
+        assert!(parse_header(&block).is_err());
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number(b"00000001750\0").unwrap(), 1000);
+        assert_eq!(parse_number(b"     \0").unwrap(), 0);
+        assert_eq!(parse_number(&[0x80, 0, 0, 0, 0, 1, 0, 0]).unwrap(), 65536);
+    }
+
+    #[test]
+    fn test_parse_pax() {
+        let records = parse_pax(b"30 mtime=1600000000.123456789\n14 path=a/b/c\n").unwrap();
+        assert_eq!(records.get("path").map(String::as_str), Some("a/b/c"));
+        assert_eq!(parse_pax_number(&records["mtime"]).unwrap(), 1_600_000_000);
+
+        assert!(parse_pax(b"100 path=a\n").is_err());
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("./a/b"), "a/b");
+        assert_eq!(normalize_name("/a/"), "a/");
+        assert_eq!(normalize_name("././a"), "a");
+        assert_eq!(normalize_name("./"), "");
+        assert_eq!(normalize_name("."), "");
+    }
+
+    #[cfg(feature = "services-memory")]
+    #[tokio::test]
+    async fn test_load_index() {
+        let mut buf = Vec::new();
+        member(&mut buf, "./", b"", b'5');
+        member(&mut buf, "./dir/", b"", b'5');
+        member(&mut buf, "./dir/file", b"hello", b'0');
+        let long = format!("{}/file", "x".repeat(120));
+        member(
+            &mut buf,
+            "././@LongLink",
+            format!("{long}\0").as_bytes(),
+            b'L',
+        );
+        member(&mut buf, "truncated", b"world!", b'0');
+        member(&mut buf, "PaxHeaders/pax", b"12 path=pax\n", b'x');
+        member(&mut buf, "ignored", b"pax", b'0');
+        buf.extend(header("link", "./dir/file", 0, b'1'));
+        // Two empty blocks mark the end of archive.
+        buf.resize(buf.len() + 2 * BLOCK_SIZE as usize, 0);
+
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        op.write("test.tar", buf.clone()).await.unwrap();
+
+        let index = TarIndex::load(&op, "test.tar").await.unwrap();
+        let paths: Vec<_> = index.list("", true).into_iter().map(|(k, _)| k).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "dir/".to_string(),
+                "dir/file".to_string(),
+                "link".to_string(),
+                "pax".to_string(),
+                format!("{}/", "x".repeat(120)),
+                long.clone(),
+            ]
+        );
+        assert_eq!(index.list("", false).len(), 4);
+
+        let read = |path: &str| {
+            let entry = index.get(path).unwrap();
+            buf[entry.offset as usize..(entry.offset + entry.size) as usize].to_vec()
+        };
+        assert_eq!(read("dir/file"), b"hello");
+        assert_eq!(read(&long), b"world!");
+        assert_eq!(read("pax"), b"pax");
+        assert_eq!(read("link"), b"hello");
+    }
+}
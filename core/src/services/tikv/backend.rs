@@ -0,0 +1,302 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use async_trait::async_trait;
+use tikv_client::Config;
+use tikv_client::Key;
+use tikv_client::RawClient;
+use tokio::sync::OnceCell;
+
+use crate::raw::adapters::kv;
+use crate::raw::*;
+use crate::*;
+
+/// The max number of keys returned by one raw scan request.
+const DEFAULT_SCAN_LIMIT: u32 = 1024;
+
+/// TiKV backend builder
+#[doc = include_str!("docs.md")]
+#[derive(Clone, Default)]
+pub struct TikvBuilder {
+    /// network address of the TiKV service.
+    endpoints: Option<Vec<String>>,
+    /// the working directory of the TiKV service. Can be "/path/to/dir"
+    ///
+    /// default is "/"
+    root: Option<String>,
+    /// certificate authority file path
+    ca_path: Option<String>,
+    /// cert path
+    cert_path: Option<String>,
+    /// key path
+    key_path: Option<String>,
+}
+
+impl Debug for TikvBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+        ds.field("endpoints", &self.endpoints);
+        ds.field("root", &self.root);
+        ds.field("ca_path", &self.ca_path);
+        ds.field("cert_path", &self.cert_path);
+        if self.key_path.is_some() {
+            ds.field("key_path", &"<redacted>");
+        }
+        ds.finish()
+    }
+}
+
+impl TikvBuilder {
+    /// Set the network address of the TiKV service (PD endpoints).
+    pub fn endpoints(&mut self, endpoints: Vec<String>) -> &mut Self {
+        let endpoints: Vec<String> = endpoints.into_iter().filter(|v| !v.is_empty()).collect();
+        if !endpoints.is_empty() {
+            self.endpoints = Some(endpoints);
+        }
+        self
+    }
+
+    /// set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_owned());
+        }
+        self
+    }
+
+    /// Set the certificate authority file path.
+    ///
+    /// TLS will be enabled if this is set, and `cert_path` and `key_path`
+    /// are also required.
+    pub fn ca_path(&mut self, ca_path: &str) -> &mut Self {
+        if !ca_path.is_empty() {
+            self.ca_path = Some(ca_path.to_string());
+        }
+        self
+    }
+
+    /// Set the certificate file path.
+    pub fn cert_path(&mut self, cert_path: &str) -> &mut Self {
+        if !cert_path.is_empty() {
+            self.cert_path = Some(cert_path.to_string());
+        }
+        self
+    }
+
+    /// Set the key file path.
+    pub fn key_path(&mut self, key_path: &str) -> &mut Self {
+        if !key_path.is_empty() {
+            self.key_path = Some(key_path.to_string());
+        }
+        self
+    }
+}
+
+impl Builder for TikvBuilder {
+    const SCHEME: Scheme = Scheme::Tikv;
+    type Accessor = TikvBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = TikvBuilder::default();
+
+        map.get("endpoints")
+            .map(|v| builder.endpoints(v.split(',').map(|s| s.trim().to_owned()).collect()));
+        map.get("root").map(|v| builder.root(v));
+        map.get("ca_path").map(|v| builder.ca_path(v));
+        map.get("cert_path").map(|v| builder.cert_path(v));
+        map.get("key_path").map(|v| builder.key_path(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let endpoints = self.endpoints.take().ok_or_else(|| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "endpoints is required but not set",
+            )
+            .with_context("service", Scheme::Tikv)
+        })?;
+
+        let security = match (
+            self.ca_path.take(),
+            self.cert_path.take(),
+            self.key_path.take(),
+        ) {
+            (None, None, None) => None,
+            (Some(ca_path), Some(cert_path), Some(key_path)) => {
+                Some((ca_path, cert_path, key_path))
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "ca_path, cert_path and key_path must be set together",
+                )
+                .with_context("service", Scheme::Tikv))
+            }
+        };
+
+        let root = normalize_root(
+            self.root
+                .clone()
+                .unwrap_or_else(|| "/".to_string())
+                .as_str(),
+        );
+
+        Ok(TikvBackend::new(Adapter {
+            endpoints,
+            security,
+            client: OnceCell::new(),
+        })
+        .with_root(&root))
+    }
+}
+
+/// Backend for TiKV service
+pub type TikvBackend = kv::Backend<Adapter>;
+
+#[derive(Clone)]
+pub struct Adapter {
+    endpoints: Vec<String>,
+    /// (ca_path, cert_path, key_path) used to connect with TLS.
+    security: Option<(String, String, String)>,
+    client: OnceCell<RawClient>,
+}
+
+impl Debug for Adapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Adapter");
+        ds.field("endpoints", &self.endpoints);
+        ds.field("tls", &self.security.is_some());
+        ds.finish()
+    }
+}
+
+impl Adapter {
+    async fn client(&self) -> Result<RawClient> {
+        Ok(self
+            .client
+            .get_or_try_init(|| async {
+                let client = match &self.security {
+                    Some((ca_path, cert_path, key_path)) => {
+                        RawClient::new_with_config(
+                            self.endpoints.clone(),
+                            Config::default().with_security(ca_path, cert_path, key_path),
+                        )
+                        .await
+                    }
+                    None => RawClient::new(self.endpoints.clone()).await,
+                };
+                client.map_err(parse_tikv_config_error)
+            })
+            .await?
+            .clone())
+    }
+}
+
+#[async_trait]
+impl kv::Adapter for Adapter {
+    fn metadata(&self) -> kv::Metadata {
+        kv::Metadata::new(
+            Scheme::Tikv,
+            &self.endpoints.join(","),
+            Capability {
+                read: true,
+                write: true,
+                list: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        self.client()
+            .await?
+            .get(path.to_owned())
+            .await
+            .map_err(parse_tikv_error)
+    }
+
+    async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
+        self.client()
+            .await?
+            .put(path.to_owned(), value.to_vec())
+            .await
+            .map_err(parse_tikv_error)
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        self.client()
+            .await?
+            .delete(path.to_owned())
+            .await
+            .map_err(parse_tikv_error)
+    }
+
+    /// Scan keys in order from `path` page by page, and stop at the first
+    /// key that doesn't share the prefix.
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        let client = self.client().await?;
+        let prefix = path.as_bytes();
+
+        let mut res = Vec::new();
+        let mut start = prefix.to_vec();
+        loop {
+            let keys: Vec<Key> = client
+                .scan_keys(start.clone().., DEFAULT_SCAN_LIMIT)
+                .await
+                .map_err(parse_tikv_error)?;
+            let done = keys.len() < DEFAULT_SCAN_LIMIT as usize;
+
+            for key in keys {
+                let key: Vec<u8> = key.into();
+                if !key.starts_with(prefix) {
+                    return Ok(res);
+                }
+
+                // The smallest key after current one is itself with a `\0` appended.
+                start = key.clone();
+                start.push(0);
+
+                res.push(String::from_utf8(key).map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "store key is not valid utf-8 string")
+                        .set_source(err)
+                })?);
+            }
+
+            if done {
+                return Ok(res);
+            }
+        }
+    }
+}
+
+fn parse_tikv_error(e: tikv_client::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "error from tikv").set_source(e)
+}
+
+fn parse_tikv_config_error(e: tikv_client::Error) -> Error {
+    Error::new(ErrorKind::ConfigInvalid, "invalid configuration")
+        .with_context("service", Scheme::Tikv)
+        .set_source(e)
+}
@@ -0,0 +1,369 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+
+use super::core::TosCore;
+use super::core::TosSigner;
+use super::error::parse_error;
+use super::pager::TosPager;
+use super::writer::TosWriter;
+use crate::raw::*;
+use crate::*;
+
+/// [Volcengine TOS](https://www.volcengine.com/product/TOS) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct TosBuilder {
+    root: Option<String>,
+    bucket: Option<String>,
+    region: Option<String>,
+    endpoint: Option<String>,
+    access_key_id: Option<String>,
+    secret_access_key: Option<String>,
+    security_token: Option<String>,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for TosBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+
+        ds.field("root", &self.root);
+        ds.field("bucket", &self.bucket);
+        ds.field("region", &self.region);
+        ds.field("endpoint", &self.endpoint);
+        if self.access_key_id.is_some() {
+            ds.field("access_key_id", &"<redacted>");
+        }
+        if self.secret_access_key.is_some() {
+            ds.field("secret_access_key", &"<redacted>");
+        }
+        if self.security_token.is_some() {
+            ds.field("security_token", &"<redacted>");
+        }
+        ds.finish()
+    }
+}
+
+impl TosBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+        self
+    }
+
+    /// Set the bucket name.
+    pub fn bucket(&mut self, bucket: &str) -> &mut Self {
+        if !bucket.is_empty() {
+            self.bucket = Some(bucket.to_string())
+        }
+        self
+    }
+
+    /// Set the region of bucket, like `cn-beijing`.
+    pub fn region(&mut self, region: &str) -> &mut Self {
+        if !region.is_empty() {
+            self.region = Some(region.to_string())
+        }
+        self
+    }
+
+    /// Set the endpoint of TOS, the bucket will be prepended to the host
+    /// of endpoint as virtual hosted style.
+    ///
+    /// default: "https://tos-<region>.volces.com"
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+        self
+    }
+
+    /// Set the access key id of Volcengine.
+    pub fn access_key_id(&mut self, access_key_id: &str) -> &mut Self {
+        if !access_key_id.is_empty() {
+            self.access_key_id = Some(access_key_id.to_string())
+        }
+        self
+    }
+
+    /// Set the secret access key of Volcengine.
+    pub fn secret_access_key(&mut self, secret_access_key: &str) -> &mut Self {
+        if !secret_access_key.is_empty() {
+            self.secret_access_key = Some(secret_access_key.to_string())
+        }
+        self
+    }
+
+    /// Set the security token of the temporary credential issued by STS.
+    pub fn security_token(&mut self, security_token: &str) -> &mut Self {
+        if !security_token.is_empty() {
+            self.security_token = Some(security_token.to_string())
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for TosBuilder {
+    const SCHEME: Scheme = Scheme::Tos;
+    type Accessor = TosBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = TosBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("bucket").map(|v| builder.bucket(v));
+        map.get("region").map(|v| builder.region(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("access_key_id").map(|v| builder.access_key_id(v));
+        map.get("secret_access_key")
+            .map(|v| builder.secret_access_key(v));
+        map.get("security_token").map(|v| builder.security_token(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        debug!("backend build started: {:?}", self);
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let bucket = self.bucket.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "bucket is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Tos)
+        })?;
+        debug!("backend use bucket {}", bucket);
+
+        let region = self.region.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "region is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Tos)
+        })?;
+        debug!("backend use region {}", region);
+
+        let endpoint = self
+            .endpoint
+            .take()
+            .unwrap_or_else(|| format!("https://tos-{region}.volces.com"));
+        let endpoint = match endpoint.split_once("://") {
+            Some((scheme, host)) => format!("{scheme}://{bucket}.{host}"),
+            None => format!("https://{bucket}.{endpoint}"),
+        };
+        debug!("backend use endpoint {}", endpoint);
+
+        let access_key_id = self.access_key_id.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "access_key_id is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Tos)
+        })?;
+        let secret_access_key = self.secret_access_key.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "secret_access_key is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Tos)
+        })?;
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Tos)
+            })?
+        };
+
+        debug!("backend build finished");
+        Ok(TosBackend {
+            core: Arc::new(TosCore {
+                root,
+                bucket,
+                endpoint,
+                signer: TosSigner {
+                    region,
+                    access_key_id,
+                    secret_access_key,
+                    security_token: self.security_token.take(),
+                },
+                client,
+            }),
+        })
+    }
+}
+
+/// Backend for Volcengine TOS services.
+#[derive(Debug, Clone)]
+pub struct TosBackend {
+    core: Arc<TosCore>,
+}
+
+#[async_trait]
+impl Accessor for TosBackend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = TosWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = TosPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Tos)
+            .set_root(&self.core.root)
+            .set_name(&self.core.bucket)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_without_content_length: true,
+                write_with_content_type: true,
+                write_with_content_disposition: true,
+                write_with_cache_control: true,
+
+                create_dir: true,
+                delete: true,
+                copy: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_delimiter_slash: true,
+                list_without_delimiter: true,
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        let resp = self
+            .core
+            .tos_put_object(path, &OpWrite::default(), bytes::Bytes::new())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCreateDir::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let resp = self.core.tos_get_object(path, args.range()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Ok((
+            RpWrite::default(),
+            TosWriter::new(self.core.clone(), path, args),
+        ))
+    }
+
+    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.tos_copy_object(from, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCopy::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let resp = self.core.tos_head_object(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.tos_delete_object(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((
+            RpList::default(),
+            TosPager::new(self.core.clone(), path, args.delimiter(), args.limit()),
+        ))
+    }
+}
@@ -0,0 +1,621 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
+use hmac::Hmac;
+use hmac::Mac;
+use http::header::CACHE_CONTROL;
+use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_LENGTH;
+use http::header::CONTENT_TYPE;
+use http::HeaderValue;
+use http::Request;
+use http::Response;
+use percent_encoding::percent_decode_str;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::AsciiSet;
+use percent_encoding::NON_ALPHANUMERIC;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::raw::*;
+use crate::*;
+
+/// The characters to be encoded in canonical request, which are all
+/// characters except the unreserved ones in RFC 3986.
+static TOS_URI_ENCODE_SET: AsciiSet = NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+const TOS_SIGN_ALGORITHM: &str = "TOS4-HMAC-SHA256";
+const X_TOS_DATE: &str = "x-tos-date";
+const X_TOS_CONTENT_SHA256: &str = "x-tos-content-sha256";
+const X_TOS_SECURITY_TOKEN: &str = "x-tos-security-token";
+const X_TOS_COPY_SOURCE: &str = "x-tos-copy-source";
+
+pub struct TosCore {
+    pub root: String,
+    pub bucket: String,
+    /// The virtual hosted endpoint of bucket, like
+    /// `https://<bucket>.tos-cn-beijing.volces.com`.
+    pub endpoint: String,
+
+    pub signer: TosSigner,
+    pub client: HttpClient,
+}
+
+impl Debug for TosCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TosCore")
+            .field("root", &self.root)
+            .field("bucket", &self.bucket)
+            .field("endpoint", &self.endpoint)
+            .field("region", &self.signer.region)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TosCore {
+    #[inline]
+    pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        self.client.send(req).await
+    }
+
+    pub fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        self.signer.sign(req, Utc::now())
+    }
+}
+
+impl TosCore {
+    pub async fn tos_get_object(
+        &self,
+        path: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::get(&url);
+
+        if !range.is_full() {
+            req = req.header(http::header::RANGE, range.to_header())
+        }
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    pub async fn tos_head_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::head(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    pub async fn tos_put_object(
+        &self,
+        path: &str,
+        args: &OpWrite,
+        body: Bytes,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::put(&url).header(CONTENT_LENGTH, body.len());
+
+        if let Some(mime) = args.content_type() {
+            req = req.header(CONTENT_TYPE, mime)
+        }
+
+        if let Some(content_disposition) = args.content_disposition() {
+            req = req.header(CONTENT_DISPOSITION, content_disposition)
+        }
+
+        if let Some(cache_control) = args.cache_control() {
+            req = req.header(CACHE_CONTROL, cache_control)
+        }
+
+        let mut req = req
+            .body(AsyncBody::Bytes(body))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    pub async fn tos_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::delete(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    pub async fn tos_copy_object(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let source = build_abs_path(&self.root, from);
+        let target = build_abs_path(&self.root, to);
+
+        let source = format!("/{}/{}", self.bucket, source);
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&target));
+
+        let mut req = Request::put(&url)
+            .header(X_TOS_COPY_SOURCE, percent_encode_path(&source))
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    /// List objects with given prefix.
+    ///
+    /// Ref: <https://www.volcengine.com/docs/6349/74863>
+    pub async fn tos_list_objects(
+        &self,
+        path: &str,
+        marker: &str,
+        delimiter: &str,
+        limit: Option<usize>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut queries = vec![];
+        if !p.is_empty() {
+            queries.push(format!(
+                "prefix={}",
+                utf8_percent_encode(&p, &TOS_URI_ENCODE_SET)
+            ));
+        }
+        if !delimiter.is_empty() {
+            queries.push(format!(
+                "delimiter={}",
+                utf8_percent_encode(delimiter, &TOS_URI_ENCODE_SET)
+            ));
+        }
+        if let Some(limit) = limit {
+            queries.push(format!("max-keys={limit}"));
+        }
+        if !marker.is_empty() {
+            queries.push(format!(
+                "marker={}",
+                utf8_percent_encode(marker, &TOS_URI_ENCODE_SET)
+            ));
+        }
+
+        let url = if queries.is_empty() {
+            format!("{}/", self.endpoint)
+        } else {
+            format!("{}/?{}", self.endpoint, queries.join("&"))
+        };
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    /// Ref: <https://www.volcengine.com/docs/6349/74866>
+    pub async fn tos_create_multipart_upload(
+        &self,
+        path: &str,
+        args: &OpWrite,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?uploads", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::post(&url);
+
+        if let Some(mime) = args.content_type() {
+            req = req.header(CONTENT_TYPE, mime)
+        }
+
+        if let Some(content_disposition) = args.content_disposition() {
+            req = req.header(CONTENT_DISPOSITION, content_disposition)
+        }
+
+        if let Some(cache_control) = args.cache_control() {
+            req = req.header(CACHE_CONTROL, cache_control)
+        }
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    /// Ref: <https://www.volcengine.com/docs/6349/74867>
+    pub async fn tos_upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        body: Bytes,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?partNumber={}&uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            part_number,
+            percent_encode_path(upload_id)
+        );
+
+        let mut req = Request::put(&url)
+            .header(CONTENT_LENGTH, body.len())
+            .body(AsyncBody::Bytes(body))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    /// Ref: <https://www.volcengine.com/docs/6349/74868>
+    pub async fn tos_complete_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: &[CompleteMultipartUploadPart],
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            percent_encode_path(upload_id)
+        );
+
+        let content = serde_json::to_vec(&CompleteMultipartUploadRequest {
+            parts: parts.to_vec(),
+        })
+        .map_err(new_json_serialize_error)?;
+
+        let mut req = Request::post(&url)
+            // Make sure content length has been set to avoid post with chunked encoding.
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/json")
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    /// Abort an on-going multipart upload.
+    ///
+    /// Ref: <https://www.volcengine.com/docs/6349/74869>
+    pub async fn tos_abort_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            percent_encode_path(upload_id)
+        );
+
+        let mut req = Request::delete(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+}
+
+/// TosSigner signs requests with TOS V4 signature.
+///
+/// `security_token` should be set if the credential is a temporary one
+/// issued by STS.
+///
+/// Ref: <https://www.volcengine.com/docs/6349/74839>
+pub struct TosSigner {
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub security_token: Option<String>,
+}
+
+impl TosSigner {
+    pub fn sign<T>(&self, req: &mut Request<T>, now: DateTime<Utc>) -> Result<()> {
+        let datetime = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let host = req
+            .uri()
+            .authority()
+            .map(|v| v.to_string())
+            .ok_or_else(|| Error::new(ErrorKind::Unexpected, "request uri doesn't have host"))?;
+
+        let headers = req.headers_mut();
+        headers.insert(http::header::HOST, to_header_value(&host)?);
+        headers.insert(X_TOS_DATE, to_header_value(&datetime)?);
+        headers.insert(
+            X_TOS_CONTENT_SHA256,
+            HeaderValue::from_static("UNSIGNED-PAYLOAD"),
+        );
+        if let Some(token) = &self.security_token {
+            headers.insert(X_TOS_SECURITY_TOKEN, to_header_value(token)?);
+        }
+
+        let mut signed_headers = req
+            .headers()
+            .iter()
+            .filter(|(k, _)| {
+                let k = k.as_str();
+                k == "host" || k == "content-type" || k == "content-md5" || k.starts_with("x-tos-")
+            })
+            .map(|(k, v)| {
+                let v = v.to_str().map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "header value is not valid string")
+                        .set_source(err)
+                })?;
+                Ok((k.as_str().to_string(), v.trim().to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        signed_headers.sort();
+
+        let canonical_request = [
+            req.method().as_str().to_string(),
+            canonical_uri(req.uri().path()),
+            canonical_query(req.uri().query().unwrap_or_default()),
+            signed_headers
+                .iter()
+                .map(|(k, v)| format!("{k}:{v}\n"))
+                .collect::<String>(),
+            signed_headers
+                .iter()
+                .map(|(k, _)| k.as_str())
+                .collect::<Vec<_>>()
+                .join(";"),
+            "UNSIGNED-PAYLOAD".to_string(),
+        ]
+        .join("\n");
+
+        let scope = format!("{date}/{}/tos/request", self.region);
+        let string_to_sign = format!(
+            "{TOS_SIGN_ALGORITHM}\n{datetime}\n{scope}\n{:x}",
+            Sha256::digest(canonical_request.as_bytes())
+        );
+
+        let signing_key = [self.region.as_str(), "tos", "request"].iter().fold(
+            hmac_sha256(self.secret_access_key.as_bytes(), date.as_bytes()),
+            |key, v| hmac_sha256(&key, v.as_bytes()),
+        );
+        let signature = hex_encode(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "{TOS_SIGN_ALGORITHM} Credential={}/{scope}, SignedHeaders={}, Signature={signature}",
+            self.access_key_id,
+            signed_headers
+                .iter()
+                .map(|(k, _)| k.as_str())
+                .collect::<Vec<_>>()
+                .join(";"),
+        );
+        req.headers_mut().insert(
+            http::header::AUTHORIZATION,
+            to_header_value(&authorization)?,
+        );
+
+        Ok(())
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac can take key of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex_encode(bs: &[u8]) -> String {
+    bs.iter().map(|v| format!("{v:02x}")).collect()
+}
+
+fn to_header_value(v: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(v)
+        .map_err(|err| Error::new(ErrorKind::Unexpected, "header value is invalid").set_source(err))
+}
+
+fn canonical_encode(v: &str) -> String {
+    utf8_percent_encode(
+        &percent_decode_str(v).decode_utf8_lossy(),
+        &TOS_URI_ENCODE_SET,
+    )
+    .to_string()
+}
+
+/// Encode every segment of the path, `/` is kept as is.
+fn canonical_uri(path: &str) -> String {
+    if path.is_empty() {
+        return "/".to_string();
+    }
+
+    path.split('/')
+        .map(canonical_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Encode every key and value of the query and sort them by key, keys
+/// without value like `uploads` are treated as `uploads=`.
+fn canonical_query(query: &str) -> String {
+    let mut pairs = query
+        .split('&')
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            let (k, v) = v.split_once('=').unwrap_or((v, ""));
+            (canonical_encode(k), canonical_encode(v))
+        })
+        .collect::<Vec<_>>();
+    pairs.sort();
+
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Result of CreateMultipartUpload
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct CreateMultipartUploadOutput {
+    pub upload_id: String,
+}
+
+/// Request of CompleteMultipartUpload
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CompleteMultipartUploadRequest {
+    pub parts: Vec<CompleteMultipartUploadPart>,
+}
+
+#[derive(Clone, Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CompleteMultipartUploadPart {
+    pub part_number: usize,
+    #[serde(rename = "ETag")]
+    pub etag: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_canonical_query() {
+        assert_eq!(canonical_query(""), "");
+        assert_eq!(canonical_query("uploads"), "uploads=");
+        assert_eq!(
+            canonical_query("uploadId=a%2Fb&partNumber=1"),
+            "partNumber=1&uploadId=a%2Fb"
+        );
+        assert_eq!(
+            canonical_query("prefix=dir/a b&max-keys=10"),
+            "max-keys=10&prefix=dir%2Fa%20b"
+        );
+    }
+
+    #[test]
+    fn test_canonical_uri() {
+        assert_eq!(canonical_uri(""), "/");
+        assert_eq!(canonical_uri("/"), "/");
+        assert_eq!(canonical_uri("/dir/a%20b!.txt"), "/dir/a%20b%21.txt");
+    }
+
+    #[test]
+    fn test_sign() {
+        let signer = TosSigner {
+            region: "cn-beijing".to_string(),
+            access_key_id: "AKTEST".to_string(),
+            secret_access_key: "SKTEST".to_string(),
+            security_token: Some("STSTOKEN".to_string()),
+        };
+
+        let mut req = Request::put(
+            "https://examplebucket.tos-cn-beijing.volces.com/dir/a.txt?partNumber=1&uploadId=abc",
+        )
+        .header(CONTENT_TYPE, "text/plain")
+        .body(())
+        .unwrap();
+        signer
+            .sign(&mut req, Utc.timestamp_opt(1_700_000_000, 0).unwrap())
+            .unwrap();
+
+        assert_eq!(req.headers()[X_TOS_DATE], "20231114T221320Z");
+        assert_eq!(req.headers()[X_TOS_SECURITY_TOKEN], "STSTOKEN");
+        assert_eq!(
+            req.headers()[http::header::AUTHORIZATION],
+            "TOS4-HMAC-SHA256 Credential=AKTEST/20231114/cn-beijing/tos/request, SignedHeaders=content-type;host;x-tos-content-sha256;x-tos-date;x-tos-security-token, Signature=ff359613bd5807a4420fdced432851fa9789b17ed76139aa8fd277b38bf9017b"
+        );
+    }
+
+    #[test]
+    fn test_serialize_complete_multipart_upload_request() {
+        let req = CompleteMultipartUploadRequest {
+            parts: vec![
+                CompleteMultipartUploadPart {
+                    part_number: 1,
+                    etag: "\"0cce40bdbaf2fa0ff204c20fc965dd3f\"".to_string(),
+                },
+                CompleteMultipartUploadPart {
+                    part_number: 2,
+                    etag: "\"0cce40bdbaf2fa0ff204c20fc965dd3f\"".to_string(),
+                },
+            ],
+        };
+
+        let actual = serde_json::to_string(&req).expect("must succeed");
+
+        assert_eq!(
+            actual,
+            r#"{"Parts":[{"PartNumber":1,"ETag":"\"0cce40bdbaf2fa0ff204c20fc965dd3f\""},{"PartNumber":2,"ETag":"\"0cce40bdbaf2fa0ff204c20fc965dd3f\""}]}"#
+        )
+    }
+}
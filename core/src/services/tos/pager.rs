@@ -0,0 +1,172 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::core::TosCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct TosPager {
+    core: Arc<TosCore>,
+    path: String,
+    delimiter: String,
+    limit: Option<usize>,
+
+    next_marker: String,
+    done: bool,
+}
+
+impl TosPager {
+    pub fn new(core: Arc<TosCore>, path: &str, delimiter: &str, limit: Option<usize>) -> Self {
+        Self {
+            core,
+            path: path.to_string(),
+            delimiter: delimiter.to_string(),
+            limit,
+
+            next_marker: "".to_string(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for TosPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let resp = self
+            .core
+            .tos_list_objects(&self.path, &self.next_marker, &self.delimiter, self.limit)
+            .await?;
+
+        if resp.status() != http::StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: ListObjectsOutput =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        self.done = !output.is_truncated || output.next_marker.is_empty();
+        self.next_marker = output.next_marker;
+
+        let mut entries = Vec::with_capacity(output.common_prefixes.len() + output.contents.len());
+
+        for prefix in output.common_prefixes {
+            let de = oio::Entry::new(
+                &build_rel_path(&self.core.root, &prefix.prefix),
+                Metadata::new(EntryMode::DIR),
+            );
+
+            entries.push(de);
+        }
+
+        for object in output.contents {
+            if object.key.ends_with('/') {
+                continue;
+            }
+
+            let mut meta = Metadata::new(EntryMode::FILE);
+            meta.set_content_length(object.size);
+            meta.set_etag(&object.etag);
+            meta.set_last_modified(parse_datetime_from_rfc3339(&object.last_modified)?);
+
+            let de = oio::Entry::new(&build_rel_path(&self.core.root, &object.key), meta);
+
+            entries.push(de);
+        }
+
+        Ok(Some(entries))
+    }
+}
+
+/// Ref: <https://www.volcengine.com/docs/6349/74863>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct ListObjectsOutput {
+    is_truncated: bool,
+    next_marker: String,
+    common_prefixes: Vec<CommonPrefix>,
+    contents: Vec<Content>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct CommonPrefix {
+    prefix: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct Content {
+    key: String,
+    size: u64,
+    #[serde(rename = "ETag")]
+    etag: String,
+    last_modified: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_list_objects_output() {
+        let content = r#"{
+            "Name": "examplebucket",
+            "Prefix": "dir/",
+            "Marker": "",
+            "MaxKeys": 2,
+            "NextMarker": "dir/b.txt",
+            "Delimiter": "/",
+            "IsTruncated": true,
+            "EncodingType": "",
+            "CommonPrefixes": [{"Prefix": "dir/sub/"}],
+            "Contents": [
+                {
+                    "Key": "dir/a.txt",
+                    "LastModified": "2023-06-01T08:00:00.000Z",
+                    "ETag": "\"0cce40bdbaf2fa0ff204c20fc965dd3f\"",
+                    "Size": 1024,
+                    "StorageClass": "STANDARD",
+                    "HashCrc64ecma": 5873247474231214096
+                }
+            ]
+        }"#;
+
+        let output: ListObjectsOutput =
+            serde_json::from_str(content).expect("output must be valid");
+        assert!(output.is_truncated);
+        assert_eq!(output.next_marker, "dir/b.txt");
+        assert_eq!(output.common_prefixes[0].prefix, "dir/sub/");
+        assert_eq!(output.contents[0].key, "dir/a.txt");
+        assert_eq!(output.contents[0].size, 1024);
+        assert_eq!(
+            output.contents[0].etag,
+            "\"0cce40bdbaf2fa0ff204c20fc965dd3f\""
+        );
+        assert_eq!(output.contents[0].last_modified, "2023-06-01T08:00:00.000Z");
+    }
+}
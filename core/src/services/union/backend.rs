@@ -0,0 +1,351 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use super::pager::UnionPager;
+use crate::raw::*;
+use crate::*;
+
+/// Union service support which merges several operators into one.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct UnionBuilder {
+    layers: Vec<Operator>,
+    write_layers: Vec<usize>,
+}
+
+impl UnionBuilder {
+    /// Add an operator as a read only layer.
+    ///
+    /// Layers added earlier take precedence over layers added later.
+    pub fn layer(&mut self, op: Operator) -> &mut Self {
+        self.layers.push(op);
+        self
+    }
+
+    /// Add an operator as a layer which also serves all writes.
+    ///
+    /// Layers added earlier take precedence over layers added later.
+    /// Only one write layer is allowed.
+    pub fn write_layer(&mut self, op: Operator) -> &mut Self {
+        self.write_layers.push(self.layers.len());
+        self.layers.push(op);
+        self
+    }
+}
+
+impl Builder for UnionBuilder {
+    const SCHEME: Scheme = Scheme::Union;
+    type Accessor = UnionBackend;
+
+    /// Union can't be built from map since it's composed by operators,
+    /// the returning builder doesn't have any layers.
+    fn from_map(_: HashMap<String, String>) -> Self {
+        Self::default()
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        if self.layers.is_empty() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "union requires at least one layer",
+            )
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Union));
+        }
+        if self.write_layers.len() > 1 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "union allows only one write layer",
+            )
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Union));
+        }
+
+        Ok(UnionBackend {
+            layers: self.layers.iter().map(|v| v.inner().clone()).collect(),
+            write_layer: self.write_layers.first().copied(),
+        })
+    }
+}
+
+/// Backend is used to serve `Accessor` support for union.
+#[derive(Debug, Clone)]
+pub struct UnionBackend {
+    layers: Vec<FusedAccessor>,
+    write_layer: Option<usize>,
+}
+
+impl UnionBackend {
+    fn write_layer(&self) -> Result<&FusedAccessor> {
+        match self.write_layer {
+            Some(idx) => Ok(&self.layers[idx]),
+            None => Err(Error::new(
+                ErrorKind::Unsupported,
+                "union doesn't have a write layer",
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl Accessor for UnionBackend {
+    type Reader = oio::Reader;
+    type BlockingReader = oio::BlockingReader;
+    type Writer = oio::Writer;
+    type BlockingWriter = oio::BlockingWriter;
+    type Appender = oio::Appender;
+    type Pager = UnionPager<oio::Pager>;
+    type BlockingPager = UnionPager<oio::BlockingPager>;
+
+    fn info(&self) -> AccessorInfo {
+        let caps: Vec<Capability> = self.layers.iter().map(|v| v.info().capability()).collect();
+        let all = |f: fn(&Capability) -> bool| caps.iter().all(f);
+        let w = self.write_layer.map(|idx| caps[idx]).unwrap_or_default();
+
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Union)
+            .set_root("/")
+            .set_capability(Capability {
+                stat: all(|c| c.stat),
+                stat_has_permission: all(|c| c.stat_has_permission),
+                stat_has_user_metadata: all(|c| c.stat_has_user_metadata),
+
+                read: all(|c| c.read),
+                read_can_seek: all(|c| c.read_can_seek),
+                read_can_next: all(|c| c.read_can_next),
+                read_with_range: all(|c| c.read_with_range),
+
+                write: w.write,
+                write_without_content_length: w.write_without_content_length,
+                write_with_content_type: w.write_with_content_type,
+                write_with_content_disposition: w.write_with_content_disposition,
+                write_with_cache_control: w.write_with_cache_control,
+                write_with_permission: w.write_with_permission,
+                write_with_user_metadata: w.write_with_user_metadata,
+                append: w.append,
+                append_with_content_type: w.append_with_content_type,
+                append_with_content_disposition: w.append_with_content_disposition,
+                append_with_cache_control: w.append_with_cache_control,
+                create_dir: w.create_dir,
+                delete: w.delete,
+
+                list: all(|c| c.list),
+                list_with_delimiter_slash: all(|c| c.list_with_delimiter_slash),
+                list_without_delimiter: all(|c| c.list_without_delimiter),
+
+                blocking: all(|c| c.blocking),
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.write_layer()?.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let mut last_err = None;
+        for layer in &self.layers {
+            match layer.read(path, args.clone()).await {
+                Err(err) if err.kind() == ErrorKind::NotFound => last_err = Some(err),
+                v => return v,
+            }
+        }
+
+        Err(last_err.expect("union must have at least one layer"))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.write_layer()?.write(path, args).await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.write_layer()?.append(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let mut last_err = None;
+        for layer in &self.layers {
+            match layer.stat(path, args.clone()).await {
+                Err(err) if err.kind() == ErrorKind::NotFound => last_err = Some(err),
+                v => return v,
+            }
+        }
+
+        Err(last_err.expect("union must have at least one layer"))
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.write_layer()?.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let mut pagers = Vec::with_capacity(self.layers.len());
+        for layer in &self.layers {
+            match layer.list(path, args.clone()).await {
+                Ok((_, pager)) => pagers.push(pager),
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((RpList::default(), UnionPager::new(pagers)))
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.write_layer()?.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let mut last_err = None;
+        for layer in &self.layers {
+            match layer.blocking_read(path, args.clone()) {
+                Err(err) if err.kind() == ErrorKind::NotFound => last_err = Some(err),
+                v => return v,
+            }
+        }
+
+        Err(last_err.expect("union must have at least one layer"))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.write_layer()?.blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let mut last_err = None;
+        for layer in &self.layers {
+            match layer.blocking_stat(path, args.clone()) {
+                Err(err) if err.kind() == ErrorKind::NotFound => last_err = Some(err),
+                v => return v,
+            }
+        }
+
+        Err(last_err.expect("union must have at least one layer"))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.write_layer()?.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        let mut pagers = Vec::with_capacity(self.layers.len());
+        for layer in &self.layers {
+            match layer.blocking_list(path, args.clone()) {
+                Ok((_, pager)) => pagers.push(pager),
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((RpList::default(), UnionPager::new(pagers)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    fn new_memory() -> Operator {
+        Operator::new(Memory::default()).unwrap().finish()
+    }
+
+    #[tokio::test]
+    async fn test_read_first_hit() {
+        let upper = new_memory();
+        let lower = new_memory();
+        upper.write("a", "upper").await.unwrap();
+        lower.write("a", "lower").await.unwrap();
+        lower.write("b", "lower").await.unwrap();
+
+        let mut builder = UnionBuilder::default();
+        builder.layer(upper).layer(lower);
+        let op = Operator::new(builder).unwrap().finish();
+
+        assert_eq!(op.read("a").await.unwrap(), b"upper");
+        assert_eq!(op.read("b").await.unwrap(), b"lower");
+        assert_eq!(op.stat("b").await.unwrap().content_length(), 5);
+        assert_eq!(op.read("c").await.unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(
+            op.write("a", "new").await.unwrap_err().kind(),
+            ErrorKind::Unsupported
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_merged() {
+        let upper = new_memory();
+        let lower = new_memory();
+        upper.write("dir/a", "upper").await.unwrap();
+        lower.write("dir/a", "lower").await.unwrap();
+        lower.write("dir/b", "lower").await.unwrap();
+
+        let mut builder = UnionBuilder::default();
+        builder.layer(upper).layer(lower);
+        let op = Operator::new(builder).unwrap().finish();
+
+        let mut paths: Vec<String> = op
+            .scan("dir/")
+            .await
+            .unwrap()
+            .map_ok(|v| v.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        paths.sort();
+        assert_eq!(paths, vec!["dir/a", "dir/b"]);
+    }
+
+    #[test]
+    fn test_write_layer() {
+        let upper = new_memory();
+        let lower = new_memory();
+        lower.blocking().write("a", "lower").unwrap();
+
+        let mut builder = UnionBuilder::default();
+        builder.write_layer(upper.clone()).layer(lower);
+        let op = Operator::new(builder).unwrap().finish().blocking();
+
+        op.write("a", "upper").unwrap();
+        assert_eq!(op.read("a").unwrap(), b"upper");
+        assert_eq!(upper.blocking().read("a").unwrap(), b"upper");
+
+        // Delete only happens in write layer.
+        op.delete("a").unwrap();
+        assert_eq!(op.read("a").unwrap(), b"lower");
+    }
+
+    #[test]
+    fn test_build_invalid() {
+        let err = UnionBuilder::default().build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let mut builder = UnionBuilder::default();
+        builder.write_layer(new_memory()).write_layer(new_memory());
+        let err = builder.build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+}
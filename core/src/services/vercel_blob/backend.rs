@@ -0,0 +1,329 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+
+use super::core::HeadResponse;
+use super::core::VercelBlobCore;
+use super::error::parse_error;
+use super::pager::VercelBlobPager;
+use super::writer::VercelBlobWriter;
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_ENDPOINT: &str = "https://blob.vercel-storage.com";
+
+/// [Vercel Blob](https://vercel.com/docs/storage/vercel-blob) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct VercelBlobBuilder {
+    root: Option<String>,
+    token: Option<String>,
+    endpoint: Option<String>,
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for VercelBlobBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Builder")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+impl VercelBlobBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string());
+        }
+        self
+    }
+
+    /// Set the read write token of the blob store.
+    ///
+    /// The token is exposed as `BLOB_READ_WRITE_TOKEN` in Vercel projects.
+    pub fn token(&mut self, token: &str) -> &mut Self {
+        if !token.is_empty() {
+            self.token = Some(token.to_string());
+        }
+        self
+    }
+
+    /// Set the endpoint of Vercel Blob API.
+    ///
+    /// default: `https://blob.vercel-storage.com`
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for VercelBlobBuilder {
+    const SCHEME: Scheme = Scheme::VercelBlob;
+    type Accessor = VercelBlobBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = VercelBlobBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("token").map(|v| builder.token(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let token = self.token.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "token is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::VercelBlob)
+        })?;
+
+        let endpoint = self
+            .endpoint
+            .take()
+            .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
+        debug!("backend use endpoint {}", endpoint);
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::VercelBlob)
+            })?
+        };
+
+        Ok(VercelBlobBackend {
+            core: Arc::new(VercelBlobCore {
+                root,
+                endpoint,
+                token,
+                client,
+            }),
+        })
+    }
+}
+
+/// Backend for Vercel Blob services.
+#[derive(Clone, Debug)]
+pub struct VercelBlobBackend {
+    core: Arc<VercelBlobCore>,
+}
+
+#[async_trait]
+impl Accessor for VercelBlobBackend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = VercelBlobWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = VercelBlobPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut ma = AccessorInfo::default();
+        ma.set_scheme(Scheme::VercelBlob)
+            .set_root(&self.core.root)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_without_content_length: true,
+                write_with_content_type: true,
+
+                create_dir: true,
+                delete: true,
+                copy: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_delimiter_slash: true,
+                list_without_delimiter: true,
+
+                ..Default::default()
+            });
+
+        ma
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        let resp = self
+            .core
+            .vercel_blob_put(path, Some(0), None, AsyncBody::Empty)
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCreateDir::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let blob = self
+            .core
+            .vercel_blob_head(path)
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "blob not found in vercel blob"))?;
+
+        let resp = self.core.vercel_blob_get(&blob.url, args.range()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Ok((
+            RpWrite::default(),
+            VercelBlobWriter::new(self.core.clone(), args, path.to_string()),
+        ))
+    }
+
+    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
+        let blob = self
+            .core
+            .vercel_blob_head(from)
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "blob not found in vercel blob"))?;
+
+        let resp = self.core.vercel_blob_copy(&blob.url, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCopy::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let blob = self
+            .core
+            .vercel_blob_head(path)
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "blob not found in vercel blob"))?;
+        if blob.pathname.ends_with('/') {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        // List API doesn't return the content type of blobs, we need to
+        // fetch it from head API.
+        let resp = self.core.vercel_blob_get_metadata(&blob.url).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let output: HeadResponse =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+                Ok(RpStat::new(output.to_metadata()?))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let blob = match self.core.vercel_blob_head(path).await? {
+            Some(blob) => blob,
+            // Deleting a not exist blob should succeed.
+            None => return Ok(RpDelete::default()),
+        };
+
+        let resp = self.core.vercel_blob_delete(&[&blob.url]).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let folded = match args.delimiter() {
+            "/" => true,
+            "" => false,
+            v => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "vercel blob only support delimiter `/` or empty",
+                )
+                .with_context("delimiter", v))
+            }
+        };
+
+        Ok((
+            RpList::default(),
+            VercelBlobPager::new(self.core.clone(), path, args.limit(), folded),
+        ))
+    }
+}
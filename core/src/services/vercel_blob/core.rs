@@ -0,0 +1,470 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use bytes::Bytes;
+use http::header;
+use http::request;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The version of Vercel Blob API we are using.
+const VERCEL_BLOB_API_VERSION: &str = "7";
+
+pub struct VercelBlobCore {
+    pub root: String,
+    pub endpoint: String,
+    pub token: String,
+    pub client: HttpClient,
+}
+
+impl Debug for VercelBlobCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VercelBlobCore")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl VercelBlobCore {
+    fn sign(&self, req: &mut Request<AsyncBody>) -> Result<()> {
+        let headers = req.headers_mut();
+
+        headers.insert(
+            header::AUTHORIZATION,
+            format_authorization_by_bearer(&self.token)?
+                .parse()
+                .map_err(|_| Error::new(ErrorKind::Unexpected, "invalid token"))?,
+        );
+        headers.insert(
+            "x-api-version",
+            VERCEL_BLOB_API_VERSION
+                .parse()
+                .map_err(|_| Error::new(ErrorKind::Unexpected, "invalid api version"))?,
+        );
+
+        Ok(())
+    }
+
+    /// Vercel Blob adds a random suffix to the pathname and rejects
+    /// overwriting by default, we disable both to behave like a normal
+    /// storage service.
+    fn insert_put_headers(mut req: request::Builder, args: Option<&OpWrite>) -> request::Builder {
+        req = req
+            .header("x-add-random-suffix", "0")
+            .header("x-allow-overwrite", "1");
+
+        if let Some(args) = args {
+            if let Some(content_type) = args.content_type() {
+                req = req.header("x-content-type", content_type);
+            }
+        }
+
+        req
+    }
+
+    pub async fn vercel_blob_put(
+        &self,
+        path: &str,
+        size: Option<usize>,
+        args: Option<&OpWrite>,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Self::insert_put_headers(Request::put(&url), args);
+        if let Some(size) = size {
+            req = req.header(header::CONTENT_LENGTH, size);
+        }
+
+        let mut req = req.body(body).map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn vercel_blob_copy(
+        &self,
+        from_url: &str,
+        to: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, to);
+
+        let url = format!(
+            "{}/{}?fromUrl={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            utf8_percent_encode(from_url, NON_ALPHANUMERIC)
+        );
+
+        let mut req = Self::insert_put_headers(Request::put(&url), None)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn vercel_blob_list(
+        &self,
+        prefix: &str,
+        limit: Option<usize>,
+        cursor: Option<&str>,
+        folded: bool,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut url = format!(
+            "{}?prefix={}",
+            self.endpoint,
+            utf8_percent_encode(prefix, NON_ALPHANUMERIC)
+        );
+        if let Some(limit) = limit {
+            url.push_str(&format!("&limit={limit}"));
+        }
+        if let Some(cursor) = cursor {
+            url.push_str(&format!(
+                "&cursor={}",
+                utf8_percent_encode(cursor, NON_ALPHANUMERIC)
+            ));
+        }
+        if folded {
+            url.push_str("&mode=folded");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    /// Get the blob of given path.
+    ///
+    /// Vercel Blob identifies blobs by url, so we find the blob by listing
+    /// with the pathname as prefix. Returns `None` if the blob doesn't exist.
+    pub async fn vercel_blob_head(&self, path: &str) -> Result<Option<VercelBlobItem>> {
+        let p = build_abs_path(&self.root, path);
+
+        let resp = self.vercel_blob_list(&p, Some(1), None, false).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: ListResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        // Blobs are sorted by pathname, so the blob itself must be the
+        // first one if exists.
+        Ok(output.blobs.into_iter().next().filter(|v| v.pathname == p))
+    }
+
+    /// Get the full metadata of the blob via its url.
+    pub async fn vercel_blob_get_metadata(&self, url: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}?url={}",
+            self.endpoint,
+            utf8_percent_encode(url, NON_ALPHANUMERIC)
+        );
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    /// Download the blob via its public url.
+    ///
+    /// The url is public so we should not sign it.
+    pub async fn vercel_blob_get(
+        &self,
+        url: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::get(url);
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn vercel_blob_delete(&self, urls: &[&str]) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}/delete", self.endpoint);
+
+        let bs = serde_json::to_vec(&json!({ "urls": urls })).map_err(new_json_serialize_error)?;
+
+        let mut req = Request::post(&url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(AsyncBody::Bytes(bs.into()))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    fn mpu_request(&self, path: &str, action: &str) -> request::Builder {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/mpu/{}", self.endpoint, percent_encode_path(&p));
+
+        Request::post(&url).header("x-mpu-action", action)
+    }
+
+    pub async fn vercel_blob_initiate_multipart_upload(
+        &self,
+        path: &str,
+        args: &OpWrite,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let req = Self::insert_put_headers(self.mpu_request(path, "create"), Some(args));
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn vercel_blob_upload_part(
+        &self,
+        path: &str,
+        upload: &InitiateMultipartUploadResponse,
+        part_number: usize,
+        bs: Bytes,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self
+            .mpu_request(path, "upload")
+            .header(
+                "x-mpu-key",
+                utf8_percent_encode(&upload.key, NON_ALPHANUMERIC).to_string(),
+            )
+            .header("x-mpu-upload-id", &upload.upload_id)
+            .header("x-mpu-part-number", part_number)
+            .header(header::CONTENT_LENGTH, bs.len())
+            .body(AsyncBody::Bytes(bs))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn vercel_blob_complete_multipart_upload(
+        &self,
+        path: &str,
+        upload: &InitiateMultipartUploadResponse,
+        parts: &[UploadedPart],
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let bs = serde_json::to_vec(parts).map_err(new_json_serialize_error)?;
+
+        let req = Self::insert_put_headers(self.mpu_request(path, "complete"), None);
+        let mut req = req
+            .header(
+                "x-mpu-key",
+                utf8_percent_encode(&upload.key, NON_ALPHANUMERIC).to_string(),
+            )
+            .header("x-mpu-upload-id", &upload.upload_id)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(AsyncBody::Bytes(bs.into()))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send(req).await
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ListResponse {
+    pub blobs: Vec<VercelBlobItem>,
+    /// Only returned in `folded` mode.
+    pub folders: Vec<String>,
+    pub cursor: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct VercelBlobItem {
+    pub url: String,
+    pub download_url: String,
+    pub pathname: String,
+    pub size: u64,
+    pub uploaded_at: String,
+}
+
+impl VercelBlobItem {
+    pub fn to_metadata(&self) -> Result<Metadata> {
+        // Dirs are created as empty blobs ends with `/`.
+        if self.pathname.ends_with('/') {
+            return Ok(Metadata::new(EntryMode::DIR));
+        }
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        meta.set_content_length(self.size);
+        if !self.uploaded_at.is_empty() {
+            meta.set_last_modified(parse_datetime_from_rfc3339(&self.uploaded_at)?);
+        }
+
+        Ok(meta)
+    }
+}
+
+/// The response of head API, which contains more metadata than list API.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HeadResponse {
+    pub pathname: String,
+    pub size: u64,
+    pub uploaded_at: String,
+    pub content_type: String,
+    pub content_disposition: String,
+}
+
+impl HeadResponse {
+    pub fn to_metadata(&self) -> Result<Metadata> {
+        if self.pathname.ends_with('/') {
+            return Ok(Metadata::new(EntryMode::DIR));
+        }
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        meta.set_content_length(self.size);
+        if !self.uploaded_at.is_empty() {
+            meta.set_last_modified(parse_datetime_from_rfc3339(&self.uploaded_at)?);
+        }
+        if !self.content_type.is_empty() {
+            meta.set_content_type(&self.content_type);
+        }
+        if !self.content_disposition.is_empty() {
+            meta.set_content_disposition(&self.content_disposition);
+        }
+
+        Ok(meta)
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InitiateMultipartUploadResponse {
+    pub key: String,
+    pub upload_id: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct UploadPartResponse {
+    pub etag: String,
+}
+
+#[derive(Default, Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadedPart {
+    pub part_number: usize,
+    pub etag: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list_response() -> Result<()> {
+        let bs = r#"{
+            "blobs": [
+                {
+                    "url": "https://store.public.blob.vercel-storage.com/dir/file",
+                    "downloadUrl": "https://store.public.blob.vercel-storage.com/dir/file?download=1",
+                    "pathname": "dir/file",
+                    "size": 42,
+                    "uploadedAt": "2023-11-01T06:12:54.000Z"
+                },
+                {
+                    "url": "https://store.public.blob.vercel-storage.com/dir/sub/",
+                    "downloadUrl": "https://store.public.blob.vercel-storage.com/dir/sub/?download=1",
+                    "pathname": "dir/sub/",
+                    "size": 0,
+                    "uploadedAt": "2023-11-01T06:12:54.000Z"
+                }
+            ],
+            "folders": ["dir/sub/"],
+            "cursor": "abc",
+            "hasMore": true
+        }"#;
+
+        let output: ListResponse = serde_json::from_str(bs).map_err(new_json_deserialize_error)?;
+        assert!(output.has_more);
+        assert_eq!(output.cursor.as_deref(), Some("abc"));
+        assert_eq!(output.folders, vec!["dir/sub/".to_string()]);
+
+        let meta = output.blobs[0].to_metadata()?;
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), 42);
+        assert_eq!(
+            meta.last_modified(),
+            Some(parse_datetime_from_rfc3339("2023-11-01T06:12:54.000Z")?)
+        );
+        assert_eq!(output.blobs[1].to_metadata()?.mode(), EntryMode::DIR);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_head_response() -> Result<()> {
+        let bs = r#"{
+            "url": "https://store.public.blob.vercel-storage.com/dir/file.txt",
+            "downloadUrl": "https://store.public.blob.vercel-storage.com/dir/file.txt?download=1",
+            "pathname": "dir/file.txt",
+            "size": 42,
+            "uploadedAt": "2023-11-01T06:12:54.000Z",
+            "contentType": "text/plain",
+            "contentDisposition": "attachment; filename=\"file.txt\"",
+            "cacheControl": "public, max-age=31536000"
+        }"#;
+
+        let output: HeadResponse = serde_json::from_str(bs).map_err(new_json_deserialize_error)?;
+        let meta = output.to_metadata()?;
+        assert_eq!(meta.content_length(), 42);
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(
+            meta.content_disposition(),
+            Some(r#"attachment; filename="file.txt""#)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::core::ListResponse;
+use super::core::VercelBlobCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct VercelBlobPager {
+    core: Arc<VercelBlobCore>,
+    path: String,
+    limit: Option<usize>,
+    folded: bool,
+
+    cursor: Option<String>,
+    done: bool,
+}
+
+impl VercelBlobPager {
+    pub fn new(core: Arc<VercelBlobCore>, path: &str, limit: Option<usize>, folded: bool) -> Self {
+        VercelBlobPager {
+            core,
+            path: path.to_string(),
+            limit,
+            folded,
+
+            cursor: None,
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for VercelBlobPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let p = build_abs_path(&self.core.root, &self.path);
+
+        let resp = self
+            .core
+            .vercel_blob_list(&p, self.limit, self.cursor.as_deref(), self.folded)
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: ListResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+        match output.cursor {
+            Some(cursor) if output.has_more => self.cursor = Some(cursor),
+            _ => self.done = true,
+        }
+
+        let mut entries = Vec::with_capacity(output.folders.len() + output.blobs.len());
+
+        for folder in output.folders {
+            let path = build_rel_path(&self.core.root, &format!("/{folder}"));
+            entries.push(oio::Entry::new(&path, Metadata::new(EntryMode::DIR)));
+        }
+
+        for blob in output.blobs {
+            // Dirs are returned in folders, we should ignore the blobs
+            // created by create_dir which ends with `/`.
+            if blob.pathname.ends_with('/') {
+                continue;
+            }
+
+            let path = build_rel_path(&self.core.root, &format!("/{}", blob.pathname));
+            entries.push(oio::Entry::new(&path, blob.to_metadata()?));
+        }
+
+        Ok(Some(entries))
+    }
+}
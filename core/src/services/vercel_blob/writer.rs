@@ -0,0 +1,235 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::StatusCode;
+
+use super::core::InitiateMultipartUploadResponse;
+use super::core::UploadPartResponse;
+use super::core::UploadedPart;
+use super::core::VercelBlobCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The size of each part in multipart upload.
+///
+/// Vercel Blob requires every part except the last one to be at least 5 MiB.
+const VERCEL_BLOB_PART_SIZE: usize = 8 * 1024 * 1024;
+
+pub struct VercelBlobWriter {
+    core: Arc<VercelBlobCore>,
+    op: OpWrite,
+    path: String,
+
+    upload: Option<InitiateMultipartUploadResponse>,
+    parts: Vec<UploadedPart>,
+    buffer: oio::VectorCursor,
+}
+
+impl VercelBlobWriter {
+    pub fn new(core: Arc<VercelBlobCore>, op: OpWrite, path: String) -> Self {
+        VercelBlobWriter {
+            core,
+            op,
+            path,
+
+            upload: None,
+            parts: vec![],
+            buffer: oio::VectorCursor::new(),
+        }
+    }
+
+    async fn write_oneshot(&self, bs: Bytes) -> Result<()> {
+        let resp = self
+            .core
+            .vercel_blob_put(
+                &self.path,
+                Some(bs.len()),
+                Some(&self.op),
+                AsyncBody::Bytes(bs),
+            )
+            .await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn initiate_upload(&self) -> Result<InitiateMultipartUploadResponse> {
+        let resp = self
+            .core
+            .vercel_blob_initiate_multipart_upload(&self.path, &self.op)
+            .await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Upload the first `size` bytes in buffer as a part.
+    async fn write_part(&mut self, size: usize) -> Result<()> {
+        let upload = match &self.upload {
+            Some(upload) => upload,
+            None => unreachable!("upload must be initiated"),
+        };
+
+        // Vercel Blob's part number starts from 1.
+        let part_number = self.parts.len() + 1;
+        let bs = self.buffer.peak_exact(size);
+
+        let resp = self
+            .core
+            .vercel_blob_upload_part(&self.path, upload, part_number, bs)
+            .await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                let body = resp.into_body().bytes().await?;
+                let output: UploadPartResponse =
+                    serde_json::from_slice(&body).map_err(new_json_deserialize_error)?;
+
+                self.buffer.take(size);
+                self.parts.push(UploadedPart {
+                    part_number,
+                    etag: output.etag,
+                });
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Write for VercelBlobWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        if self.upload.is_none() && self.buffer.is_empty() {
+            if let Some(size) = self.op.content_length() {
+                if size == bs.len() as u64 {
+                    return self.write_oneshot(bs).await;
+                }
+            }
+        }
+
+        // Ignore empty bytes
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.buffer.push(bs);
+        // Return directly if the buffer is not full
+        if self.buffer.len() < VERCEL_BLOB_PART_SIZE {
+            return Ok(());
+        }
+
+        if self.upload.is_none() {
+            match self.initiate_upload().await {
+                Ok(upload) => self.upload = Some(upload),
+                Err(e) => {
+                    self.buffer.pop();
+                    return Err(e);
+                }
+            }
+        }
+
+        match self.write_part(VERCEL_BLOB_PART_SIZE).await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // If the upload fails, we should pop the given bs to make sure
+                // write is re-enter safe.
+                self.buffer.pop();
+                Err(e)
+            }
+        }
+    }
+
+    async fn sink(&mut self, _size: u64, _s: oio::Streamer) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "Write::sink is not supported",
+        ))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        // Vercel Blob doesn't provide API to abort multipart upload, the
+        // uploaded parts will be cleaned up automatically.
+        self.buffer.clear();
+        self.upload = None;
+        self.parts.clear();
+
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if self.upload.is_none() {
+            // Nothing has been written.
+            if self.buffer.is_empty() && self.op.content_length().is_some() {
+                return Ok(());
+            }
+
+            let bs = if self.buffer.is_empty() {
+                Bytes::new()
+            } else {
+                self.buffer.peak_exact(self.buffer.len())
+            };
+            self.write_oneshot(bs).await?;
+            self.buffer.clear();
+            return Ok(());
+        }
+
+        while !self.buffer.is_empty() {
+            let size = self.buffer.len().min(VERCEL_BLOB_PART_SIZE);
+            self.write_part(size).await?;
+        }
+
+        let upload = match &self.upload {
+            Some(upload) => upload,
+            None => unreachable!("upload must be initiated"),
+        };
+
+        let resp = self
+            .core
+            .vercel_blob_complete_multipart_upload(&self.path, upload, &self.parts)
+            .await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                self.upload = None;
+                self.parts.clear();
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
@@ -32,10 +32,11 @@ use md5::Md5;
 use once_cell::sync::Lazy;
 use reqsign::AwsConfig;
 use reqsign::AwsCredentialLoad;
-use reqsign::AwsLoader;
+use reqsign::AwsDefaultLoader;
 use reqsign::AwsV4Signer;
 
 use super::core::*;
+use super::credential::CredentialChain;
 use super::error::parse_error;
 use super::error::parse_wasabi_error_code;
 use super::pager::WasabiPager;
@@ -623,6 +624,10 @@ impl WasabiBuilder {
     }
 
     /// Adding a customed credential load for service.
+    ///
+    /// The customed credential load will be tried before the default
+    /// credential chain, which is still used as the fallback. Loaded
+    /// credential will be cached until it expires.
     pub fn customed_credential_load(&mut self, cred: Box<dyn AwsCredentialLoad>) -> &mut Self {
         self.customed_credential_load = Some(cred);
         self
@@ -854,13 +859,18 @@ impl Builder for WasabiBuilder {
         let endpoint = self.build_endpoint(&region);
         debug!("backend use endpoint: {endpoint}");
 
-        let mut loader = AwsLoader::new(client.client(), cfg);
-        if self.disable_ec2_metadata {
-            loader = loader.with_disable_ec2_metadata();
-        }
+        // Customed credential load will be tried first, and the default
+        // credential chain is always used as the fallback.
+        let mut loaders: Vec<Box<dyn AwsCredentialLoad>> = Vec::new();
         if let Some(v) = self.customed_credential_load.take() {
-            loader = loader.with_customed_credential_loader(v);
+            loaders.push(v);
+        }
+        let mut default_loader = AwsDefaultLoader::new(client.client(), cfg);
+        if self.disable_ec2_metadata {
+            default_loader = default_loader.with_disable_ec2_metadata();
         }
+        loaders.push(Box::new(default_loader));
+        let loader: Box<dyn AwsCredentialLoad> = Box::new(CredentialChain::new(loaders));
 
         let signer = AwsV4Signer::new("s3", &region);
 
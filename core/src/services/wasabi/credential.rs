@@ -0,0 +1,138 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use async_trait::async_trait;
+use log::debug;
+use parking_lot::Mutex;
+use reqsign::AwsCredential;
+use reqsign::AwsCredentialLoad;
+
+/// CredentialChain tries loaders in order and uses the first credential
+/// loaded.
+///
+/// Errors of all loaders except the last one are logged and skipped, so the
+/// result of the last loader (usually the default credential chain) will be
+/// returned if no other loader could load a credential.
+///
+/// Loaded credential will be cached until it expires.
+pub struct CredentialChain {
+    loaders: Vec<Box<dyn AwsCredentialLoad>>,
+
+    credential: Mutex<Option<AwsCredential>>,
+}
+
+impl Debug for CredentialChain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialChain")
+            .field("loaders", &self.loaders.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CredentialChain {
+    /// Create a new chain with given loaders.
+    pub fn new(loaders: Vec<Box<dyn AwsCredentialLoad>>) -> Self {
+        Self {
+            loaders,
+            credential: Mutex::default(),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl AwsCredentialLoad for CredentialChain {
+    async fn load_credential(
+        &self,
+        client: reqwest::Client,
+    ) -> anyhow::Result<Option<AwsCredential>> {
+        if let Some(cred) = self.credential.lock().clone() {
+            if cred.is_valid() {
+                return Ok(Some(cred));
+            }
+        }
+
+        let (last, loaders) = match self.loaders.split_last() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let mut cred = None;
+        for loader in loaders {
+            match loader.load_credential(client.clone()).await {
+                Ok(Some(v)) => {
+                    cred = Some(v);
+                    break;
+                }
+                Ok(None) => {}
+                Err(err) => debug!("load credential failed, try next loader: {err:?}"),
+            }
+        }
+        let cred = match cred {
+            Some(v) => Some(v),
+            None => last.load_credential(client).await?,
+        };
+
+        *self.credential.lock() = cred.clone();
+        Ok(cred)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use chrono::Utc;
+
+    use super::*;
+
+    struct StaticCredentialLoad(Option<&'static str>);
+
+    #[async_trait]
+    impl AwsCredentialLoad for StaticCredentialLoad {
+        async fn load_credential(
+            &self,
+            _: reqwest::Client,
+        ) -> anyhow::Result<Option<AwsCredential>> {
+            match self.0 {
+                Some(ak) => Ok(Some(AwsCredential {
+                    access_key_id: ak.to_string(),
+                    secret_access_key: "SECRET_ACCESS_KEY".to_string(),
+                    session_token: None,
+                    expires_in: Some(Utc::now() + chrono::Duration::hours(1)),
+                })),
+                None => Err(anyhow!("failed")),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_credential_chain() {
+        let chain = CredentialChain::new(vec![
+            Box::new(StaticCredentialLoad(None)),
+            Box::new(StaticCredentialLoad(Some("DEFAULT"))),
+        ]);
+        let cred = chain
+            .load_credential(reqwest::Client::new())
+            .await
+            .expect("must succeed")
+            .expect("must be some");
+        assert_eq!(cred.access_key_id, "DEFAULT");
+    }
+}
@@ -19,6 +19,7 @@ mod backend;
 pub use backend::WasabiBuilder as Wasabi;
 
 mod core;
+mod credential;
 mod error;
 mod pager;
 mod writer;
@@ -18,6 +18,7 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Buf;
@@ -27,7 +28,10 @@ use http::Request;
 use http::Response;
 use http::StatusCode;
 use log::debug;
+use parking_lot::Mutex;
 
+use super::digest::format_authorization_by_digest;
+use super::digest::DigestChallenge;
 use super::error::parse_error;
 use super::list_response::Multistatus;
 use super::pager::WebdavPager;
@@ -62,6 +66,17 @@ use crate::*;
 ///
 /// - `endpoint`: set the endpoint for webdav
 /// - `root`: Set the work directory for backend
+/// - `username`: set the username for basic or digest auth
+/// - `password`: set the password for basic or digest auth
+/// - `token`: set the bearer token for webdav
+///
+/// # Authentication
+///
+/// - If `token` is set, opendal will send requests with bearer token.
+/// - If `username` is set, opendal will send requests with basic auth first.
+///   If server asks for [digest auth](https://datatracker.ietf.org/doc/html/rfc2617)
+///   instead, opendal will retry the request with the given challenge and
+///   use digest auth for all following requests.
 ///
 /// You can refer to [`WebdavBuilder`]'s docs for more information
 ///
@@ -122,9 +137,9 @@ impl WebdavBuilder {
         self
     }
 
-    /// set the password for Webdav
+    /// set the username for Webdav
     ///
-    /// default: no password
+    /// default: no username
     pub fn username(&mut self, username: &str) -> &mut Self {
         if !username.is_empty() {
             self.username = Some(username.to_owned());
@@ -229,6 +244,9 @@ impl Builder for WebdavBuilder {
         Ok(WebdavBackend {
             endpoint: endpoint.to_string(),
             authorization: auth,
+            username: self.username.take(),
+            password: self.password.take(),
+            digest: Arc::new(Mutex::new(None)),
             root,
             client,
         })
@@ -243,6 +261,12 @@ pub struct WebdavBackend {
     client: HttpClient,
 
     authorization: Option<String>,
+
+    username: Option<String>,
+    password: Option<String>,
+    /// The latest digest challenge returned by server and the count of
+    /// requests that have been sent with it.
+    digest: Arc<Mutex<Option<(DigestChallenge, u32)>>>,
 }
 
 impl Debug for WebdavBackend {
@@ -443,6 +467,96 @@ impl Accessor for WebdavBackend {
 }
 
 impl WebdavBackend {
+    /// Send request with digest auth support.
+    ///
+    /// If server returns `401 Unauthorized` with a digest challenge, we will
+    /// retry this request with digest authorization once.
+    async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        if self.username.is_none() {
+            return self.client.send(req).await;
+        }
+
+        let (mut parts, body) = req.into_parts();
+
+        if let Some(auth) = self.format_digest_authorization(parts.method.as_str(), &parts.uri)? {
+            parts.headers.insert(header::AUTHORIZATION, auth);
+        }
+
+        // Streaming body can't be replayed, we have to send it directly.
+        let replay_body = match &body {
+            AsyncBody::Empty => Some(AsyncBody::Empty),
+            AsyncBody::Bytes(bs) => Some(AsyncBody::Bytes(bs.clone())),
+            AsyncBody::Stream(_) => None,
+        };
+        let method = parts.method.clone();
+        let uri = parts.uri.clone();
+        let version = parts.version;
+        let mut headers = parts.headers.clone();
+
+        let resp = self.client.send(Request::from_parts(parts, body)).await?;
+
+        if resp.status() != StatusCode::UNAUTHORIZED {
+            return Ok(resp);
+        }
+
+        let challenge = resp
+            .headers()
+            .get_all(header::WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .find_map(DigestChallenge::parse);
+        let (challenge, replay_body) = match (challenge, replay_body) {
+            (Some(challenge), Some(body)) => (challenge, body),
+            _ => return Ok(resp),
+        };
+        debug!("webdav server {} asks for digest auth", self.endpoint);
+        resp.into_body().consume().await?;
+
+        *self.digest.lock() = Some((challenge, 0));
+
+        if let Some(auth) = self.format_digest_authorization(method.as_str(), &uri)? {
+            headers.insert(header::AUTHORIZATION, auth);
+        }
+
+        let mut req = Request::builder().method(method).uri(uri).version(version);
+        if let Some(hs) = req.headers_mut() {
+            *hs = headers;
+        }
+        let req = req.body(replay_body).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    /// Format digest authorization if server has sent a digest challenge
+    /// before.
+    fn format_digest_authorization(
+        &self,
+        method: &str,
+        uri: &http::Uri,
+    ) -> Result<Option<http::HeaderValue>> {
+        let mut digest = self.digest.lock();
+        let (challenge, nc) = match digest.as_mut() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        *nc += 1;
+
+        let uri = uri.path_and_query().map(|v| v.as_str()).unwrap_or("/");
+        let cnonce = uuid::Uuid::new_v4().simple().to_string();
+
+        let auth = format_authorization_by_digest(
+            self.username.as_deref().unwrap_or_default(),
+            self.password.as_deref().unwrap_or_default(),
+            challenge,
+            method,
+            uri,
+            *nc,
+            &cnonce,
+        );
+
+        build_header_value(&auth).map(Some)
+    }
+
     async fn webdav_get(
         &self,
         path: &str,
@@ -465,7 +579,7 @@ impl WebdavBackend {
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     pub async fn webdav_put(
@@ -499,7 +613,7 @@ impl WebdavBackend {
         // Set body
         let req = req.body(body).map_err(new_request_build_error)?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     async fn webdav_mkcol(
@@ -526,7 +640,7 @@ impl WebdavBackend {
 
         let req = req.body(body).map_err(new_request_build_error)?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     async fn webdav_propfind(
@@ -565,7 +679,7 @@ impl WebdavBackend {
 
         let req = req.body(body).map_err(new_request_build_error)?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     async fn webdav_delete(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
@@ -583,7 +697,7 @@ impl WebdavBackend {
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     async fn webdav_copy(&self, from: &str, to: &str) -> Result<Response<IncomingAsyncBody>> {
@@ -608,7 +722,7 @@ impl WebdavBackend {
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     async fn webdav_move(&self, from: &str, to: &str) -> Result<Response<IncomingAsyncBody>> {
@@ -633,7 +747,7 @@ impl WebdavBackend {
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     async fn create_internal(&self, abs_path: &str) -> Result<RpCreateDir> {
@@ -0,0 +1,227 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use md5::Digest;
+use md5::Md5;
+
+/// DigestChallenge is the challenge carried by `WWW-Authenticate` header
+/// returned by servers which require [HTTP Digest Access Authentication](https://datatracker.ietf.org/doc/html/rfc2617).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DigestChallenge {
+    pub realm: String,
+    pub nonce: String,
+    pub opaque: Option<String>,
+    /// Only `MD5` and `MD5-sess` are supported.
+    pub algorithm: Option<String>,
+    pub qop: Option<String>,
+}
+
+impl DigestChallenge {
+    /// Parse challenge from the value of `WWW-Authenticate` header.
+    ///
+    /// Returns `None` if it's not a digest challenge or the challenge is
+    /// not supported.
+    pub fn parse(v: &str) -> Option<Self> {
+        let v = v.trim();
+        if v.len() < 6 || !v[..6].eq_ignore_ascii_case("digest") {
+            return None;
+        }
+
+        let mut challenge = DigestChallenge::default();
+        let mut has_nonce = false;
+
+        for (key, value) in split_params(&v[6..]) {
+            match key.to_ascii_lowercase().as_str() {
+                "realm" => challenge.realm = value,
+                "nonce" => {
+                    challenge.nonce = value;
+                    has_nonce = true;
+                }
+                "opaque" => challenge.opaque = Some(value),
+                "algorithm" => challenge.algorithm = Some(value),
+                "qop" => challenge.qop = Some(value),
+                _ => {}
+            }
+        }
+
+        if !has_nonce {
+            return None;
+        }
+
+        match challenge.algorithm.as_deref() {
+            None => Some(challenge),
+            Some(v) if v.eq_ignore_ascii_case("md5") || v.eq_ignore_ascii_case("md5-sess") => {
+                Some(challenge)
+            }
+            _ => None,
+        }
+    }
+
+    /// Check if server supports `qop=auth`.
+    fn support_qop_auth(&self) -> bool {
+        match &self.qop {
+            None => false,
+            Some(v) => v.split(',').any(|v| v.trim() == "auth"),
+        }
+    }
+
+    fn is_sess(&self) -> bool {
+        matches!(&self.algorithm, Some(v) if v.eq_ignore_ascii_case("md5-sess"))
+    }
+}
+
+/// Format authorization header by digest challenge.
+///
+/// - `nc` is the count of requests that sent with the same nonce.
+/// - `cnonce` is the nonce generated by client.
+pub fn format_authorization_by_digest(
+    username: &str,
+    password: &str,
+    challenge: &DigestChallenge,
+    method: &str,
+    uri: &str,
+    nc: u32,
+    cnonce: &str,
+) -> String {
+    let mut ha1 = md5_hex(&format!("{username}:{}:{password}", challenge.realm));
+    if challenge.is_sess() {
+        ha1 = md5_hex(&format!("{ha1}:{}:{cnonce}", challenge.nonce));
+    }
+    let ha2 = md5_hex(&format!("{method}:{uri}"));
+
+    let mut auth = format!(
+        r#"Digest username="{username}", realm="{}", nonce="{}", uri="{uri}""#,
+        challenge.realm, challenge.nonce
+    );
+
+    if let Some(v) = &challenge.algorithm {
+        auth.push_str(&format!(", algorithm={v}"));
+    }
+
+    if challenge.support_qop_auth() {
+        let nc = format!("{nc:08x}");
+        let response = md5_hex(&format!(
+            "{ha1}:{}:{nc}:{cnonce}:auth:{ha2}",
+            challenge.nonce
+        ));
+        auth.push_str(&format!(
+            r#", response="{response}", qop=auth, nc={nc}, cnonce="{cnonce}""#
+        ));
+    } else {
+        let response = md5_hex(&format!("{ha1}:{}:{ha2}", challenge.nonce));
+        auth.push_str(&format!(r#", response="{response}""#));
+    }
+
+    if let Some(v) = &challenge.opaque {
+        auth.push_str(&format!(r#", opaque="{v}""#));
+    }
+
+    auth
+}
+
+fn md5_hex(s: &str) -> String {
+    format!("{:x}", Md5::digest(s.as_bytes()))
+}
+
+/// Split `k1="v1", k2=v2` into key-value pairs, commas inside quoted
+/// values are kept as is.
+fn split_params(s: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+
+    let mut key = String::new();
+    let mut value = String::new();
+    let mut in_value = false;
+    let mut in_quote = false;
+
+    for c in s.chars() {
+        match c {
+            '"' if in_value => in_quote = !in_quote,
+            ',' if !in_quote => {
+                if !key.trim().is_empty() {
+                    params.push((key.trim().to_string(), value.trim().to_string()));
+                }
+                key.clear();
+                value.clear();
+                in_value = false;
+            }
+            '=' if !in_value => in_value = true,
+            _ if in_value => value.push(c),
+            _ => key.push(c),
+        }
+    }
+    if !key.trim().is_empty() {
+        params.push((key.trim().to_string(), value.trim().to_string()));
+    }
+
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_challenge() {
+        let challenge = DigestChallenge::parse(
+            r#"Digest realm="testrealm@host.com", qop="auth,auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", opaque="5ccc069c403ebaf9f0171e9517f40e41""#,
+        )
+        .expect("must be valid challenge");
+
+        assert_eq!(
+            challenge,
+            DigestChallenge {
+                realm: "testrealm@host.com".to_string(),
+                nonce: "dcd98b7102dd2f0e8b11d0f600bfb0c093".to_string(),
+                opaque: Some("5ccc069c403ebaf9f0171e9517f40e41".to_string()),
+                algorithm: None,
+                qop: Some("auth,auth-int".to_string()),
+            }
+        );
+
+        assert!(DigestChallenge::parse(r#"Basic realm="test""#).is_none());
+        assert!(
+            DigestChallenge::parse(r#"Digest realm="test", nonce="abc", algorithm=SHA-256"#)
+                .is_none()
+        );
+    }
+
+    /// Example is from <https://datatracker.ietf.org/doc/html/rfc2617#section-3.5>
+    #[test]
+    fn test_format_authorization_by_digest() {
+        let challenge = DigestChallenge {
+            realm: "testrealm@host.com".to_string(),
+            nonce: "dcd98b7102dd2f0e8b11d0f600bfb0c093".to_string(),
+            opaque: Some("5ccc069c403ebaf9f0171e9517f40e41".to_string()),
+            algorithm: None,
+            qop: Some("auth,auth-int".to_string()),
+        };
+
+        let auth = format_authorization_by_digest(
+            "Mufasa",
+            "Circle Of Life",
+            &challenge,
+            "GET",
+            "/dir/index.html",
+            1,
+            "0a4f113b",
+        );
+
+        assert_eq!(
+            auth,
+            r#"Digest username="Mufasa", realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", uri="/dir/index.html", response="6629fae49393a05397450978507c4ef1", qop=auth, nc=00000001, cnonce="0a4f113b", opaque="5ccc069c403ebaf9f0171e9517f40e41""#
+        );
+    }
+}
@@ -18,6 +18,7 @@
 mod backend;
 pub use backend::WebdavBuilder as Webdav;
 
+mod digest;
 mod error;
 mod list_response;
 mod pager;
@@ -0,0 +1,322 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+
+use super::core::Resource;
+use super::core::YandexDiskCore;
+use super::error::parse_error;
+use super::pager::YandexDiskPager;
+use super::writer::YandexDiskWriter;
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_ENDPOINT: &str = "https://cloud-api.yandex.net";
+
+/// [Yandex Disk](https://360.yandex.com/disk/) services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct YandexDiskBuilder {
+    root: Option<String>,
+    endpoint: Option<String>,
+    access_token: Option<String>,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for YandexDiskBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+
+        ds.field("root", &self.root);
+        ds.field("endpoint", &self.endpoint);
+        if self.access_token.is_some() {
+            ds.field("access_token", &"<redacted>");
+        }
+        ds.finish()
+    }
+}
+
+impl YandexDiskBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+        self
+    }
+
+    /// Set the endpoint of Yandex Disk REST API.
+    ///
+    /// default: "https://cloud-api.yandex.net"
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+        self
+    }
+
+    /// Set the OAuth token of Yandex ID.
+    ///
+    /// Ref: <https://yandex.com/dev/disk/api/concepts/quickstart.html#oauth>
+    pub fn access_token(&mut self, access_token: &str) -> &mut Self {
+        if !access_token.is_empty() {
+            self.access_token = Some(access_token.to_string())
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for YandexDiskBuilder {
+    const SCHEME: Scheme = Scheme::YandexDisk;
+    type Accessor = YandexDiskBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = YandexDiskBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("access_token").map(|v| builder.access_token(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        debug!("backend build started: {:?}", self);
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let endpoint = self
+            .endpoint
+            .take()
+            .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
+        debug!("backend use endpoint {}", endpoint);
+
+        let access_token = self.access_token.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "access_token is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::YandexDisk)
+        })?;
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::YandexDisk)
+            })?
+        };
+
+        debug!("backend build finished");
+        Ok(YandexDiskBackend {
+            core: Arc::new(YandexDiskCore {
+                root,
+                endpoint,
+                access_token,
+                client,
+            }),
+        })
+    }
+}
+
+/// Backend for Yandex Disk services.
+#[derive(Debug, Clone)]
+pub struct YandexDiskBackend {
+    core: Arc<YandexDiskCore>,
+}
+
+#[async_trait]
+impl Accessor for YandexDiskBackend {
+    type Reader = IncomingAsyncBody;
+    type BlockingReader = ();
+    type Writer = YandexDiskWriter;
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = YandexDiskPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::YandexDisk)
+            .set_root(&self.core.root)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+
+                create_dir: true,
+                delete: true,
+                copy: true,
+                rename: true,
+
+                list: true,
+                list_with_limit: true,
+                list_with_delimiter_slash: true,
+
+                ..Default::default()
+            });
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.core.yandex_disk_ensure_dirs(path).await?;
+
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let resp = self.core.yandex_disk_download(path, args.range()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if args.content_length().is_none() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write without content length is not supported",
+            ));
+        }
+
+        Ok((
+            RpWrite::default(),
+            YandexDiskWriter::new(self.core.clone(), path.to_string()),
+        ))
+    }
+
+    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
+        self.core.yandex_disk_ensure_dirs(to).await?;
+
+        let resp = self.core.yandex_disk_copy(from, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            // ACCEPTED means the operation will be completed asynchronously.
+            StatusCode::CREATED | StatusCode::ACCEPTED => {
+                resp.into_body().consume().await?;
+                Ok(RpCopy::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
+        self.core.yandex_disk_ensure_dirs(to).await?;
+
+        let resp = self.core.yandex_disk_move(from, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            // ACCEPTED means the operation will be completed asynchronously.
+            StatusCode::CREATED | StatusCode::ACCEPTED => {
+                resp.into_body().consume().await?;
+                Ok(RpRename::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let resp = self.core.yandex_disk_stat(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let resource: Resource =
+                    serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+                // Yandex Disk ignores the trailing `/` in path, so we need to
+                // check the type of resource by ourselves.
+                if path.ends_with('/') && !resource.is_dir() {
+                    return Err(Error::new(ErrorKind::NotFound, "path is not a dir"));
+                }
+
+                Ok(RpStat::new(resource.to_metadata()?))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.yandex_disk_delete(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            // ACCEPTED means the operation will be completed asynchronously.
+            StatusCode::NO_CONTENT | StatusCode::ACCEPTED => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        Ok((
+            RpList::default(),
+            YandexDiskPager::new(self.core.clone(), path, args.limit()),
+        ))
+    }
+}
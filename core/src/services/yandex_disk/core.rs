@@ -0,0 +1,409 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use bytes::Bytes;
+use http::header;
+use http::HeaderValue;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use serde::Deserialize;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The fields we need in the returning resource.
+const RESOURCE_FIELDS: &str = "type,name,size,modified,md5,mime_type";
+
+pub struct YandexDiskCore {
+    pub root: String,
+    pub endpoint: String,
+    /// The OAuth token of Yandex ID.
+    pub access_token: String,
+    pub client: HttpClient,
+}
+
+impl Debug for YandexDiskCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("YandexDiskCore")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl YandexDiskCore {
+    #[inline]
+    pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        self.client.send(req).await
+    }
+
+    fn sign(&self, req: &mut Request<AsyncBody>) -> Result<()> {
+        req.headers_mut().insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("OAuth {}", self.access_token)).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "access token is invalid").set_source(err)
+            })?,
+        );
+
+        Ok(())
+    }
+
+    /// Build the percent encoded path of resource on disk.
+    fn disk_path(&self, path: &str) -> String {
+        encode_disk_path(&build_abs_path(&self.root, path))
+    }
+
+    async fn yandex_disk_request(
+        &self,
+        method: http::Method,
+        url: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = Request::builder()
+            .method(method)
+            .uri(url)
+            .header(header::CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req)?;
+
+        self.send(req).await
+    }
+
+    /// Get the link for downloading or uploading.
+    async fn yandex_disk_get_link(&self, url: &str) -> Result<Link> {
+        let resp = self.yandex_disk_request(http::Method::GET, url).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        serde_json::from_slice(&bs).map_err(new_json_deserialize_error)
+    }
+
+    /// Get the meta information about a file or folder.
+    ///
+    /// Ref: <https://yandex.com/dev/disk/api/reference/meta.html>
+    pub async fn yandex_disk_stat(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}/v1/disk/resources?path={}&fields={RESOURCE_FIELDS}",
+            self.endpoint,
+            self.disk_path(path)
+        );
+
+        self.yandex_disk_request(http::Method::GET, &url).await
+    }
+
+    /// List the contents of folder, which will be returned in `_embedded`.
+    ///
+    /// Ref: <https://yandex.com/dev/disk/api/reference/meta.html>
+    pub async fn yandex_disk_list(
+        &self,
+        path: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}/v1/disk/resources?path={}&limit={limit}&offset={offset}",
+            self.endpoint,
+            self.disk_path(path)
+        );
+
+        self.yandex_disk_request(http::Method::GET, &url).await
+    }
+
+    /// Ref: <https://yandex.com/dev/disk/api/reference/content.html>
+    pub async fn yandex_disk_download(
+        &self,
+        path: &str,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}/v1/disk/resources/download?path={}",
+            self.endpoint,
+            self.disk_path(path)
+        );
+        let link = self.yandex_disk_get_link(&url).await?;
+
+        // The download link has been signed, we should not sign it again.
+        let mut req = Request::get(&link.href);
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    /// Ref: <https://yandex.com/dev/disk/api/reference/upload.html>
+    pub async fn yandex_disk_upload(
+        &self,
+        path: &str,
+        bs: Bytes,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}/v1/disk/resources/upload?path={}&overwrite=true",
+            self.endpoint,
+            self.disk_path(path)
+        );
+        let link = self.yandex_disk_get_link(&url).await?;
+
+        let req = Request::put(&link.href)
+            .header(header::CONTENT_LENGTH, bs.len())
+            .body(AsyncBody::Bytes(bs))
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    /// Ref: <https://yandex.com/dev/disk/api/reference/delete.html>
+    pub async fn yandex_disk_delete(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}/v1/disk/resources?path={}&permanently=true",
+            self.endpoint,
+            self.disk_path(path)
+        );
+
+        self.yandex_disk_request(http::Method::DELETE, &url).await
+    }
+
+    /// Ref: <https://yandex.com/dev/disk/api/reference/copy.html>
+    pub async fn yandex_disk_copy(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}/v1/disk/resources/copy?from={}&path={}&overwrite=true",
+            self.endpoint,
+            self.disk_path(from),
+            self.disk_path(to)
+        );
+
+        self.yandex_disk_request(http::Method::POST, &url).await
+    }
+
+    /// Ref: <https://yandex.com/dev/disk/api/reference/move.html>
+    pub async fn yandex_disk_move(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}/v1/disk/resources/move?from={}&path={}&overwrite=true",
+            self.endpoint,
+            self.disk_path(from),
+            self.disk_path(to)
+        );
+
+        self.yandex_disk_request(http::Method::POST, &url).await
+    }
+
+    /// Ref: <https://yandex.com/dev/disk/api/reference/create-folder.html>
+    async fn yandex_disk_create_folder(
+        &self,
+        abs_path: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}/v1/disk/resources?path={}",
+            self.endpoint,
+            encode_disk_path(abs_path)
+        );
+
+        self.yandex_disk_request(http::Method::PUT, &url).await
+    }
+
+    /// Yandex Disk doesn't create parent folders automatically, so we
+    /// need to create all of them one by one from top to bottom.
+    ///
+    /// The input path will be treated as a folder if it ends with `/`,
+    /// otherwise only its parents will be created.
+    pub async fn yandex_disk_ensure_dirs(&self, path: &str) -> Result<()> {
+        let p = build_abs_path(&self.root, path);
+        let p = if p.ends_with('/') {
+            p.as_str()
+        } else {
+            p.rsplit_once('/').map(|(parent, _)| parent).unwrap_or("")
+        };
+
+        let mut dir = String::with_capacity(p.len());
+        for part in p.split('/').filter(|v| !v.is_empty()) {
+            dir.push_str(part);
+            dir.push('/');
+
+            let resp = self.yandex_disk_create_folder(&dir).await?;
+            match resp.status() {
+                // CONFLICT means the folder already exists.
+                StatusCode::CREATED | StatusCode::CONFLICT => {
+                    resp.into_body().consume().await?;
+                }
+                _ => {
+                    return Err(parse_error(resp)
+                        .await?
+                        .with_operation("Backend::yandex_disk_create_folder"))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Encode the absolute path into the form of `disk:/path/to/resource`.
+///
+/// Yandex Disk doesn't accept trailing `/` in path, so we trim it.
+fn encode_disk_path(abs_path: &str) -> String {
+    let p = format!("disk:/{}", abs_path.trim_end_matches('/'));
+
+    utf8_percent_encode(&p, NON_ALPHANUMERIC).to_string()
+}
+
+/// Link returned by download and upload API.
+///
+/// Ref: <https://yandex.com/dev/disk/api/reference/response-objects.html#link>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct Link {
+    href: String,
+}
+
+/// Ref: <https://yandex.com/dev/disk/api/reference/response-objects.html#resource>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct Resource {
+    /// Either `dir` or `file`.
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub name: String,
+    pub size: u64,
+    pub modified: String,
+    pub md5: Option<String>,
+    pub mime_type: Option<String>,
+    /// The contents of folder, only returned for folders.
+    #[serde(rename = "_embedded")]
+    pub embedded: Option<ResourceList>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct ResourceList {
+    pub items: Vec<Resource>,
+    pub total: usize,
+}
+
+impl Resource {
+    pub fn is_dir(&self) -> bool {
+        self.type_ == "dir"
+    }
+
+    pub fn to_metadata(&self) -> Result<Metadata> {
+        if self.is_dir() {
+            return Ok(Metadata::new(EntryMode::DIR));
+        }
+
+        let mut m = Metadata::new(EntryMode::FILE);
+        m.set_content_length(self.size);
+        if let Some(v) = &self.md5 {
+            m.set_content_md5(v);
+        }
+        if let Some(v) = &self.mime_type {
+            m.set_content_type(v);
+        }
+        if !self.modified.is_empty() {
+            m.set_last_modified(parse_datetime_from_rfc3339(&self.modified)?);
+        }
+
+        Ok(m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use chrono::Utc;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_disk_path() {
+        assert_eq!(encode_disk_path(""), "disk%3A%2F");
+        assert_eq!(encode_disk_path("foo/"), "disk%3A%2Ffoo");
+        assert_eq!(
+            encode_disk_path("foo/a b.txt"),
+            "disk%3A%2Ffoo%2Fa%20b%2Etxt"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_resource() {
+        let content = r#"{
+            "path": "disk:/foo",
+            "type": "dir",
+            "name": "foo",
+            "modified": "2023-07-25T09:13:18+00:00",
+            "_embedded": {
+                "sort": "",
+                "path": "disk:/foo",
+                "items": [
+                    {
+                        "path": "disk:/foo/bar",
+                        "type": "dir",
+                        "name": "bar",
+                        "modified": "2023-07-25T09:13:18+00:00"
+                    },
+                    {
+                        "path": "disk:/foo/a.txt",
+                        "type": "file",
+                        "name": "a.txt",
+                        "size": 1024,
+                        "md5": "4b4d8dbd4b8e2d4f7ff30c1fa1cd4aa5",
+                        "mime_type": "text/plain",
+                        "modified": "2023-07-25T09:13:18+00:00"
+                    }
+                ],
+                "limit": 20,
+                "offset": 0,
+                "total": 2
+            }
+        }"#;
+
+        let resource: Resource = serde_json::from_str(content).expect("must be valid");
+        assert!(resource.is_dir());
+
+        let list = resource.embedded.expect("must have embedded");
+        assert_eq!(list.total, 2);
+        assert!(list.items[0].is_dir());
+
+        let meta = list.items[1].to_metadata().expect("must be valid");
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), 1024);
+        assert_eq!(meta.content_md5(), Some("4b4d8dbd4b8e2d4f7ff30c1fa1cd4aa5"));
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(
+            meta.last_modified(),
+            Some(Utc.with_ymd_and_hms(2023, 7, 25, 9, 13, 18).unwrap())
+        );
+    }
+}
@@ -0,0 +1,130 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// YandexDiskError is the error returned by Yandex Disk REST API.
+///
+/// Ref: <https://yandex.com/dev/disk/api/reference/response-objects.html#error>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct YandexDiskError {
+    message: String,
+    description: String,
+    error: String,
+}
+
+/// Parse error response into Error.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    let yandex_error = serde_json::from_slice::<YandexDiskError>(&bs).unwrap_or_default();
+
+    let (kind, retryable) = match parts.status {
+        StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        // Conflict is also returned while the parent dir doesn't exist.
+        StatusCode::CONFLICT if yandex_error.error == "DiskResourceAlreadyExistsError" => {
+            (ErrorKind::AlreadyExists, false)
+        }
+        StatusCode::PRECONDITION_FAILED => (ErrorKind::ConditionNotMatch, false),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
+        // Locked is returned while the resource is under technical works.
+        StatusCode::LOCKED
+        | StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let message = if yandex_error.error.is_empty() {
+        String::from_utf8_lossy(&bs).into_owned()
+    } else {
+        format!("{yandex_error:?}")
+    };
+
+    let mut err = Error::new(kind, &message).with_context("response", format!("{parts:?}"));
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::raw::oio::into_stream;
+
+    #[tokio::test]
+    async fn test_parse_error() -> Result<()> {
+        let cases = vec![
+            (
+                StatusCode::CONFLICT,
+                r#"{"message": "Resource \"disk:/a\" already exists.", "description": "Resource \"disk:/a\" already exists.", "error": "DiskResourceAlreadyExistsError"}"#,
+                ErrorKind::AlreadyExists,
+                false,
+            ),
+            (
+                StatusCode::CONFLICT,
+                r#"{"message": "The specified path doesn't exist.", "description": "Specified path doesn't exists.", "error": "DiskPathDoesntExistsError"}"#,
+                ErrorKind::Unexpected,
+                false,
+            ),
+            (
+                StatusCode::NOT_FOUND,
+                r#"{"message": "Resource not found.", "description": "Resource not found.", "error": "DiskNotFoundError"}"#,
+                ErrorKind::NotFound,
+                false,
+            ),
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Service Unavailable",
+                ErrorKind::Unexpected,
+                true,
+            ),
+        ];
+
+        for (status, body, kind, temporary) in cases {
+            let body = IncomingAsyncBody::new(
+                Box::new(into_stream::from_futures_stream(stream::iter(vec![Ok(
+                    bytes::Bytes::from(body),
+                )]))),
+                None,
+            );
+            let resp = Response::builder().status(status).body(body).unwrap();
+
+            let err = parse_error(resp).await?;
+            assert_eq!(err.kind(), kind);
+            assert_eq!(err.is_temporary(), temporary);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::core::Resource;
+use super::core::YandexDiskCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The default number of items returned by one list request.
+const DEFAULT_LIST_LIMIT: usize = 1000;
+
+/// YandexDiskPager lists a folder via meta information API, the contents
+/// are paginated by `limit` and `offset`.
+pub struct YandexDiskPager {
+    core: Arc<YandexDiskCore>,
+    path: String,
+    limit: usize,
+    offset: usize,
+    done: bool,
+}
+
+impl YandexDiskPager {
+    pub fn new(core: Arc<YandexDiskCore>, path: &str, limit: Option<usize>) -> Self {
+        YandexDiskPager {
+            core,
+            path: path.to_string(),
+            limit: limit.unwrap_or(DEFAULT_LIST_LIMIT),
+            offset: 0,
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Page for YandexDiskPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let resp = self
+            .core
+            .yandex_disk_list(&self.path, self.limit, self.offset)
+            .await?;
+
+        let resource: Resource = match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?
+            }
+            // Listing a not exist dir should return empty.
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                self.done = true;
+                return Ok(None);
+            }
+            _ => return Err(parse_error(resp).await?),
+        };
+
+        // Listing a file should return empty.
+        let list = match resource.embedded {
+            Some(list) => list,
+            None => {
+                self.done = true;
+                return Ok(None);
+            }
+        };
+
+        self.offset += list.items.len();
+        if list.items.is_empty() || self.offset >= list.total {
+            self.done = true;
+        }
+
+        let mut entries = Vec::with_capacity(list.items.len());
+        for item in list.items {
+            let mut path = format!("{}{}", self.path.trim_start_matches('/'), item.name);
+            if item.is_dir() {
+                path.push('/');
+            }
+
+            entries.push(oio::Entry::new(&path, item.to_metadata()?));
+        }
+
+        Ok(Some(entries))
+    }
+}
@@ -0,0 +1,285 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::debug;
+use tokio::sync::OnceCell;
+
+use super::index::parse_local_header;
+use super::index::ZipIndex;
+use super::index::LFH_SIZE;
+use super::index::METHOD_DEFLATED;
+use super::index::METHOD_STORED;
+use super::pager::ZipPager;
+use super::reader::DeflateReader;
+use super::reader::ZipReader;
+use crate::raw::*;
+use crate::services::Fs;
+use crate::*;
+
+/// Read-only zip archive services support.
+#[doc = include_str!("docs.md")]
+#[derive(Default, Debug)]
+pub struct ZipBuilder {
+    root: Option<String>,
+    path: Option<String>,
+    operator: Option<Operator>,
+}
+
+impl ZipBuilder {
+    /// Set the working directory inside the archive, all operations will
+    /// be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string());
+        }
+        self
+    }
+
+    /// Set the path of the archive.
+    ///
+    /// It's a path on local fs by default, or a path inside the operator
+    /// if [`ZipBuilder::operator`] is set.
+    pub fn path(&mut self, path: &str) -> &mut Self {
+        if !path.is_empty() {
+            self.path = Some(path.to_string());
+        }
+        self
+    }
+
+    /// Read the archive from given operator instead of local fs.
+    pub fn operator(&mut self, op: Operator) -> &mut Self {
+        self.operator = Some(op);
+        self
+    }
+}
+
+impl Builder for ZipBuilder {
+    const SCHEME: Scheme = Scheme::Zip;
+    type Accessor = ZipBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = ZipBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("path").map(|v| builder.path(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let path = self.path.take().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "path is empty")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Zip)
+        })?;
+        debug!("backend use path {}", path);
+
+        let (op, path) = match self.operator.take() {
+            Some(op) => (op, path),
+            None => {
+                // Serve the local archive via fs service rooted at its parent.
+                let p = Path::new(&path);
+                let (parent, name) = match (p.parent(), p.file_name()) {
+                    (Some(parent), Some(name)) => (parent, name),
+                    _ => {
+                        return Err(Error::new(ErrorKind::ConfigInvalid, "path is invalid")
+                            .with_operation("Builder::build")
+                            .with_context("service", Scheme::Zip)
+                            .with_context("path", &path))
+                    }
+                };
+
+                let mut builder = Fs::default();
+                builder.root(&parent.to_string_lossy());
+                let op = Operator::new(builder)
+                    .map_err(|err| {
+                        err.with_operation("Builder::build")
+                            .with_context("service", Scheme::Zip)
+                    })?
+                    .finish();
+
+                (op, name.to_string_lossy().to_string())
+            }
+        };
+
+        Ok(ZipBackend {
+            root,
+            op,
+            path,
+            index: Arc::new(OnceCell::new()),
+        })
+    }
+}
+
+/// Backend for zip services.
+#[derive(Clone, Debug)]
+pub struct ZipBackend {
+    root: String,
+    op: Operator,
+    path: String,
+    index: Arc<OnceCell<ZipIndex>>,
+}
+
+impl ZipBackend {
+    /// Load the index on first use, it will be reused until the backend is
+    /// dropped.
+    async fn index(&self) -> Result<&ZipIndex> {
+        self.index
+            .get_or_try_init(|| ZipIndex::load(&self.op, &self.path))
+            .await
+    }
+}
+
+#[async_trait]
+impl Accessor for ZipBackend {
+    type Reader = ZipReader;
+    type BlockingReader = ();
+    type Writer = ();
+    type BlockingWriter = ();
+    type Appender = ();
+    type Pager = ZipPager;
+    type BlockingPager = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut ma = AccessorInfo::default();
+        ma.set_scheme(Scheme::Zip)
+            .set_root(&self.root)
+            .set_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_with_range: true,
+
+                list: true,
+                list_with_delimiter_slash: true,
+                list_without_delimiter: true,
+
+                ..Default::default()
+            });
+
+        ma
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let p = build_abs_path(&self.root, path);
+
+        let entry = self
+            .index()
+            .await?
+            .get(&p)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "member is not found in archive"))?;
+
+        if entry.encrypted {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "encrypted member of zip archive is not supported",
+            ));
+        }
+
+        let br = args.range();
+        let (start, end) = match (br.offset(), br.size()) {
+            // Read a specific range.
+            (Some(offset), Some(size)) => (offset, offset + size),
+            // Read from offset.
+            (Some(offset), None) => (offset, entry.size),
+            // Read the last size bytes.
+            (None, Some(size)) => (entry.size.saturating_sub(size), entry.size),
+            // Read the whole file.
+            (None, None) => (0, entry.size),
+        };
+        let (start, end) = (start.min(entry.size), end.min(entry.size));
+
+        // The length of local header is only known after reading it.
+        let header = self
+            .op
+            .range_read(
+                &self.path,
+                entry.header_offset..entry.header_offset + LFH_SIZE,
+            )
+            .await?;
+        let data_offset = entry.header_offset + parse_local_header(&header)?;
+
+        let r = match entry.method {
+            METHOD_STORED => ZipReader::Stored(
+                self.op
+                    .range_reader(&self.path, data_offset + start..data_offset + end)
+                    .await?,
+            ),
+            METHOD_DEFLATED => {
+                let r = self
+                    .op
+                    .range_reader(&self.path, data_offset..data_offset + entry.compressed_size)
+                    .await?;
+                ZipReader::Deflated(DeflateReader::new(r, start, end - start))
+            }
+            v => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "compression method of zip archive is not supported",
+                )
+                .with_context("method", v.to_string()))
+            }
+        };
+
+        Ok((RpRead::new(end - start), r))
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let p = build_abs_path(&self.root, path);
+
+        let entry = self
+            .index()
+            .await?
+            .get(&p)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "member is not found in archive"))?;
+
+        Ok(RpStat::new(entry.to_metadata()))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let p = build_abs_path(&self.root, path);
+
+        let entries = self
+            .index()
+            .await?
+            .list(&p, args.delimiter().is_empty())
+            .into_iter()
+            .map(|(k, v)| {
+                oio::Entry::new(
+                    &build_rel_path(&self.root, &format!("/{k}")),
+                    v.to_metadata(),
+                )
+            })
+            .collect();
+
+        Ok((RpList::default(), ZipPager::new(entries)))
+    }
+}
@@ -0,0 +1,506 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::BTreeMap;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::TimeZone;
+use chrono::Utc;
+
+use crate::*;
+
+/// Signature of the end of central directory record.
+const EOCD_SIGNATURE: u32 = 0x06054b50;
+const EOCD_SIZE: usize = 22;
+/// Signature of the zip64 end of central directory locator.
+const ZIP64_LOCATOR_SIGNATURE: u32 = 0x07064b50;
+const ZIP64_LOCATOR_SIZE: usize = 20;
+/// Signature of the zip64 end of central directory record.
+const ZIP64_EOCD_SIGNATURE: u32 = 0x06064b50;
+const ZIP64_EOCD_SIZE: u64 = 56;
+/// Signature of the central directory file header.
+const CDFH_SIGNATURE: u32 = 0x02014b50;
+const CDFH_SIZE: usize = 46;
+/// Signature of the local file header.
+const LFH_SIGNATURE: u32 = 0x04034b50;
+pub const LFH_SIZE: u64 = 30;
+
+/// Comment of the archive is at most 65535 bytes, so the end of central
+/// directory record must be in this range.
+const MAX_TAIL_SIZE: u64 = EOCD_SIZE as u64 + u16::MAX as u64;
+
+/// The member data is stored without compression.
+pub const METHOD_STORED: u16 = 0;
+/// The member data is compressed by deflate.
+pub const METHOD_DEFLATED: u16 = 8;
+
+/// ZipEntry is a member in the archive.
+#[derive(Clone, Debug)]
+pub struct ZipEntry {
+    /// The offset of member's local file header in the archive.
+    ///
+    /// The offset of data is unknown until we read the local header since
+    /// its extra field could differ from the one in central directory.
+    pub header_offset: u64,
+    pub compressed_size: u64,
+    pub size: u64,
+    pub method: u16,
+    pub encrypted: bool,
+    pub mode: EntryMode,
+    pub mtime: Option<DateTime<Utc>>,
+}
+
+impl ZipEntry {
+    fn dir() -> Self {
+        ZipEntry {
+            header_offset: 0,
+            compressed_size: 0,
+            size: 0,
+            method: METHOD_STORED,
+            encrypted: false,
+            mode: EntryMode::DIR,
+            mtime: None,
+        }
+    }
+
+    pub fn to_metadata(&self) -> Metadata {
+        let mut m = Metadata::new(self.mode);
+        m.set_content_length(self.size);
+        if let Some(v) = self.mtime {
+            m.set_last_modified(v);
+        }
+        m
+    }
+}
+
+/// ZipIndex holds all members of the archive.
+///
+/// Paths of dirs end with `/`, for example: `path/to/dir/`.
+#[derive(Debug, Default)]
+pub struct ZipIndex {
+    entries: BTreeMap<String, ZipEntry>,
+}
+
+impl ZipIndex {
+    /// Load index from the central directory of the archive.
+    pub async fn load(op: &Operator, path: &str) -> Result<Self> {
+        let size = op.stat(path).await?.content_length();
+
+        let tail_start = size.saturating_sub(MAX_TAIL_SIZE);
+        let tail = op.range_read(path, tail_start..size).await?;
+        let (cd_offset, cd_size) = match parse_eocd(&tail)? {
+            Eocd::Regular { offset, size } => (offset, size),
+            Eocd::Zip64 { locator_offset } => {
+                if locator_offset + ZIP64_EOCD_SIZE > size {
+                    return Err(malformed("zip64 end of central directory is out of range"));
+                }
+                let bs = op
+                    .range_read(path, locator_offset..locator_offset + ZIP64_EOCD_SIZE)
+                    .await?;
+                parse_zip64_eocd(&bs)?
+            }
+        };
+        if cd_offset + cd_size > size {
+            return Err(malformed("central directory is out of range"));
+        }
+
+        let cd = op.range_read(path, cd_offset..cd_offset + cd_size).await?;
+
+        let mut index = ZipIndex::default();
+        let mut bs = Bytes::from(cd);
+        while bs.len() >= 4 && read_u32(&bs, 0) == CDFH_SIGNATURE {
+            let (name, entry, len) = parse_cdfh(&bs)?;
+            if entry.mode.is_dir() {
+                index.insert_dir(&name);
+            } else {
+                index.insert(&name, entry);
+            }
+            bs = bs.slice(len..);
+        }
+
+        Ok(index)
+    }
+
+    /// Get the entry of given path.
+    pub fn get(&self, path: &str) -> Option<&ZipEntry> {
+        self.entries.get(path)
+    }
+
+    /// List entries under given dir, all descendants will be returned if
+    /// `recursive` is true.
+    pub fn list(&self, dir: &str, recursive: bool) -> Vec<(String, ZipEntry)> {
+        self.entries
+            .range(dir.to_string()..)
+            .take_while(|(k, _)| k.starts_with(dir))
+            .filter(|(k, _)| {
+                let rest = &k[dir.len()..];
+                !rest.is_empty() && (recursive || !rest.trim_end_matches('/').contains('/'))
+            })
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    fn insert(&mut self, name: &str, entry: ZipEntry) {
+        let path = normalize_name(name);
+        if path.is_empty() {
+            return;
+        }
+
+        self.insert_parents(path);
+        self.entries.insert(path.to_string(), entry);
+    }
+
+    fn insert_dir(&mut self, name: &str) {
+        let path = normalize_name(name).trim_end_matches('/');
+        if path.is_empty() {
+            return;
+        }
+
+        self.insert_parents(path);
+        self.entries
+            .entry(format!("{path}/"))
+            .or_insert_with(ZipEntry::dir);
+    }
+
+    /// Insert all parents of given path as dirs since archive could omit
+    /// them.
+    fn insert_parents(&mut self, path: &str) {
+        for (idx, _) in path.match_indices('/') {
+            self.entries
+                .entry(path[..=idx].to_string())
+                .or_insert_with(ZipEntry::dir);
+        }
+    }
+}
+
+/// Parse the local file header, returns the length of the header so that
+/// member data starts right after it.
+pub fn parse_local_header(bs: &[u8]) -> Result<u64> {
+    if bs.len() < LFH_SIZE as usize || read_u32(bs, 0) != LFH_SIGNATURE {
+        return Err(malformed("local file header is invalid"));
+    }
+
+    let name_len = read_u16(bs, 26) as u64;
+    let extra_len = read_u16(bs, 28) as u64;
+    Ok(LFH_SIZE + name_len + extra_len)
+}
+
+enum Eocd {
+    Regular {
+        offset: u64,
+        size: u64,
+    },
+    /// The archive is in zip64 format, the real values are stored in the
+    /// zip64 end of central directory record at `locator_offset`.
+    Zip64 {
+        locator_offset: u64,
+    },
+}
+
+/// Search the end of central directory record backward in the tail of
+/// archive.
+fn parse_eocd(tail: &[u8]) -> Result<Eocd> {
+    if tail.len() < EOCD_SIZE {
+        return Err(malformed("end of central directory is not found"));
+    }
+
+    let pos = (0..=tail.len() - EOCD_SIZE)
+        .rev()
+        .find(|idx| read_u32(tail, *idx) == EOCD_SIGNATURE)
+        .ok_or_else(|| malformed("end of central directory is not found"))?;
+
+    if pos >= ZIP64_LOCATOR_SIZE {
+        let locator = &tail[pos - ZIP64_LOCATOR_SIZE..pos];
+        if read_u32(locator, 0) == ZIP64_LOCATOR_SIGNATURE {
+            return Ok(Eocd::Zip64 {
+                locator_offset: read_u64(locator, 8),
+            });
+        }
+    }
+
+    let eocd = &tail[pos..];
+    Ok(Eocd::Regular {
+        size: read_u32(eocd, 12) as u64,
+        offset: read_u32(eocd, 16) as u64,
+    })
+}
+
+/// Parse the zip64 end of central directory record, returns the offset
+/// and size of central directory.
+fn parse_zip64_eocd(bs: &[u8]) -> Result<(u64, u64)> {
+    if bs.len() < ZIP64_EOCD_SIZE as usize || read_u32(bs, 0) != ZIP64_EOCD_SIGNATURE {
+        return Err(malformed("zip64 end of central directory is invalid"));
+    }
+
+    Ok((read_u64(bs, 48), read_u64(bs, 40)))
+}
+
+/// Parse a central directory file header, returns the name, entry and the
+/// length of the whole header.
+fn parse_cdfh(bs: &[u8]) -> Result<(String, ZipEntry, usize)> {
+    if bs.len() < CDFH_SIZE {
+        return Err(malformed("central directory is truncated"));
+    }
+
+    let flags = read_u16(bs, 8);
+    let method = read_u16(bs, 10);
+    let dos_time = read_u16(bs, 12);
+    let dos_date = read_u16(bs, 14);
+    let mut compressed_size = read_u32(bs, 20) as u64;
+    let mut size = read_u32(bs, 24) as u64;
+    let name_len = read_u16(bs, 28) as usize;
+    let extra_len = read_u16(bs, 30) as usize;
+    let comment_len = read_u16(bs, 32) as usize;
+    let mut header_offset = read_u32(bs, 42) as u64;
+
+    let len = CDFH_SIZE + name_len + extra_len + comment_len;
+    if bs.len() < len {
+        return Err(malformed("central directory is truncated"));
+    }
+    let name = String::from_utf8_lossy(&bs[CDFH_SIZE..CDFH_SIZE + name_len]).to_string();
+
+    let mut mtime = None;
+    let mut extra = &bs[CDFH_SIZE + name_len..CDFH_SIZE + name_len + extra_len];
+    while extra.len() >= 4 {
+        let id = read_u16(extra, 0);
+        let data_len = (read_u16(extra, 2) as usize).min(extra.len() - 4);
+        let data = &extra[4..4 + data_len];
+
+        match id {
+            // Zip64 extended information, fields only present if the
+            // values in header are saturated.
+            0x0001 => {
+                let mut data = data;
+                for v in [&mut size, &mut compressed_size, &mut header_offset] {
+                    if *v == u32::MAX as u64 && data.len() >= 8 {
+                        *v = read_u64(data, 0);
+                        data = &data[8..];
+                    }
+                }
+            }
+            // Extended timestamp, the mtime is present if the lowest bit
+            // of flags is set.
+            0x5455 if data.len() >= 5 && data[0] & 1 != 0 => {
+                let secs = read_u32(data, 1) as i32;
+                mtime = u64::try_from(secs)
+                    .ok()
+                    .and_then(|v| UNIX_EPOCH.checked_add(Duration::from_secs(v)))
+                    .map(DateTime::<Utc>::from);
+            }
+            _ => {}
+        }
+
+        extra = &extra[4 + data_len..];
+    }
+
+    let mode = if name.ends_with('/') {
+        EntryMode::DIR
+    } else {
+        EntryMode::FILE
+    };
+
+    Ok((
+        name,
+        ZipEntry {
+            header_offset,
+            compressed_size,
+            size,
+            method,
+            encrypted: flags & 1 != 0,
+            mode,
+            mtime: mtime.or_else(|| parse_dos_datetime(dos_date, dos_time)),
+        },
+        len,
+    ))
+}
+
+/// Parse datetime in MS-DOS format which has no timezone, we treat it as
+/// UTC.
+fn parse_dos_datetime(date: u16, time: u16) -> Option<DateTime<Utc>> {
+    let year = (date >> 9) as i32 + 1980;
+    let month = ((date >> 5) & 0x0f) as u32;
+    let day = (date & 0x1f) as u32;
+    let hour = (time >> 11) as u32;
+    let min = ((time >> 5) & 0x3f) as u32;
+    let sec = ((time & 0x1f) * 2) as u32;
+
+    NaiveDate::from_ymd_opt(year, month, day)
+        .and_then(|v| v.and_hms_opt(hour, min, sec))
+        .map(|v| Utc.from_utc_datetime(&v))
+}
+
+/// Strip the leading `./` and `/` of member names.
+fn normalize_name(name: &str) -> &str {
+    let mut name = name;
+    loop {
+        if let Some(v) = name.strip_prefix("./") {
+            name = v;
+        } else if let Some(v) = name.strip_prefix('/') {
+            name = v;
+        } else {
+            break;
+        }
+    }
+    name
+}
+
+fn malformed(msg: &str) -> Error {
+    Error::new(
+        ErrorKind::Unexpected,
+        &format!("{msg}, the archive must be malformed"),
+    )
+}
+
+fn read_u16(bs: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bs[offset], bs[offset + 1]])
+}
+
+fn read_u32(bs: &[u8], offset: usize) -> u32 {
+    let mut buf = [0; 4];
+    buf.copy_from_slice(&bs[offset..offset + 4]);
+    u32::from_le_bytes(buf)
+}
+
+fn read_u64(bs: &[u8], offset: usize) -> u64 {
+    let mut buf = [0; 8];
+    buf.copy_from_slice(&bs[offset..offset + 8]);
+    u64::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build an archive with all members stored without compression.
+    fn archive(members: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let mut cd = Vec::new();
+        for (name, content) in members {
+            let offset = buf.len() as u32;
+            let size = content.len() as u32;
+
+            buf.extend(LFH_SIGNATURE.to_le_bytes());
+            buf.extend([20, 0, 0, 0, 0, 0]);
+            // 2020-09-13 12:26:40
+            buf.extend([0x54, 0x63, 0x2d, 0x51]);
+            buf.extend([0; 4]);
+            buf.extend(size.to_le_bytes());
+            buf.extend(size.to_le_bytes());
+            buf.extend((name.len() as u16).to_le_bytes());
+            buf.extend([0, 0]);
+            buf.extend(name.as_bytes());
+            buf.extend_from_slice(content);
+
+            cd.extend(CDFH_SIGNATURE.to_le_bytes());
+            cd.extend([20, 3, 20, 0, 0, 0, 0, 0]);
+            cd.extend([0x54, 0x63, 0x2d, 0x51]);
+            cd.extend([0; 4]);
+            cd.extend(size.to_le_bytes());
+            cd.extend(size.to_le_bytes());
+            cd.extend((name.len() as u16).to_le_bytes());
+            cd.extend([0; 12]);
+            cd.extend(offset.to_le_bytes());
+            cd.extend(name.as_bytes());
+        }
+
+        let cd_offset = buf.len() as u32;
+        let cd_size = cd.len() as u32;
+        buf.extend(cd);
+        buf.extend(EOCD_SIGNATURE.to_le_bytes());
+        buf.extend([0; 4]);
+        buf.extend((members.len() as u16).to_le_bytes());
+        buf.extend((members.len() as u16).to_le_bytes());
+        buf.extend(cd_size.to_le_bytes());
+        buf.extend(cd_offset.to_le_bytes());
+        buf.extend([0, 0]);
+        buf
+    }
+
+    #[test]
+    fn test_parse_dos_datetime() {
+        assert_eq!(
+            parse_dos_datetime(0x512d, 0x6354).unwrap().to_rfc3339(),
+            "2020-09-13T12:26:40+00:00"
+        );
+        assert!(parse_dos_datetime(0, 0).is_none());
+    }
+
+    #[test]
+    fn test_parse_eocd() {
+        let mut tail = vec![b'x'; 8];
+        tail.extend(archive(&[]));
+        match parse_eocd(&tail).unwrap() {
+            Eocd::Regular { offset, size } => assert_eq!((offset, size), (0, 0)),
+            Eocd::Zip64 { .. } => panic!("archive is not in zip64 format"),
+        }
+
+        assert!(parse_eocd(b"not a zip archive at all").is_err());
+    }
+
+    #[test]
+    fn test_parse_local_header() {
+        let buf = archive(&[("dir/file", b"hello")]);
+        assert_eq!(parse_local_header(&buf).unwrap(), LFH_SIZE + 8);
+        assert!(parse_local_header(&buf[1..]).is_err());
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("./a/b"), "a/b");
+        assert_eq!(normalize_name("/a/"), "a/");
+        assert_eq!(normalize_name("././a"), "a");
+        assert_eq!(normalize_name("./"), "");
+    }
+
+    #[cfg(feature = "services-memory")]
+    #[tokio::test]
+    async fn test_load_index() {
+        let buf = archive(&[
+            ("dir/", b""),
+            ("dir/file", b"hello"),
+            ("a/b/c", b"world"),
+            ("./top", b""),
+        ]);
+
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        op.write("test.zip", buf.clone()).await.unwrap();
+
+        let index = ZipIndex::load(&op, "test.zip").await.unwrap();
+        let paths: Vec<_> = index.list("", true).into_iter().map(|(k, _)| k).collect();
+        assert_eq!(
+            paths,
+            vec!["a/", "a/b/", "a/b/c", "dir/", "dir/file", "top"]
+        );
+        assert_eq!(index.list("", false).len(), 3);
+        assert_eq!(index.list("a/", false).len(), 1);
+
+        let entry = index.get("dir/file").unwrap();
+        assert_eq!(entry.size, 5);
+        assert_eq!(entry.method, METHOD_STORED);
+        assert_eq!(
+            entry.mtime.unwrap().to_rfc3339(),
+            "2020-09-13T12:26:40+00:00"
+        );
+
+        let offset = entry.header_offset as usize;
+        let data_offset = offset + parse_local_header(&buf[offset..]).unwrap() as usize;
+        assert_eq!(&buf[data_offset..data_offset + 5], b"hello");
+    }
+}
@@ -0,0 +1,184 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::cmp;
+use std::io::SeekFrom;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Buf;
+use bytes::Bytes;
+use bytes::BytesMut;
+use flate2::Decompress;
+use flate2::FlushDecompress;
+use flate2::Status;
+
+use crate::raw::*;
+use crate::*;
+
+/// ZipReader reads the data of a member.
+pub enum ZipReader {
+    /// Stored members are read from the archive directly.
+    Stored(Reader),
+    /// Deflated members are decompressed on the fly.
+    Deflated(DeflateReader),
+}
+
+impl oio::Read for ZipReader {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match self {
+            ZipReader::Stored(r) => oio::Read::poll_read(r, cx, buf),
+            ZipReader::Deflated(r) => r.poll_read(cx, buf),
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        match self {
+            ZipReader::Stored(r) => oio::Read::poll_seek(r, cx, pos),
+            ZipReader::Deflated(r) => r.poll_seek(cx, pos),
+        }
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match self {
+            ZipReader::Stored(r) => oio::Read::poll_next(r, cx),
+            ZipReader::Deflated(r) => r.poll_next(cx),
+        }
+    }
+}
+
+/// DeflateReader decompresses the raw deflate stream of a member.
+///
+/// Deflate stream can't be read from the middle, so we have to decompress
+/// from the start and discard the first `skip` bytes.
+pub struct DeflateReader {
+    inner: Reader,
+    decompress: Decompress,
+
+    /// Compressed bytes that haven't been consumed.
+    buf: BytesMut,
+    need_input: bool,
+    eof: bool,
+
+    skip: u64,
+    remaining: u64,
+    /// Scratch space for the decompressed bytes to be discarded.
+    discard: Vec<u8>,
+}
+
+impl DeflateReader {
+    pub fn new(inner: Reader, skip: u64, size: u64) -> Self {
+        DeflateReader {
+            inner,
+            decompress: Decompress::new(false),
+            buf: BytesMut::new(),
+            need_input: true,
+            eof: false,
+            skip,
+            remaining: size,
+            discard: Vec::new(),
+        }
+    }
+}
+
+impl oio::Read for DeflateReader {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        loop {
+            if self.remaining == 0 {
+                return Poll::Ready(Ok(0));
+            }
+
+            if self.need_input && !self.eof {
+                match ready!(oio::Read::poll_next(&mut self.inner, cx)) {
+                    Some(Ok(bs)) => self.buf.extend_from_slice(&bs),
+                    Some(Err(err)) => return Poll::Ready(Err(err)),
+                    None => self.eof = true,
+                }
+                self.need_input = false;
+            }
+
+            let dst = if self.skip > 0 {
+                if self.discard.is_empty() {
+                    self.discard = vec![0; 64 * 1024];
+                }
+                let n = cmp::min(self.skip, self.discard.len() as u64) as usize;
+                &mut self.discard[..n]
+            } else {
+                let n = cmp::min(self.remaining, buf.len() as u64) as usize;
+                &mut buf[..n]
+            };
+
+            let flush = if self.eof {
+                FlushDecompress::Finish
+            } else {
+                FlushDecompress::None
+            };
+            let (total_in, total_out) = (self.decompress.total_in(), self.decompress.total_out());
+            let status = self
+                .decompress
+                .decompress(&self.buf, dst, flush)
+                .map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "decompress member of zip archive")
+                        .set_source(err)
+                })?;
+            let consumed = (self.decompress.total_in() - total_in) as usize;
+            let produced = self.decompress.total_out() - total_out;
+            self.buf.advance(consumed);
+
+            if produced == 0 {
+                if status == Status::StreamEnd || self.eof {
+                    return Poll::Ready(Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "member of zip archive is truncated",
+                    )));
+                }
+                self.need_input = true;
+                continue;
+            }
+
+            if self.skip > 0 {
+                self.skip -= produced;
+                continue;
+            }
+
+            self.remaining -= produced;
+            return Poll::Ready(Ok(produced as usize));
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        let (_, _) = (cx, pos);
+
+        Poll::Ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "output reader doesn't support seeking",
+        )))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        let _ = cx;
+
+        Poll::Ready(Some(Err(Error::new(
+            ErrorKind::Unsupported,
+            "output reader doesn't support streaming",
+        ))))
+    }
+}
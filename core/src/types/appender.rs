@@ -185,6 +185,7 @@ impl AsyncWrite for Appender {
     }
 }
 
+#[cfg(feature = "tokio")]
 impl tokio::io::AsyncWrite for Appender {
     fn poll_write(
         mut self: Pin<&mut Self>,
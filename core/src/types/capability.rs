@@ -17,6 +17,9 @@
 
 use std::fmt::Debug;
 
+use serde::Deserialize;
+use serde::Serialize;
+
 /// Capability is used to describe what operations are supported
 /// by current Operator.
 ///
@@ -44,7 +47,8 @@ use std::fmt::Debug;
 /// - Operation with variants should be named like `read_can_seek`.
 /// - Operation with arguments should be named like `read_with_range`.
 /// - Operation with limitations should be named like `batch_max_operations`.
-#[derive(Copy, Clone, Default)]
+#[derive(Copy, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Capability {
     /// If operator supports stat natively, it will be true.
     pub stat: bool,
@@ -52,6 +56,11 @@ pub struct Capability {
     pub stat_with_if_match: bool,
     /// If operator supports stat with if none match natively, it will be true.
     pub stat_with_if_none_match: bool,
+    /// If operator returns permission, uid and gid in stat natively, it
+    /// will be true.
+    pub stat_has_permission: bool,
+    /// If operator returns user metadata in stat natively, it will be true.
+    pub stat_has_user_metadata: bool,
 
     /// If operator supports read natively, it will be true.
     pub read: bool,
@@ -85,6 +94,14 @@ pub struct Capability {
     pub write_with_content_disposition: bool,
     /// If operator supports write with cache control natively, it will be true.
     pub write_with_cache_control: bool,
+    /// If operator supports write with storage class natively, it will be true.
+    pub write_with_storage_class: bool,
+    /// If operator supports write with acl natively, it will be true.
+    pub write_with_acl: bool,
+    /// If operator supports write with permission natively, it will be true.
+    pub write_with_permission: bool,
+    /// If operator supports write with user metadata natively, it will be true.
+    pub write_with_user_metadata: bool,
 
     /// If operator supports append natively, it will be true.
     pub append: bool,
@@ -100,9 +117,19 @@ pub struct Capability {
 
     /// If operator supports delete natively, it will be true.
     pub delete: bool,
+    /// If operator supports delete with if match natively, it will be true.
+    pub delete_with_if_match: bool,
+    /// If operator supports delete with version natively, it will be true.
+    pub delete_with_version: bool,
 
     /// If operator supports copy natively, it will be true.
     pub copy: bool,
+    /// If operator supports copy with content type natively, it will be true.
+    pub copy_with_content_type: bool,
+    /// If operator supports copy with user metadata natively, it will be true.
+    pub copy_with_user_metadata: bool,
+    /// If operator supports copy with tags natively, it will be true.
+    pub copy_with_tags: bool,
 
     /// If operator supports rename natively, it will be true.
     pub rename: bool,
@@ -134,6 +161,15 @@ pub struct Capability {
     /// The max operations that operator supports in batch.
     pub batch_max_operations: Option<usize>,
 
+    /// If operator supports restore natively, it will be true.
+    pub restore: bool,
+    /// If operator supports select natively, it will be true.
+    pub select: bool,
+    /// If operator supports write at given offset natively, it will be true.
+    pub write_at: bool,
+    /// If operator supports truncate natively, it will be true.
+    pub truncate: bool,
+
     /// If operator supports blocking natively, it will be true.
     pub blocking: bool,
 }
@@ -175,6 +211,18 @@ impl Debug for Capability {
         if self.batch {
             s.push("Batch");
         }
+        if self.restore {
+            s.push("Restore");
+        }
+        if self.select {
+            s.push("Select");
+        }
+        if self.write_at {
+            s.push("WriteAt");
+        }
+        if self.truncate {
+            s.push("Truncate");
+        }
         if self.blocking {
             s.push("Blocking");
         }
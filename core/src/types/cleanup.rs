@@ -0,0 +1,299 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use futures::future;
+use futures::StreamExt;
+
+use crate::*;
+
+/// CleanupRule decides which files will be removed by [`Operator::cleanup`].
+///
+/// A file matches the rule only if it matches all conditions set in the
+/// rule. A rule must have at least one condition.
+#[derive(Debug, Clone, Default)]
+pub struct CleanupRule {
+    older_than: Option<Duration>,
+    larger_than: Option<u64>,
+    suffix: Option<String>,
+}
+
+impl CleanupRule {
+    /// Create a new rule without any conditions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match files that last modified earlier than given duration ago.
+    ///
+    /// Files without `last_modified` will never match this condition.
+    pub fn older_than(mut self, v: Duration) -> Self {
+        self.older_than = Some(v);
+        self
+    }
+
+    /// Match files whose size is larger than given bytes.
+    pub fn larger_than(mut self, v: u64) -> Self {
+        self.larger_than = Some(v);
+        self
+    }
+
+    /// Match files whose path ends with given suffix.
+    pub fn suffix(mut self, v: &str) -> Self {
+        self.suffix = Some(v.to_string());
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        self.older_than.is_none() && self.larger_than.is_none() && self.suffix.is_none()
+    }
+
+    fn matches(&self, path: &str, meta: &Metadata, now: DateTime<Utc>) -> bool {
+        if let Some(suffix) = &self.suffix {
+            if !path.ends_with(suffix.as_str()) {
+                return false;
+            }
+        }
+        if let Some(size) = self.larger_than {
+            if meta.content_length() <= size {
+                return false;
+            }
+        }
+        if let Some(age) = self.older_than {
+            let expired = match (meta.last_modified(), chrono::Duration::from_std(age)) {
+                (Some(lm), Ok(age)) => lm + age < now,
+                _ => false,
+            };
+            if !expired {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// CleanupReport is the result of [`Operator::cleanup`], it will also be
+/// passed to the progress callback while cleaning up.
+#[derive(Debug, Default)]
+pub struct CleanupReport {
+    scanned: usize,
+    matched: usize,
+    matched_bytes: u64,
+    deleted: usize,
+    failed: Vec<(String, Error)>,
+}
+
+impl CleanupReport {
+    /// Count of files that have been scanned.
+    pub fn scanned(&self) -> usize {
+        self.scanned
+    }
+
+    /// Count of files that matched any rule.
+    pub fn matched(&self) -> usize {
+        self.matched
+    }
+
+    /// Total size of files that matched any rule.
+    pub fn matched_bytes(&self) -> u64 {
+        self.matched_bytes
+    }
+
+    /// Count of files that have been deleted, always `0` for dry run.
+    pub fn deleted(&self) -> usize {
+        self.deleted
+    }
+
+    /// Files that matched but failed to delete.
+    pub fn failed(&self) -> &[(String, Error)] {
+        &self.failed
+    }
+}
+
+/// Callback to report progress of cleanup.
+pub(crate) type CleanupProgress = Arc<dyn Fn(&CleanupReport) + Send + Sync>;
+
+/// Args for [`Operator::cleanup_with`].
+#[derive(Clone)]
+pub(crate) struct CleanupArgs {
+    pub rules: Vec<CleanupRule>,
+    pub dry_run: bool,
+    pub concurrent: usize,
+    pub progress: Option<CleanupProgress>,
+}
+
+impl Debug for CleanupArgs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CleanupArgs")
+            .field("rules", &self.rules)
+            .field("dry_run", &self.dry_run)
+            .field("concurrent", &self.concurrent)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Scan given path and delete all files that match any of the rules.
+pub(crate) async fn cleanup(
+    op: Operator,
+    path: String,
+    args: CleanupArgs,
+) -> Result<CleanupReport> {
+    if args.rules.is_empty() || args.rules.iter().any(|v| v.is_empty()) {
+        return Err(Error::new(
+            ErrorKind::ConfigInvalid,
+            "cleanup requires at least one rule and every rule must have conditions",
+        )
+        .with_operation("Operator::cleanup")
+        .with_context("path", &path));
+    }
+
+    let now = Utc::now();
+    let mut report = CleanupReport::default();
+    let mut results = op
+        .scan(&path)
+        .await?
+        .filter(|entry| {
+            // Dirs will never be cleaned up.
+            future::ready(!matches!(entry, Ok(v) if v.path().ends_with('/')))
+        })
+        .map(|entry| {
+            let op = op.clone();
+            let rules = args.rules.clone();
+            let dry_run = args.dry_run;
+            async move {
+                let entry = entry?;
+                let meta = op
+                    .metadata(&entry, Metakey::ContentLength | Metakey::LastModified)
+                    .await?;
+                let matched = rules.iter().any(|v| v.matches(entry.path(), &meta, now));
+                let deleted = if matched && !dry_run {
+                    Some(op.delete(entry.path()).await)
+                } else {
+                    None
+                };
+
+                Ok::<_, Error>((
+                    entry.path().to_string(),
+                    meta.content_length(),
+                    matched,
+                    deleted,
+                ))
+            }
+        })
+        .buffer_unordered(args.concurrent.max(1));
+
+    while let Some(result) = results.next().await {
+        let (path, size, matched, deleted) = result?;
+
+        report.scanned += 1;
+        if matched {
+            report.matched += 1;
+            report.matched_bytes += size;
+        }
+        match deleted {
+            Some(Ok(())) => report.deleted += 1,
+            Some(Err(err)) => report.failed.push((path, err)),
+            None => {}
+        }
+
+        if let Some(progress) = &args.progress {
+            progress(&report);
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_matches() {
+        let now = Utc::now();
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_length(1024)
+            .with_last_modified(now - chrono::Duration::days(2));
+
+        assert!(CleanupRule::new()
+            .suffix(".log")
+            .matches("a.log", &meta, now));
+        assert!(!CleanupRule::new()
+            .suffix(".log")
+            .matches("a.txt", &meta, now));
+        assert!(CleanupRule::new()
+            .older_than(Duration::from_secs(86400))
+            .matches("a", &meta, now));
+        assert!(!CleanupRule::new()
+            .older_than(Duration::from_secs(3 * 86400))
+            .matches("a", &meta, now));
+        assert!(!CleanupRule::new()
+            .larger_than(1024)
+            .matches("a", &meta, now));
+
+        // All conditions must be matched.
+        let rule = CleanupRule::new().suffix(".log").larger_than(512);
+        assert!(rule.matches("a.log", &meta, now));
+        assert!(!rule.matches("a.txt", &meta, now));
+    }
+
+    #[tokio::test]
+    async fn test_cleanup() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        op.write("dir/a.log", vec![0; 16]).await.unwrap();
+        op.write("dir/b.log", vec![0; 1024]).await.unwrap();
+        op.write("dir/sub/c.txt", vec![0; 1024]).await.unwrap();
+
+        let rules = vec![CleanupRule::new().suffix(".log").larger_than(512)];
+        let report = op
+            .cleanup_with("dir/", rules.clone())
+            .dry_run(true)
+            .await
+            .unwrap();
+        assert_eq!(report.scanned(), 3);
+        assert_eq!(report.matched(), 1);
+        assert_eq!(report.matched_bytes(), 1024);
+        assert_eq!(report.deleted(), 0);
+        assert!(op.is_exist("dir/b.log").await.unwrap());
+
+        let report = op.cleanup("dir/", rules).await.unwrap();
+        assert_eq!(report.deleted(), 1);
+        assert!(!op.is_exist("dir/b.log").await.unwrap());
+        assert!(op.is_exist("dir/a.log").await.unwrap());
+
+        let err = op
+            .cleanup("dir/", vec![CleanupRule::new()])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    fn test_older_than_without_last_modified() {
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_length(1)
+            .with_bit(Metakey::LastModified);
+        let rule = CleanupRule::new().older_than(Duration::from_secs(1));
+        assert!(!rule.matches("a", &meta, Utc::now()));
+    }
+}
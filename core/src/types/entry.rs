@@ -15,11 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use serde::Deserialize;
+use serde::Serialize;
+
 use crate::raw::*;
 use crate::*;
 
 /// Entry is the file/dir entry returned by `Lister`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Entry {
     /// Path of the entry.
     path: String,
@@ -45,7 +45,7 @@ use std::io;
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// ErrorKind is all kinds of Error of opendal.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum ErrorKind {
     /// OpenDAL don't know what happened here, and no actions other than just
@@ -335,6 +335,58 @@ impl Error {
     pub fn is_temporary(&self) -> bool {
         self.status == ErrorStatus::Temporary
     }
+
+    /// Check if this error is persistent, which means it's still failed after retry.
+    pub fn is_persistent(&self) -> bool {
+        self.status == ErrorStatus::Persistent
+    }
+
+    /// Return error's message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Return the operation that raised this error.
+    ///
+    /// Returns an empty string if operation is not set.
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// Return all context attached to this error as key-value pairs.
+    ///
+    /// Errors returned by [`Operator`][crate::Operator] always carry the
+    /// following keys:
+    ///
+    /// - `service`: the scheme of underlying service.
+    /// - `path`: the full path, or `from` and `to` for copy and rename.
+    ///
+    /// And `range`, `size`, `offset` if they are available for the operation.
+    pub fn context(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.context.iter().map(|(k, v)| (*k, v.as_str()))
+    }
+
+    /// Return the value of the first context with given key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opendal::Error;
+    /// use opendal::ErrorKind;
+    ///
+    /// let err = Error::new(ErrorKind::NotFound, "not found")
+    ///     .with_context("service", "s3")
+    ///     .with_context("path", "abc");
+    ///
+    /// assert_eq!(err.context_value("service"), Some("s3"));
+    /// assert_eq!(err.context_value("range"), None);
+    /// ```
+    pub fn context_value(&self, key: &str) -> Option<&str> {
+        self.context
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
 }
 
 impl From<Error> for io::Error {
@@ -392,4 +444,20 @@ Source: networking error
 "#
         )
     }
+
+    #[test]
+    fn test_error_accessors() {
+        let err = Lazy::force(&TEST_ERROR);
+
+        assert_eq!(err.operation(), "Read");
+        assert_eq!(err.message(), "something wrong happened");
+        assert_eq!(err.context_value("path"), Some("/path/to/file"));
+        assert_eq!(err.context_value("range"), None);
+        assert_eq!(
+            err.context().collect::<Vec<_>>(),
+            vec![("path", "/path/to/file"), ("called", "send_async")]
+        );
+        assert!(!err.is_temporary());
+        assert!(!err.is_persistent());
+    }
 }
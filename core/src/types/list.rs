@@ -23,6 +23,7 @@ use std::task::Context;
 use std::task::Poll;
 
 use futures::future::BoxFuture;
+use futures::stream::FusedStream;
 use futures::FutureExt;
 use futures::Stream;
 
@@ -36,6 +37,16 @@ use crate::*;
 ///
 /// User can use lister as `Stream<Item = Result<Entry>>` or
 /// call `next_page` directly.
+///
+/// Lister fetches pages lazily: the next page will only be requested
+/// after all entries in current page have been consumed. Lister also
+/// implements [`FusedStream`], so it's safe to poll it again after
+/// `None` has been returned.
+///
+/// Lister doesn't implement `Clone`: the underlying pager holds the
+/// service specific state of the listing like continuation tokens or open
+/// directory handles, which can't be duplicated cheaply. Please call `list`
+/// again to start another listing at the same path.
 pub struct Lister {
     pager: Option<oio::Pager>,
 
@@ -45,6 +56,9 @@ pub struct Lister {
     /// a future.
     #[allow(clippy::type_complexity)]
     fut: Option<BoxFuture<'static, (oio::Pager, Result<Option<Vec<oio::Entry>>>)>>,
+    /// done will be set to `true` after the last page has been returned,
+    /// so that we will not poll the underlying pager again.
+    done: bool,
 }
 
 /// # Safety
@@ -59,6 +73,7 @@ impl Lister {
             pager: Some(pager),
             buf: VecDeque::default(),
             fut: None,
+            done: false,
         }
     }
 
@@ -134,18 +149,22 @@ impl Stream for Lister {
             return Poll::Ready(Some(Ok(oe.into_entry())));
         }
 
+        if self.done {
+            return Poll::Ready(None);
+        }
+
         if let Some(fut) = self.fut.as_mut() {
             let (op, res) = ready!(fut.poll_unpin(cx));
             self.pager = Some(op);
+            self.fut = None;
 
             return match res? {
                 Some(oes) => {
-                    self.fut = None;
                     self.buf = oes.into();
                     self.poll_next(cx)
                 }
                 None => {
-                    self.fut = None;
+                    self.done = true;
                     Poll::Ready(None)
                 }
             };
@@ -160,6 +179,21 @@ impl Stream for Lister {
         self.fut = Some(Box::pin(fut));
         self.poll_next(cx)
     }
+
+    /// Lister only knows the entries that already fetched in current page.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (self.buf.len(), None)
+        }
+    }
+}
+
+impl FusedStream for Lister {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
 }
 
 /// BlockingLister is designed to list entries at given path in a blocking
@@ -224,3 +258,31 @@ impl Iterator for BlockingLister {
         self.next()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::services;
+    use crate::Operator;
+
+    #[tokio::test]
+    async fn test_lister_fused() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        op.write("test_dir/test_file", "Hello, World!")
+            .await
+            .expect("write must succeed");
+
+        let mut lister = op.list("test_dir/").await.unwrap();
+        assert!(!lister.is_terminated());
+
+        let entry = lister.next().await.unwrap().unwrap();
+        assert_eq!(entry.path(), "test_dir/test_file");
+
+        assert!(lister.next().await.is_none());
+        assert!(lister.is_terminated());
+        assert_eq!(lister.size_hint(), (0, Some(0)));
+        assert!(lister.next().await.is_none());
+    }
+}
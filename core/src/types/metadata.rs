@@ -15,9 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+
 use chrono::prelude::*;
 use flagset::flags;
 use flagset::FlagSet;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::raw::*;
 use crate::*;
@@ -29,7 +33,10 @@ use crate::*;
 /// mode and content_length are required metadata that all services
 /// should provide during `stat` operation. But in `list` operation,
 /// a.k.a., `Entry`'s content length could be `None`.
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// Metadata implements `Serialize` and `Deserialize` so that it can be
+/// cached or persisted, the set of known keys is kept as well.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Metadata {
     /// bit stores current key store.
     bit: FlagSet<Metakey>,
@@ -44,6 +51,23 @@ pub struct Metadata {
     content_type: Option<String>,
     etag: Option<String>,
     last_modified: Option<DateTime<Utc>>,
+
+    /// Rarely set metadata are boxed to keep `Metadata` small.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extension: Option<Box<MetadataExtension>>,
+}
+
+/// MetadataExtension carries metadata that are only returned by a few services.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+struct MetadataExtension {
+    storage_class: Option<String>,
+    restore_status: Option<String>,
+    checksum: Option<String>,
+    symlink_target: Option<String>,
+    permission: Option<u32>,
+    uid: Option<u32>,
+    gid: Option<u32>,
+    user_metadata: Option<HashMap<String, String>>,
 }
 
 impl Metadata {
@@ -69,6 +93,7 @@ impl Metadata {
             last_modified: None,
             etag: None,
             content_disposition: None,
+            extension: None,
         }
     }
 
@@ -83,6 +108,11 @@ impl Metadata {
         self
     }
 
+    /// Get the mutable extension, create it if not exist.
+    fn extension_mut(&mut self) -> &mut MetadataExtension {
+        self.extension.get_or_insert_with(Default::default)
+    }
+
     /// mode represent this entry's mode.
     pub fn mode(&self) -> EntryMode {
         debug_assert!(
@@ -418,6 +448,239 @@ impl Metadata {
         self.bit |= Metakey::ContentDisposition;
         self
     }
+
+    /// Storage class of this entry.
+    ///
+    /// Storage class is service specific, OpenDAL will return this value
+    /// AS-IS like the following:
+    ///
+    /// - "STANDARD"
+    /// - "STANDARD_IA"
+    /// - "GLACIER_IR"
+    pub fn storage_class(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::StorageClass) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: storage_class, maybe a bug"
+        );
+
+        self.extension
+            .as_ref()
+            .and_then(|v| v.storage_class.as_deref())
+    }
+
+    /// Set storage class of this entry.
+    pub fn set_storage_class(&mut self, v: &str) -> &mut Self {
+        self.extension_mut().storage_class = Some(v.to_string());
+        self.bit |= Metakey::StorageClass;
+        self
+    }
+
+    /// Set storage class of this entry.
+    pub fn with_storage_class(mut self, v: String) -> Self {
+        self.extension_mut().storage_class = Some(v);
+        self.bit |= Metakey::StorageClass;
+        self
+    }
+
+    /// Restore status of this archived entry.
+    ///
+    /// Restore status is service specific, OpenDAL will return this value
+    /// AS-IS like the following:
+    ///
+    /// - `ongoing-request="true"`
+    /// - `ongoing-request="false", expiry-date="Fri, 21 Dec 2012 00:00:00 GMT"`
+    ///
+    /// `None` means this entry is not archived or no restore has been
+    /// initiated.
+    pub fn restore_status(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::RestoreStatus) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: restore_status, maybe a bug"
+        );
+
+        self.extension
+            .as_ref()
+            .and_then(|v| v.restore_status.as_deref())
+    }
+
+    /// Set restore status of this entry.
+    pub fn set_restore_status(&mut self, v: &str) -> &mut Self {
+        self.extension_mut().restore_status = Some(v.to_string());
+        self.bit |= Metakey::RestoreStatus;
+        self
+    }
+
+    /// Set restore status of this entry.
+    pub fn with_restore_status(mut self, v: String) -> Self {
+        self.extension_mut().restore_status = Some(v);
+        self.bit |= Metakey::RestoreStatus;
+        self
+    }
+
+    /// Additional checksum of this entry.
+    ///
+    /// Checksum is service specific, OpenDAL will return this value
+    /// AS-IS. For example, s3 returns the base64 encoded checksum in the
+    /// configured `checksum_algorithm`.
+    pub fn checksum(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::Checksum) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: checksum, maybe a bug"
+        );
+
+        self.extension.as_ref().and_then(|v| v.checksum.as_deref())
+    }
+
+    /// Set checksum of this entry.
+    pub fn set_checksum(&mut self, v: &str) -> &mut Self {
+        self.extension_mut().checksum = Some(v.to_string());
+        self.bit |= Metakey::Checksum;
+        self
+    }
+
+    /// Set checksum of this entry.
+    pub fn with_checksum(mut self, v: String) -> Self {
+        self.extension_mut().checksum = Some(v);
+        self.bit |= Metakey::Checksum;
+        self
+    }
+
+    /// Symlink target of this entry.
+    ///
+    /// Only services that preserve symlinks will return this value, the
+    /// target will be returned AS-IS which could be a relative path.
+    ///
+    /// `None` means this entry is not a symlink.
+    pub fn symlink_target(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::SymlinkTarget) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: symlink_target, maybe a bug"
+        );
+
+        self.extension
+            .as_ref()
+            .and_then(|v| v.symlink_target.as_deref())
+    }
+
+    /// Set symlink target of this entry.
+    pub fn set_symlink_target(&mut self, v: &str) -> &mut Self {
+        self.extension_mut().symlink_target = Some(v.to_string());
+        self.bit |= Metakey::SymlinkTarget;
+        self
+    }
+
+    /// Set symlink target of this entry.
+    pub fn with_symlink_target(mut self, v: String) -> Self {
+        self.extension_mut().symlink_target = Some(v);
+        self.bit |= Metakey::SymlinkTarget;
+        self
+    }
+
+    /// POSIX permission bits of this entry like `0o644`.
+    ///
+    /// Only returned by services with `stat_has_permission` capability.
+    pub fn permission(&self) -> Option<u32> {
+        debug_assert!(
+            self.bit.contains(Metakey::Permission) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: permission, maybe a bug"
+        );
+
+        self.extension.as_ref().and_then(|v| v.permission)
+    }
+
+    /// Set permission of this entry.
+    pub fn set_permission(&mut self, v: u32) -> &mut Self {
+        self.extension_mut().permission = Some(v);
+        self.bit |= Metakey::Permission;
+        self
+    }
+
+    /// Set permission of this entry.
+    pub fn with_permission(mut self, v: u32) -> Self {
+        self.extension_mut().permission = Some(v);
+        self.bit |= Metakey::Permission;
+        self
+    }
+
+    /// Owner user id of this entry.
+    ///
+    /// Only returned by services with `stat_has_permission` capability.
+    pub fn uid(&self) -> Option<u32> {
+        debug_assert!(
+            self.bit.contains(Metakey::Uid) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: uid, maybe a bug"
+        );
+
+        self.extension.as_ref().and_then(|v| v.uid)
+    }
+
+    /// Set owner user id of this entry.
+    pub fn set_uid(&mut self, v: u32) -> &mut Self {
+        self.extension_mut().uid = Some(v);
+        self.bit |= Metakey::Uid;
+        self
+    }
+
+    /// Set owner user id of this entry.
+    pub fn with_uid(mut self, v: u32) -> Self {
+        self.extension_mut().uid = Some(v);
+        self.bit |= Metakey::Uid;
+        self
+    }
+
+    /// Owner group id of this entry.
+    ///
+    /// Only returned by services with `stat_has_permission` capability.
+    pub fn gid(&self) -> Option<u32> {
+        debug_assert!(
+            self.bit.contains(Metakey::Gid) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: gid, maybe a bug"
+        );
+
+        self.extension.as_ref().and_then(|v| v.gid)
+    }
+
+    /// Set owner group id of this entry.
+    pub fn set_gid(&mut self, v: u32) -> &mut Self {
+        self.extension_mut().gid = Some(v);
+        self.bit |= Metakey::Gid;
+        self
+    }
+
+    /// Set owner group id of this entry.
+    pub fn with_gid(mut self, v: u32) -> Self {
+        self.extension_mut().gid = Some(v);
+        self.bit |= Metakey::Gid;
+        self
+    }
+
+    /// User defined metadata of this entry.
+    ///
+    /// Only returned by services with `stat_has_user_metadata` capability.
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        debug_assert!(
+            self.bit.contains(Metakey::UserMetadata) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: user_metadata, maybe a bug"
+        );
+
+        self.extension
+            .as_ref()
+            .and_then(|v| v.user_metadata.as_ref())
+    }
+
+    /// Set user defined metadata of this entry.
+    pub fn set_user_metadata(&mut self, v: HashMap<String, String>) -> &mut Self {
+        self.extension_mut().user_metadata = Some(v);
+        self.bit |= Metakey::UserMetadata;
+        self
+    }
+
+    /// Set user defined metadata of this entry.
+    pub fn with_user_metadata(mut self, v: HashMap<String, String>) -> Self {
+        self.extension_mut().user_metadata = Some(v);
+        self.bit |= Metakey::UserMetadata;
+        self
+    }
 }
 
 flags! {
@@ -457,5 +720,59 @@ flags! {
         Etag,
         /// Key for last last modified.
         LastModified,
+        /// Key for storage class.
+        StorageClass,
+        /// Key for restore status.
+        RestoreStatus,
+        /// Key for checksum.
+        Checksum,
+        /// Key for symlink target.
+        SymlinkTarget,
+        /// Key for permission.
+        Permission,
+        /// Key for owner user id.
+        Uid,
+        /// Key for owner group id.
+        Gid,
+        /// Key for user metadata.
+        UserMetadata,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[test]
+    fn test_metadata_serde() {
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_length(1024)
+            .with_etag("\"abc\"".to_string())
+            .with_last_modified(Utc.timestamp_opt(1_700_000_000, 0).unwrap());
+
+        let s = serde_json::to_string(&meta).expect("serialize must succeed");
+        let got: Metadata = serde_json::from_str(&s).expect("deserialize must succeed");
+        assert_eq!(got, meta);
+        assert!(got.bit().contains(Metakey::Etag));
+        assert!(!got.bit().contains(Metakey::ContentType));
+
+        let entry = Entry::new_with("path/to/file".to_string(), meta);
+        let s = serde_json::to_string(&entry).expect("serialize must succeed");
+        let got: Entry = serde_json::from_str(&s).expect("deserialize must succeed");
+        assert_eq!(got.path(), "path/to/file");
+    }
+
+    #[test]
+    fn test_operator_info_serde() {
+        let op = Operator::new(services::Memory::default())
+            .expect("build must succeed")
+            .finish();
+
+        let s = serde_json::to_string(&op.info()).expect("serialize must succeed");
+        assert!(s.contains(r#""scheme":"memory""#));
+        let got: OperatorInfo = serde_json::from_str(&s).expect("deserialize must succeed");
+        assert_eq!(got.scheme(), Scheme::Memory);
+        assert_eq!(got.capability().read, op.info().capability().read);
     }
 }
@@ -40,6 +40,16 @@ mod list;
 pub use list::BlockingLister;
 pub use list::Lister;
 
+mod cleanup;
+pub(crate) use cleanup::cleanup;
+pub(crate) use cleanup::CleanupArgs;
+pub use cleanup::CleanupReport;
+pub use cleanup::CleanupRule;
+
+mod watch;
+pub use watch::WatchEvent;
+pub use watch::Watcher;
+
 mod operator;
 pub use operator::operator_functions;
 pub use operator::operator_futures;
@@ -47,6 +57,8 @@ pub use operator::BlockingOperator;
 pub use operator::Operator;
 pub use operator::OperatorBuilder;
 pub use operator::OperatorInfo;
+pub use operator::OperatorStats;
+pub(crate) use operator::StatsCounters;
 
 mod builder;
 pub use builder::Builder;
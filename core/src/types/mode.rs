@@ -18,8 +18,11 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+use serde::Deserialize;
+use serde::Serialize;
+
 /// EntryMode represents the mode.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum EntryMode {
     /// FILE means the path has data to read.
     FILE,
@@ -17,6 +17,7 @@
 
 use std::io::Read;
 use std::ops::RangeBounds;
+use std::sync::Arc;
 
 use bytes::Bytes;
 use flagset::FlagSet;
@@ -58,6 +59,7 @@ pub struct BlockingOperator {
     accessor: FusedAccessor,
 
     limit: usize,
+    stats: Arc<StatsCounters>,
 }
 
 impl BlockingOperator {
@@ -75,7 +77,16 @@ impl BlockingOperator {
             .capability()
             .batch_max_operations
             .unwrap_or(1000);
-        Self { accessor, limit }
+        Self {
+            accessor,
+            limit,
+            stats: Arc::default(),
+        }
+    }
+
+    pub(super) fn with_stats(mut self, stats: Arc<StatsCounters>) -> Self {
+        self.stats = stats;
+        self
     }
 
     /// Get current operator's limit
@@ -110,6 +121,14 @@ impl BlockingOperator {
     pub fn info(&self) -> OperatorInfo {
         OperatorInfo::new(self.accessor.info())
     }
+
+    /// Get a snapshot of statistics of this operator.
+    ///
+    /// Statistics are shared with the [`Operator`] that creates this
+    /// blocking operator.
+    pub fn stats(&self) -> OperatorStats {
+        self.stats.snapshot()
+    }
 }
 
 /// # Operator blocking API.
@@ -655,6 +674,79 @@ impl BlockingOperator {
         BlockingWriter::create(self.inner().clone(), &path, op)
     }
 
+    /// Write bytes at given offset of the file on given path.
+    ///
+    /// # Notes
+    ///
+    /// - Other content of the file will be kept unchanged.
+    /// - The file will be created if it doesn't exist, and the gap before
+    ///   offset will be filled with zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::BlockingOperator;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.write_at("path/to/file", 4096, vec![0; 4096])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_at(&self, path: &str, offset: u64, bs: impl Into<Bytes>) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "write path is a directory")
+                    .with_operation("BlockingOperator::write_at")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        let _ = self
+            .inner()
+            .blocking_write_at(&path, OpWriteAt::new(offset), bs.into())?;
+
+        Ok(())
+    }
+
+    /// Truncate or extend the file on given path to given size.
+    ///
+    /// # Notes
+    ///
+    /// - The extended part will be filled with zeros.
+    /// - Truncate a not existing file will return `NotFound`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::BlockingOperator;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.truncate("path/to/file", 1024)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn truncate(&self, path: &str, size: u64) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "truncate path is a directory")
+                    .with_operation("BlockingOperator::truncate")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        let _ = self
+            .inner()
+            .blocking_truncate(&path, OpTruncate::new(size))?;
+
+        Ok(())
+    }
+
     /// Delete given path.
     ///
     /// # Notes
@@ -157,11 +157,29 @@ impl Operator {
             Scheme::Azblob => Self::from_map::<services::Azblob>(map)?.finish(),
             #[cfg(feature = "services-azdfs")]
             Scheme::Azdfs => Self::from_map::<services::Azdfs>(map)?.finish(),
+            #[cfg(feature = "services-azfile")]
+            Scheme::Azfile => Self::from_map::<services::Azfile>(map)?.finish(),
+            #[cfg(feature = "services-b2")]
+            Scheme::B2 => Self::from_map::<services::B2>(map)?.finish(),
+            #[cfg(feature = "services-box")]
+            Scheme::Box => Self::from_map::<services::BoxStorage>(map)?.finish(),
+            #[cfg(feature = "services-cassandra")]
+            Scheme::Cassandra => Self::from_map::<services::Cassandra>(map)?.finish(),
+            #[cfg(feature = "services-cloudflare-kv")]
+            Scheme::CloudflareKv => Self::from_map::<services::CloudflareKv>(map)?.finish(),
             #[cfg(feature = "services-cos")]
             Scheme::Cos => Self::from_map::<services::Cos>(map)?.finish(),
+            #[cfg(feature = "services-d1")]
+            Scheme::D1 => Self::from_map::<services::D1>(map)?.finish(),
             #[cfg(feature = "services-dashmap")]
             Scheme::Dashmap => Self::from_map::<services::Dashmap>(map)?.finish(),
-            #[cfg(feature = "services-fs")]
+            #[cfg(feature = "services-dynamodb")]
+            Scheme::Dynamodb => Self::from_map::<services::Dynamodb>(map)?.finish(),
+            #[cfg(feature = "services-etcd")]
+            Scheme::Etcd => Self::from_map::<services::Etcd>(map)?.finish(),
+            #[cfg(feature = "services-foundationdb")]
+            Scheme::Foundationdb => Self::from_map::<services::Foundationdb>(map)?.finish(),
+            #[cfg(feature = "services-fs-blocking")]
             Scheme::Fs => Self::from_map::<services::Fs>(map)?.finish(),
             #[cfg(feature = "services-ftp")]
             Scheme::Ftp => Self::from_map::<services::Ftp>(map)?.finish(),
@@ -169,48 +187,90 @@ impl Operator {
             Scheme::Gcs => Self::from_map::<services::Gcs>(map)?.finish(),
             #[cfg(feature = "services-ghac")]
             Scheme::Ghac => Self::from_map::<services::Ghac>(map)?.finish(),
+            #[cfg(feature = "services-github")]
+            Scheme::Github => Self::from_map::<services::Github>(map)?.finish(),
+            #[cfg(feature = "services-gridfs")]
+            Scheme::Gridfs => Self::from_map::<services::Gridfs>(map)?.finish(),
             #[cfg(feature = "services-hdfs")]
             Scheme::Hdfs => Self::from_map::<services::Hdfs>(map)?.finish(),
             #[cfg(feature = "services-http")]
             Scheme::Http => Self::from_map::<services::Http>(map)?.finish(),
+            #[cfg(feature = "services-huggingface")]
+            Scheme::Huggingface => Self::from_map::<services::Huggingface>(map)?.finish(),
             #[cfg(feature = "services-ipfs")]
             Scheme::Ipfs => Self::from_map::<services::Ipfs>(map)?.finish(),
             #[cfg(feature = "services-ipmfs")]
             Scheme::Ipmfs => Self::from_map::<services::Ipmfs>(map)?.finish(),
+            #[cfg(feature = "services-kodo")]
+            Scheme::Kodo => Self::from_map::<services::Kodo>(map)?.finish(),
             #[cfg(feature = "services-memcached")]
             Scheme::Memcached => Self::from_map::<services::Memcached>(map)?.finish(),
             #[cfg(feature = "services-memory")]
             Scheme::Memory => Self::from_map::<services::Memory>(map)?.finish(),
+            #[cfg(feature = "services-mock")]
+            Scheme::Mock => Self::from_map::<services::Mock>(map)?.finish(),
             #[cfg(feature = "services-moka")]
             Scheme::Moka => Self::from_map::<services::Moka>(map)?.finish(),
+            #[cfg(feature = "services-mysql")]
+            Scheme::Mysql => Self::from_map::<services::Mysql>(map)?.finish(),
+            #[cfg(feature = "services-nfs")]
+            Scheme::Nfs => Self::from_map::<services::Nfs>(map)?.finish(),
             #[cfg(feature = "services-obs")]
             Scheme::Obs => Self::from_map::<services::Obs>(map)?.finish(),
+            #[cfg(feature = "services-oci")]
+            Scheme::Oci => Self::from_map::<services::Oci>(map)?.finish(),
             #[cfg(feature = "services-onedrive")]
             Scheme::Onedrive => Self::from_map::<services::Onedrive>(map)?.finish(),
             #[cfg(feature = "services-gdrive")]
             Scheme::Gdrive => Self::from_map::<services::Gdrive>(map)?.finish(),
             #[cfg(feature = "services-oss")]
             Scheme::Oss => Self::from_map::<services::Oss>(map)?.finish(),
+            #[cfg(feature = "services-postgresql")]
+            Scheme::Postgresql => Self::from_map::<services::Postgresql>(map)?.finish(),
+            #[cfg(feature = "services-rados")]
+            Scheme::Rados => Self::from_map::<services::Rados>(map)?.finish(),
+            #[cfg(feature = "services-redb")]
+            Scheme::Redb => Self::from_map::<services::Redb>(map)?.finish(),
             #[cfg(feature = "services-redis")]
             Scheme::Redis => Self::from_map::<services::Redis>(map)?.finish(),
             #[cfg(feature = "services-rocksdb")]
             Scheme::Rocksdb => Self::from_map::<services::Rocksdb>(map)?.finish(),
             #[cfg(feature = "services-s3")]
             Scheme::S3 => Self::from_map::<services::S3>(map)?.finish(),
+            #[cfg(feature = "services-seafile")]
+            Scheme::Seafile => Self::from_map::<services::Seafile>(map)?.finish(),
+            #[cfg(feature = "services-seaweedfs")]
+            Scheme::Seaweedfs => Self::from_map::<services::Seaweedfs>(map)?.finish(),
             #[cfg(feature = "services-sftp")]
             Scheme::Sftp => Self::from_map::<services::Sftp>(map)?.finish(),
+            #[cfg(feature = "services-sim")]
+            Scheme::Sim => Self::from_map::<services::Sim>(map)?.finish(),
             #[cfg(feature = "services-sled")]
             Scheme::Sled => Self::from_map::<services::Sled>(map)?.finish(),
+            #[cfg(feature = "services-sqlite")]
+            Scheme::Sqlite => Self::from_map::<services::Sqlite>(map)?.finish(),
             #[cfg(feature = "services-supabase")]
             Scheme::Supabase => Self::from_map::<services::Supabase>(map)?.finish(),
+            #[cfg(feature = "services-tar")]
+            Scheme::Tar => Self::from_map::<services::Tar>(map)?.finish(),
+            #[cfg(feature = "services-tikv")]
+            Scheme::Tikv => Self::from_map::<services::Tikv>(map)?.finish(),
+            #[cfg(feature = "services-tos")]
+            Scheme::Tos => Self::from_map::<services::Tos>(map)?.finish(),
             #[cfg(feature = "services-vercel-artifacts")]
             Scheme::VercelArtifacts => Self::from_map::<services::VercelArtifacts>(map)?.finish(),
+            #[cfg(feature = "services-vercel-blob")]
+            Scheme::VercelBlob => Self::from_map::<services::VercelBlob>(map)?.finish(),
             #[cfg(feature = "services-wasabi")]
             Scheme::Wasabi => Self::from_map::<services::Wasabi>(map)?.finish(),
             #[cfg(feature = "services-webdav")]
             Scheme::Webdav => Self::from_map::<services::Webdav>(map)?.finish(),
             #[cfg(feature = "services-webhdfs")]
             Scheme::Webhdfs => Self::from_map::<services::Webhdfs>(map)?.finish(),
+            #[cfg(feature = "services-yandex-disk")]
+            Scheme::YandexDisk => Self::from_map::<services::YandexDisk>(map)?.finish(),
+            #[cfg(feature = "services-zip")]
+            Scheme::Zip => Self::from_map::<services::Zip>(map)?.finish(),
             v => {
                 return Err(Error::new(
                     ErrorKind::Unsupported,
@@ -254,9 +314,12 @@ impl Operator {
     /// ```
     #[must_use]
     pub fn layer<L: Layer<FusedAccessor>>(self, layer: L) -> Self {
+        let stats = self.stats_counters().clone();
+
         Self::from_inner(Arc::new(
             TypeEraseLayer.layer(layer.layer(self.into_inner())),
         ))
+        .with_stats(stats)
     }
 }
 
@@ -306,16 +369,116 @@ impl Operator {
 /// ```
 pub struct OperatorBuilder<A: Accessor> {
     accessor: A,
+
+    retry: Option<RetryLayer>,
+    timeout: Option<TimeoutLayer>,
+    #[cfg(feature = "tokio")]
+    concurrent_limit: Option<ConcurrentLimitLayer>,
+    buffer_pool: BufferPool,
+    stats: Arc<StatsCounters>,
 }
 
 impl<A: Accessor> OperatorBuilder<A> {
     /// Create a new operator builder.
     #[allow(clippy::new_ret_no_self)]
     pub fn new(accessor: A) -> OperatorBuilder<impl Accessor> {
+        let buffer_pool = BufferPool::default();
+        let stats = Arc::<StatsCounters>::default();
+
         // Make sure error context layer has been attached.
-        OperatorBuilder { accessor }
-            .layer(ErrorContextLayer)
-            .layer(CompleteLayer)
+        OperatorBuilder {
+            accessor,
+
+            retry: None,
+            timeout: None,
+            #[cfg(feature = "tokio")]
+            concurrent_limit: None,
+            buffer_pool: buffer_pool.clone(),
+            stats: stats.clone(),
+        }
+        .layer(ErrorContextLayer)
+        .layer(CompleteLayer::new(buffer_pool))
+        .layer(StatsLayer::new(stats))
+    }
+
+    /// Retry temporary failed operations with given [`RetryLayer`].
+    ///
+    /// The retry layer will be installed while calling [`OperatorBuilder::finish`].
+    /// Please read the [`Defaults`](#defaults) section for the installing order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use std::time::Duration;
+    ///
+    /// use opendal::layers::RetryLayer;
+    /// use opendal::layers::TimeoutLayer;
+    /// use opendal::services::Memory;
+    /// use opendal::Operator;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let op = Operator::new(Memory::default())?
+    ///     .with_retry(RetryLayer::new().with_max_times(5))
+    ///     .with_timeout(TimeoutLayer::new().with_timeout(Duration::from_secs(10)))
+    ///     .with_concurrent_limit(1024)
+    ///     .finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_retry(mut self, layer: RetryLayer) -> Self {
+        self.retry = Some(layer);
+        self
+    }
+
+    /// Set timeout for every operation with given [`TimeoutLayer`].
+    ///
+    /// The timeout layer will be installed while calling [`OperatorBuilder::finish`].
+    /// Please read the [`Defaults`](#defaults) section for the installing order.
+    #[must_use]
+    pub fn with_timeout(mut self, layer: TimeoutLayer) -> Self {
+        self.timeout = Some(layer);
+        self
+    }
+
+    /// Limit the max concurrent requests sent to underlying services.
+    ///
+    /// The concurrent limit layer will be installed while calling
+    /// [`OperatorBuilder::finish`]. Please read the [`Defaults`](#defaults)
+    /// section for the installing order.
+    #[cfg(feature = "tokio")]
+    #[must_use]
+    pub fn with_concurrent_limit(mut self, permits: usize) -> Self {
+        self.concurrent_limit = Some(ConcurrentLimitLayer::new(permits));
+        self
+    }
+
+    /// Set the chunk size of buffers used by internal readers, default to
+    /// 256 KiB.
+    ///
+    /// Buffers are kept in a pool shared by all readers of this operator
+    /// and reused after readers dropped, which reduces allocations for
+    /// workloads reading many small objects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::services::Memory;
+    /// use opendal::Operator;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let op = Operator::new(Memory::default())?
+    ///     .with_buffer_chunk_size(64 * 1024)
+    ///     .finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_buffer_chunk_size(self, size: usize) -> Self {
+        self.buffer_pool.set_chunk_size(size);
+        self
     }
 
     /// Create a new layer with static dispatch.
@@ -352,13 +515,180 @@ impl<A: Accessor> OperatorBuilder<A> {
     pub fn layer<L: Layer<A>>(self, layer: L) -> OperatorBuilder<L::LayeredAccessor> {
         OperatorBuilder {
             accessor: layer.layer(self.accessor),
+
+            retry: self.retry,
+            timeout: self.timeout,
+            #[cfg(feature = "tokio")]
+            concurrent_limit: self.concurrent_limit,
+            buffer_pool: self.buffer_pool,
+            stats: self.stats,
         }
     }
 
     /// Finish the building to construct an Operator.
-    pub fn finish(self) -> Operator {
+    ///
+    /// # Defaults
+    ///
+    /// Layers set via [`OperatorBuilder::with_concurrent_limit`],
+    /// [`OperatorBuilder::with_timeout`] and [`OperatorBuilder::with_retry`]
+    /// will be installed on top of all other layers in the following order
+    /// (from inner to outer):
+    ///
+    /// - `ConcurrentLimitLayer`: permits are only held by the request that
+    ///   is actually sending to services, not the ones waiting for retry.
+    /// - `TimeoutLayer`: timeout is applied to every single attempt.
+    /// - `RetryLayer`: timeout errors are temporary, so they will be retried.
+    pub fn finish(mut self) -> Operator {
+        let retry = self.retry.take();
+        let timeout = self.timeout.take();
+        #[cfg(feature = "tokio")]
+        let concurrent_limit = self.concurrent_limit.take();
+
         let ob = self.layer(TypeEraseLayer);
 
-        Operator::from_inner(Arc::new(ob.accessor) as FusedAccessor)
+        let mut op =
+            Operator::from_inner(Arc::new(ob.accessor) as FusedAccessor).with_stats(ob.stats);
+        #[cfg(feature = "tokio")]
+        if let Some(layer) = concurrent_limit {
+            op = op.layer(layer);
+        }
+        if let Some(layer) = timeout {
+            op = op.layer(layer);
+        }
+        if let Some(layer) = retry {
+            op = op.layer(layer);
+        }
+        op
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::services::Fs;
+    use crate::services::Memory;
+
+    #[derive(Default, Clone)]
+    struct SlowBuilder {
+        attempt: Arc<Mutex<usize>>,
+    }
+
+    impl Builder for SlowBuilder {
+        const SCHEME: Scheme = Scheme::Custom("slow");
+        type Accessor = SlowService;
+
+        fn from_map(_: HashMap<String, String>) -> Self {
+            Self::default()
+        }
+
+        fn build(&mut self) -> Result<Self::Accessor> {
+            Ok(SlowService {
+                attempt: self.attempt.clone(),
+            })
+        }
+    }
+
+    /// SlowService hangs on the first list and returns directly after.
+    #[derive(Debug, Clone, Default)]
+    struct SlowService {
+        attempt: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait]
+    impl Accessor for SlowService {
+        type Reader = ();
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Appender = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capability(Capability {
+                list: true,
+                list_with_delimiter_slash: true,
+                ..Default::default()
+            });
+
+            am
+        }
+
+        async fn list(&self, _: &str, _: OpList) -> Result<(RpList, Self::Pager)> {
+            let attempt = {
+                let mut attempt = self.attempt.lock().unwrap();
+                *attempt += 1;
+                *attempt
+            };
+
+            if attempt == 1 {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+            Ok((RpList::default(), ()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_operator_builder_with_defaults() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .with_retry(RetryLayer::new().with_max_times(3))
+            .with_timeout(TimeoutLayer::new().with_timeout(Duration::from_secs(10)))
+            .with_concurrent_limit(4)
+            .finish();
+
+        op.write("test", "Hello, World!").await?;
+        let bs = op.read("test").await?;
+        assert_eq!(bs, b"Hello, World!");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_operator_builder_retry_timeout() -> Result<()> {
+        let builder = SlowBuilder::default();
+        let attempt = builder.attempt.clone();
+
+        // Retry must be installed outside timeout so that timed out
+        // operations will be retried.
+        let op = Operator::new(builder)?
+            .with_retry(RetryLayer::new().with_min_delay(Duration::from_millis(1)))
+            .with_timeout(TimeoutLayer::new().with_timeout(Duration::from_millis(100)))
+            .finish();
+
+        op.list("test/").await?;
+        assert_eq!(*attempt.lock().unwrap(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_operator_builder_with_buffer_chunk_size() -> Result<()> {
+        let root = env::temp_dir().join(format!("opendal-{}", uuid::Uuid::new_v4()));
+
+        let mut builder = Fs::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder)?.with_buffer_chunk_size(4).finish();
+
+        op.write("test", "Hello, World!").await?;
+
+        let mut r = op.reader("test").await?;
+        let mut content = Vec::new();
+        while let Some(bs) = r.next().await {
+            let bs = bs.expect("next must succeed");
+            assert!(bs.len() <= 4, "chunk must not be larger than buffer");
+            content.extend_from_slice(&bs);
+        }
+        assert_eq!(content, b"Hello, World!");
+
+        std::fs::remove_dir_all(root).expect("remove root must succeed");
+        Ok(())
     }
 }
@@ -15,11 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use serde::Deserialize;
+use serde::Serialize;
+
 use crate::raw::*;
 use crate::*;
 
 /// Metadata for operator, users can use this metadata to get information of operator.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct OperatorInfo(AccessorInfo);
 
 impl OperatorInfo {
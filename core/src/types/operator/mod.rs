@@ -30,5 +30,11 @@ pub use builder::OperatorBuilder;
 mod metadata;
 pub use metadata::OperatorInfo;
 
+mod stats;
+pub use stats::OperatorStats;
+pub(crate) use stats::StatsCounters;
+
+mod url;
+
 pub mod operator_functions;
 pub mod operator_futures;
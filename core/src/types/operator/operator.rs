@@ -15,23 +15,32 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp;
+use std::ops::Range;
 use std::ops::RangeBounds;
+use std::sync::Arc;
 use std::time::Duration;
 
 use bytes::Bytes;
+use bytes::BytesMut;
 use flagset::FlagSet;
 use futures::stream;
 use futures::AsyncReadExt;
 use futures::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
-use tokio::io::ReadBuf;
 
 use super::BlockingOperator;
 use crate::operator_futures::*;
 use crate::raw::*;
 use crate::*;
 
+/// The default gap threshold to merge ranges in [`Operator::read_ranges`].
+const DEFAULT_READ_RANGES_GAP: u64 = 1024 * 1024;
+
+/// The max count of failed paths recorded in the error of remove.
+const REMOVE_FAILED_PATHS_LIMIT: usize = 10;
+
 /// Operator is the entry for all public async APIs.
 ///
 /// Developer should manipulate the data from storage service through Operator only by right.
@@ -70,11 +79,17 @@ pub struct Operator {
 
     // limit is usually the maximum size of data that operator will handle in one operation
     limit: usize,
+
+    // batch_concurrency is the maximum batch requests that could be sent concurrently
+    batch_concurrency: usize,
+
+    // stats is the counters maintained by the core accessor wrapper
+    stats: Arc<StatsCounters>,
 }
 
 /// # Operator basic API.
 impl Operator {
-    pub(super) fn inner(&self) -> &FusedAccessor {
+    pub(crate) fn inner(&self) -> &FusedAccessor {
         &self.accessor
     }
 
@@ -84,7 +99,21 @@ impl Operator {
             .capability()
             .batch_max_operations
             .unwrap_or(100);
-        Self { accessor, limit }
+        Self {
+            accessor,
+            limit,
+            batch_concurrency: 1,
+            stats: Arc::default(),
+        }
+    }
+
+    pub(super) fn stats_counters(&self) -> &Arc<StatsCounters> {
+        &self.stats
+    }
+
+    pub(super) fn with_stats(mut self, stats: Arc<StatsCounters>) -> Self {
+        self.stats = stats;
+        self
     }
 
     pub(super) fn into_inner(self) -> FusedAccessor {
@@ -106,6 +135,21 @@ impl Operator {
         op
     }
 
+    /// Get current operator's batch concurrency.
+    pub fn batch_concurrency(&self) -> usize {
+        self.batch_concurrency
+    }
+
+    /// Specify how many batch requests could be sent concurrently while
+    /// removing files in batch.
+    ///
+    /// Default: 1
+    pub fn with_batch_concurrency(&self, concurrency: usize) -> Self {
+        let mut op = self.clone();
+        op.batch_concurrency = concurrency.max(1);
+        op
+    }
+
     /// Get information of underlying accessor.
     ///
     /// # Examples
@@ -129,7 +173,38 @@ impl Operator {
     ///
     /// This operation is nearly no cost.
     pub fn blocking(&self) -> BlockingOperator {
-        BlockingOperator::from_inner(self.accessor.clone()).with_limit(self.limit)
+        BlockingOperator::from_inner(self.accessor.clone())
+            .with_limit(self.limit)
+            .with_stats(self.stats.clone())
+    }
+
+    /// Get a snapshot of statistics of this operator.
+    ///
+    /// Statistics are shared by all clones of this operator and its
+    /// blocking operator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::raw::Operation;
+    /// use opendal::services::Memory;
+    /// use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let op = Operator::new(Memory::default())?.finish();
+    /// op.write("test", "Hello, World!").await?;
+    ///
+    /// let stats = op.stats();
+    /// assert_eq!(stats.operations_of(Operation::Write), 1);
+    /// assert_eq!(stats.bytes_written(), 13);
+    /// assert_eq!(stats.in_flight(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stats(&self) -> OperatorStats {
+        self.stats.snapshot()
     }
 }
 
@@ -137,7 +212,16 @@ impl Operator {
 impl Operator {
     /// Check if this operator can work correctly.
     ///
-    /// We will send a `list` request to path and return any errors we met.
+    /// We will send a minimal request to the root of service and return
+    /// any errors we met:
+    ///
+    /// - If service supports `list`, we will list root with limit `1`.
+    /// - Otherwise, if service supports `stat`, we will stat root instead.
+    ///
+    /// `NotFound` will be ignored since it means the service can be reached.
+    /// The returning error will carry `service`, `root` and `name` of this
+    /// operator along with the operation used to check in context, so users
+    /// can tell which part of configuration went wrong.
     ///
     /// ```
     /// # use std::sync::Arc;
@@ -151,10 +235,29 @@ impl Operator {
     /// # }
     /// ```
     pub async fn check(&self) -> Result<()> {
-        let mut ds = self.list("/").await?;
+        let info = self.info();
+
+        let (checked_by, res) = if info.can_list() {
+            let res = match self.list_with("/").limit(1).await {
+                Ok(mut ds) => match ds.next().await {
+                    Some(Err(err)) => Err(err),
+                    _ => Ok(()),
+                },
+                Err(err) => Err(err),
+            };
+            ("list", res)
+        } else if info.capability().stat {
+            ("stat", self.stat("/").await.map(|_| ()))
+        } else {
+            return Ok(());
+        };
 
-        match ds.next().await {
-            Some(Err(e)) if e.kind() != ErrorKind::NotFound => Err(e),
+        match res {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err
+                .with_operation("Operator::check")
+                .with_context("root", info.root())
+                .with_context("name", info.name())
+                .with_context("checked_by", checked_by)),
             _ => Ok(()),
         }
     }
@@ -194,6 +297,49 @@ impl Operator {
         self.stat_with(path).await
     }
 
+    /// Get metadata of many paths concurrently.
+    ///
+    /// # Notes
+    ///
+    /// - At most [`Operator::limit`] stat requests will be sent at the same time.
+    ///   Use [`Operator::with_limit`] to change it. A limit of `0` is treated as `1`.
+    /// - Results are returned in the same order as input paths, errors of one
+    ///   path will not affect others.
+    /// - Every path is sent as a separate stat request. Native batch stat
+    ///   endpoints of services are not used yet since [`Accessor::batch`]
+    ///   only supports delete for now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let results = op
+    ///     .stat_many(vec!["a".to_string(), "b".to_string()])
+    ///     .await;
+    /// for (path, result) in results {
+    ///     match result {
+    ///         Ok(meta) => println!("{path}: {}", meta.content_length()),
+    ///         Err(err) => println!("{path}: {err}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stat_many(&self, paths: Vec<String>) -> Vec<(String, Result<Metadata>)> {
+        stream::iter(paths)
+            .map(|path| async move {
+                let result = self.stat(&path).await;
+                (path, result)
+            })
+            .buffered(self.limit.max(1))
+            .collect()
+            .await
+    }
+
     /// Get current path's metadata **without cache** directly with extra options.
     ///
     /// # Notes
@@ -480,13 +626,13 @@ impl Operator {
                     let mut buffer = Vec::with_capacity(length);
 
                     let dst = buffer.spare_capacity_mut();
-                    let mut buf = ReadBuf::uninit(dst);
-
                     // Safety: the input buffer is created with_capacity(length).
-                    unsafe { buf.assume_init(length) };
+                    let buf = unsafe {
+                        std::slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut u8, length)
+                    };
 
                     // TODO: use native read api
-                    s.read_exact(buf.initialized_mut()).await.map_err(|err| {
+                    s.read_exact(buf).await.map_err(|err| {
                         Error::new(ErrorKind::Unexpected, "read from storage")
                             .with_operation("range_read")
                             .with_context("service", inner.info().scheme().into_static())
@@ -533,8 +679,159 @@ impl Operator {
         self.read_with(path).range(range).await
     }
 
+    /// Read multiple ranges of path into bytes.
+    ///
+    /// Nearby ranges will be merged into one request to reduce the number
+    /// of requests sent to storage, which is useful for reading column
+    /// chunks of Parquet or ORC files. Use [`Operator::read_ranges_with`]
+    /// to change the gap threshold of merging.
+    ///
+    /// # Notes
+    ///
+    /// - The returning bytes are in the same order as the given ranges.
+    /// - The returning content's length may be smaller than the range specified.
+    /// - At most [`Operator::limit`] requests will be sent concurrently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bss = op
+    ///     .read_ranges("path/to/file", &[0..1024, 2048..4096])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_ranges(&self, path: &str, ranges: &[Range<u64>]) -> Result<Vec<Bytes>> {
+        self.read_ranges_with(path, ranges).await
+    }
+
+    /// Read multiple ranges of path into bytes with extra options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bss = op
+    ///     .read_ranges_with("path/to/file", &[0..1024, 2048..4096])
+    ///     .gap(4096)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_ranges_with(&self, path: &str, ranges: &[Range<u64>]) -> FutureReadRanges {
+        let path = normalize_path(path);
+
+        let fut = FutureReadRanges(OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            (
+                OpRead::default(),
+                ranges.to_vec(),
+                DEFAULT_READ_RANGES_GAP,
+                self.limit(),
+            ),
+            |inner, path, (args, ranges, gap, limit)| {
+                let fut = async move {
+                    if !validate_path(&path, EntryMode::FILE) {
+                        return Err(Error::new(
+                            ErrorKind::IsADirectory,
+                            "read path is a directory",
+                        )
+                        .with_operation("read_ranges")
+                        .with_context("service", inner.info().scheme())
+                        .with_context("path", &path));
+                    }
+
+                    let merged = merge_ranges(&ranges, gap);
+
+                    // Send at most `limit` requests concurrently, `buffered`
+                    // keeps the results in the same order as merged ranges.
+                    let bss: Vec<Bytes> = stream::iter(merged.clone())
+                        .map(|range| {
+                            let inner = inner.clone();
+                            let path = path.clone();
+                            let args = args.clone().with_range(BytesRange::new(
+                                Some(range.start),
+                                Some(range.end - range.start),
+                            ));
+
+                            async move {
+                                let br = args.range();
+                                let (_, mut r) = inner.read(&path, args).await?;
+
+                                let mut chunks = Vec::new();
+                                while let Some(bs) = oio::ReadExt::next(&mut r).await {
+                                    chunks.push(bs.map_err(|err| {
+                                        err.with_operation("read_ranges")
+                                            .with_context(
+                                                "service",
+                                                inner.info().scheme().into_static(),
+                                            )
+                                            .with_context("path", &path)
+                                            .with_context("range", br.to_string())
+                                    })?);
+                                }
+
+                                // Hand the bytes out directly if there is only one
+                                // chunk to avoid an extra copy.
+                                if chunks.len() == 1 {
+                                    return Ok::<_, Error>(chunks.pop().unwrap());
+                                }
+                                let mut buffer =
+                                    BytesMut::with_capacity(chunks.iter().map(|v| v.len()).sum());
+                                for bs in chunks {
+                                    buffer.extend_from_slice(&bs);
+                                }
+                                Ok(buffer.freeze())
+                            }
+                        })
+                        .buffered(limit.max(1))
+                        .try_collect()
+                        .await?;
+
+                    // Slice the merged content back out for every range.
+                    let result = ranges
+                        .iter()
+                        .map(|range| {
+                            if range.start >= range.end {
+                                return Bytes::new();
+                            }
+
+                            let idx = merged.partition_point(|v| v.start <= range.start) - 1;
+                            let (base, bs) = (merged[idx].start, &bss[idx]);
+
+                            let start = cmp::min((range.start - base) as usize, bs.len());
+                            let end = cmp::min((range.end - base) as usize, bs.len());
+                            bs.slice(start..end)
+                        })
+                        .collect();
+
+                    Ok(result)
+                };
+
+                Box::pin(fut)
+            },
+        ));
+
+        fut
+    }
+
     /// Create a new reader which can read the whole path.
     ///
+    /// # Notes
+    ///
+    /// - Short forward seeks will discard bytes instead of sending a new
+    ///   request.
+    /// - Use [`FutureReader::lazy`] to send the read request on the first
+    ///   read instead.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -691,41 +988,84 @@ impl Operator {
     /// # }
     /// ```
     pub async fn copy(&self, from: &str, to: &str) -> Result<()> {
+        self.copy_with(from, to).await
+    }
+
+    /// Copy a file from `from` to `to` with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - All metadata of `from` will be preserved by default.
+    /// - If `content_type` or `user_metadata` is set, the metadata of `to`
+    ///   will be replaced by given values instead.
+    /// - If the service can't apply given options, an `Unsupported` error
+    ///   will be returned instead of dropping them silently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use std::collections::HashMap;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.copy_with("path/to/file", "path/to/file2")
+    ///     .content_type("application/octet-stream")
+    ///     .user_metadata(HashMap::from([("owner".to_string(), "opendal".to_string())]))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_with(&self, from: &str, to: &str) -> FutureCopy {
         let from = normalize_path(from);
+        let to = normalize_path(to);
 
-        if !validate_path(&from, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "from path is a directory")
-                    .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from),
-            );
-        }
+        let fut = FutureCopy(OperatorFuture::new(
+            self.inner().clone(),
+            from,
+            (OpCopy::new(), to),
+            |inner, from, (args, to)| {
+                let fut = async move {
+                    if !validate_path(&from, EntryMode::FILE) {
+                        return Err(Error::new(
+                            ErrorKind::IsADirectory,
+                            "from path is a directory",
+                        )
+                        .with_operation("Operator::copy")
+                        .with_context("service", inner.info().scheme().into_static())
+                        .with_context("from", from));
+                    }
 
-        let to = normalize_path(to);
+                    if !validate_path(&to, EntryMode::FILE) {
+                        return Err(
+                            Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                                .with_operation("Operator::copy")
+                                .with_context("service", inner.info().scheme().into_static())
+                                .with_context("to", to),
+                        );
+                    }
 
-        if !validate_path(&to, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "to path is a directory")
-                    .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("to", to),
-            );
-        }
+                    if from == to {
+                        return Err(Error::new(
+                            ErrorKind::IsSameFile,
+                            "from and to paths are same",
+                        )
+                        .with_operation("Operator::copy")
+                        .with_context("service", inner.info().scheme().into_static())
+                        .with_context("from", from)
+                        .with_context("to", to));
+                    }
 
-        if from == to {
-            return Err(
-                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
-                    .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from)
-                    .with_context("to", to),
-            );
-        }
+                    inner.copy(&from, &to, args).await?;
 
-        self.inner().copy(&from, &to, OpCopy::new()).await?;
+                    Ok(())
+                };
+                Box::pin(fut)
+            },
+        ));
 
-        Ok(())
+        fut
     }
 
     /// Rename a file from `from` to `to`.
@@ -1073,9 +1413,259 @@ impl Operator {
     /// # }
     /// ```
     pub async fn delete(&self, path: &str) -> Result<()> {
+        self.delete_with(path).await
+    }
+
+    /// Delete the given path with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - Deleting a file that does not exist won't return errors.
+    /// - If the conditions are not matched, a `ConditionNotMatch` error will
+    ///   be returned and the file will not be deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.delete_with("test").if_match("<etag>").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_with(&self, path: &str) -> FutureDelete {
+        let path = normalize_path(path);
+
+        let fut = FutureDelete(OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            OpDelete::new(),
+            |inner, path, args| {
+                let fut = async move {
+                    let _ = inner.delete(&path, args).await?;
+
+                    Ok(())
+                };
+                Box::pin(fut)
+            },
+        ));
+
+        fut
+    }
+
+    /// Select the records matched by given SQL expression from the file.
+    ///
+    /// # Notes
+    ///
+    /// The expression will be evaluated in service side, only the matched
+    /// records will be returned so that we don't need to read the whole file.
+    ///
+    /// The returning reader can't be seeked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::TryStreamExt;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let r = op
+    ///     .select("test.csv", "SELECT * FROM S3Object s WHERE s.age > '18'")
+    ///     .await?;
+    /// let bs: Vec<_> = r.try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn select(&self, path: &str, expression: &str) -> Result<Reader> {
+        self.select_with(path, expression).await
+    }
+
+    /// Select the records matched by given SQL expression from the file
+    /// with extra options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::raw::SelectFormat;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let r = op
+    ///     .select_with("test.json", "SELECT s.name FROM S3Object s")
+    ///     .input_format(SelectFormat::Json)
+    ///     .output_format(SelectFormat::Json)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn select_with(&self, path: &str, expression: &str) -> FutureSelect {
+        let path = normalize_path(path);
+
+        let fut = FutureSelect(OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            OpSelect::new(expression),
+            |inner, path, args| {
+                let fut = async move {
+                    if !validate_path(&path, EntryMode::FILE) {
+                        return Err(Error::new(
+                            ErrorKind::IsADirectory,
+                            "select path is a directory",
+                        )
+                        .with_operation("select")
+                        .with_context("service", inner.info().scheme())
+                        .with_context("path", &path));
+                    }
+
+                    Reader::create_select(inner.clone(), &path, args).await
+                };
+                Box::pin(fut)
+            },
+        ));
+
+        fut
+    }
+
+    /// Restore the archived file on given path.
+    ///
+    /// # Notes
+    ///
+    /// Restore will only initiate the restoration, users can check the
+    /// restore status via [`Metadata::restore_status`] returned by `stat`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.restore("test").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn restore(&self, path: &str) -> Result<()> {
+        self.restore_with(path).await
+    }
+
+    /// Restore the archived file on given path with extra options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.restore_with("test").days(7).tier("Bulk").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn restore_with(&self, path: &str) -> FutureRestore {
+        let path = normalize_path(path);
+
+        let fut = FutureRestore(OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            OpRestore::new(),
+            |inner, path, args| {
+                let fut = async move {
+                    if !validate_path(&path, EntryMode::FILE) {
+                        return Err(Error::new(
+                            ErrorKind::IsADirectory,
+                            "restore path is a directory",
+                        )
+                        .with_operation("restore")
+                        .with_context("service", inner.info().scheme())
+                        .with_context("path", &path));
+                    }
+
+                    let _ = inner.restore(&path, args).await?;
+
+                    Ok(())
+                };
+                Box::pin(fut)
+            },
+        ));
+
+        fut
+    }
+
+    /// Write bytes at given offset of the file on given path.
+    ///
+    /// # Notes
+    ///
+    /// - Other content of the file will be kept unchanged.
+    /// - The file will be created if it doesn't exist, and the gap before
+    ///   offset will be filled with zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.write_at("path/to/file", 4096, vec![0; 4096]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_at(&self, path: &str, offset: u64, bs: impl Into<Bytes>) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "write path is a directory")
+                    .with_operation("Operator::write_at")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        let _ = self
+            .inner()
+            .write_at(&path, OpWriteAt::new(offset), bs.into())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Truncate or extend the file on given path to given size.
+    ///
+    /// # Notes
+    ///
+    /// - The extended part will be filled with zeros.
+    /// - Truncate a not existing file will return `NotFound`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.truncate("path/to/file", 1024).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn truncate(&self, path: &str, size: u64) -> Result<()> {
         let path = normalize_path(path);
 
-        let _ = self.inner().delete(&path, OpDelete::new()).await?;
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "truncate path is a directory")
+                    .with_operation("Operator::truncate")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        let _ = self.inner().truncate(&path, OpTruncate::new(size)).await?;
 
         Ok(())
     }
@@ -1131,34 +1721,84 @@ impl Operator {
     /// # }
     /// ```
     pub async fn remove_via(&self, input: impl Stream<Item = String> + Unpin) -> Result<()> {
+        self.remove_stream(input.map(Ok), self.limit)
+            .await
+            .map_err(|err| err.with_operation("Operator::remove_via"))
+    }
+
+    /// Remove all paths from the given stream.
+    ///
+    /// - If underlying services support batch, paths will be removed in
+    ///   batches with at most `batch_concurrency` batches in flight.
+    /// - Otherwise, paths will be removed one by one with at most
+    ///   `concurrent` requests in flight.
+    ///
+    /// Failure of one delete request won't stop removing the others. If a
+    /// whole batch request fails, every path in it is counted as failed. The
+    /// returning error carries the count of all failures as `failed_count`,
+    /// and the first [`REMOVE_FAILED_PATHS_LIMIT`] failed paths as
+    /// `failed_path` in the form of `<path>: <error>`. The first failure is
+    /// used as source.
+    ///
+    /// Errors returned by the input stream stop removing and are returned
+    /// directly, since we don't know which paths are left.
+    async fn remove_stream(
+        &self,
+        input: impl Stream<Item = Result<String>> + Unpin,
+        concurrent: usize,
+    ) -> Result<()> {
+        let mut failures = RemoveFailures::default();
+
         if self.info().can_batch() {
-            let mut input = input
-                .map(|v| (v, OpDelete::default().into()))
-                .chunks(self.limit());
-
-            while let Some(batches) = input.next().await {
-                let results = self
-                    .inner()
-                    .batch(OpBatch::new(batches))
-                    .await?
-                    .into_results();
-
-                // TODO: return error here directly seems not a good idea?
-                for (_, result) in results {
-                    let _ = result?;
+            let mut results = input
+                .chunks(self.limit())
+                .map(|paths| {
+                    let inner = self.inner().clone();
+                    async move {
+                        let paths = paths.into_iter().collect::<Result<Vec<_>>>()?;
+                        let batches = paths
+                            .iter()
+                            .map(|path| (path.clone(), OpDelete::default().into()))
+                            .collect();
+
+                        let result = inner.batch(OpBatch::new(batches)).await;
+                        Ok::<_, Error>((paths, result))
+                    }
+                })
+                .buffer_unordered(self.batch_concurrency);
+
+            while let Some(result) = results.next().await {
+                match result? {
+                    (_, Ok(rp)) => {
+                        for (path, result) in rp.into_results() {
+                            if let Err(err) = result {
+                                failures.record([path], err);
+                            }
+                        }
+                    }
+                    (paths, Err(err)) => failures.record(paths, err),
                 }
             }
         } else {
-            input
-                .map(Ok)
-                .try_for_each_concurrent(self.limit, |path| async move {
-                    let _ = self.inner().delete(&path, OpDelete::default()).await?;
-                    Ok::<(), Error>(())
+            let mut results = input
+                .map(|path| {
+                    let inner = self.inner().clone();
+                    async move {
+                        let path = path?;
+                        let result = inner.delete(&path, OpDelete::default()).await;
+                        Ok::<_, Error>((path, result))
+                    }
                 })
-                .await?;
+                .buffer_unordered(concurrent);
+
+            while let Some(result) = results.next().await {
+                if let (path, Err(err)) = result? {
+                    failures.record([path], err);
+                }
+            }
         }
 
-        Ok(())
+        failures.into_result()
     }
 
     /// Remove the path and all nested dirs and files recursively.
@@ -1167,6 +1807,8 @@ impl Operator {
     ///
     /// If underlying services support delete in batch, we will use batch
     /// delete instead.
+    /// At most [`Operator::batch_concurrency`] batches will be sent
+    /// concurrently, and failure of one path won't stop removing the others.
     ///
     /// # Examples
     ///
@@ -1197,33 +1839,20 @@ impl Operator {
             return self.delete(path).await;
         }
 
-        let obs = self.scan(path).await?;
-
-        if self.info().can_batch() {
-            let mut obs = obs.try_chunks(self.limit());
-
-            while let Some(batches) = obs.next().await {
-                let batches = batches
-                    .map_err(|err| err.1)?
-                    .into_iter()
-                    .map(|v| (v.path().to_string(), OpDelete::default().into()))
-                    .collect();
-
-                let results = self
-                    .inner()
-                    .batch(OpBatch::new(batches))
-                    .await?
-                    .into_results();
-
-                // TODO: return error here directly seems not a good idea?
-                for (_, result) in results {
-                    let _ = result?;
-                }
-            }
+        // Nested dirs must be removed before their parents for services that
+        // can't remove in batch, so we scan and remove them one by one.
+        let obs = if self.info().can_batch() {
+            self.list_with(path)
+                .delimiter("")
+                .concurrent(self.batch_concurrency)
+                .await?
         } else {
-            obs.try_for_each(|v| async move { self.delete(v.path()).await })
-                .await?;
-        }
+            self.scan(path).await?
+        };
+
+        self.remove_stream(obs.map_ok(|v| v.path().to_string()), 1)
+            .await
+            .map_err(|err| err.with_operation("Operator::remove_all"))?;
 
         // Remove the directory itself.
         self.delete(path).await?;
@@ -1356,6 +1985,20 @@ impl Operator {
                         .with_context("path", &path));
                     }
 
+                    if args.delimiter().is_empty()
+                        && args.concurrent() > 1
+                        && args.start_after().is_none()
+                    {
+                        let mut op = OpList::new();
+                        if let Some(limit) = args.limit() {
+                            op = op.with_limit(limit);
+                        }
+                        let (_, pager) = inner.list(&path, op).await?;
+                        let pager = oio::to_concurrent_pager(inner.clone(), &path, pager, args);
+
+                        return Ok(Lister::new(Box::new(pager)));
+                    }
+
                     let (_, pager) = inner.list(&path, args).await?;
 
                     Ok(Lister::new(pager))
@@ -1408,6 +2051,158 @@ impl Operator {
     pub async fn scan(&self, path: &str) -> Result<Lister> {
         self.list_with(path).delimiter("").await
     }
+
+    /// Watch changes of files under given path.
+    ///
+    /// The current state of given path will be taken as the baseline, and
+    /// the returned [`Watcher`] yields [`WatchEvent`]s of files created,
+    /// modified or deleted after that. Changes are detected by polling on
+    /// all services, refer to [`Watcher`] for how it works and the cost of
+    /// every scan.
+    ///
+    /// An error will be returned if given path doesn't end with `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::Operator;
+    /// use opendal::WatchEvent;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut watcher = op.watch("path/to/dir/").await?;
+    /// while let Some(event) = watcher.try_next().await? {
+    ///     match event {
+    ///         WatchEvent::Created(de) => println!("{} created", de.path()),
+    ///         WatchEvent::Modified(de) => println!("{} modified", de.path()),
+    ///         WatchEvent::Deleted(de) => println!("{} deleted", de.path()),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn watch(&self, path: &str) -> Result<Watcher> {
+        self.watch_with(path).await
+    }
+
+    /// Watch changes of files under given path with extra options.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use std::time::Duration;
+    /// use futures::TryStreamExt;
+    /// use opendal::Operator;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut watcher = op
+    ///     .watch_with("path/to/dir/")
+    ///     .interval(Duration::from_secs(10))
+    ///     .await?;
+    /// while let Some(event) = watcher.try_next().await? {
+    ///     println!("{:?}", event);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_with(&self, path: &str) -> FutureWatch {
+        let path = normalize_path(path);
+
+        let fut = FutureWatch(OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            Duration::from_secs(60),
+            |inner, path, interval| {
+                let fut = async move {
+                    if !validate_path(&path, EntryMode::DIR) {
+                        return Err(Error::new(
+                            ErrorKind::NotADirectory,
+                            "the path trying to watch should end with `/`",
+                        )
+                        .with_operation("Operator::watch")
+                        .with_context("service", inner.info().scheme().into_static())
+                        .with_context("path", &path));
+                    }
+
+                    Watcher::create(Operator::from_inner(inner), path, interval).await
+                };
+                Box::pin(fut)
+            },
+        ));
+        fut
+    }
+
+    /// Delete all files under given path that match any of the rules.
+    ///
+    /// This works as a portable lifecycle policy for services that don't
+    /// support it natively. Files that failed to delete will be recorded
+    /// in the returning [`CleanupReport`] instead of stopping the cleanup.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use std::time::Duration;
+    /// use opendal::CleanupRule;
+    /// use opendal::Operator;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let rules = vec![
+    ///     CleanupRule::new().older_than(Duration::from_secs(7 * 86400)),
+    ///     CleanupRule::new().suffix(".tmp"),
+    /// ];
+    /// let report = op.cleanup("path/to/dir/", rules).await?;
+    /// println!("deleted {} files", report.deleted());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn cleanup(&self, path: &str, rules: Vec<CleanupRule>) -> Result<CleanupReport> {
+        self.cleanup_with(path, rules).await
+    }
+
+    /// Delete all files under given path that match any of the rules with
+    /// extra options.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::CleanupRule;
+    /// use opendal::Operator;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let report = op
+    ///     .cleanup_with("path/to/dir/", vec![CleanupRule::new().larger_than(1 << 30)])
+    ///     .dry_run(true)
+    ///     .concurrent(8)
+    ///     .progress(|report| println!("scanned {} files", report.scanned()))
+    ///     .await?;
+    /// println!("{} bytes would be deleted", report.matched_bytes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cleanup_with(&self, path: &str, rules: Vec<CleanupRule>) -> FutureCleanup {
+        let path = normalize_path(path);
+
+        let fut = FutureCleanup(OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            CleanupArgs {
+                rules,
+                dry_run: false,
+                concurrent: 1,
+                progress: None,
+            },
+            |inner, path, args| Box::pin(cleanup(Operator::from_inner(inner), path, args)),
+        ));
+        fut
+    }
 }
 /// Operator presign API.
 impl Operator {
@@ -1589,3 +2384,120 @@ impl Operator {
         fut
     }
 }
+
+/// Merge ranges whose gap is not larger than `gap` into sorted and
+/// non-overlapping ranges. Empty ranges will be ignored.
+fn merge_ranges(ranges: &[Range<u64>], gap: u64) -> Vec<Range<u64>> {
+    let mut ranges: Vec<_> = ranges.iter().filter(|v| v.start < v.end).cloned().collect();
+    ranges.sort_by_key(|v| v.start);
+
+    let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end.saturating_add(gap) => {
+                last.end = cmp::max(last.end, range.end);
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// RemoveFailures records failures of [`Operator::remove_via`] and
+/// [`Operator::remove_all`].
+///
+/// Only the count, the first error and at most
+/// [`REMOVE_FAILED_PATHS_LIMIT`] failed paths are kept, so removing a large
+/// tree that fails everywhere won't hold every error in memory.
+#[derive(Default)]
+struct RemoveFailures {
+    count: usize,
+    paths: Vec<String>,
+    source: Option<Error>,
+}
+
+impl RemoveFailures {
+    /// Record that given paths failed to be removed with the same error.
+    fn record(&mut self, paths: impl IntoIterator<Item = String>, err: Error) {
+        for path in paths {
+            self.count += 1;
+            if self.paths.len() < REMOVE_FAILED_PATHS_LIMIT {
+                self.paths.push(format!("{path}: {err}"));
+            }
+        }
+        if self.source.is_none() {
+            self.source = Some(err);
+        }
+    }
+
+    fn into_result(self) -> Result<()> {
+        let Some(source) = self.source else {
+            return Ok(());
+        };
+
+        let mut err = Error::new(source.kind(), "failed to remove some paths")
+            .with_context("failed_count", self.count.to_string());
+        for path in self.paths {
+            err = err.with_context("failed_path", path);
+        }
+        Err(err.set_source(source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_failures() {
+        let mut failures = RemoveFailures::default();
+        assert!(failures.into_result().is_ok());
+
+        failures = RemoveFailures::default();
+        failures.record(
+            (0..REMOVE_FAILED_PATHS_LIMIT + 5).map(|i| format!("batch/{i}")),
+            Error::new(ErrorKind::RateLimited, "batch failed"),
+        );
+        failures.record(
+            ["single".to_string()],
+            Error::new(ErrorKind::PermissionDenied, "delete failed"),
+        );
+        assert_eq!(failures.count, REMOVE_FAILED_PATHS_LIMIT + 6);
+        assert_eq!(failures.paths.len(), REMOVE_FAILED_PATHS_LIMIT);
+
+        let err = failures.into_result().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        let msg = err.to_string();
+        assert!(msg.contains(&format!("failed_count: {}", REMOVE_FAILED_PATHS_LIMIT + 6)));
+        assert_eq!(
+            msg.matches("failed_path").count(),
+            REMOVE_FAILED_PATHS_LIMIT
+        );
+    }
+
+    #[cfg(feature = "services-mock")]
+    #[tokio::test]
+    async fn test_remove_continues_after_failures() {
+        let builder = services::Mock::default();
+        let handle = builder.handle();
+        let op = Operator::new(builder).unwrap().finish();
+
+        let paths = (0..REMOVE_FAILED_PATHS_LIMIT + 2)
+            .map(|i| format!("file-{i}"))
+            .collect::<Vec<_>>();
+        for path in &paths {
+            op.write(path, "data").await.unwrap();
+            handle.fail_path(Operation::Delete, path, ErrorKind::PermissionDenied, 1);
+        }
+        op.write("ok", "data").await.unwrap();
+
+        let mut input = paths.clone();
+        input.push("ok".to_string());
+        let err = op.remove(input).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(err
+            .to_string()
+            .contains(&format!("failed_count: {}", paths.len())));
+        assert!(!op.is_exist("ok").await.unwrap());
+    }
+}
@@ -19,9 +19,12 @@
 //!
 //! By using futures, users can add more options for operation.
 
+use std::collections::HashMap;
 use std::mem;
+use std::ops::Range;
 use std::ops::RangeBounds;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 use std::time::Duration;
@@ -357,6 +360,15 @@ impl FutureRead {
         self.0 = self.0.map_args(|args| args.with_if_none_match(v));
         self
     }
+
+    /// Set the known total size of the file.
+    ///
+    /// Reading with a suffix range needs the total size of the file,
+    /// which will be taken from this hint instead of an extra stat.
+    pub fn size_hint(mut self, size: u64) -> Self {
+        self.0 = self.0.map_args(|args| args.with_size_hint(size));
+        self
+    }
 }
 
 impl Future for FutureRead {
@@ -367,6 +379,53 @@ impl Future for FutureRead {
     }
 }
 
+/// Future that generated by [`Operator::read_ranges_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureReadRanges(pub(crate) OperatorFuture<ReadRangesArgs, Vec<Bytes>>);
+
+/// The args of [`FutureReadRanges`]: read op, ranges, gap threshold and
+/// the max concurrent requests.
+pub(crate) type ReadRangesArgs = (OpRead, Vec<Range<u64>>, u64, usize);
+
+impl FutureReadRanges {
+    /// Set the gap threshold for this operation.
+    ///
+    /// Ranges whose gap is not larger than the threshold will be merged
+    /// into one request, default to 1 MiB. Set it to 0 to only merge
+    /// overlapping or adjacent ranges.
+    pub fn gap(mut self, gap: u64) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, ranges, _, limit)| (args, ranges, gap, limit));
+        self
+    }
+
+    /// Set the If-Match for this operation.
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, ranges, gap, limit)| (args.with_if_match(v), ranges, gap, limit));
+        self
+    }
+
+    /// Set the If-None-Match for this operation.
+    pub fn if_none_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|(args, ranges, gap, limit)| {
+            (args.with_if_none_match(v), ranges, gap, limit)
+        });
+        self
+    }
+}
+
+impl Future for FutureReadRanges {
+    type Output = Result<Vec<Bytes>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
 /// Future that generated by [`Operator::reader_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
@@ -406,6 +465,42 @@ impl FutureReader {
         self.0 = self.0.map_args(|args| args.with_if_none_match(v));
         self
     }
+
+    /// Prefetch the following chunks while reading.
+    ///
+    /// The reader will fetch at most `window` chunks of `chunk_size`
+    /// concurrently ahead of the current position, which could improve
+    /// the throughput of sequential read on high-latency services a lot.
+    ///
+    /// Up to `chunk_size * window` bytes will be buffered in memory.
+    pub fn prefetch(mut self, chunk_size: usize, window: usize) -> Self {
+        self.0 = self
+            .0
+            .map_args(|args| args.with_prefetch(chunk_size, window));
+        self
+    }
+
+    /// Create the reader lazily.
+    ///
+    /// The read request will be sent on the first read instead of while
+    /// creating the reader, and seeks before the first read will be merged
+    /// into it. This saves requests for callers that open-then-seek like
+    /// format readers, but errors like `NotFound` will be returned while
+    /// reading.
+    pub fn lazy(mut self) -> Self {
+        self.0 = self.0.map_args(|args| args.with_lazy(true));
+        self
+    }
+
+    /// Set the known total size of the file.
+    ///
+    /// Readers that need the total size (like prefetch or suffix range)
+    /// will use this hint instead of sending an extra stat, which saves
+    /// one round trip for callers that already have the metadata.
+    pub fn size_hint(mut self, size: u64) -> Self {
+        self.0 = self.0.map_args(|args| args.with_size_hint(size));
+        self
+    }
 }
 
 impl Future for FutureReader {
@@ -456,6 +551,34 @@ impl FutureWrite {
             .map_args(|(args, bs)| (args.with_cache_control(v), bs));
         self
     }
+
+    /// Set the storage class of option
+    pub fn storage_class(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_storage_class(v), bs));
+        self
+    }
+
+    /// Set the acl of option
+    pub fn acl(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_acl(v), bs));
+        self
+    }
+
+    /// Set the permission of option
+    pub fn permission(mut self, v: u32) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_permission(v), bs));
+        self
+    }
+
+    /// Set the user metadata of option
+    pub fn user_metadata(mut self, v: HashMap<String, String>) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_user_metadata(v), bs));
+        self
+    }
 }
 
 impl Future for FutureWrite {
@@ -498,6 +621,30 @@ impl FutureWriter {
         self.0 = self.0.map_args(|args| args.with_cache_control(v));
         self
     }
+
+    /// Set the storage class of option
+    pub fn storage_class(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_storage_class(v));
+        self
+    }
+
+    /// Set the acl of option
+    pub fn acl(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_acl(v));
+        self
+    }
+
+    /// Set the permission of option
+    pub fn permission(mut self, v: u32) -> Self {
+        self.0 = self.0.map_args(|args| args.with_permission(v));
+        self
+    }
+
+    /// Set the user metadata of option
+    pub fn user_metadata(mut self, v: HashMap<String, String>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_user_metadata(v));
+        self
+    }
 }
 
 impl Future for FutureWriter {
@@ -508,6 +655,126 @@ impl Future for FutureWriter {
     }
 }
 
+/// Future that generated by [`Operator::copy_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureCopy(pub(crate) OperatorFuture<(OpCopy, String), ()>);
+
+impl FutureCopy {
+    /// Set the content type of the destination file.
+    pub fn content_type(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_content_type(v), to));
+        self
+    }
+
+    /// Set the user metadata of the destination file.
+    pub fn user_metadata(mut self, v: HashMap<String, String>) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_user_metadata(v), to));
+        self
+    }
+
+    /// Set the tags of the destination file.
+    pub fn tags(mut self, v: HashMap<String, String>) -> Self {
+        self.0 = self.0.map_args(|(args, to)| (args.with_tags(v), to));
+        self
+    }
+}
+
+impl Future for FutureCopy {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
+/// Future that generated by [`Operator::delete_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureDelete(pub(crate) OperatorFuture<OpDelete, ()>);
+
+impl FutureDelete {
+    /// Set the If-Match for this operation.
+    ///
+    /// The file will only be deleted if its etag matches.
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_match(v));
+        self
+    }
+
+    /// Set the version of the file to delete.
+    pub fn version(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_version(v));
+        self
+    }
+}
+
+impl Future for FutureDelete {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
+/// Future that generated by [`Operator::restore_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureRestore(pub(crate) OperatorFuture<OpRestore, ()>);
+
+impl FutureRestore {
+    /// Set the days that the restored copy will be kept.
+    pub fn days(mut self, v: u32) -> Self {
+        self.0 = self.0.map_args(|args| args.with_days(v));
+        self
+    }
+
+    /// Set the tier of this restore.
+    pub fn tier(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_tier(v));
+        self
+    }
+}
+
+impl Future for FutureRestore {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
+/// Future that generated by [`Operator::select_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureSelect(pub(crate) OperatorFuture<OpSelect, Reader>);
+
+impl FutureSelect {
+    /// Set the format of the file to select from.
+    pub fn input_format(mut self, v: SelectFormat) -> Self {
+        self.0 = self.0.map_args(|args| args.with_input_format(v));
+        self
+    }
+
+    /// Set the format of the returning records.
+    pub fn output_format(mut self, v: SelectFormat) -> Self {
+        self.0 = self.0.map_args(|args| args.with_output_format(v));
+        self
+    }
+}
+
+impl Future for FutureSelect {
+    type Output = Result<Reader>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
 /// Future that generated by [`Operator::list_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
@@ -531,6 +798,18 @@ impl FutureList {
         self.0 = self.0.map_args(|args| args.with_delimiter(v));
         self
     }
+
+    /// Scan sub-prefixes concurrently with at most `v` requests in flight.
+    ///
+    /// This only takes effect while scanning with an empty delimiter and
+    /// without `start_after`. The top level of given path will be listed
+    /// first, and then every dir in it will be scanned concurrently.
+    ///
+    /// There is no guarantee about the order of returned entries.
+    pub fn concurrent(mut self, v: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_concurrent(v));
+        self
+    }
 }
 
 impl Future for FutureList {
@@ -540,3 +819,69 @@ impl Future for FutureList {
         self.0.poll_unpin(cx)
     }
 }
+
+/// Future that generated by [`Operator::watch_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureWatch(pub(crate) OperatorFuture<Duration, Watcher>);
+
+impl FutureWatch {
+    /// Change the interval between two scans, the default interval is
+    /// 60 seconds.
+    pub fn interval(mut self, v: Duration) -> Self {
+        self.0 = self.0.map_args(|_| v);
+        self
+    }
+}
+
+impl Future for FutureWatch {
+    type Output = Result<Watcher>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
+/// Future that generated by [`Operator::cleanup_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureCleanup(pub(crate) OperatorFuture<CleanupArgs, CleanupReport>);
+
+impl FutureCleanup {
+    /// Only report files that match the rules without deleting them.
+    pub fn dry_run(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|mut args| {
+            args.dry_run = v;
+            args
+        });
+        self
+    }
+
+    /// Set the max number of files that will be checked and deleted
+    /// concurrently, the default value is `1`.
+    pub fn concurrent(mut self, v: usize) -> Self {
+        self.0 = self.0.map_args(|mut args| {
+            args.concurrent = v;
+            args
+        });
+        self
+    }
+
+    /// Set the callback that will be called with the current report
+    /// every time a file has been processed.
+    pub fn progress(mut self, f: impl Fn(&CleanupReport) + Send + Sync + 'static) -> Self {
+        self.0 = self.0.map_args(|mut args| {
+            args.progress = Some(Arc::new(f));
+            args
+        });
+        self
+    }
+}
+
+impl Future for FutureCleanup {
+    type Output = Result<CleanupReport>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
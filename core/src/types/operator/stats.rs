@@ -0,0 +1,312 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use crate::raw::*;
+use crate::*;
+
+/// Snapshot of statistics maintained by operator, users can use this to
+/// surface storage health without installing a metrics layer.
+///
+/// Statistics are shared by all clones of the operator and its blocking
+/// operator.
+///
+/// # Notes
+///
+/// Statistics are collected below layers added via [`Operator::layer`],
+/// so every attempt of a retried operation will be counted.
+#[derive(Debug, Clone, Default)]
+pub struct OperatorStats {
+    operations: HashMap<Operation, u64>,
+    errors: HashMap<ErrorKind, u64>,
+    bytes_read: u64,
+    bytes_written: u64,
+    in_flight: u64,
+}
+
+impl OperatorStats {
+    /// Total count of operations that have been started.
+    pub fn operations(&self) -> u64 {
+        self.operations.values().sum()
+    }
+
+    /// Count of given operation that have been started.
+    pub fn operations_of(&self, op: Operation) -> u64 {
+        self.operations.get(&op).copied().unwrap_or_default()
+    }
+
+    /// Total count of errors returned by operations, readers, writers
+    /// and pagers.
+    pub fn errors(&self) -> u64 {
+        self.errors.values().sum()
+    }
+
+    /// Count of errors with given kind.
+    pub fn errors_of(&self, kind: ErrorKind) -> u64 {
+        self.errors.get(&kind).copied().unwrap_or_default()
+    }
+
+    /// Total bytes that have been read.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Total bytes that have been written or appended.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Count of operations that are still in progress.
+    ///
+    /// Readers, writers and pagers returned by finished operations are not
+    /// counted.
+    pub fn in_flight(&self) -> u64 {
+        self.in_flight
+    }
+}
+
+/// All operations, ordered by [`operation_index`].
+const OPERATIONS: [Operation; 26] = [
+    Operation::Info,
+    Operation::CreateDir,
+    Operation::Read,
+    Operation::Write,
+    Operation::Append,
+    Operation::Copy,
+    Operation::Rename,
+    Operation::Stat,
+    Operation::Delete,
+    Operation::List,
+    Operation::Batch,
+    Operation::Presign,
+    Operation::Restore,
+    Operation::Select,
+    Operation::WriteAt,
+    Operation::Truncate,
+    Operation::BlockingCreateDir,
+    Operation::BlockingRead,
+    Operation::BlockingWrite,
+    Operation::BlockingCopy,
+    Operation::BlockingRename,
+    Operation::BlockingStat,
+    Operation::BlockingDelete,
+    Operation::BlockingList,
+    Operation::BlockingWriteAt,
+    Operation::BlockingTruncate,
+];
+
+/// All error kinds, ordered by [`error_kind_index`].
+const ERROR_KINDS: [ErrorKind; 13] = [
+    ErrorKind::Unexpected,
+    ErrorKind::Unsupported,
+    ErrorKind::ConfigInvalid,
+    ErrorKind::NotFound,
+    ErrorKind::PermissionDenied,
+    ErrorKind::IsADirectory,
+    ErrorKind::NotADirectory,
+    ErrorKind::AlreadyExists,
+    ErrorKind::RateLimited,
+    ErrorKind::IsSameFile,
+    ErrorKind::ConditionNotMatch,
+    ErrorKind::ContentTruncated,
+    ErrorKind::ContentIncomplete,
+];
+
+/// Index of operation in the counters.
+///
+/// The match is exhaustive, so adding a new operation fails to compile
+/// until it's given an index here and appended to [`OPERATIONS`].
+const fn operation_index(op: Operation) -> usize {
+    match op {
+        Operation::Info => 0,
+        Operation::CreateDir => 1,
+        Operation::Read => 2,
+        Operation::Write => 3,
+        Operation::Append => 4,
+        Operation::Copy => 5,
+        Operation::Rename => 6,
+        Operation::Stat => 7,
+        Operation::Delete => 8,
+        Operation::List => 9,
+        Operation::Batch => 10,
+        Operation::Presign => 11,
+        Operation::Restore => 12,
+        Operation::Select => 13,
+        Operation::WriteAt => 14,
+        Operation::Truncate => 15,
+        Operation::BlockingCreateDir => 16,
+        Operation::BlockingRead => 17,
+        Operation::BlockingWrite => 18,
+        Operation::BlockingCopy => 19,
+        Operation::BlockingRename => 20,
+        Operation::BlockingStat => 21,
+        Operation::BlockingDelete => 22,
+        Operation::BlockingList => 23,
+        Operation::BlockingWriteAt => 24,
+        Operation::BlockingTruncate => 25,
+    }
+}
+
+/// Index of error kind in the counters.
+///
+/// The match is exhaustive, so adding a new error kind fails to compile
+/// until it's given an index here and appended to [`ERROR_KINDS`].
+const fn error_kind_index(kind: ErrorKind) -> usize {
+    match kind {
+        ErrorKind::Unexpected => 0,
+        ErrorKind::Unsupported => 1,
+        ErrorKind::ConfigInvalid => 2,
+        ErrorKind::NotFound => 3,
+        ErrorKind::PermissionDenied => 4,
+        ErrorKind::IsADirectory => 5,
+        ErrorKind::NotADirectory => 6,
+        ErrorKind::AlreadyExists => 7,
+        ErrorKind::RateLimited => 8,
+        ErrorKind::IsSameFile => 9,
+        ErrorKind::ConditionNotMatch => 10,
+        ErrorKind::ContentTruncated => 11,
+        ErrorKind::ContentIncomplete => 12,
+    }
+}
+
+// Make sure every entry of the tables lives at its own index.
+const _: () = {
+    let mut i = 0;
+    while i < OPERATIONS.len() {
+        assert!(operation_index(OPERATIONS[i]) == i);
+        i += 1;
+    }
+    let mut i = 0;
+    while i < ERROR_KINDS.len() {
+        assert!(error_kind_index(ERROR_KINDS[i]) == i);
+        i += 1;
+    }
+};
+
+/// StatsCounters is the live counters behind [`OperatorStats`].
+///
+/// All counters are atomics indexed by operation or error kind, so that
+/// counting never takes a lock.
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+    operations: [AtomicU64; OPERATIONS.len()],
+    errors: [AtomicU64; ERROR_KINDS.len()],
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    in_flight: AtomicU64,
+}
+
+impl StatsCounters {
+    /// Start an operation, it will be counted as in flight until the
+    /// returning guard dropped.
+    pub(crate) fn start(&self, op: Operation) -> InFlight<'_> {
+        // Use `get` so that an operation missing from the table is skipped
+        // instead of panicking.
+        if let Some(v) = self.operations.get(operation_index(op)) {
+            v.fetch_add(1, Ordering::Relaxed);
+        }
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlight(self)
+    }
+
+    /// Record the error of given result if any.
+    pub(crate) fn record<T>(&self, res: Result<T>) -> Result<T> {
+        if let Err(err) = &res {
+            if let Some(v) = self.errors.get(error_kind_index(err.kind())) {
+                v.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        res
+    }
+
+    pub(crate) fn add_bytes_read(&self, n: u64) {
+        self.bytes_read.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_bytes_written(&self, n: u64) {
+        self.bytes_written.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> OperatorStats {
+        OperatorStats {
+            operations: collect_counters(&OPERATIONS, &self.operations),
+            errors: collect_counters(&ERROR_KINDS, &self.errors),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// InFlight will decrease in flight operations while dropped, which
+/// makes sure cancelled operations are handled too.
+pub(crate) struct InFlight<'a>(&'a StatsCounters);
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Collect non-zero counters into a map keyed by given keys.
+fn collect_counters<K: Copy + Eq + std::hash::Hash>(
+    keys: &[K],
+    counters: &[AtomicU64],
+) -> HashMap<K, u64> {
+    keys.iter()
+        .zip(counters)
+        .map(|(k, v)| (*k, v.load(Ordering::Relaxed)))
+        .filter(|(_, v)| *v > 0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_index() {
+        for (idx, op) in OPERATIONS.iter().enumerate() {
+            assert_eq!(operation_index(*op), idx, "{op}");
+        }
+        for (idx, kind) in ERROR_KINDS.iter().enumerate() {
+            assert_eq!(error_kind_index(*kind), idx, "{kind}");
+        }
+    }
+
+    #[test]
+    fn test_counters_snapshot() {
+        let counters = StatsCounters::default();
+        {
+            let _guard = counters.start(Operation::Read);
+            assert_eq!(counters.snapshot().in_flight(), 1);
+        }
+        let _ = counters.start(Operation::Read);
+        let _ = counters.record::<()>(Err(Error::new(ErrorKind::NotFound, "not found")));
+
+        let stats = counters.snapshot();
+        assert_eq!(stats.operations_of(Operation::Read), 2);
+        assert_eq!(stats.operations_of(Operation::Write), 0);
+        assert_eq!(stats.operations(), 2);
+        assert_eq!(stats.errors_of(ErrorKind::NotFound), 1);
+        assert_eq!(stats.errors(), 1);
+        assert_eq!(stats.in_flight(), 0);
+    }
+}
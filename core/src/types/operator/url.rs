@@ -0,0 +1,283 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use percent_encoding::percent_decode_str;
+
+use crate::*;
+
+/// # Operator build from url
+impl Operator {
+    /// Parse a url into scheme, builder config and the path relative to
+    /// the built operator.
+    ///
+    /// The host part of the url will be mapped to the config of services,
+    /// for example:
+    ///
+    /// - `s3://bucket/path/to/file`: `bucket` of s3, also works for `gs`, `gcs`, `oss`, `cos`, `obs`, `b2`, `tos`, `kodo` and `wasabi`.
+    /// - `azblob://container/path/to/file`: `container` of azblob, `az` is an alias.
+    /// - `azdfs://filesystem/path/to/file`: `filesystem` of azdfs, `abfs` is an alias.
+    /// - `azfile://share/path/to/file`: `share_name` of azfile.
+    /// - `hdfs://namenode:port/path/to/file`: `name_node` of hdfs.
+    /// - `http://host/path/to/file`: `endpoint` of http, `https` also works.
+    /// - `webhdfs://host:port/path/to/file`: `endpoint` of webhdfs.
+    /// - `fs:///path/to/file`: fs rooted at `/`, `file` is an alias.
+    /// - `memory:///path/to/file`: memory without any config.
+    ///
+    /// Query pairs will be added into the config, so that users can
+    /// specify other options like `s3://bucket/file?region=us-east-1`.
+    /// `root` is `/` by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    /// use opendal::Scheme;
+    ///
+    /// # fn test() -> Result<()> {
+    /// let (scheme, cfg, path) = Operator::parse_url("gs://bucket/a/b.parquet")?;
+    /// assert_eq!(scheme, Scheme::Gcs);
+    /// assert_eq!(cfg["bucket"], "bucket");
+    /// assert_eq!(path, "a/b.parquet");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_url(url: &str) -> Result<(Scheme, HashMap<String, String>, String)> {
+        let (scheme, rest) = url.split_once("://").ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "url must contain a scheme")
+                .with_operation("Operator::parse_url")
+                .with_context("url", url)
+        })?;
+
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+
+        let mut map = HashMap::new();
+        for pair in query.unwrap_or_default().split('&') {
+            if pair.is_empty() {
+                continue;
+            }
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            map.insert(percent_decode(k), percent_decode(v));
+        }
+
+        let raw_scheme = scheme.to_lowercase();
+        let scheme = match raw_scheme.as_str() {
+            "gs" => Scheme::Gcs,
+            "s3a" => Scheme::S3,
+            "az" => Scheme::Azblob,
+            "abfs" => Scheme::Azdfs,
+            "file" => Scheme::Fs,
+            v => Scheme::from_str(v)?,
+        };
+
+        let host_key = match scheme {
+            Scheme::S3
+            | Scheme::Gcs
+            | Scheme::Oss
+            | Scheme::Cos
+            | Scheme::Obs
+            | Scheme::B2
+            | Scheme::Tos
+            | Scheme::Kodo
+            | Scheme::Wasabi => Some("bucket"),
+            Scheme::Azblob => Some("container"),
+            Scheme::Azdfs => Some("filesystem"),
+            Scheme::Azfile => Some("share_name"),
+            Scheme::Hdfs | Scheme::Http | Scheme::Webhdfs => None,
+            Scheme::Fs | Scheme::Memory => {
+                if !host.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "url of this scheme must not contain a host",
+                    )
+                    .with_operation("Operator::parse_url")
+                    .with_context("url", url));
+                }
+                None
+            }
+            v => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "scheme is not supported to build from url",
+                )
+                .with_operation("Operator::parse_url")
+                .with_context("scheme", v))
+            }
+        };
+
+        if host.is_empty() && !matches!(scheme, Scheme::Fs | Scheme::Memory) {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "url must contain a host")
+                    .with_operation("Operator::parse_url")
+                    .with_context("url", url),
+            );
+        }
+
+        match scheme {
+            Scheme::Hdfs => {
+                map.insert("name_node".to_string(), format!("hdfs://{host}"));
+            }
+            Scheme::Http => {
+                map.insert("endpoint".to_string(), format!("{raw_scheme}://{host}"));
+            }
+            Scheme::Webhdfs => {
+                map.insert("endpoint".to_string(), format!("http://{host}"));
+            }
+            _ => {}
+        }
+        if let Some(key) = host_key {
+            map.insert(key.to_string(), percent_decode(host));
+        }
+        map.entry("root".to_string())
+            .or_insert_with(|| "/".to_string());
+
+        let path = percent_decode(path);
+        let path = if path.is_empty() {
+            "/".to_string()
+        } else {
+            path
+        };
+
+        Ok((scheme, map, path))
+    }
+
+    /// Build an operator and the path of the object from given url.
+    ///
+    /// Refer to [`Operator::parse_url`] for the supported urls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test() -> Result<()> {
+    /// let (op, path) = Operator::object_from_url("memory:///path/to/file")?;
+    /// op.write(&path, "Hello, World!").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn object_from_url(url: &str) -> Result<(Operator, String)> {
+        let (scheme, map, path) = Self::parse_url(url)?;
+        let op = Self::via_map(scheme, map)?;
+
+        Ok((op, path))
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    percent_decode_str(s).decode_utf8_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url() {
+        let cases = vec![
+            (
+                "s3://bucket/path/to/file",
+                Scheme::S3,
+                vec![("bucket", "bucket")],
+                "path/to/file",
+            ),
+            (
+                "gs://bucket/a/b.parquet",
+                Scheme::Gcs,
+                vec![("bucket", "bucket")],
+                "a/b.parquet",
+            ),
+            (
+                "azblob://container/key",
+                Scheme::Azblob,
+                vec![("container", "container")],
+                "key",
+            ),
+            (
+                "abfs://fs/dir/",
+                Scheme::Azdfs,
+                vec![("filesystem", "fs")],
+                "dir/",
+            ),
+            (
+                "hdfs://127.0.0.1:9000/tmp/file",
+                Scheme::Hdfs,
+                vec![("name_node", "hdfs://127.0.0.1:9000")],
+                "tmp/file",
+            ),
+            (
+                "https://example.com/a%20b",
+                Scheme::Http,
+                vec![("endpoint", "https://example.com")],
+                "a b",
+            ),
+            ("file:///tmp/file", Scheme::Fs, vec![], "tmp/file"),
+            ("memory://", Scheme::Memory, vec![], "/"),
+            (
+                "s3://bucket/file?region=us-east-1&root=/prefix",
+                Scheme::S3,
+                vec![("region", "us-east-1"), ("root", "/prefix")],
+                "file",
+            ),
+        ];
+
+        for (url, scheme, cfg, path) in cases {
+            let (actual_scheme, actual_cfg, actual_path) =
+                Operator::parse_url(url).expect("parse must succeed");
+            assert_eq!(actual_scheme, scheme, "{url}");
+            for (k, v) in cfg {
+                assert_eq!(actual_cfg[k], v, "{url}");
+            }
+            assert_eq!(actual_path, path, "{url}");
+        }
+    }
+
+    #[test]
+    fn test_parse_url_invalid() {
+        let cases = vec![
+            ("bucket/path", ErrorKind::ConfigInvalid),
+            ("s3:///path", ErrorKind::ConfigInvalid),
+            ("file://host/path", ErrorKind::ConfigInvalid),
+            ("redis://host/path", ErrorKind::Unsupported),
+            ("unknown://host/path", ErrorKind::Unsupported),
+        ];
+
+        for (url, kind) in cases {
+            let err = Operator::parse_url(url).expect_err("parse must fail");
+            assert_eq!(err.kind(), kind, "{url}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_object_from_url() {
+        let (op, path) = Operator::object_from_url("memory:///path/to/file").unwrap();
+        assert_eq!(op.info().scheme(), Scheme::Memory);
+        assert_eq!(path, "path/to/file");
+
+        op.write(&path, "Hello, World!").await.unwrap();
+        assert_eq!(op.read(&path).await.unwrap(), b"Hello, World!");
+    }
+}
@@ -15,8 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp;
 use std::io;
 use std::pin::Pin;
+#[cfg(feature = "tokio")]
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
@@ -36,10 +38,14 @@ use crate::*;
 ///
 /// Reader implements the following APIs:
 ///
-/// - `AsyncRead`
-/// - `AsyncSeek`
+/// - `AsyncRead` and `AsyncSeek` of `futures`
+/// - `AsyncRead` and `AsyncSeek` of `tokio::io` (with feature `tokio` enabled)
 /// - `Stream<Item = <io::Result<Bytes>>>`
 ///
+/// So `Reader` can be passed to tokio based code like `tokio::io::copy`
+/// or `FramedRead` directly without any compat wrappers. Use
+/// `Reader::into_tokio_read` or `From` to convert it into tokio types.
+///
 /// For reading data, we can use `AsyncRead` and `Stream`. The mainly
 /// different is where the `copy` happens.
 ///
@@ -55,6 +61,7 @@ use crate::*;
 /// stream based (like services s3, azure which based on HTTP).
 pub struct Reader {
     inner: oio::Reader,
+    #[cfg(feature = "tokio")]
     seek_state: SeekState,
 }
 
@@ -67,13 +74,112 @@ impl Reader {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) async fn create_dir(acc: FusedAccessor, path: &str, op: OpRead) -> Result<Self> {
-        let (_, r) = acc.read(path, op).await?;
+        if let Some((chunk_size, window)) = op.prefetch() {
+            return Self::create_prefetch(acc, path, op, chunk_size, window).await;
+        }
+
+        let lazy = op.lazy();
+        let mut r = oio::into_reader::lazy(acc, path, op);
+        if !lazy {
+            r = r.open().await?;
+        }
+
+        Ok(Reader {
+            inner: Box::new(r),
+            #[cfg(feature = "tokio")]
+            seek_state: SeekState::Init,
+        })
+    }
+
+    /// Create a new reader which prefetches the following chunks while
+    /// reading.
+    async fn create_prefetch(
+        acc: FusedAccessor,
+        path: &str,
+        op: OpRead,
+        chunk_size: usize,
+        window: usize,
+    ) -> Result<Self> {
+        let total_size = match op.size_hint() {
+            Some(size) => size,
+            None => {
+                // Stat with the same conditions as read so that we won't
+                // prefetch a different version of the object.
+                let mut args = OpStat::new();
+                if let Some(v) = op.if_match() {
+                    args = args.with_if_match(v);
+                }
+                if let Some(v) = op.if_none_match() {
+                    args = args.with_if_none_match(v);
+                }
+                acc.stat(path, args).await?.into_metadata().content_length()
+            }
+        };
+
+        let range = op.range();
+        let (offset, size) = match (range.offset(), range.size()) {
+            (Some(offset), size) => {
+                let offset = cmp::min(offset, total_size);
+                let remaining = total_size - offset;
+                (offset, size.map_or(remaining, |v| cmp::min(v, remaining)))
+            }
+            (None, Some(size)) => {
+                let size = cmp::min(size, total_size);
+                (total_size - size, size)
+            }
+            (None, None) => (0, total_size),
+        };
+
+        let r = oio::into_reader::by_prefetch(acc, path, op, offset, size, chunk_size, window);
+
+        Ok(Reader {
+            inner: Box::new(r),
+            #[cfg(feature = "tokio")]
+            seek_state: SeekState::Init,
+        })
+    }
+
+    /// Create a new reader which only returns the records matched by the
+    /// select expression.
+    pub(crate) async fn create_select(
+        acc: FusedAccessor,
+        path: &str,
+        op: OpSelect,
+    ) -> Result<Self> {
+        let (_, r) = acc.select(path, op).await?;
 
         Ok(Reader {
             inner: r,
+            #[cfg(feature = "tokio")]
             seek_state: SeekState::Init,
         })
     }
+
+    /// Convert into a reader that implements `tokio::io::AsyncRead` and
+    /// `tokio::io::AsyncSeek`.
+    ///
+    /// `Reader` implements them already, this conversion is useful when
+    /// the reader is stored or passed as tokio types only.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut r = op.reader("path/to/file").await?.into_tokio_read();
+    /// let mut w = tokio::io::sink();
+    /// tokio::io::copy(&mut r, &mut w).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn into_tokio_read(
+        self,
+    ) -> impl tokio::io::AsyncRead + tokio::io::AsyncSeek + Unpin + Send + Sync {
+        self
+    }
 }
 
 impl oio::Read for Reader {
@@ -110,6 +216,7 @@ impl AsyncSeek for Reader {
     }
 }
 
+#[cfg(feature = "tokio")]
 impl tokio::io::AsyncRead for Reader {
     fn poll_read(
         mut self: Pin<&mut Self>,
@@ -126,6 +233,14 @@ impl tokio::io::AsyncRead for Reader {
     }
 }
 
+#[cfg(feature = "tokio")]
+impl From<Reader> for Box<dyn tokio::io::AsyncRead + Unpin + Send + Sync> {
+    fn from(r: Reader) -> Self {
+        Box::new(r)
+    }
+}
+
+#[cfg(feature = "tokio")]
 impl tokio::io::AsyncSeek for Reader {
     fn start_seek(self: Pin<&mut Self>, pos: io::SeekFrom) -> io::Result<()> {
         let this = self.get_mut();
@@ -158,6 +273,7 @@ impl tokio::io::AsyncSeek for Reader {
     }
 }
 
+#[cfg(feature = "tokio")]
 #[derive(Debug, Clone, Copy)]
 /// SeekState is used to track the tokio seek state of Reader.
 enum SeekState {
@@ -179,6 +295,9 @@ impl Stream for Reader {
 
 /// BlockingReader is designed to read data from given path in an blocking
 /// manner.
+///
+/// BlockingReader implements `std::io::Read` and `std::io::Seek`, so it can
+/// be passed to crates like `zip` or `csv` directly.
 pub struct BlockingReader {
     pub(crate) inner: oio::BlockingReader,
 }
@@ -235,14 +354,25 @@ impl oio::BlockingRead for BlockingReader {
 impl io::Read for BlockingReader {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.read(buf)
+        oio::BlockingRead::read(&mut self.inner, buf).map_err(format_std_io_error)
     }
 }
 
 impl io::Seek for BlockingReader {
     #[inline]
     fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
-        self.inner.seek(pos)
+        oio::BlockingRead::seek(&mut self.inner, pos).map_err(format_std_io_error)
+    }
+}
+
+/// Only temporary errors will be converted into `Interrupted`, which will
+/// be retried by helpers like `read_exact` and `read_to_end`. Others must be
+/// returned as is, otherwise these helpers will retry forever.
+fn format_std_io_error(err: Error) -> io::Error {
+    if err.is_temporary() {
+        io::Error::new(io::ErrorKind::Interrupted, err)
+    } else {
+        err.into()
     }
 }
 
@@ -264,7 +394,9 @@ mod tests {
     use rand::RngCore;
     use tokio::io::AsyncReadExt;
     use tokio::io::AsyncSeekExt;
+    use tokio::io::AsyncWriteExt;
 
+    use super::*;
     use crate::services;
     use crate::Operator;
 
@@ -297,6 +429,92 @@ mod tests {
         assert_eq!(buf, content);
     }
 
+    #[test]
+    fn test_blocking_reader_std_io() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let op = op.blocking();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone()).expect("write must succeed");
+
+        let mut reader = op.reader(path).unwrap();
+        let mut buf = Vec::new();
+        io::Read::read_to_end(&mut reader, &mut buf).expect("read to end must succeed");
+        assert_eq!(buf, content);
+
+        let n = io::Seek::seek(&mut reader, io::SeekFrom::End(-10)).unwrap();
+        assert_eq!(n, content.len() as u64 - 10);
+        let mut buf = Vec::new();
+        io::Read::read_to_end(&mut reader, &mut buf).expect("read to end must succeed");
+        assert_eq!(buf, content[content.len() - 10..]);
+    }
+
+    #[test]
+    fn test_format_std_io_error() {
+        let err = format_std_io_error(Error::new(ErrorKind::NotFound, "not found"));
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        let err = format_std_io_error(Error::new(ErrorKind::Unexpected, "reset").set_temporary());
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    }
+
+    #[tokio::test]
+    async fn test_reader_tokio_copy() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let mut reader = op.reader(path).await.unwrap();
+        let mut writer = op.writer("test_file_copied").await.unwrap();
+        let n = tokio::io::copy(&mut reader, &mut writer)
+            .await
+            .expect("copy must succeed");
+        writer.shutdown().await.expect("shutdown must succeed");
+        assert_eq!(n, content.len() as u64);
+
+        let buf = op.read("test_file_copied").await.unwrap();
+        assert_eq!(buf, content);
+    }
+
+    #[tokio::test]
+    async fn test_reader_into_tokio() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let mut reader = op.reader(path).await.unwrap().into_tokio_read();
+        let mut writer = op
+            .writer("test_file_copied")
+            .await
+            .unwrap()
+            .into_tokio_write();
+        let n = tokio::io::copy(&mut reader, &mut writer)
+            .await
+            .expect("copy must succeed");
+        writer.shutdown().await.expect("shutdown must succeed");
+        assert_eq!(n, content.len() as u64);
+        assert_eq!(op.read("test_file_copied").await.unwrap(), content);
+
+        let mut reader: Box<dyn tokio::io::AsyncRead + Unpin + Send + Sync> =
+            op.reader(path).await.unwrap().into();
+        let mut writer: Box<dyn tokio::io::AsyncWrite + Unpin + Send + Sync> =
+            op.writer("test_file_boxed").await.unwrap().into();
+        tokio::io::copy(&mut reader, &mut writer)
+            .await
+            .expect("copy must succeed");
+        writer.shutdown().await.expect("shutdown must succeed");
+        assert_eq!(op.read("test_file_boxed").await.unwrap(), content);
+    }
+
     #[tokio::test]
     async fn test_reader_async_seek() {
         let op = Operator::new(services::Memory::default()).unwrap().finish();
@@ -325,4 +543,50 @@ mod tests {
             .expect("read to end must succeed");
         assert_eq!(buf, content);
     }
+
+    #[tokio::test]
+    async fn test_reader_prefetch() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let mut reader = op.reader_with(path).prefetch(256 * 1024, 4).await.unwrap();
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .expect("read to end must succeed");
+        assert_eq!(buf, content);
+
+        let offset = content.len() as u64 / 2;
+        let n = reader
+            .seek(tokio::io::SeekFrom::Start(offset))
+            .await
+            .unwrap();
+        assert_eq!(n, offset, "seek position must be {offset}");
+
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .expect("read to end must succeed");
+        assert_eq!(buf, content[offset as usize..]);
+
+        let mut reader = op
+            .reader_with(path)
+            .range(1..)
+            .prefetch(1024, 2)
+            .await
+            .unwrap();
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .expect("read to end must succeed");
+        assert_eq!(buf, content[1..]);
+    }
 }
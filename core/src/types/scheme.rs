@@ -19,6 +19,12 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::str::FromStr;
 
+use serde::de;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
 use crate::Error;
 
 /// Services that OpenDAL supports
@@ -35,10 +41,30 @@ pub enum Scheme {
     Azblob,
     /// [azdfs][crate::services::Azdfs]: Azure Data Lake Storage Gen2.
     Azdfs,
+    /// [azfile][crate::services::Azfile]: Azure Files services.
+    Azfile,
+    /// [b2][crate::services::B2]: Backblaze B2 services.
+    B2,
+    /// [box][crate::services::BoxStorage]: Box services.
+    Box,
+    /// [cassandra][crate::services::Cassandra]: Cassandra services
+    Cassandra,
+    /// [cloudflare_kv][crate::services::CloudflareKv]: Cloudflare KV services.
+    CloudflareKv,
     /// [cos][crate::services::Cos]: Tencent Cloud Object Storage services.
     Cos,
+    /// [d1][crate::services::D1]: Cloudflare D1 services.
+    D1,
     /// [dashmap][crate::services::Dashmap]: dashmap backend support.
     Dashmap,
+    /// [dynamodb][crate::services::Dynamodb]: AWS DynamoDB services.
+    Dynamodb,
+    /// [etcd][crate::services::Etcd]: Etcd Services
+    Etcd,
+    /// [failover][crate::services::Failover]: Multi-region failover of operators.
+    Failover,
+    /// [foundationdb][crate::services::Foundationdb]: Foundationdb services.
+    Foundationdb,
     /// [fs][crate::services::Fs]: POSIX alike file system.
     Fs,
     /// [ftp][crate::services::Ftp]: FTP backend.
@@ -47,23 +73,39 @@ pub enum Scheme {
     Gcs,
     /// [ghac][crate::services::Ghac]: GitHub Action Cache services.
     Ghac,
+    /// [github][crate::services::Github]: GitHub repository contents services.
+    Github,
+    /// [gridfs][crate::services::Gridfs]: MongoDB Gridfs Services
+    Gridfs,
     /// [hdfs][crate::services::Hdfs]: Hadoop Distributed File System.
     Hdfs,
     /// [http][crate::services::Http]: HTTP backend.
     Http,
+    /// [huggingface][crate::services::Huggingface]: Hugging Face Hub services.
+    Huggingface,
 
     /// [ipmfs][crate::services::Ipfs]: IPFS HTTP Gateway
     Ipfs,
     /// [ipmfs][crate::services::Ipmfs]: IPFS mutable file system
     Ipmfs,
+    /// [kodo][crate::services::Kodo]: Qiniu Kodo services.
+    Kodo,
     /// [memcached][crate::services::Memcached]: Memcached service support.
     Memcached,
     /// [memory][crate::services::Memory]: In memory backend support.
     Memory,
+    /// [mock][crate::services::Mock]: Programmable mock backend for testing.
+    Mock,
     /// [moka][crate::services::Moka]: moka backend support.
     Moka,
+    /// [mysql][crate::services::Mysql]: Mysql services
+    Mysql,
+    /// [nfs][crate::services::Nfs]: NFS services
+    Nfs,
     /// [obs][crate::services::Obs]: Huawei Cloud OBS services.
     Obs,
+    /// [oci][crate::services::Oci]: Oracle Cloud Infrastructure Object Storage services.
+    Oci,
     /// [onedrive][crate::services::Onedrive]: Microsoft OneDrive services.
     Onedrive,
     /// [gdrive][crate::services::Gdrive]: GoogleDrive services.
@@ -72,26 +114,56 @@ pub enum Scheme {
     Dropbox,
     /// [oss][crate::services::Oss]: Aliyun Object Storage Services
     Oss,
+    /// [postgresql][crate::services::Postgresql]: Postgresql services
+    Postgresql,
+    /// [rados][crate::services::Rados]: Ceph RADOS services
+    Rados,
+    /// [redb][crate::services::Redb]: Redb Services
+    Redb,
     /// [redis][crate::services::Redis]: Redis services
     Redis,
     /// [rocksdb][crate::services::Rocksdb]: RocksDB services
     Rocksdb,
     /// [s3][crate::services::S3]: AWS S3 alike services.
     S3,
+    /// [seafile][crate::services::Seafile]: Seafile services.
+    Seafile,
+    /// [seaweedfs][crate::services::Seaweedfs]: SeaweedFS services via filer API.
+    Seaweedfs,
     /// [sftp][crate::services::Sftp]: SFTP services
     Sftp,
+    /// [shard][crate::services::Shard]: Distribute paths across multiple operators.
+    Shard,
+    /// [sim][crate::services::Sim]: Simulate latency and failures deterministically.
+    Sim,
     /// [sled][crate::services::Sled]: Sled services
     Sled,
+    /// [sqlite][crate::services::Sqlite]: Sqlite services
+    Sqlite,
     /// [Supabase][crate::services::Supabase]: Supabase storage service
     Supabase,
+    /// [tar][crate::services::Tar]: Read-only tar archive services
+    Tar,
+    /// [tikv][crate::services::Tikv]: Tikv Services
+    Tikv,
+    /// [tos][crate::services::Tos]: Volcengine TOS services.
+    Tos,
+    /// [union][crate::services::Union]: Union of multiple operators.
+    Union,
     /// [Vercel Artifacts][crate::services::VercelArtifacts]: Vercel Artifacts service, as known as Vercel Remote Caching.
     VercelArtifacts,
+    /// [vercel_blob][crate::services::VercelBlob]: Vercel Blob services.
+    VercelBlob,
     /// [wasabi][crate::services::Wasabi]: Wasabi service
     Wasabi,
     /// [webdav][crate::services::Webdav]: WebDAV support.
     Webdav,
     /// [webhdfs][crate::services::Webhdfs]: WebHDFS RESTful API Services
     Webhdfs,
+    /// [yandex_disk][crate::services::YandexDisk]: Yandex Disk services.
+    YandexDisk,
+    /// [zip][crate::services::Zip]: Read-only zip archive services
+    Zip,
     /// Custom that allow users to implement services outside of OpenDAL.
     ///
     /// # NOTE
@@ -128,29 +200,62 @@ impl FromStr for Scheme {
         match s.as_str() {
             "azblob" => Ok(Scheme::Azblob),
             "azdfs" => Ok(Scheme::Azdfs),
+            "azfile" => Ok(Scheme::Azfile),
+            "b2" => Ok(Scheme::B2),
+            "box" => Ok(Scheme::Box),
+            "cassandra" => Ok(Scheme::Cassandra),
+            "cloudflare_kv" => Ok(Scheme::CloudflareKv),
             "cos" => Ok(Scheme::Cos),
+            "d1" => Ok(Scheme::D1),
             "dashmap" => Ok(Scheme::Dashmap),
+            "dynamodb" => Ok(Scheme::Dynamodb),
+            "etcd" => Ok(Scheme::Etcd),
+            "failover" => Ok(Scheme::Failover),
+            "foundationdb" => Ok(Scheme::Foundationdb),
             "fs" => Ok(Scheme::Fs),
             "gcs" => Ok(Scheme::Gcs),
             "ghac" => Ok(Scheme::Ghac),
+            "github" => Ok(Scheme::Github),
+            "gridfs" => Ok(Scheme::Gridfs),
             "hdfs" => Ok(Scheme::Hdfs),
             "http" | "https" => Ok(Scheme::Http),
+            "huggingface" | "hf" => Ok(Scheme::Huggingface),
             "ftp" | "ftps" => Ok(Scheme::Ftp),
             "ipfs" | "ipns" => Ok(Scheme::Ipfs),
             "ipmfs" => Ok(Scheme::Ipmfs),
+            "kodo" => Ok(Scheme::Kodo),
             "memcached" => Ok(Scheme::Memcached),
             "memory" => Ok(Scheme::Memory),
+            "mock" => Ok(Scheme::Mock),
             "moka" => Ok(Scheme::Moka),
+            "mysql" => Ok(Scheme::Mysql),
+            "nfs" => Ok(Scheme::Nfs),
             "obs" => Ok(Scheme::Obs),
+            "oci" => Ok(Scheme::Oci),
+            "rados" => Ok(Scheme::Rados),
+            "redb" => Ok(Scheme::Redb),
             "redis" => Ok(Scheme::Redis),
             "rocksdb" => Ok(Scheme::Rocksdb),
             "s3" => Ok(Scheme::S3),
+            "seafile" => Ok(Scheme::Seafile),
+            "seaweedfs" => Ok(Scheme::Seaweedfs),
+            "shard" => Ok(Scheme::Shard),
+            "sim" => Ok(Scheme::Sim),
             "sled" => Ok(Scheme::Sled),
+            "sqlite" => Ok(Scheme::Sqlite),
             "supabase" => Ok(Scheme::Supabase),
+            "tar" => Ok(Scheme::Tar),
+            "tikv" => Ok(Scheme::Tikv),
+            "tos" => Ok(Scheme::Tos),
+            "union" => Ok(Scheme::Union),
             "oss" => Ok(Scheme::Oss),
+            "postgresql" => Ok(Scheme::Postgresql),
+            "vercel_blob" => Ok(Scheme::VercelBlob),
             "wasabi" => Ok(Scheme::Wasabi),
             "webdav" => Ok(Scheme::Webdav),
             "webhdfs" => Ok(Scheme::Webhdfs),
+            "yandex_disk" => Ok(Scheme::YandexDisk),
+            "zip" => Ok(Scheme::Zip),
             _ => Ok(Scheme::Custom(Box::leak(s.into_boxed_str()))),
         }
     }
@@ -161,39 +266,86 @@ impl From<Scheme> for &'static str {
         match v {
             Scheme::Azblob => "azblob",
             Scheme::Azdfs => "azdfs",
+            Scheme::Azfile => "azfile",
+            Scheme::B2 => "b2",
+            Scheme::Box => "box",
+            Scheme::Cassandra => "cassandra",
+            Scheme::CloudflareKv => "cloudflare_kv",
             Scheme::Cos => "cos",
+            Scheme::D1 => "d1",
             Scheme::Dashmap => "dashmap",
+            Scheme::Dynamodb => "dynamodb",
+            Scheme::Etcd => "etcd",
+            Scheme::Failover => "failover",
+            Scheme::Foundationdb => "foundationdb",
             Scheme::Fs => "fs",
             Scheme::Gcs => "gcs",
             Scheme::Ghac => "ghac",
+            Scheme::Github => "github",
+            Scheme::Gridfs => "gridfs",
             Scheme::Hdfs => "hdfs",
             Scheme::Http => "http",
+            Scheme::Huggingface => "huggingface",
             Scheme::Ftp => "ftp",
             Scheme::Ipfs => "ipfs",
             Scheme::Ipmfs => "ipmfs",
+            Scheme::Kodo => "kodo",
             Scheme::Memcached => "memcached",
             Scheme::Memory => "memory",
+            Scheme::Mock => "mock",
             Scheme::Moka => "moka",
+            Scheme::Mysql => "mysql",
+            Scheme::Nfs => "nfs",
             Scheme::Obs => "obs",
+            Scheme::Oci => "oci",
             Scheme::Onedrive => "onedrive",
             Scheme::Gdrive => "gdrive",
             Scheme::Dropbox => "dropbox",
+            Scheme::Rados => "rados",
+            Scheme::Redb => "redb",
             Scheme::Redis => "redis",
             Scheme::Rocksdb => "rocksdb",
             Scheme::S3 => "s3",
+            Scheme::Seafile => "seafile",
+            Scheme::Seaweedfs => "seaweedfs",
             Scheme::Sftp => "sftp",
+            Scheme::Shard => "shard",
+            Scheme::Sim => "sim",
             Scheme::Sled => "sled",
+            Scheme::Sqlite => "sqlite",
             Scheme::Supabase => "supabase",
+            Scheme::Tar => "tar",
+            Scheme::Tikv => "tikv",
+            Scheme::Tos => "tos",
+            Scheme::Union => "union",
             Scheme::VercelArtifacts => "vercel_artifacts",
+            Scheme::VercelBlob => "vercel_blob",
             Scheme::Oss => "oss",
+            Scheme::Postgresql => "postgresql",
             Scheme::Wasabi => "wasabi",
             Scheme::Webdav => "webdav",
             Scheme::Webhdfs => "webhdfs",
+            Scheme::YandexDisk => "yandex_disk",
+            Scheme::Zip => "zip",
             Scheme::Custom(v) => v,
         }
     }
 }
 
+/// Scheme is serialized as its string form like `s3`.
+impl Serialize for Scheme {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.into_static())
+    }
+}
+
+impl<'de> Deserialize<'de> for Scheme {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Scheme::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
 impl From<Scheme> for String {
     fn from(v: Scheme) -> Self {
         v.into_static().to_string()
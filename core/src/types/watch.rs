@@ -0,0 +1,253 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use futures::stream::BoxStream;
+use futures::Stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// WatchEvent is the change event returned by [`Watcher`].
+#[derive(Clone, Debug)]
+pub enum WatchEvent {
+    /// A new file has been created.
+    Created(Entry),
+    /// An existing file has been modified.
+    Modified(Entry),
+    /// An existing file has been deleted.
+    Deleted(Entry),
+}
+
+impl WatchEvent {
+    /// Get the entry of this event.
+    ///
+    /// Entries of `Created` and `Modified` events carry the metadata
+    /// used to detect the change, entries of `Deleted` events don't
+    /// have any metadata.
+    pub fn entry(&self) -> &Entry {
+        match self {
+            WatchEvent::Created(entry) => entry,
+            WatchEvent::Modified(entry) => entry,
+            WatchEvent::Deleted(entry) => entry,
+        }
+    }
+}
+
+/// Watcher is designed to watch changes of files under given path in an
+/// asynchronous manner.
+///
+/// Users can construct Watcher by `watch` or `watch_with`, and use it as
+/// `Stream<Item = Result<WatchEvent>>`. The stream never ends unless it's
+/// dropped.
+///
+/// # Notes
+///
+/// Watcher scans the path in every interval and compares the etag,
+/// content length and last modified time of files with the last scan.
+/// So changes happened between two scans will be merged, for example,
+/// a file created and deleted in the same interval will not be reported.
+///
+/// All services are polled in this way, including fs. Native notification
+/// like inotify or FSEvents is not used.
+///
+/// Every scan lists the path recursively. If the listed entries don't
+/// carry the metadata above, a `stat` will be sent for each of them, for
+/// example, watching 1000 files on fs costs 1000 `stat` in every scan.
+/// Please use a larger interval for large dirs or services that charge by
+/// request.
+///
+/// Dirs are ignored, only changes of files will be reported.
+pub struct Watcher {
+    inner: BoxStream<'static, Result<WatchEvent>>,
+}
+
+/// # Safety
+///
+/// Watcher will only be accessed by `&mut Self`
+unsafe impl Sync for Watcher {}
+
+impl Watcher {
+    /// Create a new watcher which will take the current state of given
+    /// path as the baseline.
+    pub(crate) async fn create(op: Operator, path: String, interval: Duration) -> Result<Self> {
+        let snapshot = scan_snapshot(&op, &path).await?;
+
+        let state = State {
+            op,
+            path,
+            interval,
+            snapshot,
+            pending: VecDeque::new(),
+        };
+
+        let inner = futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(event) = state.pending.pop_front() {
+                    return Some((Ok(event), state));
+                }
+
+                sleep(state.interval).await;
+
+                match scan_snapshot(&state.op, &state.path).await {
+                    Ok(snapshot) => {
+                        let old = std::mem::replace(&mut state.snapshot, snapshot);
+                        state.pending.extend(diff_snapshot(old, &state.snapshot));
+                    }
+                    Err(err) => return Some((Err(err), state)),
+                }
+            }
+        });
+
+        Ok(Self {
+            inner: inner.boxed(),
+        })
+    }
+}
+
+impl Stream for Watcher {
+    type Item = Result<WatchEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+struct State {
+    op: Operator,
+    path: String,
+    interval: Duration,
+    snapshot: BTreeMap<String, (Entry, Fingerprint)>,
+    pending: VecDeque<WatchEvent>,
+}
+
+/// Fingerprint of a file which is used to detect changes.
+#[derive(Debug, PartialEq, Eq)]
+struct Fingerprint {
+    etag: Option<String>,
+    content_length: u64,
+    last_modified: Option<DateTime<Utc>>,
+}
+
+async fn scan_snapshot(
+    op: &Operator,
+    path: &str,
+) -> Result<BTreeMap<String, (Entry, Fingerprint)>> {
+    let mut snapshot = BTreeMap::new();
+
+    let mut lister = op.scan(path).await?;
+    while let Some(de) = lister.try_next().await? {
+        let meta = op
+            .metadata(
+                &de,
+                Metakey::Mode | Metakey::Etag | Metakey::ContentLength | Metakey::LastModified,
+            )
+            .await?;
+        if meta.is_dir() {
+            continue;
+        }
+
+        let fingerprint = Fingerprint {
+            etag: meta.etag().map(|v| v.to_string()),
+            content_length: meta.content_length(),
+            last_modified: meta.last_modified(),
+        };
+        let path = de.path().to_string();
+        snapshot.insert(path.clone(), (Entry::new_with(path, meta), fingerprint));
+    }
+
+    Ok(snapshot)
+}
+
+fn diff_snapshot(
+    mut old: BTreeMap<String, (Entry, Fingerprint)>,
+    new: &BTreeMap<String, (Entry, Fingerprint)>,
+) -> Vec<WatchEvent> {
+    let mut events = Vec::new();
+
+    for (path, (entry, fingerprint)) in new {
+        match old.remove(path) {
+            None => events.push(WatchEvent::Created(entry.clone())),
+            Some((_, v)) if &v != fingerprint => events.push(WatchEvent::Modified(entry.clone())),
+            Some(_) => {}
+        }
+    }
+    for path in old.into_keys() {
+        events.push(WatchEvent::Deleted(Entry::new(&path)));
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_watch() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("dir/a", "a").await.unwrap();
+        op.write("dir/b", "b").await.unwrap();
+        op.write("other", "other").await.unwrap();
+
+        let mut watcher = op
+            .watch_with("dir/")
+            .interval(Duration::from_millis(10))
+            .await
+            .unwrap();
+
+        op.write("dir/a", "aa").await.unwrap();
+        op.write("dir/c", "c").await.unwrap();
+        op.delete("dir/b").await.unwrap();
+        op.write("other", "changed").await.unwrap();
+
+        let mut events = Vec::new();
+        while events.len() < 3 {
+            events.push(watcher.try_next().await.unwrap().unwrap());
+        }
+
+        assert!(matches!(&events[0], WatchEvent::Modified(e) if e.path() == "dir/a"));
+        assert!(matches!(&events[1], WatchEvent::Created(e) if e.path() == "dir/c"));
+        assert!(matches!(&events[2], WatchEvent::Deleted(e) if e.path() == "dir/b"));
+        assert_eq!(
+            op.metadata(events[0].entry(), Metakey::ContentLength)
+                .await
+                .unwrap()
+                .content_length(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watch_not_dir() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+
+        let err = op.watch("file").await.err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::NotADirectory);
+    }
+}
@@ -53,6 +53,14 @@ use crate::*;
 /// Some services also supports `unsized` writer. They MAY buffer part of the data
 /// and flush them into storage at needs. And finally, the file will be available
 /// after `close` has been called.
+///
+/// ## Usage
+///
+/// Writer implements `AsyncWrite` of both `futures` and `tokio::io` (with
+/// feature `tokio` enabled), so it can be passed to tokio based code like
+/// `tokio::io::copy` or `FramedWrite` directly. `shutdown` (or `close` for
+/// `futures`) must be called to make the file available. Use
+/// `Writer::into_tokio_write` or `From` to convert it into tokio types.
 pub struct Writer {
     state: State,
 }
@@ -123,6 +131,33 @@ impl Writer {
             );
         }
     }
+
+    /// Convert into a writer that implements `tokio::io::AsyncWrite`.
+    ///
+    /// `Writer` implements it already, this conversion is useful when
+    /// the writer is stored or passed as tokio types only.
+    ///
+    /// `shutdown` must be called to make the file available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut w = op.writer("path/to/file").await?.into_tokio_write();
+    /// w.write_all(b"Hello, World!").await?;
+    /// w.shutdown().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn into_tokio_write(self) -> impl tokio::io::AsyncWrite + Unpin + Send + Sync {
+        self
+    }
 }
 
 enum State {
@@ -208,6 +243,14 @@ impl AsyncWrite for Writer {
     }
 }
 
+#[cfg(feature = "tokio")]
+impl From<Writer> for Box<dyn tokio::io::AsyncWrite + Unpin + Send + Sync> {
+    fn from(w: Writer) -> Self {
+        Box::new(w)
+    }
+}
+
+#[cfg(feature = "tokio")]
 impl tokio::io::AsyncWrite for Writer {
     fn poll_write(
         mut self: Pin<&mut Self>,
@@ -25,28 +25,30 @@ use opendal::Operator;
 use sha2::Digest;
 use sha2::Sha256;
 
-use super::utils::*;
+use crate::utils::*;
 
 /// Test services that meet the following capability:
 ///
 /// - can_read
 /// - can_write
 /// - can_append
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_append_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<append_ $test >]() -> anyhow::Result<()> {
+                fn [<append_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read()
                             && op.info().can_write()
                             && op.info().can_append() => RUNTIME.block_on($crate::append::$test(op.clone())),
                         Some(_) => {
-                            log::warn!("service {} doesn't support append, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support append, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -61,21 +63,17 @@ macro_rules! behavior_append_test {
 
 #[macro_export]
 macro_rules! behavior_append_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_append_test!(
-                $service,
-
-                test_append_create_append,
-                test_append_with_dir_path,
-                test_append_with_cache_control,
-                test_append_with_content_type,
-                test_append_with_content_disposition,
-
-                test_appender_futures_copy,
-                test_fuzz_appender,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_append_test!(
+            $scheme,
+            test_append_create_append,
+            test_append_with_dir_path,
+            test_append_with_cache_control,
+            test_append_with_content_type,
+            test_append_with_content_disposition,
+            test_appender_futures_copy,
+            test_fuzz_appender,
+        );
     };
 }
 
@@ -19,7 +19,7 @@ use anyhow::Result;
 use opendal::BlockingOperator;
 use opendal::ErrorKind;
 
-use super::utils::*;
+use crate::utils::*;
 
 /// Test services that meet the following capability:
 ///
@@ -27,22 +27,24 @@ use super::utils::*;
 /// - can_write
 /// - can_copy
 /// - can_blocking
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_blocking_copy_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<blocking_copy_ $test >]() -> anyhow::Result<()> {
+                fn [<blocking_copy_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read()
                             && op.info().can_write()
                             && op.info().can_copy()
                             && op.info().can_blocking() => $crate::blocking_copy::$test(op.blocking()),
                         Some(_) => {
-                            log::warn!("service {} doesn't support blocking_copy, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support blocking_copy, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -57,20 +59,17 @@ macro_rules! behavior_blocking_copy_test {
 
 #[macro_export]
 macro_rules! behavior_blocking_copy_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_blocking_copy_test!(
-                $service,
-
-                test_copy_file,
-                test_copy_non_existing_source,
-                test_copy_source_dir,
-                test_copy_target_dir,
-                test_copy_self,
-                test_copy_nested,
-                test_copy_overwrite,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_blocking_copy_test!(
+            $scheme,
+            test_copy_file,
+            test_copy_non_existing_source,
+            test_copy_source_dir,
+            test_copy_target_dir,
+            test_copy_self,
+            test_copy_nested,
+            test_copy_overwrite,
+        );
     };
 }
 
@@ -23,7 +23,7 @@ use log::debug;
 use opendal::BlockingOperator;
 use opendal::EntryMode;
 
-use super::utils::*;
+use crate::utils::*;
 
 /// Test services that meet the following capability:
 ///
@@ -31,21 +31,23 @@ use super::utils::*;
 /// - can_write
 /// - can_blocking
 /// - can_list or can_scan
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_blocking_list_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<blocking_list_ $test >]() -> anyhow::Result<()> {
+                fn [<blocking_list_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read()
                             && op.info().can_write()
                             && op.info().can_blocking() && op.info().can_list() => $crate::blocking_list::$test(op.blocking()),
                         Some(_) => {
-                            log::warn!("service {} doesn't support blocking_list, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support blocking_list, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -60,17 +62,14 @@ macro_rules! behavior_blocking_list_test {
 
 #[macro_export]
 macro_rules! behavior_blocking_list_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_blocking_list_test!(
-                $service,
-
-                test_list_dir,
-                test_list_non_exist_dir,
-                test_scan,
-                test_remove_all,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_blocking_list_test!(
+            $scheme,
+            test_list_dir,
+            test_list_non_exist_dir,
+            test_scan,
+            test_remove_all,
+        );
     };
 }
 
@@ -27,21 +27,23 @@ use sha2::Sha256;
 /// - can_read
 /// - !can_write
 /// - can_blocking
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_blocking_read_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<blocking_read_ $test >]() -> anyhow::Result<()> {
+                fn [<blocking_read_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read()
                             && !op.info().can_write()
                             && op.info().can_blocking() => $crate::blocking_read::$test(op.blocking()),
                         Some(_) => {
-                            log::warn!("service {} doesn't support blocking_read, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support blocking_read, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -56,19 +58,16 @@ macro_rules! behavior_blocking_read_test {
 
 #[macro_export]
 macro_rules! behavior_blocking_read_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_blocking_read_test!(
-                $service,
-
-                test_stat_file_and_dir,
-                test_stat_special_chars,
-                test_stat_not_exist,
-                test_read_full,
-                test_read_range,
-                test_read_not_exist,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_blocking_read_test!(
+            $scheme,
+            test_stat_file_and_dir,
+            test_stat_special_chars,
+            test_stat_not_exist,
+            test_read_full,
+            test_read_range,
+            test_read_not_exist,
+        );
     };
 }
 
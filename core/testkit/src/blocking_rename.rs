@@ -19,7 +19,7 @@ use anyhow::Result;
 use opendal::BlockingOperator;
 use opendal::ErrorKind;
 
-use super::utils::*;
+use crate::utils::*;
 
 /// Test services that meet the following capability:
 ///
@@ -27,22 +27,24 @@ use super::utils::*;
 /// - can_write
 /// - can_rename
 /// - can_blocking
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_blocking_rename_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<blocking_rename_ $test >]() -> anyhow::Result<()> {
+                fn [<blocking_rename_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read()
                             && op.info().can_write()
                             && op.info().can_rename()
                             && op.info().can_blocking() => $crate::blocking_rename::$test(op.blocking()),
                         Some(_) => {
-                            log::warn!("service {} doesn't support blocking_rename, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support blocking_rename, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -57,20 +59,17 @@ macro_rules! behavior_blocking_rename_test {
 
 #[macro_export]
 macro_rules! behavior_blocking_rename_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_blocking_rename_test!(
-                $service,
-
-                test_rename_file,
-                test_rename_non_existing_source,
-                test_rename_source_dir,
-                test_rename_target_dir,
-                test_rename_self,
-                test_rename_nested,
-                test_rename_overwrite,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_blocking_rename_test!(
+            $scheme,
+            test_rename_file,
+            test_rename_non_existing_source,
+            test_rename_source_dir,
+            test_rename_target_dir,
+            test_rename_self,
+            test_rename_nested,
+            test_rename_overwrite,
+        );
     };
 }
 
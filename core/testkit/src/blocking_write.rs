@@ -26,28 +26,30 @@ use opendal::ErrorKind;
 use sha2::Digest;
 use sha2::Sha256;
 
-use super::utils::*;
+use crate::utils::*;
 
 /// Test services that meet the following capability:
 ///
 /// - can_read
 /// - can_write
 /// - can_blocking
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_blocking_write_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<blocking_write_ $test >]() -> anyhow::Result<()> {
+                fn [<blocking_write_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read()
                             && op.info().can_write()
                             && op.info().can_blocking() => $crate::blocking_write::$test(op.blocking()),
                         Some(_) => {
-                            log::warn!("service {} doesn't support blocking_write, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support blocking_write, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -62,31 +64,28 @@ macro_rules! behavior_blocking_write_test {
 
 #[macro_export]
 macro_rules! behavior_blocking_write_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_blocking_write_test!(
-                $service,
-
-                test_create_dir,
-                test_create_dir_existing,
-                test_write_file,
-                test_write_with_dir_path,
-                test_write_with_special_chars,
-                test_stat_file,
-                test_stat_dir,
-                test_stat_with_special_chars,
-                test_stat_not_exist,
-                test_read_full,
-                test_read_range,
-                test_read_large_range,
-                test_read_not_exist,
-                test_fuzz_range_reader,
-                test_fuzz_offset_reader,
-                test_fuzz_part_reader,
-                test_delete_file,
-                test_remove_one_file,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_blocking_write_test!(
+            $scheme,
+            test_create_dir,
+            test_create_dir_existing,
+            test_write_file,
+            test_write_with_dir_path,
+            test_write_with_special_chars,
+            test_stat_file,
+            test_stat_dir,
+            test_stat_with_special_chars,
+            test_stat_not_exist,
+            test_read_full,
+            test_read_range,
+            test_read_large_range,
+            test_read_not_exist,
+            test_fuzz_range_reader,
+            test_fuzz_offset_reader,
+            test_fuzz_part_reader,
+            test_delete_file,
+            test_remove_one_file,
+        );
     };
 }
 
@@ -15,32 +15,36 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+
 use anyhow::Result;
 use opendal::ErrorKind;
 use opendal::Operator;
 
-use super::utils::*;
+use crate::utils::*;
 
 /// Test services that meet the following capability:
 ///
 /// - can_read
 /// - can_write
 /// - can_copy
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_copy_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<copy_ $test >]() -> anyhow::Result<()> {
+                fn [<copy_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read()
                             && op.info().can_write()
                             && op.info().can_copy() => RUNTIME.block_on($crate::copy::$test(op.clone())),
                         Some(_) => {
-                            log::warn!("service {} doesn't support copy, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support copy, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -55,21 +59,20 @@ macro_rules! behavior_copy_test {
 
 #[macro_export]
 macro_rules! behavior_copy_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_copy_test!(
-                $service,
-
-                test_copy_file,
-                test_copy_non_existing_source,
-                test_copy_source_dir,
-                test_copy_target_dir,
-                test_copy_self,
-                test_copy_nested,
-                test_copy_overwrite,
-
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_copy_test!(
+            $scheme,
+            test_copy_file,
+            test_copy_non_existing_source,
+            test_copy_source_dir,
+            test_copy_target_dir,
+            test_copy_self,
+            test_copy_nested,
+            test_copy_overwrite,
+            test_copy_with_content_type,
+            test_copy_with_user_metadata,
+            test_copy_with_unsupported_args,
+        );
     };
 }
 
@@ -205,3 +208,130 @@ pub async fn test_copy_overwrite(op: Operator) -> Result<()> {
     op.delete(&target_path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Copy with content type should replace the content type of target.
+pub async fn test_copy_with_content_type(op: Operator) -> Result<()> {
+    if !op.info().capability().copy_with_content_type {
+        return Ok(());
+    }
+
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, size) = gen_bytes();
+
+    op.write_with(&source_path, source_content)
+        .content_type("text/plain")
+        .await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+    let target_content_type = "application/json";
+
+    op.copy_with(&source_path, &target_path)
+        .content_type(target_content_type)
+        .await?;
+
+    let meta = op.stat(&target_path).await.expect("stat must succeed");
+    assert_eq!(
+        meta.content_type().expect("content type must exist"),
+        target_content_type
+    );
+    assert_eq!(meta.content_length(), size as u64);
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy should preserve user metadata of source unless new one is given.
+pub async fn test_copy_with_user_metadata(op: Operator) -> Result<()> {
+    let cap = op.info().capability();
+    if !cap.write_with_user_metadata || !cap.copy_with_user_metadata || !cap.stat_has_user_metadata
+    {
+        return Ok(());
+    }
+
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes();
+    let source_user_metadata = HashMap::from([("owner".to_string(), "source".to_string())]);
+
+    let mut w = op
+        .write_with(&source_path, source_content)
+        .user_metadata(source_user_metadata.clone());
+    if cap.write_with_content_type {
+        w = w.content_type("text/plain");
+    }
+    if cap.write_with_cache_control {
+        w = w.cache_control("no-cache");
+    }
+    w.await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+    op.copy(&source_path, &target_path).await?;
+    let meta = op.stat(&target_path).await.expect("stat must succeed");
+    assert_eq!(meta.user_metadata(), Some(&source_user_metadata));
+
+    let target_user_metadata = HashMap::from([("owner".to_string(), "target".to_string())]);
+    op.copy_with(&source_path, &target_path)
+        .user_metadata(target_user_metadata.clone())
+        .await?;
+    let meta = op.stat(&target_path).await.expect("stat must succeed");
+    assert_eq!(meta.user_metadata(), Some(&target_user_metadata));
+    // Replacing user metadata should keep other metadata of source.
+    if cap.write_with_content_type {
+        assert_eq!(meta.content_type(), Some("text/plain"));
+    }
+    if cap.write_with_cache_control {
+        assert_eq!(meta.cache_control(), Some("no-cache"));
+    }
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy with unsupported args should return an error instead of dropping them.
+pub async fn test_copy_with_unsupported_args(op: Operator) -> Result<()> {
+    let cap = op.info().capability();
+
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes();
+    op.write(&source_path, source_content).await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    let res = op
+        .copy_with(&source_path, &target_path)
+        .tags(HashMap::from([("k".to_string(), "v".to_string())]))
+        .await;
+    if cap.copy_with_tags {
+        res.expect("copy with tags must succeed");
+    } else {
+        let err = res.expect_err("copy with tags must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    let res = op
+        .copy_with(&source_path, &target_path)
+        .content_type("application/json")
+        .await;
+    if cap.copy_with_content_type {
+        res.expect("copy with content type must succeed");
+    } else {
+        let err = res.expect_err("copy with content type must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    let res = op
+        .copy_with(&source_path, &target_path)
+        .user_metadata(HashMap::from([("k".to_string(), "v".to_string())]))
+        .await;
+    if cap.copy_with_user_metadata {
+        res.expect("copy with user metadata must succeed");
+    } else {
+        let err = res.expect_err("copy with user metadata must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
@@ -0,0 +1,130 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Behavior tests for OpenDAL services.
+//!
+//! This crate exports the same behavior tests that OpenDAL runs for its
+//! own services, so that services implemented outside of OpenDAL can be
+//! tested in the same way.
+//!
+//! # Examples
+//!
+//! Add `opendal-testkit` into `dev-dependencies` and generate tests for
+//! the builder of service in an integration test like `tests/behavior.rs`:
+//!
+//! ```ignore
+//! opendal_testkit::behavior_tests!(services_my_service, my_service::MyBuilder);
+//! ```
+//!
+//! Tests will be skipped unless `OPENDAL_{SCHEME}_TEST` is set to `on`,
+//! the config of service will be loaded from env `OPENDAL_{SCHEME}_*`
+//! which could also be set in `.env` file.
+//!
+//! OpenDAL's own behavior tests in `core/tests/behavior` are generated
+//! from this crate too.
+
+#[doc(hidden)]
+pub use anyhow;
+#[doc(hidden)]
+pub use log;
+#[doc(hidden)]
+pub use once_cell;
+#[doc(hidden)]
+pub use opendal;
+#[doc(hidden)]
+pub use paste;
+#[doc(hidden)]
+pub use tokio;
+
+pub mod append;
+pub mod blocking_copy;
+pub mod blocking_list;
+pub mod blocking_read;
+pub mod blocking_rename;
+pub mod blocking_write;
+pub mod copy;
+pub mod list;
+pub mod list_only;
+pub mod presign;
+pub mod read_only;
+pub mod rename;
+pub mod write;
+pub mod write_at;
+
+pub mod utils;
+
+/// Generate behavior tests for service in a new module.
+///
+/// - `$module`: name of the generated module, which will be the prefix of
+///   the tests like `services_fs::write_test_stat`.
+/// - `$builder`: builder of the service.
+/// - `$scheme`: scheme used in logs, default to the scheme of builder.
+#[macro_export]
+macro_rules! behavior_tests {
+    ($module:ident, $builder:ty) => {
+        $crate::behavior_tests!(
+            $module,
+            $builder,
+            <$builder as $crate::opendal::Builder>::SCHEME
+        );
+    };
+    ($module:ident, $builder:ty, $scheme:expr) => {
+        mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            static RUNTIME: $crate::once_cell::sync::Lazy<$crate::tokio::runtime::Runtime> =
+                $crate::once_cell::sync::Lazy::new(|| {
+                    $crate::tokio::runtime::Builder::new_multi_thread()
+                        .enable_all()
+                        .build()
+                        .unwrap()
+                });
+            static OPERATOR: $crate::once_cell::sync::Lazy<Option<$crate::opendal::Operator>> =
+                $crate::once_cell::sync::Lazy::new(|| $crate::utils::init_service::<$builder>());
+
+            // can_read && !can_write
+            $crate::behavior_read_tests!($scheme);
+            // can_read && !can_write && can_blocking
+            $crate::behavior_blocking_read_tests!($scheme);
+            // can_read && can_write
+            $crate::behavior_write_tests!($scheme);
+            // can_read && can_write && can_blocking
+            $crate::behavior_blocking_write_tests!($scheme);
+            // can_read && can_write && can_append
+            $crate::behavior_append_tests!($scheme);
+            // can_read && can_write && can_copy
+            $crate::behavior_copy_tests!($scheme);
+            // can read && can_write && can_blocking && can_copy
+            $crate::behavior_blocking_copy_tests!($scheme);
+            // can_read && can_write && can_move
+            $crate::behavior_rename_tests!($scheme);
+            // can_read && can_write && can_blocking && can_move
+            $crate::behavior_blocking_rename_tests!($scheme);
+            // can_read && can_write && can_list
+            $crate::behavior_list_tests!($scheme);
+            // can_read && can_write && can_presign
+            $crate::behavior_presign_tests!($scheme);
+            // can_read && can_write && can_blocking && can_list
+            $crate::behavior_blocking_list_tests!($scheme);
+            // can_list && !can_write
+            $crate::behavior_list_only_tests!($scheme);
+            // can_read && can_write && can_write_at && can_truncate
+            $crate::behavior_write_at_tests!($scheme);
+        }
+    };
+}
@@ -27,29 +27,31 @@ use opendal::EntryMode;
 use opendal::ErrorKind;
 use opendal::Operator;
 
-use super::utils::*;
+use crate::utils::*;
 
 /// Test services that meet the following capability:
 ///
 /// - can_read
 /// - can_write
 /// - can_list or can_scan
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_list_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<list_ $test >]() -> anyhow::Result<()> {
+                fn [<list_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read()
                             && op.info().can_write()
                             && op.info().can_list()
                                  => RUNTIME.block_on($crate::list::$test(op.clone())),
                         Some(_) => {
-                            log::warn!("service {} doesn't support list, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support list, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -64,25 +66,24 @@ macro_rules! behavior_list_test {
 
 #[macro_export]
 macro_rules! behavior_list_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_list_test!(
-                $service,
-
-                test_check,
-                test_list_dir,
-                test_list_rich_dir,
-                test_list_empty_dir,
-                test_list_non_exist_dir,
-                test_list_sub_dir,
-                test_list_nested_dir,
-                test_list_dir_with_file_path,
-                test_list_with_start_after,
-                test_scan,
-                test_scan_root,
-                test_remove_all,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_list_test!(
+            $scheme,
+            test_check,
+            test_list_dir,
+            test_list_rich_dir,
+            test_list_empty_dir,
+            test_list_non_exist_dir,
+            test_list_sub_dir,
+            test_list_nested_dir,
+            test_list_dir_with_file_path,
+            test_list_with_start_after,
+            test_scan,
+            test_scan_concurrent,
+            test_scan_root,
+            test_remove_all,
+            test_remove_all_with_batch_concurrency,
+        );
     };
 }
 
@@ -369,6 +370,62 @@ pub async fn test_scan(op: Operator) -> Result<()> {
     Ok(())
 }
 
+// Scan concurrently should output the same entries as scan.
+pub async fn test_scan_concurrent(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    let expected = vec![
+        "x/", "x/y", "x/a/", "x/a/y", "x/a/b/", "x/a/b/y", "x/c/", "x/c/y", "x/d/",
+    ];
+    for path in expected.iter() {
+        if path.ends_with('/') {
+            op.create_dir(&format!("{parent}/{path}")).await?;
+        } else {
+            op.write(&format!("{parent}/{path}"), "test_scan_concurrent")
+                .await?;
+        }
+    }
+
+    let w = op.scan(&format!("{parent}/x/")).await?;
+    let serial = w
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .map(|v| v.path().to_string())
+        .collect::<Vec<_>>();
+
+    let w = op
+        .list_with(&format!("{parent}/x/"))
+        .delimiter("")
+        .concurrent(4)
+        .await?;
+    let actual = w
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .map(|v| v.path().to_string())
+        .collect::<Vec<_>>();
+
+    debug!("scan concurrently: {:?}", actual);
+
+    let actual_set = actual.iter().cloned().collect::<HashSet<_>>();
+    assert_eq!(
+        actual.len(),
+        actual_set.len(),
+        "entries must not be duplicated"
+    );
+    assert!(actual_set.contains(&format!("{parent}/x/y")));
+    assert!(actual_set.contains(&format!("{parent}/x/a/y")));
+    assert!(actual_set.contains(&format!("{parent}/x/a/b/y")));
+    assert!(actual_set.contains(&format!("{parent}/x/c/y")));
+    for path in serial.iter().filter(|v| !v.ends_with('/')) {
+        assert!(actual_set.contains(path), "{path} should be found");
+    }
+
+    op.remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
+
 // Remove all should remove all in this path.
 pub async fn test_remove_all(op: Operator) -> Result<()> {
     let parent = uuid::Uuid::new_v4().to_string();
@@ -397,3 +454,35 @@ pub async fn test_remove_all(op: Operator) -> Result<()> {
     }
     Ok(())
 }
+
+// Remove all with batch concurrency should remove all in this path.
+pub async fn test_remove_all_with_batch_concurrency(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+    // Use a small limit so that paths will be removed in many batches.
+    let op = op.with_limit(2).with_batch_concurrency(4);
+
+    let expected = vec![
+        "x/", "x/y", "x/z", "x/a/", "x/a/y", "x/a/z", "x/a/b/", "x/a/b/y", "x/c/", "x/c/y",
+    ];
+    for path in expected.iter() {
+        if path.ends_with('/') {
+            op.create_dir(&format!("{parent}/{path}")).await?;
+        } else {
+            op.write(&format!("{parent}/{path}"), "test_remove_all")
+                .await?;
+        }
+    }
+
+    op.remove_all(&format!("{parent}/x/")).await?;
+
+    for path in expected.iter() {
+        if path.ends_with('/') {
+            continue;
+        }
+        assert!(
+            !op.is_exist(&format!("{parent}/{path}")).await?,
+            "{parent}/{path} should be removed"
+        )
+    }
+    Ok(())
+}
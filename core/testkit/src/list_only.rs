@@ -26,19 +26,21 @@ use opendal::Operator;
 ///
 /// - !can_write
 /// - can_list
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_list_only_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<list_only_ $test >]() -> anyhow::Result<()> {
+                fn [<list_only_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_list() && !op.info().can_write() => RUNTIME.block_on($crate::list_only::$test(op.clone())),
                         Some(_) => {
-                            log::warn!("service {} doesn't support list, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support list, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -53,14 +55,8 @@ macro_rules! behavior_list_only_test {
 
 #[macro_export]
 macro_rules! behavior_list_only_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_list_only_test!(
-                $service,
-
-                test_list,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_list_only_test!($scheme, test_list,);
     };
 }
 
@@ -27,26 +27,28 @@ use reqwest::Url;
 use sha2::Digest;
 use sha2::Sha256;
 
-use super::utils::*;
+use crate::utils::*;
 
 /// Test services that meet the following capability:
 ///
 /// - can_read
 /// - can_write
 /// - can_presign
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_presign_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<presign_ $test >]() -> anyhow::Result<()> {
+                fn [<presign_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read() && op.info().can_write() && op.info().can_presign() => RUNTIME.block_on($crate::presign::$test(op.clone())),
                         Some(_) => {
-                            log::warn!("service {} doesn't support presign, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support presign, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -61,16 +63,13 @@ macro_rules! behavior_presign_test {
 
 #[macro_export]
 macro_rules! behavior_presign_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_presign_test!(
-                $service,
-
-                test_presign_write,
-                test_presign_read,
-                test_presign_stat,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_presign_test!(
+            $scheme,
+            test_presign_write,
+            test_presign_read,
+            test_presign_stat,
+        );
     };
 }
 
@@ -27,19 +27,21 @@ use sha2::Sha256;
 ///
 /// - can_read
 /// - !can_write
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_read_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<read_only_ $test >]() -> anyhow::Result<()> {
+                fn [<read_only_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read() && !op.info().can_write() => RUNTIME.block_on($crate::read_only::$test(op.clone())),
                         Some(_) => {
-                            log::warn!("service {} doesn't support read_only, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support read_only, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -54,30 +56,27 @@ macro_rules! behavior_read_test {
 
 #[macro_export]
 macro_rules! behavior_read_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_read_test!(
-                $service,
-
-                test_stat_file_and_dir,
-                test_stat_special_chars,
-                test_stat_not_cleaned_path,
-                test_stat_not_exist,
-                test_stat_with_if_match,
-                test_stat_with_if_none_match,
-                test_stat_root,
-                test_read_full,
-                test_read_full_with_special_chars,
-                test_read_range,
-                test_reader_range,
-                test_reader_from,
-                test_reader_tail,
-                test_read_not_exist,
-                test_read_with_dir_path,
-                test_read_with_if_match,
-                test_read_with_if_none_match,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_read_test!(
+            $scheme,
+            test_stat_file_and_dir,
+            test_stat_special_chars,
+            test_stat_not_cleaned_path,
+            test_stat_not_exist,
+            test_stat_with_if_match,
+            test_stat_with_if_none_match,
+            test_stat_root,
+            test_read_full,
+            test_read_full_with_special_chars,
+            test_read_range,
+            test_reader_range,
+            test_reader_from,
+            test_reader_tail,
+            test_read_not_exist,
+            test_read_with_dir_path,
+            test_read_with_if_match,
+            test_read_with_if_none_match,
+        );
     };
 }
 
@@ -19,28 +19,30 @@ use anyhow::Result;
 use opendal::ErrorKind;
 use opendal::Operator;
 
-use super::utils::*;
+use crate::utils::*;
 
 /// Test services that meet the following capability:
 ///
 /// - can_read
 /// - can_write
 /// - can_rename
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_rename_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<rename_ $test >]() -> anyhow::Result<()> {
+                fn [<rename_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read()
                             && op.info().can_write()
                             && op.info().can_rename() => RUNTIME.block_on($crate::rename::$test(op.clone())),
                         Some(_) => {
-                            log::warn!("service {} doesn't support rename, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support rename, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -55,21 +57,17 @@ macro_rules! behavior_rename_test {
 
 #[macro_export]
 macro_rules! behavior_rename_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_rename_test!(
-                $service,
-
-                test_rename_file,
-                test_rename_non_existing_source,
-                test_rename_source_dir,
-                test_rename_target_dir,
-                test_rename_self,
-                test_rename_nested,
-                test_rename_overwrite,
-
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_rename_test!(
+            $scheme,
+            test_rename_file,
+            test_rename_non_existing_source,
+            test_rename_source_dir,
+            test_rename_target_dir,
+            test_rename_self,
+            test_rename_nested,
+            test_rename_overwrite,
+        );
     };
 }
 
@@ -21,7 +21,6 @@ use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::io::SeekFrom;
-use std::usize;
 
 use bytes::Bytes;
 use log::debug;
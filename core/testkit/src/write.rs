@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp;
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -34,25 +36,27 @@ use reqwest::Url;
 use sha2::Digest;
 use sha2::Sha256;
 
-use super::utils::*;
+use crate::utils::*;
 
 /// Test services that meet the following capability:
 ///
 /// - can_read
 /// - can_write
+#[doc(hidden)]
+#[macro_export]
 macro_rules! behavior_write_test {
-    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
-        paste::item! {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
             $(
                 #[test]
                 $(
                     #[$meta]
                 )*
-                fn [<write_ $test >]() -> anyhow::Result<()> {
+                fn [<write_ $test >]() -> $crate::anyhow::Result<()> {
                     match OPERATOR.as_ref() {
                         Some(op) if op.info().can_read() && op.info().can_write() => RUNTIME.block_on($crate::write::$test(op.clone())),
                         Some(_) => {
-                            log::warn!("service {} doesn't support write, ignored", opendal::Scheme::$service);
+                            $crate::log::warn!("service {} doesn't support write, ignored", $scheme);
                             Ok(())
                         },
                         None => {
@@ -67,55 +71,60 @@ macro_rules! behavior_write_test {
 
 #[macro_export]
 macro_rules! behavior_write_tests {
-     ($($service:ident),*) => {
-        $(
-            behavior_write_test!(
-                $service,
-
-                test_create_dir,
-                test_create_dir_existing,
-                test_write_only,
-                test_write_with_dir_path,
-                test_write_with_special_chars,
-                test_write_with_cache_control,
-                test_write_with_content_type,
-                test_write_with_content_disposition,
-                test_stat_file,
-                test_stat_dir,
-                test_stat_with_special_chars,
-                test_stat_not_cleaned_path,
-                test_stat_not_exist,
-                test_stat_with_if_match,
-                test_stat_with_if_none_match,
-                test_stat_root,
-                test_read_full,
-                test_read_range,
-                test_read_large_range,
-                test_reader_range,
-                test_reader_from,
-                test_reader_tail,
-                test_read_not_exist,
-                test_read_with_if_match,
-                test_read_with_if_none_match,
-                test_fuzz_range_reader,
-                test_fuzz_offset_reader,
-                test_fuzz_part_reader,
-                test_read_with_dir_path,
-                test_read_with_special_chars,
-                test_read_with_override_cache_control,
-                test_read_with_override_content_disposition,
-                test_delete_file,
-                test_delete_empty_dir,
-                test_delete_with_special_chars,
-                test_delete_not_existing,
-                test_delete_stream,
-                test_remove_one_file,
-                test_writer_write,
-                test_writer_abort,
-                test_writer_futures_copy,
-                test_fuzz_unsized_writer,
-            );
-        )*
+    ($scheme:expr) => {
+        $crate::behavior_write_test!(
+            $scheme,
+            test_create_dir,
+            test_create_dir_existing,
+            test_write_only,
+            test_write_with_dir_path,
+            test_write_with_special_chars,
+            test_write_with_cache_control,
+            test_write_with_content_type,
+            test_write_with_content_disposition,
+            test_write_with_storage_class,
+            test_write_with_permission,
+            test_write_with_user_metadata,
+            test_stat_file,
+            test_stat_dir,
+            test_stat_with_special_chars,
+            test_stat_not_cleaned_path,
+            test_stat_not_exist,
+            test_stat_with_if_match,
+            test_stat_with_if_none_match,
+            test_stat_root,
+            test_stat_many,
+            test_stat_many_with_zero_limit,
+            test_read_full,
+            test_read_range,
+            test_read_large_range,
+            test_read_ranges,
+            test_reader_range,
+            test_reader_from,
+            test_reader_tail,
+            test_reader_tail_with_size_hint,
+            test_read_not_exist,
+            test_read_with_if_match,
+            test_read_with_if_none_match,
+            test_fuzz_range_reader,
+            test_fuzz_offset_reader,
+            test_fuzz_part_reader,
+            test_read_with_dir_path,
+            test_read_with_special_chars,
+            test_read_with_override_cache_control,
+            test_read_with_override_content_disposition,
+            test_delete_file,
+            test_delete_empty_dir,
+            test_delete_with_special_chars,
+            test_delete_not_existing,
+            test_delete_with_if_match,
+            test_delete_stream,
+            test_remove_one_file,
+            test_writer_write,
+            test_writer_abort,
+            test_writer_futures_copy,
+            test_fuzz_unsized_writer,
+        );
     };
 }
 
@@ -219,6 +228,93 @@ pub async fn test_write_with_cache_control(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write a single file with storage class should succeed.
+pub async fn test_write_with_storage_class(op: Operator) -> Result<()> {
+    if !op.info().capability().write_with_storage_class {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    // Use `STANDARD` here so that this test can pass on all s3 compatible services.
+    let target_storage_class = "STANDARD";
+    op.write_with(&path, content)
+        .storage_class(target_storage_class)
+        .await?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.mode(), EntryMode::FILE);
+    assert_eq!(
+        meta.storage_class().expect("storage class must exist"),
+        target_storage_class
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+
+    Ok(())
+}
+
+/// Write a single file with permission should succeed.
+pub async fn test_write_with_permission(op: Operator) -> Result<()> {
+    if !op.info().capability().write_with_permission {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write_with(&path, content.clone())
+        .permission(0o600)
+        .await?;
+
+    // Permission should be updated while overwriting existing files.
+    op.write_with(&path, content).permission(0o640).await?;
+
+    if op.info().capability().stat_has_permission {
+        let meta = op.stat(&path).await.expect("stat must succeed");
+        assert_eq!(meta.permission(), Some(0o640));
+        assert!(meta.uid().is_some());
+        assert!(meta.gid().is_some());
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+
+    Ok(())
+}
+
+/// Write a single file with user metadata should succeed.
+pub async fn test_write_with_user_metadata(op: Operator) -> Result<()> {
+    if !op.info().capability().write_with_user_metadata {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write_with(&path, content.clone())
+        .user_metadata(HashMap::from([("stale".to_string(), "v".to_string())]))
+        .await?;
+
+    // User metadata should be replaced while overwriting existing files.
+    let user_metadata = HashMap::from([
+        ("owner".to_string(), "opendal".to_string()),
+        ("sync-id".to_string(), "42".to_string()),
+    ]);
+    op.write_with(&path, content)
+        .user_metadata(user_metadata.clone())
+        .await?;
+
+    if op.info().capability().stat_has_user_metadata {
+        let meta = op.stat(&path).await.expect("stat must succeed");
+        assert_eq!(meta.user_metadata(), Some(&user_metadata));
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+
+    Ok(())
+}
+
 /// Write a single file with content type should succeed.
 pub async fn test_write_with_content_type(op: Operator) -> Result<()> {
     if !op.info().capability().write_with_content_type {
@@ -288,6 +384,49 @@ pub async fn test_stat_file(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Stat many paths should return results in the same order.
+pub async fn test_stat_many(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, size) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    let not_exist = uuid::Uuid::new_v4().to_string();
+    let results = op.stat_many(vec![path.clone(), not_exist.clone()]).await;
+    assert_eq!(results.len(), 2);
+
+    let (p, meta) = &results[0];
+    assert_eq!(p, &path);
+    let meta = meta.as_ref().expect("stat must succeed");
+    assert_eq!(meta.mode(), EntryMode::FILE);
+    assert_eq!(meta.content_length(), size as u64);
+
+    let (p, err) = &results[1];
+    assert_eq!(p, &not_exist);
+    assert_eq!(
+        err.as_ref().expect_err("stat must fail").kind(),
+        ErrorKind::NotFound
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Stat many paths should still make progress while limit is zero.
+pub async fn test_stat_many_with_zero_limit(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    let results = op.with_limit(0).stat_many(vec![path.clone()]).await;
+    assert_eq!(results.len(), 1);
+    assert!(results[0].1.is_ok(), "stat must succeed");
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Stat existing file should return metadata
 pub async fn test_stat_dir(op: Operator) -> Result<()> {
     let path = format!("{}/", uuid::Uuid::new_v4());
@@ -480,6 +619,48 @@ pub async fn test_read_range(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read multiple ranges should match.
+pub async fn test_read_ranges(op: Operator) -> Result<()> {
+    if !op.info().capability().read_with_range {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+    let size = size as u64;
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let ranges = vec![
+        size / 2..size / 2 + 1024,
+        0..1024,
+        512..2048,
+        size.saturating_sub(1024)..size + 1024,
+        4096..4096,
+    ];
+
+    for gap in [0, 1024 * 1024] {
+        let bss = op.read_ranges_with(&path, &ranges).gap(gap).await?;
+        assert_eq!(bss.len(), ranges.len(), "read ranges count");
+
+        for (bs, range) in bss.iter().zip(ranges.iter()) {
+            let start = cmp::min(range.start, size) as usize;
+            let end = cmp::min(range.end, size) as usize;
+            assert_eq!(
+                format!("{:x}", Sha256::digest(bs)),
+                format!("{:x}", Sha256::digest(&content[start..end])),
+                "read content of range {range:?}"
+            );
+        }
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Read large range content should match.
 pub async fn test_read_large_range(op: Operator) -> Result<()> {
     if !op.info().capability().read_with_range {
@@ -614,6 +795,50 @@ pub async fn test_reader_tail(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read tail with size hint should match.
+pub async fn test_reader_tail_with_size_hint(op: Operator) -> Result<()> {
+    if !op.info().capability().read_with_range {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+    let (_, length) = gen_offset_length(size);
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let mut r = match op
+        .reader_with(&path)
+        .range(..length)
+        .size_hint(size as u64)
+        .await
+    {
+        Ok(r) => r,
+        // Not all services support range with tail range, let's tolerate this.
+        Err(err) if err.kind() == ErrorKind::Unsupported => {
+            warn!("service doesn't support range with tail");
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut bs = Vec::new();
+    r.read_to_end(&mut bs).await?;
+
+    assert_eq!(bs.len(), length as usize, "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content[size - length as usize..])),
+        "read content"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Read not exist file should return NotFound
 pub async fn test_read_not_exist(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -1040,6 +1265,34 @@ pub async fn test_delete_not_existing(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Delete with if match should only delete the matched file.
+pub async fn test_delete_with_if_match(op: Operator) -> Result<()> {
+    if !op.info().capability().delete_with_if_match {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+    let meta = op.stat(&path).await?;
+
+    let err = op
+        .delete_with(&path)
+        .if_match("\"invalid_etag\"")
+        .await
+        .expect_err("delete must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    assert!(op.is_exist(&path).await?);
+
+    op.delete_with(&path)
+        .if_match(meta.etag().expect("etag must exist"))
+        .await?;
+    assert!(!op.is_exist(&path).await?);
+
+    Ok(())
+}
+
 /// Remove one file
 pub async fn test_remove_one_file(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -0,0 +1,157 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+use opendal::ErrorKind;
+use opendal::Operator;
+
+use crate::utils::*;
+
+/// Test services that meet the following capability:
+///
+/// - can_read
+/// - can_write
+/// - can_write_at
+/// - can_truncate
+#[doc(hidden)]
+#[macro_export]
+macro_rules! behavior_write_at_test {
+    ($scheme:expr, $($(#[$meta:meta])* $test:ident),*,) => {
+        $crate::paste::item! {
+            $(
+                #[test]
+                $(
+                    #[$meta]
+                )*
+                fn [<write_at_ $test >]() -> $crate::anyhow::Result<()> {
+                    match OPERATOR.as_ref() {
+                        Some(op) if op.info().can_read()
+                            && op.info().can_write()
+                            && op.info().capability().write_at
+                            && op.info().capability().truncate => RUNTIME.block_on($crate::write_at::$test(op.clone())),
+                        Some(_) => {
+                            $crate::log::warn!("service {} doesn't support write_at, ignored", $scheme);
+                            Ok(())
+                        },
+                        None => {
+                            Ok(())
+                        }
+                    }
+                }
+            )*
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! behavior_write_at_tests {
+    ($scheme:expr) => {
+        $crate::behavior_write_at_test!(
+            $scheme,
+            test_write_at_overwrite,
+            test_write_at_beyond_end,
+            test_write_at_with_dir_path,
+            test_truncate,
+            test_truncate_not_existing,
+        );
+    };
+}
+
+/// Test write at the middle of an existing file must keep other content.
+pub async fn test_write_at_overwrite(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let content = gen_fixed_bytes(4096);
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+    op.write_at(&path, 1024, vec![1; 1024])
+        .await
+        .expect("write at must succeed");
+
+    let bs = op.read(&path).await.expect("read must succeed");
+    assert_eq!(bs.len(), content.len());
+    assert_eq!(bs[..1024], content[..1024]);
+    assert_eq!(bs[1024..2048], vec![1; 1024]);
+    assert_eq!(bs[2048..], content[2048..]);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Test write at beyond the end of file must fill the gap with zeros.
+pub async fn test_write_at_beyond_end(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+
+    op.write_at(&path, 1024, vec![1; 1024])
+        .await
+        .expect("write at not existing file must succeed");
+
+    let bs = op.read(&path).await.expect("read must succeed");
+    assert_eq!(bs.len(), 2048);
+    assert_eq!(bs[..1024], vec![0; 1024]);
+    assert_eq!(bs[1024..], vec![1; 1024]);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Test write at a directory path must fail.
+pub async fn test_write_at_with_dir_path(op: Operator) -> Result<()> {
+    let path = format!("{}/", uuid::Uuid::new_v4());
+
+    let res = op.write_at(&path, 0, vec![1; 1024]).await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::IsADirectory);
+
+    Ok(())
+}
+
+/// Test truncate must shrink and extend the file.
+pub async fn test_truncate(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let content = gen_fixed_bytes(4096);
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    op.truncate(&path, 1024)
+        .await
+        .expect("truncate must succeed");
+    let bs = op.read(&path).await.expect("read must succeed");
+    assert_eq!(bs, content[..1024]);
+
+    op.truncate(&path, 2048).await.expect("extend must succeed");
+    let bs = op.read(&path).await.expect("read must succeed");
+    assert_eq!(bs[..1024], content[..1024]);
+    assert_eq!(bs[1024..], vec![0; 1024]);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Test truncate a not existing file must return NotFound.
+pub async fn test_truncate_not_existing(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+
+    let res = op.truncate(&path, 0).await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::NotFound);
+
+    Ok(())
+}
@@ -15,82 +15,26 @@
 // specific language governing permissions and limitations
 // under the License.
 
-#[macro_use]
-mod blocking_copy;
-#[macro_use]
-mod blocking_list;
-#[macro_use]
-mod blocking_rename;
-#[macro_use]
-mod blocking_read;
-#[macro_use]
-mod blocking_write;
-#[macro_use]
-mod append;
-#[macro_use]
-mod copy;
-#[macro_use]
-mod list;
-#[macro_use]
-mod list_only;
-#[macro_use]
-mod presign;
-#[macro_use]
-mod read_only;
-#[macro_use]
-mod rename;
-#[macro_use]
-mod write;
-
-mod utils;
-
 /// Generate real test cases.
-/// Update function list while changed.
+///
+/// Test cases are defined in `opendal-testkit`, update them there.
 macro_rules! behavior_tests {
     ($($service:ident),*) => {
+        $(
+            behavior_tests!(@service $service, $service);
+        )*
+    };
+    // Services whose builder name differs from the scheme, like `BoxStorage`.
+    ($service:ident => $scheme:ident) => {
+        behavior_tests!(@service $service, $scheme);
+    };
+    (@service $service:ident, $scheme:ident) => {
         paste::item! {
-            $(
-                mod [<services_ $service:snake>] {
-                    use once_cell::sync::Lazy;
-
-                    static RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
-                       tokio::runtime::Builder::new_multi_thread()
-                            .enable_all()
-                            .build()
-                            .unwrap()
-                    });
-                    static OPERATOR: Lazy<Option<opendal::Operator>> = Lazy::new(||
-                        $crate::utils::init_service::<opendal::services::$service>()
-                    );
-
-                    // can_read && !can_write
-                    behavior_read_tests!($service);
-                    // can_read && !can_write && can_blocking
-                    behavior_blocking_read_tests!($service);
-                    // can_read && can_write
-                    behavior_write_tests!($service);
-                    // can_read && can_write && can_blocking
-                    behavior_blocking_write_tests!($service);
-                    // can_read && can_write && can_append
-                    behavior_append_tests!($service);
-                    // can_read && can_write && can_copy
-                    behavior_copy_tests!($service);
-                    // can read && can_write && can_blocking && can_copy
-                    behavior_blocking_copy_tests!($service);
-                    // can_read && can_write && can_move
-                    behavior_rename_tests!($service);
-                    // can_read && can_write && can_blocking && can_move
-                    behavior_blocking_rename_tests!($service);
-                    // can_read && can_write && can_list
-                    behavior_list_tests!($service);
-                    // can_read && can_write && can_presign
-                    behavior_presign_tests!($service);
-                    // can_read && can_write && can_blocking && can_list
-                    behavior_blocking_list_tests!($service);
-                    // can_list && !can_write
-                    behavior_list_only_tests!($service);
-                }
-         )*
+            opendal_testkit::behavior_tests!(
+                [<services_ $service:snake>],
+                opendal::services::$service,
+                opendal::Scheme::$scheme
+            );
         }
     };
 }
@@ -99,10 +43,30 @@ macro_rules! behavior_tests {
 behavior_tests!(Azblob);
 #[cfg(feature = "services-azdfs")]
 behavior_tests!(Azdfs);
+#[cfg(feature = "services-azfile")]
+behavior_tests!(Azfile);
+#[cfg(feature = "services-b2")]
+behavior_tests!(B2);
+#[cfg(feature = "services-box")]
+behavior_tests!(BoxStorage => Box);
+#[cfg(feature = "services-cassandra")]
+behavior_tests!(Cassandra);
+#[cfg(feature = "services-cloudflare-kv")]
+behavior_tests!(CloudflareKv);
 #[cfg(feature = "services-cos")]
 behavior_tests!(Cos);
+#[cfg(feature = "services-d1")]
+behavior_tests!(D1);
 #[cfg(feature = "services-dashmap")]
 behavior_tests!(Dashmap);
+#[cfg(feature = "services-dropbox")]
+behavior_tests!(Dropbox);
+#[cfg(feature = "services-dynamodb")]
+behavior_tests!(Dynamodb);
+#[cfg(feature = "services-etcd")]
+behavior_tests!(Etcd);
+#[cfg(feature = "services-foundationdb")]
+behavior_tests!(Foundationdb);
 #[cfg(feature = "services-fs")]
 behavior_tests!(Fs);
 #[cfg(feature = "services-ftp")]
@@ -111,43 +75,85 @@ behavior_tests!(Ftp);
 behavior_tests!(Memcached);
 #[cfg(feature = "services-memory")]
 behavior_tests!(Memory);
+#[cfg(feature = "services-mock")]
+behavior_tests!(Mock);
 #[cfg(feature = "services-moka")]
 behavior_tests!(Moka);
+#[cfg(feature = "services-mysql")]
+behavior_tests!(Mysql);
+#[cfg(feature = "services-nfs")]
+behavior_tests!(Nfs);
 #[cfg(feature = "services-gcs")]
 behavior_tests!(Gcs);
+#[cfg(feature = "services-gdrive")]
+behavior_tests!(Gdrive);
 #[cfg(feature = "services-ghac")]
 behavior_tests!(Ghac);
+#[cfg(feature = "services-github")]
+behavior_tests!(Github);
+#[cfg(feature = "services-gridfs")]
+behavior_tests!(Gridfs);
 #[cfg(feature = "services-ipfs")]
 behavior_tests!(Ipfs);
 #[cfg(feature = "services-ipmfs")]
 behavior_tests!(Ipmfs);
+#[cfg(feature = "services-kodo")]
+behavior_tests!(Kodo);
 #[cfg(feature = "services-hdfs")]
 behavior_tests!(Hdfs);
 #[cfg(feature = "services-http")]
 behavior_tests!(Http);
+#[cfg(feature = "services-huggingface")]
+behavior_tests!(Huggingface);
 #[cfg(feature = "services-obs")]
 behavior_tests!(Obs);
+#[cfg(feature = "services-oci")]
+behavior_tests!(Oci);
+#[cfg(feature = "services-rados")]
+behavior_tests!(Rados);
+#[cfg(feature = "services-redb")]
+behavior_tests!(Redb);
 #[cfg(feature = "services-redis")]
 behavior_tests!(Redis);
 #[cfg(feature = "services-rocksdb")]
 behavior_tests!(Rocksdb);
 #[cfg(feature = "services-oss")]
 behavior_tests!(Oss);
+#[cfg(feature = "services-postgresql")]
+behavior_tests!(Postgresql);
 #[cfg(feature = "services-s3")]
 behavior_tests!(S3);
+#[cfg(feature = "services-seafile")]
+behavior_tests!(Seafile);
+#[cfg(feature = "services-seaweedfs")]
+behavior_tests!(Seaweedfs);
 #[cfg(feature = "services-sftp")]
 behavior_tests!(Sftp);
 #[cfg(feature = "services-supabase")]
 behavior_tests!(Supabase);
+#[cfg(feature = "services-tar")]
+behavior_tests!(Tar);
+#[cfg(feature = "services-tikv")]
+behavior_tests!(Tikv);
+#[cfg(feature = "services-tos")]
+behavior_tests!(Tos);
 #[cfg(feature = "services-sled")]
 behavior_tests!(Sled);
+#[cfg(feature = "services-sqlite")]
+behavior_tests!(Sqlite);
 #[cfg(feature = "services-vercel-artifacts")]
 behavior_tests!(VercelArtifacts);
+#[cfg(feature = "services-vercel-blob")]
+behavior_tests!(VercelBlob);
 #[cfg(feature = "services-wasabi")]
 behavior_tests!(Wasabi);
 #[cfg(feature = "services-webdav")]
 behavior_tests!(Webdav);
 #[cfg(feature = "services-webhdfs")]
 behavior_tests!(Webhdfs);
+#[cfg(feature = "services-yandex-disk")]
+behavior_tests!(YandexDisk);
+#[cfg(feature = "services-zip")]
+behavior_tests!(Zip);
 #[cfg(feature = "services-onedrive")]
 behavior_tests!(Onedrive);